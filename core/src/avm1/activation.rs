@@ -1,4 +1,5 @@
 use crate::avm1::callable_value::CallableValue;
+use crate::avm1::debugger::{ActionLocation, ScopeSnapshot, StopPoint};
 use crate::avm1::error::Error;
 use crate::avm1::function::{Avm1Function, ExecutionReason, FunctionObject};
 use crate::avm1::object::{Object, TObject};
@@ -426,7 +427,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     }
 
     pub fn run_actions(&mut self, code: SwfSlice) -> Result<ReturnType<'gc>, Error<'gc>> {
-        let mut read = Reader::new(&code.movie.data()[code.start..], self.swf_version());
+        let mut read = Reader::new(&code.movie_data()[code.start..], self.swf_version());
 
         loop {
             let result = self.do_action(&code, &mut read);
@@ -456,7 +457,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             //Executing beyond the end of a function constitutes an implicit return.
             Ok(FrameControl::Return(ReturnType::Implicit))
         } else {
+            let offset = reader.get_ref().as_ptr() as usize - data.movie_data().as_ptr() as usize;
             let action = reader.read_action()?;
+            if self.context.avm1.debugger.is_active() {
+                self.debugger_check_action(data, offset, &action);
+            }
+
             avm_debug!(
                 self.context.avm1,
                 "({}) Action: {action:?}",
@@ -569,6 +575,132 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
     }
 
+    /// Reports an action that is about to be executed to the debugger.
+    #[cold]
+    fn debugger_check_action(&mut self, data: &SwfSlice, offset: usize, action: &Action) {
+        // Functions don't belong to a frame, even if they were defined in a frame script.
+        let script_start_frame = if offset == data.start && self.callee.is_none() {
+            self.base_clip
+                .as_movie_clip()
+                .map(|clip| clip.current_frame())
+        } else {
+            None
+        };
+        let location = ActionLocation {
+            movie_url: data.movie.url(),
+            offset,
+            script_start_frame,
+        };
+
+        if let Some(hit_breakpoint) = self.context.avm1.debugger.check_action(&location) {
+            let stop = self.debugger_stop_point(data, offset, action, hit_breakpoint);
+            self.context.avm1.debugger.record(stop);
+        }
+    }
+
+    /// Takes a snapshot of this activation for the debugger.
+    fn debugger_stop_point(
+        &mut self,
+        data: &SwfSlice,
+        offset: usize,
+        action: &Action,
+        hit_breakpoint: bool,
+    ) -> StopPoint {
+        let mut scope_chain = vec![];
+        let mut scope = Some(self.scope);
+        while let Some(current) = scope {
+            let object = *current.locals();
+            // The global scope holds every builtin, which would drown out anything useful.
+            let variables = if current.class() == ScopeClass::Global {
+                None
+            } else {
+                let variables = object
+                    .get_keys(self, false)
+                    .into_iter()
+                    .map(|name| {
+                        let value = object
+                            .get_local_stored(name, self, false)
+                            .unwrap_or(Value::Undefined);
+                        (name.to_string(), self.debugger_describe_value(value))
+                    })
+                    .collect();
+                Some(variables)
+            };
+            scope_chain.push(ScopeSnapshot {
+                class: format!("{:?}", current.class()),
+                object: self.debugger_describe_value(object.into()),
+                variables,
+            });
+            scope = current.parent();
+        }
+
+        let registers = match self.local_registers {
+            Some(registers) => registers
+                .read()
+                .0
+                .iter()
+                .map(|value| self.debugger_describe_value(*value))
+                .collect(),
+            None => (0..4)
+                .filter_map(|id| self.context.avm1.get_register(id))
+                .map(|value| self.debugger_describe_value(*value))
+                .collect(),
+        };
+
+        let constant_pool = self
+            .constant_pool
+            .iter()
+            .map(|value| self.debugger_describe_value(*value))
+            .collect();
+
+        let stack = self
+            .context
+            .avm1
+            .stack()
+            .iter()
+            .map(|value| self.debugger_describe_value(*value))
+            .collect();
+
+        StopPoint {
+            movie: data.movie.url().to_string(),
+            offset,
+            action: format!("{action:?}"),
+            activation: self.id.to_string(),
+            clip: self.base_clip.path().to_string(),
+            frame: self
+                .base_clip
+                .as_movie_clip()
+                .map(|clip| clip.current_frame()),
+            depth: self.id.depth(),
+            hit_breakpoint,
+            scope_chain,
+            registers,
+            constant_pool,
+            stack,
+        }
+    }
+
+    /// Describes a value for the debugger, without calling into ActionScript.
+    fn debugger_describe_value(&self, value: Value<'gc>) -> String {
+        match value {
+            Value::Undefined => "undefined".to_string(),
+            Value::Null => "null".to_string(),
+            Value::Bool(value) => value.to_string(),
+            Value::Number(value) => value.to_string(),
+            Value::String(value) => format!("{:?}", value.to_utf8_lossy()),
+            Value::Object(object) => {
+                if let Some(display_object) = object.as_display_object() {
+                    format!("[object {}]", display_object.path())
+                } else if object.as_executable().is_some() {
+                    "[type Function]".to_string()
+                } else {
+                    "[object Object]".to_string()
+                }
+            }
+            Value::MovieClip(reference) => format!("[MovieClip {}]", reference.path()),
+        }
+    }
+
     fn stack_push(&mut self, mut value: Value<'gc>) {
         if let Value::Object(Object::StageObject(s)) = value {
             // Note that there currently exists a subtle issue with this logic:
@@ -1453,7 +1585,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let val = self.context.avm1.pop();
         if val.as_bool(self.swf_version()) {
-            reader.seek(data.movie.data(), action.offset);
+            reader.seek(data.movie_data(), action.offset);
         }
         Ok(FrameControl::Continue)
     }
@@ -1559,7 +1691,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         reader: &mut Reader<'b>,
         data: &'b SwfSlice,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        reader.seek(data.movie.data(), action.offset);
+        reader.seek(data.movie_data(), action.offset);
         Ok(FrameControl::Continue)
     }
 
@@ -0,0 +1,287 @@
+//! Breakpoints and traces of AVM1 actions.
+//!
+//! Breakpoints don't pause scripts, they start recording a trace of them.
+//! See `crate::debugger` for how actions are recorded.
+
+use crate::debugger::{Stop, TraceRecorder};
+
+/// A location that starts a trace when it's reached.
+///
+/// `movie` matches any movie whose URL ends with it, so an empty string matches every movie.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Trace from the action at the given byte offset into the movie's data.
+    Offset { movie: String, offset: usize },
+
+    /// Trace from the start of any timeline script (`DoAction`, `DoInitAction` or clip event)
+    /// that runs while its clip is on the given frame.
+    Frame { movie: String, frame: u16 },
+}
+
+impl Breakpoint {
+    fn movie(&self) -> &str {
+        match self {
+            Self::Offset { movie, .. } | Self::Frame { movie, .. } => movie,
+        }
+    }
+}
+
+impl std::fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let movie = if self.movie().is_empty() {
+            "<any movie>"
+        } else {
+            self.movie()
+        };
+        match self {
+            Self::Offset { offset, .. } => write!(f, "{movie} @ {offset}"),
+            Self::Frame { frame, .. } => write!(f, "{movie}, frame {frame}"),
+        }
+    }
+}
+
+/// Where an action is about to be executed, used to look up breakpoints.
+pub(crate) struct ActionLocation<'a> {
+    pub movie_url: &'a str,
+    pub offset: usize,
+
+    /// The frame of the clip running this script, if this is the first action of a timeline script.
+    pub script_start_frame: Option<u16>,
+}
+
+/// A variable scope, as seen when an action was recorded.
+#[derive(Clone, Debug)]
+pub struct ScopeSnapshot {
+    /// The kind of scope, such as `Local` or `Target`.
+    pub class: String,
+
+    /// The object holding the scope's variables.
+    pub object: String,
+
+    /// The variables defined directly on the scope, if they were listed.
+    pub variables: Option<Vec<(String, String)>>,
+}
+
+/// A snapshot of the interpreter before an action was executed.
+#[derive(Clone, Debug)]
+pub struct StopPoint {
+    pub movie: String,
+
+    /// The byte offset of the action into the movie's data.
+    pub offset: usize,
+
+    pub action: String,
+
+    /// The names of the activations leading to this one, outermost first.
+    pub activation: String,
+
+    /// The target path of the clip running the script.
+    pub clip: String,
+
+    /// The current frame of the clip running the script, if it's a movie clip.
+    pub frame: Option<u16>,
+
+    /// How deeply nested the activation is, used for stepping over and out of functions.
+    pub depth: u16,
+
+    /// Whether a breakpoint was hit here, as opposed to stepping onto it.
+    pub hit_breakpoint: bool,
+
+    /// The scope chain of the activation, innermost first.
+    pub scope_chain: Vec<ScopeSnapshot>,
+
+    /// Registers, starting from register 0.
+    pub registers: Vec<String>,
+
+    pub constant_pool: Vec<String>,
+
+    /// The operand stack, bottom first.
+    pub stack: Vec<String>,
+}
+
+impl Stop for StopPoint {
+    fn depth(&self) -> usize {
+        self.depth.into()
+    }
+
+    fn hit_breakpoint(&self) -> bool {
+        self.hit_breakpoint
+    }
+}
+
+/// The actions recorded after a breakpoint was hit.
+pub type Trace = crate::debugger::Trace<StopPoint>;
+
+/// The debugging state of the AVM1 interpreter.
+#[derive(Default)]
+pub struct Debugger {
+    breakpoints: Vec<Breakpoint>,
+
+    /// Whether the next action should start a trace, even without a breakpoint.
+    break_requested: bool,
+
+    /// The actions recorded so far, if a breakpoint has been hit.
+    recorder: TraceRecorder<StopPoint>,
+}
+
+impl Debugger {
+    /// Whether activations need to report actions.
+    ///
+    /// This is checked before every action, so it must stay cheap.
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        !self.recorder.is_truncated()
+            && (!self.breakpoints.is_empty()
+                || self.break_requested
+                || self.recorder.is_recording())
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: &Breakpoint) {
+        self.breakpoints.retain(|b| b != breakpoint);
+    }
+
+    /// Starts a trace at the next action that is run.
+    pub fn request_break(&mut self) {
+        self.break_requested = true;
+    }
+
+    pub fn is_break_requested(&self) -> bool {
+        self.break_requested
+    }
+
+    /// Removes every breakpoint and discards any recording, so that scripts run at full speed.
+    pub fn clear(&mut self) {
+        self.breakpoints.clear();
+        self.break_requested = false;
+        self.recorder.clear();
+    }
+
+    /// Takes the actions recorded since the last breakpoint was hit.
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.recorder.take_trace()
+    }
+
+    /// Checks whether an action should be recorded.
+    ///
+    /// Returns whether a breakpoint was hit, or `None` if the action shouldn't be recorded.
+    pub(crate) fn check_action(&self, location: &ActionLocation) -> Option<bool> {
+        if self.recorder.is_truncated() {
+            return None;
+        }
+
+        let hit = self.breakpoints.iter().any(|breakpoint| {
+            location.movie_url.ends_with(breakpoint.movie())
+                && match *breakpoint {
+                    Breakpoint::Offset { offset, .. } => offset == location.offset,
+                    Breakpoint::Frame { frame, .. } => location.script_start_frame == Some(frame),
+                }
+        });
+
+        (hit || self.break_requested || self.recorder.is_recording()).then_some(hit)
+    }
+
+    /// Records an action, starting a new recording if needed.
+    pub(crate) fn record(&mut self, stop: StopPoint) {
+        self.break_requested = false;
+        self.recorder.record(stop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::MAX_TRACE_LENGTH;
+
+    fn stop(hit_breakpoint: bool) -> StopPoint {
+        StopPoint {
+            movie: "file:///movie.swf".to_string(),
+            offset: 0,
+            action: "Push".to_string(),
+            activation: "[Frame 1]".to_string(),
+            clip: "_level0".to_string(),
+            frame: Some(1),
+            depth: 1,
+            hit_breakpoint,
+            scope_chain: vec![],
+            registers: vec![],
+            constant_pool: vec![],
+            stack: vec![],
+        }
+    }
+
+    fn location(offset: usize, script_start_frame: Option<u16>) -> ActionLocation<'static> {
+        ActionLocation {
+            movie_url: "file:///path/to/movie.swf",
+            offset,
+            script_start_frame,
+        }
+    }
+
+    #[test]
+    fn breakpoints_match_movie_suffix() {
+        let mut debugger = Debugger::default();
+        assert!(!debugger.is_active());
+        debugger.add_breakpoint(Breakpoint::Offset {
+            movie: "movie.swf".to_string(),
+            offset: 20,
+        });
+        debugger.add_breakpoint(Breakpoint::Frame {
+            movie: "other.swf".to_string(),
+            frame: 3,
+        });
+        assert!(debugger.is_active());
+
+        assert_eq!(debugger.check_action(&location(20, None)), Some(true));
+        assert_eq!(debugger.check_action(&location(21, None)), None);
+        assert_eq!(debugger.check_action(&location(0, Some(3))), None);
+
+        debugger.add_breakpoint(Breakpoint::Frame {
+            movie: String::new(),
+            frame: 3,
+        });
+        assert_eq!(debugger.check_action(&location(0, Some(3))), Some(true));
+        assert_eq!(debugger.check_action(&location(0, Some(2))), None);
+    }
+
+    #[test]
+    fn recording_continues_after_breakpoint() {
+        let mut debugger = Debugger::default();
+        debugger.request_break();
+        assert_eq!(debugger.check_action(&location(0, None)), Some(false));
+        debugger.record(stop(false));
+        assert!(!debugger.is_break_requested());
+        assert_eq!(debugger.check_action(&location(1, None)), Some(false));
+
+        let trace = debugger.take_trace().unwrap();
+        assert_eq!(trace.len(), 1);
+        assert!(!debugger.is_active());
+    }
+
+    #[test]
+    fn recording_stops_when_truncated() {
+        let mut debugger = Debugger::default();
+        debugger.add_breakpoint(Breakpoint::Offset {
+            movie: String::new(),
+            offset: 0,
+        });
+        for _ in 0..=MAX_TRACE_LENGTH {
+            debugger.record(stop(true));
+        }
+        assert!(!debugger.is_active());
+        assert_eq!(debugger.check_action(&location(0, None)), None);
+
+        let trace = debugger.take_trace().unwrap();
+        assert!(trace.is_truncated());
+        assert!(debugger.is_active());
+    }
+}
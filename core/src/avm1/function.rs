@@ -8,6 +8,7 @@ use crate::avm1::scope::Scope;
 use crate::avm1::value::Value;
 use crate::avm1::{ArrayObject, Object, ObjectPtr, ScriptObject, TObject};
 use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::profiler::ProfileCategory;
 use crate::string::{AvmString, SwfStrExt as _};
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, Gc, GcCell, Mutation};
@@ -343,7 +344,15 @@ impl<'gc> Executable<'gc> {
             Executable::Native(nf) => {
                 // TODO: Change NativeFunction to accept `this: Value`.
                 let this = this.coerce_to_object(activation);
-                return nf(activation, this, args);
+                activation
+                    .context
+                    .profile_enter(ProfileCategory::Native, || match name {
+                        ExecutionName::Static(n) => n.to_owned(),
+                        ExecutionName::Dynamic(n) => n.to_utf8_lossy().into_owned(),
+                    });
+                let result = nf(activation, this, args);
+                activation.context.profile_exit();
+                return result;
             }
             Executable::Action(af) => af,
         };
@@ -453,7 +462,15 @@ impl<'gc> Executable<'gc> {
             }
         }
 
-        Ok(frame.run_actions(af.data.clone())?.value())
+        frame.context.profile_enter(ProfileCategory::Avm1, || {
+            af.name().map_or_else(
+                || "[Anonymous]".to_owned(),
+                |name| name.to_utf8_lossy().into_owned(),
+            )
+        });
+        let result = frame.run_actions(af.data.clone());
+        frame.context.profile_exit();
+        Ok(result?.value())
     }
 }
 
@@ -36,12 +36,13 @@ pub(crate) mod glow_filter;
 pub(crate) mod gradient_filter;
 mod key;
 mod load_vars;
-mod local_connection;
+pub(crate) mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
 pub(crate) mod movie_clip;
 mod movie_clip_loader;
+pub(crate) mod netconnection;
 pub(crate) mod netstream;
 pub(crate) mod number;
 mod object;
@@ -52,6 +53,7 @@ pub(crate) mod shared_object;
 pub(crate) mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
@@ -537,6 +539,7 @@ pub fn create_globals<'gc>(
 
     let text_field_proto = text_field::create_proto(context, object_proto, function_proto);
     let text_format_proto = text_format::create_proto(context, object_proto, function_proto);
+    let style_sheet_proto = style_sheet::create_proto(context, object_proto, function_proto);
 
     let array_proto = array::create_proto(context, object_proto, function_proto);
 
@@ -580,6 +583,7 @@ pub fn create_globals<'gc>(
     );
 
     let video_proto = video::create_proto(context, object_proto, function_proto);
+    let netconnection_proto = netconnection::create_proto(context, object_proto, function_proto);
     let netstream_proto = netstream::create_proto(context, object_proto, function_proto);
     let xml_socket_proto = xml_socket::create_proto(context, object_proto, function_proto);
 
@@ -654,6 +658,19 @@ pub fn create_globals<'gc>(
         function_proto,
         text_field_proto,
     );
+    let style_sheet = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(style_sheet::constructor),
+        constructor_to_fn!(style_sheet::constructor),
+        function_proto,
+        style_sheet_proto,
+    );
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        Attribute::DONT_ENUM,
+    );
     let text_format = FunctionObject::constructor(
         gc_context,
         Executable::Native(text_format::constructor),
@@ -674,6 +691,7 @@ pub fn create_globals<'gc>(
     let number = number::create_number_object(context, number_proto, function_proto);
     let boolean = boolean::create_boolean_object(context, boolean_proto, function_proto);
     let date = date::create_constructor(context, object_proto, function_proto);
+    let netconnection = netconnection::create_class(context, netconnection_proto, function_proto);
     let netstream = netstream::create_class(context, netstream_proto, function_proto);
     let xml_socket = xml_socket::create_class(context, xml_socket_proto, function_proto);
 
@@ -1070,6 +1088,12 @@ pub fn create_globals<'gc>(
         )),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "NetConnection",
+        netconnection.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(
         gc_context,
         "NetStream",
@@ -549,6 +549,7 @@ fn draw<'gc>(
                 Transform {
                     matrix,
                     color_transform,
+                    ..Default::default()
                 },
                 smoothing,
                 blend_mode,
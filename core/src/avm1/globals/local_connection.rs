@@ -2,14 +2,52 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::shared_object::serialize_values;
+use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{Object, ScriptObject, Value};
+use crate::avm1::{Object, ScriptObject, TObject, Value};
 use crate::context::GcContext;
 use crate::display_object::TDisplayObject;
+use crate::local_connection::{movie_domain, LocalConnections};
 use crate::string::AvmString;
+use gc_arena::{Collect, Gc};
+use std::cell::RefCell;
+
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+struct LocalConnectionData {
+    /// The full name this connection is listening on, if it's connected.
+    connection_name: RefCell<Option<String>>,
+}
+
+#[derive(Copy, Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct LocalConnection<'gc>(Gc<'gc, LocalConnectionData>);
+
+impl<'gc> LocalConnection<'gc> {
+    pub fn connection_name(&self) -> Option<String> {
+        self.0.connection_name.borrow().clone()
+    }
+
+    pub fn set_connection_name(&self, name: Option<String>) -> Option<String> {
+        self.0.connection_name.replace(name)
+    }
+
+    pub fn cast(value: Value<'gc>) -> Option<Self> {
+        if let Value::Object(object) = value {
+            if let NativeObject::LocalConnection(local_connection) = object.native() {
+                return Some(local_connection);
+            }
+        }
+        None
+    }
+}
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "domain" => method(domain; DONT_DELETE | READ_ONLY);
+    "connect" => method(connect; DONT_DELETE | READ_ONLY);
+    "send" => method(send; DONT_DELETE | READ_ONLY);
+    "close" => method(close; DONT_DELETE | READ_ONLY);
 };
 
 pub fn domain<'gc>(
@@ -19,28 +57,98 @@ pub fn domain<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let movie = activation.base_clip().movie();
 
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
+    let Some(domain) = movie_domain(movie.url()) else {
         tracing::error!("LocalConnection::domain: Unable to parse movie URL");
         return Ok(Value::Null);
     };
 
-    Ok(Value::String(domain))
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(Value::String(name)) => *name,
+        // Anything that isn't a string is an invalid connection name.
+        _ => return Ok(false.into()),
+    };
+    if name.is_empty() || LocalConnection::cast(this.into()).is_none() {
+        return Ok(false.into());
+    }
+
+    let Some(domain) = movie_domain(activation.base_clip().movie().url()) else {
+        return Ok(false.into());
+    };
+
+    Ok(LocalConnections::connect(
+        &mut activation.context,
+        this,
+        &domain,
+        &name.to_utf8_lossy(),
+    )
+    .into())
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (connection_name, method_name) = match (args.get(0), args.get(1)) {
+        (Some(Value::String(connection_name)), Some(Value::String(method_name))) => {
+            (*connection_name, *method_name)
+        }
+        _ => return Ok(false.into()),
+    };
+    if connection_name.is_empty() || method_name.is_empty() {
+        return Ok(false.into());
+    }
+
+    let Some(domain) = movie_domain(activation.base_clip().movie().url()) else {
+        return Ok(false.into());
+    };
+    let arguments = serialize_values(activation, args.get(2..).unwrap_or_default());
+
+    activation.context.local_connections.send(
+        this,
+        &domain,
+        &connection_name.to_utf8_lossy(),
+        method_name.to_string(),
+        arguments,
+    );
+
+    Ok(true.into())
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    LocalConnections::close(&mut activation.context, this);
+    Ok(Value::Undefined)
 }
 
 pub fn constructor<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let local_connection = LocalConnection(Gc::new(
+        activation.gc(),
+        LocalConnectionData {
+            connection_name: RefCell::new(None),
+        },
+    ));
+
+    this.set_native(
+        activation.gc(),
+        NativeObject::LocalConnection(local_connection),
+    );
+
     Ok(this.into())
 }
 
@@ -0,0 +1,368 @@
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::shared_object::serialize_values;
+use crate::avm1::object::{NativeObject, Object, TObject};
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, Error, ScriptObject, Value};
+use crate::avm1_stub;
+use crate::context::GcContext;
+use crate::net_connection::{NetConnectionHandle, NetConnections};
+use crate::string::AvmString;
+use flash_lso::packet::Header;
+use flash_lso::types::Value as AmfValue;
+use gc_arena::{Collect, Gc};
+use ruffle_wstr::WStr;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+struct NetConnectionData {
+    handle: Cell<Option<NetConnectionHandle>>,
+}
+
+#[derive(Copy, Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct NetConnection<'gc>(Gc<'gc, NetConnectionData>);
+
+impl<'gc> NetConnection<'gc> {
+    pub fn handle(&self) -> Option<NetConnectionHandle> {
+        self.0.handle.get()
+    }
+
+    pub fn set_handle(&self, handle: Option<NetConnectionHandle>) -> Option<NetConnectionHandle> {
+        self.0.handle.replace(handle)
+    }
+
+    pub fn cast(value: Value<'gc>) -> Option<Self> {
+        if let Value::Object(object) = value {
+            if let NativeObject::NetConnection(net_connection) = object.native() {
+                return Some(net_connection);
+            }
+        }
+        None
+    }
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "isConnected" => property(get_is_connected);
+    "uri" => property(get_uri);
+    "addHeader" => method(add_header; DONT_ENUM | DONT_DELETE);
+    "call" => method(call; DONT_ENUM | DONT_DELETE);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+};
+
+fn get_is_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(handle) = NetConnection::cast(this.into()).and_then(|nc| nc.handle()) {
+        return Ok(activation
+            .context
+            .net_connections
+            .is_connected(handle)
+            .into());
+    }
+
+    Ok(false.into())
+}
+
+fn get_uri<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(uri) = NetConnection::cast(this.into())
+        .and_then(|nc| nc.handle())
+        .and_then(|handle| activation.context.net_connections.get_uri(handle))
+    {
+        return Ok(AvmString::new_utf8(activation.context.gc_context, uri).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn add_header<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(net_connection) = NetConnection::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let must_understand = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+    // Like AVM2, leaving out the value sends a null header rather than removing it.
+    let value = args.get(2).copied().unwrap_or(Value::Null);
+    let value = serialize_values(activation, &[value])
+        .pop()
+        .unwrap_or_else(|| Rc::new(AmfValue::Null));
+
+    if let Some(handle) = net_connection.handle() {
+        activation.context.net_connections.set_header(
+            handle,
+            Header {
+                name: name.to_string(),
+                must_understand,
+                value,
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(handle) = NetConnection::cast(this.into()).and_then(|nc| nc.handle()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let command = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let arguments = serialize_values(activation, args.get(2..).unwrap_or_default());
+
+    if let Some(Value::Object(responder)) = args.get(1) {
+        NetConnections::send_avm1(
+            &mut activation.context,
+            handle,
+            command.to_string(),
+            AmfValue::StrictArray(arguments),
+            *responder,
+        );
+    } else {
+        NetConnections::send_without_response(
+            &mut activation.context,
+            handle,
+            command.to_string(),
+            AmfValue::StrictArray(arguments),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(net_connection) = NetConnection::cast(this.into()) {
+        if let Some(previous_handle) = net_connection.set_handle(None) {
+            NetConnections::close(&mut activation.context, previous_handle, true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if NetConnection::cast(this.into()).is_none() {
+        return Ok(Value::Undefined);
+    }
+
+    let url = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Null => {
+            NetConnections::connect_to_local(&mut activation.context, this);
+            return Ok(true.into());
+        }
+        url => url.coerce_to_string(activation)?,
+    };
+
+    if url.starts_with(WStr::from_units(b"http://"))
+        || url.starts_with(WStr::from_units(b"https://"))
+    {
+        // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
+        NetConnections::connect_to_flash_remoting(&mut activation.context, this, url.to_string());
+    } else if url.starts_with(WStr::from_units(b"rtmp://")) {
+        // AVM1 always uses AMF0.
+        let arguments = serialize_values(activation, args.get(1..).unwrap_or_default())
+            .iter()
+            .map(|value| AmfValue::clone(value))
+            .collect();
+
+        NetConnections::connect_to_rtmp(
+            &mut activation.context,
+            this,
+            url.to_string(),
+            0.0,
+            arguments,
+        );
+    } else {
+        avm1_stub!(
+            activation,
+            "NetConnection",
+            "connect",
+            "with non-null, non-http, non-rtmp command"
+        );
+        return Ok(false.into());
+    }
+
+    Ok(true.into())
+}
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let net_connection = NetConnection(Gc::new(
+        activation.gc(),
+        NetConnectionData {
+            handle: Cell::new(None),
+        },
+    ));
+
+    this.set_native(activation.gc(), NativeObject::NetConnection(net_connection));
+
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let net_connection_proto = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, net_connection_proto, fn_proto);
+    net_connection_proto.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    net_connection_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        net_connection_proto,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+    use crate::avm1::Attribute;
+    use crate::net_connection::{ResponderCallback, ResponderHandle};
+
+    fn record<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let value = args.get(0).copied().unwrap_or(Value::Undefined);
+        this.set("received", value, activation)?;
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn avm1_arguments_serialize_as_amf0() {
+        with_avm(8, |activation, _this| -> Result<(), Error> {
+            let object_proto = activation.context.avm1.prototypes().object;
+            let function_proto = activation.context.avm1.prototypes().function;
+            let object = ScriptObject::new(activation.context.gc_context, Some(object_proto));
+            object.set("a", 1.into(), activation)?;
+            let function = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(record),
+                function_proto,
+                function_proto,
+            );
+
+            let values = serialize_values(
+                activation,
+                &[
+                    2.5.into(),
+                    "hello".into(),
+                    Value::Null,
+                    function.into(),
+                    object.into(),
+                ],
+            );
+            assert_eq!(values.len(), 5);
+            assert_eq!(*values[0], AmfValue::Number(2.5));
+            assert_eq!(*values[1], AmfValue::String("hello".to_string()));
+            assert_eq!(*values[2], AmfValue::Null);
+            // Functions can't be serialized, but still take up an argument.
+            assert_eq!(*values[3], AmfValue::Undefined);
+            match &*values[4] {
+                AmfValue::Object(elements, None) => {
+                    assert_eq!(elements.len(), 1);
+                    assert_eq!(elements[0].name, "a");
+                    assert_eq!(*elements[0].value(), AmfValue::Number(1.0));
+                }
+                value => panic!("Expected an AMF0 object, got {value:?}"),
+            }
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn avm1_responder_callbacks() {
+        with_avm(8, |activation, _this| -> Result<(), Error> {
+            let object_proto = activation.context.avm1.prototypes().object;
+            let function_proto = activation.context.avm1.prototypes().function;
+            let responder = ScriptObject::new(activation.context.gc_context, Some(object_proto));
+            for name in ["onResult", "onStatus"] {
+                let function = FunctionObject::function(
+                    activation.context.gc_context,
+                    Executable::Native(record),
+                    function_proto,
+                    function_proto,
+                );
+                responder.define_value(
+                    activation.context.gc_context,
+                    name,
+                    function.into(),
+                    Attribute::empty(),
+                );
+            }
+            let responder: Object<'_> = responder.into();
+            let handle = ResponderHandle::Avm1(
+                activation
+                    .context
+                    .dynamic_root
+                    .stash(activation.context.gc_context, responder),
+            );
+
+            handle.call(
+                &mut activation.context,
+                ResponderCallback::Result,
+                Rc::new(AmfValue::Number(5.0)),
+            );
+            assert_eq!(responder.get("received", activation)?, 5.into());
+
+            handle.call(
+                &mut activation.context,
+                ResponderCallback::Status,
+                Rc::new(AmfValue::String("failed".to_string())),
+            );
+            let received = responder.get("received", activation)?;
+            assert_eq!(received.coerce_to_string(activation)?.to_string(), "failed");
+
+            Ok(())
+        });
+    }
+}
@@ -1,14 +1,16 @@
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::netconnection::NetConnection;
 use crate::avm1::object::{NativeObject, Object, TObject};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, ScriptObject, Value};
 use crate::context::GcContext;
+use crate::net_connection::NetConnections;
 use crate::streams::NetStream;
 
 pub fn constructor<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let netstream = NetStream::new(activation.context.gc_context, Some(this.into()));
     this.set_native(
@@ -16,6 +18,13 @@ pub fn constructor<'gc>(
         NativeObject::NetStream(netstream),
     );
 
+    let connection = args
+        .get(0)
+        .and_then(|connection| NetConnection::cast(*connection));
+    if let Some(handle) = connection.and_then(|connection| connection.handle()) {
+        NetConnections::create_stream(&mut activation.context, handle, netstream);
+    }
+
     Ok(this.into())
 }
 
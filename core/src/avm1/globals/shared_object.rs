@@ -6,6 +6,7 @@ use crate::avm1::{
 use crate::avm1_stub;
 use crate::context::GcContext;
 use crate::display_object::TDisplayObject;
+use crate::shared_object::{domain_of_url, FlushStatus};
 use crate::string::AvmString;
 use flash_lso::amf0::read::AMF0Decoder;
 use flash_lso::amf0::writer::{Amf0Writer, CacheKey, ObjWriter};
@@ -13,6 +14,7 @@ use flash_lso::types::{Lso, Reference, Value as AmfValue};
 use gc_arena::{Collect, GcCell};
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
 #[derive(Default, Clone, Collect)]
 #[collect(require_static)]
@@ -54,19 +56,43 @@ const OBJECT_DECLS: &[Declaration] = declare_properties! {
 fn delete_all<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "deleteAll");
-    Ok(Value::Undefined)
+    let url = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let movie = activation.base_clip().movie();
+    let Some(domain) = domain_of_url(movie.url(), &url.to_utf8_lossy()) else {
+        return Ok(false.into());
+    };
+
+    let storage = &mut activation.context.storage;
+    for name in storage.keys(&format!("{domain}/")) {
+        storage.remove_key(&name);
+    }
+    Ok(true.into())
 }
 
 fn get_disk_usage<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "getDiskUsage");
-    Ok(Value::Undefined)
+    let url = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let movie = activation.base_clip().movie();
+    let Some(domain) = domain_of_url(movie.url(), &url.to_utf8_lossy()) else {
+        return Ok(0.into());
+    };
+
+    Ok(activation
+        .context
+        .storage
+        .usage(&format!("{domain}/"))
+        .into())
 }
 
 /// Serialize an Object and any children to a JSON object
@@ -78,59 +104,92 @@ fn recursive_serialize<'gc>(
     // Reversed to match flash player ordering
     for element_name in obj.get_keys(activation, false).into_iter().rev() {
         if let Ok(elem) = obj.get(element_name, activation) {
-            let name = element_name.to_utf8_lossy();
-
-            match elem {
-                Value::Object(o) => {
-                    if o.as_executable().is_some() {
-                    } else if o.as_display_object().is_some() {
-                        writer.undefined(name.as_ref())
-                    } else if o.as_array_object().is_some() {
-                        let (aw, token) = writer.array(CacheKey::from_ptr(o.as_ptr()));
-
-                        if let Some(mut aw) = aw {
-                            recursive_serialize(activation, o, &mut aw);
-
-                            // TODO: What happens if an exception is thrown here?
-                            let length = o
-                                .length(activation)
-                                .expect("Failed to get length for SharedObject array");
-
-                            aw.commit(name, length as u32);
-                        } else {
-                            writer.reference(name.as_ref(), token);
-                        }
-                    } else if let Some(xml_node) = o.as_xml_node() {
-                        // TODO: What happens if an exception is thrown here?
-                        let string = xml_node
-                            .into_string(activation)
-                            .expect("Failed to convert xml to string in SharedObject");
-                        writer.xml(name.as_ref(), string.to_utf8_lossy().as_ref(), true)
-                    } else if let NativeObject::Date(date) = o.native() {
-                        writer.date(name.as_ref(), date.get().time(), None)
-                    } else {
-                        let (ow, token) = writer.object(CacheKey::from_ptr(o.as_ptr()));
+            serialize_element(activation, &element_name.to_utf8_lossy(), elem, writer);
+        }
+    }
+}
 
-                        if let Some(mut ow) = ow {
-                            recursive_serialize(activation, o, &mut ow);
-                            ow.commit(name);
-                        } else {
-                            writer.reference(name.as_ref(), token);
-                        }
-                    }
+/// Serialize a single named Value, and any children it has
+fn serialize_element<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &str,
+    elem: Value<'gc>,
+    writer: &mut dyn ObjWriter<'_>,
+) {
+    match elem {
+        Value::Object(o) => {
+            if o.as_executable().is_some() {
+            } else if o.as_display_object().is_some() {
+                writer.undefined(name)
+            } else if o.as_array_object().is_some() {
+                let (aw, token) = writer.array(CacheKey::from_ptr(o.as_ptr()));
+
+                if let Some(mut aw) = aw {
+                    recursive_serialize(activation, o, &mut aw);
+
+                    // TODO: What happens if an exception is thrown here?
+                    let length = o
+                        .length(activation)
+                        .expect("Failed to get length for SharedObject array");
+
+                    aw.commit(name, length as u32);
+                } else {
+                    writer.reference(name, token);
+                }
+            } else if let Some(xml_node) = o.as_xml_node() {
+                // TODO: What happens if an exception is thrown here?
+                let string = xml_node
+                    .into_string(activation)
+                    .expect("Failed to convert xml to string in SharedObject");
+                writer.xml(name, string.to_utf8_lossy().as_ref(), true)
+            } else if let NativeObject::Date(date) = o.native() {
+                writer.date(name, date.get().time(), None)
+            } else {
+                let (ow, token) = writer.object(CacheKey::from_ptr(o.as_ptr()));
+
+                if let Some(mut ow) = ow {
+                    recursive_serialize(activation, o, &mut ow);
+                    ow.commit(name);
+                } else {
+                    writer.reference(name, token);
                 }
-                Value::Number(f) => writer.number(name.as_ref(), f),
-                Value::String(s) => writer.string(name.as_ref(), s.to_utf8_lossy().as_ref()),
-                Value::Undefined | Value::MovieClip(_) => writer.undefined(name.as_ref()),
-                Value::Null => writer.null(name.as_ref()),
-                Value::Bool(b) => writer.bool(name.as_ref(), b),
             }
         }
+        Value::Number(f) => writer.number(name, f),
+        Value::String(s) => writer.string(name, s.to_utf8_lossy().as_ref()),
+        Value::Undefined | Value::MovieClip(_) => writer.undefined(name),
+        Value::Null => writer.null(name),
+        Value::Bool(b) => writer.bool(name, b),
+    }
+}
+
+/// Serialize a list of Values, such as the arguments of a remote call, sharing one reference table
+pub fn serialize_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    values: &[Value<'gc>],
+) -> Vec<Rc<AmfValue>> {
+    let mut w = Amf0Writer::default();
+    for (i, value) in values.iter().enumerate() {
+        serialize_element(activation, &i.to_string(), *value, &mut w);
+    }
+
+    // Functions aren't written at all, so they are left as undefined
+    let mut serialized = vec![Rc::new(AmfValue::Undefined); values.len()];
+    for element in w.commit_lso("").body {
+        if let Some(slot) = element
+            .name
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| serialized.get_mut(i))
+        {
+            *slot = element.value;
+        }
     }
+    serialized
 }
 
 /// Deserialize a AmfValue to a Value
-fn deserialize_value<'gc>(
+pub fn deserialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     val: &AmfValue,
     lso: &AMF0Decoder,
@@ -158,7 +217,9 @@ fn deserialize_value<'gc>(
                     let value = deserialize_value(activation, entry.value(), lso, reference_cache);
 
                     if let Ok(i) = entry.name().parse::<i32>() {
-                        obj.set_element(activation, i, value).unwrap();
+                        if let Err(e) = obj.set_element(activation, i, value) {
+                            tracing::error!("Couldn't set element {i} of deserialized array: {e}");
+                        }
                     } else {
                         obj.define_value(
                             activation.context.gc_context,
@@ -488,24 +549,53 @@ fn connect<'gc>(
     Ok(Value::Undefined)
 }
 
-pub(crate) fn flush<'gc>(
+fn flush<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let min_disk_space = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    Ok(
+        match write_to_storage(activation, this, min_disk_space as usize, true)? {
+            Some(FlushStatus::Flushed) => true.into(),
+            Some(FlushStatus::Pending) => "pending".into(),
+            Some(FlushStatus::Failed) => false.into(),
+            None => Value::Undefined,
+        },
+    )
+}
+
+/// Serializes a shared object and writes it to storage, returning `None` if `this` isn't a shared object.
+///
+/// The user is only asked for more space if `prompt` is set.
+pub(crate) fn write_to_storage<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    min_disk_space: usize,
+    prompt: bool,
+) -> Result<Option<FlushStatus>, Error<'gc>> {
     let NativeObject::SharedObject(shared_object) = this.native() else {
-        return Ok(Value::Undefined);
+        return Ok(None);
     };
     let name = shared_object.read().name();
     let data = this.get("data", activation)?.coerce_to_object(activation);
     let mut lso = new_lso(activation, &name, data);
-    flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
     // Flash does not write empty LSOs to disk
     if lso.body.is_empty() {
-        Ok(true.into())
+        Ok(Some(FlushStatus::Flushed))
     } else {
         let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-        Ok(activation.context.storage.put(&name, &bytes).into())
+        let target = prompt.then_some(this.into());
+        Ok(Some(crate::shared_object::flush(
+            &mut activation.context,
+            &name,
+            bytes,
+            min_disk_space,
+            target,
+        )))
     }
 }
 
@@ -0,0 +1,181 @@
+//! `TextField.StyleSheet` impl
+
+use crate::avm1::globals::text_field::new_text_format;
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{
+    Activation, ArrayObject, Attribute, Error, Object, ScriptObject, TObject, Value,
+};
+use crate::avm1_stub;
+use crate::context::GcContext;
+use crate::html::{Style, StyleSheet};
+use crate::string::AvmString;
+use gc_arena::Gc;
+
+macro_rules! method {
+    ($name:ident) => {
+        |activation, this, args| {
+            if let NativeObject::StyleSheet(style_sheet) = this.native() {
+                return $name(activation, &mut style_sheet.borrow_mut(), args);
+            }
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "clear" => method(method!(clear); DONT_ENUM | DONT_DELETE);
+    "getStyle" => method(method!(get_style); DONT_ENUM | DONT_DELETE);
+    "getStyleNames" => method(method!(get_style_names); DONT_ENUM | DONT_DELETE);
+    "setStyle" => method(method!(set_style); DONT_ENUM | DONT_DELETE);
+    "parse" => method(method!(parse); DONT_ENUM | DONT_DELETE);
+    "parseCSS" => method(method!(parse); DONT_ENUM | DONT_DELETE);
+    "transform" => method(transform; DONT_ENUM | DONT_DELETE);
+    "load" => method(load; DONT_ENUM | DONT_DELETE);
+};
+
+/// `TextField.StyleSheet` constructor
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.set_native(
+        activation.gc(),
+        NativeObject::StyleSheet(Gc::new(activation.gc(), StyleSheet::new().into())),
+    );
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc(), Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+/// Get a copy of the style sheet of a `TextField.StyleSheet` object.
+pub fn style_sheet(object: Object<'_>) -> Option<StyleSheet> {
+    match object.native() {
+        NativeObject::StyleSheet(style_sheet) => Some(style_sheet.borrow().clone()),
+        _ => None,
+    }
+}
+
+fn clear<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    style_sheet: &mut StyleSheet,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    style_sheet.clear();
+    Ok(Value::Undefined)
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: &mut StyleSheet,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let Some(style) = style_sheet.style(&name) else {
+        return Ok(Value::Null);
+    };
+
+    let object = ScriptObject::new(
+        activation.gc(),
+        Some(activation.context.avm1.prototypes().object),
+    );
+    for (property, value) in style.iter() {
+        let property = AvmString::new(activation.gc(), property);
+        let value = AvmString::new(activation.gc(), value);
+        object.define_value(activation.gc(), property, value.into(), Attribute::empty());
+    }
+    Ok(object.into())
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: &mut StyleSheet,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names: Vec<Value<'gc>> = style_sheet
+        .style_names()
+        .map(|name| AvmString::new(activation.gc(), name).into())
+        .collect();
+    Ok(ArrayObject::new(
+        activation.gc(),
+        activation.context.avm1.prototypes().array,
+        names,
+    )
+    .into())
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: &mut StyleSheet,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let style = match args.get(1) {
+        Some(Value::Object(object)) => Some(style_from_object(activation, *object)?),
+        _ => None,
+    };
+    style_sheet.set_style(&name, style);
+    Ok(Value::Undefined)
+}
+
+fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: &mut StyleSheet,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    Ok(style_sheet.parse_css(&css).into())
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(Value::Object(object)) = args.get(0) else {
+        return Ok(Value::Null);
+    };
+
+    let style = style_from_object(activation, *object)?;
+    Ok(new_text_format(activation, style.text_format()).into())
+}
+
+fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm1_stub!(activation, "TextField.StyleSheet", "load");
+    Ok(false.into())
+}
+
+/// Read a style from the properties of a style object.
+fn style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Style, Error<'gc>> {
+    let mut style = Style::new();
+    for name in object.get_keys(activation, false) {
+        let value = object.get(name, activation)?.coerce_to_string(activation)?;
+        style.set(name.as_wstr().into(), value.as_wstr().into());
+    }
+    Ok(style)
+}
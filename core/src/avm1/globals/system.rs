@@ -6,9 +6,12 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::runtime::Avm1;
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm1_stub;
+use crate::backend::storage::storage_domain;
 use crate::context::{GcContext, UpdateContext};
+use crate::display_object::TDisplayObject;
 use bitflags::bitflags;
 use core::fmt;
+use url::Url;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "exactSettings" => property(get_exact_settings, set_exact_settings);
@@ -221,7 +224,7 @@ impl fmt::Display for PlayerType {
     }
 }
 
-#[derive(Debug, Copy, Clone, FromPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromPrimitive)]
 enum SettingsPanel {
     Privacy = 0,
     LocalStorage = 1,
@@ -453,9 +456,19 @@ pub fn show_settings<'gc>(
         .unwrap_or(&last_panel_pos.into())
         .coerce_to_i32(activation)?;
 
-    let _panel = SettingsPanel::from_u8(panel_pos as u8).unwrap_or(SettingsPanel::Privacy);
+    let panel = SettingsPanel::from_u8(panel_pos as u8).unwrap_or(SettingsPanel::Privacy);
 
-    avm1_stub!(activation, "System", "showSettings");
+    if panel == SettingsPanel::LocalStorage {
+        let movie = activation.base_clip().movie();
+        if let Ok(url) = Url::parse(movie.url()) {
+            activation
+                .context
+                .ui
+                .display_storage_settings(storage_domain(&url));
+        }
+    } else {
+        avm1_stub!(activation, "System", "showSettings");
+    }
     Ok(Value::Undefined)
 }
 
@@ -1,6 +1,6 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
-use crate::avm1::globals::bitmap_filter;
+use crate::avm1::globals::{bitmap_filter, style_sheet};
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{globals, ArrayObject, Object, ScriptObject, TObject, Value};
@@ -66,6 +66,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "border" => property(tf_getter!(border), tf_setter!(set_border));
     "borderColor" => property(tf_getter!(border_color), tf_setter!(set_border_color));
     "bottomScroll" => property(tf_getter!(bottom_scroll));
+    "condenseWhite" => property(tf_getter!(condense_white), tf_setter!(set_condense_white));
     "embedFonts" => property(tf_getter!(embed_fonts), tf_setter!(set_embed_fonts));
     "filters" => property(tf_getter!(filters), tf_setter!(set_filters); DONT_DELETE | DONT_ENUM | VERSION_8);
     "getDepth" => method(globals::get_depth; DONT_ENUM | DONT_DELETE | READ_ONLY | VERSION_6);
@@ -75,9 +76,12 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "length" => property(tf_getter!(length));
     "maxhscroll" => property(tf_getter!(maxhscroll));
     "maxscroll" => property(tf_getter!(maxscroll));
+    "mouseWheelEnabled" => property(tf_getter!(mouse_wheel_enabled), tf_setter!(set_mouse_wheel_enabled));
     "multiline" => property(tf_getter!(multiline), tf_setter!(set_multiline));
     "password" => property(tf_getter!(password), tf_setter!(set_password));
+    "restrict" => property(tf_getter!(restrict), tf_setter!(set_restrict));
     "scroll" => property(tf_getter!(scroll), tf_setter!(set_scroll));
+    "styleSheet" => property(tf_getter!(style_sheet), tf_setter!(set_style_sheet));
     "selectable" => property(tf_getter!(selectable), tf_setter!(set_selectable));
     "text" => property(tf_getter!(text), tf_setter!(set_text));
     "textColor" => property(tf_getter!(text_color), tf_setter!(set_text_color));
@@ -129,7 +133,58 @@ pub fn set_password<'gc>(
     Ok(())
 }
 
-fn new_text_format<'gc>(
+fn restrict<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(restrict) = this.restrict() {
+        return Ok(AvmString::new(activation.context.gc_context, restrict).into());
+    }
+
+    // Unset `restrict` returns null, not undefined
+    Ok(Value::Null)
+}
+
+fn set_restrict<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let restrict = match value {
+        Value::Undefined | Value::Null => None,
+        v => Some(v.coerce_to_string(activation)?),
+    };
+    this.set_restrict(restrict.as_deref(), &mut activation.context);
+    Ok(())
+}
+
+fn style_sheet<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .avm1_style_sheet()
+        .map_or(Value::Undefined, Value::from))
+}
+
+fn set_style_sheet<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let object = match value {
+        Value::Object(object) => Some(object),
+        _ => None,
+    };
+    this.set_avm1_style_sheet(activation.context.gc_context, object);
+    this.set_style_sheet(
+        &mut activation.context,
+        object.and_then(style_sheet::style_sheet),
+    );
+    Ok(())
+}
+
+pub(crate) fn new_text_format<'gc>(
     activation: &mut Activation<'_, 'gc>,
     text_format: TextFormat,
 ) -> ScriptObject<'gc> {
@@ -335,6 +390,23 @@ pub fn set_html<'gc>(
     Ok(())
 }
 
+fn condense_white<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.condense_white().into())
+}
+
+fn set_condense_white<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let condense_white = value.as_bool(activation.swf_version());
+    this.set_condense_white(&mut activation.context, condense_white);
+    Ok(())
+}
+
 pub fn text_color<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
@@ -684,6 +756,23 @@ pub fn bottom_scroll<'gc>(
     Ok(this.bottom_scroll().into())
 }
 
+fn mouse_wheel_enabled<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.is_mouse_wheel_enabled().into())
+}
+
+fn set_mouse_wheel_enabled<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let is_enabled = value.as_bool(activation.swf_version());
+    this.set_mouse_wheel_enabled(activation.context.gc_context, is_enabled);
+    Ok(())
+}
+
 pub fn anti_alias_type<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
@@ -12,6 +12,8 @@ use crate::avm1::globals::drop_shadow_filter::DropShadowFilter;
 use crate::avm1::globals::file_reference::FileReferenceObject;
 use crate::avm1::globals::glow_filter::GlowFilter;
 use crate::avm1::globals::gradient_filter::GradientFilter;
+use crate::avm1::globals::local_connection::LocalConnection;
+use crate::avm1::globals::netconnection::NetConnection;
 use crate::avm1::globals::shared_object::SharedObject;
 use crate::avm1::globals::transform::TransformObject;
 use crate::avm1::globals::xml::Xml;
@@ -23,7 +25,7 @@ use crate::avm1::{Activation, Attribute, Error, ScriptObject, SoundObject, Stage
 use crate::bitmap::bitmap_data::BitmapDataWrapper;
 use crate::display_object::DisplayObject;
 use crate::display_object::TDisplayObject;
-use crate::html::TextFormat;
+use crate::html::{StyleSheet, TextFormat};
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::xml::XmlNode;
@@ -57,6 +59,7 @@ pub enum NativeObject<'gc> {
     ColorTransform(GcCell<'gc, ColorTransformObject>),
     Transform(TransformObject<'gc>),
     TextFormat(Gc<'gc, RefCell<TextFormat>>),
+    StyleSheet(Gc<'gc, RefCell<StyleSheet>>),
     NetStream(NetStream<'gc>),
     BitmapData(BitmapDataWrapper<'gc>),
     Xml(Xml<'gc>),
@@ -64,6 +67,8 @@ pub enum NativeObject<'gc> {
     SharedObject(GcCell<'gc, SharedObject>),
     XmlSocket(XmlSocket<'gc>),
     FileReference(FileReferenceObject<'gc>),
+    NetConnection(NetConnection<'gc>),
+    LocalConnection(LocalConnection<'gc>),
 }
 
 /// Represents an object that can be directly interacted with by the AVM
@@ -1,3 +1,4 @@
+use crate::avm1::debugger::Debugger;
 use crate::avm1::function::{ExecutionReason, FunctionObject};
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::globals::{as_broadcaster, create_globals};
@@ -85,6 +86,10 @@ pub struct Avm1<'gc> {
     /// More examples of this are in the movieclip_invalid_get_bounds_X tests.
     use_new_invalid_bounds_value: bool,
 
+    /// Breakpoints and the actions recorded after hitting one.
+    #[collect(require_static)]
+    pub debugger: Debugger,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -115,6 +120,8 @@ impl<'gc> Avm1<'gc> {
             constructor_registry_case_insensitive: PropertyMap::new(),
             constructor_registry_case_sensitive: PropertyMap::new(),
 
+            debugger: Default::default(),
+
             #[cfg(feature = "avm_debug")]
             debug_output: false,
             use_new_invalid_bounds_value: false,
@@ -329,6 +336,10 @@ impl<'gc> Avm1<'gc> {
         }
     }
 
+    pub fn stack(&self) -> &[Value<'gc>] {
+        &self.stack
+    }
+
     pub fn stack_len(&self) -> usize {
         self.stack.len()
     }
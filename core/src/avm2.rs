@@ -34,6 +34,7 @@ mod array;
 pub mod bytearray;
 mod call_stack;
 mod class;
+pub mod debugger;
 mod domain;
 mod dynamic_map;
 mod e4x;
@@ -53,6 +54,7 @@ pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+mod sampler;
 mod scope;
 mod script;
 mod string;
@@ -61,10 +63,12 @@ mod traits;
 mod value;
 pub mod vector;
 mod vtable;
+mod worker;
 
 pub use crate::avm2::activation::Activation;
 pub use crate::avm2::array::ArrayStorage;
 pub use crate::avm2::call_stack::{CallNode, CallStack};
+pub use crate::avm2::debugger::Debugger;
 pub use crate::avm2::domain::Domain;
 pub use crate::avm2::error::Error;
 pub use crate::avm2::flv::FlvValueAvm2Ext;
@@ -76,7 +80,9 @@ pub use crate::avm2::object::{
     SoundChannelObject, StageObject, TObject,
 };
 pub use crate::avm2::qname::QName;
+pub use crate::avm2::sampler::Sampler;
 pub use crate::avm2::value::Value;
+pub use crate::avm2::worker::Worker;
 
 use self::api_version::ApiVersion;
 use self::object::WeakObject;
@@ -101,6 +107,9 @@ pub struct Avm2<'gc> {
     /// The current call stack of the player.
     call_stack: GcCell<'gc, CallStack<'gc>>,
 
+    /// The state of `flash.sampler`.
+    sampler: GcCell<'gc, Sampler<'gc>>,
+
     /// This domain is used exclusively for classes from playerglobals
     playerglobals_domain: Domain<'gc>,
 
@@ -135,6 +144,8 @@ pub struct Avm2<'gc> {
     pub flash_events_internal: Namespace<'gc>,
     pub flash_text_engine_internal: Namespace<'gc>,
     pub flash_net_internal: Namespace<'gc>,
+    pub flash_globalization_internal: Namespace<'gc>,
+    pub flash_sampler_internal: Namespace<'gc>,
 
     #[collect(require_static)]
     native_method_table: &'static [Option<(&'static str, NativeMethodImpl)>],
@@ -174,6 +185,13 @@ pub struct Avm2<'gc> {
     #[collect(require_static)]
     pub root_api_version: ApiVersion,
 
+    /// Breakpoints and the step points recorded after hitting one.
+    #[collect(require_static)]
+    pub debugger: Debugger,
+
+    /// The worker this interpreter belongs to, once any script has used workers.
+    worker: Option<Worker<'gc>>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -194,6 +212,7 @@ impl<'gc> Avm2<'gc> {
             stack: Vec::new(),
             scope_stack: Vec::new(),
             call_stack: GcCell::new(context.gc_context, CallStack::new()),
+            sampler: GcCell::new(context.gc_context, Sampler::default()),
             playerglobals_domain,
             stage_domain,
             system_classes: None,
@@ -226,6 +245,8 @@ impl<'gc> Avm2<'gc> {
             flash_events_internal: Namespace::internal("flash.events", context),
             flash_text_engine_internal: Namespace::internal("flash.text.engine", context),
             flash_net_internal: Namespace::internal("flash.net", context),
+            flash_globalization_internal: Namespace::internal("flash.globalization", context),
+            flash_sampler_internal: Namespace::internal("flash.sampler", context),
 
             native_method_table: Default::default(),
             native_instance_allocator_table: Default::default(),
@@ -238,6 +259,10 @@ impl<'gc> Avm2<'gc> {
             // Set the lowest version for now - this be overriden when we set our movie
             root_api_version: ApiVersion::AllVersions,
 
+            debugger: Default::default(),
+
+            worker: None,
+
             #[cfg(feature = "avm_debug")]
             debug_output: false,
         }
@@ -563,7 +588,13 @@ impl<'gc> Avm2<'gc> {
 
     /// Pushes an executable on the call stack
     pub fn push_call(&self, mc: &Mutation<'gc>, calling: &Executable<'gc>) {
-        self.call_stack.write(mc).push(calling)
+        self.call_stack.write(mc).push(calling);
+
+        if self.sampler.read().is_sampling() {
+            self.sampler
+                .write(mc)
+                .record_call(calling.as_method(), &self.call_stack.read());
+        }
     }
 
     /// Pushes script initializer (global init) on the call stack
@@ -580,6 +611,19 @@ impl<'gc> Avm2<'gc> {
         self.call_stack
     }
 
+    pub fn sampler(&self) -> GcCell<'gc, Sampler<'gc>> {
+        self.sampler
+    }
+
+    /// Records the construction of an object, if `flash.sampler` is sampling.
+    pub fn record_allocation(&self, mc: &Mutation<'gc>, object: Object<'gc>) {
+        if self.sampler.read().is_sampling() {
+            self.sampler
+                .write(mc)
+                .record_allocation(object, &self.call_stack.read());
+        }
+    }
+
     /// Push a value onto the operand stack.
     fn push(&mut self, value: impl Into<Value<'gc>>, depth: usize, max: usize) {
         if self.stack.len() - depth > max {
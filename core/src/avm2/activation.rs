@@ -2,6 +2,7 @@
 
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::debugger::{ActivationDebugInfo, StopPoint};
 use crate::avm2::domain::Domain;
 use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
@@ -143,6 +144,9 @@ pub struct Activation<'a, 'gc: 'a> {
     /// Maximum size for the scope frame.
     max_scope_size: usize,
 
+    /// Debugging information reported by the method's bytecode, used by the debugger.
+    debug_info: ActivationDebugInfo<'gc>,
+
     pub context: UpdateContext<'a, 'gc>,
 }
 
@@ -177,6 +181,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: 0,
             max_scope_size: 0,
+            debug_info: Default::default(),
             context,
         }
     }
@@ -205,6 +210,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: 0,
             max_scope_size: 0,
+            debug_info: Default::default(),
             context,
         }
     }
@@ -246,6 +252,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: max_stack as usize,
             max_scope_size: max_scope as usize,
+            debug_info: Default::default(),
             context,
         })
     }
@@ -436,6 +443,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: body.max_stack as usize,
             max_scope_size: (body.max_scope_depth - body.init_scope_depth) as usize,
+            debug_info: Default::default(),
             context,
         };
 
@@ -534,6 +542,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: 0,
             max_scope_size: 0,
+            debug_info: Default::default(),
             context,
         })
     }
@@ -943,6 +952,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if let Ok(op) = op {
             avm_debug!(self.avm2(), "Opcode: {op:?}");
 
+            if self.context.avm2.debugger.is_active() {
+                self.debugger_check_instruction(instruction_start, &op);
+            }
+
             let result = match op {
                 Op::PushByte { value } => self.op_push_byte(value),
                 Op::PushDouble { value } => self.op_push_double(method, value),
@@ -1094,7 +1107,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     register,
                 } => self.op_debug(method, is_local_register, register_name, register),
                 Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
-                Op::DebugLine { line_num } => self.op_debug_line(line_num),
+                Op::DebugLine { line_num } => self.op_debug_line(instruction_start, line_num),
                 Op::Bkpt => self.op_bkpt(),
                 Op::BkptLine { line_num } => self.op_bkpt_line(line_num),
                 Op::Timestamp => self.op_timestamp(),
@@ -1954,6 +1967,9 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let scope = self.create_scopechain();
 
         let new_fn = FunctionObject::from_function(self, method_entry, scope)?;
+        self.context
+            .avm2
+            .record_allocation(self.context.gc_context, new_fn.into());
 
         self.push_stack(new_fn);
 
@@ -3169,7 +3185,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
 
         let val = dm.read_at(2, address).map_err(|e| e.to_avm(self))?;
-        self.push_stack(u16::from_le_bytes(val.try_into().unwrap()));
+        self.push_stack(u16::from_le_bytes(val[..].try_into().unwrap()));
 
         Ok(FrameControl::Continue)
     }
@@ -3188,7 +3204,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
 
         let val = dm.read_at(4, address).map_err(|e| e.to_avm(self))?;
-        self.push_stack(i32::from_le_bytes(val.try_into().unwrap()));
+        self.push_stack(i32::from_le_bytes(val[..].try_into().unwrap()));
         Ok(FrameControl::Continue)
     }
 
@@ -3206,7 +3222,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
 
         let val = dm.read_at(4, address).map_err(|e| e.to_avm(self))?;
-        self.push_stack(f32::from_le_bytes(val.try_into().unwrap()));
+        self.push_stack(f32::from_le_bytes(val[..].try_into().unwrap()));
 
         Ok(FrameControl::Continue)
     }
@@ -3225,7 +3241,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
 
         let val = dm.read_at(8, address).map_err(|e| e.to_avm(self))?;
-        self.push_stack(f64::from_le_bytes(val.try_into().unwrap()));
+        self.push_stack(f64::from_le_bytes(val[..].try_into().unwrap()));
         Ok(FrameControl::Continue)
     }
 
@@ -3272,6 +3288,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         if is_local_register {
             let register_name = self.pool_string(&method, register_name)?;
+            if self.context.avm2.debugger.is_active() {
+                self.set_debug_register_name(register, register_name);
+            }
+
             if (register as usize) < self.local_registers.0.len() {
                 let value = self.local_register(register as u32)?;
 
@@ -3292,11 +3312,16 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     #[cfg(not(feature = "avm_debug"))]
     fn op_debug(
         &mut self,
-        _method: Gc<'gc, BytecodeMethod<'gc>>,
-        _is_local_register: bool,
-        _register_name: Index<String>,
-        _register: u8,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        is_local_register: bool,
+        register_name: Index<String>,
+        register: u8,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        if is_local_register && self.context.avm2.debugger.is_active() {
+            let register_name = self.pool_string(&method, register_name)?;
+            self.set_debug_register_name(register, register_name);
+        }
+
         Ok(FrameControl::Continue)
     }
 
@@ -3307,6 +3332,9 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         file_name: Index<String>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let file_name = self.pool_string(&method, file_name)?;
+        if self.context.avm2.debugger.is_active() {
+            self.debug_info.file = Some(file_name);
+        }
 
         avm_debug!(self.avm2(), "File: {file_name}");
 
@@ -3316,18 +3344,148 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     #[cfg(not(feature = "avm_debug"))]
     fn op_debug_file(
         &mut self,
-        _method: Gc<'gc, BytecodeMethod<'gc>>,
-        _file_name: Index<String>,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        file_name: Index<String>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        if self.context.avm2.debugger.is_active() {
+            self.debug_info.file = Some(self.pool_string(&method, file_name)?);
+        }
+
         Ok(FrameControl::Continue)
     }
 
-    fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_debug_line(
+        &mut self,
+        instruction_start: usize,
+        line_num: u32,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {line_num}");
 
+        if self.context.avm2.debugger.is_active() {
+            self.debug_info.line = Some(line_num);
+            let file_name = self.debug_info.file.map(|f| f.to_string());
+            if let Some(hit_breakpoint) = self
+                .context
+                .avm2
+                .debugger
+                .check_line(file_name.as_deref(), line_num)
+            {
+                let stop = self.debugger_stop_point(instruction_start, None, hit_breakpoint);
+                self.context.avm2.debugger.record(stop);
+            }
+        }
+
         Ok(FrameControl::Continue)
     }
 
+    fn set_debug_register_name(&mut self, register: u8, name: AvmAtom<'gc>) {
+        let names = &mut self.debug_info.register_names;
+        match names.iter_mut().find(|(r, _)| *r == register) {
+            Some((_, old_name)) => *old_name = name,
+            None => names.push((register, name)),
+        }
+    }
+
+    /// Reports an instruction that is about to be executed to the debugger.
+    #[cold]
+    fn debugger_check_instruction(&mut self, instruction_start: usize, op: &Op) {
+        // Resolved once method breakpoints exist, however late that is in this activation.
+        if self.debug_info.method_name.is_none()
+            && self.context.avm2.debugger.has_method_breakpoints()
+        {
+            self.debug_info.method_name = self.context.avm2.call_stack().read().top_frame_name();
+        }
+
+        if let Some(hit_breakpoint) = self
+            .context
+            .avm2
+            .debugger
+            .check_instruction(self.debug_info.method_name.as_deref(), instruction_start)
+        {
+            let stop = self.debugger_stop_point(
+                instruction_start,
+                Some(format!("{op:?}")),
+                hit_breakpoint,
+            );
+            self.context.avm2.debugger.record(stop);
+        }
+    }
+
+    /// Takes a snapshot of this activation for the debugger.
+    fn debugger_stop_point(
+        &self,
+        offset: usize,
+        instruction: Option<String>,
+        hit_breakpoint: bool,
+    ) -> StopPoint {
+        let call_stack = self.context.avm2.call_stack().read().frame_names();
+
+        let locals = self
+            .local_registers
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let name = self
+                    .debug_info
+                    .register_names
+                    .iter()
+                    .find(|(register, _)| *register as usize == i)
+                    .map(|(_, name)| name.to_string())
+                    .unwrap_or_else(|| match i {
+                        0 => "this".to_string(),
+                        _ => format!("_{i}"),
+                    });
+                (name, self.debugger_describe_value(*value))
+            })
+            .collect();
+
+        let scope_stack = self
+            .scope_frame()
+            .iter()
+            .map(|scope| self.debugger_describe_value(scope.values().into()))
+            .collect();
+
+        let operand_stack = self.context.avm2.stack[self.stack_depth..]
+            .iter()
+            .map(|value| self.debugger_describe_value(*value))
+            .collect();
+
+        StopPoint {
+            method: self
+                .debug_info
+                .method_name
+                .clone()
+                .or_else(|| call_stack.first().cloned())
+                .unwrap_or_default(),
+            offset,
+            instruction,
+            file: self.debug_info.file.map(|f| f.to_string()),
+            line: self.debug_info.line,
+            depth: call_stack.len(),
+            hit_breakpoint,
+            call_stack,
+            locals,
+            scope_stack,
+            operand_stack,
+        }
+    }
+
+    /// Describes a value for the debugger, without calling into ActionScript.
+    fn debugger_describe_value(&self, value: Value<'gc>) -> String {
+        match value {
+            Value::Undefined => "undefined".to_string(),
+            Value::Null => "null".to_string(),
+            Value::Bool(value) => value.to_string(),
+            Value::Number(value) => value.to_string(),
+            Value::Integer(value) => value.to_string(),
+            Value::String(value) => format!("{:?}", value.to_utf8_lossy()),
+            Value::Object(object) => {
+                format!("[object {}]", object.instance_of_class_name(self.gc()))
+            }
+        }
+    }
+
     fn op_bkpt(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
         Ok(FrameControl::Continue)
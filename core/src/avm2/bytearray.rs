@@ -5,11 +5,12 @@ use crate::string::{FromWStr, WStr};
 use flate2::read::*;
 use flate2::Compression;
 use gc_arena::Collect;
-use std::cell::Cell;
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::cmp;
 use std::fmt::{self, Display, Formatter};
 use std::io::prelude::*;
 use std::io::{self, Read, SeekFrom};
+use std::rc::Rc;
 
 #[derive(Clone, Collect, Debug, Copy, PartialEq, Eq)]
 #[collect(no_drop)]
@@ -90,11 +91,19 @@ pub enum ObjectEncoding {
     Amf3 = 3,
 }
 
-#[derive(Clone, Collect, Debug)]
+/// The bytes of a ByteArray.
+///
+/// A shareable ByteArray shares these with its copies in other workers,
+/// while each copy keeps its own position and endianness.
+#[derive(Clone, Collect, Debug, Default)]
+#[collect(require_static)]
+pub struct SharedBytes(Rc<RefCell<Vec<u8>>>);
+
+#[derive(Collect, Debug)]
 #[collect(no_drop)]
 pub struct ByteArrayStorage {
     /// Underlying ByteArray
-    bytes: Vec<u8>,
+    bytes: SharedBytes,
 
     /// The current position to read/write from
     position: Cell<usize>,
@@ -110,7 +119,7 @@ impl ByteArrayStorage {
     /// Create a new ByteArrayStorage
     pub fn new() -> ByteArrayStorage {
         ByteArrayStorage {
-            bytes: Vec::new(),
+            bytes: SharedBytes::default(),
             position: Cell::new(0),
             endian: Endian::Big,
             object_encoding: ObjectEncoding::Amf3,
@@ -119,6 +128,17 @@ impl ByteArrayStorage {
 
     /// Create a new ByteArrayStorage using an already existing vector
     pub fn from_vec(bytes: Vec<u8>) -> ByteArrayStorage {
+        ByteArrayStorage {
+            bytes: SharedBytes(Rc::new(RefCell::new(bytes))),
+            position: Cell::new(0),
+            endian: Endian::Big,
+            object_encoding: ObjectEncoding::Amf3,
+        }
+    }
+
+    /// Create a new ByteArrayStorage that shares the bytes of `bytes`,
+    /// with its own position, endianness and object encoding.
+    pub fn from_shared_bytes(bytes: SharedBytes) -> ByteArrayStorage {
         ByteArrayStorage {
             bytes,
             position: Cell::new(0),
@@ -127,6 +147,17 @@ impl ByteArrayStorage {
         }
     }
 
+    /// The bytes of this ByteArray, to be shared with a ByteArray in another worker.
+    pub fn shared_bytes(&self) -> SharedBytes {
+        self.bytes.clone()
+    }
+
+    /// Whether this ByteArray uses the same bytes as `other`,
+    /// as a shareable ByteArray does with its copies in other workers.
+    pub fn shares_bytes_with(&self, other: &ByteArrayStorage) -> bool {
+        Rc::ptr_eq(&self.bytes.0, &other.bytes.0)
+    }
+
     /// Write bytes at the next position in the ByteArray, growing if needed.
     #[inline]
     pub fn write_bytes(&mut self, buf: &[u8]) -> Result<(), ByteArrayError> {
@@ -144,7 +175,7 @@ impl ByteArrayStorage {
 
     /// Reads any amount of bytes from the current position in the ByteArray
     #[inline]
-    pub fn read_bytes(&self, amnt: usize) -> Result<&[u8], ByteArrayError> {
+    pub fn read_bytes(&self, amnt: usize) -> Result<Ref<'_, [u8]>, ByteArrayError> {
         let bytes = self.read_at(amnt, self.position.get())?;
         self.position.set(self.position.get() + amnt);
         Ok(bytes)
@@ -153,24 +184,26 @@ impl ByteArrayStorage {
     /// Same as `read_bytes`, but:
     /// - cuts the result at the first null byte to recreate a bug in FP
     /// - strips off an optional UTF8 BOM at the beginning
-    pub fn read_utf_bytes(&self, amnt: usize) -> Result<&[u8], ByteArrayError> {
-        let mut bytes = self.read_bytes(amnt)?;
-        if let Some(without_bom) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
-            bytes = without_bom;
-        }
-        if let Some(null) = bytes.iter().position(|b| *b == b'\0') {
-            bytes = &bytes[..null];
-        }
-        Ok(bytes)
+    pub fn read_utf_bytes(&self, amnt: usize) -> Result<Ref<'_, [u8]>, ByteArrayError> {
+        Ok(Ref::map(self.read_bytes(amnt)?, |bytes| {
+            let mut bytes = bytes;
+            if let Some(without_bom) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+                bytes = without_bom;
+            }
+            if let Some(null) = bytes.iter().position(|b| *b == b'\0') {
+                bytes = &bytes[..null];
+            }
+            bytes
+        }))
     }
 
     /// Reads any amount of bytes at any offset in the ByteArray
     #[inline]
-    pub fn read_at(&self, amnt: usize, offset: usize) -> Result<&[u8], ByteArrayError> {
-        self.bytes
-            .get(offset..)
-            .and_then(|bytes| bytes.get(..amnt))
-            .ok_or(ByteArrayError::EndOfFile)
+    pub fn read_at(&self, amnt: usize, offset: usize) -> Result<Ref<'_, [u8]>, ByteArrayError> {
+        Ref::filter_map(self.bytes.0.borrow(), |bytes| {
+            bytes.get(offset..).and_then(|bytes| bytes.get(..amnt))
+        })
+        .map_err(|_| ByteArrayError::EndOfFile)
     }
 
     /// Write bytes at any offset in the ByteArray
@@ -186,6 +219,8 @@ impl ByteArrayStorage {
             self.set_length(new_len);
         }
         self.bytes
+            .0
+            .borrow_mut()
             .get_mut(offset..new_len)
             .expect("ByteArray write out of bounds")
             .copy_from_slice(buf);
@@ -196,6 +231,8 @@ impl ByteArrayStorage {
     /// Will return an error if the new buffer does not fit the ByteArray
     pub fn write_at_nongrowing(&mut self, buf: &[u8], offset: usize) -> Result<(), ByteArrayError> {
         self.bytes
+            .0
+            .borrow_mut()
             .get_mut(offset..)
             .and_then(|bytes| bytes.get_mut(..buf.len()))
             .ok_or(ByteArrayError::IndexOutOfBounds)?
@@ -228,24 +265,25 @@ impl ByteArrayStorage {
             self.set_length(new_len);
         }
 
-        self.bytes.copy_within(start..end, offset);
+        self.bytes.0.borrow_mut().copy_within(start..end, offset);
         Ok(())
     }
 
     /// Compress the ByteArray into a temporary buffer.
     pub fn compress(&mut self, algorithm: CompressionAlgorithm) -> Vec<u8> {
+        let bytes = self.bytes();
         let mut buffer = Vec::new();
         let error: Option<Box<dyn std::error::Error>> = match algorithm {
             CompressionAlgorithm::Zlib => {
-                let mut encoder = ZlibEncoder::new(&*self.bytes, Compression::fast());
+                let mut encoder = ZlibEncoder::new(&*bytes, Compression::fast());
                 encoder.read_to_end(&mut buffer).err().map(|e| e.into())
             }
             CompressionAlgorithm::Deflate => {
-                let mut encoder = DeflateEncoder::new(&*self.bytes, Compression::fast());
+                let mut encoder = DeflateEncoder::new(&*bytes, Compression::fast());
                 encoder.read_to_end(&mut buffer).err().map(|e| e.into())
             }
             #[cfg(feature = "lzma")]
-            CompressionAlgorithm::Lzma => lzma_rs::lzma_compress(&mut &*self.bytes, &mut buffer)
+            CompressionAlgorithm::Lzma => lzma_rs::lzma_compress(&mut &*bytes, &mut buffer)
                 .err()
                 .map(|e| e.into()),
             #[cfg(not(feature = "lzma"))]
@@ -261,18 +299,19 @@ impl ByteArrayStorage {
 
     /// Decompress the ByteArray into a temporary buffer.
     pub fn decompress(&mut self, algorithm: CompressionAlgorithm) -> Option<Vec<u8>> {
+        let bytes = self.bytes();
         let mut buffer = Vec::new();
         let error: Option<Box<dyn std::error::Error>> = match algorithm {
             CompressionAlgorithm::Zlib => {
-                let mut decoder = ZlibDecoder::new(&*self.bytes);
+                let mut decoder = ZlibDecoder::new(&*bytes);
                 decoder.read_to_end(&mut buffer).err().map(|e| e.into())
             }
             CompressionAlgorithm::Deflate => {
-                let mut decoder = DeflateDecoder::new(&*self.bytes);
+                let mut decoder = DeflateDecoder::new(&*bytes);
                 decoder.read_to_end(&mut buffer).err().map(|e| e.into())
             }
             #[cfg(feature = "lzma")]
-            CompressionAlgorithm::Lzma => lzma_rs::lzma_decompress(&mut &*self.bytes, &mut buffer)
+            CompressionAlgorithm::Lzma => lzma_rs::lzma_decompress(&mut &*bytes, &mut buffer)
                 .err()
                 .map(|e| e.into()),
             #[cfg(not(feature = "lzma"))]
@@ -286,7 +325,7 @@ impl ByteArrayStorage {
         }
     }
 
-    pub fn read_utf(&self) -> Result<&[u8], ByteArrayError> {
+    pub fn read_utf(&self) -> Result<Ref<'_, [u8]>, ByteArrayError> {
         let len = self.read_unsigned_short()?;
         let val = self.read_utf_bytes(len.into())?;
         Ok(val)
@@ -297,7 +336,7 @@ impl ByteArrayStorage {
     }
 
     pub fn read_boolean(&self) -> Result<bool, ByteArrayError> {
-        Ok(self.read_bytes(1)? != [0])
+        Ok(*self.read_bytes(1)? != [0])
     }
 
     // Writes a UTF String into the buffer, with its length as a prefix
@@ -312,52 +351,53 @@ impl ByteArrayStorage {
 
     #[inline]
     pub fn clear(&mut self) {
-        self.bytes.clear();
+        self.bytes.0.borrow_mut().clear();
         self.position.set(0)
     }
 
     #[inline]
     pub fn shrink_to_fit(&mut self) {
-        self.bytes.shrink_to_fit()
+        self.bytes.0.borrow_mut().shrink_to_fit()
     }
 
     #[inline]
     pub fn set_length(&mut self, new_len: usize) {
-        self.bytes.resize(new_len, 0);
+        self.bytes.0.borrow_mut().resize(new_len, 0);
         self.position.set(self.position().min(new_len));
     }
 
     pub fn get(&self, pos: usize) -> Option<u8> {
-        self.bytes.get(pos).copied()
+        self.bytes.0.borrow().get(pos).copied()
     }
 
     pub fn set(&mut self, item: usize, value: u8) {
-        if self.len() < (item + 1) {
-            self.bytes.resize(item + 1, 0)
+        let mut bytes = self.bytes.0.borrow_mut();
+        if bytes.len() < (item + 1) {
+            bytes.resize(item + 1, 0)
         }
 
-        *self.bytes.get_mut(item).unwrap() = value;
+        bytes[item] = value;
     }
 
     pub fn delete(&mut self, item: usize) {
-        if let Some(i) = self.bytes.get_mut(item) {
+        if let Some(i) = self.bytes.0.borrow_mut().get_mut(item) {
             *i = 0;
         }
     }
 
     #[inline]
-    pub fn bytes(&self) -> &[u8] {
-        &self.bytes
+    pub fn bytes(&self) -> Ref<'_, [u8]> {
+        Ref::map(self.bytes.0.borrow(), |bytes| &bytes[..])
     }
 
     #[inline]
-    pub fn bytes_mut(&mut self) -> &mut [u8] {
-        &mut self.bytes
+    pub fn bytes_mut(&mut self) -> RefMut<'_, [u8]> {
+        RefMut::map(self.bytes.0.borrow_mut(), |bytes| &mut bytes[..])
     }
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.bytes.len()
+        self.bytes.0.borrow().len()
     }
 
     #[inline]
@@ -417,7 +457,7 @@ impl Read for ByteArrayStorage {
             .map_err(|_| {
                 io::Error::new(io::ErrorKind::Other, "Failed to read from ByteArrayStorage")
             })?;
-        buf[..bytes.len()].copy_from_slice(bytes);
+        buf[..bytes.len()].copy_from_slice(&bytes);
         Ok(bytes.len())
     }
 }
@@ -475,15 +515,15 @@ macro_rules! impl_read{
         impl ByteArrayStorage {
             $( pub fn $method_name (&self) -> Result<$data_type, ByteArrayError> {
                 Ok(match self.endian {
-                    Endian::Big => <$data_type>::from_be_bytes(self.read_bytes($size)?.try_into().unwrap()),
-                    Endian::Little => <$data_type>::from_le_bytes(self.read_bytes($size)?.try_into().unwrap())
+                    Endian::Big => <$data_type>::from_be_bytes(self.read_bytes($size)?[..].try_into().unwrap()),
+                    Endian::Little => <$data_type>::from_le_bytes(self.read_bytes($size)?[..].try_into().unwrap())
                 })
              } )*
 
              $( pub fn $at_method_name (&self, offset: usize) -> Result<$data_type, ByteArrayError> {
                 Ok(match self.endian {
-                    Endian::Big => <$data_type>::from_be_bytes(self.read_at($size, offset)?.try_into().unwrap()),
-                    Endian::Little => <$data_type>::from_le_bytes(self.read_at($size, offset)?.try_into().unwrap())
+                    Endian::Big => <$data_type>::from_be_bytes(self.read_at($size, offset)?[..].try_into().unwrap()),
+                    Endian::Little => <$data_type>::from_le_bytes(self.read_at($size, offset)?[..].try_into().unwrap())
                 })
              } )*
         }
@@ -493,6 +533,18 @@ macro_rules! impl_read{
 impl_write!(write_float f32, write_double f64, write_int i32, write_unsigned_int u32, write_short i16, write_unsigned_short u16);
 impl_read!(read_float read_float_at 4; f32, read_double read_double_at 8; f64, read_int read_int_at 4; i32, read_unsigned_int read_unsigned_int_at 4; u32, read_short read_short_at 2; i16, read_unsigned_short read_unsigned_short_at 2; u16, read_byte read_byte_at 1; i8, read_unsigned_byte read_unsigned_byte_at 1; u8);
 
+impl Clone for ByteArrayStorage {
+    /// Copies the bytes, so that the clone doesn't share them with this ByteArray.
+    fn clone(&self) -> Self {
+        ByteArrayStorage {
+            bytes: SharedBytes(Rc::new(RefCell::new(self.bytes.0.borrow().clone()))),
+            position: self.position.clone(),
+            endian: self.endian,
+            object_encoding: self.object_encoding,
+        }
+    }
+}
+
 impl Default for ByteArrayStorage {
     fn default() -> Self {
         Self::new()
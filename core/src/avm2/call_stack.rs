@@ -16,6 +16,32 @@ pub enum CallNode<'gc> {
     },
 }
 
+impl<'gc> CallNode<'gc> {
+    pub fn display(&self, output: &mut WString) {
+        match self {
+            CallNode::GlobalInit(script) => {
+                let name = if let Some(tuint) = script.translation_unit() {
+                    if let Some(name) = tuint.name() {
+                        name.to_utf8_lossy().to_string()
+                    } else {
+                        "<No name>".to_string()
+                    }
+                } else {
+                    "<No translation unit>".to_string()
+                };
+
+                // NOTE: We intentionally diverge from Flash Player's output
+                // here - everything with the [] brackets is extra information
+                // added by Ruffle
+                output.push_utf8(&format!("global$init() [TU={}]", name));
+            }
+            CallNode::Method { method, superclass } => {
+                display_function(output, method, *superclass)
+            }
+        }
+    }
+}
+
 #[derive(Collect, Clone)]
 #[collect(no_drop)]
 pub struct CallStack<'gc> {
@@ -45,30 +71,36 @@ impl<'gc> CallStack<'gc> {
     pub fn display(&self, output: &mut WString) {
         for call in self.stack.iter().rev() {
             output.push_utf8("\n\tat ");
-            match call {
-                CallNode::GlobalInit(script) => {
-                    let name = if let Some(tuint) = script.translation_unit() {
-                        if let Some(name) = tuint.name() {
-                            name.to_utf8_lossy().to_string()
-                        } else {
-                            "<No name>".to_string()
-                        }
-                    } else {
-                        "<No translation unit>".to_string()
-                    };
-
-                    // NOTE: We intentionally diverge from Flash Player's output
-                    // here - everything with the [] brackets is extra information
-                    // added by Ruffle
-                    output.push_utf8(&format!("global$init() [TU={}]", name));
-                }
-                CallNode::Method { method, superclass } => {
-                    display_function(output, method, *superclass)
-                }
-            }
+            call.display(output);
         }
     }
 
+    /// The names of every frame on the stack, innermost first.
+    pub fn frame_names(&self) -> Vec<String> {
+        self.stack
+            .iter()
+            .rev()
+            .map(|call| {
+                let mut output = WString::new();
+                call.display(&mut output);
+                output.to_string()
+            })
+            .collect()
+    }
+
+    /// The name of the innermost frame on the stack.
+    pub fn top_frame_name(&self) -> Option<String> {
+        self.stack.last().map(|call| {
+            let mut output = WString::new();
+            call.display(&mut output);
+            output.to_string()
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
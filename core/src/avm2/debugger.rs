@@ -0,0 +1,285 @@
+//! Breakpoints and traces of AVM2 bytecode.
+//!
+//! Breakpoints don't pause scripts, they start recording a trace of them.
+//! See `crate::debugger` for how step points are recorded.
+
+use crate::debugger::{Stop, TraceRecorder};
+use crate::string::AvmAtom;
+
+/// A location that starts a trace when it's reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Trace from the instruction at the given bytecode offset of a method.
+    ///
+    /// Methods are named the same way as they appear in stack traces, such as
+    /// `Main/onEnterFrame()`.
+    Method { name: String, offset: usize },
+
+    /// Trace from a line of source code, as reported by `debugfile` and `debugline`.
+    ///
+    /// Matches any file whose name ends with `file`, as compilers usually record
+    /// full paths.
+    Line { file: String, line: u32 },
+}
+
+impl Breakpoint {
+    fn matches_method(&self, method_name: &str, method_offset: usize) -> bool {
+        matches!(self, Self::Method { name, offset } if name == method_name && *offset == method_offset)
+    }
+
+    fn matches_line(&self, file_name: Option<&str>, line_num: u32) -> bool {
+        match (self, file_name) {
+            (Self::Line { file, line }, Some(file_name)) => {
+                *line == line_num && file_name.ends_with(file.as_str())
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Method { name, offset } => write!(f, "{name} @ {offset}"),
+            Self::Line { file, line } => write!(f, "{file}:{line}"),
+        }
+    }
+}
+
+/// How far a single step moves through the program.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StepGranularity {
+    /// Record a step point at every `debugline` instruction.
+    #[default]
+    Line,
+
+    /// Record a step point at every instruction.
+    Instruction,
+}
+
+/// A snapshot of the interpreter at a recorded step point.
+#[derive(Clone, Debug)]
+pub struct StopPoint {
+    /// The name of the method being executed.
+    pub method: String,
+
+    /// The bytecode offset of the next instruction.
+    pub offset: usize,
+
+    /// The next instruction, if stopped before one.
+    pub instruction: Option<String>,
+
+    pub file: Option<String>,
+    pub line: Option<u32>,
+
+    /// The depth of the call stack.
+    pub depth: usize,
+
+    /// Whether a breakpoint was hit here, as opposed to stepping onto it.
+    pub hit_breakpoint: bool,
+
+    /// The names of every frame on the call stack, innermost first.
+    pub call_stack: Vec<String>,
+
+    /// Local registers, named by `debug` instructions when available.
+    pub locals: Vec<(String, String)>,
+
+    /// The scope stack of the method, outermost first.
+    pub scope_stack: Vec<String>,
+
+    /// The operand stack of the method, bottom first.
+    pub operand_stack: Vec<String>,
+}
+
+impl Stop for StopPoint {
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn hit_breakpoint(&self) -> bool {
+        self.hit_breakpoint
+    }
+}
+
+/// The step points recorded after a breakpoint was hit.
+pub type Trace = crate::debugger::Trace<StopPoint>;
+
+/// The debugging state of the AVM2 interpreter.
+#[derive(Default)]
+pub struct Debugger {
+    breakpoints: Vec<Breakpoint>,
+    granularity: StepGranularity,
+
+    /// Whether the next step point should start a trace, even without a breakpoint.
+    break_requested: bool,
+
+    /// The step points recorded so far, if a breakpoint has been hit.
+    recorder: TraceRecorder<StopPoint>,
+}
+
+impl Debugger {
+    /// Whether activations need to report step points.
+    ///
+    /// This is checked before every instruction, so it must stay cheap.
+    #[inline(always)]
+    pub fn is_active(&self) -> bool {
+        !self.recorder.is_truncated()
+            && (!self.breakpoints.is_empty()
+                || self.break_requested
+                || self.recorder.is_recording())
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: &Breakpoint) {
+        self.breakpoints.retain(|b| b != breakpoint);
+    }
+
+    pub fn has_method_breakpoints(&self) -> bool {
+        self.breakpoints
+            .iter()
+            .any(|b| matches!(b, Breakpoint::Method { .. }))
+    }
+
+    pub fn granularity(&self) -> StepGranularity {
+        self.granularity
+    }
+
+    pub fn set_granularity(&mut self, granularity: StepGranularity) {
+        self.granularity = granularity;
+    }
+
+    /// Starts a trace at the next step point that is reached.
+    pub fn request_break(&mut self) {
+        self.break_requested = true;
+    }
+
+    pub fn is_break_requested(&self) -> bool {
+        self.break_requested
+    }
+
+    /// Removes every breakpoint and discards any recording, so that scripts run at full speed.
+    pub fn clear(&mut self) {
+        self.breakpoints.clear();
+        self.break_requested = false;
+        self.recorder.clear();
+    }
+
+    /// Takes the step points recorded since the last breakpoint was hit.
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.recorder.take_trace()
+    }
+
+    /// Whether a step point at the given location should be recorded.
+    ///
+    /// `breakpoint_hit` is whether a breakpoint matches the location.
+    fn should_stop(&self, granularity: StepGranularity, breakpoint_hit: bool) -> bool {
+        if self.recorder.is_truncated() {
+            return false;
+        }
+
+        if breakpoint_hit {
+            return true;
+        }
+
+        (self.recorder.is_recording() || self.break_requested) && self.granularity == granularity
+    }
+
+    /// Checks whether a step point before the instruction at `offset` should be recorded.
+    ///
+    /// `method_name` is only needed when there are method breakpoints.
+    /// See `has_method_breakpoints`.
+    pub(crate) fn check_instruction(
+        &self,
+        method_name: Option<&str>,
+        offset: usize,
+    ) -> Option<bool> {
+        let hit = method_name.is_some_and(|name| {
+            self.breakpoints
+                .iter()
+                .any(|b| b.matches_method(name, offset))
+        });
+        self.should_stop(StepGranularity::Instruction, hit)
+            .then_some(hit)
+    }
+
+    /// Checks whether a step point at a `debugline` instruction should be recorded.
+    pub(crate) fn check_line(&self, file_name: Option<&str>, line: u32) -> Option<bool> {
+        let hit = self
+            .breakpoints
+            .iter()
+            .any(|b| b.matches_line(file_name, line));
+        self.should_stop(StepGranularity::Line, hit).then_some(hit)
+    }
+
+    /// Records a step point, starting a new recording if needed.
+    pub(crate) fn record(&mut self, stop: StopPoint) {
+        self.break_requested = false;
+        self.recorder.record(stop);
+    }
+}
+
+/// The debugging information reported by an activation's bytecode so far.
+#[derive(Default)]
+pub(crate) struct ActivationDebugInfo<'gc> {
+    /// The name of the executing method, if it was needed to check breakpoints.
+    pub method_name: Option<String>,
+
+    /// The source file reported by the last `debugfile` instruction.
+    pub file: Option<AvmAtom<'gc>>,
+
+    /// The source line reported by the last `debugline` instruction.
+    pub line: Option<u32>,
+
+    /// Names given to local registers by `debug` instructions.
+    pub register_names: Vec<(u8, AvmAtom<'gc>)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::MAX_TRACE_LENGTH;
+
+    fn stop(depth: usize, hit_breakpoint: bool) -> StopPoint {
+        StopPoint {
+            method: format!("method{depth}"),
+            offset: 0,
+            instruction: None,
+            file: None,
+            line: None,
+            depth,
+            hit_breakpoint,
+            call_stack: vec![],
+            locals: vec![],
+            scope_stack: vec![],
+            operand_stack: vec![],
+        }
+    }
+
+    #[test]
+    fn recording_stops_when_truncated() {
+        let mut debugger = Debugger::default();
+        debugger.add_breakpoint(Breakpoint::Line {
+            file: "Main.as".to_string(),
+            line: 1,
+        });
+        for _ in 0..=MAX_TRACE_LENGTH {
+            debugger.record(stop(1, false));
+        }
+        assert!(!debugger.is_active());
+        assert_eq!(debugger.check_line(Some("Main.as"), 1), None);
+
+        let trace = debugger.take_trace().unwrap();
+        assert!(trace.is_truncated());
+        assert_eq!(trace.len(), MAX_TRACE_LENGTH);
+        assert!(debugger.is_active());
+    }
+}
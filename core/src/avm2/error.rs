@@ -307,6 +307,24 @@ pub fn make_error_2008<'gc>(activation: &mut Activation<'_, 'gc>, param_name: &s
     }
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_2122<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    property: &str,
+    content_url: &str,
+) -> Error<'gc> {
+    let message = format!(
+        "Error #2122: Security sandbox violation: {property}: {} cannot access {content_url}. A policy file is required, but the checkPolicyFile flag was not set when this media was loaded.",
+        activation.context.swf.url()
+    );
+    let err = security_error(activation, &message, 2122);
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
 #[inline(never)]
 #[cold]
 pub fn make_error_2126<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
@@ -445,6 +463,17 @@ pub fn eof_error<'gc>(
     error_constructor(activation, class, message, code)
 }
 
+#[inline(never)]
+#[cold]
+pub fn script_timeout_error<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    message: &str,
+    code: u32,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let class = activation.avm2().classes().scripttimeouterror;
+    error_constructor(activation, class, message, code)
+}
+
 #[inline(never)]
 #[cold]
 pub fn uri_error<'gc>(
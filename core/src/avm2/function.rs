@@ -7,6 +7,7 @@ use crate::avm2::scope::ScopeChain;
 use crate::avm2::traits::TraitKind;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::profiler::ProfileCategory;
 use crate::string::WString;
 use gc_arena::{Collect, Gc};
 use std::fmt;
@@ -155,6 +156,9 @@ impl<'gc> Executable<'gc> {
                     .context
                     .avm2
                     .push_call(activation.context.gc_context, self);
+                activation
+                    .context
+                    .profile_enter(ProfileCategory::Native, || self.full_name());
                 method(&mut activation, receiver, &arguments)
             }
             Executable::Action(bm) => {
@@ -191,6 +195,9 @@ impl<'gc> Executable<'gc> {
                     .context
                     .avm2
                     .push_call(activation.context.gc_context, self);
+                activation
+                    .context
+                    .profile_enter(ProfileCategory::Avm2, || self.full_name());
                 activation.run_actions(bm.method)
             }
         };
@@ -198,6 +205,7 @@ impl<'gc> Executable<'gc> {
             .context
             .avm2
             .pop_call(activation.context.gc_context);
+        activation.context.profile_exit();
         ret
     }
 
@@ -212,6 +220,22 @@ impl<'gc> Executable<'gc> {
         }
     }
 
+    /// The scope chain this executable closes over.
+    pub fn scope(&self) -> ScopeChain<'gc> {
+        match self {
+            Executable::Native(NativeExecutable { scope, .. }) => *scope,
+            Executable::Action(BytecodeExecutable { scope, .. }) => *scope,
+        }
+    }
+
+    /// The receiver this executable is bound to, if any.
+    pub fn bound_receiver(&self) -> Option<Object<'gc>> {
+        match self {
+            Executable::Native(NativeExecutable { bound_receiver, .. }) => *bound_receiver,
+            Executable::Action(BytecodeExecutable { receiver, .. }) => *receiver,
+        }
+    }
+
     pub fn as_method(&self) -> Method<'gc> {
         match self {
             Executable::Native(nm) => Method::Native(nm.method),
@@ -223,6 +247,13 @@ impl<'gc> Executable<'gc> {
         display_function(output, &self.as_method(), self.bound_superclass());
     }
 
+    /// The full name of this executable, as displayed in stack traces.
+    pub fn full_name(&self) -> String {
+        let mut output = WString::new();
+        self.write_full_name(&mut output);
+        output.to_string()
+    }
+
     pub fn num_parameters(&self) -> usize {
         match self {
             Executable::Native(NativeExecutable { method, .. }) => method.signature.len(),
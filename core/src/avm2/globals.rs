@@ -112,6 +112,7 @@ pub struct SystemClasses<'gc> {
     pub qname: ClassObject<'gc>,
     pub mouseevent: ClassObject<'gc>,
     pub progressevent: ClassObject<'gc>,
+    pub sampledataevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
@@ -134,6 +135,7 @@ pub struct SystemClasses<'gc> {
     pub verifyerror: ClassObject<'gc>,
     pub ioerror: ClassObject<'gc>,
     pub eoferror: ClassObject<'gc>,
+    pub scripttimeouterror: ClassObject<'gc>,
     pub urierror: ClassObject<'gc>,
     pub securityerror: ClassObject<'gc>,
     pub error: ClassObject<'gc>,
@@ -169,6 +171,14 @@ pub struct SystemClasses<'gc> {
     pub contextmenuevent: ClassObject<'gc>,
     pub font: ClassObject<'gc>,
     pub textline: ClassObject<'gc>,
+    pub worker: ClassObject<'gc>,
+    pub messagechannel: ClassObject<'gc>,
+    pub mutex: ClassObject<'gc>,
+    pub condition: ClassObject<'gc>,
+    pub sample: ClassObject<'gc>,
+    pub newobjectsample: ClassObject<'gc>,
+    pub deleteobjectsample: ClassObject<'gc>,
+    pub stackframe: ClassObject<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -239,6 +249,7 @@ impl<'gc> SystemClasses<'gc> {
             qname: object,
             mouseevent: object,
             progressevent: object,
+            sampledataevent: object,
             httpstatusevent: object,
             textevent: object,
             errorevent: object,
@@ -261,6 +272,7 @@ impl<'gc> SystemClasses<'gc> {
             verifyerror: object,
             ioerror: object,
             eoferror: object,
+            scripttimeouterror: object,
             urierror: object,
             securityerror: object,
             error: object,
@@ -296,6 +308,14 @@ impl<'gc> SystemClasses<'gc> {
             contextmenuevent: object,
             font: object,
             textline: object,
+            worker: object,
+            messagechannel: object,
+            mutex: object,
+            condition: object,
+            sample: object,
+            newobjectsample: object,
+            deleteobjectsample: object,
+            stackframe: object,
         }
     }
 }
@@ -777,12 +797,14 @@ fn load_playerglobal<'gc>(
             ),
             ("flash.errors", "IOError", ioerror),
             ("flash.errors", "EOFError", eoferror),
+            ("flash.errors", "ScriptTimeoutError", scripttimeouterror),
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
             ("flash.events", "ErrorEvent", errorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
+            ("flash.events", "SampleDataEvent", sampledataevent),
             ("flash.events", "HTTPStatusEvent", httpstatusevent),
             ("flash.events", "SecurityErrorEvent", securityerrorevent),
             ("flash.events", "IOErrorEvent", ioerrorevent),
@@ -803,6 +825,14 @@ fn load_playerglobal<'gc>(
             ("flash.net", "URLVariables", urlvariables),
             ("flash.utils", "ByteArray", bytearray),
             ("flash.system", "ApplicationDomain", application_domain),
+            ("flash.system", "Worker", worker),
+            ("flash.system", "MessageChannel", messagechannel),
+            ("flash.concurrent", "Mutex", mutex),
+            ("flash.concurrent", "Condition", condition),
+            ("flash.sampler", "Sample", sample),
+            ("flash.sampler", "NewObjectSample", newobjectsample),
+            ("flash.sampler", "DeleteObjectSample", deleteobjectsample),
+            ("flash.sampler", "StackFrame", stackframe),
             ("flash.text", "Font", font),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
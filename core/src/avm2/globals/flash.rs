@@ -1,5 +1,6 @@
 //! `flash` namespace
 
+pub mod concurrent;
 pub mod crypto;
 pub mod display;
 #[allow(non_snake_case)]
@@ -7,8 +8,10 @@ pub mod display3D;
 pub mod events;
 pub mod external;
 pub mod geom;
+pub mod globalization;
 pub mod media;
 pub mod net;
+pub mod sampler;
 pub mod system;
 pub mod text;
 pub mod ui;
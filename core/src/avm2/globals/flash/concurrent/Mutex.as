@@ -1,9 +1,12 @@
 package flash.concurrent {
+    [Ruffle(InstanceAllocator)]
     public final class Mutex {
-        public static const isSupported: Boolean = false;
-        
-        public function Mutex() {
-            throw new Error("Error #1520: Mutex cannot be initialized.", 1520);
-        }
+        public static const isSupported: Boolean = true;
+
+        public function Mutex() {}
+
+        public native function lock():void;
+        public native function tryLock():Boolean;
+        public native function unlock():void;
     }
-}
\ No newline at end of file
+}
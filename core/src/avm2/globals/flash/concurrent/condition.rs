@@ -0,0 +1,147 @@
+//! `flash.concurrent.Condition` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::argument_error;
+use crate::avm2::globals::flash::concurrent::mutex::{make_error_3747, wait_for_workers};
+pub use crate::avm2::object::condition_allocator;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::worker::{Condition, Worker};
+use crate::avm2::Error;
+use std::time::Duration;
+
+/// Implements `flash.concurrent.Condition`'s native instance constructor.
+///
+/// This is only used for conditions passed from another worker, which already have a mutex.
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Condition.init`, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mutex = args.get_object(activation, 0, "mutex")?;
+
+    if let (Some(condition_object), Some(mutex)) =
+        (this.as_condition_object(), mutex.as_mutex_object())
+    {
+        let condition = Condition::new(activation.context.gc_context, mutex.mutex());
+        condition_object.init_condition(activation.context.gc_context, condition);
+        condition.set_object(&mut activation.context, this);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Condition.mutex`
+pub fn get_mutex<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(condition) = this.as_condition_object() {
+        return Ok(condition.condition().mutex().object(activation)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Throws if the current worker hasn't locked the condition's mutex.
+fn check_owned<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    if let Some(condition) = this.as_condition_object() {
+        let worker = Worker::current(&mut activation.context);
+        if !condition.condition().mutex().is_owned_by(worker) {
+            return Err(make_error_3747(activation));
+        }
+    }
+
+    Ok(())
+}
+
+/// Implements `Condition.notify`
+pub fn notify<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_owned(activation, this)?;
+
+    if let Some(condition) = this.as_condition_object() {
+        condition.condition().notify(activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Condition.notifyAll`
+pub fn notify_all<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_owned(activation, this)?;
+
+    if let Some(condition) = this.as_condition_object() {
+        condition
+            .condition()
+            .notify_all(activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Condition.wait`
+///
+/// This releases the mutex while waiting, and locks it again before returning,
+/// even if the wait timed out.
+pub fn wait<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_owned(activation, this)?;
+    let timeout = args.get_f64(activation, 0)?;
+
+    if timeout < 0.0 && timeout != -1.0 {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #1508: The value specified for argument timeout is invalid.",
+            1508,
+        )?));
+    }
+
+    let Some(condition) = this.as_condition_object() else {
+        return Ok(false.into());
+    };
+    let condition = condition.condition();
+    let mutex = condition.mutex();
+    let worker = Worker::current(&mut activation.context);
+    let Some(count) = mutex.release(activation.context.gc_context, worker) else {
+        return Err(make_error_3747(activation));
+    };
+
+    condition.add_waiting(activation.context.gc_context, worker);
+    let timeout = Duration::try_from_secs_f64(timeout / 1000.0).ok();
+    let waited = wait_for_workers(activation, timeout, |_| !condition.is_waiting(worker));
+    let notified = !condition.remove_waiting(activation.context.gc_context, worker);
+
+    wait_for_workers(activation, None, |context| {
+        mutex.reacquire(context.gc_context, worker, count)
+    })?;
+    waited?;
+
+    Ok(notified.into())
+}
@@ -0,0 +1,95 @@
+//! `flash.concurrent.Mutex` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::{illegal_operation_error, script_timeout_error};
+pub use crate::avm2::object::mutex_allocator;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::worker::{WaitResult, Worker};
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use std::time::Duration;
+
+/// Implements `Mutex.lock`
+pub fn lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mutex) = this.as_mutex_object() {
+        let mutex = mutex.mutex();
+        let worker = Worker::current(&mut activation.context);
+        wait_for_workers(activation, None, |context| {
+            mutex.try_lock(context.gc_context, worker)
+        })?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mutex.tryLock`
+pub fn try_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mutex) = this.as_mutex_object() {
+        let worker = Worker::current(&mut activation.context);
+        return Ok(mutex
+            .mutex()
+            .try_lock(activation.context.gc_context, worker)
+            .into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `Mutex.unlock`
+pub fn unlock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mutex) = this.as_mutex_object() {
+        let worker = Worker::current(&mut activation.context);
+        if !mutex.mutex().unlock(activation.context.gc_context, worker) {
+            return Err(make_error_3747(activation));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// The error thrown when a worker uses a mutex that it hasn't locked.
+pub fn make_error_3747<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    let err = illegal_operation_error(
+        activation,
+        "Error #3747: The mutex is not owned by the current worker.",
+        3747,
+    );
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+/// Blocks the current worker until `ready` returns `true`, returning `false` if
+/// `timeout` passes first.
+///
+/// This throws a `ScriptTimeoutError` if no worker that can run during this
+/// frame unblocks it in time.
+pub fn wait_for_workers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    timeout: Option<Duration>,
+    ready: impl FnMut(&mut UpdateContext<'_, 'gc>) -> bool,
+) -> Result<bool, Error<'gc>> {
+    match Worker::wait_until(&mut activation.context, timeout, ready) {
+        WaitResult::Ready => Ok(true),
+        WaitResult::TimedOut => Ok(false),
+        WaitResult::Stuck => Err(Error::AvmError(script_timeout_error(
+            activation,
+            "Error #1502: A script has executed for longer than the default timeout period of 15 seconds.",
+            1502,
+        )?)),
+    }
+}
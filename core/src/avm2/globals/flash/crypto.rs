@@ -30,7 +30,7 @@ pub fn generate_random_bytes<'gc>(
 
     let mut rng = OsRng {};
 
-    rng.fill_bytes(ba_write.bytes_mut());
+    rng.fill_bytes(&mut ba_write.bytes_mut());
 
     Ok(ba.into())
 }
@@ -9,7 +9,7 @@ use crate::avm2::Error;
 use ruffle_render::bitmap::PixelSnapping;
 use ruffle_wstr::WStr;
 
-use crate::avm2::error::make_error_2008;
+use crate::avm2::error::{make_error_2008, make_error_2122};
 use crate::avm2::parameters::ParametersExt;
 use crate::bitmap::bitmap_data::BitmapDataWrapper;
 use crate::character::Character;
@@ -101,11 +101,20 @@ pub fn init<'gc>(
 
 /// Implements `Bitmap.bitmapData`'s getter.
 pub fn get_bitmap_data<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap) = this.as_display_object().and_then(|dobj| dobj.as_bitmap()) {
+        // The bitmap may still be reached through the display list of its `Loader`.
+        if bitmap.bitmap_data_wrapper().is_cross_domain() {
+            return Err(make_error_2122(
+                activation,
+                "Bitmap.bitmapData",
+                "an image from another domain",
+            ));
+        }
+
         let mut value = bitmap.bitmap_data_wrapper().object2();
 
         // AS3 expects an unset BitmapData to be null, not 'undefined'
@@ -2,7 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::ByteArrayStorage;
-use crate::avm2::error::{argument_error, make_error_2008, range_error};
+use crate::avm2::error::{argument_error, make_error_2008, range_error, security_error};
 use crate::avm2::filters::FilterAvm2Ext;
 pub use crate::avm2::object::bitmap_data_allocator;
 use crate::avm2::object::{BitmapDataObject, ByteArrayObject, Object, TObject, VectorObject};
@@ -18,7 +18,7 @@ use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
 use crate::bitmap::{is_size_valid, operations};
 use crate::character::Character;
 use crate::display_object::Bitmap;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::round_to_even;
 use crate::swf::BlendMode;
 use gc_arena::GcCell;
@@ -843,6 +843,44 @@ pub fn hit_test<'gc>(
     Ok(false.into())
 }
 
+/// Fail with a `SecurityError` if `source` contains an image whose pixels this movie
+/// may not access, because it was loaded from another domain without a policy file
+/// granting access.
+fn check_source_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    source: &IBitmapDrawable<'gc>,
+    method: &str,
+) -> Result<(), Error<'gc>> {
+    fn find_cross_domain_bitmap(object: DisplayObject<'_>) -> Option<String> {
+        if let Some(bitmap) = object.as_bitmap() {
+            if bitmap.bitmap_data_wrapper().is_cross_domain() {
+                return Some(object.movie().url().to_string());
+            }
+        }
+        object
+            .as_container()?
+            .iter_render_list()
+            .find_map(find_cross_domain_bitmap)
+    }
+
+    let content = match source {
+        IBitmapDrawable::BitmapData(bitmap_data) => bitmap_data
+            .is_cross_domain()
+            .then(|| "an image from another domain".to_string()),
+        IBitmapDrawable::DisplayObject(object) => find_cross_domain_bitmap(*object),
+    };
+
+    if let Some(content) = content {
+        let message = format!(
+            "Error #2123: Security sandbox violation: BitmapData.{method}: {} cannot access {content}. No policy files granted access.",
+            activation.context.swf.url()
+        );
+        return Err(Error::AvmError(security_error(activation, &message, 2123)?));
+    }
+
+    Ok(())
+}
+
 /// Implements `BitmapData.draw`
 pub fn draw<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -895,6 +933,7 @@ pub fn draw<'gc>(
         } else {
             return Err(format!("BitmapData.draw: unexpected source {source:?}").into());
         };
+        check_source_access(activation, &source, "draw")?;
 
         // If the bitmapdata is invalid, it's fine to return early, since the pixels
         // are inaccessible
@@ -974,6 +1013,7 @@ pub fn draw_with_quality<'gc>(
         } else {
             return Err(format!("BitmapData.drawWithQuality: unexpected source {source:?}").into());
         };
+        check_source_access(activation, &source, "drawWithQuality")?;
 
         // Unknown quality defaults to stage's quality
         let quality = if let Some(quality) = args.try_get_string(activation, 6)? {
@@ -15,7 +15,6 @@ use crate::prelude::*;
 use crate::string::AvmString;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::Instantiator;
-use crate::{avm2_stub_getter, avm2_stub_setter};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::filters::Filter;
 use std::str::FromStr;
@@ -152,7 +151,6 @@ pub fn get_scale9grid<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scale9Grid");
     if let Some(dobj) = this.as_display_object() {
         let rect = dobj.scaling_grid();
         return if rect.is_valid() {
@@ -172,7 +170,6 @@ pub fn set_scale9grid<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scale9Grid");
     if let Some(dobj) = this.as_display_object() {
         let rect = match args.try_get_object(activation, 0) {
             None => Rectangle::default(),
@@ -387,93 +384,146 @@ pub fn set_y<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `z`'s getter.
 pub fn get_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "z");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `z`'s setter.
 pub fn set_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "z");
+    if let Some(dobj) = this.as_display_object() {
+        let z = args.get_f64(activation, 0)?;
+        dobj.set_z(activation.context.gc_context, z);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s getter.
 pub fn get_rotation_x<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationX");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.components3d().rotation[0].to_degrees().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s setter.
 pub fn set_rotation_x<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationX");
+    if let Some(dobj) = this.as_display_object() {
+        let degrees = args.get_f64(activation, 0)?;
+        let mut components = dobj.components3d();
+        components.rotation[0] = degrees.to_radians();
+        dobj.set_components3d(activation.context.gc_context, &components);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s getter.
 pub fn get_rotation_y<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationY");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.components3d().rotation[1].to_degrees().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s setter.
 pub fn set_rotation_y<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationY");
+    if let Some(dobj) = this.as_display_object() {
+        let degrees = args.get_f64(activation, 0)?;
+        let mut components = dobj.components3d();
+        components.rotation[1] = degrees.to_radians();
+        dobj.set_components3d(activation.context.gc_context, &components);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationZ`'s getter.
 pub fn get_rotation_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.components3d().rotation[2].to_degrees().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationZ`'s setter.
 pub fn set_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationZ");
+    if let Some(dobj) = this.as_display_object() {
+        let degrees = args.get_f64(activation, 0)?;
+        let mut components = dobj.components3d();
+        components.rotation[2] = degrees.to_radians();
+        dobj.set_components3d(activation.context.gc_context, &components);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `scaleZ`'s getter.
 pub fn get_scale_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scaleZ");
-    Ok(1.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.components3d().scale[2].into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `scaleZ`'s setter.
 pub fn set_scale_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scaleZ");
+    if let Some(dobj) = this.as_display_object() {
+        let scale_z = args.get_f64(activation, 0)?;
+        let mut components = dobj.components3d();
+        components.scale[2] = scale_z;
+        dobj.set_components3d(activation.context.gc_context, &components);
+    }
+
     Ok(Value::Undefined)
 }
 
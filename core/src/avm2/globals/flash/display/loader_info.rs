@@ -2,7 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::Endian;
-use crate::avm2::error::error;
+use crate::avm2::error::{error, make_error_2122};
 use crate::avm2::object::{DomainObject, LoaderStream, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::{AvmString, Error};
@@ -138,7 +138,7 @@ pub fn get_bytes_loaded<'gc>(
 
 /// `content` getter
 pub fn get_content<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -147,7 +147,14 @@ pub fn get_content<'gc>(
         .and_then(|o| o.as_loader_stream())
     {
         match &*loader_stream {
-            LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
+            LoaderStream::Swf(movie, root) | LoaderStream::NotYetLoaded(movie, Some(root), _) => {
+                // Images from other domains can only be reached if a policy file allowed it.
+                if let Some(bitmap) = root.as_bitmap() {
+                    if bitmap.bitmap_data_wrapper().is_cross_domain() {
+                        let url = movie.url().to_string();
+                        return Err(make_error_2122(activation, "LoaderInfo.content", &url));
+                    }
+                }
                 return Ok(root.object2());
             }
             _ => {
@@ -398,7 +405,8 @@ pub fn get_bytes<'gc>(
         let ba_class = activation.context.avm2.classes().bytearray;
         let ba = ba_class.construct(activation, &[])?;
 
-        if root.data().is_empty() {
+        let root_data = root.data();
+        if root_data.is_empty() {
             return Ok(ba.into());
         }
 
@@ -418,7 +426,7 @@ pub fn get_bytes<'gc>(
         let correct_header_length = ba_write.len() - 2;
         ba_write.set_position(correct_header_length);
         ba_write
-            .write_bytes(root.data())
+            .write_bytes(&root_data)
             .map_err(|e| e.to_avm(activation))?;
 
         // `swf` wrote the wrong length (since we wrote the data
@@ -426,7 +434,7 @@ pub fn get_bytes<'gc>(
         ba_write.set_position(4);
         ba_write.set_endian(Endian::Little);
         ba_write
-            .write_unsigned_int((root.data().len() + correct_header_length) as u32)
+            .write_unsigned_int((root_data.len() + correct_header_length) as u32)
             .map_err(|e| e.to_avm(activation))?;
 
         // Finally, reset the array to the correct state.
@@ -21,7 +21,7 @@ pub fn init<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let bytecode = args.get_object(activation, 0, "bytecode")?;
     let bytecode = bytecode.as_bytearray().unwrap();
-    let shader = parse_shader(bytecode.bytes()).expect("Failed to parse PixelBender");
+    let shader = parse_shader(&bytecode.bytes()).expect("Failed to parse PixelBender");
 
     for meta in &shader.metadata {
         let name = AvmString::new_utf8(activation.context.gc_context, &meta.key);
@@ -1,4 +1,3 @@
-use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::object::TextureObject;
 use crate::avm2::Activation;
 use crate::avm2::Error;
@@ -21,7 +20,8 @@ pub fn do_compressed_upload<'gc>(
     is_cube: bool,
 ) -> Result<(), Error<'gc>> {
     let bytes = data.as_bytearray().unwrap();
-    let raw_atf = &ByteArrayStorage::bytes(&bytes)[byte_array_offset..];
+    let bytes = bytes.bytes();
+    let raw_atf = &bytes[byte_array_offset..];
 
     let atf_texture = ATFTexture::from_bytes(raw_atf).expect("Failed to parse ATF texture");
 
@@ -1,39 +1,39 @@
 package flash.geom {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
     import __ruffle__.stub_method;
-    import __ruffle__.stub_setter;
     import flash.geom.Matrix3D;
     import flash.geom.Point;
 
     public class PerspectiveProjection {
-        // Getters are stubbed with what seem to be Flash's default values
+        // Flash derives the focal length from the field of view and the width of the stage.
+        // A standalone projection behaves as if it was on a 500x500 stage.
+        internal var _fieldOfView:Number = 55;
+        internal var _focalLength:Number = 480.25;
+        internal var _projectionCenter:Point = new Point(250, 250);
+
         public function PerspectiveProjection() {
-            stub_constructor("flash.geom.PerspectiveProjection");
         }
 
         public function get fieldOfView():Number {
-            stub_getter("flash.geom.PerspectiveProjection", "fieldOfView");
-            return 55;
+            return this._fieldOfView;
         }
         public function set fieldOfView(value:Number) {
-            stub_setter("flash.geom.PerspectiveProjection", "fieldOfView");
+            this._fieldOfView = value;
+            this._focalLength = 250 / Math.tan(value * Math.PI / 360);
         }
 
         public function get focalLength():Number {
-            stub_getter("flash.geom.PerspectiveProjection", "focalLength");
-            return 480.25;
+            return this._focalLength;
         }
         public function set focalLength(value:Number) {
-            stub_setter("flash.geom.PerspectiveProjection", "focalLength");
+            this._focalLength = value;
+            this._fieldOfView = Math.atan(250 / value) * 360 / Math.PI;
         }
 
         public function get projectionCenter():Point {
-            stub_getter("flash.geom.PerspectiveProjection", "projectionCenter");
-            return new Point(250, 250);
+            return this._projectionCenter.clone();
         }
         public function set projectionCenter(value:Point) {
-            stub_setter("flash.geom.PerspectiveProjection", "projectionCenter");
+            this._projectionCenter = value.clone();
         }
 
         public function toMatrix3D():Matrix3D {
@@ -41,4 +41,4 @@ package flash.geom {
             return new Matrix3D();
         }
     }
-}
\ No newline at end of file
+}
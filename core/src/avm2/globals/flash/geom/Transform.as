@@ -2,9 +2,8 @@ package flash.geom {
 	import flash.display.DisplayObject;
 	import flash.geom.Matrix3D;
 	import flash.geom.PerspectiveProjection;
-	import __ruffle__.stub_getter;
+	import flash.geom.Point;
 	import __ruffle__.stub_method;
-	import __ruffle__.stub_setter;
 
 	public class Transform {
 		internal var _displayObject:DisplayObject;
@@ -24,23 +23,44 @@ package flash.geom {
 		public native function get pixelBounds():Rectangle;
 
 		public function get matrix3D():Matrix3D {
-			stub_getter("flash.geom.Transform", "matrix3D");
-			return new Matrix3D();
+			var rawData:Vector.<Number> = this.getRawMatrix3D();
+			if (rawData == null) {
+				return null;
+			}
+			return new Matrix3D(rawData);
 		}
 
 		public function set matrix3D(m:Matrix3D):void {
-			stub_setter("flash.geom.Transform", "matrix3D");
+			this.setRawMatrix3D(m == null ? null : m.rawData);
 		}
 
+		private native function getRawMatrix3D():Vector.<Number>;
+		private native function setRawMatrix3D(rawData:Vector.<Number>):void;
+
 		public function get perspectiveProjection():PerspectiveProjection {
-			stub_getter("flash.geom.Transform", "perspectiveProjection");
-			return new PerspectiveProjection();
+			// [fieldOfView, focalLength, projectionCenter.x, projectionCenter.y]
+			var values:Array = this.getPerspectiveValues();
+			if (values == null) {
+				return null;
+			}
+			var projection:PerspectiveProjection = new PerspectiveProjection();
+			projection._fieldOfView = values[0];
+			projection._focalLength = values[1];
+			projection._projectionCenter = new Point(values[2], values[3]);
+			return projection;
 		}
 
-		public function set perspectiveProjection(val: PerspectiveProjection):void {
-			stub_setter("flash.geom.Transform", "perspectiveProjection");
+		public function set perspectiveProjection(val:PerspectiveProjection):void {
+			if (val == null) {
+				this.setPerspectiveValues(null);
+			} else {
+				this.setPerspectiveValues([val._fieldOfView, val._focalLength, val._projectionCenter.x, val._projectionCenter.y]);
+			}
 		}
 
+		private native function getPerspectiveValues():Array;
+		private native function setPerspectiveValues(values:Array):void;
+
 		public function getRelativeMatrix3D(relativeTo:DisplayObject):Matrix3D {
 			stub_method("flash.geom.Transform", "getRelativeMatrix3D");
 			return new Matrix3D();
@@ -1,9 +1,13 @@
+use crate::avm2::error::type_error;
+use crate::avm2::object::{ArrayObject, VectorObject};
 use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::avm2_stub_getter;
 use crate::display_object::TDisplayObject;
 use crate::prelude::{DisplayObject, Matrix, Twips};
+use ruffle_render::matrix3d::{Matrix3D, PerspectiveProjection};
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
 
@@ -75,6 +79,8 @@ pub fn set_matrix<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let matrix = object_to_matrix(args.get_object(activation, 0, "value")?, activation)?;
     let dobj = get_display_object(this, activation)?;
+    // Setting a 2D matrix turns the object back into a 2D object.
+    dobj.set_matrix3d(activation.context.gc_context, None);
     dobj.set_matrix(activation.context.gc_context, matrix);
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
@@ -84,6 +90,114 @@ pub fn set_matrix<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_raw_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(matrix3d) = get_display_object(this, activation)?.matrix3d() else {
+        return Ok(Value::Null);
+    };
+    let values = matrix3d.raw_data.iter().map(|v| (*v).into()).collect();
+    let number = activation.avm2().classes().number;
+    let storage = VectorStorage::from_values(values, false, Some(number));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+pub fn set_raw_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let matrix3d = match args.try_get_object(activation, 0) {
+        Some(raw_data) => {
+            let Some(raw_data) = raw_data
+                .as_vector_storage()
+                .map(|storage| storage.iter().collect::<Vec<_>>())
+            else {
+                return Err(Error::AvmError(type_error(
+                    activation,
+                    &format!(
+                        "Error #1034: Type Coercion failed: cannot convert {raw_data:?} to __AS3__.vec.Vector.<Number>."
+                    ),
+                    1034,
+                )?));
+            };
+            let mut matrix3d = Matrix3D::IDENTITY;
+            for (dest, value) in matrix3d.raw_data.iter_mut().zip(raw_data) {
+                *dest = value.coerce_to_number(activation)?;
+            }
+            Some(matrix3d)
+        }
+        None => None,
+    };
+    dobj.set_matrix3d(activation.context.gc_context, matrix3d);
+    Ok(Value::Undefined)
+}
+
+pub fn get_perspective_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = match dobj.perspective_projection() {
+        Some(projection) => projection,
+        // The root always reports a projection, falling back to the one of the stage.
+        None if dobj.is_root() => activation.context.stage.default_perspective_projection(),
+        None => return Ok(Value::Null),
+    };
+    let storage = [
+        projection.field_of_view,
+        projection.focal_length,
+        projection.center.0,
+        projection.center.1,
+    ]
+    .into_iter()
+    .map(Value::from)
+    .collect();
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+pub fn set_perspective_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = match args.try_get_object(activation, 0) {
+        Some(values) => {
+            let Some(values) = values
+                .as_array_storage()
+                .map(|storage| storage.iter().collect::<Vec<_>>())
+            else {
+                return Err(Error::AvmError(type_error(
+                    activation,
+                    &format!(
+                        "Error #1034: Type Coercion failed: cannot convert {values:?} to Array."
+                    ),
+                    1034,
+                )?));
+            };
+            let mut numbers = [0.0; 4];
+            for (dest, value) in numbers.iter_mut().zip(values) {
+                *dest = value
+                    .unwrap_or(Value::Undefined)
+                    .coerce_to_number(activation)?;
+            }
+            Some(PerspectiveProjection {
+                field_of_view: numbers[0],
+                focal_length: numbers[1],
+                center: (numbers[2], numbers[3]),
+            })
+        }
+        None => None,
+    };
+    dobj.set_perspective_projection(activation.context.gc_context, projection);
+    Ok(Value::Undefined)
+}
+
 pub fn get_concatenated_matrix<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -0,0 +1,212 @@
+//! `flash.globalization` namespace
+//!
+//! The formatter classes keep their settings in `flash.globalization` internal
+//! fields, which the native methods read and update.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Error, Multiname};
+use crate::globalization::{LastOperationStatus, LocaleData, NumberFormat, LOCALES};
+use crate::string::AvmString;
+
+pub mod collator;
+pub mod currency_formatter;
+pub mod date_time_formatter;
+pub mod locale_id;
+pub mod number_formatter;
+
+fn get_field<'gc>(
+    this: Object<'gc>,
+    name: &'static str,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.get_property(
+        &Multiname::new(activation.avm2().flash_globalization_internal, name),
+        activation,
+    )
+}
+
+fn get_string_field<'gc>(
+    this: Object<'gc>,
+    name: &'static str,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<String, Error<'gc>> {
+    Ok(get_field(this, name, activation)?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned())
+}
+
+fn set_field<'gc>(
+    this: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<(), Error<'gc>> {
+    this.set_property(
+        &Multiname::new(activation.avm2().flash_globalization_internal, name),
+        value,
+        activation,
+    )
+}
+
+fn set_string_field<'gc>(
+    this: Object<'gc>,
+    name: &'static str,
+    value: &str,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<(), Error<'gc>> {
+    let value = AvmString::new_utf8(activation.context.gc_context, value);
+    set_field(this, name, value.into(), activation)
+}
+
+/// Set `lastOperationStatus`.
+fn set_status<'gc>(
+    this: Object<'gc>,
+    status: LastOperationStatus,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<(), Error<'gc>> {
+    set_field(
+        this,
+        "_lastOperationStatus",
+        status.as_str().into(),
+        activation,
+    )
+}
+
+/// Resolve the locale requested by a constructor, storing the requested and
+/// actual locale names and the status.
+fn init_locale<'gc>(
+    this: Object<'gc>,
+    requested: AvmString<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let default_locale = activation.context.ui.language().to_string();
+    let (locale, status) =
+        crate::globalization::resolve_locale(&requested.to_utf8_lossy(), &default_locale);
+
+    set_field(this, "_requestedLocaleIDName", requested.into(), activation)?;
+    set_string_field(this, "_actualLocaleIDName", locale.name, activation)?;
+    set_status(this, status, activation)?;
+    Ok(locale)
+}
+
+/// Get the data of the locale that an object was created with.
+fn locale_data<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let name = get_string_field(this, "_actualLocaleIDName", activation)?;
+    Ok(LOCALES
+        .iter()
+        .find(|locale| locale.name == name)
+        .unwrap_or(&LOCALES[0]))
+}
+
+/// Store the settings of a number format in the fields shared by
+/// `NumberFormatter` and `CurrencyFormatter`.
+///
+/// `negativeNumberFormat` only exists on `NumberFormatter`, and is handled there.
+fn set_number_format<'gc>(
+    this: Object<'gc>,
+    format: &NumberFormat,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<(), Error<'gc>> {
+    set_string_field(
+        this,
+        "_decimalSeparator",
+        &format.decimal_separator,
+        activation,
+    )?;
+    set_string_field(
+        this,
+        "_groupingSeparator",
+        &format.grouping_separator,
+        activation,
+    )?;
+    set_string_field(
+        this,
+        "_groupingPattern",
+        &format.grouping_pattern,
+        activation,
+    )?;
+    set_string_field(this, "_negativeSymbol", &format.negative_symbol, activation)?;
+    set_field(this, "_digitsType", format.digits_type.into(), activation)?;
+    set_field(
+        this,
+        "_fractionalDigits",
+        format.fractional_digits.into(),
+        activation,
+    )?;
+    set_field(this, "_leadingZero", format.leading_zero.into(), activation)?;
+    set_field(
+        this,
+        "_trailingZeros",
+        format.trailing_zeros.into(),
+        activation,
+    )?;
+    set_field(this, "_useGrouping", format.use_grouping.into(), activation)
+}
+
+/// Read the settings of a number format from the fields shared by
+/// `NumberFormatter` and `CurrencyFormatter`.
+fn number_format<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<NumberFormat, Error<'gc>> {
+    Ok(NumberFormat {
+        decimal_separator: get_string_field(this, "_decimalSeparator", activation)?,
+        grouping_separator: get_string_field(this, "_groupingSeparator", activation)?,
+        grouping_pattern: get_string_field(this, "_groupingPattern", activation)?,
+        negative_symbol: get_string_field(this, "_negativeSymbol", activation)?,
+        digits_type: get_field(this, "_digitsType", activation)?.coerce_to_u32(activation)?,
+        fractional_digits: get_field(this, "_fractionalDigits", activation)?
+            .coerce_to_i32(activation)?,
+        leading_zero: get_field(this, "_leadingZero", activation)?.coerce_to_boolean(),
+        trailing_zeros: get_field(this, "_trailingZeros", activation)?.coerce_to_boolean(),
+        use_grouping: get_field(this, "_useGrouping", activation)?.coerce_to_boolean(),
+        ..NumberFormat::default()
+    })
+}
+
+fn string_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    strings: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let values = strings
+        .into_iter()
+        .map(|string| AvmString::new_utf8(activation.context.gc_context, string.as_ref()).into())
+        .collect();
+    let storage =
+        VectorStorage::from_values(values, false, Some(activation.avm2().classes().string));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// Read the strings of a `Vector.<String>`.
+fn strings_from_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    vector: Object<'gc>,
+) -> Result<Vec<String>, Error<'gc>> {
+    let values: Vec<Value<'gc>> = match vector.as_vector_storage() {
+        Some(storage) => storage.iter().collect(),
+        None => return Ok(Vec::new()),
+    };
+    values
+        .into_iter()
+        .map(|value| {
+            Ok(value
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned())
+        })
+        .collect()
+}
+
+/// Implements `getAvailableLocaleIDNames` of all `flash.globalization` classes.
+fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    string_vector(activation, LOCALES.iter().map(|locale| locale.name))
+}
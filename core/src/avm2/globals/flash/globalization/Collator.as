@@ -0,0 +1,88 @@
+package flash.globalization {
+    import flash.globalization.LastOperationStatus;
+
+    public final class Collator {
+        internal var _actualLocaleIDName:String;
+        internal var _requestedLocaleIDName:String;
+        internal var _lastOperationStatus:String;
+
+        internal var _ignoreCase:Boolean;
+        internal var _ignoreCharacterWidth:Boolean;
+        internal var _ignoreDiacritics:Boolean;
+        internal var _ignoreKanaType:Boolean;
+        internal var _ignoreSymbols:Boolean;
+        internal var _numericComparison:Boolean;
+
+        public function Collator(requestedLocaleIDName:String, initialMode:String = "sorting") {
+            this.init(requestedLocaleIDName, initialMode);
+        }
+
+        private native function init(requestedLocaleIDName:String, initialMode:String):void;
+
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleIDName;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._requestedLocaleIDName;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get ignoreCase():Boolean {
+            return this._ignoreCase;
+        }
+        public function set ignoreCase(value:Boolean):void {
+            this._ignoreCase = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get ignoreCharacterWidth():Boolean {
+            return this._ignoreCharacterWidth;
+        }
+        public function set ignoreCharacterWidth(value:Boolean):void {
+            this._ignoreCharacterWidth = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get ignoreDiacritics():Boolean {
+            return this._ignoreDiacritics;
+        }
+        public function set ignoreDiacritics(value:Boolean):void {
+            this._ignoreDiacritics = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get ignoreKanaType():Boolean {
+            return this._ignoreKanaType;
+        }
+        public function set ignoreKanaType(value:Boolean):void {
+            this._ignoreKanaType = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get ignoreSymbols():Boolean {
+            return this._ignoreSymbols;
+        }
+        public function set ignoreSymbols(value:Boolean):void {
+            this._ignoreSymbols = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get numericComparison():Boolean {
+            return this._numericComparison;
+        }
+        public function set numericComparison(value:Boolean):void {
+            this._numericComparison = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public native function compare(string1:String, string2:String):int;
+
+        public native function equals(string1:String, string2:String):Boolean;
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
+    }
+}
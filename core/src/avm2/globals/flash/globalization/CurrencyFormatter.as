@@ -1,18 +1,194 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
+    import flash.globalization.CurrencyParseResult;
+    import flash.globalization.LastOperationStatus;
 
     public final class CurrencyFormatter {
+        internal var _actualLocaleIDName:String;
+        internal var _requestedLocaleIDName:String;
+        internal var _lastOperationStatus:String;
 
-        public var fractionalDigits: int = 0;
+        internal var _currencyISOCode:String;
+        internal var _currencySymbol:String;
+        internal var _decimalSeparator:String;
+        internal var _digitsType:uint;
+        internal var _fractionalDigits:int;
+        internal var _groupingPattern:String;
+        internal var _groupingSeparator:String;
+        internal var _leadingZero:Boolean;
+        internal var _negativeCurrencyFormat:uint;
+        internal var _negativeSymbol:String;
+        internal var _positiveCurrencyFormat:uint;
+        internal var _trailingZeros:Boolean;
+        internal var _useGrouping:Boolean;
 
-        public function CurrencyFormatter(locale: String) {
-            stub_constructor("flash.globalization.CurrencyFormatter");
+        public function CurrencyFormatter(requestedLocaleIDName:String) {
+            this.init(requestedLocaleIDName);
         }
 
-        public function format(value:Number, withCurrencySymbol:Boolean = false):String {
-            stub_method("flash.globalization.CurrencyFormatter", "format");
-            return value.toString();
+        private native function init(requestedLocaleIDName:String):void;
+
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleIDName;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._requestedLocaleIDName;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get currencyISOCode():String {
+            return this._currencyISOCode;
+        }
+
+        public function get currencySymbol():String {
+            return this._currencySymbol;
+        }
+
+        public function setCurrency(currencyISOCode:String, currencySymbol:String):void {
+            if (currencyISOCode == null) {
+                throw new TypeError("Error #2007: Parameter currencyISOCode must be non-null.", 2007);
+            }
+            if (currencySymbol == null) {
+                throw new TypeError("Error #2007: Parameter currencySymbol must be non-null.", 2007);
+            }
+            this._currencyISOCode = currencyISOCode;
+            this._currencySymbol = currencySymbol;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get decimalSeparator():String {
+            return this._decimalSeparator;
+        }
+        public function set decimalSeparator(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter decimalSeparator must be non-null.", 2007);
+            }
+            this._decimalSeparator = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get digitsType():uint {
+            return this._digitsType;
+        }
+        public function set digitsType(value:uint):void {
+            this._digitsType = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get fractionalDigits():int {
+            return this._fractionalDigits;
+        }
+        public function set fractionalDigits(value:int):void {
+            this._fractionalDigits = value < 0 ? 0 : value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get groupingPattern():String {
+            return this._groupingPattern;
+        }
+        public function set groupingPattern(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter groupingPattern must be non-null.", 2007);
+            }
+            if (!isValidGroupingPattern(value)) {
+                this._lastOperationStatus = LastOperationStatus.ILLEGAL_ARGUMENT_ERROR;
+                return;
+            }
+            this._groupingPattern = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get groupingSeparator():String {
+            return this._groupingSeparator;
+        }
+        public function set groupingSeparator(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter groupingSeparator must be non-null.", 2007);
+            }
+            this._groupingSeparator = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
+
+        public function get leadingZero():Boolean {
+            return this._leadingZero;
+        }
+        public function set leadingZero(value:Boolean):void {
+            this._leadingZero = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get negativeCurrencyFormat():uint {
+            return this._negativeCurrencyFormat;
+        }
+        public function set negativeCurrencyFormat(value:uint):void {
+            if (value > 15) {
+                throw new ArgumentError("Error #2008: Parameter negativeCurrencyFormat must be one of the accepted values.", 2008);
+            }
+            this._negativeCurrencyFormat = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get negativeSymbol():String {
+            return this._negativeSymbol;
+        }
+        public function set negativeSymbol(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter negativeSymbol must be non-null.", 2007);
+            }
+            this._negativeSymbol = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get positiveCurrencyFormat():uint {
+            return this._positiveCurrencyFormat;
+        }
+        public function set positiveCurrencyFormat(value:uint):void {
+            if (value > 3) {
+                throw new ArgumentError("Error #2008: Parameter positiveCurrencyFormat must be one of the accepted values.", 2008);
+            }
+            this._positiveCurrencyFormat = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get trailingZeros():Boolean {
+            return this._trailingZeros;
+        }
+        public function set trailingZeros(value:Boolean):void {
+            this._trailingZeros = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get useGrouping():Boolean {
+            return this._useGrouping;
+        }
+        public function set useGrouping(value:Boolean):void {
+            this._useGrouping = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public native function format(value:Number, withCurrencySymbol:Boolean = false):String;
+
+        public native function formattingWithCurrencySymbolIsSafe(requestedISOCode:String):Boolean;
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
+
+        public function parse(inputString:String):CurrencyParseResult {
+            if (inputString == null) {
+                throw new TypeError("Error #2007: Parameter inputString must be non-null.", 2007);
+            }
+            // [value, currencyString], or null if nothing could be parsed.
+            var result:Array = this.innerParse(inputString);
+            if (result == null) {
+                return new CurrencyParseResult();
+            }
+            return new CurrencyParseResult(result[0], result[1]);
+        }
+
+        private native function innerParse(inputString:String):Array;
+
+        private static native function isValidGroupingPattern(pattern:String):Boolean;
     }
 }
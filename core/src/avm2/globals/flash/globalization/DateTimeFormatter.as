@@ -0,0 +1,65 @@
+package flash.globalization {
+    import flash.globalization.DateTimeNameContext;
+    import flash.globalization.DateTimeNameStyle;
+    import flash.globalization.DateTimeStyle;
+    import flash.globalization.LastOperationStatus;
+
+    public final class DateTimeFormatter {
+        internal var _actualLocaleIDName:String;
+        internal var _requestedLocaleIDName:String;
+        internal var _lastOperationStatus:String;
+
+        internal var _dateStyle:String;
+        internal var _timeStyle:String;
+        internal var _pattern:String;
+
+        public function DateTimeFormatter(requestedLocaleIDName:String, dateStyle:String = "long", timeStyle:String = "long") {
+            this.init(requestedLocaleIDName, dateStyle, timeStyle);
+        }
+
+        private native function init(requestedLocaleIDName:String, dateStyle:String, timeStyle:String):void;
+
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleIDName;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._requestedLocaleIDName;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public native function format(dateTime:Date):String;
+
+        public native function formatUTC(dateTime:Date):String;
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
+
+        public function getDateStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._dateStyle;
+        }
+
+        public function getTimeStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._timeStyle;
+        }
+
+        public function getDateTimePattern():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return this._pattern;
+        }
+
+        public native function getFirstWeekday():int;
+
+        public native function getMonthNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String>;
+
+        public native function getWeekdayNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String>;
+
+        public native function setDateTimePattern(pattern:String):void;
+
+        public native function setDateTimeStyles(dateStyle:String, timeStyle:String):void;
+    }
+}
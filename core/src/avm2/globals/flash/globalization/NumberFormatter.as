@@ -1,39 +1,50 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
-    import flash.globalization.NationalDigitsType;
     import flash.globalization.NumberParseResult;
 
     public final class NumberFormatter {
-        private var _decimalSeparator = ".";
-        private var _digitsType = NationalDigitsType.EUROPEAN;
-        private var _fractionalDigits = 2;
-        private var _groupingPattern = "3;*";
-        private var _groupingSeparator = ",";
-        private var _leadingZero = true;
-        private var _localeIDName:String;
-        private var _negativeNumberFormat = 1;
-        private var _negativeSymbol = "-";
-        private var _trailingZeros = true;
-        private var _useGrouping = true;
+        internal var _actualLocaleIDName:String;
+        internal var _requestedLocaleIDName:String;
+        internal var _lastOperationStatus:String;
+
+        internal var _decimalSeparator:String;
+        internal var _digitsType:uint;
+        internal var _fractionalDigits:int;
+        internal var _groupingPattern:String;
+        internal var _groupingSeparator:String;
+        internal var _leadingZero:Boolean;
+        internal var _negativeNumberFormat:uint;
+        internal var _negativeSymbol:String;
+        internal var _trailingZeros:Boolean;
+        internal var _useGrouping:Boolean;
 
         public function NumberFormatter(requestedLocaleIDName:String) {
-            stub_constructor("flash.globalization.NumberFormatter");
-            this._localeIDName = requestedLocaleIDName;
+            this.init(requestedLocaleIDName);
         }
 
+        private native function init(requestedLocaleIDName:String):void;
+
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.NumberFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            return this._actualLocaleIDName;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._requestedLocaleIDName;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
         }
 
         public function get decimalSeparator():String {
             return this._decimalSeparator;
         }
         public function set decimalSeparator(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter decimalSeparator must be non-null.", 2007);
+            }
             this._decimalSeparator = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get digitsType():uint {
@@ -41,32 +52,41 @@ package flash.globalization {
         }
         public function set digitsType(value:uint):void {
             this._digitsType = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get fractionalDigits():int {
             return this._fractionalDigits;
         }
         public function set fractionalDigits(value:int):void {
-            this._fractionalDigits = value;
+            this._fractionalDigits = value < 0 ? 0 : value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get groupingPattern():String {
             return this._groupingPattern;
         }
         public function set groupingPattern(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter groupingPattern must be non-null.", 2007);
+            }
+            if (!isValidGroupingPattern(value)) {
+                this._lastOperationStatus = LastOperationStatus.ILLEGAL_ARGUMENT_ERROR;
+                return;
+            }
             this._groupingPattern = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get groupingSeparator():String {
             return this._groupingSeparator;
         }
         public function set groupingSeparator(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter groupingSeparator must be non-null.", 2007);
+            }
             this._groupingSeparator = value;
-        }
-
-        public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.NumberFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get leadingZero():Boolean {
@@ -74,26 +94,29 @@ package flash.globalization {
         }
         public function set leadingZero(value:Boolean):void {
             this._leadingZero = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get negativeNumberFormat():uint {
             return this._negativeNumberFormat;
         }
         public function set negativeNumberFormat(value:uint):void {
-            if (value >= 0 && value <= 4) {
-                this._negativeNumberFormat = value;
+            if (value > 4) {
+                throw new ArgumentError("Error #2008: Parameter negativeNumberFormat must be one of the accepted values.", 2008);
             }
+            this._negativeNumberFormat = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get negativeSymbol():String {
             return this._negativeSymbol;
         }
         public function set negativeSymbol(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter negativeSymbol must be non-null.", 2007);
+            }
             this._negativeSymbol = value;
-        }
-
-        public function get requestedLocaleIDName():String {
-            return this._localeIDName;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get trailingZeros():Boolean {
@@ -101,6 +124,7 @@ package flash.globalization {
         }
         public function set trailingZeros(value:Boolean):void {
             this._trailingZeros = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get useGrouping():Boolean {
@@ -108,36 +132,33 @@ package flash.globalization {
         }
         public function set useGrouping(value:Boolean):void {
             this._useGrouping = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
-        public function formatInt(value:int):String {
-            stub_method("flash.globalization.NumberFormatter", "formatInt");
-            return value.toString();
-        }
+        public native function formatInt(value:int):String;
 
-        public function formatNumber(value:Number):String {
-            stub_method("flash.globalization.NumberFormatter", "formatNumber");
-            return value.toString();
-        }
+        public native function formatNumber(value:Number):String;
 
-        public function formatUint(value:uint):String {
-            stub_method("flash.globalization.NumberFormatter", "formatUint");
-            return value.toString();
-        }
+        public native function formatUint(value:uint):String;
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.NumberFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
 
         public function parse(parseString:String):NumberParseResult {
-            stub_method("flash.globalization.NumberFormatter", "parse");
-            return new NumberParseResult();
+            if (parseString == null) {
+                throw new TypeError("Error #2007: Parameter parseString must be non-null.", 2007);
+            }
+            // [value, startIndex, endIndex], or null if nothing could be parsed.
+            var result:Array = this.innerParse(parseString);
+            if (result == null) {
+                return new NumberParseResult();
+            }
+            return new NumberParseResult(result[0], result[1], result[2]);
         }
 
-        public function parseNumber(parseString:String):Number {
-            stub_method("flash.globalization.NumberFormatter", "parseNumber");
-            return Number(parseString);
-        }
+        public native function parseNumber(parseString:String):Number;
+
+        private native function innerParse(parseString:String):Array;
+
+        private static native function isValidGroupingPattern(pattern:String):Boolean;
     }
 }
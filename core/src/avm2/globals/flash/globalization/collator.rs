@@ -0,0 +1,121 @@
+//! `flash.globalization.Collator` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::globals::flash::globalization::{
+    get_available_locale_id_names as available_locale_id_names, get_field, init_locale,
+    locale_data, set_field, set_status,
+};
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::globalization::{CollatorMode, CollatorOptions, LastOperationStatus};
+use std::cmp::Ordering;
+
+/// Read the comparison options of a `Collator`.
+fn collator_options<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<CollatorOptions, Error<'gc>> {
+    Ok(CollatorOptions {
+        ignore_case: get_field(this, "_ignoreCase", activation)?.coerce_to_boolean(),
+        ignore_character_width: get_field(this, "_ignoreCharacterWidth", activation)?
+            .coerce_to_boolean(),
+        ignore_diacritics: get_field(this, "_ignoreDiacritics", activation)?.coerce_to_boolean(),
+        ignore_kana_type: get_field(this, "_ignoreKanaType", activation)?.coerce_to_boolean(),
+        ignore_symbols: get_field(this, "_ignoreSymbols", activation)?.coerce_to_boolean(),
+        numeric_comparison: get_field(this, "_numericComparison", activation)?.coerce_to_boolean(),
+    })
+}
+
+/// Implements `Collator.init`, which sets up a new collator for the requested locale.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    let mode = args.get_string_non_null(activation, 1, "initialMode")?;
+    let mode = CollatorMode::parse(&mode.to_utf8_lossy())
+        .ok_or_else(|| make_error_2008(activation, "initialMode"))?;
+
+    init_locale(this, requested, activation)?;
+    let options = CollatorOptions::for_mode(mode);
+    set_field(this, "_ignoreCase", options.ignore_case.into(), activation)?;
+    set_field(
+        this,
+        "_ignoreCharacterWidth",
+        options.ignore_character_width.into(),
+        activation,
+    )?;
+    set_field(
+        this,
+        "_ignoreDiacritics",
+        options.ignore_diacritics.into(),
+        activation,
+    )?;
+    set_field(
+        this,
+        "_ignoreKanaType",
+        options.ignore_kana_type.into(),
+        activation,
+    )?;
+    set_field(
+        this,
+        "_ignoreSymbols",
+        options.ignore_symbols.into(),
+        activation,
+    )?;
+    set_field(
+        this,
+        "_numericComparison",
+        options.numeric_comparison.into(),
+        activation,
+    )?;
+    Ok(Value::Undefined)
+}
+
+/// Compare the two string arguments with the options of a `Collator`.
+fn compare_strings<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Ordering, Error<'gc>> {
+    let string1 = args.get_string_non_null(activation, 0, "string1")?;
+    let string2 = args.get_string_non_null(activation, 1, "string2")?;
+    let locale = locale_data(this, activation)?;
+    let options = collator_options(this, activation)?;
+
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(options.compare(locale, &string1.to_utf8_lossy(), &string2.to_utf8_lossy()))
+}
+
+/// Implements `Collator.compare`
+pub fn compare<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let ordering = compare_strings(activation, this, args)?;
+    Ok((ordering as i32).into())
+}
+
+/// Implements `Collator.equals`
+pub fn equals<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let ordering = compare_strings(activation, this, args)?;
+    Ok((ordering == Ordering::Equal).into())
+}
+
+/// Implements `Collator.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
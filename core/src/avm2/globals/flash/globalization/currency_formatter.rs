@@ -0,0 +1,137 @@
+//! `flash.globalization.CurrencyFormatter` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::globals::flash::globalization::{
+    get_available_locale_id_names as available_locale_id_names, get_field, get_string_field,
+    init_locale, locale_data, number_format, set_field, set_number_format, set_status,
+    set_string_field,
+};
+use crate::avm2::object::{ArrayObject, Object};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::globalization::{self, CurrencyFormat, LastOperationStatus};
+use crate::string::AvmString;
+
+/// Read the settings of a `CurrencyFormatter`.
+fn currency_format<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<CurrencyFormat, Error<'gc>> {
+    Ok(CurrencyFormat {
+        number: number_format(this, activation)?,
+        currency_iso_code: get_string_field(this, "_currencyISOCode", activation)?,
+        currency_symbol: get_string_field(this, "_currencySymbol", activation)?,
+        positive_currency_format: get_field(this, "_positiveCurrencyFormat", activation)?
+            .coerce_to_u32(activation)?,
+        negative_currency_format: get_field(this, "_negativeCurrencyFormat", activation)?
+            .coerce_to_u32(activation)?,
+    })
+}
+
+/// Implements `CurrencyFormatter.init`, which sets up a new formatter for the requested locale.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    let locale = init_locale(this, requested, activation)?;
+    let format = CurrencyFormat::from_locale(locale);
+
+    set_number_format(this, &format.number, activation)?;
+    set_string_field(
+        this,
+        "_currencyISOCode",
+        &format.currency_iso_code,
+        activation,
+    )?;
+    set_string_field(this, "_currencySymbol", &format.currency_symbol, activation)?;
+    set_field(
+        this,
+        "_positiveCurrencyFormat",
+        format.positive_currency_format.into(),
+        activation,
+    )?;
+    set_field(
+        this,
+        "_negativeCurrencyFormat",
+        format.negative_currency_format.into(),
+        activation,
+    )?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `CurrencyFormatter.format`
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let with_currency_symbol = args.get_bool(1);
+
+    let formatted = currency_format(this, activation)?.format(value, with_currency_symbol);
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Implements `CurrencyFormatter.formattingWithCurrencySymbolIsSafe`
+///
+/// A symbol is only unambiguous for the currency of the formatter's locale,
+/// e.g. `$` means USD in `en-US`, but not in `es-MX`.
+pub fn formatting_with_currency_symbol_is_safe<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedISOCode")?;
+    let locale = locale_data(this, activation)?;
+
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(requested
+        .to_utf8_lossy()
+        .eq_ignore_ascii_case(locale.currency_iso_code)
+        .into())
+}
+
+/// Implements `CurrencyFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
+
+/// Implements `CurrencyFormatter.innerParse`, which returns `[value, currencyString]`,
+/// or null if the string doesn't contain an amount.
+pub fn inner_parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args.get_string(activation, 0)?;
+    let format = currency_format(this, activation)?;
+
+    let Some((value, currency)) = format.parse(&input.to_utf8_lossy()) else {
+        set_status(this, LastOperationStatus::ParseError, activation)?;
+        return Ok(Value::Null);
+    };
+
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    let currency = AvmString::new_utf8(activation.context.gc_context, currency);
+    let storage = ArrayStorage::from_args(&[value.into(), currency.into()]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements `CurrencyFormatter.isValidGroupingPattern`
+pub fn is_valid_grouping_pattern<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pattern = args.get_string(activation, 0)?;
+    Ok(globalization::is_valid_grouping_pattern(&pattern.to_utf8_lossy()).into())
+}
@@ -0,0 +1,206 @@
+//! `flash.globalization.DateTimeFormatter` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::globals::flash::globalization::{
+    get_available_locale_id_names as available_locale_id_names, get_string_field, init_locale,
+    locale_data, set_field, set_status, set_string_field, string_vector,
+};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::globalization::{
+    format_date_time, is_valid_pattern, month_names, pattern_for_styles, weekday_names,
+    DateTimeNameContext, DateTimeNameStyle, DateTimeStyle, LastOperationStatus, LocaleData,
+};
+use crate::locale::get_timezone;
+use crate::string::AvmString;
+use chrono::FixedOffset;
+
+/// Read a `DateTimeStyle` argument. `CUSTOM` is accepted, but can't be used
+/// to choose a pattern.
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    index: usize,
+    name: &'static str,
+) -> Result<DateTimeStyle, Error<'gc>> {
+    let style = args.get_string_non_null(activation, index, name)?;
+    DateTimeStyle::parse(&style.to_utf8_lossy()).ok_or_else(|| make_error_2008(activation, name))
+}
+
+/// Apply the pattern of a locale for a date and time style.
+fn set_styles<'gc>(
+    this: Object<'gc>,
+    locale: &LocaleData,
+    date_style: DateTimeStyle,
+    time_style: DateTimeStyle,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<(), Error<'gc>> {
+    let pattern = pattern_for_styles(locale, date_style, time_style);
+    set_string_field(this, "_dateStyle", date_style.as_str(), activation)?;
+    set_string_field(this, "_timeStyle", time_style.as_str(), activation)?;
+    set_string_field(this, "_pattern", &pattern, activation)
+}
+
+/// Implements `DateTimeFormatter.init`, which sets up a new formatter for the requested locale.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    let date_style = get_style(activation, args, 1, "dateStyle")?;
+    let time_style = get_style(activation, args, 2, "timeStyle")?;
+    if date_style == DateTimeStyle::Custom {
+        return Err(make_error_2008(activation, "dateStyle"));
+    }
+    if time_style == DateTimeStyle::Custom {
+        return Err(make_error_2008(activation, "timeStyle"));
+    }
+
+    let locale = init_locale(this, requested, activation)?;
+    set_styles(this, locale, date_style, time_style, activation)?;
+    Ok(Value::Undefined)
+}
+
+fn format_in_timezone<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    timezone: FixedOffset,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = args.get_object(activation, 0, "dateTime")?;
+    let Some(date_time) = date.as_date_object().and_then(|date| date.date_time()) else {
+        // Invalid dates can't be formatted.
+        set_status(this, LastOperationStatus::IllegalArgumentError, activation)?;
+        return Ok(AvmString::default().into());
+    };
+
+    let locale = locale_data(this, activation)?;
+    let pattern = get_string_field(this, "_pattern", activation)?;
+    let date_time = date_time.with_timezone(&timezone);
+    let formatted = format_date_time(&pattern, locale, &date_time).unwrap_or_default();
+
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Implements `DateTimeFormatter.format`, which uses the local timezone.
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_in_timezone(activation, this, args, get_timezone())
+}
+
+/// Implements `DateTimeFormatter.formatUTC`
+pub fn format_utc<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let utc = FixedOffset::east_opt(0).expect("UTC is a valid offset");
+    format_in_timezone(activation, this, args, utc)
+}
+
+/// Implements `DateTimeFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
+
+/// Implements `DateTimeFormatter.getFirstWeekday`
+pub fn get_first_weekday<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale_data(this, activation)?;
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(locale.first_weekday.into())
+}
+
+/// Read the `nameStyle` and `context` arguments of `getMonthNames` and `getWeekdayNames`.
+fn get_name_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<(DateTimeNameStyle, DateTimeNameContext), Error<'gc>> {
+    let style = args.get_string_non_null(activation, 0, "nameStyle")?;
+    let style = DateTimeNameStyle::parse(&style.to_utf8_lossy())
+        .ok_or_else(|| make_error_2008(activation, "nameStyle"))?;
+    let context = args.get_string_non_null(activation, 1, "context")?;
+    let context = DateTimeNameContext::parse(&context.to_utf8_lossy())
+        .ok_or_else(|| make_error_2008(activation, "context"))?;
+    Ok((style, context))
+}
+
+/// Implements `DateTimeFormatter.getMonthNames`
+pub fn get_month_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (style, context) = get_name_style(activation, args)?;
+    let locale = locale_data(this, activation)?;
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    string_vector(activation, month_names(locale, style, context))
+}
+
+/// Implements `DateTimeFormatter.getWeekdayNames`
+pub fn get_weekday_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Weekday names are the same in both contexts in all our locales.
+    let (style, _context) = get_name_style(activation, args)?;
+    let locale = locale_data(this, activation)?;
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    string_vector(activation, weekday_names(locale, style))
+}
+
+/// Implements `DateTimeFormatter.setDateTimePattern`
+pub fn set_date_time_pattern<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pattern = args.get_string_non_null(activation, 0, "pattern")?;
+    if !is_valid_pattern(&pattern.to_utf8_lossy()) {
+        set_status(this, LastOperationStatus::PatternSyntaxError, activation)?;
+        return Ok(Value::Undefined);
+    }
+
+    let custom = DateTimeStyle::Custom.as_str();
+    set_string_field(this, "_dateStyle", custom, activation)?;
+    set_string_field(this, "_timeStyle", custom, activation)?;
+    set_field(this, "_pattern", pattern.into(), activation)?;
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `DateTimeFormatter.setDateTimeStyles`
+pub fn set_date_time_styles<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date_style = get_style(activation, args, 0, "dateStyle")?;
+    let time_style = get_style(activation, args, 1, "timeStyle")?;
+    if date_style == DateTimeStyle::Custom || time_style == DateTimeStyle::Custom {
+        // Custom patterns have to be set with `setDateTimePattern`.
+        set_status(this, LastOperationStatus::IllegalArgumentError, activation)?;
+        return Ok(Value::Undefined);
+    }
+
+    let locale = locale_data(this, activation)?;
+    set_styles(this, locale, date_style, time_style, activation)?;
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,130 @@
+//! `flash.globalization.LocaleID` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::globalization::{
+    get_string_field, set_status, set_string_field, string_vector, strings_from_vector,
+};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::globalization::{self, LastOperationStatus, LocaleId};
+use crate::string::AvmString;
+
+/// Parse the name of a `LocaleID`, and report success in `lastOperationStatus`.
+fn locale_id<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<LocaleId, Error<'gc>> {
+    let locale = LocaleId::parse(&get_string_field(this, "_name", activation)?);
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(locale)
+}
+
+/// Implements `LocaleID.init`, which normalizes the given name.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?;
+    let locale = LocaleId::parse(&name.to_utf8_lossy());
+
+    set_string_field(this, "_name", locale.name(), activation)?;
+    let status = if locale.is_valid() {
+        LastOperationStatus::NoError
+    } else {
+        LastOperationStatus::IllegalArgumentError
+    };
+    set_status(this, status, activation)?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocaleID.determinePreferredLocales`
+///
+/// Only the `userinterface` keyword exists, so it's ignored.
+pub fn determine_preferred_locales<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let want = args.get_object(activation, 0, "want")?;
+    let have = args.get_object(activation, 1, "have")?;
+    let want = strings_from_vector(activation, want)?;
+    let have = strings_from_vector(activation, have)?;
+
+    string_vector(
+        activation,
+        globalization::determine_preferred_locales(&want, &have),
+    )
+}
+
+/// Implements `LocaleID.getKeysAndValues`
+pub fn get_keys_and_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale_id(this, activation)?;
+    let object = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    for (key, value) in locale.keywords() {
+        let key = AvmString::new_utf8(activation.context.gc_context, key);
+        let value = AvmString::new_utf8(activation.context.gc_context, value);
+        object.set_public_property(key, value.into(), activation)?;
+    }
+    Ok(object.into())
+}
+
+/// Implements `LocaleID.getLanguage`
+pub fn get_language<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale_id(this, activation)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, locale.language()).into())
+}
+
+/// Implements `LocaleID.getRegion`
+pub fn get_region<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale_id(this, activation)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, locale.region()).into())
+}
+
+/// Implements `LocaleID.getScript`
+pub fn get_script<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale_id(this, activation)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, locale.script()).into())
+}
+
+/// Implements `LocaleID.getVariant`
+pub fn get_variant<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale_id(this, activation)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, locale.variant()).into())
+}
+
+/// Implements `LocaleID.isRightToLeft`
+pub fn is_right_to_left<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale_id(this, activation)?;
+    Ok(locale.is_right_to_left().into())
+}
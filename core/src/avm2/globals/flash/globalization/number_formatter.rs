@@ -0,0 +1,155 @@
+//! `flash.globalization.NumberFormatter` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::globals::flash::globalization::{
+    get_available_locale_id_names as available_locale_id_names, get_field, init_locale,
+    number_format, set_field, set_number_format, set_status,
+};
+use crate::avm2::object::{ArrayObject, Object};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::globalization::{self, LastOperationStatus, NumberFormat};
+use crate::string::AvmString;
+
+/// Implements `NumberFormatter.init`, which sets up a new formatter for the requested locale.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    let locale = init_locale(this, requested, activation)?;
+    let format = NumberFormat::from_locale(locale);
+    set_number_format(this, &format, activation)?;
+    set_field(
+        this,
+        "_negativeNumberFormat",
+        format.negative_number_format.into(),
+        activation,
+    )?;
+    Ok(Value::Undefined)
+}
+
+/// Read the settings of a `NumberFormatter`.
+fn formatter_number_format<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<NumberFormat, Error<'gc>> {
+    Ok(NumberFormat {
+        negative_number_format: get_field(this, "_negativeNumberFormat", activation)?
+            .coerce_to_u32(activation)?,
+        ..number_format(this, activation)?
+    })
+}
+
+fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    value: f64,
+    is_integer: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = formatter_number_format(this, activation)?;
+    let formatted = if is_integer {
+        format.format_integer(value)
+    } else {
+        format.format(value)
+    };
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, formatted).into())
+}
+
+/// Implements `NumberFormatter.formatInt`
+pub fn format_int<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_i32(activation, 0)?;
+    format(activation, this, value.into(), true)
+}
+
+/// Implements `NumberFormatter.formatUint`
+pub fn format_uint<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_u32(activation, 0)?;
+    format(activation, this, value.into(), true)
+}
+
+/// Implements `NumberFormatter.formatNumber`
+pub fn format_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    format(activation, this, value, false)
+}
+
+/// Implements `NumberFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
+
+/// Implements `NumberFormatter.innerParse`, which returns `[value, startIndex, endIndex]`,
+/// or null if the string doesn't contain a number.
+pub fn inner_parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args.get_string(activation, 0)?;
+    let format = formatter_number_format(this, activation)?;
+
+    let Some(parsed) = format.parse(&input.to_utf8_lossy()) else {
+        set_status(this, LastOperationStatus::ParseError, activation)?;
+        return Ok(Value::Null);
+    };
+
+    set_status(this, LastOperationStatus::NoError, activation)?;
+    let storage = ArrayStorage::from_args(&[
+        parsed.value.into(),
+        parsed.start_index.into(),
+        parsed.end_index.into(),
+    ]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements `NumberFormatter.parseNumber`
+pub fn parse_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args.get_string_non_null(activation, 0, "parseString")?;
+    let format = formatter_number_format(this, activation)?;
+
+    match format.parse(&input.to_utf8_lossy()) {
+        Some(parsed) => {
+            set_status(this, LastOperationStatus::NoError, activation)?;
+            Ok(parsed.value.into())
+        }
+        None => {
+            set_status(this, LastOperationStatus::ParseError, activation)?;
+            Ok(f64::NAN.into())
+        }
+    }
+}
+
+/// Implements `NumberFormatter.isValidGroupingPattern`
+pub fn is_valid_grouping_pattern<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pattern = args.get_string(activation, 0)?;
+    Ok(globalization::is_valid_grouping_pattern(&pattern.to_utf8_lossy()).into())
+}
@@ -1,15 +1,17 @@
 //! `flash.media.Sound` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::{argument_error, io_error};
 use crate::avm2::object::{Object, QueuedPlay, SoundChannelObject, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::audio::AudioManager;
 use crate::backend::navigator::Request;
 use crate::character::Character;
 use crate::display_object::SoundTransform;
 use crate::{avm2_stub_getter, avm2_stub_method};
-use swf::{SoundEvent, SoundInfo};
+use swf::{AudioCompression, SoundEvent, SoundFormat, SoundInfo};
 
 pub use crate::avm2::object::sound_allocator;
 
@@ -69,13 +71,14 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Sound.bytesLoaded`
 pub fn get_bytes_loaded<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // This should have a different value from bytesTotal when the sound is loading.
-    avm2_stub_getter!(activation, "flash.media.Sound", "bytesLoaded");
+    // Sounds are only registered once fully downloaded. Before that, both `bytesLoaded` and
+    // `bytesTotal` are 0, and afterwards everything has been loaded.
     get_bytes_total(activation, this, args)
 }
 
@@ -103,12 +106,14 @@ pub fn get_is_url_inaccessible<'gc>(
 
 /// Implements `Sound.url`
 pub fn get_url<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.media.Sound", "url");
-    //STUB: We do not yet support network-loaded sounds.
+    if let Some(url) = this.as_sound_object().and_then(|sound| sound.url()) {
+        return Ok(url.into());
+    }
+
     Ok(Value::Null)
 }
 
@@ -174,6 +179,35 @@ pub fn play<'gc>(
 
         let sound_channel = SoundChannelObject::empty(activation)?;
 
+        if sound_object.sound_handle().is_none()
+            && sound_object.url().is_none()
+            && this
+                .call_public_property("hasEventListener", &["sampleData".into()], activation)?
+                .coerce_to_boolean()
+        {
+            // A sound with no data of its own is generated by its `sampleData` listeners.
+            let Some(instance) = AudioManager::start_dynamic_sound(&mut activation.context, this)
+            else {
+                return Ok(Value::Null);
+            };
+
+            if let Some(sound_transform) = sound_transform {
+                activation
+                    .context
+                    .set_local_sound_transform(instance, sound_transform);
+            }
+
+            sound_channel
+                .as_sound_channel()
+                .unwrap()
+                .set_sound_instance(activation, instance);
+            activation
+                .context
+                .attach_avm2_sound_channel(instance, sound_channel);
+
+            return Ok(sound_channel.into());
+        }
+
         let queued_play = QueuedPlay {
             position,
             sound_info,
@@ -194,36 +228,70 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
+    let bytearray = args.get_object(activation, 0, "target")?;
+    let length = args.get_f64(activation, 1)?;
+    let start_position = args.get_f64(activation, 2)?;
+
+    let Some(sound_object) = this.as_sound_object() else {
+        return Ok(0.into());
+    };
+    let Some(sound_handle) = sound_object.sound_handle() else {
+        return Ok(0.into());
+    };
+
+    let start_frame = if start_position >= 0.0 {
+        start_position as u32
+    } else {
+        sound_object.extract_position()
+    };
+    let num_frames = if length > 0.0 { length as u32 } else { 0 };
 
-    let bytearray = args
-        .get(0)
-        .unwrap_or(&Value::Undefined)
-        .coerce_to_object(activation)?;
-    let length = args
-        .get(1)
-        .unwrap_or(&Value::Number(0.0))
-        .coerce_to_number(activation)?;
+    let frames = activation
+        .context
+        .audio
+        .extract_sound(sound_handle, start_frame, num_frames)
+        .unwrap_or_default();
 
     if let Some(mut bytearray) = bytearray.as_bytearray_mut(activation.context.gc_context) {
-        bytearray
-            .write_bytes(vec![0u8; length.ceil() as usize].as_slice())
-            .map_err(|e| e.to_avm(activation))?;
+        for [left, right] in &frames {
+            bytearray
+                .write_float(*left)
+                .map_err(|e| e.to_avm(activation))?;
+            bytearray
+                .write_float(*right)
+                .map_err(|e| e.to_avm(activation))?;
+        }
     }
 
-    Ok(Value::Undefined)
+    sound_object.set_extract_position(
+        activation.context.gc_context,
+        start_frame + frames.len() as u32,
+    );
+
+    Ok(frames.len().into())
 }
 
 /// `Sound.close`
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "close");
+    if let Some(sound_object) = this.as_sound_object() {
+        if !sound_object.is_loading() {
+            return Err(Error::AvmError(io_error(
+                activation,
+                "Error #2029: This URLStream object does not have a stream opened.",
+                2029,
+            )?));
+        }
+
+        sound_object.close(activation.context.gc_context);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -244,6 +312,10 @@ pub fn load<'gc>(
         .get_public_property("url", activation)?
         .coerce_to_string(activation)?;
 
+    if let Some(sound_object) = this.as_sound_object() {
+        sound_object.set_url(activation.context.gc_context, url);
+    }
+
     // TODO: context parameter currently unused.
     let sound_context = args.try_get_object(activation, 1);
     if sound_context.is_some() {
@@ -276,7 +348,7 @@ pub fn load_compressed_data_from_byte_array<'gc>(
         Error::RustError(format!("Missing bytes from sound bytearray: {e:?}").into())
     })?;
 
-    let handle = activation.context.audio.register_mp3(bytes).map_err(|e| {
+    let handle = activation.context.audio.register_mp3(&bytes).map_err(|e| {
         Error::RustError(format!("Failed to register sound from bytearray: {e:?}").into())
     })?;
 
@@ -290,9 +362,76 @@ pub fn load_compressed_data_from_byte_array<'gc>(
 /// `Sound.loadPCMFromByteArray`
 pub fn load_pcm_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "loadPCMFromByteArray");
+    let bytearray = args.get_object(activation, 0, "bytes")?;
+    let samples = args.get_u32(activation, 1)?;
+    let format = args.get_string(activation, 2)?;
+    let is_stereo = args.get_bool(3);
+    let sample_rate = args.get_f64(activation, 4)?;
+
+    let is_float = if &format == b"float" {
+        true
+    } else if &format == b"short" {
+        false
+    } else {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2008: Parameter format must be one of the accepted values.",
+            2008,
+        )?));
+    };
+
+    if ![5512.0, 11025.0, 22050.0, 44100.0].contains(&sample_rate) {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2008: Parameter sampleRate must be one of the accepted values.",
+            2008,
+        )?));
+    }
+
+    let bytearray = bytearray.as_bytearray().unwrap();
+    let channels = if is_stereo { 2 } else { 1 };
+    let value_size = if is_float { 4 } else { 2 };
+    // Only whole samples are read, and reading stops early if the `ByteArray` runs out.
+    let num_samples = (samples as usize).min(bytearray.bytes_available() / value_size / channels);
+    let num_values = num_samples * channels;
+
+    let mut data = Vec::with_capacity(num_values * 2);
+    for _ in 0..num_values {
+        let value = if is_float {
+            let value = bytearray.read_float().map_err(|e| e.to_avm(activation))?;
+            (value.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+        } else {
+            bytearray.read_short().map_err(|e| e.to_avm(activation))?
+        };
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let sound = swf::Sound {
+        id: 0,
+        format: SoundFormat {
+            compression: AudioCompression::Uncompressed,
+            sample_rate: sample_rate as u16,
+            is_stereo,
+            is_16_bit: true,
+        },
+        num_samples: num_samples as u32,
+        data: &data,
+    };
+
+    let handle = activation
+        .context
+        .audio
+        .register_sound(&sound)
+        .map_err(|e| {
+            Error::RustError(format!("Failed to register sound from bytearray: {e:?}").into())
+        })?;
+
+    this.as_sound_object()
+        .unwrap()
+        .set_sound(&mut activation.context, handle)?;
+
     Ok(Value::Undefined)
 }
@@ -11,6 +11,7 @@ pub mod responder;
 pub mod shared_object;
 pub mod socket;
 pub mod url_loader;
+pub mod url_stream;
 pub mod xml_socket;
 
 /// Implements `flash.net.navigateToURL`
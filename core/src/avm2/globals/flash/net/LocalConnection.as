@@ -1,10 +1,7 @@
 package flash.net {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
 
-    // NOTE: this entire class is a stub.
-    // Thankfully (hopefully) a lot of code like Mochicrypt doesn't actually require this to... well do anything.
+    [Ruffle(InstanceAllocator)]
     public class LocalConnection extends EventDispatcher {
 
         public var client: Object;
@@ -15,22 +12,14 @@ package flash.net {
 
         public native function get domain():String;
 
-        public function close(): void {
-            stub_method("flash.net.LocalConnection", "close");
-        }
+        public native function close(): void;
 
-        public function connect(connectionName:String): void {
-            stub_method("flash.net.LocalConnection", "connect");
-        }
+        public native function connect(connectionName:String): void;
 
-        public native function send(connectionName: String, methodName: String, ... arguments);
+        public native function send(connectionName: String, methodName: String, ... arguments):void;
 
-        public function allowDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowDomain");
-        }
+        public native function allowDomain(... domains): void;
 
-        public function allowInsecureDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowInsecureDomain");
-        }
+        public native function allowInsecureDomain(... domains): void;
     }
 }
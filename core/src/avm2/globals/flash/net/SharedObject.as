@@ -15,6 +15,8 @@ package flash.net {
         // to work with AMF0.
 
         public static native function getLocal(name:String, localPath:String = null, secure:Boolean = false): SharedObject;
+        public static native function deleteAll(url:String):int;
+        public static native function getDiskUsage(url:String):int;
 
         public native function get size() : uint;
 
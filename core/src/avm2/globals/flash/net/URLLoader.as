@@ -6,6 +6,8 @@ package flash.net {
 	public class URLLoader extends EventDispatcher {
 		public var data: *;
 		public var dataFormat: String = "text";
+		public var bytesLoaded: uint = 0;
+		public var bytesTotal: uint = 0;
 
 		public function URLLoader(request:URLRequest = null) {
 			if (request != null) {
@@ -13,19 +15,6 @@ package flash.net {
 			}
 		}
 
-		// FIXME - this should be a normal property for consistency with Flash
-		public function get bytesTotal():uint {
-			if (this.data) {
-				return this.data.length;
-			}
-			return 0;
-		}
-
-		// FIXME - this should be a normal property for consistency with Flash
-		public function get bytesLoaded():uint {
-			// TODO - update this as the download progresses
-			return this.bytesTotal
-		}
 		public native function load(request:URLRequest):void;
 
 		public function close():void {
@@ -1,10 +1,14 @@
-use crate::avm2::error::type_error;
+use crate::avm2::amf::serialize_value;
+use crate::avm2::error::{argument_error, type_error};
+pub use crate::avm2::object::local_connection_allocator;
 use crate::avm2::object::TObject;
 use crate::avm2::parameters::ParametersExt;
-use crate::avm2::{Activation, Avm2, Error, Object, Value};
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::local_connection::{movie_domain, LocalConnections};
 use crate::string::AvmString;
-
-use crate::avm2_stub_method;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+use fnv::FnvHashMap;
+use std::rc::Rc;
 
 /// Implements `domain` getter
 pub fn get_domain<'gc>(
@@ -14,21 +18,70 @@ pub fn get_domain<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let movie = activation.context.swf;
 
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
+    let Some(domain) = movie_domain(movie.url()) else {
         tracing::error!("LocalConnection::domain: Unable to parse movie URL");
         return Ok(Value::Null);
     };
 
-    Ok(Value::String(domain))
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+/// Implements `LocalConnection.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = this
+        .as_local_connection_object()
+        .expect("Must be LocalConnection object");
+
+    if matches!(args.get_value(0), Value::Null) {
+        return Err(Error::AvmError(type_error(
+            activation,
+            "Error #2007: Parameter connectionName must be non-null.",
+            2007,
+        )?));
+    }
+
+    let name = args.get_string(activation, 0)?;
+    let domain = movie_domain(activation.context.swf.url()).unwrap_or_default();
+
+    if !LocalConnections::connect(
+        &mut activation.context,
+        connection,
+        &domain,
+        &name.to_utf8_lossy(),
+    ) {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2082: Connect failed because the object is already connected.",
+            2082,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = this
+        .as_local_connection_object()
+        .expect("Must be LocalConnection object");
+
+    if !LocalConnections::close(&mut activation.context, connection) {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2083: Close failed because the object is not connected.",
+            2083,
+        )?));
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `LocalConnection.send`
@@ -37,6 +90,10 @@ pub fn send<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = this
+        .as_local_connection_object()
+        .expect("Must be LocalConnection object");
+
     if matches!(args.get_value(0), Value::Null) {
         return Err(Error::AvmError(type_error(
             activation,
@@ -53,21 +110,54 @@ pub fn send<'gc>(
         )?));
     }
 
-    avm2_stub_method!(activation, "flash.net.LocalConnection", "send");
+    let connection_name = args.get_string(activation, 0)?;
+    let method_name = args.get_string(activation, 1)?;
+    let domain = movie_domain(activation.context.swf.url()).unwrap_or_default();
+
+    // Arguments are always sent as AMF0, so that AVM1 movies can receive them too.
+    let mut object_table = FnvHashMap::default();
+    let mut arguments = Vec::new();
+    for arg in &args[2..] {
+        let value = serialize_value(activation, *arg, AMFVersion::AMF0, &mut object_table)
+            .unwrap_or(AmfValue::Undefined);
+        arguments.push(Rc::new(value));
+    }
+
+    activation.context.local_connections.send(
+        connection,
+        &domain,
+        &connection_name.to_utf8_lossy(),
+        method_name.to_string(),
+        arguments,
+    );
+
+    Ok(Value::Undefined)
+}
 
-    let event = activation.avm2().classes().statusevent.construct(
-        activation,
-        &[
-            "status".into(),
-            false.into(),
-            false.into(),
-            Value::Null,
-            "error".into(),
-        ],
-    )?;
+/// Implements `LocalConnection.allowDomain`
+pub fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = this
+        .as_local_connection_object()
+        .expect("Must be LocalConnection object");
 
-    // FIXME: Adding the event listener after calling `send` works in FP.
-    Avm2::dispatch_event(&mut activation.context, event, this);
+    for domain in args {
+        let domain = domain.coerce_to_string(activation)?;
+        connection.allow_domain(domain.to_string());
+    }
 
     Ok(Value::Undefined)
 }
+
+/// Implements `LocalConnection.allowInsecureDomain`
+pub fn allow_insecure_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // We don't distinguish between HTTP and HTTPS senders.
+    allow_domain(activation, this, args)
+}
@@ -40,12 +40,31 @@ pub fn connect<'gc>(
             connection,
             url.to_string(),
         );
+    } else if url.starts_with(WStr::from_units(b"rtmp://")) {
+        let object_encoding = this
+            .get_public_property("objectEncoding", activation)?
+            .coerce_to_number(activation)?;
+        let mut object_table = FnvHashMap::default();
+        let arguments = args[1..]
+            .iter()
+            .filter_map(|arg| {
+                serialize_value(activation, *arg, AMFVersion::AMF0, &mut object_table)
+            })
+            .collect();
+
+        NetConnections::connect_to_rtmp(
+            &mut activation.context,
+            connection,
+            url.to_string(),
+            object_encoding,
+            arguments,
+        );
     } else {
         avm2_stub_method!(
             activation,
             "flash.net.NetConnection",
             "connect",
-            "with non-null, non-http command"
+            "with non-null, non-http, non-rtmp command"
         );
     }
 
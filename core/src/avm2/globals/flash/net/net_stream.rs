@@ -1,9 +1,29 @@
 use crate::avm2::error::type_error;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::avm2_stub_method;
+use crate::net_connection::NetConnections;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let connection = args
+            .try_get_object(activation, 0)
+            .and_then(|connection| connection.as_net_connection());
+
+        if let Some(handle) = connection.and_then(|connection| connection.handle()) {
+            NetConnections::create_stream(&mut activation.context, handle, ns);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bytes_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -47,6 +67,47 @@ pub fn play<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn publish<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let name = args.try_get_string(activation, 0)?;
+        let publish_type = args.try_get_string(activation, 1)?;
+
+        if !ns.publish(&mut activation.context, name, publish_type) {
+            avm2_stub_method!(
+                activation,
+                "flash.net.NetStream",
+                "publish",
+                "without an RTMP connection"
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        if !ns.close(&mut activation.context) {
+            avm2_stub_method!(
+                activation,
+                "flash.net.NetStream",
+                "close",
+                "without an RTMP connection"
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn pause<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -3,10 +3,12 @@
 use crate::avm2::api_version::ApiVersion;
 use crate::avm2::error::error;
 use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Error::AvmError;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Namespace, Object, Value};
 use crate::avm2_stub_method;
+use crate::shared_object::{domain_of_url, FlushStatus};
 use crate::string::AvmString;
 use flash_lso::types::{AMFVersion, Lso};
 use std::borrow::Cow;
@@ -199,8 +201,29 @@ pub fn get_local<'gc>(
 pub fn flush<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let min_disk_space = args.get_i32(activation, 0)?;
+    match write_to_storage(activation, this, min_disk_space.max(0) as usize, true)? {
+        FlushStatus::Flushed => Ok("flushed".into()),
+        FlushStatus::Pending => Ok("pending".into()),
+        FlushStatus::Failed => Err(AvmError(error(
+            activation,
+            "Error #2130: Unable to flush SharedObject.",
+            2130,
+        )?)),
+    }
+}
+
+/// Serializes a shared object and writes it to storage.
+///
+/// The user is only asked for more space if `prompt` is set.
+pub fn write_to_storage<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    min_disk_space: usize,
+    prompt: bool,
+) -> Result<FlushStatus, Error<'gc>> {
     let data = this
         .get_public_property("data", activation)?
         .coerce_to_object(activation)?;
@@ -221,20 +244,55 @@ pub fn flush<'gc>(
     let mut lso = new_lso(activation, &name, data)?;
     // Flash does not write empty LSOs to disk
     if lso.body.is_empty() {
-        Ok("flushed".into())
+        Ok(FlushStatus::Flushed)
     } else {
         let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-        if activation.context.storage.put(&name, &bytes) {
-            Ok("flushed".into())
-        } else {
-            Err(AvmError(error(
-                activation,
-                "Error #2130: Unable to flush SharedObject.",
-                2130,
-            )?))
-        }
+        let target = prompt.then_some(this.into());
+        Ok(crate::shared_object::flush(
+            &mut activation.context,
+            &name,
+            bytes,
+            min_disk_space,
+            target,
+        ))
     }
-    // FIXME - We should dispatch a NetStatusEvent after this function returns
+}
+
+pub fn delete_all<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?;
+    let movie = activation.caller_movie_or_root();
+    let Some(domain) = domain_of_url(movie.url(), &url.to_utf8_lossy()) else {
+        return Ok(0.into());
+    };
+
+    let storage = &mut activation.context.storage;
+    let names = storage.keys(&format!("{domain}/"));
+    for name in &names {
+        storage.remove_key(name);
+    }
+    Ok(names.len().into())
+}
+
+pub fn get_disk_usage<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?;
+    let movie = activation.caller_movie_or_root();
+    let Some(domain) = domain_of_url(movie.url(), &url.to_utf8_lossy()) else {
+        return Ok(0.into());
+    };
+
+    Ok(activation
+        .context
+        .storage
+        .usage(&format!("{domain}/"))
+        .into())
 }
 
 pub fn get_size<'gc>(
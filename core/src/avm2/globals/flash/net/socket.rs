@@ -7,6 +7,7 @@ use crate::avm2::parameters::ParametersExt;
 use crate::avm2::string::AvmString;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::context::UpdateContext;
+use crate::socket::Sockets;
 use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
 use flash_lso::amf0::read::AMF0Decoder;
@@ -41,11 +42,12 @@ pub fn connect<'gc>(
         .try_into()
         .map_err(|_| invalid_port_number(activation))?;
 
-    let UpdateContext {
-        sockets, navigator, ..
-    } = &mut activation.context;
-
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    Sockets::connect_avm2(
+        &mut activation.context,
+        socket,
+        host.to_utf8_lossy().into_owned(),
+        port,
+    );
 
     Ok(Value::Undefined)
 }
@@ -561,7 +563,7 @@ pub fn write_bytes<'gc>(
             )
             .map_err(|e| e.to_avm(activation))?;
 
-        socket.write_bytes(to_write);
+        socket.write_bytes(&to_write);
     }
 
     Ok(Value::Undefined)
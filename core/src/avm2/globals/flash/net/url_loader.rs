@@ -44,6 +44,9 @@ fn spawn_fetch<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let request = request_from_url_request(activation, url_request)?;
 
+    loader_object.set_public_property("bytesLoaded", 0.into(), activation)?;
+    loader_object.set_public_property("bytesTotal", 0.into(), activation)?;
+
     let future = activation.context.load_manager.load_data_into_url_loader(
         activation.context.player.clone(),
         loader_object,
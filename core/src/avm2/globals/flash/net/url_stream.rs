@@ -0,0 +1,33 @@
+//! `flash.net.URLStream` native function definitions
+
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Multiname, Object, Value};
+
+/// Native function definition for `URLStream.startLoad`
+pub fn start_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, request)?;
+
+    let buffer = this
+        .get_property(
+            &Multiname::new(activation.avm2().flash_net_internal, "_buffer"),
+            activation,
+        )?
+        .as_object()
+        .expect("URLStream buffer must be a ByteArray");
+
+    let future = activation.context.load_manager.load_data_into_url_stream(
+        activation.context.player.clone(),
+        this,
+        buffer,
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
@@ -1,26 +1,44 @@
 package flash.sampler {
     import __ruffle__.stub_method;
 
-    public function getSize(param1:*):Number {
-        stub_method("flash.sampler", "getSize");
-        return 0;
-    }
+    public native function clearSamples():void;
 
-    public function clearSamples():void {
-        stub_method("flash.sampler", "clearSamples");
-    }
+    public native function getGetterInvocationCount(obj:Object, qname:QName):Number;
+
+    public native function getInvocationCount(obj:Object, qname:QName):Number;
+
+    public native function getLexicalScopes(obj:Function):Array;
 
-    public function startSampling():void {
-        stub_method("flash.sampler", "startSampling");
+    // Ruffle strings never depend on a master string.
+    public function getMasterString(str:String):String {
+        return null;
     }
 
-    public function stopSampling():void {
-        stub_method("flash.sampler", "stopSampling");
+    public native function getMemberNames(o:Object, instanceNames:Boolean = false):Object;
+
+    public native function getSampleCount():Number;
+
+    public native function getSamples():Object;
+
+    public native function getSavedThis(obj:Function):Object;
+
+    public native function getSetterInvocationCount(obj:Object, qname:QName):Number;
+
+    public native function getSize(o:*):Number;
+
+    public native function isGetterSetter(obj:Object, qname:QName):Boolean;
+
+    public native function pauseSampling():void;
+
+    // Ruffle doesn't allocate script-visible objects internally.
+    public function sampleInternalAllocs(b:Boolean):void {
     }
-    
-    public function getSamples():Object {
-        stub_method("flash.sampler", "getSamples");
-        return {};
+
+    public function setSamplerCallback(f:Function):void {
+        stub_method("flash.sampler", "setSamplerCallback");
     }
-}
 
+    public native function startSampling():void;
+
+    public native function stopSampling():void;
+}
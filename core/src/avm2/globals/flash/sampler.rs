@@ -0,0 +1,380 @@
+//! `flash.sampler` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::method::Method;
+use crate::avm2::object::{ArrayObject, ClassObject, Object, QNameObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::property::Property;
+use crate::avm2::sampler::{value_size, Sample, SampleKind};
+use crate::avm2::value::Value;
+use crate::avm2::vtable::VTable;
+use crate::avm2::{ArrayStorage, Error, Multiname};
+use crate::string::AvmString;
+
+pub mod new_object_sample;
+
+/// Implements `flash.sampler.clearSamples`
+pub fn clear_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    sampler.write(activation.context.gc_context).clear();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.startSampling`
+pub fn start_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let start_time = activation.context.start_time;
+    let sampler = activation.avm2().sampler();
+    sampler
+        .write(activation.context.gc_context)
+        .start(start_time);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.pauseSampling`
+pub fn pause_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    sampler.write(activation.context.gc_context).pause();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.stopSampling`
+pub fn stop_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    sampler.write(activation.context.gc_context).stop();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    let mut sampler = sampler.write(activation.context.gc_context);
+    sampler.record_collected();
+    Ok(sampler.samples().len().into())
+}
+
+/// Implements `flash.sampler.getSamples`
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.avm2().sampler();
+    let samples = {
+        let mut sampler = sampler.write(activation.context.gc_context);
+        sampler.record_collected();
+        sampler.samples().iter().cloned().collect::<Vec<_>>()
+    };
+
+    // The objects created here shouldn't show up as samples themselves.
+    let was_sampling = sampler.read().is_sampling();
+    sampler.write(activation.context.gc_context).pause();
+
+    let result = sample_array(activation, &samples);
+
+    if was_sampling {
+        let start_time = activation.context.start_time;
+        sampler
+            .write(activation.context.gc_context)
+            .start(start_time);
+    }
+
+    result
+}
+
+fn sample_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    samples: &[Sample<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let samples = samples
+        .iter()
+        .map(|sample| Ok(Some(sample_object(activation, sample)?.into())))
+        .collect::<Result<Vec<_>, Error<'gc>>>()?;
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(samples))?.into())
+}
+
+/// Initialize a public const of a sample object.
+fn init_field<'gc>(
+    object: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<(), Error<'gc>> {
+    let name = Multiname::new(activation.avm2().public_namespace_base_version, name);
+    object.init_property(&name, value, activation)
+}
+
+/// Create a `Sample`, `NewObjectSample` or `DeleteObjectSample` object.
+fn sample_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    sample: &Sample<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let object = match sample.kind {
+        SampleKind::Cpu => {
+            let sample_class = activation.avm2().classes().sample;
+            sample_class.construct(activation, &[])?
+        }
+        SampleKind::NewObject { id, class, size } => {
+            let sample_class = activation.avm2().classes().newobjectsample;
+            let object = sample_class.construct(activation, &[])?;
+            init_field(object, "id", (id as f64).into(), activation)?;
+            let class = class.map_or(Value::Null, |class| class.into());
+            init_field(object, "type", class, activation)?;
+            object.set_property(
+                &Multiname::new(activation.avm2().flash_sampler_internal, "_size"),
+                (size as f64).into(),
+                activation,
+            )?;
+            object
+        }
+        SampleKind::DeleteObject { id, size } => {
+            let sample_class = activation.avm2().classes().deleteobjectsample;
+            let object = sample_class.construct(activation, &[])?;
+            init_field(object, "id", (id as f64).into(), activation)?;
+            init_field(object, "size", (size as f64).into(), activation)?;
+            object
+        }
+    };
+
+    init_field(object, "time", sample.time.into(), activation)?;
+    let stack = stack_frames(activation, &sample.stack)?;
+    init_field(object, "stack", stack, activation)?;
+    Ok(object)
+}
+
+/// Create an array of `StackFrame`s, or null for an empty stack.
+fn stack_frames<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    stack: &[String],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if stack.is_empty() {
+        return Ok(Value::Null);
+    }
+
+    let stack_frame = activation.avm2().classes().stackframe;
+    let frames = stack
+        .iter()
+        .map(|name| {
+            // `StackFrame.toString` adds the parentheses itself.
+            let name = name.strip_suffix("()").unwrap_or(name);
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let frame = stack_frame.construct(activation, &[])?;
+            init_field(frame, "name", name.into(), activation)?;
+            Ok(Some(frame.into()))
+        })
+        .collect::<Result<Vec<_>, Error<'gc>>>()?;
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(frames))?.into())
+}
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((value_size(args[0]) as f64).into())
+}
+
+/// Which method of a property to look up.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MethodKind {
+    Method,
+    Getter,
+    Setter,
+}
+
+/// Find the method of `obj` named by a `QName`.
+///
+/// A null name refers to the constructor of a class.
+fn find_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    kind: MethodKind,
+) -> Result<Option<Method<'gc>>, Error<'gc>> {
+    let obj = args.get_object(activation, 0, "obj")?;
+    let Some(name) = args.try_get_object(activation, 1) else {
+        return Ok(match (obj.as_class_object(), kind) {
+            (Some(class), MethodKind::Method) => {
+                Some(class.inner_class_definition().read().instance_init())
+            }
+            _ => None,
+        });
+    };
+    let Some(name) = name.as_qname_object() else {
+        return Ok(None);
+    };
+    let name = name.name().clone();
+
+    // Static methods of a class are found on the class itself, and its
+    // instance methods on the instance vtable.
+    let vtables = match obj.as_class_object() {
+        Some(class) => vec![class.class_vtable(), class.instance_vtable()],
+        None => obj.vtable().into_iter().collect(),
+    };
+
+    Ok(vtables
+        .into_iter()
+        .find_map(|vtable| method_in_vtable(vtable, &name, kind)))
+}
+
+fn method_in_vtable<'gc>(
+    vtable: VTable<'gc>,
+    name: &Multiname<'gc>,
+    kind: MethodKind,
+) -> Option<Method<'gc>> {
+    let disp_id = match (vtable.get_trait(name)?, kind) {
+        (Property::Method { disp_id }, MethodKind::Method) => disp_id,
+        (Property::Virtual { get, .. }, MethodKind::Getter) => get?,
+        (Property::Virtual { set, .. }, MethodKind::Setter) => set?,
+        _ => return None,
+    };
+    vtable.get_method(disp_id)
+}
+
+fn invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    kind: MethodKind,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(method) = find_method(activation, args, kind)? else {
+        return Ok((-1).into());
+    };
+
+    let count = activation.avm2().sampler().read().invocation_count(method);
+    Ok(count.into())
+}
+
+/// Implements `flash.sampler.getInvocationCount`
+pub fn get_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, MethodKind::Method)
+}
+
+/// Implements `flash.sampler.getGetterInvocationCount`
+pub fn get_getter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, MethodKind::Getter)
+}
+
+/// Implements `flash.sampler.getSetterInvocationCount`
+pub fn get_setter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, MethodKind::Setter)
+}
+
+/// Implements `flash.sampler.isGetterSetter`
+pub fn is_getter_setter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let is_accessor = find_method(activation, args, MethodKind::Getter)?.is_some()
+        || find_method(activation, args, MethodKind::Setter)?.is_some();
+    Ok(is_accessor.into())
+}
+
+/// Implements `flash.sampler.getLexicalScopes`
+pub fn get_lexical_scopes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let function = args.get_object(activation, 0, "obj")?;
+    let Some(scope) = function.as_executable().map(|exec| exec.scope()) else {
+        return Ok(Value::Null);
+    };
+
+    let scopes = (0..)
+        .map_while(|index| scope.get(index))
+        .map(|scope| Some(scope.values().into()))
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(scopes))?.into())
+}
+
+/// Implements `flash.sampler.getSavedThis`
+pub fn get_saved_this<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let function = args.get_object(activation, 0, "obj")?;
+    let receiver = function
+        .as_executable()
+        .and_then(|exec| exec.bound_receiver());
+    Ok(receiver.map_or(Value::Null, |receiver| receiver.into()))
+}
+
+/// Implements `flash.sampler.getMemberNames`
+pub fn get_member_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "o")?;
+    let instance_names = args.get_bool(1);
+
+    let class: Option<ClassObject<'gc>> = object.as_class_object();
+    let vtable = match class {
+        Some(class) if instance_names => Some(class.instance_vtable()),
+        _ => object.vtable(),
+    };
+
+    let mut names: Vec<Multiname<'gc>> = vtable
+        .map(|vtable| {
+            vtable
+                .resolved_traits()
+                .iter()
+                .map(|(name, ns, _)| Multiname::new(ns, name))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Dynamic properties are only listed for the object itself.
+    if class.is_none() || !instance_names {
+        let public = activation.avm2().public_namespace_base_version;
+        let mut last_index = object.get_next_enumerant(0, activation)?;
+        while let Some(index) = last_index {
+            let name = object
+                .get_enumerant_name(index, activation)?
+                .coerce_to_string(activation)?;
+            names.push(Multiname::new(public, name));
+            last_index = object.get_next_enumerant(index, activation)?;
+        }
+    }
+
+    let names = names
+        .into_iter()
+        .map(|name| Ok(Some(QNameObject::from_name(activation, name)?.into())))
+        .collect::<Result<Vec<_>, Error<'gc>>>()?;
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(names))?.into())
+}
@@ -0,0 +1,24 @@
+//! `flash.sampler.NewObjectSample` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `NewObjectSample.object`, which is undefined once the object
+/// has been collected.
+pub fn get_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = this
+        .get_public_property("id", activation)?
+        .coerce_to_number(activation)? as u64;
+
+    let sampler = activation.avm2().sampler();
+    let object = sampler
+        .read()
+        .live_object(id, activation.context.gc_context);
+    Ok(object.map_or(Value::Undefined, |object| object.into()))
+}
@@ -0,0 +1,17 @@
+package flash.system {
+    import flash.events.EventDispatcher;
+
+    [Ruffle(NativeInstanceInit)]
+    public final class MessageChannel extends EventDispatcher {
+        public function MessageChannel() {
+            throw new ArgumentError("Error #2012: MessageChannel$ class cannot be instantiated.", 2012);
+        }
+
+        public native function get messageAvailable():Boolean;
+        public native function get state():String;
+
+        public native function close():void;
+        public native function receive(blockUntilReceived:Boolean = false):*;
+        public native function send(arg:*, queueLimit:int = -1):void;
+    }
+}
@@ -1,11 +1,25 @@
 package flash.system {
-    public final class Worker {
+    import flash.events.EventDispatcher;
+
+    [Ruffle(NativeInstanceInit)]
+    public final class Worker extends EventDispatcher {
         public function Worker() {
             throw new ArgumentError("Error #2012: Worker$ class cannot be instantiated.", 2012);
         }
 
         public static function get isSupported():Boolean {
-            return false;
+            return true;
         }
+
+        public static native function get current():Worker;
+
+        public native function get isPrimordial():Boolean;
+        public native function get state():String;
+
+        public native function createMessageChannel(receiver:Worker):MessageChannel;
+        public native function getSharedProperty(key:String):*;
+        public native function setSharedProperty(key:String, value:*):void;
+        public native function start():void;
+        public native function terminate():Boolean;
     }
 }
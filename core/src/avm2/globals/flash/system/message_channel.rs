@@ -0,0 +1,120 @@
+//! `flash.system.MessageChannel` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::illegal_operation_error;
+use crate::avm2::globals::flash::concurrent::mutex::wait_for_workers;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::worker::{MessageChannelState, Transferable};
+use crate::avm2::Error;
+
+/// Implements `flash.system.MessageChannel`'s native instance constructor.
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.messageAvailable`
+pub fn get_message_available<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(channel) = this.as_message_channel_object() {
+        return Ok(channel.channel().message_available().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.state`
+pub fn get_state<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(channel) = this.as_message_channel_object() {
+        return Ok(channel.channel().state().as_str().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(channel) = this.as_message_channel_object() {
+        channel.channel().close(activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.receive`
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let block_until_received = args.get_bool(0);
+
+    if let Some(channel) = this.as_message_channel_object() {
+        let channel = channel.channel();
+
+        // Nothing more can arrive on a closed channel, so there's nothing to wait for.
+        if block_until_received {
+            wait_for_workers(activation, None, |_| {
+                channel.message_available() || channel.state() == MessageChannelState::Closed
+            })?;
+        }
+
+        if let Some(message) = channel.receive(activation.context.gc_context) {
+            return message.to_value(activation);
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `MessageChannel.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let message = Transferable::from_value(activation, args.get_value(0))?;
+    let queue_limit = args.get_i32(activation, 1)?;
+
+    if let Some(channel) = this.as_message_channel_object() {
+        let channel = channel.channel();
+
+        // The sender waits for the receiver to shrink the queue.
+        if queue_limit >= 0 {
+            wait_for_workers(activation, None, |_| {
+                channel.queue_length() < queue_limit as usize
+                    || channel.state() != MessageChannelState::Open
+            })?;
+        }
+
+        if channel.state() != MessageChannelState::Open {
+            return Err(Error::AvmError(illegal_operation_error(
+                activation,
+                "The message channel is closed.",
+                0,
+            )?));
+        }
+
+        channel.send(activation.context.gc_context, message);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -2,9 +2,11 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
+use crate::backend::storage::storage_domain;
 use crate::string::AvmString;
 use url::Url;
 
@@ -63,17 +65,31 @@ pub fn allow_insecure_domain<'gc>(
 pub fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "loadPolicyFile");
+    let url = args.get_string(activation, 0)?.to_utf8_lossy();
+    match activation.context.navigator.resolve_url(&url) {
+        Ok(url) => activation.context.policy_files.load_policy_file(url),
+        Err(e) => tracing::warn!("flash.system.Security.loadPolicyFile: Invalid URL {url}: {e}"),
+    }
     Ok(Value::Undefined)
 }
 
 pub fn show_settings<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "showSettings");
+    let panel = args.get_string(activation, 0)?;
+    if &panel == b"localStorage" {
+        if let Ok(url) = Url::parse(activation.context.swf.url()) {
+            activation
+                .context
+                .ui
+                .display_storage_settings(storage_domain(&url));
+        }
+    } else {
+        avm2_stub_method!(activation, "flash.system.Security", "showSettings");
+    }
     Ok(Value::Undefined)
 }
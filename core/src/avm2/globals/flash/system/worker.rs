@@ -0,0 +1,139 @@
+//! `flash.system.Worker` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::worker::{Transferable, Worker};
+use crate::avm2::Error;
+
+/// Implements `flash.system.Worker`'s native instance constructor.
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.current`
+pub fn get_current<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let worker = Worker::current(&mut activation.context);
+    Ok(worker.object(activation)?.into())
+}
+
+/// Implements `Worker.isPrimordial`
+pub fn get_is_primordial<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(worker) = this.as_worker_object() {
+        return Ok(worker.worker().is_primordial().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.state`
+pub fn get_state<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(worker) = this.as_worker_object() {
+        return Ok(worker.worker().state().as_str().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.createMessageChannel`
+pub fn create_message_channel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let receiver = args.get_object(activation, 0, "receiver")?;
+
+    if let (Some(sender), Some(receiver)) = (this.as_worker_object(), receiver.as_worker_object()) {
+        let channel = sender
+            .worker()
+            .create_message_channel(activation.context.gc_context, receiver.worker());
+        return Ok(channel.object(activation)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Worker.getSharedProperty`
+pub fn get_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let key = args.get_string(activation, 0)?;
+
+    if let Some(worker) = this.as_worker_object() {
+        if let Some(value) = worker.worker().shared_property(&key.to_utf8_lossy()) {
+            return value.to_value(activation);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.setSharedProperty`
+pub fn set_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let key = args.get_string(activation, 0)?;
+    let value = Transferable::from_value(activation, args.get_value(1))?;
+
+    if let Some(worker) = this.as_worker_object() {
+        worker.worker().set_shared_property(
+            activation.context.gc_context,
+            key.to_utf8_lossy().into_owned(),
+            value,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.start`
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(worker) = this.as_worker_object() {
+        worker.worker().start(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.terminate`
+pub fn terminate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(worker) = this.as_worker_object() {
+        return Ok(worker
+            .worker()
+            .terminate(activation.context.gc_context)
+            .into());
+    }
+
+    Ok(false.into())
+}
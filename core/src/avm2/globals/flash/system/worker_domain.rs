@@ -0,0 +1,50 @@
+//! `flash.system.WorkerDomain` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::argument_error;
+use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::worker::Worker;
+use crate::avm2::Error;
+
+/// Implements `WorkerDomain.createWorker`
+pub fn create_worker<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let swf = args.get_object(activation, 0, "swf")?;
+    let bytes = swf
+        .as_bytearray()
+        .map(|bytes| bytes.bytes().to_vec())
+        .unwrap_or_default();
+
+    let Some(worker) = Worker::create(&mut activation.context, &bytes) else {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2004: One of the parameters is invalid.",
+            2004,
+        )?));
+    };
+
+    Ok(worker.object(activation)?.into())
+}
+
+/// Implements `WorkerDomain.listWorkers`
+pub fn list_workers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let workers = Worker::current(&mut activation.context).workers();
+    let mut values: Vec<Value<'gc>> = Vec::with_capacity(workers.len());
+    for worker in workers {
+        values.push(worker.object(activation)?.into());
+    }
+
+    let storage =
+        VectorStorage::from_values(values, false, Some(activation.avm2().classes().worker));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
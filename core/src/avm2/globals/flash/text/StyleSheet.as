@@ -1,37 +1,60 @@
 package flash.text {
-    
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
-    
-    public dynamic class StyleSheet {
+    import flash.events.EventDispatcher;
+
+    public dynamic class StyleSheet extends EventDispatcher {
+        // Copies of the style objects, keyed by lowercase style name.
+        internal var _styles:Object = {};
+
         public function StyleSheet() {}
-        
+
         public function get styleNames():Array {
-            stub_getter("flash.text.StyleSheet", "styleNames");
-            return [];
+            var names:Array = [];
+            for (var name:String in this._styles) {
+                names.push(name);
+            }
+            return names;
         }
-        
+
         public function clear():void {
-            stub_method("flash.text.StyleSheet", "clear");
+            this._styles = {};
         }
-        
+
         public function getStyle(styleName:String):Object {
-            stub_method("flash.text.StyleSheet", "getStyle");
-            return null;
+            var style:Object = this._styles[styleName.toLowerCase()];
+            if (style == null) {
+                return null;
+            }
+            return copyStyle(style);
         }
-        
+
         public function parseCSS(CSSText:String):void {
-            stub_method("flash.text.StyleSheet", "parseCSS");
+            var styles:Object = this.innerParseCSS(CSSText);
+            if (styles == null) {
+                return;
+            }
+            for (var name:String in styles) {
+                this._styles[name] = styles[name];
+            }
         }
-        
+
         public function setStyle(styleName:String, styleObject:Object):void {
-            stub_method("flash.text.StyleSheet", "setStyle");
+            if (styleObject == null) {
+                delete this._styles[styleName.toLowerCase()];
+            } else {
+                this._styles[styleName.toLowerCase()] = copyStyle(styleObject);
+            }
         }
-        
-        public function transform(formatObject:Object):TextFormat {
-            stub_method("flash.text.StyleSheet", "transform");
-            return null;
+
+        public native function transform(formatObject:Object):TextFormat;
+
+        private native function innerParseCSS(css:String):Object;
+
+        private static function copyStyle(style:Object):Object {
+            var copy:Object = {};
+            for (var key:String in style) {
+                copy[key] = style[key];
+            }
+            return copy;
         }
     }
 }
@@ -28,5 +28,13 @@ package flash.text.engine {
         public function set elementFormat(value:ElementFormat):void {
             this._elementFormat = value;
         }
+
+        // Adds the text of this element, its format and whether it's a GraphicElement
+        // to the runs laid out by a TextBlock.
+        internal function appendRuns(runs:Array, parentFormat:ElementFormat):void {
+            if (this._text) {
+                runs.push(this._text, this._elementFormat ? this._elementFormat : parentFormat, null);
+            }
+        }
     }
 }
@@ -1,6 +1,4 @@
 package flash.text.engine {
-    import __ruffle__.stub_method;
-
     public final class TextBlock {
         public var userData;
 
@@ -16,6 +14,7 @@ package flash.text.engine {
 
         internal var _textLineCreationResult:String = null;
         internal var _firstLine:TextLine = null;
+        internal var _lastLine:TextLine = null;
 
 
         public function TextBlock(content:ContentElement = null,
@@ -92,6 +91,7 @@ package flash.text.engine {
 
         public function set bidiLevel(value:int):void {
             this._bidiLevel = value;
+            this.invalidateLines();
         }
 
         public function get lineRotation():String {
@@ -114,6 +114,7 @@ package flash.text.engine {
         // Note: FP makes a copy of the Vector passed to it, then sets its internal representation to that
         public function set tabStops(value:Vector.<TabStop>):void {
             this._tabStops = value;
+            this.invalidateLines();
         }
 
         public function get textJustifier():TextJustifier {
@@ -122,6 +123,7 @@ package flash.text.engine {
 
         public function set textJustifier(value:TextJustifier):void {
             this._textJustifier = value;
+            this.invalidateLines();
         }
 
         public function get content():ContentElement {
@@ -130,25 +132,74 @@ package flash.text.engine {
 
         public function set content(value:ContentElement):void {
             this._content = value;
+            this.invalidateLines();
         }
 
-        public native function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine;
+        public function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine {
+            return this.layoutLine(null, previousLine, width, lineOffset, fitSomething);
+        }
 
         public function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine {
-            if (textLine == null) {
+            if (textLine == null || (textLine._textBlock != null && textLine._textBlock != this)) {
                 throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
             }
 
-            if (previousLine) {
+            return this.layoutLine(textLine, previousLine, width, lineOffset, fitSomething);
+        }
+
+        private function layoutLine(textLine:TextLine, previousLine:TextLine, width:Number, lineOffset:Number, fitSomething:Boolean):TextLine {
+            if (previousLine != null && previousLine._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (!(width >= 0 && width <= TextLine.MAX_LINE_WIDTH)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (this._content == null) {
                 return null;
             }
 
-            stub_method("flash.text.engine.TextBlock", "recreateTextLine");
+            var runs:Array = [];
+            this._content.appendRuns(runs, null);
+
+            var line:TextLine = this.breakLine(runs, textLine, previousLine, width, lineOffset, fitSomething);
+            if (line == null) {
+                return null;
+            }
+
+            // The new line replaces every line that was after the previous one.
+            var oldLine:TextLine = previousLine ? previousLine._nextLine : this._firstLine;
+            while (oldLine != null) {
+                var nextLine:TextLine = oldLine._nextLine;
+                if (oldLine != line) {
+                    oldLine._validity = TextLineValidity.INVALID;
+                    oldLine._textBlock = null;
+                    oldLine._previousLine = null;
+                    oldLine._nextLine = null;
+                }
+                oldLine = nextLine;
+            }
+
+            line._textBlock = this;
+            line._validity = TextLineValidity.VALID;
+            line._specifiedWidth = width;
+            line._previousLine = previousLine;
+            line._nextLine = null;
+            if (previousLine) {
+                previousLine._nextLine = line;
+            } else {
+                this._firstLine = line;
+            }
+            this._lastLine = line;
 
-            // FIXME: Properly recalculate new properties of new TextLine. Text layout
-            // modules often depend on this returning the same textLine, so we can't
-            // call `createTextLine` again.
-            return textLine;
+            return line;
+        }
+
+        private native function breakLine(runs:Array, textLine:TextLine, previousLine:TextLine, width:Number, lineOffset:Number, fitSomething:Boolean):TextLine;
+
+        private function invalidateLines():void {
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                line._validity = TextLineValidity.INVALID;
+            }
         }
 
         public function get textLineCreationResult():String {
@@ -160,16 +211,48 @@ package flash.text.engine {
         }
 
         public function get lastLine():TextLine {
-            return this._firstLine;
+            return this._lastLine;
         }
-        
-        public function releaseLines(start:TextLine, end:TextLine):void {
-            if (start != end || end != this._firstLine) {
-                stub_method("flash.text.engine.TextBlock", "releaseLines", "with start != end or multiple lines");
-                return;
+
+        public function get firstInvalidLine():TextLine {
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                if (line._validity != TextLineValidity.VALID) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function releaseLines(firstLine:TextLine, lastLine:TextLine):void {
+            if (firstLine == null || lastLine == null || firstLine._textBlock != this || lastLine._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var before:TextLine = firstLine._previousLine;
+            var after:TextLine = lastLine._nextLine;
+            var line:TextLine = firstLine;
+            while (line != null) {
+                var next:TextLine = line._nextLine;
+                line._validity = TextLineValidity.INVALID;
+                line._textBlock = null;
+                line._previousLine = null;
+                line._nextLine = null;
+                if (line == lastLine) {
+                    break;
+                }
+                line = next;
+            }
+
+            if (before) {
+                before._nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after) {
+                after._previousLine = before;
+            } else {
+                this._lastLine = before;
             }
-            this._firstLine._validity = "invalid";
-            this._firstLine._textBlock = null;
         }
     }
 }
@@ -1,21 +1,14 @@
 package flash.text.engine {
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
-    import __ruffle__.stub_method;
-
     import flash.display.DisplayObjectContainer;
     import flash.geom.Rectangle;
 
-    // FIXME: None of the DisplayObjectContainer methods actually work on
-    // the TextLine class in Ruffle, despite the methods working fine in FP-
-    // however, it's unlikely that SWFs will actually attempt to add children
-    // to a TextLine.
     [Ruffle(NativeInstanceInit)]
     public final class TextLine extends DisplayObjectContainer {
         internal var _specifiedWidth:Number = 0.0;
         internal var _textBlock:TextBlock = null;
-        internal var _rawTextLength:int = 0;
         internal var _validity:String = "valid";
+        internal var _previousLine:TextLine = null;
+        internal var _nextLine:TextLine = null;
 
         public static const MAX_LINE_WIDTH:int = 1000000;
 
@@ -25,14 +18,9 @@ package flash.text.engine {
             throw new ArgumentError("Error #2012: TextLine$ class cannot be instantiated.", 2012);
         }
 
-        public function get rawTextLength():int {
-            return this._rawTextLength;
-        }
+        public native function get rawTextLength():int;
 
-        public function get textBlockBeginIndex():int {
-            stub_getter("flash.text.engine.TextLine", "textBlockBeginIndex");
-            return 0;
-        }
+        public native function get textBlockBeginIndex():int;
 
         public function get specifiedWidth():Number {
             return this._specifiedWidth;
@@ -42,78 +30,74 @@ package flash.text.engine {
             return this._textBlock;
         }
 
-        public function get ascent():Number {
-            stub_getter("flash.text.engine.TextLine", "ascent");
-            return 12.0;
-        }
+        public native function get ascent():Number;
+
+        public native function get descent():Number;
 
-        public function get descent():Number {
-            stub_getter("flash.text.engine.TextLine", "descent");
-            return 3.0;
+        // Graphic elements are already included in `ascent` and `descent`.
+        public function get totalAscent():Number {
+            return this.ascent;
         }
 
-        public function get unjustifiedTextWidth():Number {
-            stub_getter("flash.text.engine.TextLine", "unjustifiedTextWidth");
-            return this._specifiedWidth;
+        public function get totalDescent():Number {
+            return this.descent;
         }
 
-        public function get textWidth():Number {
-            stub_getter("flash.text.engine.TextLine", "textWidth");
-            return this._specifiedWidth;
+        public function get totalHeight():Number {
+            return this.ascent + this.descent;
         }
 
+        public native function get unjustifiedTextWidth():Number;
+
+        public native function get textWidth():Number;
+
         public function get textHeight():Number {
-            stub_getter("flash.text.engine.TextLine", "textHeight");
-            return 15.0;
+            return this.ascent + this.descent;
         }
 
         public function get validity():String {
-            stub_getter("flash.text.engine.TextLine", "validity");
             return this._validity;
         }
 
         public function set validity(value:String):void {
-            stub_setter("flash.text.engine.TextLine", "validity");
+            if (value != TextLineValidity.VALID && value != TextLineValidity.INVALID &&
+                value != TextLineValidity.POSSIBLY_INVALID && value != TextLineValidity.STATIC) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
             this._validity = value;
         }
 
-        public function get hasGraphicElement():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasGraphicElement");
-            return false;
-        }
+        public native function get hasGraphicElement():Boolean;
 
-        public function get atomCount():int {
-            stub_getter("flash.text.engine.TextLine", "atomCount");
-            return this._rawTextLength;
-        }
+        public native function get hasTabs():Boolean;
+
+        public native function get atomCount():int;
 
         public function get nextLine():TextLine {
-            return null;
+            return this._nextLine;
         }
 
         public function get previousLine():TextLine {
-            return null;
+            return this._previousLine;
         }
 
-        public function getBaselinePosition(baseline:String):Number {
-            stub_method("flash.text.engine.TextLine", "getBaselinePosition");
-            return 0.0;
-        }
+        public native function getBaselinePosition(baseline:String):Number;
 
-        public function hasTabs():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasTabs");
-            return false;
-        }
+        public native function getAtomIndexAtPoint(stageX:Number, stageY:Number):int;
 
-        public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtPoint");
-            return -1;
-        }
+        public native function getAtomIndexAtCharIndex(charIndex:int):int;
 
-        public function getAtomBounds(index:int):Rectangle {
-            stub_method("flash.text.engine.TextLine", "getAtomBounds");
-            return new Rectangle(0, 0, 0, 0);
-        }
+        public native function getAtomBounds(atomIndex:int):Rectangle;
+
+        public native function getAtomCenter(atomIndex:int):Number;
+
+        public native function getAtomBidiLevel(atomIndex:int):int;
+
+        public native function getAtomWordBoundaryOnLeft(atomIndex:int):Boolean;
+
+        public native function getAtomTextBlockBeginIndex(atomIndex:int):int;
+
+        public native function getAtomTextBlockEndIndex(atomIndex:int):int;
 
         // This function does nothing in Flash Player 32
         public function flushAtomData():void { }
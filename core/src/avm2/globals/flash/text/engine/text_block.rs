@@ -5,155 +5,307 @@ use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
-use crate::avm2_stub_method;
-use crate::display_object::{EditText, TDisplayObject};
-use crate::html::TextFormat;
-use crate::string::WStr;
+use crate::display_object::{DisplayObject, TDisplayObject, TextLine};
+use crate::html::{
+    layout_line, BreakOpportunity, ContentRun, Justifier, LineJustification, LineRequest,
+    TabAlignment, TabStop, TextBlockSettings, TextFormat,
+};
+use crate::string::WString;
 
-pub fn create_text_line<'gc>(
+/// Implements `TextBlock.breakLine`, which lays out the line after `previousLine`.
+///
+/// If `textLine` is given, it's laid out again instead of creating a new line.
+/// Linking the line into the block is left to ActionScript.
+pub fn break_line<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.TextBlock", "createTextLine");
+    let runs = args.get_object(activation, 0, "runs")?;
+    let text_line = args.try_get_object(activation, 1);
+    let previous_line = args.try_get_object(activation, 2);
+    let width = args.get_f64(activation, 3)?;
+    let line_offset = args.get_f64(activation, 4)?;
+    let fit_something = args.get_bool(5);
 
-    let previous_text_line = args.try_get_object(activation, 0);
-    let width = args.get_f64(activation, 1)?;
+    let (runs, graphics) = content_runs(activation, runs)?;
+    let settings = block_settings(activation, this)?;
 
-    let content = this.get_public_property("content", activation)?;
-
-    let content = if matches!(content, Value::Null) {
-        return Ok(Value::Null);
-    } else {
-        content.as_object().unwrap()
+    let begin = match previous_line {
+        Some(previous_line) => previous_line
+            .as_display_object()
+            .and_then(|d| d.as_text_line())
+            .map(|line| {
+                let layout = line.layout();
+                layout.begin + layout.raw_text_length
+            })
+            .unwrap_or_default(),
+        None => 0,
     };
 
-    let text = match previous_text_line {
-        Some(_) => {
-            // Some SWFs rely on eventually getting `null` from createLineText.
-            // TODO: Support multiple lines
-            this.set_property(
-                &Multiname::new(
-                    activation.avm2().flash_text_engine_internal,
-                    "_textLineCreationResult",
-                ),
-                "complete".into(),
-                activation,
-            )?;
-            return Ok(Value::Null);
-        }
-        // Get the content element's text property.
-        // TODO: GraphicElement?
-        None => {
-            let txt = content
-                .get_public_property("text", activation)
-                .unwrap_or("".into());
-
-            if matches!(txt, Value::Null) {
-                // FP returns a null TextLine when `o` is null- note that
-                // `o` is already coerced to a String because of the AS bindings.
-                return Ok(Value::Null);
-            } else {
-                txt.coerce_to_string(activation)
-                    .expect("Guaranteed by AS bindings")
-            }
-        }
+    let request = LineRequest {
+        begin,
+        width,
+        line_offset,
+        fit_something,
     };
-
-    let class = activation.avm2().classes().textline;
     let movie = activation.context.swf.clone();
+    let (result, line) = layout_line(&mut activation.context, movie, &runs, &settings, request);
 
-    // FIXME: TextLine should be its own DisplayObject
-    let display_object: EditText =
-        EditText::new_tlf(&mut activation.context, movie, 0.0, 0.0, width, 15.0);
-
-    display_object.set_text(text.as_wstr(), &mut activation.context);
+    this.set_property(
+        &Multiname::new(
+            activation.avm2().flash_text_engine_internal,
+            "_textLineCreationResult",
+        ),
+        result.as_str().into(),
+        activation,
+    )?;
 
-    // FIXME: This needs to use `intrinsic_bounds` to measure the width
-    // of the provided text, and set the width of the EditText to that.
-    // Some games depend on this (e.g. Realm Grinder).
+    let Some(line) = line else {
+        return Ok(Value::Null);
+    };
 
-    let element_format = content
-        .get_public_property("elementFormat", activation)?
-        .as_object();
+    let graphics = line
+        .graphics
+        .iter()
+        .filter_map(|placed| Some((graphics[placed.run]?, placed.x, placed.y)))
+        .collect();
 
-    apply_format(activation, display_object, text.as_wstr(), element_format)?;
+    let existing = text_line.and_then(|l| l.as_display_object()?.as_text_line());
+    if let (Some(text_line), Some(display_object)) = (text_line, existing) {
+        display_object.set_line(&mut activation.context, line, graphics);
+        return Ok(text_line.into());
+    }
 
+    let class = activation.avm2().classes().textline;
+    let movie = activation.context.swf.clone();
+    let display_object = TextLine::new(activation, movie);
     let instance = initialize_for_allocator(activation, display_object.into(), class)?;
     class.call_native_init(instance.into(), &[], activation)?;
 
-    instance.set_property(
-        &Multiname::new(activation.avm2().flash_text_engine_internal, "_textBlock"),
-        this.into(),
-        activation,
-    )?;
+    // Graphics are added as children, which needs the line's AVM2 object to exist.
+    display_object.set_line(&mut activation.context, line, graphics);
 
-    instance.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_specifiedWidth",
-        ),
-        args.get_value(1),
-        activation,
-    )?;
+    Ok(instance.into())
+}
 
-    instance.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_rawTextLength",
-        ),
-        text.len().into(),
-        activation,
-    )?;
+/// Read the `[text, elementFormat, graphicElement, ...]` triples collected from a block's content.
+///
+/// `graphicElement` is null for anything but a `GraphicElement`. Along with the
+/// runs, this returns the graphic displayed by each run, if any.
+#[allow(clippy::type_complexity)]
+fn content_runs<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    runs: Object<'gc>,
+) -> Result<(Vec<ContentRun>, Vec<Option<DisplayObject<'gc>>>), Error<'gc>> {
+    let values: Vec<_> = runs
+        .as_array_storage()
+        .map(|storage| storage.iter().map(|v| v.unwrap_or(Value::Null)).collect())
+        .unwrap_or_default();
 
-    this.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_textLineCreationResult",
-        ),
-        "success".into(),
-        activation,
-    )?;
+    let mut content_runs = Vec::with_capacity(values.len() / 3);
+    let mut graphics = Vec::with_capacity(values.len() / 3);
+    for triple in values.chunks_exact(3) {
+        let text = triple[0].coerce_to_string(activation)?;
+        let element_format = triple[1].as_object();
+        let mut run = content_run(activation, text.as_wstr().into(), element_format)?;
+        let mut graphic = None;
 
-    this.set_property(
-        &Multiname::new(activation.avm2().flash_text_engine_internal, "_firstLine"),
-        instance.into(),
-        activation,
-    )?;
+        if let Some(graphic_element) = triple[2].as_object() {
+            let width = graphic_element
+                .get_public_property("elementWidth", activation)?
+                .coerce_to_number(activation)?;
+            let height = graphic_element
+                .get_public_property("elementHeight", activation)?
+                .coerce_to_number(activation)?;
+            run.graphic_size = Some((width, height));
+            graphic = graphic_element
+                .get_public_property("graphic", activation)?
+                .as_object()
+                .and_then(|graphic| graphic.as_display_object());
+        }
 
-    Ok(instance.into())
+        content_runs.push(run);
+        graphics.push(graphic);
+    }
+
+    Ok((content_runs, graphics))
 }
 
-fn apply_format<'gc>(
+fn content_run<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    display_object: EditText<'gc>,
-    text: &WStr,
+    text: WString,
     element_format: Option<Object<'gc>>,
-) -> Result<(), Error<'gc>> {
-    if let Some(element_format) = element_format {
-        // TODO: Support more ElementFormat properties
-        let color = element_format
-            .get_public_property("color", activation)?
-            .coerce_to_u32(activation)?;
-        let size = element_format
-            .get_public_property("fontSize", activation)?
-            .coerce_to_number(activation)?;
-
-        let format = TextFormat {
-            color: Some(swf::Color::from_rgb(color, 0xFF)),
-            size: Some(size),
+) -> Result<ContentRun, Error<'gc>> {
+    // These match the defaults of `ElementFormat` and `FontDescription`.
+    let mut run = ContentRun {
+        text,
+        format: TextFormat {
+            font: Some(WString::from_utf8("_serif")),
+            size: Some(12.0),
+            color: Some(swf::Color::BLACK),
+            bold: Some(false),
+            italic: Some(false),
+            kerning: Some(true),
             ..TextFormat::default()
-        };
+        },
+        is_device_font: true,
+        break_opportunity: BreakOpportunity::Auto,
+        baseline_shift: 0.0,
+        tracking_left: 0.0,
+        tracking_right: 0.0,
+        graphic_size: None,
+    };
 
-        display_object.set_text_format(0, text.len(), format.clone(), &mut activation.context);
-        display_object.set_new_text_format(format, &mut activation.context);
+    let Some(element_format) = element_format else {
+        return Ok(run);
+    };
+
+    let color = element_format
+        .get_public_property("color", activation)?
+        .coerce_to_u32(activation)?;
+    let size = element_format
+        .get_public_property("fontSize", activation)?
+        .coerce_to_number(activation)?;
+    let kerning = element_format
+        .get_public_property("kerning", activation)?
+        .coerce_to_string(activation)?;
+    run.format.color = Some(swf::Color::from_rgb(color, 0xFF));
+    run.format.size = Some(size);
+    run.format.kerning = Some(&kerning != b"off");
+
+    run.baseline_shift = element_format
+        .get_public_property("baselineShift", activation)?
+        .coerce_to_number(activation)?;
+    run.tracking_left = element_format
+        .get_public_property("trackingLeft", activation)?
+        .coerce_to_number(activation)?;
+    run.tracking_right = element_format
+        .get_public_property("trackingRight", activation)?
+        .coerce_to_number(activation)?;
+
+    let break_opportunity = element_format
+        .get_public_property("breakOpportunity", activation)?
+        .coerce_to_string(activation)?;
+    run.break_opportunity = if &break_opportunity == b"any" {
+        BreakOpportunity::Any
+    } else if &break_opportunity == b"all" {
+        BreakOpportunity::All
+    } else if &break_opportunity == b"none" {
+        BreakOpportunity::None
+    } else {
+        BreakOpportunity::Auto
+    };
+
+    if let Some(font_description) = element_format
+        .get_public_property("fontDescription", activation)?
+        .as_object()
+    {
+        let font_name = font_description
+            .get_public_property("fontName", activation)?
+            .coerce_to_string(activation)?;
+        let font_weight = font_description
+            .get_public_property("fontWeight", activation)?
+            .coerce_to_string(activation)?;
+        let font_posture = font_description
+            .get_public_property("fontPosture", activation)?
+            .coerce_to_string(activation)?;
+        let font_lookup = font_description
+            .get_public_property("fontLookup", activation)?
+            .coerce_to_string(activation)?;
+
+        run.format.font = Some(font_name.as_wstr().into());
+        run.format.bold = Some(&font_weight == b"bold");
+        run.format.italic = Some(&font_posture == b"italic");
+        // TODO: `embeddedCFF` fonts come from DefineFont4, which we can't render yet.
+        run.is_device_font = &font_lookup == b"device";
+    }
+
+    Ok(run)
+}
+
+fn block_settings<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<TextBlockSettings, Error<'gc>> {
+    let mut settings = TextBlockSettings {
+        bidi_level: this
+            .get_public_property("bidiLevel", activation)?
+            .coerce_to_i32(activation)?
+            .clamp(0, 125) as u8,
+        ..Default::default()
+    };
+
+    if let Some(tab_stops) = this
+        .get_public_property("tabStops", activation)?
+        .as_object()
+    {
+        let tab_stops: Vec<_> = tab_stops
+            .as_vector_storage()
+            .map(|storage| storage.iter().collect())
+            .unwrap_or_default();
+        for tab_stop in tab_stops.iter().filter_map(|v| v.as_object()) {
+            let alignment = tab_stop
+                .get_public_property("alignment", activation)?
+                .coerce_to_string(activation)?;
+            let position = tab_stop
+                .get_public_property("position", activation)?
+                .coerce_to_number(activation)?;
+            let decimal_token = tab_stop
+                .get_public_property("decimalAlignmentToken", activation)?
+                .coerce_to_string(activation)?;
+
+            let alignment = if &alignment == b"center" {
+                TabAlignment::Center
+            } else if &alignment == b"end" {
+                TabAlignment::End
+            } else if &alignment == b"decimal" {
+                TabAlignment::Decimal
+            } else {
+                TabAlignment::Start
+            };
+            settings.tab_stops.push(TabStop {
+                alignment,
+                position,
+                decimal_token: decimal_token.as_wstr().into(),
+            });
+        }
+        settings
+            .tab_stops
+            .sort_by(|a, b| a.position.total_cmp(&b.position));
     }
 
-    display_object.set_word_wrap(true, &mut activation.context);
+    if let Some(justifier) = this
+        .get_public_property("textJustifier", activation)?
+        .as_object()
+    {
+        let line_justification = justifier
+            .get_public_property("lineJustification", activation)?
+            .coerce_to_string(activation)?;
+        let line_justification = if &line_justification == b"allButLast" {
+            LineJustification::AllButLast
+        } else if &line_justification == b"allButMandatoryBreak" {
+            LineJustification::AllButMandatoryBreak
+        } else if &line_justification == b"allIncludingLast" {
+            LineJustification::AllIncludingLast
+        } else {
+            LineJustification::Unjustified
+        };
 
-    let measured_text = display_object.measure_text(&mut activation.context);
+        // `EastAsianJustifier` has no `letterSpacing`, and always spaces out every character.
+        let letter_spacing = if justifier.has_public_property("letterSpacing", activation) {
+            justifier
+                .get_public_property("letterSpacing", activation)?
+                .coerce_to_boolean()
+        } else {
+            true
+        };
 
-    display_object.set_height(&mut activation.context, measured_text.1.to_pixels());
+        settings.justifier = Justifier {
+            line_justification,
+            letter_spacing,
+        };
+    }
 
-    Ok(())
+    Ok(settings)
 }
@@ -1,7 +1,11 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::error::Error;
-use crate::avm2::object::Object;
+use crate::avm2::error::{argument_error, range_error, Error};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
+use crate::display_object::TDisplayObject;
+use crate::html::TextLineLayout;
+use swf::Point;
 
 pub fn native_instance_init<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -11,3 +15,256 @@ pub fn native_instance_init<'gc>(
     activation.super_init(this, &[])?;
     Ok(Value::Undefined)
 }
+
+/// Run `f` with the layout of a `TextLine`.
+///
+/// Every `TextLine` is laid out by `TextBlock` before it's given to scripts.
+fn with_layout<'gc, R>(this: Object<'gc>, f: impl FnOnce(&TextLineLayout) -> R) -> Option<R> {
+    let text_line = this.as_display_object()?.as_text_line()?;
+    let result = f(&text_line.layout());
+    Some(result)
+}
+
+fn layout_value<'gc>(
+    this: Object<'gc>,
+    f: impl FnOnce(&TextLineLayout) -> Value<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(with_layout(this, f).unwrap_or(Value::Undefined))
+}
+
+/// Get the index of the atom requested by the first argument, throwing if it's out of bounds.
+fn atom_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<usize, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let atom_count = with_layout(this, |layout| layout.atoms.len()).unwrap_or(0);
+    if index < 0 || index as usize >= atom_count {
+        return Err(Error::AvmError(range_error(
+            activation,
+            "Error #2006: The supplied index is out of bounds.",
+            2006,
+        )?));
+    }
+    Ok(index as usize)
+}
+
+pub fn get_ascent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.ascent.into())
+}
+
+pub fn get_descent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.descent.into())
+}
+
+pub fn get_text_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.text_width.into())
+}
+
+pub fn get_unjustified_text_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.unjustified_text_width.into())
+}
+
+pub fn get_raw_text_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.raw_text_length.into())
+}
+
+pub fn get_text_block_begin_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.begin.into())
+}
+
+pub fn get_atom_count<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.atoms.len().into())
+}
+
+pub fn get_has_tabs<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.has_tabs.into())
+}
+
+pub fn get_has_graphic_element<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    layout_value(this, |layout| layout.has_graphic_element.into())
+}
+
+pub fn get_baseline_position<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let baseline = args.get_string(activation, 0)?;
+    let Some((ascent, descent)) = with_layout(this, |layout| (layout.ascent, layout.descent))
+    else {
+        return Ok(Value::Undefined);
+    };
+
+    // Positions are relative to the roman baseline, which is the line's origin.
+    let position = if &baseline == b"roman" {
+        0.0
+    } else if &baseline == b"ascent" || &baseline == b"ideographicTop" {
+        -ascent
+    } else if &baseline == b"descent" || &baseline == b"ideographicBottom" {
+        descent
+    } else if &baseline == b"ideographicCenter" {
+        (descent - ascent) / 2.0
+    } else {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2004: One of the parameters is invalid.",
+            2004,
+        )?));
+    };
+
+    Ok(position.into())
+}
+
+pub fn get_atom_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = atom_index(activation, this, args)?;
+    let bounds = with_layout(this, |layout| {
+        let atom = &layout.atoms[index];
+        [atom.x, atom.y, atom.width, atom.height]
+    })
+    .unwrap_or_default();
+
+    let rectangle = activation
+        .avm2()
+        .classes()
+        .rectangle
+        .construct(activation, &bounds.map(Value::from))?;
+    Ok(rectangle.into())
+}
+
+pub fn get_atom_center<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = atom_index(activation, this, args)?;
+    layout_value(this, |layout| {
+        let atom = &layout.atoms[index];
+        (atom.x + atom.width / 2.0).into()
+    })
+}
+
+pub fn get_atom_bidi_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = atom_index(activation, this, args)?;
+    layout_value(this, |layout| layout.atoms[index].bidi_level.into())
+}
+
+pub fn get_atom_word_boundary_on_left<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = atom_index(activation, this, args)?;
+    layout_value(this, |layout| {
+        layout.atoms[index].word_boundary_on_left.into()
+    })
+}
+
+pub fn get_atom_text_block_begin_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = atom_index(activation, this, args)?;
+    layout_value(this, |layout| {
+        (layout.begin + layout.atoms[index].begin).into()
+    })
+}
+
+pub fn get_atom_text_block_end_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = atom_index(activation, this, args)?;
+    layout_value(this, |layout| {
+        (layout.begin + layout.atoms[index].end).into()
+    })
+}
+
+pub fn get_atom_index_at_char_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let char_index = args.get_i32(activation, 0)?;
+    if char_index < 0 {
+        return Ok((-1).into());
+    }
+
+    layout_value(this, |layout| {
+        layout
+            .atom_at_char_index(char_index as usize)
+            .map_or(-1, |index| index as i32)
+            .into()
+    })
+}
+
+pub fn get_atom_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(display_object) = this.as_display_object() else {
+        return Ok((-1).into());
+    };
+
+    let x = args.get_f64(activation, 0)?;
+    let y = args.get_f64(activation, 1)?;
+    let global = Point::from_pixels(x, y);
+    let Some(local) = display_object.global_to_local(global) else {
+        return Ok((-1).into());
+    };
+
+    layout_value(this, |layout| {
+        layout
+            .atom_at_point(local.x.to_pixels(), local.y.to_pixels())
+            .map_or(-1, |index| index as i32)
+            .into()
+    })
+}
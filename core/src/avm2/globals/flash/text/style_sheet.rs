@@ -0,0 +1,109 @@
+//! `flash.text.StyleSheet` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject, TextFormatObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::{Style, StyleSheet};
+use crate::string::AvmString;
+
+/// Implements `StyleSheet.innerParseCSS`, which returns an object of style objects, or null
+/// if the CSS is malformed.
+pub fn inner_parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args.get_string(activation, 0)?;
+
+    let mut style_sheet = StyleSheet::new();
+    if !style_sheet.parse_css(&css) {
+        return Ok(Value::Null);
+    }
+
+    let styles = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    for name in style_sheet.style_names() {
+        let Some(style) = style_sheet.style(name) else {
+            continue;
+        };
+
+        let style_object = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        for (property, value) in style.iter() {
+            let property = AvmString::new(activation.context.gc_context, property);
+            let value = AvmString::new(activation.context.gc_context, value);
+            style_object.set_public_property(property, value.into(), activation)?;
+        }
+
+        let name = AvmString::new(activation.context.gc_context, name);
+        styles.set_public_property(name, style_object.into(), activation)?;
+    }
+
+    Ok(styles.into())
+}
+
+/// Implements `StyleSheet.transform`.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format_object) = args.try_get_object(activation, 0) else {
+        return Ok(Value::Null);
+    };
+
+    let style = style_from_object(activation, format_object)?;
+    Ok(TextFormatObject::from_text_format(activation, style.text_format())?.into())
+}
+
+/// Read a style from the dynamic properties of a style object.
+fn style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Style, Error<'gc>> {
+    let mut style = Style::new();
+
+    let mut last_index = object.get_next_enumerant(0, activation)?;
+    while let Some(index) = last_index {
+        let name = object
+            .get_enumerant_name(index, activation)?
+            .coerce_to_string(activation)?;
+        let value = object
+            .get_public_property(name, activation)?
+            .coerce_to_string(activation)?;
+        style.set(name.as_wstr().into(), value.as_wstr().into());
+        last_index = object.get_next_enumerant(index, activation)?;
+    }
+
+    Ok(style)
+}
+
+/// Read a whole style sheet from `StyleSheet._styles`, for use by a text field.
+pub fn style_sheet_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    styles: Object<'gc>,
+) -> Result<StyleSheet, Error<'gc>> {
+    let mut style_sheet = StyleSheet::new();
+
+    let mut last_index = styles.get_next_enumerant(0, activation)?;
+    while let Some(index) = last_index {
+        let name = styles
+            .get_enumerant_name(index, activation)?
+            .coerce_to_string(activation)?;
+        if let Some(style_object) = styles.get_public_property(name, activation)?.as_object() {
+            let style = style_from_object(activation, style_object)?;
+            style_sheet.set_style(&name, Some(style));
+        }
+        last_index = styles.get_next_enumerant(index, activation)?;
+    }
+
+    Ok(style_sheet)
+}
@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2008;
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::globals::flash::text::style_sheet::style_sheet_from_object;
 use crate::avm2::object::{ClassObject, Object, TObject, TextFormatObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
@@ -10,7 +11,6 @@ use crate::avm2::Error;
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
 use crate::html::TextFormat;
 use crate::string::AvmString;
-use crate::{avm2_stub_getter, avm2_stub_setter};
 use swf::Color;
 
 pub fn text_field_allocator<'gc>(
@@ -49,20 +49,33 @@ pub fn text_field_allocator<'gc>(
 }
 
 pub fn get_always_show_selection<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "alwaysShowSelection");
-    Ok(Value::Bool(false))
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.always_show_selection().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_always_show_selection<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "alwaysShowSelection");
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args.get_bool(0);
+        this.set_always_show_selection(activation.context.gc_context, value);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -242,20 +255,33 @@ pub fn set_border_color<'gc>(
 }
 
 pub fn get_condense_white<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "condenseWhite");
-    Ok(Value::Bool(false))
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.condense_white().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_condense_white<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "condenseWhite");
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args.get_bool(0);
+        this.set_condense_white(&mut activation.context, value);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -392,6 +418,26 @@ pub fn set_html_text<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `TextField.applyStyleSheet`, which attaches the styles of a `StyleSheet`.
+pub fn apply_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = match args.try_get_object(activation, 0) {
+            Some(styles) => Some(style_sheet_from_object(activation, styles)?),
+            None => None,
+        };
+        this.set_style_sheet(&mut activation.context, style_sheet);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_length<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -1306,37 +1352,66 @@ pub fn set_max_chars<'gc>(
 }
 
 pub fn get_mouse_wheel_enabled<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "mouseWheelEnabled");
-    Ok(true.into())
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.is_mouse_wheel_enabled().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_mouse_wheel_enabled<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "mouseWheelEnabled");
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args.get_bool(0);
+        this.set_mouse_wheel_enabled(activation.context.gc_context, value);
+    }
+
     Ok(Value::Undefined)
 }
 
 pub fn get_restrict<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "restrict");
-    Ok(Value::Null)
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(match this.restrict() {
+            Some(value) => AvmString::new(activation.context.gc_context, value).into(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_restrict<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "restrict");
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args.try_get_string(activation, 0)?;
+        this.set_restrict(value.as_deref(), &mut activation.context);
+    }
+
     Ok(Value::Undefined)
 }
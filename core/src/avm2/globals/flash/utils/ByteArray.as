@@ -24,12 +24,22 @@ package flash.utils {
 		public native function get position():uint;
 		public native function set position(value:uint):void;
 
+		[API("684")]
+		public native function get shareable():Boolean;
+		[API("684")]
+		public native function set shareable(value:Boolean):void;
+
 		public function ByteArray() {
 			this.objectEncoding = _defaultObjectEncoding;
 		}
 
 		public native function clear():void;
 
+		[API("684")]
+		public native function atomicCompareAndSwapIntAt(byteIndex:int, expectedValue:int, newValue:int):int;
+		[API("684")]
+		public native function atomicCompareAndSwapLength(expectedLength:int, newLength:int):int;
+
 		public function deflate(): void {
 			this.compress("deflate");
 		}
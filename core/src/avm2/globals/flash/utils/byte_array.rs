@@ -2,9 +2,10 @@ use std::rc::Rc;
 
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::{Endian, ObjectEncoding};
-use crate::avm2::error::make_error_2008;
+use crate::avm2::error::{make_error_1506, make_error_2008};
 pub use crate::avm2::object::byte_array_allocator;
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::string::AvmString;
@@ -53,7 +54,7 @@ pub fn write_bytes<'gc>(
         .get(2)
         .unwrap_or(&Value::Integer(0))
         .coerce_to_u32(activation)? as usize;
-    if !Object::ptr_eq(this, bytearray) {
+    if !shares_bytes(this, bytearray) {
         // The ByteArray we are reading from is different than the ByteArray we are writing to,
         // so we are allowed to borrow both at the same time without worrying about a panic
 
@@ -74,11 +75,11 @@ pub fn write_bytes<'gc>(
 
         if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
             bytearray
-                .write_bytes(to_write)
+                .write_bytes(&to_write)
                 .map_err(|e| e.to_avm(activation))?;
         }
     } else if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
-        // The ByteArray we are reading from is the same as the ByteArray we are writing to,
+        // The ByteArray we are reading from uses the same bytes as the ByteArray we are writing to,
         // so we only need to borrow once, and we can use `write_bytes_within` to write bytes from our own ByteArray
         let amnt = if length != 0 {
             length
@@ -93,6 +94,20 @@ pub fn write_bytes<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Whether two ByteArrays use the same bytes, which can't be borrowed mutably while they're read.
+///
+/// This is the case when they're the same object, or when one is a shareable ByteArray
+/// that was passed to another worker and back.
+fn shares_bytes<'gc>(a: Object<'gc>, b: Object<'gc>) -> bool {
+    if Object::ptr_eq(a, b) {
+        return true;
+    }
+    match (a.as_bytearray(), b.as_bytearray()) {
+        (Some(a), Some(b)) => a.shares_bytes_with(&b),
+        _ => false,
+    }
+}
+
 // Reads the bytes from the current bytearray into another bytearray
 pub fn read_bytes<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -112,7 +127,7 @@ pub fn read_bytes<'gc>(
         .unwrap_or(&Value::Integer(0))
         .coerce_to_u32(activation)? as usize;
 
-    if !Object::ptr_eq(this, bytearray) {
+    if !shares_bytes(this, bytearray) {
         if let Some(bytearray_read) = this.as_bytearray() {
             let to_write = bytearray_read
                 .read_bytes(
@@ -130,7 +145,7 @@ pub fn read_bytes<'gc>(
                 .ok_or("ArgumentError: Parameter must be a bytearray")?;
 
             ba_write
-                .write_at(to_write, offset)
+                .write_at(&to_write, offset)
                 .map_err(|e| e.to_avm(activation))?;
         }
     } else if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
@@ -176,7 +191,7 @@ pub fn read_utf<'gc>(
     if let Some(bytearray) = this.as_bytearray() {
         return Ok(AvmString::new_utf8_bytes(
             activation.context.gc_context,
-            bytearray.read_utf().map_err(|e| e.to_avm(activation))?,
+            &bytearray.read_utf().map_err(|e| e.to_avm(activation))?,
         )
         .into());
     }
@@ -189,7 +204,8 @@ pub fn to_string<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bytearray) = this.as_bytearray() {
-        let mut bytes = bytearray.bytes();
+        let bytes = bytearray.bytes();
+        let mut bytes = &*bytes;
         if let Some(without_bom) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
             bytes = without_bom;
         // Little-endian UTF-16 BOM
@@ -262,6 +278,88 @@ pub fn set_position<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_shareable<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bytearray) = this.as_bytearray_object() {
+        return Ok(bytearray.shareable().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_shareable<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bytearray) = this.as_bytearray_object() {
+        let shareable = args.get_bool(0);
+        bytearray.set_shareable(activation.context.gc_context, shareable);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Replaces the int at `byteIndex` if it has the expected value, returning the previous value.
+///
+/// Workers are run cooperatively on a single thread, so no other worker can observe the
+/// ByteArray halfway through this.
+pub fn atomic_compare_and_swap_int_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let byte_index = args.get_i32(activation, 0)?;
+    let expected_value = args.get_i32(activation, 1)?;
+    let new_value = args.get_i32(activation, 2)?;
+
+    if byte_index < 0 || byte_index % 4 != 0 {
+        return Err(make_error_1506(activation));
+    }
+
+    if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+        let offset = byte_index as usize;
+        let previous = bytearray
+            .read_int_at(offset)
+            .map_err(|e| e.to_avm(activation))?;
+        if previous == expected_value {
+            let bytes = match bytearray.endian() {
+                Endian::Big => new_value.to_be_bytes(),
+                Endian::Little => new_value.to_le_bytes(),
+            };
+            bytearray
+                .write_at_nongrowing(&bytes, offset)
+                .map_err(|e| e.to_avm(activation))?;
+        }
+        return Ok(previous.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Resizes the ByteArray if it has the expected length, returning the previous length.
+pub fn atomic_compare_and_swap_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let expected_length = args.get_i32(activation, 0)?;
+    let new_length = args.get_i32(activation, 1)?;
+
+    if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+        let previous = bytearray.len() as i32;
+        if previous == expected_length {
+            bytearray.set_length(new_length.max(0) as usize);
+        }
+        return Ok(previous.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bytes_available<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -472,7 +570,7 @@ pub fn read_utf_bytes<'gc>(
         return Ok(AvmString::new_utf8(
             activation.context.gc_context,
             String::from_utf8_lossy(
-                bytearray
+                &bytearray
                     .read_utf_bytes(len as usize)
                     .map_err(|e| e.to_avm(activation))?,
             ),
@@ -641,9 +739,10 @@ pub fn read_multi_byte<'gc>(
             .get(1)
             .unwrap_or(&"UTF-8".into())
             .coerce_to_string(activation)?;
-        let mut bytes = bytearray
+        let bytes = bytearray
             .read_bytes(len as usize)
             .map_err(|e| e.to_avm(activation))?;
+        let mut bytes = &*bytes;
 
         // Flash cuts off the string at the first null byte (after checking that
         // the original length fits in the ByteArray)
@@ -763,7 +862,7 @@ pub fn read_object<'gc>(
             ObjectEncoding::Amf0 => {
                 let mut decoder = AMF0Decoder::default();
                 let (extra, amf) = decoder
-                    .parse_single_element(bytes)
+                    .parse_single_element(&bytes)
                     .map_err(|_| "Error: Invalid object")?;
                 (
                     extra.len(),
@@ -773,7 +872,7 @@ pub fn read_object<'gc>(
             ObjectEncoding::Amf3 => {
                 let mut decoder = AMF3Decoder::default();
                 let (extra, amf) = decoder
-                    .parse_single_element(bytes)
+                    .parse_single_element(&bytes)
                     .map_err(|_| "Error: Invalid object")?;
                 (
                     extra.len(),
@@ -216,9 +216,11 @@ include "flash/geom/Rectangle.as"
 include "flash/geom/Transform.as"
 include "flash/geom/Utils3D.as"
 include "flash/geom/Vector3D.as"
+include "flash/globalization/Collator.as"
 include "flash/globalization/CollatorMode.as"
 include "flash/globalization/CurrencyParseResult.as"
 include "flash/globalization/CurrencyFormatter.as"
+include "flash/globalization/DateTimeFormatter.as"
 include "flash/globalization/DateTimeNameContext.as"
 include "flash/globalization/DateTimeNameStyle.as"
 include "flash/globalization/DateTimeStyle.as"
@@ -309,6 +311,7 @@ include "flash/system/IMEConversionMode.as"
 include "flash/system/ImageDecodingPolicy.as"
 include "flash/system/LoaderContext.as"
 include "flash/system/JPEGLoaderContext.as"
+include "flash/system/MessageChannel.as"
 include "flash/system/MessageChannelState.as"
 include "flash/system/Security.as"
 include "flash/system/SecurityDomain.as"
@@ -352,6 +355,7 @@ include "flash/text/engine/FontLookup.as"
 include "flash/text/engine/FontMetrics.as"
 include "flash/text/engine/FontPosture.as"
 include "flash/text/engine/FontWeight.as"
+include "flash/text/engine/GraphicElement.as"
 include "flash/text/engine/GroupElement.as"
 include "flash/text/engine/JustificationStyle.as"
 include "flash/text/engine/Kerning.as"
@@ -33,6 +33,7 @@ mod array_object;
 mod bitmapdata_object;
 mod bytearray_object;
 mod class_object;
+mod condition_object;
 mod context3d_object;
 mod date_object;
 mod dictionary_object;
@@ -44,6 +45,9 @@ mod font_object;
 mod function_object;
 mod index_buffer_3d_object;
 mod loaderinfo_object;
+mod local_connection_object;
+mod message_channel_object;
+mod mutex_object;
 mod namespace_object;
 mod net_connection_object;
 mod netstream_object;
@@ -64,6 +68,7 @@ mod textformat_object;
 mod texture_object;
 mod vector_object;
 mod vertex_buffer_3d_object;
+mod worker_object;
 mod xml_list_object;
 mod xml_object;
 
@@ -75,6 +80,9 @@ pub use crate::avm2::object::bytearray_object::{
     byte_array_allocator, ByteArrayObject, ByteArrayObjectWeak,
 };
 pub use crate::avm2::object::class_object::{ClassObject, ClassObjectWeak};
+pub use crate::avm2::object::condition_object::{
+    condition_allocator, ConditionObject, ConditionObjectWeak,
+};
 pub use crate::avm2::object::context3d_object::{Context3DObject, Context3DObjectWeak};
 pub use crate::avm2::object::date_object::{date_allocator, DateObject, DateObjectWeak};
 pub use crate::avm2::object::dictionary_object::{
@@ -96,6 +104,13 @@ pub use crate::avm2::object::index_buffer_3d_object::{
 pub use crate::avm2::object::loaderinfo_object::{
     loader_info_allocator, LoaderInfoObject, LoaderInfoObjectWeak, LoaderStream,
 };
+pub use crate::avm2::object::local_connection_object::{
+    local_connection_allocator, LocalConnectionObject, LocalConnectionObjectWeak,
+};
+pub use crate::avm2::object::message_channel_object::{
+    MessageChannelObject, MessageChannelObjectWeak,
+};
+pub use crate::avm2::object::mutex_object::{mutex_allocator, MutexObject, MutexObjectWeak};
 pub use crate::avm2::object::namespace_object::{
     namespace_allocator, NamespaceObject, NamespaceObjectWeak,
 };
@@ -138,6 +153,7 @@ pub use crate::avm2::object::vector_object::{vector_allocator, VectorObject, Vec
 pub use crate::avm2::object::vertex_buffer_3d_object::{
     VertexBuffer3DObject, VertexBuffer3DObjectWeak,
 };
+pub use crate::avm2::object::worker_object::{WorkerObject, WorkerObjectWeak};
 pub use crate::avm2::object::xml_list_object::{
     xml_list_allocator, E4XOrXml, XmlListObject, XmlListObjectWeak,
 };
@@ -184,10 +200,15 @@ use crate::font::Font;
         Program3DObject(Program3DObject<'gc>),
         NetStreamObject(NetStreamObject<'gc>),
         NetConnectionObject(NetConnectionObject<'gc>),
+        LocalConnectionObject(LocalConnectionObject<'gc>),
         ResponderObject(ResponderObject<'gc>),
         ShaderDataObject(ShaderDataObject<'gc>),
         SocketObject(SocketObject<'gc>),
-        FontObject(FontObject<'gc>)
+        FontObject(FontObject<'gc>),
+        WorkerObject(WorkerObject<'gc>),
+        MessageChannelObject(MessageChannelObject<'gc>),
+        MutexObject(MutexObject<'gc>),
+        ConditionObject(ConditionObject<'gc>)
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -1409,9 +1430,29 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    fn as_local_connection_object(&self) -> Option<LocalConnectionObject<'gc>> {
+        None
+    }
+
     fn as_socket(&self) -> Option<SocketObject<'gc>> {
         None
     }
+
+    fn as_worker_object(&self) -> Option<WorkerObject<'gc>> {
+        None
+    }
+
+    fn as_message_channel_object(&self) -> Option<MessageChannelObject<'gc>> {
+        None
+    }
+
+    fn as_mutex_object(&self) -> Option<MutexObject<'gc>> {
+        None
+    }
+
+    fn as_condition_object(&self) -> Option<ConditionObject<'gc>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -1457,10 +1498,15 @@ impl<'gc> Object<'gc> {
             Self::Program3DObject(o) => WeakObject::Program3DObject(Program3DObjectWeak(Gc::downgrade(o.0))),
             Self::NetStreamObject(o) => WeakObject::NetStreamObject(NetStreamObjectWeak(GcCell::downgrade(o.0))),
             Self::NetConnectionObject(o) => WeakObject::NetConnectionObject(NetConnectionObjectWeak(Gc::downgrade(o.0))),
+            Self::LocalConnectionObject(o) => WeakObject::LocalConnectionObject(LocalConnectionObjectWeak(Gc::downgrade(o.0))),
             Self::ResponderObject(o) => WeakObject::ResponderObject(ResponderObjectWeak(GcCell::downgrade(o.0))),
             Self::ShaderDataObject(o) => WeakObject::ShaderDataObject(ShaderDataObjectWeak(Gc::downgrade(o.0))),
             Self::SocketObject(o) => WeakObject::SocketObject(SocketObjectWeak(Gc::downgrade(o.0))),
             Self::FontObject(o) => WeakObject::FontObject(FontObjectWeak(GcCell::downgrade(o.0))),
+            Self::WorkerObject(o) => WeakObject::WorkerObject(WorkerObjectWeak(Gc::downgrade(o.0))),
+            Self::MessageChannelObject(o) => WeakObject::MessageChannelObject(MessageChannelObjectWeak(Gc::downgrade(o.0))),
+            Self::MutexObject(o) => WeakObject::MutexObject(MutexObjectWeak(Gc::downgrade(o.0))),
+            Self::ConditionObject(o) => WeakObject::ConditionObject(ConditionObjectWeak(Gc::downgrade(o.0))),
         }
     }
 }
@@ -1516,10 +1562,15 @@ pub enum WeakObject<'gc> {
     Program3DObject(Program3DObjectWeak<'gc>),
     NetStreamObject(NetStreamObjectWeak<'gc>),
     NetConnectionObject(NetConnectionObjectWeak<'gc>),
+    LocalConnectionObject(LocalConnectionObjectWeak<'gc>),
     ResponderObject(ResponderObjectWeak<'gc>),
     ShaderDataObject(ShaderDataObjectWeak<'gc>),
     SocketObject(SocketObjectWeak<'gc>),
     FontObject(FontObjectWeak<'gc>),
+    WorkerObject(WorkerObjectWeak<'gc>),
+    MessageChannelObject(MessageChannelObjectWeak<'gc>),
+    MutexObject(MutexObjectWeak<'gc>),
+    ConditionObject(ConditionObjectWeak<'gc>),
 }
 
 impl<'gc> WeakObject<'gc> {
@@ -1558,10 +1609,65 @@ impl<'gc> WeakObject<'gc> {
             Self::Program3DObject(o) => Program3DObject(o.0.upgrade(mc)?).into(),
             Self::NetStreamObject(o) => NetStreamObject(o.0.upgrade(mc)?).into(),
             Self::NetConnectionObject(o) => NetConnectionObject(o.0.upgrade(mc)?).into(),
+            Self::LocalConnectionObject(o) => LocalConnectionObject(o.0.upgrade(mc)?).into(),
             Self::ResponderObject(o) => ResponderObject(o.0.upgrade(mc)?).into(),
             Self::ShaderDataObject(o) => ShaderDataObject(o.0.upgrade(mc)?).into(),
             Self::SocketObject(o) => SocketObject(o.0.upgrade(mc)?).into(),
             Self::FontObject(o) => FontObject(o.0.upgrade(mc)?).into(),
+            Self::WorkerObject(o) => WorkerObject(o.0.upgrade(mc)?).into(),
+            Self::MessageChannelObject(o) => MessageChannelObject(o.0.upgrade(mc)?).into(),
+            Self::MutexObject(o) => MutexObject(o.0.upgrade(mc)?).into(),
+            Self::ConditionObject(o) => ConditionObject(o.0.upgrade(mc)?).into(),
         })
     }
+
+    /// Whether the object has been garbage collected.
+    ///
+    /// Unlike `upgrade`, this never keeps the object alive.
+    pub fn is_dropped(self) -> bool {
+        match self {
+            Self::ScriptObject(o) => o.0.is_dropped(),
+            Self::FunctionObject(o) => o.0.is_dropped(),
+            Self::PrimitiveObject(o) => o.0.is_dropped(),
+            Self::NamespaceObject(o) => o.0.is_dropped(),
+            Self::ArrayObject(o) => o.0.is_dropped(),
+            Self::StageObject(o) => o.0.is_dropped(),
+            Self::DomainObject(o) => o.0.is_dropped(),
+            Self::EventObject(o) => o.0.is_dropped(),
+            Self::DispatchObject(o) => o.0.is_dropped(),
+            Self::XmlObject(o) => o.0.is_dropped(),
+            Self::XmlListObject(o) => o.0.is_dropped(),
+            Self::RegExpObject(o) => o.0.is_dropped(),
+            Self::ByteArrayObject(o) => o.0.is_dropped(),
+            Self::LoaderInfoObject(o) => o.0.is_dropped(),
+            Self::ClassObject(o) => o.0.is_dropped(),
+            Self::VectorObject(o) => o.0.is_dropped(),
+            Self::SoundObject(o) => o.0.is_dropped(),
+            Self::SoundChannelObject(o) => o.0.is_dropped(),
+            Self::BitmapDataObject(o) => o.0.is_dropped(),
+            Self::DateObject(o) => o.0.is_dropped(),
+            Self::DictionaryObject(o) => o.0.is_dropped(),
+            Self::QNameObject(o) => o.0.is_dropped(),
+            Self::TextFormatObject(o) => o.0.is_dropped(),
+            Self::ProxyObject(o) => o.0.is_dropped(),
+            Self::ErrorObject(o) => o.0.is_dropped(),
+            Self::Stage3DObject(o) => o.0.is_dropped(),
+            Self::Context3DObject(o) => o.0.is_dropped(),
+            Self::IndexBuffer3DObject(o) => o.0.is_dropped(),
+            Self::VertexBuffer3DObject(o) => o.0.is_dropped(),
+            Self::TextureObject(o) => o.0.is_dropped(),
+            Self::Program3DObject(o) => o.0.is_dropped(),
+            Self::NetStreamObject(o) => o.0.is_dropped(),
+            Self::NetConnectionObject(o) => o.0.is_dropped(),
+            Self::LocalConnectionObject(o) => o.0.is_dropped(),
+            Self::ResponderObject(o) => o.0.is_dropped(),
+            Self::ShaderDataObject(o) => o.0.is_dropped(),
+            Self::SocketObject(o) => o.0.is_dropped(),
+            Self::FontObject(o) => o.0.is_dropped(),
+            Self::WorkerObject(o) => o.0.is_dropped(),
+            Self::MessageChannelObject(o) => o.0.is_dropped(),
+            Self::MutexObject(o) => o.0.is_dropped(),
+            Self::ConditionObject(o) => o.0.is_dropped(),
+        }
+    }
 }
@@ -78,6 +78,10 @@ impl<'gc> ArrayObject<'gc> {
         ))
         .into();
         instance.install_instance_slots(activation.context.gc_context);
+        activation
+            .context
+            .avm2
+            .record_allocation(activation.context.gc_context, instance);
 
         class.call_native_init(instance.into(), &[], activation)?;
 
@@ -1,5 +1,5 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::bytearray::{ByteArrayStorage, SharedBytes};
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
@@ -7,7 +7,8 @@ use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::character::Character;
 use core::fmt;
-use gc_arena::{Collect, GcCell, GcWeakCell, Mutation};
+use gc_arena::lock::RefLock;
+use gc_arena::{Collect, Gc, GcCell, GcWeakCell, Mutation};
 use std::cell::{Ref, RefMut};
 
 /// A class instance allocator that allocates ByteArray objects.
@@ -39,10 +40,15 @@ pub fn byte_array_allocator<'gc>(
     });
 
     let base = ScriptObjectData::new(class);
+    let storage = Gc::new(activation.context.gc_context, RefLock::new(storage));
 
     Ok(ByteArrayObject(GcCell::new(
         activation.context.gc_context,
-        ByteArrayObjectData { base, storage },
+        ByteArrayObjectData {
+            base,
+            storage,
+            shareable: false,
+        },
     ))
     .into())
 }
@@ -69,13 +75,40 @@ pub struct ByteArrayObjectData<'gc> {
     /// Base script object
     base: ScriptObjectData<'gc>,
 
-    storage: ByteArrayStorage,
+    /// The bytes of this ByteArray, along with its position and endianness.
+    ///
+    /// Only the bytes are shared with ByteArrays in other workers when this one is shareable.
+    storage: Gc<'gc, RefLock<ByteArrayStorage>>,
+
+    /// Whether passing this ByteArray to another worker shares its bytes instead of copying them.
+    shareable: bool,
 }
 
 impl<'gc> ByteArrayObject<'gc> {
     pub fn from_storage(
         activation: &mut Activation<'_, 'gc>,
         bytes: ByteArrayStorage,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let storage = Gc::new(activation.context.gc_context, RefLock::new(bytes));
+        Self::from_storage_ref(activation, storage, false)
+    }
+
+    /// Creates a shareable ByteArray that uses the bytes of a ByteArray from another worker.
+    ///
+    /// Like in Flash, only the bytes are shared: each ByteArray has its own position and endianness.
+    pub fn from_shared_bytes(
+        activation: &mut Activation<'_, 'gc>,
+        bytes: SharedBytes,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let storage = ByteArrayStorage::from_shared_bytes(bytes);
+        let storage = Gc::new(activation.context.gc_context, RefLock::new(storage));
+        Self::from_storage_ref(activation, storage, true)
+    }
+
+    fn from_storage_ref(
+        activation: &mut Activation<'_, 'gc>,
+        storage: Gc<'gc, RefLock<ByteArrayStorage>>,
+        shareable: bool,
     ) -> Result<Object<'gc>, Error<'gc>> {
         let class = activation.avm2().classes().bytearray;
         let base = ScriptObjectData::new(class);
@@ -84,7 +117,8 @@ impl<'gc> ByteArrayObject<'gc> {
             activation.context.gc_context,
             ByteArrayObjectData {
                 base,
-                storage: bytes,
+                storage,
+                shareable,
             },
         ))
         .into();
@@ -96,7 +130,24 @@ impl<'gc> ByteArrayObject<'gc> {
     }
 
     pub fn storage(&self) -> Ref<ByteArrayStorage> {
-        Ref::map(self.0.read(), |d| &d.storage)
+        Gc::as_ref(self.0.read().storage).borrow()
+    }
+
+    pub fn storage_mut(&self, mc: &Mutation<'gc>) -> RefMut<ByteArrayStorage> {
+        Gc::write(mc, self.0.read().storage).unlock().borrow_mut()
+    }
+
+    /// The bytes of this ByteArray, to be shared with another worker.
+    pub fn shared_bytes(&self) -> SharedBytes {
+        self.storage().shared_bytes()
+    }
+
+    pub fn shareable(&self) -> bool {
+        self.0.read().shareable
+    }
+
+    pub fn set_shareable(&self, mc: &Mutation<'gc>, shareable: bool) {
+        self.0.write(mc).shareable = shareable;
     }
 }
 
@@ -118,12 +169,10 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        let read = self.0.read();
-
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    return Ok(if let Some(val) = read.storage.get(index) {
+                    return Ok(if let Some(val) = self.storage().get(index) {
                         Value::Integer(val as i32)
                     } else {
                         Value::Undefined
@@ -132,7 +181,7 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
             }
         }
 
-        read.base.get_property_local(name, activation)
+        self.0.read().base.get_property_local(name, activation)
     }
 
     fn set_property_local(
@@ -141,20 +190,19 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        let mut write = self.0.write(activation.context.gc_context);
-
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    write
-                        .storage
-                        .set(index, value.coerce_to_u32(activation)? as u8);
+                    let value = value.coerce_to_u32(activation)? as u8;
+                    self.storage_mut(activation.context.gc_context)
+                        .set(index, value);
 
                     return Ok(());
                 }
             }
         }
 
+        let mut write = self.0.write(activation.context.gc_context);
         write.base.set_property_local(name, value, activation)
     }
 
@@ -164,20 +212,19 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        let mut write = self.0.write(activation.context.gc_context);
-
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    write
-                        .storage
-                        .set(index, value.coerce_to_u32(activation)? as u8);
+                    let value = value.coerce_to_u32(activation)? as u8;
+                    self.storage_mut(activation.context.gc_context)
+                        .set(index, value);
 
                     return Ok(());
                 }
             }
         }
 
+        let mut write = self.0.write(activation.context.gc_context);
         write.base.init_property_local(name, value, activation)
     }
 
@@ -189,9 +236,7 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    self.0
-                        .write(activation.context.gc_context)
-                        .storage
+                    self.storage_mut(activation.context.gc_context)
                         .delete(index);
                     return Ok(true);
                 }
@@ -209,7 +254,7 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
-                    return self.0.read().storage.get(index).is_some();
+                    return self.storage().get(index).is_some();
                 }
             }
         }
@@ -222,11 +267,11 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
     }
 
     fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
-        Some(Ref::map(self.0.read(), |d| &d.storage))
+        Some(self.storage())
     }
 
     fn as_bytearray_mut(&self, mc: &Mutation<'gc>) -> Option<RefMut<ByteArrayStorage>> {
-        Some(RefMut::map(self.0.write(mc), |d| &mut d.storage))
+        Some(self.storage_mut(mc))
     }
 
     fn as_bytearray_object(&self) -> Option<ByteArrayObject<'gc>> {
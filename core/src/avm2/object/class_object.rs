@@ -894,6 +894,11 @@ impl<'gc> TObject<'gc> for ClassObject<'gc> {
 
         self.call_init(instance.into(), arguments, activation)?;
 
+        activation
+            .context
+            .avm2
+            .record_allocation(activation.context.gc_context, instance);
+
         Ok(instance)
     }
 
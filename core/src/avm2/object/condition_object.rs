@@ -0,0 +1,111 @@
+//! Object representation for Condition
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::worker::Condition;
+use crate::avm2::Error;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::{Lock, RefLock};
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use std::cell::{Ref, RefMut};
+use std::fmt;
+use std::fmt::Debug;
+
+pub fn condition_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class).into();
+    let this: Object<'gc> = ConditionObject(Gc::new(
+        activation.context.gc_context,
+        ConditionObjectData {
+            base,
+            condition: Lock::new(None),
+        },
+    ))
+    .into();
+
+    Ok(this)
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct ConditionObject<'gc>(pub Gc<'gc, ConditionObjectData<'gc>>);
+
+#[derive(Collect, Clone, Copy, Debug)]
+#[collect(no_drop)]
+pub struct ConditionObjectWeak<'gc>(pub GcWeak<'gc, ConditionObjectData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct ConditionObjectData<'gc> {
+    base: RefLock<ScriptObjectData<'gc>>,
+
+    /// The condition, which is created by the constructor once it knows the mutex.
+    condition: Lock<Option<Condition<'gc>>>,
+}
+
+impl<'gc> ConditionObject<'gc> {
+    /// Creates the object for a condition that was passed from another worker.
+    pub fn from_condition(
+        activation: &mut Activation<'_, 'gc>,
+        condition: Condition<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let class = activation.avm2().classes().condition;
+        let base = ScriptObjectData::new(class).into();
+        let this: Object<'gc> = ConditionObject(Gc::new(
+            activation.context.gc_context,
+            ConditionObjectData {
+                base,
+                condition: Lock::new(Some(condition)),
+            },
+        ))
+        .into();
+        this.install_instance_slots(activation.context.gc_context);
+
+        class.call_native_init(this.into(), &[], activation)?;
+
+        Ok(this)
+    }
+
+    pub fn condition(&self) -> Condition<'gc> {
+        self.0
+            .condition
+            .get()
+            .expect("Condition should be initialized by its constructor")
+    }
+
+    pub fn init_condition(&self, mc: &Mutation<'gc>, condition: Condition<'gc>) {
+        unlock!(Gc::write(mc, self.0), ConditionObjectData, condition).set(Some(condition));
+    }
+}
+
+impl<'gc> TObject<'gc> for ConditionObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), ConditionObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_condition_object(&self) -> Option<ConditionObject<'gc>> {
+        Some(*self)
+    }
+}
+
+impl<'gc> Debug for ConditionObject<'gc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ConditionObject")
+    }
+}
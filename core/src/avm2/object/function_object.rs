@@ -209,6 +209,11 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
 
         self.call(instance.into(), arguments, activation)?;
 
+        activation
+            .context
+            .avm2
+            .record_allocation(activation.context.gc_context, instance);
+
         Ok(instance)
     }
 }
@@ -207,7 +207,8 @@ impl<'gc> LoaderInfoObject<'gc> {
             self.0.write(context.gc_context).init_event_fired = true;
 
             // TODO - 'init' should be fired earlier during the download.
-            // Right now, we fire it when downloading is fully completed.
+            // Right now, we only do that for movies that are shown while they're
+            // downloading, and fire it when downloading is fully completed otherwise.
             let init_evt = EventObject::bare_default_event(context, "init");
             Avm2::dispatch_event(context, init_evt, (*self).into());
         }
@@ -0,0 +1,103 @@
+//! Object representation for LocalConnection
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::RefLock;
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use std::cell::{Ref, RefCell, RefMut};
+use std::fmt;
+use std::fmt::Debug;
+
+pub fn local_connection_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class).into();
+    let this: Object<'gc> = LocalConnectionObject(Gc::new(
+        activation.context.gc_context,
+        LocalConnectionObjectData {
+            base,
+            connection_name: RefCell::new(None),
+            allowed_domains: RefCell::new(vec![]),
+        },
+    ))
+    .into();
+
+    Ok(this)
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct LocalConnectionObject<'gc>(pub Gc<'gc, LocalConnectionObjectData<'gc>>);
+
+#[derive(Collect, Clone, Copy, Debug)]
+#[collect(no_drop)]
+pub struct LocalConnectionObjectWeak<'gc>(pub GcWeak<'gc, LocalConnectionObjectData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct LocalConnectionObjectData<'gc> {
+    base: RefLock<ScriptObjectData<'gc>>,
+
+    /// The full name this connection is listening on, if it's connected.
+    #[collect(require_static)]
+    connection_name: RefCell<Option<String>>,
+
+    /// The domains that were allowed to send to this connection with `allowDomain`.
+    #[collect(require_static)]
+    allowed_domains: RefCell<Vec<String>>,
+}
+
+impl<'gc> TObject<'gc> for LocalConnectionObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), LocalConnectionObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_local_connection_object(&self) -> Option<LocalConnectionObject<'gc>> {
+        Some(*self)
+    }
+}
+
+impl<'gc> LocalConnectionObject<'gc> {
+    pub fn connection_name(&self) -> Option<String> {
+        self.0.connection_name.borrow().clone()
+    }
+
+    pub fn set_connection_name(&self, name: Option<String>) -> Option<String> {
+        self.0.connection_name.replace(name)
+    }
+
+    pub fn allow_domain(&self, domain: String) {
+        self.0.allowed_domains.borrow_mut().push(domain);
+    }
+
+    pub fn is_domain_allowed(&self, domain: &str) -> bool {
+        self.0
+            .allowed_domains
+            .borrow()
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(domain))
+    }
+}
+
+impl<'gc> Debug for LocalConnectionObject<'gc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LocalConnectionObject")
+    }
+}
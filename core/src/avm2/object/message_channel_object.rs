@@ -0,0 +1,84 @@
+//! Object representation for MessageChannel
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::worker::MessageChannel;
+use crate::avm2::Error;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::RefLock;
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use std::cell::{Ref, RefMut};
+use std::fmt;
+use std::fmt::Debug;
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct MessageChannelObject<'gc>(pub Gc<'gc, MessageChannelObjectData<'gc>>);
+
+#[derive(Collect, Clone, Copy, Debug)]
+#[collect(no_drop)]
+pub struct MessageChannelObjectWeak<'gc>(pub GcWeak<'gc, MessageChannelObjectData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct MessageChannelObjectData<'gc> {
+    base: RefLock<ScriptObjectData<'gc>>,
+    channel: MessageChannel<'gc>,
+}
+
+impl<'gc> MessageChannelObject<'gc> {
+    /// Creates the object representing a message channel in the worker that is currently running.
+    ///
+    /// Use `MessageChannel::object` instead, so that each worker only sees one object per channel.
+    pub fn from_channel(
+        activation: &mut Activation<'_, 'gc>,
+        channel: MessageChannel<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let class = activation.avm2().classes().messagechannel;
+        let base = ScriptObjectData::new(class).into();
+        let this: Object<'gc> = MessageChannelObject(Gc::new(
+            activation.context.gc_context,
+            MessageChannelObjectData { base, channel },
+        ))
+        .into();
+        this.install_instance_slots(activation.context.gc_context);
+
+        class.call_native_init(this.into(), &[], activation)?;
+
+        Ok(this)
+    }
+
+    pub fn channel(&self) -> MessageChannel<'gc> {
+        self.0.channel
+    }
+}
+
+impl<'gc> TObject<'gc> for MessageChannelObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), MessageChannelObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_message_channel_object(&self) -> Option<MessageChannelObject<'gc>> {
+        Some(*self)
+    }
+}
+
+impl<'gc> Debug for MessageChannelObject<'gc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MessageChannelObject")
+    }
+}
@@ -0,0 +1,98 @@
+//! Object representation for Mutex
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::worker::Mutex;
+use crate::avm2::Error;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::RefLock;
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use std::cell::{Ref, RefMut};
+use std::fmt;
+use std::fmt::Debug;
+
+pub fn mutex_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let mutex = Mutex::new(activation.context.gc_context);
+    let base = ScriptObjectData::new(class).into();
+    let this: Object<'gc> = MutexObject(Gc::new(
+        activation.context.gc_context,
+        MutexObjectData { base, mutex },
+    ))
+    .into();
+    mutex.set_object(&mut activation.context, this);
+
+    Ok(this)
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct MutexObject<'gc>(pub Gc<'gc, MutexObjectData<'gc>>);
+
+#[derive(Collect, Clone, Copy, Debug)]
+#[collect(no_drop)]
+pub struct MutexObjectWeak<'gc>(pub GcWeak<'gc, MutexObjectData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct MutexObjectData<'gc> {
+    base: RefLock<ScriptObjectData<'gc>>,
+    mutex: Mutex<'gc>,
+}
+
+impl<'gc> MutexObject<'gc> {
+    /// Creates the object for a mutex that was passed from another worker.
+    pub fn from_mutex(
+        activation: &mut Activation<'_, 'gc>,
+        mutex: Mutex<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let class = activation.avm2().classes().mutex;
+        let base = ScriptObjectData::new(class).into();
+        let this: Object<'gc> = MutexObject(Gc::new(
+            activation.context.gc_context,
+            MutexObjectData { base, mutex },
+        ))
+        .into();
+        this.install_instance_slots(activation.context.gc_context);
+
+        class.call_native_init(this.into(), &[], activation)?;
+
+        Ok(this)
+    }
+
+    pub fn mutex(&self) -> Mutex<'gc> {
+        self.0.mutex
+    }
+}
+
+impl<'gc> TObject<'gc> for MutexObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), MutexObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_mutex_object(&self) -> Option<MutexObject<'gc>> {
+        Some(*self)
+    }
+}
+
+impl<'gc> Debug for MutexObject<'gc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MutexObject")
+    }
+}
@@ -8,6 +8,7 @@ use crate::avm2::Error;
 use crate::backend::audio::SoundHandle;
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
+use crate::string::AvmString;
 use core::fmt;
 use gc_arena::{Collect, GcCell, GcWeakCell, Mutation};
 use std::cell::{Ref, RefMut};
@@ -29,6 +30,9 @@ pub fn sound_allocator<'gc>(
             sound_data: SoundData::NotLoaded {
                 queued_plays: Vec::new(),
             },
+            url: None,
+            is_closed: false,
+            extract_position: 0,
         },
     ))
     .into())
@@ -58,6 +62,17 @@ pub struct SoundObjectData<'gc> {
 
     /// The sound this object holds.
     sound_data: SoundData<'gc>,
+
+    /// The URL this sound was requested from with `Sound.load`, if any.
+    url: Option<AvmString<'gc>>,
+
+    /// Whether a pending load was cancelled with `Sound.close`.
+    is_closed: bool,
+
+    /// The sample frame after the last one returned by `Sound.extract`.
+    ///
+    /// This is used when `extract` is called without a start position.
+    extract_position: u32,
 }
 
 #[derive(Collect)]
@@ -92,6 +107,43 @@ impl<'gc> SoundObject<'gc> {
         }
     }
 
+    pub fn url(self) -> Option<AvmString<'gc>> {
+        self.0.read().url
+    }
+
+    pub fn set_url(self, mc: &Mutation<'gc>, url: AvmString<'gc>) {
+        self.0.write(mc).url = Some(url);
+    }
+
+    /// Whether this sound is still waiting on a `Sound.load` request to finish.
+    pub fn is_loading(self) -> bool {
+        let this = self.0.read();
+        this.url.is_some()
+            && !this.is_closed
+            && matches!(this.sound_data, SoundData::NotLoaded { .. })
+    }
+
+    pub fn is_closed(self) -> bool {
+        self.0.read().is_closed
+    }
+
+    /// Cancels a pending load, discarding any plays that were queued while loading.
+    pub fn close(self, mc: &Mutation<'gc>) {
+        let mut this = self.0.write(mc);
+        this.is_closed = true;
+        if let SoundData::NotLoaded { queued_plays } = &mut this.sound_data {
+            queued_plays.clear();
+        }
+    }
+
+    pub fn extract_position(self) -> u32 {
+        self.0.read().extract_position
+    }
+
+    pub fn set_extract_position(self, mc: &Mutation<'gc>, position: u32) {
+        self.0.write(mc).extract_position = position;
+    }
+
     /// Returns `true` if a `SoundChannel` should be returned back to the AVM2 caller.
     pub fn play(
         self,
@@ -0,0 +1,84 @@
+//! Object representation for Worker
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::worker::Worker;
+use crate::avm2::Error;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::RefLock;
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use std::cell::{Ref, RefMut};
+use std::fmt;
+use std::fmt::Debug;
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct WorkerObject<'gc>(pub Gc<'gc, WorkerObjectData<'gc>>);
+
+#[derive(Collect, Clone, Copy, Debug)]
+#[collect(no_drop)]
+pub struct WorkerObjectWeak<'gc>(pub GcWeak<'gc, WorkerObjectData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct WorkerObjectData<'gc> {
+    base: RefLock<ScriptObjectData<'gc>>,
+    worker: Worker<'gc>,
+}
+
+impl<'gc> WorkerObject<'gc> {
+    /// Creates the object representing a worker in the worker that is currently running.
+    ///
+    /// Use `Worker::object` instead, so that each worker only sees one object per worker.
+    pub fn from_worker(
+        activation: &mut Activation<'_, 'gc>,
+        worker: Worker<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let class = activation.avm2().classes().worker;
+        let base = ScriptObjectData::new(class).into();
+        let this: Object<'gc> = WorkerObject(Gc::new(
+            activation.context.gc_context,
+            WorkerObjectData { base, worker },
+        ))
+        .into();
+        this.install_instance_slots(activation.context.gc_context);
+
+        class.call_native_init(this.into(), &[], activation)?;
+
+        Ok(this)
+    }
+
+    pub fn worker(&self) -> Worker<'gc> {
+        self.0.worker
+    }
+}
+
+impl<'gc> TObject<'gc> for WorkerObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), WorkerObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_worker_object(&self) -> Option<WorkerObject<'gc>> {
+        Some(*self)
+    }
+}
+
+impl<'gc> Debug for WorkerObject<'gc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WorkerObject")
+    }
+}
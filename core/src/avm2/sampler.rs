@@ -0,0 +1,339 @@
+//! Memory and CPU sampling, as exposed by `flash.sampler`.
+//!
+//! While sampling is running, every object constructed by AVM2 is recorded
+//! along with the call stack that allocated it, and a CPU sample of the call
+//! stack is taken at most once per millisecond of script execution. Objects
+//! are recorded when they're constructed from ActionScript, and when arrays
+//! and functions are created; strings and objects created internally by the
+//! player aren't reported.
+//!
+//! Objects are tracked through weak references, so collection can be noticed
+//! without keeping anything alive. The player calls [`Sampler::record_collected`]
+//! after every garbage collection step, which reports the objects that were
+//! freed as deleted.
+
+use crate::avm2::call_stack::CallStack;
+use crate::avm2::method::Method;
+use crate::avm2::object::{ClassObject, Object, TObject, WeakObject};
+use crate::avm2::value::Value;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Gc, Mutation};
+use std::collections::VecDeque;
+use std::time::Duration;
+use web_time::Instant;
+
+/// How often CPU samples are taken.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The most samples kept at once.
+///
+/// Once this many have been taken, the oldest are dropped, so that a movie
+/// that never calls `clearSamples` doesn't run out of memory.
+const MAX_SAMPLES: usize = 100_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum SamplerState {
+    #[default]
+    Stopped,
+    Running,
+    Paused,
+}
+
+/// What a sample records.
+#[derive(Collect, Clone)]
+#[collect(no_drop)]
+pub enum SampleKind<'gc> {
+    /// The call stack at a point in time.
+    Cpu,
+
+    /// An object was constructed.
+    NewObject {
+        id: u64,
+        class: Option<ClassObject<'gc>>,
+        size: usize,
+    },
+
+    /// A previously constructed object was garbage collected.
+    DeleteObject { id: u64, size: usize },
+}
+
+#[derive(Collect, Clone)]
+#[collect(no_drop)]
+pub struct Sample<'gc> {
+    /// The time of the sample, in microseconds since the player started.
+    pub time: f64,
+
+    /// The names of the frames on the call stack, innermost first.
+    #[collect(require_static)]
+    pub stack: Vec<String>,
+
+    pub kind: SampleKind<'gc>,
+}
+
+/// An object allocated while sampling that hasn't been collected yet.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+struct LiveObject<'gc> {
+    object: WeakObject<'gc>,
+    size: usize,
+}
+
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct Sampler<'gc> {
+    #[collect(require_static)]
+    state: SamplerState,
+
+    /// The time that `time` values of samples are relative to.
+    #[collect(require_static)]
+    start_time: Option<Instant>,
+
+    /// When the last CPU sample was taken.
+    #[collect(require_static)]
+    last_cpu_sample: Option<Instant>,
+
+    /// The ID given to the next allocated object.
+    next_id: u64,
+
+    samples: VecDeque<Sample<'gc>>,
+
+    /// Objects that will be reported when they're collected, by ID.
+    live_objects: FnvHashMap<u64, LiveObject<'gc>>,
+
+    /// How often each method was called while sampling, keyed by method pointer.
+    #[collect(require_static)]
+    invocation_counts: FnvHashMap<usize, u32>,
+}
+
+impl<'gc> Sampler<'gc> {
+    pub fn is_sampling(&self) -> bool {
+        self.state == SamplerState::Running
+    }
+
+    /// Start sampling, or resume after `pause`.
+    pub fn start(&mut self, start_time: Instant) {
+        self.start_time.get_or_insert(start_time);
+        self.state = SamplerState::Running;
+    }
+
+    pub fn pause(&mut self) {
+        if self.state == SamplerState::Running {
+            self.state = SamplerState::Paused;
+        }
+    }
+
+    /// Stop sampling and forget everything that was recorded.
+    pub fn stop(&mut self) {
+        *self = Self {
+            next_id: self.next_id,
+            ..Default::default()
+        };
+    }
+
+    /// Discard the samples taken so far.
+    ///
+    /// Objects that are still alive are still reported when they're collected.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn samples(&self) -> &VecDeque<Sample<'gc>> {
+        &self.samples
+    }
+
+    fn push_sample(&mut self, sample: Sample<'gc>) {
+        if self.samples.len() >= MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Whether any allocated objects are waiting to be reported as deleted.
+    pub fn has_live_objects(&self) -> bool {
+        !self.live_objects.is_empty()
+    }
+
+    fn now(&self) -> f64 {
+        self.start_time
+            .map_or(0.0, |start_time| start_time.elapsed().as_micros() as f64)
+    }
+
+    /// Count a call of `method`, and take a CPU sample if one is due.
+    pub fn record_call(&mut self, method: Method<'gc>, call_stack: &CallStack<'gc>) {
+        *self
+            .invocation_counts
+            .entry(method_key(method))
+            .or_default() += 1;
+
+        let now = Instant::now();
+        if self
+            .last_cpu_sample
+            .is_some_and(|last| now.duration_since(last) < SAMPLE_INTERVAL)
+        {
+            return;
+        }
+        self.last_cpu_sample = Some(now);
+        let time = self.now();
+        self.push_sample(Sample {
+            time,
+            stack: call_stack.frame_names(),
+            kind: SampleKind::Cpu,
+        });
+    }
+
+    /// Record the construction of an object.
+    pub fn record_allocation(&mut self, object: Object<'gc>, call_stack: &CallStack<'gc>) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let size = object_size(object);
+        self.live_objects.insert(
+            id,
+            LiveObject {
+                object: object.downgrade(),
+                size,
+            },
+        );
+        let time = self.now();
+        self.push_sample(Sample {
+            time,
+            stack: call_stack.frame_names(),
+            kind: SampleKind::NewObject {
+                id,
+                class: object.instance_of(),
+                size,
+            },
+        });
+    }
+
+    /// Record the deletion of every tracked object that has been collected.
+    pub fn record_collected(&mut self) {
+        let time = self.now();
+        let mut collected: Vec<(u64, usize)> = self
+            .live_objects
+            .iter()
+            .filter(|(_, live)| live.object.is_dropped())
+            .map(|(id, live)| (*id, live.size))
+            .collect();
+        // Report objects in allocation order.
+        collected.sort_unstable();
+
+        for (id, size) in collected {
+            self.live_objects.remove(&id);
+            self.push_sample(Sample {
+                time,
+                stack: Vec::new(),
+                kind: SampleKind::DeleteObject { id, size },
+            });
+        }
+    }
+
+    /// The object with the given allocation ID, if it's still alive.
+    pub fn live_object(&self, id: u64, mc: &Mutation<'gc>) -> Option<Object<'gc>> {
+        self.live_objects
+            .get(&id)
+            .and_then(|live| live.object.upgrade(mc))
+    }
+
+    /// How often `method` has been called while sampling.
+    pub fn invocation_count(&self, method: Method<'gc>) -> u32 {
+        self.invocation_counts
+            .get(&method_key(method))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+fn method_key(method: Method<'_>) -> usize {
+    match method {
+        Method::Native(method) => Gc::as_ptr(method) as usize,
+        Method::Bytecode(method) => Gc::as_ptr(method) as usize,
+    }
+}
+
+/// Estimate the number of bytes used by a value, as reported by `getSize`.
+pub fn value_size(value: Value<'_>) -> usize {
+    match value {
+        Value::Undefined | Value::Null => 0,
+        Value::Bool(_) | Value::Integer(_) => 4,
+        Value::Number(_) => 8,
+        Value::String(string) => {
+            let char_size = if string.is_wide() { 2 } else { 1 };
+            std::mem::size_of_val(&string) + string.len() * char_size
+        }
+        Value::Object(object) => object_size(object),
+    }
+}
+
+/// Estimate the number of bytes used by an object, including its slots,
+/// dynamic properties and any native storage such as array elements or pixels.
+pub fn object_size(object: Object<'_>) -> usize {
+    let value_size = std::mem::size_of::<Value>();
+
+    let mut size = std::mem::size_of_val(&*object.base());
+    if let Some(vtable) = object.vtable() {
+        size += vtable.default_slots().len() * value_size;
+    }
+    size += object.base().values.as_hashmap().len() * value_size * 2;
+
+    if let Some(array) = object.as_array_storage() {
+        size += array.length() * value_size;
+    }
+    if let Some(vector) = object.as_vector_storage() {
+        size += vector.length() * value_size;
+    }
+    if let Some(bytearray) = object.as_bytearray() {
+        size += bytearray.len();
+    }
+    if let Some(bitmap_data) = object.as_bitmap_data() {
+        size += bitmap_data.width() as usize * bitmap_data.height() as usize * 4;
+    }
+
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cpu_sample(time: f64) -> Sample<'static> {
+        Sample {
+            time,
+            stack: vec![],
+            kind: SampleKind::Cpu,
+        }
+    }
+
+    #[test]
+    fn oldest_samples_are_dropped() {
+        let mut sampler = Sampler::default();
+        for i in 0..MAX_SAMPLES + 10 {
+            sampler.push_sample(cpu_sample(i as f64));
+        }
+
+        assert_eq!(sampler.samples().len(), MAX_SAMPLES);
+        assert_eq!(sampler.samples().front().map(|s| s.time), Some(10.0));
+
+        sampler.clear();
+        assert!(sampler.samples().is_empty());
+    }
+
+    #[test]
+    fn pause_and_stop() {
+        let mut sampler = Sampler::default();
+        assert!(!sampler.is_sampling());
+
+        sampler.start(Instant::now());
+        assert!(sampler.is_sampling());
+        sampler.pause();
+        assert!(!sampler.is_sampling());
+        sampler.start(Instant::now());
+        assert!(sampler.is_sampling());
+
+        sampler.push_sample(cpu_sample(0.0));
+        sampler.stop();
+        assert!(!sampler.is_sampling());
+        assert!(sampler.samples().is_empty());
+        assert!(!sampler.has_live_objects());
+    }
+}
@@ -0,0 +1,980 @@
+//! Workers, which run a SWF in the background with their own AVM2 interpreter.
+//!
+//! Each background worker has its own `Avm2` (and so its own globals and domains),
+//! and runs its own copy of the SWF that created it. Workers are not run in
+//! parallel: once the primordial worker has run a frame, `Player` runs a frame
+//! of each background worker in turn, swapping the worker's interpreter into the
+//! update context while it runs.
+//!
+//! Since only one worker runs at a time, a call that blocks until another worker
+//! does something, such as locking a mutex held by another worker, runs frames of
+//! the other background workers until it can return (see `Worker::wait_until`).
+//! Timers and loaders started by a background worker are still run by the
+//! primordial worker's interpreter.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::{deserialize_value, serialize_value};
+use crate::avm2::api_version::ApiVersion;
+use crate::avm2::bytearray::SharedBytes;
+use crate::avm2::object::{
+    ByteArrayObject, ConditionObject, MessageChannelObject, MutexObject, Object, TObject,
+    WorkerObject,
+};
+use crate::avm2::{Avm2, Error, EventObject, Value};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::frame_lifecycle::run_worker_phases_avm2;
+use crate::limits::ExecutionLimit;
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::Instantiator;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+use gc_arena::{Collect, GcCell, Mutation};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use web_time::Instant;
+
+/// The lifecycle of a worker, as reported by `Worker.state`.
+#[derive(Clone, Copy, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum WorkerState {
+    New,
+    Running,
+    Terminated,
+}
+
+impl WorkerState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Running => "running",
+            Self::Terminated => "terminated",
+        }
+    }
+}
+
+/// The state of a `MessageChannel`, as reported by `MessageChannel.state`.
+#[derive(Clone, Copy, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum MessageChannelState {
+    Open,
+    Closing,
+    Closed,
+}
+
+impl MessageChannelState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closing => "closing",
+            Self::Closed => "closed",
+        }
+    }
+}
+
+/// The objects that represent a shared value in each worker.
+///
+/// Every worker has its own classes, so a value passed between workers needs a
+/// separate object in each of them.
+#[derive(Clone, Collect, Default)]
+#[collect(no_drop)]
+struct IsolateObjects<'gc>(Vec<(Worker<'gc>, Object<'gc>)>);
+
+impl<'gc> IsolateObjects<'gc> {
+    fn get(&self, isolate: Worker<'gc>) -> Option<Object<'gc>> {
+        self.0
+            .iter()
+            .find(|(w, _)| Worker::ptr_eq(*w, isolate))
+            .map(|(_, o)| *o)
+    }
+
+    fn insert(&mut self, isolate: Worker<'gc>, object: Object<'gc>) {
+        self.0.push((isolate, object));
+    }
+
+    fn remove(&mut self, isolate: Worker<'gc>) {
+        self.0.retain(|(w, _)| !Worker::ptr_eq(*w, isolate));
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (Worker<'gc>, Object<'gc>)> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+/// A value shared between workers, which has an object in each worker that uses it.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+enum SharedValue<'gc> {
+    Worker(Worker<'gc>),
+    MessageChannel(MessageChannel<'gc>),
+    Mutex(Mutex<'gc>),
+    Condition(Condition<'gc>),
+}
+
+impl<'gc> SharedValue<'gc> {
+    /// Drops the object representing this value in `isolate`.
+    fn remove_object(self, mc: &Mutation<'gc>, isolate: Worker<'gc>) {
+        match self {
+            Self::Worker(worker) => worker.0.write(mc).objects.remove(isolate),
+            Self::MessageChannel(channel) => channel.0.write(mc).objects.remove(isolate),
+            Self::Mutex(mutex) => mutex.0.write(mc).objects.remove(isolate),
+            Self::Condition(condition) => condition.0.write(mc).objects.remove(isolate),
+        }
+    }
+}
+
+/// A value that was serialized to be passed to another worker.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct CopiedValue(AmfValue);
+
+/// A value passed between workers, either with `setSharedProperty` or through a `MessageChannel`.
+///
+/// Most values are copied with AMF3, but shareable `ByteArray`s and the concurrency
+/// primitives themselves are passed by reference.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub enum Transferable<'gc> {
+    Copied(CopiedValue),
+    SharedBytes(SharedBytes),
+    Worker(Worker<'gc>),
+    MessageChannel(MessageChannel<'gc>),
+    Mutex(Mutex<'gc>),
+    Condition(Condition<'gc>),
+}
+
+impl<'gc> Transferable<'gc> {
+    pub fn from_value(
+        activation: &mut Activation<'_, 'gc>,
+        value: Value<'gc>,
+    ) -> Result<Self, Error<'gc>> {
+        if let Value::Object(object) = value {
+            if let Some(bytearray) = object.as_bytearray_object() {
+                if bytearray.shareable() {
+                    return Ok(Self::SharedBytes(bytearray.shared_bytes()));
+                }
+            } else if let Some(worker) = object.as_worker_object() {
+                return Ok(Self::Worker(worker.worker()));
+            } else if let Some(channel) = object.as_message_channel_object() {
+                return Ok(Self::MessageChannel(channel.channel()));
+            } else if let Some(mutex) = object.as_mutex_object() {
+                return Ok(Self::Mutex(mutex.mutex()));
+            } else if let Some(condition) = object.as_condition_object() {
+                return Ok(Self::Condition(condition.condition()));
+            }
+        }
+
+        let value = serialize_value(activation, value, AMFVersion::AMF3, &mut Default::default())
+            .unwrap_or(AmfValue::Undefined);
+        Ok(Self::Copied(CopiedValue(value)))
+    }
+
+    /// Creates the value in the worker that is currently running.
+    pub fn to_value(&self, activation: &mut Activation<'_, 'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(match self {
+            Self::Copied(CopiedValue(value)) => deserialize_value(activation, value)?,
+            Self::SharedBytes(bytes) => {
+                ByteArrayObject::from_shared_bytes(activation, bytes.clone())?.into()
+            }
+            Self::Worker(worker) => worker.object(activation)?.into(),
+            Self::MessageChannel(channel) => channel.object(activation)?.into(),
+            Self::Mutex(mutex) => mutex.object(activation)?.into(),
+            Self::Condition(condition) => condition.object(activation)?.into(),
+        })
+    }
+}
+
+/// How a call that waited for other workers ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitResult {
+    /// The worker can carry on.
+    Ready,
+
+    /// The timeout passed first.
+    TimedOut,
+
+    /// No worker that can run during this frame unblocked it before the script timeout.
+    Stuck,
+}
+
+/// An event to be dispatched the next time a worker runs.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct PendingEvent<'gc> {
+    target: Object<'gc>,
+
+    #[collect(require_static)]
+    event_type: &'static str,
+}
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Worker<'gc>(GcCell<'gc, WorkerData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct WorkerData<'gc> {
+    /// The movie run by this worker, or `None` for the primordial worker.
+    #[collect(require_static)]
+    movie: Option<Arc<SwfMovie>>,
+
+    state: WorkerState,
+
+    /// Every worker that hasn't been terminated, starting with the primordial worker.
+    registry: GcCell<'gc, Vec<Worker<'gc>>>,
+
+    /// The interpreter of a background worker, while it's not running.
+    avm2: Option<Box<Avm2<'gc>>>,
+
+    /// The root clip of a background worker, once it has been loaded.
+    root: Option<DisplayObject<'gc>>,
+
+    shared_properties: HashMap<String, Transferable<'gc>>,
+
+    objects: IsolateObjects<'gc>,
+
+    /// The shared values that have an object in this worker, which are dropped
+    /// when it terminates.
+    shared_values: Vec<SharedValue<'gc>>,
+
+    /// Events for objects in this worker, to be dispatched the next time it runs.
+    pending_events: Vec<PendingEvent<'gc>>,
+}
+
+impl<'gc> Worker<'gc> {
+    fn new(
+        mc: &Mutation<'gc>,
+        movie: Option<Arc<SwfMovie>>,
+        state: WorkerState,
+        registry: GcCell<'gc, Vec<Worker<'gc>>>,
+    ) -> Self {
+        let worker = Self(GcCell::new(
+            mc,
+            WorkerData {
+                movie,
+                state,
+                registry,
+                avm2: None,
+                root: None,
+                shared_properties: HashMap::new(),
+                objects: Default::default(),
+                shared_values: vec![],
+                pending_events: vec![],
+            },
+        ));
+        registry.write(mc).push(worker);
+        worker
+    }
+
+    pub fn ptr_eq(a: Self, b: Self) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
+    /// The worker whose interpreter is currently running.
+    pub fn current(context: &mut UpdateContext<'_, 'gc>) -> Self {
+        if let Some(worker) = context.avm2.worker {
+            return worker;
+        }
+
+        // Nothing has used workers yet, so this must be the primordial worker.
+        let registry = GcCell::new(context.gc_context, vec![]);
+        let worker = Self::new(context.gc_context, None, WorkerState::Running, registry);
+        context.avm2.worker = Some(worker);
+        worker
+    }
+
+    /// Creates a worker that will run a SWF, returning `None` if it isn't a valid AS3 movie.
+    pub fn create(context: &mut UpdateContext<'_, 'gc>, swf: &[u8]) -> Option<Self> {
+        let current = Self::current(context);
+        let movie = SwfMovie::from_data(swf, context.swf.url().to_string(), None)
+            .ok()
+            .filter(|movie| movie.is_action_script_3())?;
+        let registry = current.0.read().registry;
+        Some(Self::new(
+            context.gc_context,
+            Some(Arc::new(movie)),
+            WorkerState::New,
+            registry,
+        ))
+    }
+
+    pub fn is_primordial(self) -> bool {
+        self.0.read().movie.is_none()
+    }
+
+    pub fn state(self) -> WorkerState {
+        self.0.read().state
+    }
+
+    /// Every worker that hasn't been terminated.
+    pub fn workers(self) -> Vec<Worker<'gc>> {
+        self.0.read().registry.read().clone()
+    }
+
+    pub fn shared_property(self, key: &str) -> Option<Transferable<'gc>> {
+        self.0.read().shared_properties.get(key).cloned()
+    }
+
+    pub fn set_shared_property(self, mc: &Mutation<'gc>, key: String, value: Transferable<'gc>) {
+        self.0.write(mc).shared_properties.insert(key, value);
+    }
+
+    /// The object representing this worker in the worker that is currently running.
+    pub fn object(self, activation: &mut Activation<'_, 'gc>) -> Result<Object<'gc>, Error<'gc>> {
+        let isolate = Self::current(&mut activation.context);
+        if let Some(object) = self.0.read().objects.get(isolate) {
+            return Ok(object);
+        }
+
+        let object = WorkerObject::from_worker(activation, self)?;
+        let mc = activation.context.gc_context;
+        self.0.write(mc).objects.insert(isolate, object);
+        isolate.add_shared_value(mc, SharedValue::Worker(self));
+        Ok(object)
+    }
+
+    /// Starts running a new worker from the next frame.
+    pub fn start(self, context: &mut UpdateContext<'_, 'gc>) {
+        if self.state() != WorkerState::New {
+            return;
+        }
+
+        let player_version = context.player_version;
+        let mut avm2 = Box::new(Avm2::new(&mut context.borrow_gc(), player_version));
+        avm2.worker = Some(self);
+        self.0.write(context.gc_context).avm2 = Some(avm2);
+        self.set_state(context.gc_context, WorkerState::Running);
+    }
+
+    /// Stops a worker, returning `false` if it wasn't running.
+    pub fn terminate(self, mc: &Mutation<'gc>) -> bool {
+        if self.is_primordial() || self.state() != WorkerState::Running {
+            return false;
+        }
+
+        self.set_state(mc, WorkerState::Terminated);
+        let mut write = self.0.write(mc);
+        write.avm2 = None;
+        write.root = None;
+        write.pending_events.clear();
+        let shared_values = std::mem::take(&mut write.shared_values);
+        drop(write);
+
+        // Nothing can use this worker's objects anymore.
+        for value in shared_values {
+            value.remove_object(mc, self);
+        }
+
+        let registry = self.0.read().registry;
+        registry.write(mc).retain(|w| !Self::ptr_eq(*w, self));
+        true
+    }
+
+    fn add_shared_value(self, mc: &Mutation<'gc>, value: SharedValue<'gc>) {
+        self.0.write(mc).shared_values.push(value);
+    }
+
+    fn set_state(self, mc: &Mutation<'gc>, state: WorkerState) {
+        self.0.write(mc).state = state;
+
+        let objects = self.0.read().objects.clone();
+        for (isolate, object) in objects.iter() {
+            isolate.queue_event(mc, object, "workerState");
+        }
+    }
+
+    fn queue_event(self, mc: &Mutation<'gc>, target: Object<'gc>, event_type: &'static str) {
+        if self.state() != WorkerState::Terminated {
+            self.0
+                .write(mc)
+                .pending_events
+                .push(PendingEvent { target, event_type });
+        }
+    }
+
+    fn dispatch_pending_events(self, context: &mut UpdateContext<'_, 'gc>) {
+        let events = std::mem::take(&mut self.0.write(context.gc_context).pending_events);
+        for event in events {
+            let event_object = EventObject::bare_default_event(context, event.event_type);
+            Avm2::dispatch_event(context, event_object, event.target);
+        }
+    }
+
+    /// Runs a frame of every background worker.
+    ///
+    /// This must be called after the primordial worker has run its frame.
+    pub fn run_all(context: &mut UpdateContext<'_, 'gc>) {
+        let Some(primordial) = context.avm2.worker else {
+            // No workers have been created.
+            return;
+        };
+
+        primordial.dispatch_pending_events(context);
+        for worker in primordial.workers() {
+            if !worker.is_primordial() {
+                worker.run_frame(context);
+            }
+        }
+    }
+
+    /// Blocks the current worker until `ready` returns `true`, by running frames
+    /// of the other background workers.
+    ///
+    /// Only the background workers that aren't already running further up the
+    /// stack can run meanwhile. In particular, the primordial worker can't run
+    /// until the current frame is over, so a background worker waiting for it is
+    /// `Stuck`. A wait that outlasts the script timeout is `Stuck` too.
+    pub fn wait_until(
+        context: &mut UpdateContext<'_, 'gc>,
+        timeout: Option<Duration>,
+        mut ready: impl FnMut(&mut UpdateContext<'_, 'gc>) -> bool,
+    ) -> WaitResult {
+        let current = Self::current(context);
+        let start = Instant::now();
+        let frame_phase = *context.frame_phase;
+
+        let result = loop {
+            if ready(context) {
+                break WaitResult::Ready;
+            }
+            if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+                break WaitResult::TimedOut;
+            }
+            if context.update_start.elapsed() >= context.max_execution_duration {
+                break WaitResult::Stuck;
+            }
+
+            let mut ran_any = false;
+            for worker in current.workers() {
+                if !worker.is_primordial() {
+                    ran_any |= worker.run_frame(context);
+                }
+            }
+
+            // Nothing else can change before this frame is over.
+            if !ran_any {
+                break match timeout {
+                    Some(_) => WaitResult::TimedOut,
+                    None => WaitResult::Stuck,
+                };
+            }
+        };
+
+        context.set_frame_phase(frame_phase);
+        result
+    }
+
+    /// Runs a frame of this worker, returning `false` if it can't run right now.
+    fn run_frame(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let Some(mut avm2) = self.0.write(context.gc_context).avm2.take() else {
+            // The worker isn't running, or is waiting further up the stack.
+            return false;
+        };
+
+        std::mem::swap(context.avm2, &mut *avm2);
+
+        let root = self.0.read().root;
+        let root = match root {
+            Some(root) => Ok(root),
+            None => self.load(context),
+        };
+        match root {
+            Ok(root) => {
+                self.dispatch_pending_events(context);
+                run_worker_phases_avm2(context, root);
+            }
+            Err(e) => {
+                tracing::error!("Failed to load worker: {e:?}");
+                self.terminate(context.gc_context);
+            }
+        }
+
+        std::mem::swap(context.avm2, &mut *avm2);
+
+        // The worker may have terminated itself while it was running.
+        if self.state() == WorkerState::Running {
+            self.0.write(context.gc_context).avm2 = Some(avm2);
+        }
+        true
+    }
+
+    /// Loads a background worker's movie into its interpreter, which must be the current one.
+    fn load(self, context: &mut UpdateContext<'_, 'gc>) -> Result<DisplayObject<'gc>, Error<'gc>> {
+        let movie = self
+            .0
+            .read()
+            .movie
+            .clone()
+            .expect("Only background workers are loaded");
+
+        Avm2::load_player_globals(context)?;
+        if let Some(api_version) = ApiVersion::from_swf_version(movie.version()) {
+            context.avm2.root_api_version = api_version;
+        }
+
+        let domain = context.avm2.stage_domain();
+        let mut activation = Activation::from_domain(context.reborrow(), domain);
+        activation
+            .context
+            .library
+            .library_for_movie_mut(movie.clone())
+            .set_avm2_domain(domain);
+        let root: DisplayObject = MovieClip::player_root_movie(&mut activation, movie).into();
+        drop(activation);
+
+        root.set_depth(context.gc_context, 0);
+        root.post_instantiation(context, None, Instantiator::Movie, false);
+        if let Some(clip) = root.as_movie_clip() {
+            clip.preload(context, &mut ExecutionLimit::none());
+        }
+
+        self.0.write(context.gc_context).root = Some(root);
+        Ok(root)
+    }
+
+    /// Creates a channel for the current worker to send messages to `receiver`.
+    pub fn create_message_channel(
+        self,
+        mc: &Mutation<'gc>,
+        receiver: Worker<'gc>,
+    ) -> MessageChannel<'gc> {
+        MessageChannel(GcCell::new(
+            mc,
+            MessageChannelData {
+                sender: self,
+                receiver,
+                state: MessageChannelState::Open,
+                messages: VecDeque::new(),
+                objects: Default::default(),
+            },
+        ))
+    }
+}
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct MessageChannel<'gc>(GcCell<'gc, MessageChannelData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct MessageChannelData<'gc> {
+    sender: Worker<'gc>,
+    receiver: Worker<'gc>,
+    state: MessageChannelState,
+    messages: VecDeque<Transferable<'gc>>,
+    objects: IsolateObjects<'gc>,
+}
+
+impl<'gc> MessageChannel<'gc> {
+    pub fn state(self) -> MessageChannelState {
+        self.0.read().state
+    }
+
+    pub fn message_available(self) -> bool {
+        !self.0.read().messages.is_empty()
+    }
+
+    /// The number of messages waiting to be received.
+    pub fn queue_length(self) -> usize {
+        self.0.read().messages.len()
+    }
+
+    /// The object representing this channel in the worker that is currently running.
+    pub fn object(self, activation: &mut Activation<'_, 'gc>) -> Result<Object<'gc>, Error<'gc>> {
+        let isolate = Worker::current(&mut activation.context);
+        if let Some(object) = self.0.read().objects.get(isolate) {
+            return Ok(object);
+        }
+
+        let object = MessageChannelObject::from_channel(activation, self)?;
+        let mc = activation.context.gc_context;
+        self.0.write(mc).objects.insert(isolate, object);
+        isolate.add_shared_value(mc, SharedValue::MessageChannel(self));
+        Ok(object)
+    }
+
+    pub fn send(self, mc: &Mutation<'gc>, message: Transferable<'gc>) {
+        self.0.write(mc).messages.push_back(message);
+        self.notify_receiver(mc, "channelMessage");
+    }
+
+    pub fn receive(self, mc: &Mutation<'gc>) -> Option<Transferable<'gc>> {
+        let mut write = self.0.write(mc);
+        let message = write.messages.pop_front();
+        if write.state == MessageChannelState::Closing && write.messages.is_empty() {
+            write.state = MessageChannelState::Closed;
+            drop(write);
+            self.notify_both(mc, "channelState");
+        }
+        message
+    }
+
+    /// Closes the channel, once the receiver has taken every queued message.
+    pub fn close(self, mc: &Mutation<'gc>) {
+        let mut write = self.0.write(mc);
+        if write.state != MessageChannelState::Open {
+            return;
+        }
+        write.state = if write.messages.is_empty() {
+            MessageChannelState::Closed
+        } else {
+            MessageChannelState::Closing
+        };
+        drop(write);
+
+        self.notify_both(mc, "channelState");
+    }
+
+    fn notify_receiver(self, mc: &Mutation<'gc>, event_type: &'static str) {
+        let receiver = self.0.read().receiver;
+        let object = self.0.read().objects.get(receiver);
+        if let Some(object) = object {
+            receiver.queue_event(mc, object, event_type);
+        }
+    }
+
+    fn notify_both(self, mc: &Mutation<'gc>, event_type: &'static str) {
+        let read = self.0.read();
+        let (sender, receiver) = (read.sender, read.receiver);
+        let objects = read.objects.clone();
+        drop(read);
+
+        for (isolate, object) in objects.iter() {
+            if Worker::ptr_eq(isolate, sender) || Worker::ptr_eq(isolate, receiver) {
+                isolate.queue_event(mc, object, event_type);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Mutex<'gc>(GcCell<'gc, MutexData<'gc>>);
+
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct MutexData<'gc> {
+    /// The worker holding the lock, and how many times it has locked it.
+    owner: Option<(Worker<'gc>, u32)>,
+
+    objects: IsolateObjects<'gc>,
+}
+
+impl<'gc> Mutex<'gc> {
+    pub fn new(mc: &Mutation<'gc>) -> Self {
+        Self(GcCell::new(mc, Default::default()))
+    }
+
+    pub fn ptr_eq(a: Self, b: Self) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
+    /// Registers the object created for this mutex in the worker that is currently running.
+    pub fn set_object(self, context: &mut UpdateContext<'_, 'gc>, object: Object<'gc>) {
+        let isolate = Worker::current(context);
+        self.0
+            .write(context.gc_context)
+            .objects
+            .insert(isolate, object);
+        isolate.add_shared_value(context.gc_context, SharedValue::Mutex(self));
+    }
+
+    /// The object representing this mutex in the worker that is currently running.
+    pub fn object(self, activation: &mut Activation<'_, 'gc>) -> Result<Object<'gc>, Error<'gc>> {
+        let isolate = Worker::current(&mut activation.context);
+        if let Some(object) = self.0.read().objects.get(isolate) {
+            return Ok(object);
+        }
+
+        let object = MutexObject::from_mutex(activation, self)?;
+        self.set_object(&mut activation.context, object);
+        Ok(object)
+    }
+
+    pub fn is_owned_by(self, worker: Worker<'gc>) -> bool {
+        self.0
+            .read()
+            .owner
+            .is_some_and(|(owner, _)| Worker::ptr_eq(owner, worker))
+    }
+
+    /// Locks the mutex for `worker`, returning `false` if another worker holds it.
+    ///
+    /// A worker can lock a mutex it already holds, and must unlock it as many times.
+    pub fn try_lock(self, mc: &Mutation<'gc>, worker: Worker<'gc>) -> bool {
+        let mut write = self.0.write(mc);
+        match write.owner {
+            Some((owner, count)) if Worker::ptr_eq(owner, worker) => {
+                write.owner = Some((owner, count + 1));
+                true
+            }
+            Some(_) => false,
+            None => {
+                write.owner = Some((worker, 1));
+                true
+            }
+        }
+    }
+
+    /// Fully unlocks the mutex, returning how many times `worker` had locked it.
+    pub fn release(self, mc: &Mutation<'gc>, worker: Worker<'gc>) -> Option<u32> {
+        let mut write = self.0.write(mc);
+        match write.owner {
+            Some((owner, count)) if Worker::ptr_eq(owner, worker) => {
+                write.owner = None;
+                Some(count)
+            }
+            _ => None,
+        }
+    }
+
+    /// Locks the mutex `count` times for `worker`, returning `false` if another worker holds it.
+    pub fn reacquire(self, mc: &Mutation<'gc>, worker: Worker<'gc>, count: u32) -> bool {
+        let mut write = self.0.write(mc);
+        if write.owner.is_some() {
+            return false;
+        }
+        write.owner = Some((worker, count));
+        true
+    }
+
+    /// Unlocks the mutex once, returning `false` if `worker` doesn't hold it.
+    pub fn unlock(self, mc: &Mutation<'gc>, worker: Worker<'gc>) -> bool {
+        let mut write = self.0.write(mc);
+        match write.owner {
+            Some((owner, count)) if Worker::ptr_eq(owner, worker) => {
+                write.owner = (count > 1).then_some((owner, count - 1));
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Condition<'gc>(GcCell<'gc, ConditionData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct ConditionData<'gc> {
+    mutex: Mutex<'gc>,
+
+    /// The workers waiting to be notified, in the order they started waiting.
+    waiting: Vec<Worker<'gc>>,
+
+    objects: IsolateObjects<'gc>,
+}
+
+impl<'gc> Condition<'gc> {
+    pub fn new(mc: &Mutation<'gc>, mutex: Mutex<'gc>) -> Self {
+        Self(GcCell::new(
+            mc,
+            ConditionData {
+                mutex,
+                waiting: vec![],
+                objects: Default::default(),
+            },
+        ))
+    }
+
+    pub fn mutex(self) -> Mutex<'gc> {
+        self.0.read().mutex
+    }
+
+    /// Starts waiting for `worker` to be notified.
+    pub fn add_waiting(self, mc: &Mutation<'gc>, worker: Worker<'gc>) {
+        self.0.write(mc).waiting.push(worker);
+    }
+
+    /// Stops waiting for `worker`, returning `false` if it had already been notified.
+    pub fn remove_waiting(self, mc: &Mutation<'gc>, worker: Worker<'gc>) -> bool {
+        let mut write = self.0.write(mc);
+        let len = write.waiting.len();
+        write.waiting.retain(|w| !Worker::ptr_eq(*w, worker));
+        write.waiting.len() != len
+    }
+
+    pub fn is_waiting(self, worker: Worker<'gc>) -> bool {
+        self.0
+            .read()
+            .waiting
+            .iter()
+            .any(|w| Worker::ptr_eq(*w, worker))
+    }
+
+    /// Wakes the worker that has been waiting the longest.
+    pub fn notify(self, mc: &Mutation<'gc>) {
+        let mut write = self.0.write(mc);
+        if !write.waiting.is_empty() {
+            write.waiting.remove(0);
+        }
+    }
+
+    /// Wakes every waiting worker.
+    pub fn notify_all(self, mc: &Mutation<'gc>) {
+        self.0.write(mc).waiting.clear();
+    }
+
+    /// Registers the object created for this condition in the worker that is currently running.
+    pub fn set_object(self, context: &mut UpdateContext<'_, 'gc>, object: Object<'gc>) {
+        let isolate = Worker::current(context);
+        self.0
+            .write(context.gc_context)
+            .objects
+            .insert(isolate, object);
+        isolate.add_shared_value(context.gc_context, SharedValue::Condition(self));
+    }
+
+    /// The object representing this condition in the worker that is currently running.
+    pub fn object(self, activation: &mut Activation<'_, 'gc>) -> Result<Object<'gc>, Error<'gc>> {
+        let isolate = Worker::current(&mut activation.context);
+        if let Some(object) = self.0.read().objects.get(isolate) {
+            return Ok(object);
+        }
+
+        let object = ConditionObject::from_condition(activation, self)?;
+        self.set_object(&mut activation.context, object);
+        Ok(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gc_arena::rootless_arena;
+
+    /// Creates a primordial worker and a running background worker.
+    fn workers<'gc>(mc: &Mutation<'gc>) -> (Worker<'gc>, Worker<'gc>) {
+        let registry = GcCell::new(mc, vec![]);
+        let primordial = Worker::new(mc, None, WorkerState::Running, registry);
+        let movie = Some(Arc::new(SwfMovie::empty(10)));
+        let background = Worker::new(mc, movie, WorkerState::Running, registry);
+        (primordial, background)
+    }
+
+    fn copied<'gc>(value: f64) -> Transferable<'gc> {
+        Transferable::Copied(CopiedValue(AmfValue::Number(value)))
+    }
+
+    fn number(message: Option<Transferable<'_>>) -> Option<f64> {
+        match message {
+            Some(Transferable::Copied(CopiedValue(AmfValue::Number(n)))) => Some(n),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn mutex_lock_and_unlock() {
+        rootless_arena(|mc| {
+            let (worker, other) = workers(mc);
+            let mutex = Mutex::new(mc);
+            assert!(!mutex.unlock(mc, worker));
+
+            assert!(mutex.try_lock(mc, worker));
+            assert!(mutex.try_lock(mc, worker));
+            assert!(!mutex.try_lock(mc, other));
+            assert!(!mutex.unlock(mc, other));
+
+            assert!(mutex.unlock(mc, worker));
+            assert!(mutex.is_owned_by(worker));
+            assert!(mutex.unlock(mc, worker));
+            assert!(!mutex.is_owned_by(worker));
+            assert!(!mutex.unlock(mc, worker));
+
+            assert!(mutex.try_lock(mc, other));
+            assert!(mutex.is_owned_by(other));
+        });
+    }
+
+    #[test]
+    fn mutex_release_and_reacquire() {
+        rootless_arena(|mc| {
+            let (worker, other) = workers(mc);
+            let mutex = Mutex::new(mc);
+            assert!(mutex.try_lock(mc, worker));
+            assert!(mutex.try_lock(mc, worker));
+            assert_eq!(mutex.release(mc, other), None);
+            assert_eq!(mutex.release(mc, worker), Some(2));
+            assert!(!mutex.is_owned_by(worker));
+
+            assert!(mutex.try_lock(mc, other));
+            assert!(!mutex.reacquire(mc, worker, 2));
+            assert!(mutex.unlock(mc, other));
+            assert!(mutex.reacquire(mc, worker, 2));
+            assert!(mutex.unlock(mc, worker));
+            assert!(mutex.unlock(mc, worker));
+            assert!(!mutex.is_owned_by(worker));
+        });
+    }
+
+    #[test]
+    fn condition_notifies_in_order() {
+        rootless_arena(|mc| {
+            let (first, second) = workers(mc);
+            let condition = Condition::new(mc, Mutex::new(mc));
+            condition.add_waiting(mc, first);
+            condition.add_waiting(mc, second);
+
+            condition.notify(mc);
+            assert!(!condition.is_waiting(first));
+            assert!(condition.is_waiting(second));
+
+            condition.add_waiting(mc, first);
+            condition.notify_all(mc);
+            assert!(!condition.is_waiting(first));
+            assert!(!condition.is_waiting(second));
+
+            condition.add_waiting(mc, first);
+            assert!(condition.remove_waiting(mc, first));
+            assert!(!condition.remove_waiting(mc, first));
+        });
+    }
+
+    #[test]
+    fn message_channel_closes_once_drained() {
+        rootless_arena(|mc| {
+            let (sender, receiver) = workers(mc);
+            let channel = sender.create_message_channel(mc, receiver);
+            assert_eq!(channel.state(), MessageChannelState::Open);
+            assert!(!channel.message_available());
+
+            channel.send(mc, copied(1.0));
+            channel.send(mc, copied(2.0));
+            assert!(channel.message_available());
+            assert_eq!(channel.queue_length(), 2);
+
+            channel.close(mc);
+            assert_eq!(channel.state(), MessageChannelState::Closing);
+            assert_eq!(number(channel.receive(mc)), Some(1.0));
+            assert_eq!(channel.state(), MessageChannelState::Closing);
+            assert_eq!(number(channel.receive(mc)), Some(2.0));
+            assert_eq!(channel.state(), MessageChannelState::Closed);
+            assert!(channel.receive(mc).is_none());
+        });
+    }
+
+    #[test]
+    fn empty_message_channel_closes_immediately() {
+        rootless_arena(|mc| {
+            let (sender, receiver) = workers(mc);
+            let channel = sender.create_message_channel(mc, receiver);
+            channel.close(mc);
+            assert_eq!(channel.state(), MessageChannelState::Closed);
+        });
+    }
+
+    #[test]
+    fn terminate_unregisters_worker() {
+        rootless_arena(|mc| {
+            let (primordial, background) = workers(mc);
+            assert!(!primordial.terminate(mc));
+            assert_eq!(primordial.workers().len(), 2);
+
+            assert!(background.terminate(mc));
+            assert_eq!(background.state(), WorkerState::Terminated);
+            assert_eq!(primordial.workers().len(), 1);
+            assert!(!background.terminate(mc));
+        });
+    }
+}
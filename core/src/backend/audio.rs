@@ -1,6 +1,9 @@
 use crate::{
     avm1::SoundObject,
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{
+        Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+        SoundChannelObject, TObject as _,
+    },
     buffer::Substream,
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
@@ -131,6 +134,36 @@ pub trait AudioBackend: Downcast {
         stream_info: &SoundStreamInfo,
     ) -> Result<SoundInstanceHandle, DecodeError>;
 
+    /// Starts playing a sound whose sample data is provided on demand by ActionScript,
+    /// through `SampleDataEvent`.
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError>;
+
+    /// Appends 44.1KHz stereo sample frames to a sound started with `start_dynamic_sound`.
+    ///
+    /// If `is_final` is set, the sound ends once all of its data has been played.
+    fn append_dynamic_sound_data(
+        &mut self,
+        instance: SoundInstanceHandle,
+        frames: &[[f32; 2]],
+        is_final: bool,
+    );
+
+    /// Returns the number of sample frames that were appended to a dynamic sound,
+    /// but haven't been played yet.
+    /// Returns `None` if the sound is not/no longer playing.
+    fn dynamic_sound_buffered_frames(&mut self, instance: SoundInstanceHandle) -> Option<u32>;
+
+    /// Decodes a registered sound into 44.1KHz stereo sample frames, as used by `Sound.extract`.
+    ///
+    /// Returns at most `num_frames` frames, starting at `start_frame`.
+    /// Returns `None` if the sound is not registered or can't be decoded.
+    fn extract_sound(
+        &mut self,
+        sound: SoundHandle,
+        start_frame: u32,
+        num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>>;
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -290,6 +323,33 @@ impl AudioBackend for NullAudioBackend {
         Ok(SoundInstanceHandle::from_raw_parts(0, 0))
     }
 
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(SoundInstanceHandle::from_raw_parts(0, 0))
+    }
+
+    fn append_dynamic_sound_data(
+        &mut self,
+        _instance: SoundInstanceHandle,
+        _frames: &[[f32; 2]],
+        _is_final: bool,
+    ) {
+    }
+
+    fn dynamic_sound_buffered_frames(&mut self, _instance: SoundInstanceHandle) -> Option<u32> {
+        // Nothing is ever played, so report the sound as stopped instead of running out of data,
+        // which would request `sampleData` events every frame.
+        None
+    }
+
+    fn extract_sound(
+        &mut self,
+        _sound: SoundHandle,
+        _start_frame: u32,
+        _num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        None
+    }
+
     fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
 
     fn stop_all_sounds(&mut self) {}
@@ -368,6 +428,17 @@ impl<'gc> AudioManager<'gc> {
     /// The default timeline stream buffer time in seconds.
     pub const DEFAULT_STREAM_BUFFER_TIME: i32 = 5;
 
+    /// Dynamic sounds request more data with `sampleData` events while they have fewer
+    /// sample frames than this buffered.
+    pub const SAMPLE_DATA_BUFFER_FRAMES: u32 = 8192;
+
+    /// The maximum number of `sampleData` events dispatched to a single sound per frame.
+    pub const MAX_SAMPLE_DATA_REQUESTS: usize = 4;
+
+    /// If a `sampleData` listener provides fewer sample frames than this, the sound ends
+    /// once it has played them.
+    pub const MIN_SAMPLE_DATA_FRAMES: usize = 2048;
+
     /// The threshold in seconds where an audio stream is considered too out-of-sync and will be stopped.
     pub const STREAM_RESTART_THRESHOLD: f64 = 1.0;
 
@@ -437,6 +508,18 @@ impl<'gc> AudioManager<'gc> {
             Avm2::dispatch_event(context, event, target.into());
         }
 
+        // Top up dynamic sounds that are running out of data.
+        let dynamic_sounds: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter(|sound| sound.sample_data_source.is_some())
+            .map(|sound| sound.instance)
+            .collect();
+        for instance in dynamic_sounds {
+            Self::request_sample_data(context, instance);
+        }
+
         // Update sound transforms, if dirty.
         context.audio_manager.update_sound_transforms(context.audio);
     }
@@ -459,6 +542,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                sample_data_source: None,
+                sample_data_position: 0.0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -468,6 +553,118 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Starts a sound whose data is provided by `sampleData` events dispatched to `source`.
+    pub fn start_dynamic_sound(
+        context: &mut UpdateContext<'_, 'gc>,
+        source: Avm2Object<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        let audio_manager = &mut context.audio_manager;
+        if audio_manager.sounds.len() >= Self::MAX_SOUNDS {
+            return None;
+        }
+        let handle = context.audio.start_dynamic_sound().ok()?;
+        let instance = SoundInstance {
+            sound: None,
+            instance: handle,
+            display_object: None,
+            transform: display_object::SoundTransform::default(),
+            avm1_object: None,
+            avm2_object: None,
+            stream_start_frame: None,
+            sample_data_source: Some(source),
+            sample_data_position: 0.0,
+        };
+        context
+            .audio
+            .set_sound_transform(handle, audio_manager.transform_for_sound(&instance));
+        audio_manager.sounds.push(instance);
+
+        // Flash asks for the first data immediately.
+        Self::request_sample_data(context, handle);
+        Some(handle)
+    }
+
+    /// Dispatches `sampleData` events for a dynamic sound until it has enough data buffered.
+    fn request_sample_data(context: &mut UpdateContext<'_, 'gc>, instance: SoundInstanceHandle) {
+        for _ in 0..Self::MAX_SAMPLE_DATA_REQUESTS {
+            match context.audio.dynamic_sound_buffered_frames(instance) {
+                Some(buffered) if buffered < Self::SAMPLE_DATA_BUFFER_FRAMES => {}
+                _ => return,
+            }
+            let Some(sound) = context
+                .audio_manager
+                .sounds
+                .iter()
+                .find(|sound| sound.instance == instance)
+            else {
+                return;
+            };
+            let (Some(source), position) = (sound.sample_data_source, sound.sample_data_position)
+            else {
+                return;
+            };
+
+            let frames = {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                Self::dispatch_sample_data_event(&mut activation, source, position).unwrap_or_else(
+                    |e| {
+                        tracing::error!("Error dispatching sampleData event: {e:?}");
+                        Vec::new()
+                    },
+                )
+            };
+            let is_final = frames.len() < Self::MIN_SAMPLE_DATA_FRAMES;
+            context
+                .audio
+                .append_dynamic_sound_data(instance, &frames, is_final);
+            if let Some(sound) = context
+                .audio_manager
+                .sounds
+                .iter_mut()
+                .find(|sound| sound.instance == instance)
+            {
+                sound.sample_data_position += frames.len() as f64;
+            }
+            if is_final {
+                return;
+            }
+        }
+    }
+
+    /// Dispatches a `sampleData` event, and returns the sample frames that the listeners wrote.
+    fn dispatch_sample_data_event(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        source: Avm2Object<'gc>,
+        position: f64,
+    ) -> Result<Vec<[f32; 2]>, crate::avm2::Error<'gc>> {
+        let data = activation
+            .avm2()
+            .classes()
+            .bytearray
+            .construct(activation, &[])?;
+        let event = activation.avm2().classes().sampledataevent.construct(
+            activation,
+            &[
+                "sampleData".into(),
+                false.into(),
+                false.into(),
+                position.into(),
+                data.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, event, source);
+
+        let data = data.as_bytearray().expect("data is a ByteArray");
+        let frames = (0..data.len() / 8)
+            .map(|i| {
+                let left = data.read_float_at(i * 8).unwrap_or_default();
+                let right = data.read_float_at(i * 8 + 4).unwrap_or_default();
+                [left, right]
+            })
+            .collect();
+        Ok(frames)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -556,6 +753,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                sample_data_source: None,
+                sample_data_position: 0.0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -582,6 +781,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: None,
+                sample_data_source: None,
+                sample_data_position: 0.0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -765,6 +966,12 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The AVM2 `Sound` object that provides the data of a dynamic sound with `sampleData` events.
+    sample_data_source: Option<Avm2Object<'gc>>,
+
+    /// The number of sample frames requested by `sampleData` events so far.
+    sample_data_position: f64,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
@@ -4,7 +4,9 @@ use crate::backend::audio::{DecodeError, RegisterError};
 use crate::buffer::Substream;
 use crate::tag_utils::SwfSlice;
 use generational_arena::Arena;
+use std::collections::VecDeque;
 use std::io::Cursor;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use swf::AudioCompression;
 
@@ -70,6 +72,24 @@ pub struct AudioMixer {
 
     /// The last two windows of output samples.
     output_memory: Arc<RwLock<CircBuf>>,
+
+    /// The sample queues of playing dynamic sounds, which are fed by `SampleDataEvent`.
+    dynamic_sounds: Vec<(SoundInstanceHandle, Arc<DynamicSoundBuffer>)>,
+
+    /// The decoder of the last `Sound.extract` call, which continues where it stopped if the
+    /// next call extracts the frames right after it.
+    extractor: Option<SoundExtractor>,
+}
+
+/// A sound being decoded for `Sound.extract`, resampled to 44.1KHz.
+struct SoundExtractor {
+    sound: SoundHandle,
+    /// The position of the next frame of `stream`, in 44.1KHz sample frames.
+    position: u32,
+    stream: dasp::signal::interpolate::Converter<
+        EventSoundStream,
+        dasp::interpolate::linear::Linear<[i16; 2]>,
+    >,
 }
 
 /// An audio stream.
@@ -137,6 +157,91 @@ impl<D: Decoder> dasp::signal::Signal for DecoderStream<D> {
     }
 }
 
+/// The sample queue of a dynamic sound, shared between the player and the audio thread.
+struct DynamicSoundBuffer {
+    queue: Mutex<DynamicSoundQueue>,
+
+    /// The number of sample frames that were played so far.
+    played: AtomicU32,
+}
+
+#[derive(Default)]
+struct DynamicSoundQueue {
+    frames: VecDeque<[i16; 2]>,
+
+    /// The number of sample frames that were appended so far.
+    appended: u32,
+
+    /// Whether the script stopped providing data, so that the sound ends once the queue is empty.
+    is_final: bool,
+}
+
+/// A stream that plays sample frames provided by ActionScript through `SampleDataEvent`.
+///
+/// Frames are moved out of the shared queue in batches, so that the audio thread doesn't need to
+/// lock it for every frame.
+struct DynamicSoundStream {
+    buffer: Arc<DynamicSoundBuffer>,
+    frames: VecDeque<[i16; 2]>,
+    is_final: bool,
+    is_exhausted: bool,
+}
+
+impl DynamicSoundStream {
+    /// Dynamic sounds are always provided at 44.1KHz.
+    const SAMPLE_RATE: u16 = 44100;
+
+    fn new(buffer: Arc<DynamicSoundBuffer>) -> Self {
+        Self {
+            buffer,
+            frames: VecDeque::new(),
+            is_final: false,
+            is_exhausted: false,
+        }
+    }
+}
+
+impl Stream for DynamicSoundStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.buffer.played.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        Self::SAMPLE_RATE
+    }
+}
+
+impl dasp::signal::Signal for DynamicSoundStream {
+    type Frame = [i16; 2];
+
+    fn next(&mut self) -> [i16; 2] {
+        if self.frames.is_empty() {
+            let mut queue = self
+                .buffer
+                .queue
+                .lock()
+                .expect("Cannot be called reentrant");
+            std::mem::swap(&mut self.frames, &mut queue.frames);
+            self.is_final = queue.is_final;
+        }
+        if let Some(frame) = self.frames.pop_front() {
+            self.buffer.played.fetch_add(1, Ordering::Relaxed);
+            frame
+        } else {
+            // Play silence while waiting for more data, unless the script stopped providing it.
+            self.is_exhausted = self.is_final;
+            Default::default()
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
 /// Contains the data and metadata for a sound in an SWF file.
 ///
 /// A sound is defined by the `DefineSound` SWF tags and contains the audio data for the sound.
@@ -245,6 +350,8 @@ impl AudioMixer {
             num_output_channels,
             output_sample_rate,
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
+            dynamic_sounds: Vec::new(),
+            extractor: None,
         }
     }
 
@@ -624,6 +731,133 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts a sound that is fed with sample data by ActionScript.
+    pub fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        let buffer = Arc::new(DynamicSoundBuffer {
+            queue: Mutex::new(DynamicSoundQueue::default()),
+            played: AtomicU32::new(0),
+        });
+        let stream = self.make_resampler(DynamicSoundStream::new(Arc::clone(&buffer)));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(SoundInstance::new_stream(Box::new(stream)));
+        self.dynamic_sounds.push((handle, buffer));
+        Ok(handle)
+    }
+
+    /// Appends 44.1KHz sample frames to a dynamic sound.
+    pub fn append_dynamic_sound_data(
+        &mut self,
+        instance: SoundInstanceHandle,
+        frames: &[[f32; 2]],
+        is_final: bool,
+    ) {
+        use dasp::Sample;
+
+        if let Some((_, buffer)) = self.dynamic_sounds.iter().find(|(h, _)| *h == instance) {
+            let mut queue = buffer.queue.lock().expect("Cannot be called reentrant");
+            queue.frames.extend(
+                frames
+                    .iter()
+                    .map(|[left, right]| [left.to_sample(), right.to_sample()]),
+            );
+            queue.appended += frames.len() as u32;
+            queue.is_final |= is_final;
+        }
+    }
+
+    /// Returns the number of sample frames that were given to a dynamic sound,
+    /// but haven't been played yet.
+    ///
+    /// Returns `None` if the sound is no longer playing.
+    pub fn dynamic_sound_buffered_frames(&mut self, instance: SoundInstanceHandle) -> Option<u32> {
+        let is_playing = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant")
+            .contains(instance);
+        if !is_playing {
+            self.dynamic_sounds.retain(|(h, _)| *h != instance);
+            return None;
+        }
+        let (_, buffer) = self.dynamic_sounds.iter().find(|(h, _)| *h == instance)?;
+        let appended = buffer
+            .queue
+            .lock()
+            .expect("Cannot be called reentrant")
+            .appended;
+        Some(appended.saturating_sub(buffer.played.load(Ordering::Relaxed)))
+    }
+
+    /// Decodes part of a registered sound into 44.1KHz sample frames.
+    ///
+    /// Returns at most `num_frames` frames, starting at `start_frame`.
+    pub fn extract_sound(
+        &mut self,
+        sound_handle: SoundHandle,
+        start_frame: u32,
+        num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        use dasp::signal::Signal;
+        use dasp::Sample;
+
+        let sound = self.sounds.get(sound_handle)?;
+        let mut extractor = match self.extractor.take() {
+            // Scripts usually extract a sound in consecutive chunks, so keep decoding from
+            // where the last call stopped instead of seeking from the start again.
+            Some(extractor)
+                if extractor.sound == sound_handle && extractor.position == start_frame =>
+            {
+                extractor
+            }
+            _ => {
+                let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+                let decoder = Self::make_seekable_decoder(&sound.format, data).ok()?;
+                let settings = swf::SoundInfo {
+                    event: swf::SoundEvent::Start,
+                    in_sample: Some(start_frame),
+                    out_sample: None,
+                    num_loops: 1,
+                    envelope: None,
+                };
+                let mut stream = EventSoundStream::new_with_settings(
+                    decoder,
+                    &settings,
+                    sound.num_sample_frames,
+                    sound.skip_sample_frames,
+                );
+
+                // Resample to 44.1KHz, which is the rate that `Sound.extract` always uses.
+                let left = stream.next();
+                let right = stream.next();
+                let interpolator = dasp::interpolate::linear::Linear::new(left, right);
+                let sample_rate = stream.source_sample_rate().into();
+                SoundExtractor {
+                    sound: sound_handle,
+                    position: start_frame,
+                    stream: dasp::signal::interpolate::Converter::from_hz_to_hz(
+                        stream,
+                        interpolator,
+                        sample_rate,
+                        44100.0,
+                    ),
+                }
+            }
+        };
+
+        let mut frames = Vec::with_capacity(num_frames as usize);
+        while frames.len() < num_frames as usize && !extractor.stream.is_exhausted() {
+            let [left, right] = extractor.stream.next();
+            frames.push([left.to_sample(), right.to_sample()]);
+        }
+        extractor.position = extractor.position.saturating_add(frames.len() as u32);
+        self.extractor = Some(extractor);
+        Some(frames)
+    }
+
     /// Stops a playing sound instance.
     pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self
@@ -631,6 +865,7 @@ impl AudioMixer {
             .lock()
             .expect("Cannot be called reentrant");
         sound_instances.remove(sound);
+        self.dynamic_sounds.retain(|(h, _)| *h != sound);
     }
 
     pub fn stop_all_sounds(&mut self) {
@@ -646,6 +881,7 @@ impl AudioMixer {
             sound_instances.remove(i);
         }
         sound_instances.clear();
+        self.dynamic_sounds.clear();
     }
 
     /// Returns the position of a playing sound in milliseconds.
@@ -1117,6 +1353,37 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.start_substream(stream_data, stream_info)
         }
 
+        #[inline]
+        fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_dynamic_sound()
+        }
+
+        #[inline]
+        fn append_dynamic_sound_data(
+            &mut self,
+            instance: SoundInstanceHandle,
+            frames: &[[f32; 2]],
+            is_final: bool,
+        ) {
+            self.$mixer
+                .append_dynamic_sound_data(instance, frames, is_final)
+        }
+
+        #[inline]
+        fn dynamic_sound_buffered_frames(&mut self, instance: SoundInstanceHandle) -> Option<u32> {
+            self.$mixer.dynamic_sound_buffered_frames(instance)
+        }
+
+        #[inline]
+        fn extract_sound(
+            &mut self,
+            sound: SoundHandle,
+            start_frame: u32,
+            num_frames: u32,
+        ) -> Option<Vec<[f32; 2]>> {
+            self.$mixer.extract_sound(sound, start_frame, num_frames)
+        }
+
         #[inline]
         fn stop_sound(&mut self, sound: SoundInstanceHandle) {
             self.$mixer.stop_sound(sound)
@@ -1176,3 +1443,77 @@ macro_rules! impl_audio_mixer_backend {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mixes `num_frames` stereo frames, and returns them.
+    fn mix(mixer: &mut AudioMixer, num_frames: usize) -> Vec<i16> {
+        let mut output = vec![0i16; num_frames * 2];
+        mixer.mix(&mut output);
+        output
+    }
+
+    #[test]
+    fn dynamic_sound_buffer() {
+        let mut mixer = AudioMixer::new(2, 44100);
+        let instance = mixer.start_dynamic_sound().unwrap();
+        assert_eq!(mixer.dynamic_sound_buffered_frames(instance), Some(0));
+
+        mixer.append_dynamic_sound_data(instance, &[[0.5, -0.5]; 100], false);
+        assert_eq!(mixer.dynamic_sound_buffered_frames(instance), Some(100));
+
+        // The resampler reads a few frames ahead, so the exact amount depends on it.
+        mix(&mut mixer, 40);
+        let buffered = mixer.dynamic_sound_buffered_frames(instance).unwrap();
+        assert!((55..=65).contains(&buffered), "{buffered} frames buffered");
+
+        // Without data, silence is played until the script signals the end of the sound.
+        mix(&mut mixer, 200);
+        assert_eq!(mixer.dynamic_sound_buffered_frames(instance), Some(0));
+        mixer.append_dynamic_sound_data(instance, &[], true);
+        mix(&mut mixer, 200);
+        assert_eq!(mixer.dynamic_sound_buffered_frames(instance), None);
+    }
+
+    #[test]
+    fn extract_sound() {
+        let data: Vec<u8> = (0..10i16)
+            .flat_map(|i| [i * 1000, -i * 1000])
+            .flat_map(i16::to_le_bytes)
+            .collect();
+        let mut mixer = AudioMixer::new(2, 44100);
+        let sound = mixer
+            .register_sound(&swf::Sound {
+                id: 1,
+                format: swf::SoundFormat {
+                    compression: AudioCompression::Uncompressed,
+                    sample_rate: 44100,
+                    is_stereo: true,
+                    is_16_bit: true,
+                },
+                num_samples: 10,
+                data: &data,
+            })
+            .unwrap();
+
+        let frames = mixer.extract_sound(sound, 2, 3).unwrap();
+        let expected: Vec<_> = (2..5i16)
+            .map(|i| {
+                let sample = f32::from(i * 1000) / 32768.0;
+                [sample, -sample]
+            })
+            .collect();
+        assert_eq!(frames, expected);
+
+        assert!(mixer.extract_sound(sound, 0, 100).unwrap().len() <= 11);
+
+        // Extracting in consecutive chunks gives the same frames as extracting them at once.
+        let whole = mixer.extract_sound(sound, 1, 7).unwrap();
+        let mut chunks = mixer.extract_sound(sound, 1, 3).unwrap();
+        chunks.extend(mixer.extract_sound(sound, 4, 2).unwrap());
+        chunks.extend(mixer.extract_sound(sound, 6, 2).unwrap());
+        assert_eq!(chunks, whole);
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::{Error, ErrorKind, Read};
+
+/// A message sent through a `LocalConnection`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalConnectionMessage {
+    /// The full name of the receiving connection, such as `localhost:name` or `_name`.
+    pub connection_name: String,
+
+    /// The domain of the movie that sent the message.
+    pub sender_domain: String,
+
+    /// The method to call on the receiving connection.
+    pub method_name: String,
+
+    /// The arguments of the call, as an AMF0 LSO with one element per argument.
+    pub arguments: Vec<u8>,
+}
+
+impl LocalConnectionMessage {
+    /// Encode this message, so that it can be sent to another process.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        for field in [
+            self.connection_name.as_bytes(),
+            self.sender_domain.as_bytes(),
+            self.method_name.as_bytes(),
+            &self.arguments,
+        ] {
+            bytes.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes
+    }
+
+    /// Decode a message that was encoded with `to_bytes`.
+    ///
+    /// The data may come from anywhere, so lengths are checked against the
+    /// data that's left before anything is allocated.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, Error> {
+        fn read_field(bytes: &mut &[u8]) -> Result<Vec<u8>, Error> {
+            let mut len = [0; 4];
+            bytes.read_exact(&mut len)?;
+            let len = u32::from_le_bytes(len) as usize;
+            if len > bytes.len() {
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "LocalConnection message field is longer than the message",
+                ));
+            }
+            let (field, rest) = bytes.split_at(len);
+            *bytes = rest;
+            Ok(field.to_vec())
+        }
+
+        fn read_string(bytes: &mut &[u8]) -> Result<String, Error> {
+            String::from_utf8(read_field(bytes)?).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+        }
+
+        Ok(Self {
+            connection_name: read_string(&mut bytes)?,
+            sender_domain: read_string(&mut bytes)?,
+            method_name: read_string(&mut bytes)?,
+            arguments: read_field(&mut bytes)?,
+        })
+    }
+}
+
+/// Lets `LocalConnection`s reach connections that belong to other players,
+/// such as players running in other processes.
+///
+/// Connections within the same player never go through this backend.
+pub trait LocalConnectionBackend {
+    /// Claim a connection name for this player.
+    ///
+    /// Returns `false` if another player is already listening on it.
+    fn register(&mut self, name: &str) -> bool;
+
+    /// Release a connection name that was claimed with `register`.
+    fn unregister(&mut self, name: &str);
+
+    /// Send a message to a connection that belongs to another player.
+    ///
+    /// Returns `false` if no other player is listening on the connection.
+    fn send(&mut self, message: LocalConnectionMessage) -> bool;
+
+    /// Take the messages that other players have sent to this player's connections.
+    fn receive(&mut self) -> Vec<LocalConnectionMessage>;
+}
+
+/// Local connection backend that doesn't bridge to any other player.
+#[derive(Default)]
+pub struct NullLocalConnectionBackend;
+
+impl NullLocalConnectionBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LocalConnectionBackend for NullLocalConnectionBackend {
+    fn register(&mut self, _name: &str) -> bool {
+        true
+    }
+
+    fn unregister(&mut self, _name: &str) {}
+
+    fn send(&mut self, _message: LocalConnectionMessage) -> bool {
+        false
+    }
+
+    fn receive(&mut self) -> Vec<LocalConnectionMessage> {
+        vec![]
+    }
+}
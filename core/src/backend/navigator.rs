@@ -213,6 +213,79 @@ pub struct ErrorResponse {
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
 
+/// The body of a response, which may still be in the process of being received.
+pub trait ResponseBody {
+    /// Wait for the next chunk of the body.
+    ///
+    /// Returns `None` once the whole body has been received.
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>>;
+}
+
+/// A body that has already been received completely.
+struct CompleteBody(Option<Vec<u8>>);
+
+impl ResponseBody for CompleteBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        let chunk = self.0.take();
+        Box::pin(async move { Ok(chunk) })
+    }
+}
+
+/// A response to a successful fetch request, whose body is delivered as it's received.
+pub struct StreamingResponse {
+    /// The final URL obtained after any redirects.
+    pub url: String,
+
+    /// The status code of the response.
+    pub status: u16,
+
+    /// The field to indicate if the request has been redirected.
+    pub redirected: bool,
+
+    /// The length of the body, if it's known in advance.
+    pub expected_length: Option<u64>,
+
+    /// The contents of the response body.
+    pub body: Box<dyn ResponseBody>,
+}
+
+impl StreamingResponse {
+    /// Wrap a response whose body has already been received.
+    pub fn from_complete(response: SuccessResponse) -> Self {
+        Self {
+            url: response.url,
+            status: response.status,
+            redirected: response.redirected,
+            expected_length: Some(response.body.len() as u64),
+            body: Box::new(CompleteBody(Some(response.body))),
+        }
+    }
+
+    /// Wait for the rest of the body to be received.
+    pub async fn into_complete(mut self) -> Result<SuccessResponse, ErrorResponse> {
+        let mut body = Vec::new();
+        loop {
+            match self.body.next_chunk().await {
+                Ok(Some(chunk)) => body.extend(chunk),
+                Ok(None) => break,
+                Err(error) => {
+                    return Err(ErrorResponse {
+                        url: self.url,
+                        error,
+                    })
+                }
+            }
+        }
+
+        Ok(SuccessResponse {
+            url: self.url,
+            body,
+            status: self.status,
+            redirected: self.redirected,
+        })
+    }
+}
+
 /// A backend interacting with a browser environment.
 pub trait NavigatorBackend {
     /// Cause a browser navigation to a given URL.
@@ -247,6 +320,16 @@ pub trait NavigatorBackend {
     /// Fetch data and return it some time in the future.
     fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse>;
 
+    /// Fetch data, returning the response as soon as it starts being received.
+    ///
+    /// The body is delivered in chunks as they arrive, which lets loaders report
+    /// their progress. The default implementation waits for `fetch` to finish, and
+    /// delivers the whole body as a single chunk.
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, ErrorResponse> {
+        let fetch = self.fetch(request);
+        Box::pin(async move { fetch.await.map(StreamingResponse::from_complete) })
+    }
+
     /// Take a URL string and resolve it to the actual URL from which a file
     /// can be fetched. This includes handling of relative links and pre-processing.
     ///
@@ -288,6 +371,14 @@ pub trait NavigatorBackend {
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     );
+
+    /// Whether the user or the embedder already approved sockets to this host
+    /// and port, for example by configuring a proxy for them.
+    ///
+    /// Approved sockets are opened without checking socket policy files.
+    fn is_socket_approved(&self, _host: &str, _port: u16) -> bool {
+        false
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -1,4 +1,19 @@
 use std::collections::HashMap;
+use url::Url;
+
+/// The amount of bytes a domain may store before the user is asked for more space.
+pub const DEFAULT_QUOTA: usize = 100 * 1024;
+
+/// The quotas offered to the user, as in the Local Storage settings panel of Flash Player.
+/// `usize::MAX` represents an unlimited quota.
+pub const QUOTA_STEPS: [usize; 6] = [
+    0,
+    10 * 1024,
+    100 * 1024,
+    1024 * 1024,
+    10 * 1024 * 1024,
+    usize::MAX,
+];
 
 pub trait StorageBackend {
     fn get(&self, name: &str) -> Option<Vec<u8>>;
@@ -10,6 +25,59 @@ pub trait StorageBackend {
     }
 
     fn remove_key(&mut self, name: &str);
+
+    /// Lists the names of all stored shared objects that start with `prefix`.
+    fn keys(&self, prefix: &str) -> Vec<String>;
+
+    /// Returns the total size of all stored shared objects that start with `prefix`.
+    fn usage(&self, prefix: &str) -> usize {
+        self.keys(prefix)
+            .iter()
+            .filter_map(|name| self.get_size(name))
+            .sum()
+    }
+
+    /// Returns the amount of bytes that shared objects of `domain` may use.
+    fn quota(&self, domain: &str) -> usize {
+        self.get(&quota_key(domain))
+            .and_then(|quota| String::from_utf8(quota).ok())
+            .and_then(|quota| quota.parse().ok())
+            .unwrap_or_else(|| self.default_quota(domain))
+    }
+
+    /// Returns the quota of a `domain` that has none set.
+    ///
+    /// Shared objects stored before quotas were introduced may exceed `DEFAULT_QUOTA`,
+    /// so they're allowed to keep the space they already use.
+    fn default_quota(&self, domain: &str) -> usize {
+        DEFAULT_QUOTA.max(quota_for(self.usage(&format!("{domain}/"))))
+    }
+
+    fn set_quota(&mut self, domain: &str, quota: usize) -> bool {
+        self.put(&quota_key(domain), quota.to_string().as_bytes())
+    }
+}
+
+/// Quotas are stored next to the shared objects, under a name that can't collide with them.
+fn quota_key(domain: &str) -> String {
+    format!("#quota/{domain}")
+}
+
+/// Returns the domain that shared objects of a movie loaded from `url` are stored under.
+pub fn storage_domain(url: &Url) -> &str {
+    if url.scheme() == "file" {
+        "localhost"
+    } else {
+        url.host_str().unwrap_or_default()
+    }
+}
+
+/// Returns the smallest quota offered to the user that fits `size` bytes.
+pub fn quota_for(size: usize) -> usize {
+    QUOTA_STEPS
+        .into_iter()
+        .find(|quota| *quota >= size)
+        .unwrap_or(usize::MAX)
 }
 
 #[derive(Default)]
@@ -36,4 +104,52 @@ impl StorageBackend for MemoryStorageBackend {
     fn remove_key(&mut self, name: &str) {
         self.map.remove(name);
     }
+
+    fn keys(&self, prefix: &str) -> Vec<String> {
+        self.map
+            .keys()
+            .filter(|name| name.starts_with(prefix) && !name.starts_with('#'))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_and_quota() {
+        let mut storage = MemoryStorageBackend::new();
+        storage.put("example.com/game.swf/save", &[0; 100]);
+        storage.put("example.com/other.swf/save", &[0; 50]);
+        storage.put("example.org/game.swf/save", &[0; 10]);
+
+        assert_eq!(storage.usage("example.com/"), 150);
+        assert_eq!(storage.usage("example.com/game.swf/"), 100);
+        assert_eq!(storage.quota("example.com"), DEFAULT_QUOTA);
+
+        assert!(storage.set_quota("example.com", 0));
+        assert_eq!(storage.quota("example.com"), 0);
+        assert_eq!(storage.quota("example.org"), DEFAULT_QUOTA);
+        assert_eq!(storage.keys("").len(), 3);
+    }
+
+    #[test]
+    fn existing_usage_is_grandfathered() {
+        let mut storage = MemoryStorageBackend::new();
+        storage.put("example.com/game.swf/save", &[0; 200 * 1024]);
+        assert_eq!(storage.quota("example.com"), 1024 * 1024);
+
+        assert!(storage.set_quota("example.com", 10 * 1024));
+        assert_eq!(storage.quota("example.com"), 10 * 1024);
+    }
+
+    #[test]
+    fn quota_steps() {
+        assert_eq!(quota_for(0), 0);
+        assert_eq!(quota_for(1), 10 * 1024);
+        assert_eq!(quota_for(100 * 1024 + 1), 1024 * 1024);
+        assert_eq!(quota_for(usize::MAX - 1), usize::MAX);
+    }
 }
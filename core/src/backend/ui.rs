@@ -54,6 +54,9 @@ impl_downcast!(FileDialogResult);
 /// Future representing a file selection in process
 pub type DialogResultFuture = OwnedFuture<Box<dyn FileDialogResult>, DialogLoaderError>;
 
+/// Future representing the user's answer to a storage quota prompt
+pub type StorageQuotaFuture = OwnedFuture<bool, DialogLoaderError>;
+
 pub trait UiBackend: Downcast {
     fn mouse_visible(&self) -> bool;
 
@@ -116,6 +119,19 @@ pub trait UiBackend: Downcast {
 
     /// Mark that any previously open dialog has been closed
     fn close_file_dialog(&mut self);
+
+    /// Asks the user whether `domain` may store `requested` bytes of shared objects,
+    /// which is more than its current quota.
+    /// Returns None if the prompt cannot be displayed, in which case the quota is not enforced.
+    fn display_storage_quota_prompt(
+        &mut self,
+        domain: &str,
+        requested: usize,
+    ) -> Option<StorageQuotaFuture>;
+
+    /// Displays the local storage settings of `domain`, where the user can change its quota
+    /// or delete its shared objects.
+    fn display_storage_settings(&mut self, domain: &str);
 }
 impl_downcast!(UiBackend);
 
@@ -308,6 +324,16 @@ impl UiBackend for NullUiBackend {
     ) -> Option<DialogResultFuture> {
         None
     }
+
+    fn display_storage_quota_prompt(
+        &mut self,
+        _domain: &str,
+        _requested: usize,
+    ) -> Option<StorageQuotaFuture> {
+        None
+    }
+
+    fn display_storage_settings(&mut self, _domain: &str) {}
 }
 
 impl Default for NullUiBackend {
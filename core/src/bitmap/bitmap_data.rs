@@ -207,6 +207,10 @@ pub struct BitmapData<'gc> {
     // so we need a separate 'disposed' flag.
     disposed: bool,
 
+    /// Whether this bitmap was loaded from another domain, without a policy
+    /// file granting access to its pixels.
+    cross_domain: bool,
+
     /// The bitmap handle for this data.
     ///
     /// This is lazily initialized; a value of `None` indicates that
@@ -301,6 +305,7 @@ mod wrapper {
                     height: 0,
                     transparency: false,
                     disposed: true,
+                    cross_domain: false,
                     bitmap_handle: None,
                     avm2_object: None,
                     display_objects: vec![],
@@ -322,6 +327,7 @@ mod wrapper {
                 height: data.height,
                 transparency: data.transparency,
                 disposed: data.disposed,
+                cross_domain: data.cross_domain,
                 bitmap_handle: None,
                 avm2_object: None,
                 display_objects: vec![],
@@ -423,6 +429,10 @@ mod wrapper {
             self.0.read().disposed
         }
 
+        pub fn is_cross_domain(&self) -> bool {
+            self.0.read().cross_domain
+        }
+
         pub fn transparency(&self) -> bool {
             self.0.read().transparency
         }
@@ -549,6 +559,7 @@ impl<'gc> BitmapData<'gc> {
             height,
             transparency,
             disposed: false,
+            cross_domain: false,
             bitmap_handle: None,
             avm2_object: None,
             display_objects: vec![],
@@ -570,6 +581,7 @@ impl<'gc> BitmapData<'gc> {
             bitmap_handle: None,
             avm2_object: None,
             disposed: false,
+            cross_domain: false,
             dirty_state: DirtyState::Clean,
             display_objects: vec![],
         }
@@ -579,6 +591,10 @@ impl<'gc> BitmapData<'gc> {
         self.disposed
     }
 
+    pub fn set_cross_domain(&mut self, cross_domain: bool) {
+        self.cross_domain = cross_domain;
+    }
+
     pub fn dispose(&mut self) {
         self.width = 0;
         self.height = 0;
@@ -1179,7 +1179,7 @@ pub fn apply_filter<'gc>(
             return;
         }
 
-        // Until we support these filters, treat this like a copy
+        // The renderer can't apply this filter, so treat this like a copy
         copy_on_cpu(
             context.gc_context,
             source,
@@ -6,6 +6,7 @@ use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
@@ -18,9 +19,12 @@ use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
 use crate::net_connection::NetConnections;
 use crate::player::Player;
+use crate::policy_file::PolicyFiles;
 use crate::prelude::*;
+use crate::profiler::{ProfileCategory, Profiler};
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::AvmStringInterner;
@@ -85,6 +89,12 @@ pub struct UpdateContext<'a, 'gc> {
     /// A collection of stubs encountered during this movie.
     pub stub_tracker: &'a mut StubCollection,
 
+    /// The policy files that grant movies access to other domains.
+    pub policy_files: &'a mut PolicyFiles,
+
+    /// The data of shared objects that are waiting for the user to grant more storage space, by name.
+    pub pending_flushes: &'a mut HashMap<String, Vec<u8>>,
+
     /// The library containing character definitions for this SWF.
     /// Used to instantiate a `DisplayObject` of a given ID.
     pub library: &'a mut Library<'gc>,
@@ -120,6 +130,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
+    /// The local connection backend, used to reach LocalConnections of other players.
+    pub local_connection_backend: &'a mut dyn LocalConnectionBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -225,6 +238,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// If we are not doing frame processing, then this is `FramePhase::Enter`.
     pub frame_phase: &'a mut FramePhase,
 
+    /// The ActionScript profiler, if profiling is enabled.
+    pub profiler: &'a mut Option<Profiler>,
+
     /// Manager of in-progress media streams.
     pub stream_manager: &'a mut StreamManager<'gc>,
 
@@ -233,6 +249,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// List of active NetConnection instances.
     pub net_connections: &'a mut NetConnections<'gc>,
 
+    /// List of LocalConnection objects, and the messages sent between them.
+    pub local_connections: &'a mut LocalConnections<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     pub dynamic_root: gc_arena::DynamicRootSet<'gc>,
 }
@@ -357,6 +376,8 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             gc_context: self.gc_context,
             interner: self.interner,
             stub_tracker: self.stub_tracker,
+            policy_files: self.policy_files,
+            pending_flushes: self.pending_flushes,
             library: self.library,
             player_version: self.player_version,
             needs_render: self.needs_render,
@@ -369,6 +390,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             ui: self.ui,
             video: self.video,
             storage: self.storage,
+            local_connection_backend: self.local_connection_backend,
             rng: self.rng,
             stage: self.stage,
             mouse_over_object: self.mouse_over_object,
@@ -399,9 +421,11 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             forced_frame_rate: self.forced_frame_rate,
             actions_since_timeout_check: self.actions_since_timeout_check,
             frame_phase: self.frame_phase,
+            profiler: self.profiler,
             stream_manager: self.stream_manager,
             sockets: self.sockets,
             net_connections: self.net_connections,
+            local_connections: self.local_connections,
             dynamic_root: self.dynamic_root,
         }
     }
@@ -417,6 +441,32 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
         }
     }
 
+    /// Record entering a frame in the profiler, if profiling.
+    ///
+    /// `name` is only called while profiling.
+    #[inline]
+    pub fn profile_enter(&mut self, category: ProfileCategory, name: impl FnOnce() -> String) {
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.enter(category, name());
+        }
+    }
+
+    /// Change the current AVM2 frame phase, recording it in the profiler.
+    pub fn set_frame_phase(&mut self, phase: FramePhase) {
+        *self.frame_phase = phase;
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.set_frame_phase(phase);
+        }
+    }
+
+    /// Record exiting the innermost frame in the profiler, if profiling.
+    #[inline]
+    pub fn profile_exit(&mut self) {
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.exit();
+        }
+    }
+
     pub fn is_action_script_3(&self) -> bool {
         self.swf.is_action_script_3()
     }
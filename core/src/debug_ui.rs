@@ -1,12 +1,17 @@
 mod avm1;
+mod avm1_debugger;
 mod avm2;
+mod avm2_debugger;
+mod debugger;
 mod display_object;
 mod handle;
 mod movie;
 
 use crate::context::{RenderContext, UpdateContext};
 use crate::debug_ui::avm1::Avm1ObjectWindow;
+use crate::debug_ui::avm1_debugger::Avm1DebuggerWindow;
 use crate::debug_ui::avm2::Avm2ObjectWindow;
+use crate::debug_ui::avm2_debugger::Avm2DebuggerWindow;
 use crate::debug_ui::display_object::{DisplayObjectSearchWindow, DisplayObjectWindow};
 use crate::debug_ui::handle::{AVM1ObjectHandle, AVM2ObjectHandle, DisplayObjectHandle};
 use crate::debug_ui::movie::{MovieListWindow, MovieWindow};
@@ -31,6 +36,8 @@ pub struct DebugUi {
     items_to_save: Vec<ItemToSave>,
     movie_list: Option<MovieListWindow>,
     display_object_search: Option<DisplayObjectSearchWindow>,
+    avm1_debugger: Option<Avm1DebuggerWindow>,
+    avm2_debugger: Option<Avm2DebuggerWindow>,
 }
 
 #[derive(Debug)]
@@ -44,6 +51,8 @@ pub enum Message {
     ShowKnownMovies,
     SaveFile(ItemToSave),
     SearchForDisplayObject,
+    ShowAvm1Debugger,
+    ShowAvm2Debugger,
 }
 
 impl DebugUi {
@@ -85,6 +94,18 @@ impl DebugUi {
             }
         }
 
+        if let Some(mut debugger) = self.avm1_debugger.take() {
+            if debugger.show(egui_ctx, context) {
+                self.avm1_debugger = Some(debugger);
+            }
+        }
+
+        if let Some(mut debugger) = self.avm2_debugger.take() {
+            if debugger.show(egui_ctx, context) {
+                self.avm2_debugger = Some(debugger);
+            }
+        }
+
         for message in messages {
             match message {
                 Message::TrackDisplayObject(object) => {
@@ -114,12 +135,26 @@ impl DebugUi {
                 Message::SearchForDisplayObject => {
                     self.display_object_search = Some(Default::default());
                 }
+                Message::ShowAvm1Debugger => {
+                    self.avm1_debugger.get_or_insert_with(Default::default);
+                }
+                Message::ShowAvm2Debugger => {
+                    self.avm2_debugger.get_or_insert_with(Default::default);
+                }
             }
         }
     }
 
     pub fn should_suspend_player(&self) -> bool {
         self.display_object_search.is_some()
+            || self
+                .avm1_debugger
+                .as_ref()
+                .is_some_and(|debugger| debugger.is_paused())
+            || self
+                .avm2_debugger
+                .as_ref()
+                .is_some_and(|debugger| debugger.is_paused())
     }
 
     pub fn items_to_save(&mut self) -> Vec<ItemToSave> {
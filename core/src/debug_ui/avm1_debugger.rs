@@ -0,0 +1,185 @@
+use crate::avm1::debugger::{Breakpoint, StopPoint, Trace};
+use crate::context::UpdateContext;
+use crate::debug_ui::debugger::{
+    show_position, show_replay_note, show_step_controls, show_value_list,
+};
+use egui::{CollapsingHeader, Grid, TextEdit, Ui, Window};
+
+#[derive(Debug, Default)]
+pub struct Avm1DebuggerWindow {
+    /// The actions recorded after the last breakpoint was hit.
+    ///
+    /// The player is paused while this exists, so that the trace is shown next to
+    /// the state the script left behind.
+    trace: Option<Trace>,
+    movie: String,
+    frame: String,
+    offset: String,
+}
+
+impl Avm1DebuggerWindow {
+    pub fn is_paused(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    pub fn show(&mut self, egui_ctx: &egui::Context, context: &mut UpdateContext) -> bool {
+        let mut keep_open = true;
+        let debugger = &mut context.avm1.debugger;
+
+        if self.trace.is_none() {
+            self.trace = debugger.take_trace();
+        }
+
+        Window::new("AVM1 Script Tracer")
+            .open(&mut keep_open)
+            .scroll2([true, true])
+            .show(egui_ctx, |ui| {
+                show_replay_note(ui);
+                show_step_controls(ui, &mut self.trace, debugger.is_break_requested(), || {
+                    debugger.request_break()
+                });
+                ui.separator();
+
+                if let Some(trace) = &self.trace {
+                    let stop = trace.current();
+                    ui.horizontal(|ui| {
+                        ui.strong(format!("At {} @ {}", stop.movie, stop.offset));
+                        if ui.button("Add Breakpoint Here").clicked() {
+                            debugger.add_breakpoint(Breakpoint::Offset {
+                                movie: stop.movie.clone(),
+                                offset: stop.offset,
+                            });
+                        }
+                    });
+                    ui.monospace(&stop.action);
+                    show_position(ui, trace, "Action", "actions");
+                    show_stop_point(ui, stop);
+                    ui.separator();
+                }
+
+                CollapsingHeader::new("Breakpoints")
+                    .id_source("avm1_debugger_breakpoints")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        Grid::new("avm1_debugger_breakpoint_list")
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                for breakpoint in debugger.breakpoints().to_vec() {
+                                    ui.label(breakpoint.to_string());
+                                    if ui.button("Remove").clicked() {
+                                        debugger.remove_breakpoint(&breakpoint);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+
+                        if let Some(breakpoint) = self.show_new_breakpoint(ui) {
+                            debugger.add_breakpoint(breakpoint);
+                        }
+                    });
+            });
+
+        if !keep_open {
+            // Nothing would be left to show the traces that are recorded.
+            debugger.clear();
+            self.trace = None;
+        }
+        keep_open
+    }
+
+    fn show_new_breakpoint(&mut self, ui: &mut Ui) -> Option<Breakpoint> {
+        let mut breakpoint = None;
+
+        ui.add(TextEdit::singleline(&mut self.movie).hint_text("Movie URL (empty for any movie)"));
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.frame)
+                    .hint_text("Frame")
+                    .desired_width(60.0),
+            );
+            let frame = self.frame.trim().parse::<u16>();
+            ui.add_enabled_ui(frame.is_ok(), |ui| {
+                if ui.button("Add").clicked() {
+                    if let Ok(frame) = frame {
+                        breakpoint = Some(Breakpoint::Frame {
+                            movie: self.movie.trim().to_string(),
+                            frame,
+                        });
+                    }
+                }
+            });
+
+            ui.add(
+                TextEdit::singleline(&mut self.offset)
+                    .hint_text("Byte offset")
+                    .desired_width(80.0),
+            );
+            let offset = self.offset.trim().parse::<usize>();
+            ui.add_enabled_ui(offset.is_ok(), |ui| {
+                if ui.button("Add").clicked() {
+                    if let Ok(offset) = offset {
+                        breakpoint = Some(Breakpoint::Offset {
+                            movie: self.movie.trim().to_string(),
+                            offset,
+                        });
+                    }
+                }
+            });
+        });
+
+        breakpoint
+    }
+}
+
+fn show_stop_point(ui: &mut Ui, stop: &StopPoint) {
+    Grid::new("avm1_debugger_location")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Activation");
+            ui.label(&stop.activation);
+            ui.end_row();
+
+            ui.label("Clip");
+            ui.label(&stop.clip);
+            ui.end_row();
+
+            if let Some(frame) = stop.frame {
+                ui.label("Frame");
+                ui.label(frame.to_string());
+                ui.end_row();
+            }
+        });
+
+    CollapsingHeader::new("Scope Chain")
+        .id_source("avm1_debugger_scope_chain")
+        .default_open(true)
+        .show(ui, |ui| {
+            for (i, scope) in stop.scope_chain.iter().enumerate() {
+                CollapsingHeader::new(format!("{} {}", scope.class, scope.object))
+                    .id_source(("avm1_debugger_scope", i))
+                    .default_open(i == 0)
+                    .show(ui, |ui| {
+                        let Some(variables) = &scope.variables else {
+                            ui.weak("(Not listed)");
+                            return;
+                        };
+                        if variables.is_empty() {
+                            ui.weak("(Empty)");
+                        }
+                        Grid::new(("avm1_debugger_scope_grid", i))
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                for (name, value) in variables {
+                                    ui.label(name);
+                                    ui.monospace(value);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+        });
+
+    show_value_list(ui, "avm1_debugger", "Registers", &stop.registers);
+    show_value_list(ui, "avm1_debugger", "Constant Pool", &stop.constant_pool);
+    show_value_list(ui, "avm1_debugger", "Stack", &stop.stack);
+}
@@ -0,0 +1,206 @@
+use crate::avm2::debugger::{Breakpoint, StepGranularity, StopPoint, Trace};
+use crate::context::UpdateContext;
+use crate::debug_ui::debugger::{
+    show_position, show_replay_note, show_step_controls, show_value_list,
+};
+use egui::{CollapsingHeader, Grid, TextEdit, Ui, Window};
+
+#[derive(Debug, Default)]
+pub struct Avm2DebuggerWindow {
+    /// The step points recorded after the last breakpoint was hit.
+    ///
+    /// The player is paused while this exists, so that the trace is shown next to
+    /// the state the script left behind.
+    trace: Option<Trace>,
+    method_name: String,
+    method_offset: String,
+    file_name: String,
+    line: String,
+}
+
+impl Avm2DebuggerWindow {
+    pub fn is_paused(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    pub fn show(&mut self, egui_ctx: &egui::Context, context: &mut UpdateContext) -> bool {
+        let mut keep_open = true;
+        let debugger = &mut context.avm2.debugger;
+
+        if self.trace.is_none() {
+            self.trace = debugger.take_trace();
+        }
+
+        Window::new("AVM2 Script Tracer")
+            .open(&mut keep_open)
+            .scroll2([true, true])
+            .show(egui_ctx, |ui| {
+                show_replay_note(ui);
+                ui.horizontal(|ui| {
+                    ui.label("Step by");
+                    let mut granularity = debugger.granularity();
+                    ui.selectable_value(&mut granularity, StepGranularity::Line, "Line");
+                    ui.selectable_value(
+                        &mut granularity,
+                        StepGranularity::Instruction,
+                        "Instruction",
+                    );
+                    debugger.set_granularity(granularity);
+                });
+
+                show_step_controls(ui, &mut self.trace, debugger.is_break_requested(), || {
+                    debugger.request_break()
+                });
+                ui.separator();
+
+                if let Some(trace) = &self.trace {
+                    show_trace(ui, trace, &mut |breakpoint| {
+                        debugger.add_breakpoint(breakpoint)
+                    });
+                    ui.separator();
+                }
+
+                CollapsingHeader::new("Breakpoints")
+                    .id_source("avm2_debugger_breakpoints")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        self.show_breakpoints(ui, debugger.breakpoints().to_vec(), |action| {
+                            match action {
+                                BreakpointAction::Add(breakpoint) => {
+                                    debugger.add_breakpoint(breakpoint)
+                                }
+                                BreakpointAction::Remove(breakpoint) => {
+                                    debugger.remove_breakpoint(&breakpoint)
+                                }
+                            }
+                        });
+                    });
+            });
+
+        if !keep_open {
+            // Nothing would be left to show the traces that are recorded.
+            debugger.clear();
+            self.trace = None;
+        }
+        keep_open
+    }
+
+    fn show_breakpoints(
+        &mut self,
+        ui: &mut Ui,
+        breakpoints: Vec<Breakpoint>,
+        mut on_action: impl FnMut(BreakpointAction),
+    ) {
+        Grid::new("avm2_debugger_breakpoint_list")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for breakpoint in breakpoints {
+                    ui.label(breakpoint.to_string());
+                    if ui.button("Remove").clicked() {
+                        on_action(BreakpointAction::Remove(breakpoint));
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.method_name)
+                    .hint_text("Method, e.g. Main/onEnterFrame()"),
+            );
+            ui.add(
+                TextEdit::singleline(&mut self.method_offset)
+                    .hint_text("Offset")
+                    .desired_width(60.0),
+            );
+            let offset = self.method_offset.trim().parse::<usize>();
+            ui.add_enabled_ui(!self.method_name.is_empty() && offset.is_ok(), |ui| {
+                if ui.button("Add").clicked() {
+                    if let Ok(offset) = offset {
+                        on_action(BreakpointAction::Add(Breakpoint::Method {
+                            name: self.method_name.trim().to_string(),
+                            offset,
+                        }));
+                    }
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.file_name).hint_text("File, e.g. Main.as"));
+            ui.add(
+                TextEdit::singleline(&mut self.line)
+                    .hint_text("Line")
+                    .desired_width(60.0),
+            );
+            let line = self.line.trim().parse::<u32>();
+            ui.add_enabled_ui(!self.file_name.is_empty() && line.is_ok(), |ui| {
+                if ui.button("Add").clicked() {
+                    if let Ok(line) = line {
+                        on_action(BreakpointAction::Add(Breakpoint::Line {
+                            file: self.file_name.trim().to_string(),
+                            line,
+                        }));
+                    }
+                }
+            });
+        });
+    }
+}
+
+enum BreakpointAction {
+    Add(Breakpoint),
+    Remove(Breakpoint),
+}
+
+fn show_trace(ui: &mut Ui, trace: &Trace, add_breakpoint: &mut dyn FnMut(Breakpoint)) {
+    let stop = trace.current();
+
+    ui.horizontal(|ui| {
+        ui.strong(format!("At {} @ {}", stop.method, stop.offset));
+        if ui.button("Add Breakpoint Here").clicked() {
+            add_breakpoint(Breakpoint::Method {
+                name: stop.method.clone(),
+                offset: stop.offset,
+            });
+        }
+    });
+    if let (Some(file), Some(line)) = (&stop.file, stop.line) {
+        ui.label(format!("{file}:{line}"));
+    }
+    if let Some(instruction) = &stop.instruction {
+        ui.monospace(instruction);
+    }
+    show_position(ui, trace, "Step", "steps");
+
+    show_stop_point(ui, stop);
+}
+
+fn show_stop_point(ui: &mut Ui, stop: &StopPoint) {
+    CollapsingHeader::new("Call Stack")
+        .id_source("avm2_debugger_call_stack")
+        .default_open(true)
+        .show(ui, |ui| {
+            for frame in &stop.call_stack {
+                ui.label(frame);
+            }
+        });
+
+    CollapsingHeader::new("Locals")
+        .id_source("avm2_debugger_locals")
+        .default_open(true)
+        .show(ui, |ui| {
+            Grid::new("avm2_debugger_locals_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (name, value) in &stop.locals {
+                        ui.label(name);
+                        ui.monospace(value);
+                        ui.end_row();
+                    }
+                });
+        });
+
+    show_value_list(ui, "avm2_debugger", "Scope Stack", &stop.scope_stack);
+    show_value_list(ui, "avm2_debugger", "Operand Stack", &stop.operand_stack);
+}
@@ -0,0 +1,87 @@
+//! Widgets shared by the AVM1 and AVM2 debugger windows.
+
+use crate::debugger::{Stop, Trace};
+use egui::{CollapsingHeader, Ui};
+
+/// Explains that this records traces, rather than pausing scripts.
+pub fn show_replay_note(ui: &mut Ui) {
+    ui.weak(
+        "This records traces, it doesn't pause scripts. When a breakpoint is hit, \
+        the script runs to completion while each of its steps is recorded, and the \
+        buttons below move through that trace: everything in it has already happened. \
+        The player is paused while a trace is shown.",
+    );
+}
+
+/// Shows the buttons for stepping through `trace`, or for starting one if there is none.
+///
+/// The trace is dropped once stepping runs past its end, resuming the player.
+pub fn show_step_controls<S: Stop>(
+    ui: &mut Ui,
+    trace: &mut Option<Trace<S>>,
+    is_break_requested: bool,
+    request_break: impl FnOnce(),
+) {
+    ui.horizontal(|ui| {
+        if let Some(current) = trace {
+            let mut resumed = false;
+            if ui.button("Next Breakpoint").clicked() {
+                resumed = !current.resume();
+            }
+            if ui.button("Step In").clicked() {
+                resumed = !current.step_in();
+            }
+            if ui.button("Step Over").clicked() {
+                resumed = !current.step_over();
+            }
+            if ui.button("Step Out").clicked() {
+                resumed = !current.step_out();
+            }
+            ui.add_enabled_ui(current.position() > 0, |ui| {
+                if ui.button("Step Back").clicked() {
+                    current.step_back();
+                }
+            });
+            if resumed {
+                *trace = None;
+            }
+        } else if is_break_requested {
+            ui.weak("Waiting for a script to run...");
+        } else if ui.button("Trace Next Script").clicked() {
+            request_break();
+        }
+    });
+}
+
+/// Shows how far through the trace the current step point is, such as "Step 3 of 10".
+pub fn show_position<S: Stop>(ui: &mut Ui, trace: &Trace<S>, unit: &str, units: &str) {
+    ui.weak(format!(
+        "{unit} {} of {}{}",
+        trace.position() + 1,
+        trace.len(),
+        if trace.is_truncated() {
+            format!(" (too many {units} to record the rest of this script)")
+        } else {
+            String::new()
+        }
+    ));
+}
+
+/// Shows a collapsible list of values, numbered from 0.
+///
+/// `id_prefix` keeps the state of the AVM1 and AVM2 windows apart.
+pub fn show_value_list(ui: &mut Ui, id_prefix: &str, name: &str, values: &[String]) {
+    CollapsingHeader::new(name)
+        .id_source(format!("{id_prefix}_{name}"))
+        .show(ui, |ui| {
+            if values.is_empty() {
+                ui.weak("(Empty)");
+            }
+            for (i, value) in values.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{i}"));
+                    ui.monospace(value);
+                });
+            }
+        });
+}
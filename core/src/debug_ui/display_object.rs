@@ -755,6 +755,7 @@ fn display_object_type(object: DisplayObject) -> &'static str {
         DisplayObject::Text(_) => "Text",
         DisplayObject::Video(_) => "Video",
         DisplayObject::LoaderDisplay(_) => "LoaderDisplay",
+        DisplayObject::TextLine(_) => "TextLine",
     }
 }
 
@@ -0,0 +1,246 @@
+//! Tracing the execution of scripts, shared by the AVM1 and AVM2 debuggers.
+//!
+//! This is a trace recorder, not a live debugger: scripts are never paused. When a
+//! breakpoint is hit, the script keeps running to completion while a snapshot of the
+//! interpreter is recorded at every step point. Stepping then moves through that
+//! recorded trace after the fact, so it can't change what the script does, and the
+//! values it shows are copies taken when each step point was reached.
+
+/// The maximum number of step points recorded in a single trace.
+pub const MAX_TRACE_LENGTH: usize = 10000;
+
+/// A snapshot of an interpreter at a point where execution stopped.
+pub trait Stop {
+    /// How deeply nested the executing function is, used for stepping over and out of it.
+    fn depth(&self) -> usize;
+
+    /// Whether a breakpoint was hit here, as opposed to stepping onto it.
+    fn hit_breakpoint(&self) -> bool;
+}
+
+/// The step points recorded after a breakpoint was hit.
+///
+/// Stepping only moves through these recorded step points.
+#[derive(Clone, Debug)]
+pub struct Trace<S> {
+    stops: Vec<S>,
+    current: usize,
+
+    /// Whether recording stopped early because too many step points were hit.
+    truncated: bool,
+}
+
+impl<S: Stop> Trace<S> {
+    pub fn current(&self) -> &S {
+        &self.stops[self.current]
+    }
+
+    pub fn position(&self) -> usize {
+        self.current
+    }
+
+    pub fn len(&self) -> usize {
+        self.stops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Moves to the previous step point, returning `false` if there is none.
+    pub fn step_back(&mut self) -> bool {
+        if self.current > 0 {
+            self.current -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves to the next step point, entering any called functions.
+    ///
+    /// Returns `false` if execution ran to completion instead.
+    pub fn step_in(&mut self) -> bool {
+        self.step_until(|_| true)
+    }
+
+    /// Moves to the next step point in this function or any of its callers.
+    pub fn step_over(&mut self) -> bool {
+        let depth = self.current().depth();
+        self.step_until(|stop| stop.depth() <= depth)
+    }
+
+    /// Moves to the next step point in a caller of this function.
+    pub fn step_out(&mut self) -> bool {
+        let depth = self.current().depth();
+        self.step_until(|stop| stop.depth() < depth)
+    }
+
+    /// Moves to the next step point that hit a breakpoint.
+    pub fn resume(&mut self) -> bool {
+        self.step_until(|stop| stop.hit_breakpoint())
+    }
+
+    fn step_until(&mut self, predicate: impl Fn(&S) -> bool) -> bool {
+        let next = self
+            .stops
+            .iter()
+            .enumerate()
+            .skip(self.current + 1)
+            .find(|(_, stop)| predicate(stop));
+        match next {
+            Some((i, _)) => {
+                self.current = i;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Collects step points into a `Trace`, until the debug UI takes it.
+#[derive(Debug)]
+pub struct TraceRecorder<S> {
+    recording: Option<Trace<S>>,
+}
+
+impl<S: Stop> TraceRecorder<S> {
+    /// Whether a breakpoint was hit, and step points are being recorded.
+    #[inline(always)]
+    pub fn is_recording(&self) -> bool {
+        self.recording.as_ref().is_some_and(|s| !s.truncated)
+    }
+
+    /// Whether too many step points were recorded, so that nothing more is recorded
+    /// until the trace is taken.
+    #[inline(always)]
+    pub fn is_truncated(&self) -> bool {
+        self.recording.as_ref().is_some_and(|s| s.truncated)
+    }
+
+    /// Records a step point, starting a new trace if needed.
+    pub fn record(&mut self, stop: S) {
+        let trace = self.recording.get_or_insert_with(|| Trace {
+            stops: vec![],
+            current: 0,
+            truncated: false,
+        });
+
+        if trace.stops.len() >= MAX_TRACE_LENGTH {
+            trace.truncated = true;
+        } else {
+            trace.stops.push(stop);
+        }
+    }
+
+    /// Takes the step points recorded since the last breakpoint was hit.
+    pub fn take_trace(&mut self) -> Option<Trace<S>> {
+        self.recording.take().filter(|trace| !trace.is_empty())
+    }
+
+    /// Discards any recorded step points.
+    pub fn clear(&mut self) {
+        self.recording = None;
+    }
+}
+
+impl<S> Default for TraceRecorder<S> {
+    fn default() -> Self {
+        Self { recording: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestStop {
+        depth: usize,
+        hit_breakpoint: bool,
+    }
+
+    impl Stop for TestStop {
+        fn depth(&self) -> usize {
+            self.depth
+        }
+
+        fn hit_breakpoint(&self) -> bool {
+            self.hit_breakpoint
+        }
+    }
+
+    /// Records a trace with the given depths, where `true` marks a breakpoint.
+    fn trace(stops: &[(usize, bool)]) -> Trace<TestStop> {
+        let mut recorder = TraceRecorder::default();
+        for &(depth, hit_breakpoint) in stops {
+            recorder.record(TestStop {
+                depth,
+                hit_breakpoint,
+            });
+        }
+        recorder.take_trace().unwrap()
+    }
+
+    #[test]
+    fn step_in_visits_every_stop() {
+        let mut trace = trace(&[(1, true), (2, false), (1, false)]);
+        assert!(trace.step_in());
+        assert_eq!(trace.current().depth, 2);
+        assert!(trace.step_in());
+        assert_eq!(trace.position(), 2);
+        assert!(!trace.step_in());
+        assert_eq!(trace.position(), 2);
+    }
+
+    #[test]
+    fn step_over_and_out() {
+        let mut trace = trace(&[(1, true), (2, false), (3, false), (2, false), (1, false)]);
+        assert!(trace.step_over());
+        assert_eq!(trace.position(), 4);
+
+        assert!(trace.step_back());
+        assert!(trace.step_back());
+        assert_eq!(trace.current().depth, 3);
+        assert!(trace.step_out());
+        assert_eq!(trace.position(), 3);
+        assert!(trace.step_out());
+        assert_eq!(trace.position(), 4);
+        assert!(!trace.step_out());
+    }
+
+    #[test]
+    fn resume_stops_at_breakpoints() {
+        let mut trace = trace(&[(1, true), (1, false), (2, true), (1, false)]);
+        assert!(trace.resume());
+        assert_eq!(trace.position(), 2);
+        assert!(!trace.resume());
+
+        while trace.step_back() {}
+        assert_eq!(trace.position(), 0);
+        assert!(!trace.step_back());
+    }
+
+    #[test]
+    fn recording_stops_when_truncated() {
+        let mut recorder = TraceRecorder::default();
+        for _ in 0..=MAX_TRACE_LENGTH {
+            recorder.record(TestStop {
+                depth: 1,
+                hit_breakpoint: false,
+            });
+        }
+        assert!(!recorder.is_recording());
+        assert!(recorder.is_truncated());
+
+        let trace = recorder.take_trace().unwrap();
+        assert!(trace.is_truncated());
+        assert_eq!(trace.len(), MAX_TRACE_LENGTH);
+        assert!(!recorder.is_truncated());
+        assert!(recorder.take_trace().is_none());
+    }
+}
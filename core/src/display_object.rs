@@ -33,6 +33,7 @@ mod morph_shape;
 mod movie_clip;
 mod stage;
 mod text;
+mod text_line;
 mod video;
 
 use crate::avm1::Activation;
@@ -55,11 +56,15 @@ use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
+use ruffle_render::matrix3d::{Components3D, Matrix3D, PerspectiveProjection};
+use ruffle_render::scale9::Scale9Mapping;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
 pub use text::Text;
+pub use text_line::TextLine;
 pub use video::Video;
 
 use self::loader_display::LoaderDisplayWeak;
+use self::text_line::TextLineWeak;
 
 /// If a `DisplayObject` is marked `cacheAsBitmap` (via tag or AS),
 /// this struct keeps the information required to uphold that cache.
@@ -260,6 +265,11 @@ pub struct DisplayObjectBase<'gc> {
     /// None means not cached, Some means cached.
     #[collect(require_static)]
     cache: Option<BitmapCache>,
+
+    /// The bitmap this object is rendered into before it's projected, if its 3D transform
+    /// foreshortens it.
+    #[collect(require_static)]
+    projection_cache: Option<BitmapCache>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -288,6 +298,7 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             next_scroll_rect: Default::default(),
             scaling_grid: Default::default(),
             cache: None,
+            projection_cache: None,
         }
     }
 }
@@ -330,6 +341,54 @@ impl<'gc> DisplayObjectBase<'gc> {
     pub fn set_matrix(&mut self, matrix: Matrix) {
         self.transform.matrix = matrix;
         self.set_scale_rotation_cached(false);
+        self.sync_matrix3d();
+    }
+
+    /// The 3D transform of this object, if it has been given one.
+    pub fn matrix3d(&self) -> Option<&Matrix3D> {
+        self.transform.matrix3d.as_ref()
+    }
+
+    /// Sets or clears the 3D transform of this object.
+    /// The 2D matrix is kept in sync with the x/y part of the 3D transform.
+    pub fn set_matrix3d(&mut self, matrix3d: Option<Matrix3D>) {
+        if let Some(matrix3d) = &matrix3d {
+            self.transform.matrix = matrix3d.to_2d();
+            self.set_scale_rotation_cached(false);
+        }
+        self.transform.matrix3d = matrix3d;
+    }
+
+    /// Copies the 2D matrix back into the 3D transform after a 2D property was changed.
+    fn sync_matrix3d(&mut self) {
+        if let Some(matrix3d) = &mut self.transform.matrix3d {
+            matrix3d.set_2d(&self.transform.matrix);
+        }
+    }
+
+    /// The 3D components of this object's transform.
+    /// Objects without a 3D transform are treated as lying flat on the z = 0 plane.
+    fn components3d(&self) -> Components3D {
+        self.transform
+            .matrix3d
+            .unwrap_or_else(|| Matrix3D::from_2d(&self.transform.matrix))
+            .decompose()
+    }
+
+    fn set_components3d(&mut self, components: &Components3D) -> bool {
+        let matrix3d = Matrix3D::compose(components);
+        let changed = self.transform.matrix3d != Some(matrix3d);
+        self.set_transformed_by_script(true);
+        self.set_matrix3d(Some(matrix3d));
+        changed
+    }
+
+    pub fn perspective_projection(&self) -> Option<&PerspectiveProjection> {
+        self.transform.perspective_projection.as_ref()
+    }
+
+    pub fn set_perspective_projection(&mut self, projection: Option<PerspectiveProjection>) {
+        self.transform.perspective_projection = projection;
     }
 
     pub fn color_transform(&self) -> &ColorTransform {
@@ -352,6 +411,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let changed = self.transform.matrix.tx != x;
         self.set_transformed_by_script(true);
         self.transform.matrix.tx = x;
+        self.sync_matrix3d();
         changed
     }
 
@@ -363,6 +423,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let changed = self.transform.matrix.ty != y;
         self.set_transformed_by_script(true);
         self.transform.matrix.ty = y;
+        self.sync_matrix3d();
         changed
     }
 
@@ -435,6 +496,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.b = (self.scale_x.unit() * sin_x) as f32;
         matrix.c = (self.scale_y.unit() * -sin_y) as f32;
         matrix.d = (self.scale_y.unit() * cos_y) as f32;
+        self.sync_matrix3d();
 
         changed
     }
@@ -462,6 +524,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let matrix = &mut self.transform.matrix;
         matrix.a = (cos * value.unit()) as f32;
         matrix.b = (sin * value.unit()) as f32;
+        self.sync_matrix3d();
 
         changed
     }
@@ -489,6 +552,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let matrix = &mut self.transform.matrix;
         matrix.c = (-sin * value.unit()) as f32;
         matrix.d = (cos * value.unit()) as f32;
+        self.sync_matrix3d();
 
         changed
     }
@@ -686,6 +750,14 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.cache.as_mut()
     }
 
+    fn projection_cache_mut(&mut self) -> &mut BitmapCache {
+        self.projection_cache.get_or_insert_with(Default::default)
+    }
+
+    fn clear_projection_cache(&mut self) {
+        self.projection_cache = None;
+    }
+
     /// Invalidates a cached bitmap, if it exists.
     /// This may only be called once per frame - the first call will return true, regardless of
     /// if there was a cache.
@@ -698,6 +770,9 @@ impl<'gc> DisplayObjectBase<'gc> {
         if let Some(cache) = &mut self.cache {
             cache.make_dirty();
         }
+        if let Some(cache) = &mut self.projection_cache {
+            cache.make_dirty();
+        }
         self.flags.insert(DisplayObjectFlags::CACHE_INVALIDATED);
         true
     }
@@ -771,6 +846,11 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
+    // 3D objects are projected with the transform of their parent.
+    let parent_transform = this
+        .base()
+        .matrix3d()
+        .map(|_| context.transform_stack.transform());
     context.transform_stack.push(this.base().transform());
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
@@ -779,7 +859,12 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         None
     };
 
-    let cache_info = if context.use_bitmap_cache && this.is_bitmap_cached() {
+    let projected = match parent_transform {
+        Some(parent_transform) => render_projected(this, context, &parent_transform),
+        None => false,
+    };
+
+    let cache_info = if !projected && context.use_bitmap_cache && this.is_bitmap_cached() {
         let mut cache_info: Option<DrawCacheInfo> = None;
         let base_transform = context.transform_stack.transform();
         let bounds: Rectangle<Twips> = this.render_bounds_with_transform(
@@ -882,6 +967,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                     ty: -offset_y,
                     ..cache_info.base_transform.matrix
                 },
+                ..Default::default()
             });
             let mut offscreen_context = RenderContext {
                 renderer: context.renderer,
@@ -914,12 +1000,13 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                         ..Default::default()
                     },
                     color_transform: cache_info.base_transform.color_transform,
+                    ..Default::default()
                 },
                 true,
                 PixelSnapping::Always, // cacheAsBitmap forces pixel snapping
             )
         });
-    } else {
+    } else if !projected {
         if let Some(background) = this.opaque_background() {
             // This is intended for use with cacheAsBitmap, but can be set for non-cached objects too
             // It wants the entire bounding box to be cleared before any draws happen
@@ -959,6 +1046,175 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     context.transform_stack.pop();
 }
 
+/// Renders an object whose 3D transform foreshortens it, by rendering it into a bitmap
+/// and drawing that bitmap with the exact projection of its 3D transform.
+///
+/// Returns false if the object isn't foreshortened or can't be drawn this way, in which
+/// case it has to be rendered normally, with the linear approximation of its projection.
+fn render_projected<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    parent_transform: &Transform,
+) -> bool {
+    let Some(matrix3d) = this.base().matrix3d().copied() else {
+        return false;
+    };
+    let projection = matrix3d.project_plane(
+        &parent_transform.matrix,
+        parent_transform.perspective_projection.as_ref(),
+    );
+    if !context.use_bitmap_cache || projection.is_affine_on_plane() {
+        this.base_mut(context.gc_context).clear_projection_cache();
+        return false;
+    }
+
+    let view_matrix = context.stage.view_matrix();
+    let local_bounds = this.render_bounds_with_transform(&Matrix::IDENTITY, false, &view_matrix);
+    if !local_bounds.is_valid() {
+        return false;
+    }
+    let (x_min, y_min) = (
+        local_bounds.x_min.to_pixels(),
+        local_bounds.y_min.to_pixels(),
+    );
+    let (x_max, y_max) = (
+        local_bounds.x_max.to_pixels(),
+        local_bounds.y_max.to_pixels(),
+    );
+    let corners = [
+        (x_min, y_min),
+        (x_max, y_min),
+        (x_max, y_max),
+        (x_min, y_max),
+    ]
+    .map(|(x, y)| projection.transform_point([x, y, 0.0]));
+    if corners.iter().any(|corner| corner[3] <= 0.0) {
+        // Part of the object is behind the viewer, which a single projected quad can't show.
+        return false;
+    }
+    let corners = corners.map(|[x, y, _, w]| (x / w, y / w));
+    let distance =
+        |a: usize, b: usize| f64::hypot(corners[a].0 - corners[b].0, corners[a].1 - corners[b].1);
+
+    // Render at the largest scale any part of the object is drawn with, so that the bitmap
+    // is never magnified. The scale is rounded up to avoid reallocating the bitmap for
+    // every small change of the projection.
+    let raster_scale = |length: f64, size: f64| {
+        let scale = if size > 0.0 { length / size } else { 1.0 };
+        ((scale * 8.0).ceil() / 8.0).max(1.0 / 8.0)
+    };
+    let scale_x = raster_scale(distance(0, 1).max(distance(3, 2)), x_max - x_min);
+    let scale_y = raster_scale(distance(0, 3).max(distance(1, 2)), y_max - y_min);
+    let raster_matrix = Matrix::scale(scale_x as f32, scale_y as f32);
+
+    let bounds = this.render_bounds_with_transform(&raster_matrix, false, &view_matrix);
+    let width = bounds.width().to_pixels().ceil().max(0.0);
+    let height = bounds.height().to_pixels().ceil().max(0.0);
+    if width > u16::MAX as f64 || height > u16::MAX as f64 {
+        return false;
+    }
+    let (width, height) = (width as u16, height as u16);
+
+    let mut filters = this.filters();
+    filters.retain(|f| !f.impotent());
+    let mut filter_rect = Rectangle {
+        x_min: Twips::ZERO,
+        x_max: Twips::from_pixels_i32(width as i32),
+        y_min: Twips::ZERO,
+        y_max: Twips::from_pixels_i32(height as i32),
+    };
+    for filter in &mut filters {
+        // Filters are applied to the object before it's projected.
+        filter.scale(scale_x as f32, scale_y as f32);
+        filter_rect = filter.calculate_dest_rect(filter_rect);
+    }
+    let draw_offset = Point::new(
+        filter_rect.x_min.to_pixels().floor() as i32,
+        filter_rect.y_min.to_pixels().floor() as i32,
+    );
+    let filter_width = filter_rect.x_max.to_pixels().ceil() as i32 - draw_offset.x;
+    let filter_height = filter_rect.y_max.to_pixels().ceil() as i32 - draw_offset.y;
+
+    let swf_version = this.swf_version();
+    let (handle, dirty) = {
+        let mut base = this.base_mut(context.gc_context);
+        let cache = base.projection_cache_mut();
+        let dirty = cache.is_dirty(&raster_matrix, width, height);
+        if dirty {
+            cache.update(
+                context.renderer,
+                raster_matrix,
+                width,
+                height,
+                filter_width as u16,
+                filter_height as u16,
+                draw_offset,
+                swf_version,
+            );
+        }
+        (cache.handle(), dirty)
+    };
+    let Some(handle) = handle else {
+        return false;
+    };
+
+    let offset_x = bounds.x_min + Twips::from_pixels_i32(draw_offset.x);
+    let offset_y = bounds.y_min + Twips::from_pixels_i32(draw_offset.y);
+    if dirty {
+        let mut transform_stack = TransformStack::new();
+        transform_stack.push(&Transform {
+            matrix: Matrix {
+                tx: -offset_x,
+                ty: -offset_y,
+                ..raster_matrix
+            },
+            ..Default::default()
+        });
+        let mut offscreen_context = RenderContext {
+            renderer: context.renderer,
+            commands: CommandList::new(),
+            cache_draws: context.cache_draws,
+            gc_context: context.gc_context,
+            library: context.library,
+            transform_stack: &mut transform_stack,
+            is_offscreen: true,
+            use_bitmap_cache: true,
+            stage: context.stage,
+        };
+        this.render_self(&mut offscreen_context);
+        offscreen_context.cache_draws.push(BitmapCacheEntry {
+            handle: handle.clone(),
+            commands: offscreen_context.commands,
+            clear: this.opaque_background().unwrap_or_default(),
+            filters,
+        });
+    }
+
+    // Maps the pixels of the bitmap back to the local coordinates of the object.
+    let bitmap_to_local = Matrix::scale(1.0 / scale_x as f32, 1.0 / scale_y as f32)
+        * Matrix::translate(offset_x, offset_y);
+    apply_standard_mask_and_scroll(this, context, |context| {
+        // The transform stack already includes the scroll, but the projection doesn't.
+        let scrolled_to_local = match this.scroll_rect() {
+            Some(rect) => Matrix::translate(-rect.x_min, -rect.y_min) * bitmap_to_local,
+            None => bitmap_to_local,
+        };
+        let transform = context.transform_stack.transform();
+        context.commands.render_bitmap(
+            handle,
+            Transform {
+                matrix: transform.matrix * bitmap_to_local,
+                color_transform: transform.color_transform,
+                matrix3d: Some(projection * Matrix3D::from_2d(&scrolled_to_local)),
+                perspective_projection: None,
+            },
+            true,
+            PixelSnapping::Never,
+        )
+    });
+    true
+}
+
 /// This applies the **standard** method of `mask` and `scrollRect`.
 ///
 /// It uses the stencil buffer so that any pixel drawn in the mask will allow the inner contents to show.
@@ -990,6 +1246,7 @@ pub fn apply_standard_mask_and_scroll<'gc, F>(
         context.transform_stack.push(&Transform {
             matrix: Matrix::translate(-rect.x_min, -rect.y_min),
             color_transform: Default::default(),
+            ..Default::default()
         });
     }
 
@@ -1061,7 +1318,8 @@ pub fn apply_standard_mask_and_scroll<'gc, F>(
         MovieClip(MovieClip<'gc>),
         Text(Text<'gc>),
         Video(Video<'gc>),
-        LoaderDisplay(LoaderDisplay<'gc>)
+        LoaderDisplay(LoaderDisplay<'gc>),
+        TextLine(TextLine<'gc>)
     }
 )]
 pub trait TDisplayObject<'gc>:
@@ -1353,6 +1611,83 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The `z` position in pixels of this display object in local space.
+    /// Returned by the `z` ActionScript property.
+    fn z(&self) -> f64 {
+        self.base()
+            .matrix3d()
+            .map(|matrix3d| matrix3d.raw_data[14])
+            .unwrap_or(0.0)
+    }
+
+    /// Sets the `z` position in pixels of this display object in local space.
+    /// Set by the `z` ActionScript property. This turns the object into a 3D object.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_z(&self, gc_context: &Mutation<'gc>, z: f64) {
+        let mut base = self.base_mut(gc_context);
+        let mut matrix3d = base
+            .matrix3d()
+            .copied()
+            .unwrap_or_else(|| Matrix3D::from_2d(base.matrix()));
+        if matrix3d.raw_data[14] == z {
+            return;
+        }
+        matrix3d.raw_data[14] = z;
+        base.set_transformed_by_script(true);
+        base.set_matrix3d(Some(matrix3d));
+        drop(base);
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The 3D components (translation, rotation and scale) of this display object.
+    fn components3d(&self) -> Components3D {
+        self.base().components3d()
+    }
+
+    /// Replaces the 3D components of this display object, turning it into a 3D object.
+    /// Used by the `rotationX`/`rotationY`/`rotationZ`/`scaleZ` ActionScript properties.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_components3d(&self, gc_context: &Mutation<'gc>, components: &Components3D) {
+        if self.base_mut(gc_context).set_components3d(components) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The 3D transform of this display object, or `None` if it is a 2D object.
+    fn matrix3d(&self) -> Option<Matrix3D> {
+        self.base().matrix3d().copied()
+    }
+
+    /// Sets or clears the 3D transform of this display object.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_matrix3d(&self, gc_context: &Mutation<'gc>, matrix3d: Option<Matrix3D>) {
+        self.base_mut(gc_context).set_matrix3d(matrix3d);
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The perspective projection used for the 3D children of this display object,
+    /// if one was explicitly set.
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.base().perspective_projection().copied()
+    }
+
+    /// Sets the perspective projection used for the 3D children of this display object.
+    fn set_perspective_projection(
+        &self,
+        gc_context: &Mutation<'gc>,
+        projection: Option<PerspectiveProjection>,
+    ) {
+        self.base_mut(gc_context)
+            .set_perspective_projection(projection);
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -1633,6 +1968,41 @@ pub trait TDisplayObject<'gc>:
 
     fn set_scaling_grid(&self, gc_context: &Mutation<'gc>, rect: Rectangle<Twips>) {
         self.base_mut(gc_context).scaling_grid = rect;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// The nine-slice mapping that the `scale9Grid` of this object applies to its
+    /// own graphics and to the shapes it contains.
+    /// Returns `None` if there is no grid, or if this object isn't scaled.
+    ///
+    /// The borders keep their size on the stage, so the scale of all ancestors is undone as well.
+    fn scale9_mapping(&self) -> Option<Scale9Mapping> {
+        let grid = self.scaling_grid();
+        if !grid.is_valid() || grid.width() <= Twips::ZERO || grid.height() <= Twips::ZERO {
+            return None;
+        }
+        let matrix = self.local_to_global_matrix();
+        let scale_x = f64::from(matrix.a).hypot(f64::from(matrix.b));
+        let scale_y = f64::from(matrix.c).hypot(f64::from(matrix.d));
+        Scale9Mapping::new(&self.bounds(), &grid, scale_x, scale_y)
+            .filter(|mapping| !mapping.is_identity())
+    }
+
+    /// The nine-slice mapping of the closest ancestor with a `scale9Grid`, along with the matrix
+    /// that transforms the local space of this object into the one of that ancestor.
+    ///
+    /// This also finds grids through intermediate containers, such as the state sprites of a `SimpleButton`.
+    fn ancestor_scale9_mapping(&self) -> Option<(Scale9Mapping, Matrix)> {
+        let mut to_ancestor = *self.base().matrix();
+        let mut node = self.parent();
+        while let Some(ancestor) = node {
+            if let Some(mapping) = ancestor.scale9_mapping() {
+                return Some((mapping, to_ancestor));
+            }
+            to_ancestor = *ancestor.base().matrix() * to_ancestor;
+            node = ancestor.parent();
+        }
+        None
     }
 
     /// Whether this object has been removed. Only applies to AVM1.
@@ -2087,6 +2457,9 @@ pub trait TDisplayObject<'gc>:
     fn as_video(self) -> Option<Video<'gc>> {
         None
     }
+    fn as_text_line(self) -> Option<TextLine<'gc>> {
+        None
+    }
     fn as_drawing(&self, _gc_context: &Mutation<'gc>) -> Option<RefMut<'_, Drawing>> {
         None
     }
@@ -2368,6 +2741,7 @@ impl<'gc> DisplayObject<'gc> {
             DisplayObject::MovieClip(mc) => DisplayObjectWeak::MovieClip(mc.downgrade()),
             DisplayObject::LoaderDisplay(l) => DisplayObjectWeak::LoaderDisplay(l.downgrade()),
             DisplayObject::Bitmap(b) => DisplayObjectWeak::Bitmap(b.downgrade()),
+            DisplayObject::TextLine(t) => DisplayObjectWeak::TextLine(t.downgrade()),
             _ => panic!("Downgrade not yet implemented for {:?}", self),
         }
     }
@@ -2604,6 +2978,7 @@ pub enum DisplayObjectWeak<'gc> {
     MovieClip(MovieClipWeak<'gc>),
     LoaderDisplay(LoaderDisplayWeak<'gc>),
     Bitmap(BitmapWeak<'gc>),
+    TextLine(TextLineWeak<'gc>),
 }
 
 impl<'gc> DisplayObjectWeak<'gc> {
@@ -2612,6 +2987,7 @@ impl<'gc> DisplayObjectWeak<'gc> {
             DisplayObjectWeak::MovieClip(mc) => mc.as_ptr(),
             DisplayObjectWeak::LoaderDisplay(ld) => ld.as_ptr(),
             DisplayObjectWeak::Bitmap(b) => b.as_ptr(),
+            DisplayObjectWeak::TextLine(t) => t.as_ptr(),
         }
     }
 
@@ -2620,6 +2996,7 @@ impl<'gc> DisplayObjectWeak<'gc> {
             DisplayObjectWeak::MovieClip(movie) => movie.upgrade(mc).map(|m| m.into()),
             DisplayObjectWeak::LoaderDisplay(ld) => ld.upgrade(mc).map(|ld| ld.into()),
             DisplayObjectWeak::Bitmap(b) => b.upgrade(mc).map(|ld| ld.into()),
+            DisplayObjectWeak::TextLine(t) => t.upgrade(mc).map(|t| t.into()),
         }
     }
 }
@@ -22,7 +22,8 @@ use crate::drawing::Drawing;
 use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
 use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
 use crate::html::{
-    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, TextFormat,
+    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, StyleSheet,
+    TextFormat,
 };
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
@@ -162,12 +163,21 @@ pub struct EditTextData<'gc> {
     /// Doesn't affect script-triggered modifications.
     max_chars: i32,
 
+    /// The characters that can be manually input by the user.
+    /// Doesn't affect script-triggered modifications.
+    #[collect(require_static)]
+    restrict: TextRestrict,
+
+    /// The style sheet applied to the HTML text, if any.
+    #[collect(require_static)]
+    style_sheet: Option<Box<EditTextStyleSheet>>,
+
+    /// The `TextField.StyleSheet` object assigned to `styleSheet` (AVM1 only).
+    avm1_style_sheet: Option<Avm1Object<'gc>>,
+
     /// Flags indicating the text field's settings.
     #[collect(require_static)]
     flags: EditTextFlag,
-
-    /// Whether this EditText represents an AVM2 TextLine.
-    is_tlf: bool,
 }
 
 impl<'gc> EditTextData<'gc> {
@@ -184,6 +194,19 @@ impl<'gc> EditTextData<'gc> {
             Twips::ZERO
         }
     }
+
+    /// The selection that should be drawn.
+    ///
+    /// Without focus, only a range selected with `alwaysShowSelection` is drawn, never a caret.
+    fn visible_selection(&self) -> Option<TextSelection> {
+        if self.flags.contains(EditTextFlag::HAS_FOCUS) {
+            self.selection
+        } else if self.flags.contains(EditTextFlag::ALWAYS_SHOW_SELECTION) {
+            self.selection.filter(|selection| !selection.is_caret())
+        } else {
+            None
+        }
+    }
 }
 
 // TODO: would be nicer to compute (and return) this during layout, instead of afterwards
@@ -246,7 +269,7 @@ impl<'gc> EditText<'gc> {
         let text = swf_tag.initial_text().unwrap_or_default().decode(encoding);
 
         let mut text_spans = if swf_tag.is_html() {
-            FormatSpans::from_html(&text, default_format, swf_tag.is_multiline())
+            FormatSpans::from_html(&text, default_format, swf_tag.is_multiline(), false, None)
         } else {
             FormatSpans::from_text(text.into_owned(), default_format)
         };
@@ -333,7 +356,9 @@ impl<'gc> EditText<'gc> {
                 line_data,
                 scroll: 1,
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
-                is_tlf: false,
+                restrict: Default::default(),
+                style_sheet: None,
+                avm1_style_sheet: None,
             },
         ));
 
@@ -380,22 +405,6 @@ impl<'gc> EditText<'gc> {
         text_field
     }
 
-    /// Create a new, dynamic `EditText` representing an AVM2 TextLine.
-    pub fn new_tlf(
-        context: &mut UpdateContext<'_, 'gc>,
-        swf_movie: Arc<SwfMovie>,
-        x: f64,
-        y: f64,
-        width: f64,
-        height: f64,
-    ) -> Self {
-        let text = Self::new(context, swf_movie, x, y, width, height);
-        text.set_is_tlf(context.gc_context, true);
-        text.set_selectable(false, context);
-
-        text
-    }
-
     pub fn text(self) -> WString {
         self.0.read().text_spans.text().into()
     }
@@ -404,6 +413,11 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        let html = edit_text.text_spans.to_html();
+        if let Some(style_sheet) = &mut edit_text.style_sheet {
+            style_sheet.html = html;
+            style_sheet.hovered_link = None;
+        }
         drop(edit_text);
 
         self.relayout(context);
@@ -411,7 +425,11 @@ impl<'gc> EditText<'gc> {
 
     pub fn html_text(self) -> WString {
         if self.is_html() {
-            self.0.read().text_spans.to_html()
+            let read = self.0.read();
+            match &read.style_sheet {
+                Some(style_sheet) => style_sheet.html.clone(),
+                None => read.text_spans.to_html(),
+            }
         } else {
             // Non-HTML text fields always return plain text.
             self.text()
@@ -421,11 +439,21 @@ impl<'gc> EditText<'gc> {
     pub fn set_html_text(self, text: &WStr, context: &mut UpdateContext<'_, 'gc>) {
         if self.is_html() {
             let mut write = self.0.write(context.gc_context);
+            if let Some(style_sheet) = &mut write.style_sheet {
+                style_sheet.html = text.into();
+                style_sheet.hovered_link = None;
+                drop(write);
+                self.apply_style_sheet(context);
+                return;
+            }
+
             let default_format = write.text_spans.default_format().clone();
             write.text_spans = FormatSpans::from_html(
                 text,
                 default_format,
                 write.flags.contains(EditTextFlag::MULTILINE),
+                write.flags.contains(EditTextFlag::CONDENSE_WHITE),
+                None,
             );
             drop(write);
 
@@ -435,6 +463,128 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Attach a style sheet to this text field, or remove it.
+    ///
+    /// While a style sheet is attached, the field can't be edited by the user,
+    /// and `htmlText` returns the HTML as it was set.
+    pub fn set_style_sheet(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        style_sheet: Option<StyleSheet>,
+    ) {
+        let Some(style_sheet) = style_sheet else {
+            let removed = self.0.write(context.gc_context).style_sheet.take();
+            if let Some(removed) = removed {
+                // The HTML that was set is lowered again, now without any styles.
+                self.set_html_text(&removed.html, context);
+            }
+            return;
+        };
+
+        let html = self.html_text();
+        self.0.write(context.gc_context).style_sheet = Some(Box::new(EditTextStyleSheet {
+            style_sheet,
+            html,
+            hovered_link: None,
+        }));
+        self.apply_style_sheet(context);
+    }
+
+    pub fn avm1_style_sheet(self) -> Option<Avm1Object<'gc>> {
+        self.0.read().avm1_style_sheet
+    }
+
+    pub fn set_avm1_style_sheet(self, gc_context: &Mutation<'gc>, object: Option<Avm1Object<'gc>>) {
+        self.0.write(gc_context).avm1_style_sheet = object;
+    }
+
+    /// Lower the HTML text again with the attached style sheet.
+    fn apply_style_sheet(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        let Some(style_sheet) = write.style_sheet.as_deref() else {
+            return;
+        };
+
+        let mut text_spans = FormatSpans::from_html(
+            &style_sheet.html,
+            write.text_spans.default_format().clone(),
+            write.flags.contains(EditTextFlag::MULTILINE),
+            write.flags.contains(EditTextFlag::CONDENSE_WHITE),
+            Some(&style_sheet.style_sheet),
+        );
+        if let Some((start, end)) = style_sheet.hovered_link {
+            if let Some(hover) = style_sheet.style_sheet.style(WStr::from_units(b"a:hover")) {
+                text_spans.set_text_format(start, end, &hover.text_format());
+            }
+        }
+        write.text_spans = text_spans;
+        drop(write);
+
+        self.relayout(context);
+    }
+
+    /// Whether links change their style when hovered.
+    fn has_hover_style(self) -> bool {
+        self.0
+            .read()
+            .style_sheet
+            .as_ref()
+            .is_some_and(|style_sheet| {
+                style_sheet
+                    .style_sheet
+                    .style(WStr::from_units(b"a:hover"))
+                    .is_some()
+            })
+    }
+
+    fn set_hovered_link(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        hovered_link: Option<(usize, usize)>,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        let Some(style_sheet) = &mut write.style_sheet else {
+            return;
+        };
+        if style_sheet.hovered_link != hovered_link {
+            style_sheet.hovered_link = hovered_link;
+            drop(write);
+            self.apply_style_sheet(context);
+        }
+    }
+
+    /// The text range of the link at the given point, including all adjacent spans with the same URL.
+    fn link_range_at(self, point: Point<Twips>) -> Option<(usize, usize)> {
+        if !self.is_link_at(point) {
+            return None;
+        }
+        let position = self.screen_position_to_index(point)?;
+
+        let read = self.0.read();
+        let spans: Vec<_> = read
+            .text_spans
+            .iter_spans()
+            .map(|(start, end, _, span)| (start, end, &span.url))
+            .collect();
+        let index = spans
+            .iter()
+            .position(|(start, end, _)| (*start..*end).contains(&position))?;
+        let url = spans[index].2;
+        if url.is_empty() {
+            return None;
+        }
+
+        let first = spans[..index]
+            .iter()
+            .rposition(|(_, _, u)| *u != url)
+            .map_or(0, |i| i + 1);
+        let last = spans[index..]
+            .iter()
+            .position(|(_, _, u)| *u != url)
+            .map_or(spans.len(), |i| index + i);
+        Some((spans[first].0, spans[last - 1].1))
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -606,14 +756,6 @@ impl<'gc> EditText<'gc> {
             .set(EditTextFlag::HTML, is_html);
     }
 
-    pub fn is_tlf(self) -> bool {
-        self.0.read().is_tlf
-    }
-
-    pub fn set_is_tlf(self, gc_context: &Mutation<'gc>, is_tlf: bool) {
-        self.0.write(gc_context).is_tlf = is_tlf;
-    }
-
     pub fn replace_text(
         self,
         from: usize,
@@ -919,10 +1061,12 @@ impl<'gc> EditText<'gc> {
 
         let edit_text = self.0.read();
 
-        let visible_selection = if edit_text.flags.contains(EditTextFlag::HAS_FOCUS) {
-            edit_text.selection
+        let visible_selection = edit_text.visible_selection();
+        // An unfocused selection is only shown with `alwaysShowSelection`, and is drawn in gray.
+        let selection_color = if edit_text.flags.contains(EditTextFlag::HAS_FOCUS) {
+            Color::BLACK
         } else {
-            None
+            Color::from_rgb(0x808080, 255)
         };
 
         let caret = if let LayoutContent::Text { start, end, .. } = &lbox.content() {
@@ -968,7 +1112,7 @@ impl<'gc> EditText<'gc> {
                         // If it's highlighted, override the color.
                         match visible_selection {
                             Some(visible_selection) if visible_selection.contains(start + pos) => {
-                                // Draw black selection rect
+                                // Draw selection rect
                                 let selection_box = context.transform_stack.transform().matrix
                                     * Matrix::create_box(
                                         advance.to_pixels() as f32,
@@ -977,12 +1121,13 @@ impl<'gc> EditText<'gc> {
                                         x + Twips::from_pixels(-1.0),
                                         Twips::from_pixels(2.0),
                                     );
-                                context.commands.draw_rect(Color::BLACK, selection_box);
+                                context.commands.draw_rect(selection_color, selection_box);
 
                                 // Set text color to white
                                 context.transform_stack.push(&Transform {
                                     matrix: transform.matrix,
                                     color_transform: ColorTransform::IDENTITY,
+                                    ..Default::default()
                                 });
                             }
                             _ => {
@@ -1222,6 +1367,53 @@ impl<'gc> EditText<'gc> {
         self.0.write(context.gc_context).max_chars = value;
     }
 
+    pub fn restrict(self) -> Option<WString> {
+        self.0.read().restrict.value().map(WString::from)
+    }
+
+    pub fn set_restrict(self, value: Option<&WStr>, context: &mut UpdateContext<'_, 'gc>) {
+        self.0.write(context.gc_context).restrict = TextRestrict::from_pattern(value);
+    }
+
+    /// Whether extra whitespace is removed from HTML text set after this is enabled.
+    pub fn condense_white(self) -> bool {
+        self.0.read().flags.contains(EditTextFlag::CONDENSE_WHITE)
+    }
+
+    pub fn set_condense_white(self, context: &mut UpdateContext<'_, 'gc>, condense_white: bool) {
+        self.0
+            .write(context.gc_context)
+            .flags
+            .set(EditTextFlag::CONDENSE_WHITE, condense_white);
+    }
+
+    /// Whether the selection stays visible when the text field loses focus.
+    pub fn always_show_selection(self) -> bool {
+        self.0
+            .read()
+            .flags
+            .contains(EditTextFlag::ALWAYS_SHOW_SELECTION)
+    }
+
+    pub fn set_always_show_selection(self, gc_context: &Mutation<'gc>, value: bool) {
+        self.0
+            .write(gc_context)
+            .flags
+            .set(EditTextFlag::ALWAYS_SHOW_SELECTION, value);
+    }
+
+    /// Whether the mouse wheel scrolls this text field.
+    pub fn is_mouse_wheel_enabled(self) -> bool {
+        !self.0.read().flags.contains(EditTextFlag::NO_MOUSE_WHEEL)
+    }
+
+    pub fn set_mouse_wheel_enabled(self, gc_context: &Mutation<'gc>, is_enabled: bool) {
+        self.0
+            .write(gc_context)
+            .flags
+            .set(EditTextFlag::NO_MOUSE_WHEEL, !is_enabled);
+    }
+
     pub fn screen_position_to_index(self, position: Point<Twips>) -> Option<usize> {
         let text = self.0.read();
         let Some(mut position) = self.global_to_local(position) else {
@@ -1279,6 +1471,11 @@ impl<'gc> EditText<'gc> {
         None
     }
 
+    /// Whether the user can edit the text. Text fields with a style sheet are never editable.
+    fn accepts_input(self) -> bool {
+        self.is_editable() && self.0.read().style_sheet.is_none()
+    }
+
     /// The number of characters that currently can be inserted, considering `TextField.maxChars`
     /// constraint, current text length, and current text selection length.
     fn available_chars(self) -> usize {
@@ -1302,7 +1499,7 @@ impl<'gc> EditText<'gc> {
         control_code: TextControlCode,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        if !self.is_editable() && control_code.is_edit_input() {
+        if !self.accepts_input() && control_code.is_edit_input() {
             return;
         }
 
@@ -1365,16 +1562,15 @@ impl<'gc> EditText<'gc> {
                     }
                 }
                 TextControlCode::Paste => {
-                    let text = &context.ui.clipboard_content();
+                    let text = self
+                        .0
+                        .read()
+                        .restrict
+                        .filter(&context.ui.clipboard_content());
                     // TODO: To match Flash Player, we should truncate pasted text that is longer than max_chars
                     // instead of canceling the paste action entirely
                     if text.len() <= self.available_chars() {
-                        self.replace_text(
-                            selection.start(),
-                            selection.end(),
-                            &WString::from_utf8(text),
-                            context,
-                        );
+                        self.replace_text(selection.start(), selection.end(), &text, context);
                         let new_pos = selection.start() + text.len();
                         if is_selectable {
                             self.set_selection(
@@ -1464,10 +1660,14 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc>) {
-        if self.0.read().flags.contains(EditTextFlag::READ_ONLY) {
+        if !self.accepts_input() {
             return;
         }
 
+        let Some(character) = self.0.read().restrict.to_allowed(character) else {
+            return;
+        };
+
         if let Some(selection) = self.selection() {
             let mut changed = false;
             let mut cancelled = false;
@@ -2081,18 +2281,42 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         _context: &mut UpdateContext<'_, 'gc>,
         event: ClipEvent,
     ) -> ClipEventResult {
-        if event != ClipEvent::Press {
-            return ClipEventResult::NotHandled;
+        match event {
+            ClipEvent::Press => ClipEventResult::Handled,
+            ClipEvent::MouseWheel { .. } if self.is_mouse_wheel_enabled() => {
+                ClipEventResult::Handled
+            }
+            ClipEvent::MouseMoveInside | ClipEvent::RollOut { .. } if self.has_hover_style() => {
+                ClipEventResult::Handled
+            }
+            _ => ClipEventResult::NotHandled,
         }
-
-        ClipEventResult::Handled
     }
 
     fn event_dispatch(
         self,
         context: &mut UpdateContext<'_, 'gc>,
-        _event: ClipEvent<'gc>,
+        event: ClipEvent<'gc>,
     ) -> ClipEventResult {
+        match event {
+            ClipEvent::MouseWheel { delta } => {
+                // Scrolling the wheel up moves the text down.
+                let scroll = self.scroll() as f64 - delta.lines();
+                self.set_scroll(scroll, context);
+                return ClipEventResult::Handled;
+            }
+            ClipEvent::MouseMoveInside => {
+                let hovered_link = self.link_range_at(*context.mouse_position);
+                self.set_hovered_link(context, hovered_link);
+                return ClipEventResult::Handled;
+            }
+            ClipEvent::RollOut { .. } => {
+                self.set_hovered_link(context, None);
+                return ClipEventResult::Handled;
+            }
+            _ => {}
+        }
+
         if self.is_editable() || self.is_selectable() {
             let tracker = context.focus_tracker;
             tracker.set(Some(self.into()), context);
@@ -2202,11 +2426,28 @@ bitflags::bitflags! {
         const WAS_STATIC = 1 << 10;
         const BORDER = 1 << 11;
         const NO_SELECT = 1 << 12;
+        const CONDENSE_WHITE = 1 << 13;
+        const ALWAYS_SHOW_SELECTION = 1 << 14;
+        const NO_MOUSE_WHEEL = 1 << 15;
         const SWF_FLAGS = Self::READ_ONLY.bits() | Self::PASSWORD.bits() | Self::MULTILINE.bits() | Self::WORD_WRAP.bits() | Self::USE_OUTLINES.bits() |
                           Self::HTML.bits() | Self::WAS_STATIC.bits() | Self::BORDER.bits() | Self::NO_SELECT.bits();
     }
 }
 
+/// A style sheet attached to a text field with `TextField.styleSheet`.
+#[derive(Clone, Debug)]
+struct EditTextStyleSheet {
+    style_sheet: StyleSheet,
+
+    /// The HTML text as set by scripts.
+    ///
+    /// It's lowered again whenever the hovered link changes.
+    html: WString,
+
+    /// The text range of the link under the mouse, which is styled with `a:hover`.
+    hovered_link: Option<(usize, usize)>,
+}
+
 /// Static data shared between all instances of a text object.
 #[derive(Debug, Clone, Collect)]
 #[collect(require_static)]
@@ -2295,3 +2536,199 @@ impl TextSelection {
         self.to == self.from
     }
 }
+
+/// The set of characters a user may type into a text field, as given by `TextField.restrict`.
+///
+/// The pattern lists characters and ranges (`A-Z`) that are allowed. A `^` switches between
+/// allowing and forbidding the characters that follow it, and a pattern starting with `^`
+/// allows everything that isn't forbidden. `\` escapes the next character, so `\-`, `\^` and
+/// `\\` can be used literally.
+#[derive(Clone, Debug, Default)]
+struct TextRestrict {
+    /// The pattern as set by scripts, or `None` if any character is allowed.
+    value: Option<WString>,
+
+    /// Whether characters not matched by any range are allowed.
+    allow_by_default: bool,
+
+    /// Inclusive ranges of characters, and whether they're allowed.
+    /// Later ranges take priority over earlier ones.
+    ranges: Vec<(bool, char, char)>,
+}
+
+impl TextRestrict {
+    fn from_pattern(value: Option<&WStr>) -> Self {
+        let Some(value) = value else {
+            return Self::default();
+        };
+
+        let mut chars = char::decode_utf16(value.iter())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .peekable();
+        let mut allow_by_default = false;
+        let mut allowed = true;
+        let mut ranges: Vec<(bool, char, char)> = Vec::new();
+        // Whether the last range is a single character that a `-` can extend.
+        let mut can_extend = false;
+        let mut is_first = true;
+
+        while let Some(c) = chars.next() {
+            let c = match c {
+                '^' => {
+                    allow_by_default |= is_first;
+                    allowed = !allowed;
+                    can_extend = false;
+                    is_first = false;
+                    continue;
+                }
+                '-' if can_extend && chars.peek().is_some() => {
+                    let mut end = chars.next().unwrap_or_default();
+                    if end == '\\' {
+                        let Some(escaped) = chars.next() else {
+                            break;
+                        };
+                        end = escaped;
+                    }
+                    if let Some(range) = ranges.last_mut() {
+                        range.2 = end;
+                    }
+                    can_extend = false;
+                    continue;
+                }
+                '\\' => match chars.next() {
+                    Some(escaped) => escaped,
+                    None => break,
+                },
+                c => c,
+            };
+            ranges.push((allowed, c, c));
+            can_extend = true;
+            is_first = false;
+        }
+
+        Self {
+            value: Some(value.into()),
+            allow_by_default,
+            ranges,
+        }
+    }
+
+    /// The pattern as set by scripts, or `None` if any character is allowed.
+    fn value(&self) -> Option<&WStr> {
+        self.value.as_deref()
+    }
+
+    fn is_allowed(&self, c: char) -> bool {
+        if self.value.is_none() {
+            return true;
+        }
+
+        self.ranges
+            .iter()
+            .rev()
+            .find(|(_, start, end)| (*start..=*end).contains(&c))
+            .map_or(self.allow_by_default, |(allowed, _, _)| *allowed)
+    }
+
+    /// Get the character to input in place of `c`, if any.
+    ///
+    /// Like Flash Player, a letter that isn't allowed is accepted in its other case if that is.
+    fn to_allowed(&self, c: char) -> Option<char> {
+        if self.is_allowed(c) {
+            return Some(c);
+        }
+
+        let other_case: Vec<char> = if c.is_lowercase() {
+            c.to_uppercase().collect()
+        } else {
+            c.to_lowercase().collect()
+        };
+        match other_case[..] {
+            [other] if self.is_allowed(other) => Some(other),
+            _ => None,
+        }
+    }
+
+    /// Drop or convert the characters of pasted text according to this restriction.
+    fn filter(&self, text: &str) -> WString {
+        let mut filtered = WString::new();
+        for c in text.chars().filter_map(|c| self.to_allowed(c)) {
+            filtered.push_char(c);
+        }
+        filtered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextRestrict;
+    use crate::string::WStr;
+
+    fn restrict(pattern: &str) -> TextRestrict {
+        TextRestrict::from_pattern(Some(WStr::from_units(pattern.as_bytes())))
+    }
+
+    #[test]
+    fn restrict_null_allows_everything() {
+        let restrict = TextRestrict::from_pattern(None);
+        assert!(restrict.is_allowed('a'));
+        assert!(restrict.is_allowed('\u{263A}'));
+        assert_eq!(restrict.value(), None);
+    }
+
+    #[test]
+    fn restrict_empty_allows_nothing() {
+        let restrict = restrict("");
+        assert!(!restrict.is_allowed('a'));
+        assert!(!restrict.is_allowed(' '));
+    }
+
+    #[test]
+    fn restrict_ranges() {
+        let restrict = restrict("A-Z 0-9");
+        assert!(restrict.is_allowed('Q'));
+        assert!(restrict.is_allowed('5'));
+        assert!(restrict.is_allowed(' '));
+        assert!(!restrict.is_allowed('-'));
+        assert!(!restrict.is_allowed('!'));
+        assert_eq!(restrict.to_allowed('q'), Some('Q'));
+    }
+
+    #[test]
+    fn restrict_negation() {
+        let restrict = restrict("^a-z");
+        assert!(restrict.is_allowed('A'));
+        assert!(restrict.is_allowed('1'));
+        assert!(!restrict.is_allowed('m'));
+        assert_eq!(restrict.to_allowed('m'), Some('M'));
+
+        let restrict = restrict("A-Z^Q");
+        assert!(restrict.is_allowed('P'));
+        assert!(!restrict.is_allowed('Q'));
+        assert!(!restrict.is_allowed('a'));
+    }
+
+    #[test]
+    fn restrict_escapes() {
+        let restrict = restrict("0-9\\-\\^\\\\");
+        assert!(restrict.is_allowed('-'));
+        assert!(restrict.is_allowed('^'));
+        assert!(restrict.is_allowed('\\'));
+        assert!(restrict.is_allowed('7'));
+        assert!(!restrict.is_allowed('a'));
+    }
+
+    #[test]
+    fn restrict_literal_dashes() {
+        let restrict = restrict("-a-");
+        assert!(restrict.is_allowed('-'));
+        assert!(restrict.is_allowed('a'));
+        assert!(!restrict.is_allowed('b'));
+    }
+
+    #[test]
+    fn restrict_filters_pasted_text() {
+        let restrict = restrict("0-9.");
+        assert_eq!(restrict.filter("1,234.5x"), WStr::from_units(b"1234.5"));
+    }
+}
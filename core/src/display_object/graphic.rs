@@ -13,7 +13,8 @@ use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::commands::CommandHandler;
-use std::cell::{Ref, RefMut};
+use ruffle_render::scale9::{Scale9Cache, Scale9Mapping};
+use std::cell::{Ref, RefCell, RefMut};
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -58,6 +59,7 @@ impl<'gc> Graphic<'gc> {
             )),
             shape: swf_shape,
             movie,
+            scale9_handles: Default::default(),
         };
 
         Graphic(GcCell::new(
@@ -71,6 +73,31 @@ impl<'gc> Graphic<'gc> {
         ))
     }
 
+    /// Returns the shape of this graphic distorted by a `scale9Grid`, registering it if needed.
+    fn scale9_render_handle(
+        &self,
+        context: &mut RenderContext<'_, 'gc>,
+        mapping: Scale9Mapping,
+        to_grid: Matrix,
+    ) -> Option<ShapeHandle> {
+        let static_data = self.0.read().static_data;
+        static_data.render_handle.as_ref()?;
+        let mut handles = static_data.scale9_handles.borrow_mut();
+        handles.get_or_insert_with(&mapping, &to_grid, || {
+            let library = context
+                .library
+                .library_for_movie(static_data.movie.clone())?;
+            let shape = mapping.map_shape(&(&static_data.shape).into(), &to_grid);
+            Some(context.renderer.register_shape(
+                shape,
+                &MovieLibrarySource {
+                    library,
+                    gc_context: context.gc_context,
+                },
+            ))
+        })
+    }
+
     /// Construct an empty `Graphic`.
     pub fn empty(context: &mut UpdateContext<'_, 'gc>) -> Self {
         let static_data = GraphicStatic {
@@ -90,6 +117,7 @@ impl<'gc> Graphic<'gc> {
                 shape: Vec::new(),
             },
             movie: context.swf.clone(),
+            scale9_handles: Default::default(),
         };
         let drawing = Drawing::new();
 
@@ -100,6 +128,7 @@ impl<'gc> Graphic<'gc> {
                 static_data: gc_arena::Gc::new(context.gc_context, static_data),
                 avm2_object: None,
                 drawing: Some(drawing),
+                scale9_handle: None,
             },
         ))
     }
@@ -170,7 +199,8 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             .library_for_movie_mut(self.movie())
             .get_graphic(id)
         {
-            self.0.write(context.gc_context).static_data = new_graphic.0.read().static_data;
+            let mut write = self.0.write(context.gc_context);
+            write.static_data = new_graphic.0.read().static_data;
         } else {
             tracing::warn!("PlaceObject: expected Graphic at character ID {}", id);
         }
@@ -187,8 +217,21 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             return;
         }
 
+        // Shapes are distorted by the `scale9Grid` of the containers they are in.
+        let scale9 = self.ancestor_scale9_mapping();
+
         if let Some(drawing) = &self.0.read().drawing {
-            drawing.render(context);
+            if let Some((mapping, to_grid)) = &scale9 {
+                drawing.render_scale9(context, mapping, to_grid);
+            } else {
+                drawing.render(context);
+            }
+        } else if let Some((mapping, to_grid)) = scale9 {
+            if let Some(render_handle) = self.scale9_render_handle(context, mapping, to_grid) {
+                context
+                    .commands
+                    .render_shape(render_handle, context.transform_stack.transform())
+            }
         } else if let Some(render_handle) = self.0.read().static_data.render_handle.clone() {
             context
                 .commands
@@ -274,4 +317,6 @@ struct GraphicStatic {
     render_handle: Option<ShapeHandle>,
     bounds: Rectangle<Twips>,
     movie: Arc<SwfMovie>,
+    /// The shapes registered for the `scale9Grid` of an ancestor, shared by all instances.
+    scale9_handles: RefCell<Scale9Cache<ShapeHandle>>,
 }
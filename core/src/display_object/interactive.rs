@@ -11,6 +11,7 @@ use crate::display_object::edit_text::EditText;
 use crate::display_object::loader_display::LoaderDisplay;
 use crate::display_object::movie_clip::MovieClip;
 use crate::display_object::stage::Stage;
+use crate::display_object::text_line::TextLine;
 use crate::display_object::{
     DisplayObject, DisplayObjectBase, TDisplayObject, TDisplayObjectContainer,
 };
@@ -112,6 +113,7 @@ impl<'gc> Default for InteractiveObjectBase<'gc> {
         MovieClip(MovieClip<'gc>),
         EditText(EditText<'gc>),
         LoaderDisplay(LoaderDisplay<'gc>),
+        TextLine(TextLine<'gc>),
     }
 )]
 pub trait TInteractiveObject<'gc>:
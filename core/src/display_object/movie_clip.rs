@@ -456,12 +456,25 @@ impl<'gc> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc>,
         chunk_limit: &mut ExecutionLimit,
     ) -> bool {
+        // The root clip of a movie that's still downloading can only preload what
+        // arrived so far, and then waits for the rest.
+        let downloading = self.is_root() && !self.movie().is_complete();
+        if self.is_root() {
+            self.extend_to_received_data(context.gc_context);
+        }
+
         {
             let read = self.0.read();
-            if read.static_data.preload_progress.read().next_preload_chunk
-                >= read.static_data.swf.len() as u64
-            {
-                return true;
+            let progress = read.static_data.preload_progress.read();
+            if progress.next_preload_chunk >= read.static_data.swf.len() as u64 {
+                if downloading {
+                    return false;
+                }
+                // Once the download is done, a clip that was waiting for it still
+                // has to finish its preload.
+                if !progress.awaiting_data {
+                    return true;
+                }
             }
         }
 
@@ -712,7 +725,10 @@ impl<'gc> MovieClip<'gc> {
         } else {
             Ok(true)
         };
-        let is_finished = end_tag_found || result.is_err() || !result.unwrap_or_default();
+        // Running out of data only means that the rest hasn't been downloaded yet.
+        let awaiting_data = downloading && !end_tag_found && reader.get_ref().is_empty();
+        let is_finished =
+            !awaiting_data && (end_tag_found || result.is_err() || !result.unwrap_or_default());
 
         // These variables will be persisted to be picked back up in the next
         // chunk.
@@ -734,6 +750,7 @@ impl<'gc> MovieClip<'gc> {
                 cur_frame
             };
             write.last_frame_start_pos = start_pos;
+            write.awaiting_data = awaiting_data;
         }
 
         if is_finished {
@@ -749,6 +766,24 @@ impl<'gc> MovieClip<'gc> {
         is_finished
     }
 
+    /// Extends the tag stream of a root clip to the data of its movie that arrived
+    /// since it was last preloaded.
+    fn extend_to_received_data(self, gc_context: &Mutation<'gc>) {
+        let swf = {
+            let read = self.0.read();
+            let swf = &read.static_data.swf;
+            let received = SwfSlice::from(swf.movie.clone());
+            if swf.start != 0 || swf.end >= received.end {
+                return;
+            }
+            received
+        };
+
+        let mut static_data = (*self.0.read().static_data).clone();
+        static_data.swf = swf;
+        self.0.write(gc_context).static_data = Gc::new(gc_context, static_data);
+    }
+
     #[inline]
     fn do_init_action(
         self,
@@ -1300,7 +1335,14 @@ impl<'gc> MovieClip<'gc> {
             return max(self.total_bytes(), 0) as u32;
         }
 
-        let swf_header_size = max(self.total_bytes(), 0) as u32 - self.tag_stream_len() as u32;
+        // The tag stream of a movie that's still downloading isn't complete yet.
+        let tag_stream_len = if self.is_root() {
+            self.movie().full_data_len()
+        } else {
+            self.tag_stream_len()
+        };
+        let swf_header_size =
+            (max(self.total_bytes(), 0) as u32).saturating_sub(tag_stream_len as u32);
 
         swf_header_size + progress_read.next_preload_chunk as u32
     }
@@ -1318,7 +1360,7 @@ impl<'gc> MovieClip<'gc> {
         if self.is_root() {
             compressed_movie_size as u32
         } else {
-            let uncompressed_movie_size = movie.data().len();
+            let uncompressed_movie_size = movie.full_data_len();
             let uncompressed_clip_size = self.tag_stream_len() as u32;
 
             (uncompressed_clip_size as f64 * compressed_movie_size as f64
@@ -2777,7 +2819,18 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
-        self.0.read().drawing.render(context);
+        let scale9 = self
+            .scale9_mapping()
+            .map(|mapping| (mapping, Matrix::IDENTITY))
+            .or_else(|| self.ancestor_scale9_mapping());
+        if let Some((mapping, to_grid)) = scale9 {
+            self.0
+                .read()
+                .drawing
+                .render_scale9(context, &mapping, &to_grid);
+        } else {
+            self.0.read().drawing.render(context);
+        }
         self.render_children(context);
     }
 
@@ -4463,6 +4516,9 @@ struct PreloadProgress {
 
     /// The symbol we are currently asynchronously preloading.
     cur_preload_symbol: Option<CharacterId>,
+
+    /// Whether the preload caught up with a download, and waits for more data.
+    awaiting_data: bool,
 }
 
 impl Default for PreloadProgress {
@@ -4472,6 +4528,7 @@ impl Default for PreloadProgress {
             cur_preload_frame: 1,
             last_frame_start_pos: 0,
             cur_preload_symbol: None,
+            awaiting_data: false,
         }
     }
 }
@@ -27,6 +27,7 @@ use bitflags::bitflags;
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::backend::ViewportDimensions;
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix3d::PerspectiveProjection;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
 use std::cell::{Ref, RefMut};
@@ -304,6 +305,17 @@ impl<'gc> Stage<'gc> {
         self.0.read().stage_size
     }
 
+    /// The perspective projection used by 3D objects that have no other projection set.
+    /// This is centered on the stage, with the focal length derived from the stage width.
+    pub fn default_perspective_projection(self) -> PerspectiveProjection {
+        let (width, height) = self.stage_size();
+        PerspectiveProjection::from_field_of_view(
+            PerspectiveProjection::DEFAULT_FIELD_OF_VIEW,
+            width as f64,
+            (width as f64 / 2.0, height as f64 / 2.0),
+        )
+    }
+
     /// Get the stage mode.
     /// This controls how the content scales to fill the viewport.
     pub fn scale_mode(self) -> StageScaleMode {
@@ -808,6 +820,8 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
         context.transform_stack.push(&Transform {
             matrix: self.0.read().viewport_matrix,
             color_transform: Default::default(),
+            matrix3d: None,
+            perspective_projection: Some(self.default_perspective_projection()),
         });
 
         // All of our Stage3D instances get rendered *underneath* the main stage.
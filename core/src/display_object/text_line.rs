@@ -0,0 +1,388 @@
+//! `TextLine` display object, laid out by `flash.text.engine`
+
+use crate::avm2::Activation;
+use crate::avm2::Object as Avm2Object;
+use crate::context::RenderContext;
+use crate::context::UpdateContext;
+use crate::display_object::container::ChildContainer;
+use crate::display_object::interactive::{
+    Avm2MousePick, InteractiveObject, InteractiveObjectBase, TInteractiveObject,
+};
+use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
+use crate::events::{ClipEvent, ClipEventResult};
+use crate::font::Glyph;
+use crate::html::{FormatSpans, LaidOutLine, LayoutBox, TextLineLayout};
+use crate::prelude::*;
+use crate::tag_utils::SwfMovie;
+use core::fmt;
+use gc_arena::{Collect, GcCell, GcWeakCell, Mutation};
+use ruffle_render::transform::Transform;
+use std::cell::{Ref, RefMut};
+use std::sync::Arc;
+
+/// A single line of text laid out by a `TextBlock`.
+///
+/// Its origin is on the baseline at the start of the line. The graphics of any
+/// `GraphicElement`s on the line are its children, along with anything a script
+/// adds to it.
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct TextLine<'gc>(GcCell<'gc, TextLineData<'gc>>);
+
+impl fmt::Debug for TextLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextLine")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct TextLineData<'gc> {
+    base: InteractiveObjectBase<'gc>,
+    container: ChildContainer<'gc>,
+    avm2_object: Option<Avm2Object<'gc>>,
+    movie: Arc<SwfMovie>,
+
+    /// The text and formatting of the line.
+    #[collect(require_static)]
+    spans: FormatSpans,
+
+    /// A box for every visible glyph, relative to the top-left of the line.
+    boxes: Vec<LayoutBox<'gc>>,
+
+    /// The graphics of the line's `GraphicElement`s, which were added as children.
+    graphics: Vec<DisplayObject<'gc>>,
+
+    #[collect(require_static)]
+    layout: TextLineLayout,
+}
+
+impl<'gc> TextLine<'gc> {
+    pub fn new(activation: &mut Activation<'_, 'gc>, movie: Arc<SwfMovie>) -> Self {
+        let text_line = TextLine(GcCell::new(
+            activation.context.gc_context,
+            TextLineData {
+                base: Default::default(),
+                container: ChildContainer::new(),
+                avm2_object: None,
+                movie,
+                spans: FormatSpans::new(),
+                boxes: Vec::new(),
+                graphics: Vec::new(),
+                layout: Default::default(),
+            },
+        ));
+
+        text_line.set_placed_by_script(activation.context.gc_context, true);
+        activation.context.avm2.add_orphan_obj(text_line.into());
+        text_line
+    }
+
+    pub fn downgrade(self) -> TextLineWeak<'gc> {
+        TextLineWeak(GcCell::downgrade(self.0))
+    }
+
+    /// The measurements and atoms of the line.
+    pub fn layout(&self) -> Ref<'_, TextLineLayout> {
+        Ref::map(self.0.read(), |line| &line.layout)
+    }
+
+    /// Display a newly laid-out line.
+    ///
+    /// `graphics` holds the graphic of each of the line's `GraphicElement`s,
+    /// which replace the graphics of the previous layout.
+    pub fn set_line(
+        mut self,
+        context: &mut UpdateContext<'_, 'gc>,
+        line: LaidOutLine<'gc>,
+        graphics: Vec<(DisplayObject<'gc>, f64, f64)>,
+    ) {
+        let old_graphics = std::mem::take(&mut self.0.write(context.gc_context).graphics);
+        for graphic in old_graphics {
+            if graphic
+                .parent()
+                .is_some_and(|parent| DisplayObject::ptr_eq(parent, self.into()))
+            {
+                self.remove_child(context, graphic);
+            }
+        }
+
+        let mut write = self.0.write(context.gc_context);
+        write.spans = line.spans;
+        write.boxes = line.boxes;
+        write.layout = line.layout;
+        drop(write);
+
+        for (graphic, x, y) in graphics {
+            // A graphic can only be in one place, so the last element using it wins.
+            if let Some(mut parent) = graphic.parent().and_then(|parent| parent.as_container()) {
+                parent.remove_child(context, graphic);
+            }
+            graphic.set_x(context.gc_context, Twips::from_pixels(x));
+            graphic.set_y(context.gc_context, Twips::from_pixels(y));
+            let index = self.num_children();
+            self.insert_at_index(context, graphic, index);
+            self.0.write(context.gc_context).graphics.push(graphic);
+        }
+
+        self.invalidate_cached_bitmap(context.gc_context);
+    }
+
+    /// Whether a point on the stage is within the bounds of the line's text.
+    fn hit_test_text(self, point: Point<Twips>) -> bool {
+        self.global_to_local(point)
+            .is_some_and(|local| self.self_bounds().contains(local))
+    }
+
+    fn render_layout_box(
+        context: &mut RenderContext<'_, 'gc>,
+        lbox: &LayoutBox<'gc>,
+        spans: &FormatSpans,
+    ) {
+        let Some((text, _tf, font, params, color)) =
+            lbox.as_renderable_text(spans.displayed_text())
+        else {
+            return;
+        };
+
+        let origin = lbox.bounds().origin();
+        context.transform_stack.push(&Transform {
+            matrix: Matrix::translate(origin.x(), origin.y()),
+            ..Default::default()
+        });
+
+        // Glyphs are drawn from their baseline, while the box starts at the top of the line.
+        let mut transform = Transform::default();
+        transform.color_transform.set_mult_color(&color);
+        transform.matrix.ty = font.get_baseline_for_height(params.height()) - params.height();
+        font.evaluate(
+            text,
+            transform,
+            params,
+            |_pos, transform, glyph: &Glyph, _advance, _x| {
+                if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
+                    context.transform_stack.push(transform);
+                    context
+                        .commands
+                        .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                    context.transform_stack.pop();
+                }
+            },
+        );
+
+        context.transform_stack.pop();
+    }
+}
+
+impl<'gc> TDisplayObject<'gc> for TextLine<'gc> {
+    fn base(&self) -> Ref<DisplayObjectBase<'gc>> {
+        Ref::map(self.0.read(), |r| &r.base.base)
+    }
+
+    fn base_mut<'a>(&'a self, mc: &Mutation<'gc>) -> RefMut<'a, DisplayObjectBase<'gc>> {
+        RefMut::map(self.0.write(mc), |w| &mut w.base.base)
+    }
+
+    fn instantiate(&self, gc_context: &Mutation<'gc>) -> DisplayObject<'gc> {
+        Self(GcCell::new(gc_context, self.0.read().clone())).into()
+    }
+
+    fn as_ptr(&self) -> *const DisplayObjectPtr {
+        self.0.as_ptr() as *const DisplayObjectPtr
+    }
+
+    fn id(&self) -> CharacterId {
+        u16::MAX
+    }
+
+    fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
+        let line = self.0.read();
+        context.transform_stack.push(&Transform {
+            matrix: Matrix::translate(Twips::ZERO, -Twips::from_pixels(line.layout.ascent)),
+            ..Default::default()
+        });
+        for lbox in &line.boxes {
+            Self::render_layout_box(context, lbox, &line.spans);
+        }
+        context.transform_stack.pop();
+        drop(line);
+
+        self.render_children(context);
+    }
+
+    fn self_bounds(&self) -> Rectangle<Twips> {
+        let layout = self.layout();
+        Rectangle {
+            x_min: Twips::ZERO,
+            y_min: -Twips::from_pixels(layout.ascent),
+            x_max: Twips::from_pixels(layout.text_width),
+            y_max: Twips::from_pixels(layout.descent),
+        }
+    }
+
+    fn hit_test_shape(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        point: Point<Twips>,
+        options: HitTestOptions,
+    ) -> bool {
+        if options.contains(HitTestOptions::SKIP_INVISIBLE) && !self.visible() {
+            return false;
+        }
+
+        if self.hit_test_text(point) {
+            return true;
+        }
+
+        self.iter_render_list()
+            .any(|child| child.hit_test_shape(context, point, options))
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Null)
+    }
+
+    fn set_object2(&self, context: &mut UpdateContext<'_, 'gc>, to: Avm2Object<'gc>) {
+        self.0.write(context.gc_context).avm2_object = Some(to);
+    }
+
+    fn as_container(self) -> Option<DisplayObjectContainer<'gc>> {
+        Some(self.into())
+    }
+
+    fn as_interactive(self) -> Option<InteractiveObject<'gc>> {
+        Some(self.into())
+    }
+
+    fn as_text_line(self) -> Option<TextLine<'gc>> {
+        Some(self)
+    }
+
+    fn enter_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
+        let skip_frame = self.base().should_skip_next_enter_frame();
+        for child in self.iter_render_list() {
+            // See MovieClip::enter_frame for an explanation of this.
+            if skip_frame {
+                child
+                    .base_mut(context.gc_context)
+                    .set_skip_next_enter_frame(true);
+            }
+            child.enter_frame(context);
+        }
+        self.base_mut(context.gc_context)
+            .set_skip_next_enter_frame(false);
+    }
+
+    fn construct_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
+        for child in self.iter_render_list() {
+            child.construct_frame(context);
+        }
+    }
+
+    fn movie(&self) -> Arc<SwfMovie> {
+        self.0.read().movie.clone()
+    }
+
+    fn set_parent(&self, context: &mut UpdateContext<'_, 'gc>, parent: Option<DisplayObject<'gc>>) {
+        let had_parent = self.parent().is_some();
+        self.base_mut(context.gc_context)
+            .set_parent_ignoring_orphan_list(parent);
+        let has_parent = self.parent().is_some();
+
+        if context.is_action_script_3() && had_parent && !has_parent {
+            context.avm2.add_orphan_obj((*self).into())
+        }
+    }
+}
+
+impl<'gc> TInteractiveObject<'gc> for TextLine<'gc> {
+    fn raw_interactive(&self) -> Ref<InteractiveObjectBase<'gc>> {
+        Ref::map(self.0.read(), |r| &r.base)
+    }
+
+    fn raw_interactive_mut(&self, mc: &Mutation<'gc>) -> RefMut<InteractiveObjectBase<'gc>> {
+        RefMut::map(self.0.write(mc), |w| &mut w.base)
+    }
+
+    fn as_displayobject(self) -> DisplayObject<'gc> {
+        self.into()
+    }
+
+    fn filter_clip_event(
+        self,
+        _context: &mut UpdateContext<'_, 'gc>,
+        _event: ClipEvent,
+    ) -> ClipEventResult {
+        ClipEventResult::NotHandled
+    }
+
+    fn event_dispatch(
+        self,
+        _context: &mut UpdateContext<'_, 'gc>,
+        _event: ClipEvent<'gc>,
+    ) -> ClipEventResult {
+        ClipEventResult::NotHandled
+    }
+
+    fn mouse_pick_avm2(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        point: Point<Twips>,
+        require_button_mode: bool,
+    ) -> Avm2MousePick<'gc> {
+        if !self.visible() {
+            return Avm2MousePick::Miss;
+        }
+
+        for child in self.iter_render_list().rev() {
+            let result = if let Some(int) = child.as_interactive() {
+                int.mouse_pick_avm2(context, point, require_button_mode)
+            } else if child.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK) {
+                Avm2MousePick::Hit((*self).into())
+            } else {
+                Avm2MousePick::Miss
+            };
+
+            if !matches!(result, Avm2MousePick::Miss) {
+                return result.combine_with_parent((*self).into());
+            }
+        }
+
+        // The text itself is hit anywhere within the line's bounds.
+        if self.hit_test_text(point) && self.mouse_enabled() {
+            Avm2MousePick::Hit((*self).into())
+        } else {
+            Avm2MousePick::Miss
+        }
+    }
+}
+
+impl<'gc> TDisplayObjectContainer<'gc> for TextLine<'gc> {
+    fn raw_container(&self) -> Ref<'_, ChildContainer<'gc>> {
+        Ref::map(self.0.read(), |this| &this.container)
+    }
+
+    fn raw_container_mut(&self, gc_context: &Mutation<'gc>) -> RefMut<'_, ChildContainer<'gc>> {
+        RefMut::map(self.0.write(gc_context), |this| &mut this.container)
+    }
+}
+
+#[derive(Clone, Debug, Collect, Copy)]
+#[collect(no_drop)]
+pub struct TextLineWeak<'gc>(GcWeakCell<'gc, TextLineData<'gc>>);
+
+impl<'gc> TextLineWeak<'gc> {
+    pub fn upgrade(self, mc: &Mutation<'gc>) -> Option<TextLine<'gc>> {
+        self.0.upgrade(mc).map(TextLine)
+    }
+
+    pub fn as_ptr(self) -> *const DisplayObjectPtr {
+        self.0.as_ptr() as *const DisplayObjectPtr
+    }
+}
@@ -318,9 +318,10 @@ impl<'gc> Video<'gc> {
         let res = match &*source.read() {
             VideoSource::Swf { streamdef, frames } => match frames.get(&frame_id) {
                 Some((slice_start, slice_end)) => {
+                    let movie_data = read.movie.data();
                     let encframe = EncodedFrame {
                         codec: streamdef.codec,
-                        data: &read.movie.data()[*slice_start..*slice_end],
+                        data: &movie_data[*slice_start..*slice_end],
                         frame_id,
                     };
                     context
@@ -409,13 +410,14 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
                     let stream = stream.unwrap();
                     let mut keyframes = BTreeSet::new();
+                    let movie_data = movie.data();
 
                     for (frame_id, (frame_start, frame_end)) in frames {
                         let dep = context.video.preload_video_stream_frame(
                             stream,
                             EncodedFrame {
                                 codec: streamdef.codec,
-                                data: &movie.data()[*frame_start..*frame_end],
+                                data: &movie_data[*frame_start..*frame_end],
                                 frame_id: *frame_id,
                             },
                         );
@@ -2,6 +2,8 @@ use crate::context::RenderContext;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::scale9::{Scale9Cache, Scale9Mapping};
 use ruffle_render::shape_utils::{
     cubic_curve_bounds, quadratic_curve_bounds, DistilledShape, DrawCommand, DrawPath, FillRule,
 };
@@ -11,6 +13,8 @@ use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
 #[derive(Clone, Debug)]
 pub struct Drawing {
     render_handle: RefCell<Option<ShapeHandle>>,
+    /// The shapes registered for nine-slice scaling, for the most recently used mappings.
+    scale9_handles: RefCell<Scale9Cache<ShapeHandle>>,
     shape_bounds: Rectangle<Twips>,
     edge_bounds: Rectangle<Twips>,
     dirty: Cell<bool>,
@@ -34,6 +38,7 @@ impl Drawing {
     pub fn new() -> Self {
         Self {
             render_handle: RefCell::new(None),
+            scale9_handles: Default::default(),
             shape_bounds: Default::default(),
             edge_bounds: Default::default(),
             dirty: Cell::new(false),
@@ -51,6 +56,7 @@ impl Drawing {
     pub fn from_swf_shape(shape: &swf::Shape) -> Self {
         let mut this = Self {
             render_handle: RefCell::new(None),
+            scale9_handles: Default::default(),
             shape_bounds: shape.shape_bounds.clone(),
             edge_bounds: shape.edge_bounds.clone(),
             dirty: Cell::new(true),
@@ -107,6 +113,7 @@ impl Drawing {
     pub fn copy_from(&mut self, other: &Drawing) {
         *self = Drawing {
             render_handle: RefCell::new(None),
+            scale9_handles: Default::default(),
             dirty: Cell::new(true),
             shape_bounds: other.shape_bounds.clone(),
             edge_bounds: other.edge_bounds.clone(),
@@ -242,72 +249,8 @@ impl Drawing {
     pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> ShapeHandle {
         if self.dirty.get() || self.render_handle.borrow().is_none() {
             self.dirty.set(false);
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                            winding_rule: FillRule::EvenOdd,
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
-                    }
-                }
-            }
-
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                    winding_rule: FillRule::EvenOdd,
-                })
-            }
-
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
-
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
-
-            let shape = DistilledShape {
-                paths,
-                shape_bounds: self.shape_bounds.clone(),
-                edge_bounds: self.edge_bounds.clone(),
-                id: 0,
-            };
-            let handle = renderer.register_shape(shape, self);
+            self.scale9_handles.borrow_mut().clear();
+            let handle = renderer.register_shape(self.distilled_shape(), self);
             self.render_handle.replace(Some(handle.clone()));
             handle
         } else {
@@ -318,6 +261,74 @@ impl Drawing {
         }
     }
 
+    fn distilled_shape(&self) -> DistilledShape<'_> {
+        let mut paths = Vec::with_capacity(self.paths.len());
+
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => {
+                    paths.push(DrawPath::Fill {
+                        style: &fill.style,
+                        commands: fill.commands.to_owned(),
+                        winding_rule: FillRule::EvenOdd,
+                    });
+                }
+                DrawingPath::Line(line) => {
+                    paths.push(DrawPath::Stroke {
+                        style: &line.style,
+                        commands: line.commands.to_owned(),
+                        is_closed: line.is_closed,
+                    });
+                }
+            }
+        }
+
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding_rule: FillRule::EvenOdd,
+            })
+        }
+
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        DistilledShape {
+            paths,
+            shape_bounds: self.shape_bounds.clone(),
+            edge_bounds: self.edge_bounds.clone(),
+            id: 0,
+        }
+    }
+
     pub fn render(&self, context: &mut RenderContext) {
         let handle = self.register_or_replace(context.renderer);
         context
@@ -325,6 +336,32 @@ impl Drawing {
             .render_shape(handle, context.transform_stack.transform());
     }
 
+    /// Renders this drawing distorted by the `scale9Grid` of an ancestor.
+    /// `to_grid` transforms from the space of this drawing into the space of the grid.
+    pub fn render_scale9(
+        &self,
+        context: &mut RenderContext,
+        mapping: &Scale9Mapping,
+        to_grid: &Matrix,
+    ) {
+        // Make sure that the cached shape is thrown away if the drawing changed.
+        self.register_or_replace(context.renderer);
+
+        let handle = self
+            .scale9_handles
+            .borrow_mut()
+            .get_or_insert_with(mapping, to_grid, || {
+                let shape = mapping.map_shape(&self.distilled_shape(), to_grid);
+                Some(context.renderer.register_shape(shape, self))
+            });
+        let Some(handle) = handle else {
+            return;
+        };
+        context
+            .commands
+            .render_shape(handle, context.transform_stack.transform());
+    }
+
     pub fn self_bounds(&self) -> &Rectangle<Twips> {
         &self.shape_bounds
     }
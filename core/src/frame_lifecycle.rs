@@ -11,7 +11,7 @@
 //! runs in one phase, with timeline operations executing with all phases
 //! inline in the order that clips were originally created.
 
-use crate::avm2::Avm2;
+use crate::avm2::{Avm2, EventObject as Avm2EventObject};
 use crate::avm2_stub_method_context;
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
@@ -72,26 +72,26 @@ pub enum FramePhase {
 pub fn run_all_phases_avm2(context: &mut UpdateContext<'_, '_>) {
     let stage = context.stage;
 
-    *context.frame_phase = FramePhase::Enter;
+    context.set_frame_phase(FramePhase::Enter);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.enter_frame(context);
     });
     stage.enter_frame(context);
 
-    *context.frame_phase = FramePhase::Construct;
+    context.set_frame_phase(FramePhase::Construct);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
     stage.frame_constructed(context);
 
-    *context.frame_phase = FramePhase::FrameScripts;
+    context.set_frame_phase(FramePhase::FrameScripts);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.run_frame_scripts(context);
     });
     stage.run_frame_scripts(context);
 
-    *context.frame_phase = FramePhase::Exit;
+    context.set_frame_phase(FramePhase::Exit);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.on_exit_frame(context);
     });
@@ -104,7 +104,62 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_, '_>) {
     // a result of a RemoveObject tag - see `cleanup_dead_orphans` for details.
     Avm2::cleanup_dead_orphans(context);
 
-    *context.frame_phase = FramePhase::Idle;
+    context.set_frame_phase(FramePhase::Idle);
+}
+
+/// Like `run_all_phases_avm2`, but for a background worker.
+///
+/// Workers have no stage, so their root clip is run directly alongside the
+/// worker's orphans, and the broadcast events that the stage would normally
+/// send are sent by the root clip instead.
+#[instrument(level = "debug", skip_all)]
+pub fn run_worker_phases_avm2<'gc>(context: &mut UpdateContext<'_, 'gc>, root: DisplayObject<'gc>) {
+    context.set_frame_phase(FramePhase::Enter);
+    each_worker_orphan(context, root, |orphan, context| {
+        orphan.enter_frame(context);
+    });
+    root.enter_frame(context);
+    let enter_frame_evt = Avm2EventObject::bare_default_event(context, "enterFrame");
+    let dobject_constr = context.avm2.classes().display_object;
+    Avm2::broadcast_event(context, enter_frame_evt, dobject_constr);
+
+    context.set_frame_phase(FramePhase::Construct);
+    each_worker_orphan(context, root, |orphan, context| {
+        orphan.construct_frame(context);
+    });
+    root.construct_frame(context);
+    root.frame_constructed(context);
+
+    context.set_frame_phase(FramePhase::FrameScripts);
+    each_worker_orphan(context, root, |orphan, context| {
+        orphan.run_frame_scripts(context);
+    });
+    root.run_frame_scripts(context);
+
+    context.set_frame_phase(FramePhase::Exit);
+    each_worker_orphan(context, root, |orphan, context| {
+        orphan.on_exit_frame(context);
+    });
+    root.exit_frame(context);
+
+    Avm2::cleanup_dead_orphans(context);
+
+    context.set_frame_phase(FramePhase::Idle);
+}
+
+/// Runs `f` on every orphan of a worker, except for its root clip.
+///
+/// The root clip has no parent, so it ends up on the orphan list once it's constructed.
+fn each_worker_orphan<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    root: DisplayObject<'gc>,
+    mut f: impl FnMut(DisplayObject<'gc>, &mut UpdateContext<'_, 'gc>),
+) {
+    Avm2::each_orphan_obj(context, |orphan, context| {
+        if !DisplayObject::ptr_eq(orphan, root) {
+            f(orphan, context);
+        }
+    });
 }
 
 /// Like `run_all_phases_avm2`, but specialized for the "nested frame" triggered
@@ -147,14 +202,14 @@ pub fn run_inner_goto_frame<'gc>(
 
     // Note - we do *not* call `enter_frame` or dispatch an `enterFrame` event
 
-    *context.frame_phase = FramePhase::Construct;
+    context.set_frame_phase(FramePhase::Construct);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
     stage.frame_constructed(context);
 
-    *context.frame_phase = FramePhase::FrameScripts;
+    context.set_frame_phase(FramePhase::FrameScripts);
     stage.run_frame_scripts(context);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.run_frame_scripts(context);
@@ -164,7 +219,7 @@ pub fn run_inner_goto_frame<'gc>(
         child.run_frame_scripts(context);
     }
 
-    *context.frame_phase = FramePhase::Exit;
+    context.set_frame_phase(FramePhase::Exit);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.on_exit_frame(context);
     });
@@ -177,7 +232,7 @@ pub fn run_inner_goto_frame<'gc>(
     // a result of a RemoveObject tag - see `cleanup_dead_orphans` for details.
     Avm2::cleanup_dead_orphans(context);
 
-    *context.frame_phase = old_phase;
+    context.set_frame_phase(old_phase);
 }
 
 /// Run all previously-executed frame phases on a newly-constructed display
@@ -0,0 +1,120 @@
+//! Locale data and locale-aware formatting, as used by `flash.globalization`
+
+mod collator;
+mod date_time;
+mod locale_data;
+mod locale_id;
+mod number;
+
+pub use collator::{CollatorMode, CollatorOptions};
+pub use date_time::{
+    format_date_time, is_valid_pattern, month_names, pattern_for_styles, weekday_names,
+    DateTimeNameContext, DateTimeNameStyle, DateTimeStyle,
+};
+pub use locale_data::{LocaleData, LOCALES};
+pub use locale_id::{determine_preferred_locales, LocaleId};
+pub use number::{is_valid_grouping_pattern, CurrencyFormat, NumberFormat, ParsedNumber};
+
+#[cfg(test)]
+mod test;
+
+/// The name of the locale that stands for the user's default locale.
+pub const DEFAULT_LOCALE_ID: &str = "i-default";
+
+/// The outcome of the most recent operation of a `flash.globalization` object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LastOperationStatus {
+    NoError,
+    IllegalArgumentError,
+    IndexOutOfBoundsError,
+    ParseError,
+    PatternSyntaxError,
+    UsingDefaultWarning,
+    UsingFallbackWarning,
+}
+
+impl LastOperationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LastOperationStatus::NoError => "noError",
+            LastOperationStatus::IllegalArgumentError => "illegalArgumentError",
+            LastOperationStatus::IndexOutOfBoundsError => "indexOutOfBoundsError",
+            LastOperationStatus::ParseError => "parseError",
+            LastOperationStatus::PatternSyntaxError => "patternSyntaxError",
+            LastOperationStatus::UsingDefaultWarning => "usingDefaultWarning",
+            LastOperationStatus::UsingFallbackWarning => "usingFallbackWarning",
+        }
+    }
+}
+
+/// Find the locale data to use for a requested locale ID.
+///
+/// `i-default` resolves to `default_locale`, the language of the player. A
+/// locale that isn't available falls back to another region of the same
+/// language, then to the default locale, and finally to `en-US`.
+pub fn resolve_locale(
+    requested: &str,
+    default_locale: &str,
+) -> (&'static LocaleData, LastOperationStatus) {
+    if requested.eq_ignore_ascii_case(DEFAULT_LOCALE_ID) {
+        let (locale, _) = resolve_locale(default_locale, "en-US");
+        return (locale, LastOperationStatus::UsingDefaultWarning);
+    }
+
+    let requested = LocaleId::parse(requested);
+    let exact = LOCALES.iter().find(|locale| {
+        let available = LocaleId::parse(locale.name);
+        available.language() == requested.language()
+            && available.region() == requested.region()
+            && (requested.explicit_script().is_empty()
+                || requested.explicit_script() == available.script())
+    });
+    if let Some(locale) = exact {
+        return (locale, LastOperationStatus::NoError);
+    }
+
+    let same_language = LOCALES
+        .iter()
+        .find(|locale| LocaleId::parse(locale.name).language() == requested.language());
+    let fallback = same_language.unwrap_or_else(|| {
+        let default_language = LocaleId::parse(default_locale);
+        LOCALES
+            .iter()
+            .find(|locale| locale.name.eq_ignore_ascii_case(default_language.name()))
+            .or_else(|| {
+                LOCALES.iter().find(|locale| {
+                    LocaleId::parse(locale.name).language() == default_language.language()
+                })
+            })
+            .unwrap_or(&LOCALES[0])
+    });
+    (fallback, LastOperationStatus::UsingFallbackWarning)
+}
+
+/// Replace the ASCII digits of `text` with the digits starting at `digits_type`,
+/// one of the `NationalDigitsType` values.
+pub fn localize_digits(text: &str, digits_type: u32) -> String {
+    text.chars()
+        .map(|c| match c.to_digit(10) {
+            Some(digit) => char::from_u32(digits_type + digit).unwrap_or(c),
+            None => c,
+        })
+        .collect()
+}
+
+/// Get the value of a decimal digit, in ASCII or in any of the national digit sets.
+fn digit_value(c: char) -> Option<u32> {
+    if let Some(digit) = c.to_digit(10) {
+        return Some(digit);
+    }
+    const ZEROS: &[u32] = &[
+        0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+        0x0D66, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1B50,
+        0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xAA50, 0xFF10, 0x104A0,
+    ];
+    let c = u32::from(c);
+    ZEROS
+        .iter()
+        .find(|&&zero| (zero..zero + 10).contains(&c))
+        .map(|zero| c - zero)
+}
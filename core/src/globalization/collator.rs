@@ -0,0 +1,211 @@
+//! Locale-sensitive string comparison, as used by `Collator`
+
+use crate::globalization::{digit_value, LocaleData};
+use std::cmp::Ordering;
+
+/// A `CollatorMode` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollatorMode {
+    Sorting,
+    Matching,
+}
+
+impl CollatorMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "sorting" => Some(Self::Sorting),
+            "matching" => Some(Self::Matching),
+            _ => None,
+        }
+    }
+}
+
+/// The comparison options of a `Collator`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CollatorOptions {
+    pub ignore_case: bool,
+    pub ignore_character_width: bool,
+    pub ignore_diacritics: bool,
+    pub ignore_kana_type: bool,
+    pub ignore_symbols: bool,
+    pub numeric_comparison: bool,
+}
+
+/// Base letters of the precomposed Latin letters with diacritics.
+const DIACRITICS: &[(char, &str)] = &[
+    ('a', "àáâãäåāăą"),
+    ('c', "çćĉċč"),
+    ('d', "ď"),
+    ('e', "èéêëēĕėęě"),
+    ('g', "ĝğġģ"),
+    ('h', "ĥ"),
+    ('i', "ìíîïĩīĭįı"),
+    ('j', "ĵ"),
+    ('k', "ķ"),
+    ('l', "ĺļľŀł"),
+    ('n', "ñńņňŉ"),
+    ('o', "òóôõöøōŏő"),
+    ('r', "ŕŗř"),
+    ('s', "śŝşš"),
+    ('t', "ţťŧ"),
+    ('u', "ùúûüũūŭůűų"),
+    ('w', "ŵ"),
+    ('y', "ýÿŷ"),
+    ('z', "źżž"),
+];
+
+/// Primary weights of the classes of characters. Symbols sort before digits,
+/// and digits before letters.
+const SYMBOL_WEIGHT: u64 = 0;
+const DIGIT_WEIGHT: u64 = 1 << 40;
+const LETTER_WEIGHT: u64 = 2 << 40;
+
+/// The collation weights of one character or number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Weights {
+    /// The base letter.
+    primary: u64,
+    /// The diacritic, or 0 for none.
+    secondary: u32,
+    /// The case and width; lowercase sorts first.
+    tertiary: u32,
+}
+
+impl CollatorOptions {
+    pub fn for_mode(mode: CollatorMode) -> Self {
+        match mode {
+            CollatorMode::Sorting => Self::default(),
+            CollatorMode::Matching => Self {
+                ignore_case: true,
+                ignore_character_width: true,
+                ignore_diacritics: true,
+                ignore_kana_type: true,
+                ignore_symbols: false,
+                numeric_comparison: false,
+            },
+        }
+    }
+
+    /// Compare two strings using the conventions of a locale.
+    ///
+    /// Like ICU, strings are first compared by their base letters; differences in
+    /// diacritics, then in case and width, only matter if the base letters are equal.
+    pub fn compare(&self, locale: &LocaleData, a: &str, b: &str) -> Ordering {
+        let a = self.weights(locale, a);
+        let b = self.weights(locale, b);
+
+        let primary = |w: &[Weights]| w.iter().map(|w| w.primary).collect::<Vec<_>>();
+        let secondary = |w: &[Weights]| w.iter().map(|w| w.secondary).collect::<Vec<_>>();
+        let tertiary = |w: &[Weights]| w.iter().map(|w| w.tertiary).collect::<Vec<_>>();
+
+        primary(&a)
+            .cmp(&primary(&b))
+            .then_with(|| {
+                if self.ignore_diacritics {
+                    Ordering::Equal
+                } else {
+                    secondary(&a).cmp(&secondary(&b))
+                }
+            })
+            .then_with(|| tertiary(&a).cmp(&tertiary(&b)))
+    }
+
+    fn weights(&self, locale: &LocaleData, text: &str) -> Vec<Weights> {
+        let mut weights = Vec::new();
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if self.numeric_comparison {
+                if let Some(digit) = digit_value(c) {
+                    // A run of digits sorts as one number.
+                    let mut value = u64::from(digit);
+                    while let Some(digit) = chars.peek().and_then(|c| digit_value(*c)) {
+                        value = value.saturating_mul(10).saturating_add(u64::from(digit));
+                        chars.next();
+                    }
+                    weights.push(Weights {
+                        primary: DIGIT_WEIGHT + value.min(DIGIT_WEIGHT - 1),
+                        secondary: 0,
+                        tertiary: 0,
+                    });
+                    continue;
+                }
+            }
+
+            let mut tertiary = 0;
+            let mut c = c;
+            if let Some(narrow) = narrow_width(c) {
+                c = narrow;
+                if !self.ignore_character_width {
+                    tertiary |= 2;
+                }
+            }
+            if let Some(hiragana) = katakana_to_hiragana(c) {
+                c = hiragana;
+                if !self.ignore_kana_type {
+                    tertiary |= 4;
+                }
+            }
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            if lower != c && !self.ignore_case {
+                tertiary |= 1;
+            }
+
+            let (primary, secondary) = if let Some(digit) = digit_value(lower) {
+                (DIGIT_WEIGHT + u64::from(digit), 0)
+            } else if lower.is_alphabetic() {
+                letter_weights(locale, lower)
+            } else if self.ignore_symbols {
+                continue;
+            } else {
+                (SYMBOL_WEIGHT + u64::from(u32::from(lower)), 0)
+            };
+            weights.push(Weights {
+                primary,
+                secondary,
+                tertiary,
+            });
+        }
+
+        weights
+    }
+}
+
+/// Get the primary and secondary weights of a lowercase letter.
+fn letter_weights(locale: &LocaleData, letter: char) -> (u64, u32) {
+    // Letters tailored by the locale sort as separate letters after their base letter.
+    if let Some((_, after, rank)) = locale
+        .collation_tailoring
+        .iter()
+        .find(|(tailored, _, _)| *tailored == letter)
+    {
+        return (letter_weight(*after) + u64::from(*rank), 0);
+    }
+
+    for (base, letters) in DIACRITICS {
+        if let Some(index) = letters.chars().position(|c| c == letter) {
+            return (letter_weight(*base), index as u32 + 1);
+        }
+    }
+    (letter_weight(letter), 0)
+}
+
+fn letter_weight(letter: char) -> u64 {
+    LETTER_WEIGHT + (u64::from(u32::from(letter)) << 4)
+}
+
+/// Convert a full-width ASCII character to its normal width.
+fn narrow_width(c: char) -> Option<char> {
+    match u32::from(c) {
+        c @ 0xFF01..=0xFF5E => char::from_u32(c - 0xFEE0),
+        0x3000 => Some(' '),
+        _ => None,
+    }
+}
+
+fn katakana_to_hiragana(c: char) -> Option<char> {
+    match u32::from(c) {
+        c @ 0x30A1..=0x30F6 => char::from_u32(c - 0x60),
+        _ => None,
+    }
+}
@@ -0,0 +1,300 @@
+//! Date and time patterns, as used by `DateTimeFormatter`
+
+use crate::globalization::{localize_digits, LocaleData};
+use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+
+/// A `DateTimeStyle` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeStyle {
+    Long,
+    Medium,
+    Short,
+    None,
+    Custom,
+}
+
+impl DateTimeStyle {
+    pub fn parse(style: &str) -> Option<Self> {
+        match style {
+            "long" => Some(Self::Long),
+            "medium" => Some(Self::Medium),
+            "short" => Some(Self::Short),
+            "none" => Some(Self::None),
+            "custom" => Some(Self::Custom),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Long => "long",
+            Self::Medium => "medium",
+            Self::Short => "short",
+            Self::None => "none",
+            Self::Custom => "custom",
+        }
+    }
+
+    fn index(self) -> Option<usize> {
+        match self {
+            Self::Long => Some(0),
+            Self::Medium => Some(1),
+            Self::Short => Some(2),
+            Self::None | Self::Custom => None,
+        }
+    }
+}
+
+/// A `DateTimeNameStyle` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeNameStyle {
+    Full,
+    LongAbbreviation,
+    ShortAbbreviation,
+}
+
+impl DateTimeNameStyle {
+    pub fn parse(style: &str) -> Option<Self> {
+        match style {
+            "full" => Some(Self::Full),
+            "longAbbreviation" => Some(Self::LongAbbreviation),
+            "shortAbbreviation" => Some(Self::ShortAbbreviation),
+            _ => None,
+        }
+    }
+}
+
+/// A `DateTimeNameContext` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeNameContext {
+    Format,
+    Standalone,
+}
+
+impl DateTimeNameContext {
+    pub fn parse(context: &str) -> Option<Self> {
+        match context {
+            "format" => Some(Self::Format),
+            "standalone" => Some(Self::Standalone),
+            _ => None,
+        }
+    }
+}
+
+/// Get the pattern of a locale for a combination of date and time styles.
+///
+/// Neither style may be `Custom`.
+pub fn pattern_for_styles(
+    locale: &LocaleData,
+    date_style: DateTimeStyle,
+    time_style: DateTimeStyle,
+) -> String {
+    let date = date_style.index().map(|i| locale.date_patterns[i]);
+    let time = time_style.index().map(|i| locale.time_patterns[i]);
+    match (date, time) {
+        (Some(date), Some(time)) => format!("{date} {time}"),
+        (Some(pattern), None) | (None, Some(pattern)) => pattern.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Abbreviate a name to its first letter, for `DateTimeNameStyle.SHORT_ABBREVIATION`.
+fn narrow_name(name: &str) -> String {
+    name.chars().take(1).collect()
+}
+
+pub fn month_names(
+    locale: &LocaleData,
+    style: DateTimeNameStyle,
+    context: DateTimeNameContext,
+) -> Vec<String> {
+    let full = match context {
+        DateTimeNameContext::Format => locale.months,
+        DateTimeNameContext::Standalone => locale.standalone_months,
+    };
+    match style {
+        DateTimeNameStyle::Full => full.iter().map(|name| name.to_string()).collect(),
+        DateTimeNameStyle::LongAbbreviation => locale
+            .short_months
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        DateTimeNameStyle::ShortAbbreviation => full.iter().map(|name| narrow_name(name)).collect(),
+    }
+}
+
+pub fn weekday_names(locale: &LocaleData, style: DateTimeNameStyle) -> Vec<String> {
+    match style {
+        DateTimeNameStyle::Full => locale
+            .weekdays
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        DateTimeNameStyle::LongAbbreviation => locale
+            .short_weekdays
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        DateTimeNameStyle::ShortAbbreviation => locale
+            .weekdays
+            .iter()
+            .map(|name| narrow_name(name))
+            .collect(),
+    }
+}
+
+/// A piece of a date/time pattern.
+enum PatternPart<'a> {
+    /// A pattern letter repeated a number of times, e.g. `yyyy`.
+    Field(char, usize),
+    Literal(&'a str),
+    QuotedLiteral(String),
+}
+
+/// Split a pattern into fields and literals. Returns `None` if a quote is unterminated.
+fn parse_pattern(pattern: &str) -> Option<Vec<PatternPart<'_>>> {
+    let mut parts = Vec::new();
+    let mut chars = pattern.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c == '\'' {
+            if chars.next_if(|(_, c)| *c == '\'').is_some() {
+                parts.push(PatternPart::Literal("'"));
+                continue;
+            }
+            let mut literal = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '\'')) if chars.next_if(|(_, c)| *c == '\'').is_some() => {
+                        literal.push('\'')
+                    }
+                    Some((_, '\'')) => break,
+                    Some((_, c)) => literal.push(c),
+                    None => return None,
+                }
+            }
+            parts.push(PatternPart::QuotedLiteral(literal));
+        } else if c.is_ascii_alphabetic() {
+            let mut count = 1;
+            while chars.next_if(|(_, next)| *next == c).is_some() {
+                count += 1;
+            }
+            parts.push(PatternPart::Field(c, count));
+        } else {
+            let mut end = start + c.len_utf8();
+            while let Some((index, next)) =
+                chars.next_if(|(_, next)| *next != '\'' && !next.is_ascii_alphabetic())
+            {
+                end = index + next.len_utf8();
+            }
+            parts.push(PatternPart::Literal(&pattern[start..end]));
+        }
+    }
+
+    Some(parts)
+}
+
+/// Whether `pattern` is a valid date/time pattern.
+pub fn is_valid_pattern(pattern: &str) -> bool {
+    parse_pattern(pattern).is_some()
+}
+
+/// Format a date with a pattern, using the names and digits of a locale.
+///
+/// Returns `None` if the pattern is invalid.
+pub fn format_date_time(
+    pattern: &str,
+    locale: &LocaleData,
+    date_time: &DateTime<FixedOffset>,
+) -> Option<String> {
+    let mut result = String::new();
+
+    for part in parse_pattern(pattern)? {
+        let (letter, count) = match part {
+            PatternPart::Literal(literal) => {
+                result.push_str(literal);
+                continue;
+            }
+            PatternPart::QuotedLiteral(literal) => {
+                result.push_str(&literal);
+                continue;
+            }
+            PatternPart::Field(letter, count) => (letter, count),
+        };
+
+        let number = |value: u32| localize_digits(&format!("{value:0count$}"), locale.digits_type);
+        let month = date_time.month0() as usize;
+        let weekday = date_time.weekday().num_days_from_sunday() as usize;
+        let field = match letter {
+            'G' => {
+                if date_time.year() > 0 {
+                    "AD".to_string()
+                } else {
+                    "BC".to_string()
+                }
+            }
+            'y' if count == 2 => number(date_time.year().unsigned_abs() % 100),
+            'y' => number(date_time.year().unsigned_abs()),
+            'Q' => number(date_time.month0() / 3 + 1),
+            'M' | 'L' => {
+                let names = if letter == 'M' {
+                    locale.months
+                } else {
+                    locale.standalone_months
+                };
+                match count {
+                    1 | 2 => number(date_time.month()),
+                    3 => locale.short_months[month].to_string(),
+                    4 => names[month].to_string(),
+                    _ => narrow_name(names[month]),
+                }
+            }
+            'w' => number(date_time.iso_week().week()),
+            'd' => number(date_time.day()),
+            'D' => number(date_time.ordinal()),
+            'E' => match count {
+                1..=3 => locale.short_weekdays[weekday].to_string(),
+                4 => locale.weekdays[weekday].to_string(),
+                _ => narrow_name(locale.weekdays[weekday]),
+            },
+            'a' => locale.am_pm[(date_time.hour() >= 12) as usize].to_string(),
+            'h' => number(match date_time.hour() % 12 {
+                0 => 12,
+                hour => hour,
+            }),
+            'H' => number(date_time.hour()),
+            'K' => number(date_time.hour() % 12),
+            'k' => number(match date_time.hour() {
+                0 => 24,
+                hour => hour,
+            }),
+            'm' => number(date_time.minute()),
+            's' => number(date_time.second()),
+            'S' => {
+                // Fractional seconds, truncated or padded to the field width.
+                let millis = format!("{:03}", date_time.timestamp_subsec_millis().min(999));
+                let digits: String = millis
+                    .chars()
+                    .chain(std::iter::repeat('0'))
+                    .take(count)
+                    .collect();
+                localize_digits(&digits, locale.digits_type)
+            }
+            'z' | 'Z' | 'v' => {
+                let offset = date_time.offset().local_minus_utc() / 60;
+                let sign = if offset < 0 { '-' } else { '+' };
+                let (hours, minutes) = (offset.abs() / 60, offset.abs() % 60);
+                if letter == 'Z' {
+                    format!("{sign}{hours:02}{minutes:02}")
+                } else {
+                    format!("GMT{sign}{hours:02}:{minutes:02}")
+                }
+            }
+            // Unknown pattern letters are copied as they are.
+            letter => letter.to_string().repeat(count),
+        };
+        result.push_str(&field);
+    }
+
+    Some(result)
+}
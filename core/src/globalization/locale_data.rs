@@ -0,0 +1,681 @@
+//! Built-in locale data, following the conventions of the Unicode CLDR
+
+/// The conventions of a single locale.
+///
+/// Weekday lists start with Sunday. Date and time patterns are ordered long,
+/// medium, short, and use the pattern syntax of `DateTimeFormatter`.
+#[derive(Debug)]
+pub struct LocaleData {
+    pub name: &'static str,
+
+    /// The first digit of the digit set, one of the `NationalDigitsType` values.
+    pub digits_type: u32,
+    pub decimal_separator: &'static str,
+    pub grouping_separator: &'static str,
+    pub grouping_pattern: &'static str,
+    pub negative_symbol: &'static str,
+    pub negative_number_format: u32,
+
+    pub currency_iso_code: &'static str,
+    pub currency_symbol: &'static str,
+    pub currency_fractional_digits: i32,
+    pub positive_currency_format: u32,
+    pub negative_currency_format: u32,
+
+    pub date_patterns: [&'static str; 3],
+    pub time_patterns: [&'static str; 3],
+
+    /// Month names as used in dates, which are inflected in some languages.
+    pub months: &'static [&'static str; 12],
+    /// Month names as used on their own, e.g. in calendar headers.
+    pub standalone_months: &'static [&'static str; 12],
+    pub short_months: &'static [&'static str; 12],
+    pub weekdays: &'static [&'static str; 7],
+    pub short_weekdays: &'static [&'static str; 7],
+    pub am_pm: [&'static str; 2],
+
+    /// The first day of the week, where 0 is Sunday.
+    pub first_weekday: u32,
+
+    /// Letters that sort as separate letters after another letter, with their rank
+    /// among the letters sorting after the same letter.
+    pub collation_tailoring: &'static [(char, char, u32)],
+}
+
+const EUROPEAN: u32 = 0x0030;
+const ARABIC_INDIC: u32 = 0x0660;
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const EN_SHORT_MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const EN_WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const EN_SHORT_WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const DE_MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+const DE_SHORT_MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+];
+const DE_WEEKDAYS: [&str; 7] = [
+    "Sonntag",
+    "Montag",
+    "Dienstag",
+    "Mittwoch",
+    "Donnerstag",
+    "Freitag",
+    "Samstag",
+];
+const DE_SHORT_WEEKDAYS: [&str; 7] = ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"];
+
+const FR_MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+const FR_SHORT_MONTHS: [&str; 12] = [
+    "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+    "déc.",
+];
+const FR_WEEKDAYS: [&str; 7] = [
+    "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+];
+const FR_SHORT_WEEKDAYS: [&str; 7] = ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."];
+
+const ES_MONTHS: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+const ES_SHORT_MONTHS: [&str; 12] = [
+    "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+];
+const ES_WEEKDAYS: [&str; 7] = [
+    "domingo",
+    "lunes",
+    "martes",
+    "miércoles",
+    "jueves",
+    "viernes",
+    "sábado",
+];
+const ES_SHORT_WEEKDAYS: [&str; 7] = ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"];
+
+const IT_MONTHS: [&str; 12] = [
+    "gennaio",
+    "febbraio",
+    "marzo",
+    "aprile",
+    "maggio",
+    "giugno",
+    "luglio",
+    "agosto",
+    "settembre",
+    "ottobre",
+    "novembre",
+    "dicembre",
+];
+const IT_SHORT_MONTHS: [&str; 12] = [
+    "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+];
+const IT_WEEKDAYS: [&str; 7] = [
+    "domenica",
+    "lunedì",
+    "martedì",
+    "mercoledì",
+    "giovedì",
+    "venerdì",
+    "sabato",
+];
+const IT_SHORT_WEEKDAYS: [&str; 7] = ["dom", "lun", "mar", "mer", "gio", "ven", "sab"];
+
+const PT_MONTHS: [&str; 12] = [
+    "janeiro",
+    "fevereiro",
+    "março",
+    "abril",
+    "maio",
+    "junho",
+    "julho",
+    "agosto",
+    "setembro",
+    "outubro",
+    "novembro",
+    "dezembro",
+];
+const PT_SHORT_MONTHS: [&str; 12] = [
+    "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+];
+const PT_WEEKDAYS: [&str; 7] = [
+    "domingo",
+    "segunda-feira",
+    "terça-feira",
+    "quarta-feira",
+    "quinta-feira",
+    "sexta-feira",
+    "sábado",
+];
+const PT_SHORT_WEEKDAYS: [&str; 7] = ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"];
+
+const RU_MONTHS: [&str; 12] = [
+    "января",
+    "февраля",
+    "марта",
+    "апреля",
+    "мая",
+    "июня",
+    "июля",
+    "августа",
+    "сентября",
+    "октября",
+    "ноября",
+    "декабря",
+];
+const RU_STANDALONE_MONTHS: [&str; 12] = [
+    "январь",
+    "февраль",
+    "март",
+    "апрель",
+    "май",
+    "июнь",
+    "июль",
+    "август",
+    "сентябрь",
+    "октябрь",
+    "ноябрь",
+    "декабрь",
+];
+const RU_SHORT_MONTHS: [&str; 12] = [
+    "янв.",
+    "февр.",
+    "мар.",
+    "апр.",
+    "мая",
+    "июн.",
+    "июл.",
+    "авг.",
+    "сент.",
+    "окт.",
+    "нояб.",
+    "дек.",
+];
+const RU_WEEKDAYS: [&str; 7] = [
+    "воскресенье",
+    "понедельник",
+    "вторник",
+    "среда",
+    "четверг",
+    "пятница",
+    "суббота",
+];
+const RU_SHORT_WEEKDAYS: [&str; 7] = ["вс", "пн", "вт", "ср", "чт", "пт", "сб"];
+
+const SV_MONTHS: [&str; 12] = [
+    "januari",
+    "februari",
+    "mars",
+    "april",
+    "maj",
+    "juni",
+    "juli",
+    "augusti",
+    "september",
+    "oktober",
+    "november",
+    "december",
+];
+const SV_SHORT_MONTHS: [&str; 12] = [
+    "jan.", "feb.", "mars", "apr.", "maj", "juni", "juli", "aug.", "sep.", "okt.", "nov.", "dec.",
+];
+const SV_WEEKDAYS: [&str; 7] = [
+    "söndag", "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag",
+];
+const SV_SHORT_WEEKDAYS: [&str; 7] = ["sön", "mån", "tis", "ons", "tors", "fre", "lör"];
+
+const JA_MONTHS: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+const JA_WEEKDAYS: [&str; 7] = [
+    "日曜日",
+    "月曜日",
+    "火曜日",
+    "水曜日",
+    "木曜日",
+    "金曜日",
+    "土曜日",
+];
+const JA_SHORT_WEEKDAYS: [&str; 7] = ["日", "月", "火", "水", "木", "金", "土"];
+
+const ZH_STANDALONE_MONTHS: [&str; 12] = [
+    "一月",
+    "二月",
+    "三月",
+    "四月",
+    "五月",
+    "六月",
+    "七月",
+    "八月",
+    "九月",
+    "十月",
+    "十一月",
+    "十二月",
+];
+const ZH_WEEKDAYS: [&str; 7] = [
+    "星期日",
+    "星期一",
+    "星期二",
+    "星期三",
+    "星期四",
+    "星期五",
+    "星期六",
+];
+const ZH_SHORT_WEEKDAYS: [&str; 7] = ["周日", "周一", "周二", "周三", "周四", "周五", "周六"];
+
+const KO_MONTHS: [&str; 12] = [
+    "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+];
+const KO_WEEKDAYS: [&str; 7] = [
+    "일요일",
+    "월요일",
+    "화요일",
+    "수요일",
+    "목요일",
+    "금요일",
+    "토요일",
+];
+const KO_SHORT_WEEKDAYS: [&str; 7] = ["일", "월", "화", "수", "목", "금", "토"];
+
+const AR_MONTHS: [&str; 12] = [
+    "يناير",
+    "فبراير",
+    "مارس",
+    "أبريل",
+    "مايو",
+    "يونيو",
+    "يوليو",
+    "أغسطس",
+    "سبتمبر",
+    "أكتوبر",
+    "نوفمبر",
+    "ديسمبر",
+];
+const AR_WEEKDAYS: [&str; 7] = [
+    "الأحد",
+    "الاثنين",
+    "الثلاثاء",
+    "الأربعاء",
+    "الخميس",
+    "الجمعة",
+    "السبت",
+];
+
+/// All available locales. The first one is used when nothing else matches.
+pub static LOCALES: &[LocaleData] = &[
+    LocaleData {
+        name: "en-US",
+        digits_type: EUROPEAN,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "USD",
+        currency_symbol: "$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        date_patterns: ["EEEE, MMMM d, yyyy", "MMM d, yyyy", "M/d/yyyy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+        months: &EN_MONTHS,
+        standalone_months: &EN_MONTHS,
+        short_months: &EN_SHORT_MONTHS,
+        weekdays: &EN_WEEKDAYS,
+        short_weekdays: &EN_SHORT_WEEKDAYS,
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "en-GB",
+        digits_type: EUROPEAN,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "GBP",
+        currency_symbol: "£",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        months: &EN_MONTHS,
+        standalone_months: &EN_MONTHS,
+        short_months: &EN_SHORT_MONTHS,
+        weekdays: &EN_WEEKDAYS,
+        short_weekdays: &EN_SHORT_WEEKDAYS,
+        am_pm: ["am", "pm"],
+        first_weekday: 1,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "de-DE",
+        digits_type: EUROPEAN,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        date_patterns: ["EEEE, d. MMMM yyyy", "dd.MM.yyyy", "dd.MM.yy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        months: &DE_MONTHS,
+        standalone_months: &DE_MONTHS,
+        short_months: &DE_SHORT_MONTHS,
+        weekdays: &DE_WEEKDAYS,
+        short_weekdays: &DE_SHORT_WEEKDAYS,
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "fr-FR",
+        digits_type: EUROPEAN,
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        months: &FR_MONTHS,
+        standalone_months: &FR_MONTHS,
+        short_months: &FR_SHORT_MONTHS,
+        weekdays: &FR_WEEKDAYS,
+        short_weekdays: &FR_SHORT_WEEKDAYS,
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "es-ES",
+        digits_type: EUROPEAN,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        date_patterns: ["EEEE, d 'de' MMMM 'de' yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+        months: &ES_MONTHS,
+        standalone_months: &ES_MONTHS,
+        short_months: &ES_SHORT_MONTHS,
+        weekdays: &ES_WEEKDAYS,
+        short_weekdays: &ES_SHORT_WEEKDAYS,
+        am_pm: ["a. m.", "p. m."],
+        first_weekday: 1,
+        collation_tailoring: &[('ñ', 'n', 1)],
+    },
+    LocaleData {
+        name: "it-IT",
+        digits_type: EUROPEAN,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        months: &IT_MONTHS,
+        standalone_months: &IT_MONTHS,
+        short_months: &IT_SHORT_MONTHS,
+        weekdays: &IT_WEEKDAYS,
+        short_weekdays: &IT_SHORT_WEEKDAYS,
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "pt-BR",
+        digits_type: EUROPEAN,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "BRL",
+        currency_symbol: "R$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 9,
+        date_patterns: [
+            "EEEE, d 'de' MMMM 'de' yyyy",
+            "d 'de' MMM 'de' yyyy",
+            "dd/MM/yyyy",
+        ],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        months: &PT_MONTHS,
+        standalone_months: &PT_MONTHS,
+        short_months: &PT_SHORT_MONTHS,
+        weekdays: &PT_WEEKDAYS,
+        short_weekdays: &PT_SHORT_WEEKDAYS,
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "ru-RU",
+        digits_type: EUROPEAN,
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "RUB",
+        currency_symbol: "₽",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        date_patterns: ["EEEE, d MMMM yyyy 'г'.", "d MMM yyyy 'г'.", "dd.MM.yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        months: &RU_MONTHS,
+        standalone_months: &RU_STANDALONE_MONTHS,
+        short_months: &RU_SHORT_MONTHS,
+        weekdays: &RU_WEEKDAYS,
+        short_weekdays: &RU_SHORT_WEEKDAYS,
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "sv-SE",
+        digits_type: EUROPEAN,
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "SEK",
+        currency_symbol: "kr",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "yyyy-MM-dd"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        months: &SV_MONTHS,
+        standalone_months: &SV_MONTHS,
+        short_months: &SV_SHORT_MONTHS,
+        weekdays: &SV_WEEKDAYS,
+        short_weekdays: &SV_SHORT_WEEKDAYS,
+        am_pm: ["fm", "em"],
+        first_weekday: 1,
+        collation_tailoring: &[('å', 'z', 1), ('ä', 'z', 2), ('ö', 'z', 3)],
+    },
+    LocaleData {
+        name: "ja-JP",
+        digits_type: EUROPEAN,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "JPY",
+        currency_symbol: "¥",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        date_patterns: ["yyyy'年'M'月'd'日' EEEE", "yyyy/MM/dd", "yyyy/MM/dd"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+        months: &JA_MONTHS,
+        standalone_months: &JA_MONTHS,
+        short_months: &JA_MONTHS,
+        weekdays: &JA_WEEKDAYS,
+        short_weekdays: &JA_SHORT_WEEKDAYS,
+        am_pm: ["午前", "午後"],
+        first_weekday: 0,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "zh-CN",
+        digits_type: EUROPEAN,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "CNY",
+        currency_symbol: "¥",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 2,
+        date_patterns: ["yyyy'年'M'月'd'日' EEEE", "yyyy'年'M'月'd'日'", "yyyy/M/d"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        months: &JA_MONTHS,
+        standalone_months: &ZH_STANDALONE_MONTHS,
+        short_months: &JA_MONTHS,
+        weekdays: &ZH_WEEKDAYS,
+        short_weekdays: &ZH_SHORT_WEEKDAYS,
+        am_pm: ["上午", "下午"],
+        first_weekday: 1,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "ko-KR",
+        digits_type: EUROPEAN,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "KRW",
+        currency_symbol: "₩",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        date_patterns: ["yyyy'년' M'월' d'일' EEEE", "yyyy. M. d.", "yy. M. d."],
+        time_patterns: ["a h:mm:ss", "a h:mm:ss", "a h:mm"],
+        months: &KO_MONTHS,
+        standalone_months: &KO_MONTHS,
+        short_months: &KO_MONTHS,
+        weekdays: &KO_WEEKDAYS,
+        short_weekdays: &KO_SHORT_WEEKDAYS,
+        am_pm: ["오전", "오후"],
+        first_weekday: 0,
+        collation_tailoring: &[],
+    },
+    LocaleData {
+        name: "ar-SA",
+        digits_type: ARABIC_INDIC,
+        decimal_separator: "\u{66b}",
+        grouping_separator: "\u{66c}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "SAR",
+        currency_symbol: "ر.س.\u{200f}",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        date_patterns: ["EEEE، d MMMM yyyy", "dd/MM/yyyy", "d/M/yyyy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+        months: &AR_MONTHS,
+        standalone_months: &AR_MONTHS,
+        short_months: &AR_MONTHS,
+        weekdays: &AR_WEEKDAYS,
+        short_weekdays: &AR_WEEKDAYS,
+        am_pm: ["ص", "م"],
+        first_weekday: 0,
+        collation_tailoring: &[],
+    },
+];
@@ -0,0 +1,199 @@
+//! Locale ID parsing, such as `zh-Hant-TW` or `de-DE@collation=phonebook`
+
+use crate::globalization::LOCALES;
+
+/// A parsed locale ID.
+///
+/// Both `-` and `_` are accepted as subtag separators. Keywords can be given
+/// after an `@` (`de@collation=phonebook;currency=EUR`) or as a Unicode
+/// extension (`de-u-co-phonebook`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocaleId {
+    name: String,
+    language: String,
+    script: String,
+    region: String,
+    variants: Vec<String>,
+    keywords: Vec<(String, String)>,
+    is_valid: bool,
+}
+
+impl LocaleId {
+    pub fn parse(name: &str) -> Self {
+        let name = name.replace('_', "-");
+        let (tags, keywords) = match name.split_once('@') {
+            Some((tags, keywords)) => (tags, Some(keywords)),
+            None => (&name[..], None),
+        };
+
+        let mut locale = LocaleId {
+            name: name.clone(),
+            is_valid: true,
+            ..Default::default()
+        };
+
+        let mut subtags = tags.split('-');
+        match subtags.next() {
+            Some(language)
+                if (2..=3).contains(&language.len())
+                    && language.chars().all(|c| c.is_ascii_alphabetic()) =>
+            {
+                locale.language = language.to_ascii_lowercase();
+            }
+            _ => locale.is_valid = false,
+        }
+
+        while let Some(subtag) = subtags.next() {
+            let is_alpha = subtag.chars().all(|c| c.is_ascii_alphabetic());
+            let is_digit = subtag.chars().all(|c| c.is_ascii_digit());
+            if subtag.eq_ignore_ascii_case("u") {
+                // Unicode extension: a list of key-value pairs.
+                while let (Some(key), Some(value)) = (subtags.next(), subtags.next()) {
+                    locale
+                        .keywords
+                        .push((unicode_keyword(key).to_string(), value.to_ascii_lowercase()));
+                }
+            } else if subtag.len() == 4
+                && is_alpha
+                && locale.script.is_empty()
+                && locale.region.is_empty()
+                && locale.variants.is_empty()
+            {
+                locale.script =
+                    subtag[..1].to_ascii_uppercase() + &subtag[1..].to_ascii_lowercase();
+            } else if ((subtag.len() == 2 && is_alpha) || (subtag.len() == 3 && is_digit))
+                && locale.region.is_empty()
+                && locale.variants.is_empty()
+            {
+                locale.region = subtag.to_ascii_uppercase();
+            } else if (5..=8).contains(&subtag.len())
+                || (subtag.len() == 4 && subtag.starts_with(|c: char| c.is_ascii_digit()))
+            {
+                locale.variants.push(subtag.to_ascii_uppercase());
+            } else {
+                locale.is_valid = false;
+            }
+        }
+
+        for keyword in keywords.into_iter().flat_map(|k| k.split(';')) {
+            match keyword.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => locale
+                    .keywords
+                    .push((key.trim().to_string(), value.trim().to_string())),
+                _ => locale.is_valid = false,
+            }
+        }
+
+        locale
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the whole ID could be parsed.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// The script given in the ID, or an empty string.
+    pub fn explicit_script(&self) -> &str {
+        &self.script
+    }
+
+    /// The script given in the ID, or the most likely script for the language.
+    pub fn script(&self) -> &str {
+        if !self.script.is_empty() {
+            return &self.script;
+        }
+        match &self.language[..] {
+            "" => "",
+            "ar" | "fa" | "ur" | "ps" => "Arab",
+            "he" | "yi" => "Hebr",
+            "ru" | "uk" | "be" | "bg" | "sr" | "mk" | "kk" => "Cyrl",
+            "el" => "Grek",
+            "ja" => "Jpan",
+            "ko" => "Kore",
+            "zh" => match &self.region[..] {
+                "TW" | "HK" | "MO" => "Hant",
+                _ => "Hans",
+            },
+            "th" => "Thai",
+            "hi" | "mr" | "ne" => "Deva",
+            _ => "Latn",
+        }
+    }
+
+    /// The region given in the ID, or the region of the first available locale
+    /// of the language.
+    pub fn region(&self) -> &str {
+        if !self.region.is_empty() {
+            return &self.region;
+        }
+        LOCALES
+            .iter()
+            .filter_map(|locale| locale.name.split_once('-'))
+            .find(|(language, _)| *language == self.language)
+            .map_or("", |(_, region)| region)
+    }
+
+    /// The variants given in the ID, joined with `_`.
+    pub fn variant(&self) -> String {
+        self.variants.join("_")
+    }
+
+    pub fn keywords(&self) -> &[(String, String)] {
+        &self.keywords
+    }
+
+    pub fn is_right_to_left(&self) -> bool {
+        matches!(self.script(), "Arab" | "Hebr" | "Thaa" | "Syrc" | "Nkoo")
+    }
+}
+
+/// Map a Unicode extension key to its `@keyword` name.
+fn unicode_keyword(key: &str) -> &str {
+    match key {
+        "ca" => "calendar",
+        "co" => "collation",
+        "cu" => "currency",
+        "nu" => "numbers",
+        _ => key,
+    }
+}
+
+/// Order the `available` locales by how well they match the `wanted` locales.
+///
+/// Every wanted locale is matched exactly first, then by language. Available
+/// locales that match nothing are left out.
+pub fn determine_preferred_locales(wanted: &[String], available: &[String]) -> Vec<String> {
+    let available: Vec<(LocaleId, &String)> = available
+        .iter()
+        .map(|name| (LocaleId::parse(name), name))
+        .collect();
+    let mut result: Vec<String> = Vec::new();
+    let mut push = |name: &String| {
+        if !result.contains(name) {
+            result.push(name.clone());
+        }
+    };
+
+    for wanted in wanted.iter().map(|name| LocaleId::parse(name)) {
+        for (locale, name) in &available {
+            if locale.name().eq_ignore_ascii_case(wanted.name()) {
+                push(name);
+            }
+        }
+        for (locale, name) in &available {
+            if locale.language() == wanted.language() && locale.script() == wanted.script() {
+                push(name);
+            }
+        }
+    }
+
+    result
+}
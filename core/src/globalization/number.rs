@@ -0,0 +1,348 @@
+//! Number and currency formatting and parsing
+
+use crate::globalization::{digit_value, localize_digits, LocaleData};
+
+/// The settings of a `NumberFormatter`, also used for the amounts of a `CurrencyFormatter`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NumberFormat {
+    pub decimal_separator: String,
+    pub grouping_separator: String,
+    pub grouping_pattern: String,
+    pub digits_type: u32,
+    pub fractional_digits: i32,
+    pub leading_zero: bool,
+    pub trailing_zeros: bool,
+    pub use_grouping: bool,
+    pub negative_symbol: String,
+    pub negative_number_format: u32,
+}
+
+/// The result of parsing a number out of a string.
+///
+/// The indices are in characters, and `end_index` is exclusive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParsedNumber {
+    pub value: f64,
+    pub start_index: usize,
+    pub end_index: usize,
+}
+
+/// Patterns of `NumberFormatter.negativeNumberFormat`, where `-` stands for
+/// the negative symbol and `n` for the number.
+const NEGATIVE_NUMBER_FORMATS: [&str; 5] = ["(n)", "-n", "- n", "n-", "n -"];
+
+/// Patterns of `CurrencyFormatter.positiveCurrencyFormat`, where `¤` stands
+/// for the currency symbol or ISO code.
+const POSITIVE_CURRENCY_FORMATS: [&str; 4] = ["¤n", "n¤", "¤ n", "n ¤"];
+
+/// Patterns of `CurrencyFormatter.negativeCurrencyFormat`.
+const NEGATIVE_CURRENCY_FORMATS: [&str; 16] = [
+    "(¤n)", "-¤n", "¤-n", "¤n-", "(n¤)", "-n¤", "n-¤", "n¤-", "-n ¤", "-¤ n", "n ¤-", "¤ n-",
+    "¤ -n", "n- ¤", "(¤ n)", "(n ¤)",
+];
+
+/// The largest number of fractional digits that can be formatted.
+const MAX_FRACTIONAL_DIGITS: i32 = 20;
+
+impl NumberFormat {
+    pub fn from_locale(locale: &LocaleData) -> Self {
+        Self {
+            decimal_separator: locale.decimal_separator.to_string(),
+            grouping_separator: locale.grouping_separator.to_string(),
+            grouping_pattern: locale.grouping_pattern.to_string(),
+            digits_type: locale.digits_type,
+            fractional_digits: 2,
+            leading_zero: true,
+            trailing_zeros: true,
+            use_grouping: true,
+            negative_symbol: locale.negative_symbol.to_string(),
+            negative_number_format: locale.negative_number_format,
+        }
+    }
+
+    pub fn max_negative_number_format() -> u32 {
+        NEGATIVE_NUMBER_FORMATS.len() as u32 - 1
+    }
+
+    /// Format a number, rounded to `fractional_digits` decimals.
+    pub fn format(&self, value: f64) -> String {
+        self.format_with_digits(value, self.fractional_digits)
+    }
+
+    /// Format an integer, without decimals.
+    pub fn format_integer(&self, value: f64) -> String {
+        self.format_with_digits(value, 0)
+    }
+
+    fn format_with_digits(&self, value: f64, fractional_digits: i32) -> String {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+        let (digits, is_negative) = self.format_amount(value, fractional_digits);
+        if !is_negative {
+            return digits;
+        }
+        let pattern = NEGATIVE_NUMBER_FORMATS
+            .get(self.negative_number_format as usize)
+            .unwrap_or(&NEGATIVE_NUMBER_FORMATS[1]);
+        apply_pattern(pattern, &digits, &self.negative_symbol, "")
+    }
+
+    /// Format the absolute value of a number, returning the digits and whether
+    /// a negative sign is needed.
+    fn format_amount(&self, value: f64, fractional_digits: i32) -> (String, bool) {
+        if value.is_infinite() {
+            return ("∞".to_string(), value < 0.0);
+        }
+
+        let fractional_digits = fractional_digits.clamp(0, MAX_FRACTIONAL_DIGITS) as usize;
+        let formatted = format!("{:.*}", fractional_digits, value.abs());
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (&formatted[..], ""),
+        };
+        let fraction = if self.trailing_zeros {
+            fraction
+        } else {
+            fraction.trim_end_matches('0')
+        };
+        let is_zero = integer.chars().chain(fraction.chars()).all(|c| c == '0');
+
+        let mut result = String::new();
+        if integer != "0" || self.leading_zero || fraction.is_empty() {
+            if self.use_grouping {
+                result.push_str(&self.group(integer));
+            } else {
+                result.push_str(integer);
+            }
+        }
+        if !fraction.is_empty() {
+            result.push_str(&self.decimal_separator);
+            result.push_str(fraction);
+        }
+
+        (
+            localize_digits(&result, self.digits_type),
+            value < 0.0 && !is_zero,
+        )
+    }
+
+    /// Insert grouping separators into a string of integer digits.
+    fn group(&self, integer: &str) -> String {
+        let Some((sizes, repeat)) = parse_grouping_pattern(&self.grouping_pattern) else {
+            return integer.to_string();
+        };
+
+        let mut groups = Vec::new();
+        let mut rest = integer;
+        for index in 0.. {
+            let size = match sizes.get(index) {
+                Some(size) => *size,
+                None if repeat => *sizes.last().unwrap_or(&0),
+                None => 0,
+            };
+            if size == 0 || rest.len() <= size {
+                break;
+            }
+            let (head, tail) = rest.split_at(rest.len() - size);
+            groups.push(tail);
+            rest = head;
+        }
+        groups.push(rest);
+        groups.reverse();
+        groups.join(&self.grouping_separator)
+    }
+
+    /// Find a number in a string, which may only be surrounded by whitespace.
+    pub fn parse(&self, input: &str) -> Option<ParsedNumber> {
+        let chars: Vec<char> = input.chars().collect();
+        let start = chars.iter().position(|c| !c.is_whitespace())?;
+        let end = chars.iter().rposition(|c| !c.is_whitespace())? + 1;
+        let text: String = chars[start..end].iter().collect();
+
+        let (number, is_negative) = self.strip_negative(&text);
+        let value = self.parse_amount(number)?;
+        Some(ParsedNumber {
+            value: if is_negative { -value } else { value },
+            start_index: start,
+            end_index: end,
+        })
+    }
+
+    /// Remove the negative sign or parentheses from a number.
+    fn strip_negative<'a>(&self, text: &'a str) -> (&'a str, bool) {
+        if let Some(inner) = text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            return (inner.trim(), true);
+        }
+        for symbol in [&self.negative_symbol[..], "-"] {
+            if symbol.is_empty() {
+                continue;
+            }
+            if let Some(rest) = text.strip_prefix(symbol) {
+                return (rest.trim_start(), true);
+            }
+            if let Some(rest) = text.strip_suffix(symbol) {
+                return (rest.trim_end(), true);
+            }
+        }
+        (text.strip_prefix('+').unwrap_or(text), false)
+    }
+
+    /// Parse an unsigned amount with grouping and decimal separators.
+    fn parse_amount(&self, text: &str) -> Option<f64> {
+        let mut number = String::new();
+        let mut rest = text;
+        let mut seen_decimal = false;
+        let mut previous_was_digit = false;
+
+        while let Some(c) = rest.chars().next() {
+            if let Some(digit) = digit_value(c) {
+                number.push(char::from_digit(digit, 10)?);
+                previous_was_digit = true;
+                rest = &rest[c.len_utf8()..];
+            } else if !seen_decimal
+                && !self.decimal_separator.is_empty()
+                && rest.starts_with(&self.decimal_separator[..])
+            {
+                number.push('.');
+                seen_decimal = true;
+                previous_was_digit = false;
+                rest = &rest[self.decimal_separator.len()..];
+            } else if !seen_decimal
+                && previous_was_digit
+                && !self.grouping_separator.is_empty()
+                && rest.starts_with(&self.grouping_separator[..])
+            {
+                previous_was_digit = false;
+                rest = &rest[self.grouping_separator.len()..];
+            } else {
+                return None;
+            }
+        }
+
+        if !number.chars().any(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        number.parse().ok()
+    }
+}
+
+/// Parse a grouping pattern such as `3;*` or `3;2;*` into its group sizes,
+/// and whether the last size repeats.
+fn parse_grouping_pattern(pattern: &str) -> Option<(Vec<usize>, bool)> {
+    let mut sizes = Vec::new();
+    let mut repeat = false;
+    for part in pattern.split(';') {
+        if repeat {
+            return None;
+        }
+        if part == "*" {
+            repeat = true;
+        } else {
+            sizes.push(part.parse().ok().filter(|size| (1..=9).contains(size))?);
+        }
+    }
+    if sizes.is_empty() {
+        return None;
+    }
+    Some((sizes, repeat))
+}
+
+/// Whether `pattern` is a valid `groupingPattern`.
+pub fn is_valid_grouping_pattern(pattern: &str) -> bool {
+    parse_grouping_pattern(pattern).is_some()
+}
+
+/// Substitute a number, a negative symbol and a currency into a format pattern.
+fn apply_pattern(pattern: &str, number: &str, negative_symbol: &str, currency: &str) -> String {
+    let mut result = String::new();
+    for c in pattern.chars() {
+        match c {
+            'n' => result.push_str(number),
+            '-' => result.push_str(negative_symbol),
+            '¤' => result.push_str(currency),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// The settings of a `CurrencyFormatter`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurrencyFormat {
+    pub number: NumberFormat,
+    pub currency_iso_code: String,
+    pub currency_symbol: String,
+    pub positive_currency_format: u32,
+    pub negative_currency_format: u32,
+}
+
+impl CurrencyFormat {
+    pub fn from_locale(locale: &LocaleData) -> Self {
+        Self {
+            number: NumberFormat {
+                fractional_digits: locale.currency_fractional_digits,
+                ..NumberFormat::from_locale(locale)
+            },
+            currency_iso_code: locale.currency_iso_code.to_string(),
+            currency_symbol: locale.currency_symbol.to_string(),
+            positive_currency_format: locale.positive_currency_format,
+            negative_currency_format: locale.negative_currency_format,
+        }
+    }
+
+    pub fn max_positive_currency_format() -> u32 {
+        POSITIVE_CURRENCY_FORMATS.len() as u32 - 1
+    }
+
+    pub fn max_negative_currency_format() -> u32 {
+        NEGATIVE_CURRENCY_FORMATS.len() as u32 - 1
+    }
+
+    /// Format an amount with the currency symbol, or with the ISO code.
+    pub fn format(&self, value: f64, with_currency_symbol: bool) -> String {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+        let currency = if with_currency_symbol {
+            &self.currency_symbol
+        } else {
+            &self.currency_iso_code
+        };
+        let (amount, is_negative) = self
+            .number
+            .format_amount(value, self.number.fractional_digits);
+        let pattern = if is_negative {
+            NEGATIVE_CURRENCY_FORMATS
+                .get(self.negative_currency_format as usize)
+                .unwrap_or(&NEGATIVE_CURRENCY_FORMATS[1])
+        } else {
+            POSITIVE_CURRENCY_FORMATS
+                .get(self.positive_currency_format as usize)
+                .unwrap_or(&POSITIVE_CURRENCY_FORMATS[0])
+        };
+        apply_pattern(pattern, &amount, &self.number.negative_symbol, currency)
+    }
+
+    /// Parse an amount, returning it with the currency symbol or ISO code found
+    /// in the input, if any.
+    pub fn parse(&self, input: &str) -> Option<(f64, String)> {
+        let text = input.trim();
+        let mut currency = String::new();
+        let mut amount = text.to_string();
+        for candidate in [&self.currency_symbol, &self.currency_iso_code] {
+            if !candidate.is_empty() && text.contains(&candidate[..]) {
+                currency = candidate.clone();
+                amount = text.replacen(&candidate[..], "", 1);
+                break;
+            }
+        }
+
+        // Once the currency is removed, negative amounts look like `- n`, `(n)`
+        // or `n-`, with optional spaces around the sign.
+        let amount = amount.trim();
+        let (amount, is_negative) = self.number.strip_negative(amount);
+        let value = self.number.parse_amount(amount.trim())?;
+        Some((if is_negative { -value } else { value }, currency))
+    }
+}
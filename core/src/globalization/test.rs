@@ -0,0 +1,241 @@
+//! Tests for globalization module
+
+use crate::globalization::{
+    determine_preferred_locales, format_date_time, is_valid_grouping_pattern, is_valid_pattern,
+    month_names, pattern_for_styles, resolve_locale, CollatorMode, CollatorOptions, CurrencyFormat,
+    DateTimeNameContext, DateTimeNameStyle, DateTimeStyle, LastOperationStatus, LocaleData,
+    LocaleId, NumberFormat,
+};
+use chrono::{FixedOffset, TimeZone};
+use std::cmp::Ordering;
+
+fn locale(name: &str) -> &'static LocaleData {
+    let (locale, status) = resolve_locale(name, "en-US");
+    assert_eq!(status, LastOperationStatus::NoError, "{name} is available");
+    locale
+}
+
+#[test]
+fn resolve_locale_fallbacks() {
+    let (locale, status) = resolve_locale("de_DE", "en-US");
+    assert_eq!(
+        (locale.name, status),
+        ("de-DE", LastOperationStatus::NoError)
+    );
+
+    let (locale, status) = resolve_locale("de-AT", "en-US");
+    assert_eq!(
+        (locale.name, status),
+        ("de-DE", LastOperationStatus::UsingFallbackWarning)
+    );
+
+    let (locale, status) = resolve_locale("xx-YY", "fr-FR");
+    assert_eq!(
+        (locale.name, status),
+        ("fr-FR", LastOperationStatus::UsingFallbackWarning)
+    );
+
+    let (locale, status) = resolve_locale("i-default", "ja-JP");
+    assert_eq!(
+        (locale.name, status),
+        ("ja-JP", LastOperationStatus::UsingDefaultWarning)
+    );
+}
+
+#[test]
+fn locale_id_parts() {
+    let id = LocaleId::parse("zh-Hant_TW");
+    assert!(id.is_valid());
+    assert_eq!(id.language(), "zh");
+    assert_eq!(id.script(), "Hant");
+    assert_eq!(id.region(), "TW");
+
+    let id = LocaleId::parse("de-DE-1996@collation=phonebook");
+    assert_eq!(id.variant(), "1996");
+    assert_eq!(
+        id.keywords(),
+        &[("collation".to_string(), "phonebook".to_string())]
+    );
+
+    let id = LocaleId::parse("ar");
+    assert_eq!(id.script(), "Arab");
+    assert_eq!(id.region(), "SA");
+    assert!(id.is_right_to_left());
+
+    assert!(!LocaleId::parse("1234").is_valid());
+}
+
+#[test]
+fn preferred_locales() {
+    let wanted = ["fr-CA".to_string(), "en-US".to_string()];
+    let available = [
+        "de-DE".to_string(),
+        "en-GB".to_string(),
+        "fr-FR".to_string(),
+        "en-US".to_string(),
+    ];
+    assert_eq!(
+        determine_preferred_locales(&wanted, &available),
+        ["fr-FR", "en-US", "en-GB"]
+    );
+}
+
+#[test]
+fn format_numbers() {
+    let mut format = NumberFormat::from_locale(locale("en-US"));
+    assert_eq!(format.format(-123456789.19), "-123,456,789.19");
+    assert_eq!(format.format_integer(123456789.0), "123,456,789");
+    assert_eq!(format.format(0.5), "0.50");
+
+    format.leading_zero = false;
+    format.trailing_zeros = false;
+    assert_eq!(format.format(0.5), ".5");
+
+    format.negative_number_format = 0;
+    assert_eq!(format.format(-12.0), "(12)");
+
+    format.grouping_pattern = "3;2;*".to_string();
+    assert_eq!(format.format_integer(123456789.0), "12,34,56,789");
+    format.grouping_pattern = "3".to_string();
+    assert_eq!(format.format_integer(123456789.0), "123456,789");
+
+    let format = NumberFormat::from_locale(locale("fr-FR"));
+    assert_eq!(format.format(1234.5), "1\u{a0}234,50");
+
+    let format = NumberFormat::from_locale(locale("ar-SA"));
+    assert_eq!(format.format_integer(1234.0), "١٬٢٣٤");
+}
+
+#[test]
+fn grouping_patterns() {
+    assert!(is_valid_grouping_pattern("3;*"));
+    assert!(is_valid_grouping_pattern("3;2;*"));
+    assert!(is_valid_grouping_pattern("3"));
+    assert!(!is_valid_grouping_pattern("*"));
+    assert!(!is_valid_grouping_pattern("3;*;2"));
+    assert!(!is_valid_grouping_pattern("a"));
+}
+
+#[test]
+fn parse_numbers() {
+    let format = NumberFormat::from_locale(locale("en-US"));
+    let parsed = format.parse("  -123,456.75 ").unwrap();
+    assert_eq!(parsed.value, -123456.75);
+    assert_eq!((parsed.start_index, parsed.end_index), (2, 13));
+    assert_eq!(format.parse("(12)").unwrap().value, -12.0);
+    assert_eq!(format.parse("12 apples"), None);
+    assert_eq!(format.parse(",12"), None);
+
+    let format = NumberFormat::from_locale(locale("de-DE"));
+    assert_eq!(format.parse("1.234,5").unwrap().value, 1234.5);
+}
+
+#[test]
+fn format_and_parse_currency() {
+    let format = CurrencyFormat::from_locale(locale("en-US"));
+    assert_eq!(format.format(1234.5, true), "$1,234.50");
+    assert_eq!(format.format(-1234.5, false), "-USD1,234.50");
+    assert_eq!(format.parse("-$1,234.50"), Some((-1234.5, "$".to_string())));
+
+    let format = CurrencyFormat::from_locale(locale("de-DE"));
+    assert_eq!(format.format(-1234.5, true), "-1.234,50 €");
+    assert_eq!(
+        format.parse("1.234,50 EUR"),
+        Some((1234.5, "EUR".to_string()))
+    );
+
+    let format = CurrencyFormat::from_locale(locale("ja-JP"));
+    assert_eq!(format.format(1234.0, true), "¥1,234");
+}
+
+#[test]
+fn format_dates() {
+    let date = FixedOffset::east_opt(3600)
+        .unwrap()
+        .with_ymd_and_hms(2011, 4, 5, 15, 4, 9)
+        .unwrap();
+
+    let en = locale("en-US");
+    let pattern = pattern_for_styles(en, DateTimeStyle::Long, DateTimeStyle::Long);
+    assert_eq!(
+        format_date_time(&pattern, en, &date).unwrap(),
+        "Tuesday, April 5, 2011 3:04:09 PM"
+    );
+    let pattern = pattern_for_styles(en, DateTimeStyle::Short, DateTimeStyle::None);
+    assert_eq!(format_date_time(&pattern, en, &date).unwrap(), "4/5/2011");
+    assert_eq!(
+        format_date_time("yy-MM-dd'T'HH:mm 'o''clock' Z", en, &date).unwrap(),
+        "11-04-05T15:04 o'clock +0100"
+    );
+
+    let de = locale("de-DE");
+    let pattern = pattern_for_styles(de, DateTimeStyle::Long, DateTimeStyle::Short);
+    assert_eq!(
+        format_date_time(&pattern, de, &date).unwrap(),
+        "Dienstag, 5. April 2011 15:04"
+    );
+
+    let ru = locale("ru-RU");
+    assert_eq!(format_date_time("d MMMM", ru, &date).unwrap(), "5 апреля");
+    assert_eq!(format_date_time("LLLL", ru, &date).unwrap(), "апрель");
+
+    assert!(!is_valid_pattern("'unterminated"));
+    assert_eq!(format_date_time("'unterminated", en, &date), None);
+}
+
+#[test]
+fn date_time_names() {
+    let ru = locale("ru-RU");
+    let names = month_names(ru, DateTimeNameStyle::Full, DateTimeNameContext::Standalone);
+    assert_eq!(names[0], "январь");
+    let names = month_names(ru, DateTimeNameStyle::Full, DateTimeNameContext::Format);
+    assert_eq!(names[0], "января");
+
+    let en = locale("en-US");
+    let names = month_names(
+        en,
+        DateTimeNameStyle::ShortAbbreviation,
+        DateTimeNameContext::Format,
+    );
+    assert_eq!(names[11], "D");
+}
+
+#[test]
+fn collator_sorting() {
+    let en = locale("en-US");
+    let sorting = CollatorOptions::for_mode(CollatorMode::Sorting);
+    assert_eq!(sorting.compare(en, "apple", "Banana"), Ordering::Less);
+    assert_eq!(sorting.compare(en, "a", "A"), Ordering::Less);
+    assert_eq!(sorting.compare(en, "résumé", "resume"), Ordering::Greater);
+    assert_eq!(sorting.compare(en, "résumé", "resumes"), Ordering::Less);
+    assert_eq!(sorting.compare(en, "item10", "item9"), Ordering::Less);
+
+    let numeric = CollatorOptions {
+        numeric_comparison: true,
+        ..sorting
+    };
+    assert_eq!(numeric.compare(en, "item10", "item9"), Ordering::Greater);
+
+    let es = locale("es-ES");
+    assert_eq!(sorting.compare(es, "ñu", "nz"), Ordering::Greater);
+    assert_eq!(sorting.compare(en, "ñu", "nz"), Ordering::Less);
+
+    let sv = locale("sv-SE");
+    assert_eq!(sorting.compare(sv, "ö", "z"), Ordering::Greater);
+}
+
+#[test]
+fn collator_matching() {
+    let en = locale("en-US");
+    let matching = CollatorOptions::for_mode(CollatorMode::Matching);
+    assert_eq!(matching.compare(en, "Résumé", "resume"), Ordering::Equal);
+    assert_eq!(matching.compare(en, "ＡＢＣ", "abc"), Ordering::Equal);
+    assert_eq!(matching.compare(en, "カナ", "かな"), Ordering::Equal);
+    assert_ne!(matching.compare(en, "a-b", "ab"), Ordering::Equal);
+
+    let ignore_symbols = CollatorOptions {
+        ignore_symbols: true,
+        ..matching
+    };
+    assert_eq!(ignore_symbols.compare(en, "a-b", "ab"), Ordering::Equal);
+}
@@ -3,11 +3,18 @@
 mod dimensions;
 mod iterators;
 mod layout;
+mod style_sheet;
+mod text_engine;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use layout::{LayoutBox, LayoutContent, LayoutMetrics};
+pub use style_sheet::{Style, StyleSheet};
+pub use text_engine::{
+    layout_line, BreakOpportunity, ContentRun, Justifier, LaidOutLine, LineCreationResult,
+    LineJustification, LineRequest, TabAlignment, TabStop, TextBlockSettings, TextLineLayout,
+};
 pub use text_format::{FormatSpans, TextFormat, TextSpan};
 
 #[cfg(test)]
@@ -45,6 +45,63 @@ fn draw_underline(
     )));
 }
 
+/// Find the font used to render a text span, falling back to device and default fonts.
+pub fn resolve_font<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    movie: Arc<SwfMovie>,
+    span: &TextSpan,
+    is_device_font: bool,
+) -> Option<Font<'gc>> {
+    let font_name = span.font.to_utf8_lossy();
+
+    // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
+    // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
+    if !is_device_font {
+        if let Some(font) = context
+            .library
+            .get_embedded_font_by_name(&font_name, span.bold, span.italic, Some(movie))
+            .filter(|f| f.has_glyphs())
+        {
+            return Some(font);
+        }
+        // TODO: If set to use embedded fonts and we couldn't find any matching font, show nothing
+        // However - at time of writing, we don't support DefineFont4. If we matched this behaviour,
+        // then a bunch of SWFs would just show no text suddenly.
+        // return None;
+    }
+
+    if let Some(font) = context.library.get_or_load_device_font(
+        &font_name,
+        span.bold,
+        span.italic,
+        context.ui,
+        context.renderer,
+        context.gc_context,
+    ) {
+        return Some(font);
+    }
+
+    // [NA] I suspect that there might be undocumented more aliases.
+    // I think I've seen translated versions of these used in the wild...
+    let default_font = match font_name.deref() {
+        "_serif" => DefaultFont::Serif,
+        "_typewriter" => DefaultFont::Typewriter,
+        _ => DefaultFont::Sans,
+    };
+
+    // TODO: handle multiple fonts for a definition, each covering different sets of glyphs
+    context
+        .library
+        .default_font(
+            default_font,
+            context.ui,
+            context.renderer,
+            context.gc_context,
+        )
+        .first()
+        .copied()
+}
+
 /// Contains information relating to the current layout operation.
 pub struct LayoutContext<'a, 'gc> {
     /// The movie this layout context is pulling fonts from.
@@ -463,59 +520,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span: &TextSpan,
         is_device_font: bool,
     ) -> Option<Font<'gc>> {
-        let font_name = span.font.to_utf8_lossy();
-
-        // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
-        if !is_device_font {
-            if let Some(font) = context
-                .library
-                .get_embedded_font_by_name(
-                    &font_name,
-                    span.bold,
-                    span.italic,
-                    Some(self.movie.clone()),
-                )
-                .filter(|f| f.has_glyphs())
-            {
-                return Some(font);
-            }
-            // TODO: If set to use embedded fonts and we couldn't find any matching font, show nothing
-            // However - at time of writing, we don't support DefineFont4. If we matched this behaviour,
-            // then a bunch of SWFs would just show no text suddenly.
-            // return None;
-        }
-
-        if let Some(font) = context.library.get_or_load_device_font(
-            &font_name,
-            span.bold,
-            span.italic,
-            context.ui,
-            context.renderer,
-            context.gc_context,
-        ) {
-            return Some(font);
-        }
-
-        // [NA] I suspect that there might be undocumented more aliases.
-        // I think I've seen translated versions of these used in the wild...
-        let default_font = match font_name.deref() {
-            "_serif" => DefaultFont::Serif,
-            "_typewriter" => DefaultFont::Typewriter,
-            _ => DefaultFont::Sans,
-        };
-
-        // TODO: handle multiple fonts for a definition, each covering different sets of glyphs
-        context
-            .library
-            .default_font(
-                default_font,
-                context.ui,
-                context.renderer,
-                context.gc_context,
-            )
-            .first()
-            .copied()
+        resolve_font(context, self.movie.clone(), span, is_device_font)
     }
 
     /// Append text to the current line of the ongoing layout operation.
@@ -921,6 +926,12 @@ impl<'gc> LayoutBox<'gc> {
         self.bounds
     }
 
+    /// Place this box at the given bounds, for boxes positioned outside of `LayoutContext`.
+    pub fn with_bounds(mut self, bounds: BoxBounds<Twips>) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
     pub fn content(&self) -> &LayoutContent<'gc> {
         &self.content
     }
@@ -0,0 +1,284 @@
+//! CSS style sheets for HTML text
+
+use crate::html::TextFormat;
+use crate::string::{WStr, WString};
+use gc_arena::Collect;
+
+/// How an element is laid out, as given by the CSS `display` property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Display {
+    /// The element is part of the surrounding line.
+    Inline,
+
+    /// The element ends with a line break, like a `<p>` tag.
+    Block,
+
+    /// The element and its contents are not shown.
+    None,
+}
+
+/// A single style of a style sheet.
+///
+/// Properties are keyed by their ActionScript names (`fontSize` instead of
+/// `font-size`), and their values are kept as written, so that scripts can
+/// read them back with `getStyle`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    properties: Vec<(WString, WString)>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, name: &WStr) -> Option<&WStr> {
+        self.properties
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_wstr())
+    }
+
+    pub fn set(&mut self, name: WString, value: WString) {
+        match self.properties.iter_mut().find(|(n, _)| n == &name) {
+            Some((_, v)) => *v = value,
+            None => self.properties.push((name, value)),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&WStr, &WStr)> {
+        self.properties
+            .iter()
+            .map(|(name, value)| (name.as_wstr(), value.as_wstr()))
+    }
+
+    pub fn display(&self) -> Option<Display> {
+        let display = self.get(WStr::from_units(b"display"))?;
+        match &display.trim().to_utf8_lossy()[..] {
+            "inline" => Some(Display::Inline),
+            "block" => Some(Display::Block),
+            "none" => Some(Display::None),
+            _ => None,
+        }
+    }
+
+    /// Convert this style into the text format it applies.
+    ///
+    /// Unsupported properties and values are ignored, like in Flash Player.
+    pub fn text_format(&self) -> TextFormat {
+        let mut format = TextFormat::default();
+
+        for (name, value) in self.iter() {
+            let value = value.trim().to_utf8_lossy();
+            let value = &value[..];
+            match &name.to_utf8_lossy()[..] {
+                "color" => format.color = parse_color(value).or(format.color),
+                "fontFamily" => format.font = parse_font_family(value).or(format.font),
+                "fontSize" => format.size = parse_length(value).or(format.size),
+                "fontStyle" => match value {
+                    "italic" => format.italic = Some(true),
+                    "normal" => format.italic = Some(false),
+                    _ => {}
+                },
+                "fontWeight" => match value {
+                    "bold" => format.bold = Some(true),
+                    "normal" => format.bold = Some(false),
+                    _ => {}
+                },
+                "kerning" => match value {
+                    "true" => format.kerning = Some(true),
+                    "false" => format.kerning = Some(false),
+                    _ => {}
+                },
+                "leading" => format.leading = parse_length(value).or(format.leading),
+                "letterSpacing" => {
+                    format.letter_spacing = parse_length(value).or(format.letter_spacing)
+                }
+                "marginLeft" => format.left_margin = parse_length(value).or(format.left_margin),
+                "marginRight" => format.right_margin = parse_length(value).or(format.right_margin),
+                "textAlign" => match value {
+                    "left" => format.align = Some(swf::TextAlign::Left),
+                    "center" => format.align = Some(swf::TextAlign::Center),
+                    "right" => format.align = Some(swf::TextAlign::Right),
+                    "justify" => format.align = Some(swf::TextAlign::Justify),
+                    _ => {}
+                },
+                "textDecoration" => match value {
+                    "underline" => format.underline = Some(true),
+                    "none" => format.underline = Some(false),
+                    _ => {}
+                },
+                "textIndent" => format.indent = parse_length(value).or(format.indent),
+                _ => {}
+            }
+        }
+
+        format
+    }
+}
+
+/// A set of named styles, as used by `TextField.styleSheet`.
+///
+/// Style names are selectors: a tag name (`p`), a class (`.title`), or one of
+/// the link states (`a:link`, `a:hover` and `a:active`).
+#[derive(Clone, Debug, Default, PartialEq, Collect)]
+#[collect(require_static)]
+pub struct StyleSheet {
+    styles: Vec<(WString, Style)>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn style_names(&self) -> impl Iterator<Item = &WStr> {
+        self.styles.iter().map(|(name, _)| name.as_wstr())
+    }
+
+    pub fn style(&self, name: &WStr) -> Option<&Style> {
+        self.styles
+            .iter()
+            .find(|(n, _)| n.eq_ignore_case(name))
+            .map(|(_, style)| style)
+    }
+
+    /// Add or replace a style, or remove it if `style` is `None`.
+    pub fn set_style(&mut self, name: &WStr, style: Option<Style>) {
+        let index = self.styles.iter().position(|(n, _)| n.eq_ignore_case(name));
+        match (index, style) {
+            (Some(index), Some(style)) => self.styles[index].1 = style,
+            (Some(index), None) => {
+                self.styles.remove(index);
+            }
+            (None, Some(style)) => self.styles.push((name.to_ascii_lowercase(), style)),
+            (None, None) => {}
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.styles.clear();
+    }
+
+    /// Parse CSS text, adding its styles to this style sheet.
+    ///
+    /// Returns `false` and leaves the style sheet unchanged if the CSS is malformed.
+    pub fn parse_css(&mut self, css: &WStr) -> bool {
+        match parse_css(css) {
+            Some(styles) => {
+                for (name, style) in styles {
+                    self.set_style(&name, Some(style));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Parse the rules of a style sheet into named styles.
+///
+/// A rule with several selectors (`h1, h2 { ... }`) creates a style for each.
+fn parse_css(css: &WStr) -> Option<Vec<(WString, Style)>> {
+    let css = strip_comments(css);
+    let mut rest = css.as_wstr();
+    let mut styles = Vec::new();
+
+    while !rest.trim().is_empty() {
+        let (selectors, after) = rest.split_once(b'{')?;
+        let (body, after) = after.split_once(b'}')?;
+        rest = after;
+
+        let mut style = Style::new();
+        for declaration in body.split(b';') {
+            if declaration.trim().is_empty() {
+                continue;
+            }
+            let (name, value) = declaration.split_once(b':')?;
+            style.set(property_name(name.trim()), value.trim().into());
+        }
+
+        for selector in selectors.split(b',') {
+            let selector = selector.trim();
+            if selector.is_empty() {
+                return None;
+            }
+            styles.push((selector.to_ascii_lowercase(), style.clone()));
+        }
+    }
+
+    Some(styles)
+}
+
+fn strip_comments(css: &WStr) -> WString {
+    let mut result = WString::new();
+    let mut rest = css;
+    while let Some(start) = rest.find(WStr::from_units(b"/*")) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+        match rest.find(WStr::from_units(b"*/")) {
+            Some(end) => rest = &rest[end + 2..],
+            None => rest = WStr::empty(),
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Convert a CSS property name into its ActionScript name, e.g. `font-size` into `fontSize`.
+fn property_name(name: &WStr) -> WString {
+    let mut result = WString::new();
+    let mut capitalize = false;
+    for c in name.to_ascii_lowercase().iter() {
+        if c == u16::from(b'-') {
+            capitalize = !result.is_empty();
+        } else if capitalize {
+            result.push(u8::try_from(c).map_or(c, |c| c.to_ascii_uppercase().into()));
+            capitalize = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn parse_color(value: &str) -> Option<swf::Color> {
+    let hex = value.strip_prefix('#')?;
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    let rgb = match hex.len() {
+        6 => rgb,
+        // `#RGB` is short for `#RRGGBB`.
+        3 => {
+            let (r, g, b) = ((rgb >> 8) & 0xF, (rgb >> 4) & 0xF, rgb & 0xF);
+            ((r * 0x11) << 16) | ((g * 0x11) << 8) | (b * 0x11)
+        }
+        _ => return None,
+    };
+    // This matches the colors of `<font color>` tags.
+    Some(swf::Color::from_rgb(rgb, 0))
+}
+
+/// Parse a length like `12`, `12px` or `12pt` as a number of pixels.
+fn parse_length(value: &str) -> Option<f64> {
+    let value = value
+        .strip_suffix("px")
+        .or_else(|| value.strip_suffix("pt"))
+        .unwrap_or(value);
+    value.trim().parse().ok()
+}
+
+/// Get the font to use from a `font-family` list.
+///
+/// Only the first family is used. Generic families map to Flash's device fonts.
+fn parse_font_family(value: &str) -> Option<WString> {
+    let family = value.split(',').next()?.trim();
+    let family = family.trim_matches(|c| c == '"' || c == '\'');
+    let font = match family {
+        "" => return None,
+        "sans-serif" => "_sans",
+        "serif" => "_serif",
+        "monospace" => "_typewriter",
+        family => family,
+    };
+    Some(WString::from_utf8(font))
+}
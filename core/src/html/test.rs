@@ -1,6 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::style_sheet::{Display, StyleSheet};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
@@ -864,3 +865,286 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_from_html_condense_white() {
+    let html = WStr::from_units(b"  <b>Hello</b>   \n\t world  ");
+
+    let spans = FormatSpans::from_html(html, TextFormat::default(), false, false, None);
+    assert_eq!(spans.text(), WStr::from_units(b"  Hello   \n\t world  "));
+
+    let spans = FormatSpans::from_html(html, TextFormat::default(), false, true, None);
+    assert_eq!(spans.text(), WStr::from_units(b"Hello world "));
+}
+
+#[test]
+fn style_sheet_parse_css() {
+    let mut style_sheet = StyleSheet::new();
+    let css = WStr::from_units(
+        b"/* headings */ H1, .Title { font-size: 24px; font-weight: bold }\n\
+          a:hover { color: #f00; text-decoration: underline; }\n\
+          .hidden { display: none; }",
+    );
+    assert!(style_sheet.parse_css(css));
+
+    let names: Vec<_> = style_sheet.style_names().map(WString::from).collect();
+    assert_eq!(
+        names,
+        vec![
+            WString::from_utf8("h1"),
+            WString::from_utf8(".title"),
+            WString::from_utf8("a:hover"),
+            WString::from_utf8(".hidden"),
+        ]
+    );
+
+    let title = style_sheet.style(WStr::from_units(b".title")).unwrap();
+    assert_eq!(
+        title.get(WStr::from_units(b"fontSize")),
+        Some(WStr::from_units(b"24px"))
+    );
+    let format = title.text_format();
+    assert_eq!(format.size, Some(24.0));
+    assert_eq!(format.bold, Some(true));
+
+    let hover = style_sheet.style(WStr::from_units(b"a:hover")).unwrap();
+    let format = hover.text_format();
+    assert_eq!(format.color, Some(swf::Color::from_rgb(0xFF0000, 0)));
+    assert_eq!(format.underline, Some(true));
+
+    let hidden = style_sheet.style(WStr::from_units(b".hidden")).unwrap();
+    assert_eq!(hidden.display(), Some(Display::None));
+}
+
+#[test]
+fn style_sheet_rejects_malformed_css() {
+    let mut style_sheet = StyleSheet::new();
+    assert!(!style_sheet.parse_css(WStr::from_units(b"p { color: #000000; ")));
+    assert!(!style_sheet.parse_css(WStr::from_units(b"p { color }")));
+    assert_eq!(style_sheet.style_names().count(), 0);
+}
+
+#[test]
+fn formatspans_from_html_with_style_sheet() {
+    let mut style_sheet = StyleSheet::new();
+    assert!(style_sheet.parse_css(WStr::from_units(
+        b"p { font-size: 20 } .big { font-size: 30 } span { display: none }"
+    )));
+    let html =
+        WStr::from_units(b"<p>one</p><p class=\"big\">two<span>hidden</span></p><b>three</b>");
+
+    let default_format = TextFormat {
+        size: Some(10.0),
+        ..TextFormat::default()
+    };
+
+    let spans = FormatSpans::from_html(html, default_format, true, false, Some(&style_sheet));
+    assert_eq!(spans.text(), WStr::from_units(b"one\ntwo\nthree"));
+    assert_eq!(spans.get_text_format(0, 3).size, Some(20.0));
+    assert_eq!(spans.get_text_format(4, 7).size, Some(30.0));
+    assert_eq!(spans.get_text_format(8, 13).size, Some(10.0));
+}
+
+#[test]
+fn bidi_levels_embed_numbers_in_rtl_text() {
+    use crate::html::text_engine::{resolve_bidi_levels, BidiClass};
+
+    // "abc אב 12" in a left-to-right paragraph.
+    let classes: Vec<_> = "abc \u{5d0}\u{5d1} 12".chars().map(BidiClass::of).collect();
+    let levels = resolve_bidi_levels(&classes, 0);
+
+    assert_eq!(levels, vec![0, 0, 0, 0, 1, 1, 1, 2, 2]);
+}
+
+#[test]
+fn bidi_reorder_reverses_rtl_runs() {
+    use crate::html::text_engine::reorder_by_levels;
+
+    assert_eq!(reorder_by_levels(&[0, 0, 1, 1, 0]), vec![0, 1, 3, 2, 4]);
+    assert_eq!(reorder_by_levels(&[1, 2, 2, 1]), vec![3, 1, 2, 0]);
+    assert_eq!(reorder_by_levels(&[0, 0]), vec![0, 1]);
+}
+
+/// Text engine runs and measured characters where every character is 10 pixels wide.
+fn text_engine_line(
+    text: &str,
+) -> (
+    Vec<crate::html::text_engine::MeasuredChar>,
+    Vec<crate::html::ContentRun>,
+    Vec<TextSpan>,
+) {
+    use crate::html::text_engine::MeasuredChar;
+    use crate::html::{BreakOpportunity, ContentRun};
+
+    let format = TextFormat {
+        size: Some(10.0),
+        ..Default::default()
+    };
+    let runs = vec![ContentRun {
+        text: WString::from_utf8(text),
+        format: format.clone(),
+        is_device_font: true,
+        break_opportunity: BreakOpportunity::Auto,
+        baseline_shift: 0.0,
+        tracking_left: 0.0,
+        tracking_right: 0.0,
+        graphic_size: None,
+    }];
+    let spans = vec![TextSpan::with_length_and_format(text.len(), format)];
+    let chars = text
+        .chars()
+        .enumerate()
+        .map(|(i, ch)| MeasuredChar {
+            run: 0,
+            begin: i,
+            end: i + 1,
+            ch,
+            advance: if ch == '\n' {
+                Twips::ZERO
+            } else {
+                Twips::from_pixels(10.0)
+            },
+            glyph_offset: Twips::ZERO,
+            ascent: Twips::from_pixels(8.0),
+            descent: Twips::from_pixels(2.0),
+            baseline_shift: Twips::ZERO,
+        })
+        .collect();
+    (chars, runs, spans)
+}
+
+fn text_engine_line_end(text: &str, width: f64, fit_something: bool) -> Option<(usize, bool)> {
+    use crate::html::text_engine::find_line_end;
+    use crate::html::{LineCreationResult, LineRequest, TextBlockSettings};
+
+    let (mut chars, runs, spans) = text_engine_line(text);
+    let request = LineRequest {
+        begin: 0,
+        width,
+        line_offset: 0.0,
+        fit_something,
+    };
+    let line_end = find_line_end(
+        &mut chars,
+        &runs,
+        &spans,
+        &TextBlockSettings::default(),
+        request,
+    )?;
+    Some((
+        line_end.end,
+        line_end.result == LineCreationResult::Emergency,
+    ))
+}
+
+#[test]
+fn text_engine_breaks_after_whitespace() {
+    // Trailing whitespace hangs past the end of the line.
+    assert_eq!(
+        text_engine_line_end("aa bb cc", 55.0, false),
+        Some((6, false))
+    );
+    assert_eq!(
+        text_engine_line_end("aa bb cc", 25.0, false),
+        Some((3, false))
+    );
+    assert_eq!(
+        text_engine_line_end("aa bb cc", 100.0, false),
+        Some((8, false))
+    );
+}
+
+#[test]
+fn text_engine_emergency_break() {
+    assert_eq!(text_engine_line_end("abcdef", 35.0, false), Some((3, true)));
+    assert_eq!(text_engine_line_end("abcdef", 5.0, false), None);
+    assert_eq!(text_engine_line_end("abcdef", 5.0, true), Some((1, false)));
+}
+
+#[test]
+fn text_engine_mandatory_break() {
+    use crate::html::text_engine::find_line_end;
+    use crate::html::{LineRequest, TextBlockSettings};
+
+    let (mut chars, runs, spans) = text_engine_line("ab\ncd");
+    let request = LineRequest {
+        begin: 0,
+        width: 1000.0,
+        line_offset: 0.0,
+        fit_something: false,
+    };
+    let line_end = find_line_end(
+        &mut chars,
+        &runs,
+        &spans,
+        &TextBlockSettings::default(),
+        request,
+    )
+    .unwrap();
+    assert_eq!(line_end.end, 3);
+    assert!(line_end.is_mandatory);
+}
+
+#[test]
+fn text_engine_tab_stops() {
+    use crate::html::text_engine::find_line_end;
+    use crate::html::{LineRequest, TabAlignment, TabStop, TextBlockSettings};
+
+    let (mut chars, runs, spans) = text_engine_line("a\tbc\td");
+    let settings = TextBlockSettings {
+        tab_stops: vec![
+            TabStop {
+                alignment: TabAlignment::Start,
+                position: 50.0,
+                decimal_token: WString::new(),
+            },
+            TabStop {
+                alignment: TabAlignment::End,
+                position: 100.0,
+                decimal_token: WString::new(),
+            },
+        ],
+        ..Default::default()
+    };
+    let request = LineRequest {
+        begin: 0,
+        width: 1000.0,
+        line_offset: 0.0,
+        fit_something: false,
+    };
+    find_line_end(&mut chars, &runs, &spans, &settings, request).unwrap();
+
+    // The first tab moves "bc" to 50, and the second ends "d" at 100.
+    assert_eq!(chars[1].advance, Twips::from_pixels(40.0));
+    assert_eq!(chars[4].advance, Twips::from_pixels(20.0));
+}
+
+#[test]
+fn text_engine_measures_only_what_fits() {
+    use crate::html::text_engine::MeasureLimit;
+
+    let (chars, _, _) = text_engine_line("aa bb cc dd");
+    let mut limit = MeasureLimit::new(Twips::from_pixels(45.0));
+    let measured = chars.iter().position(|c| limit.add(c));
+    // Stops at the first "b" that can't fit, rather than measuring the whole paragraph.
+    assert_eq!(measured, Some(4));
+
+    // Text after a tab is needed to align it, up to the next tab.
+    let (chars, _, _) = text_engine_line("a\tbbbbbb\tcc dd");
+    let mut limit = MeasureLimit::new(Twips::from_pixels(45.0));
+    let measured = chars.iter().position(|c| limit.add(c));
+    assert_eq!(measured, Some(8));
+}
+
+#[test]
+fn text_engine_justifies_between_words() {
+    use crate::html::text_engine::justify_chars;
+
+    let (mut chars, _, _) = text_engine_line("a b c ");
+    justify_chars(&mut chars, Twips::from_pixels(60.0), false);
+
+    // The 10 pixels left over are shared by the two inner spaces, but not the trailing one.
+    assert_eq!(chars[1].advance, Twips::from_pixels(15.0));
+    assert_eq!(chars[3].advance, Twips::from_pixels(15.0));
+    assert_eq!(chars[5].advance, Twips::from_pixels(10.0));
+}
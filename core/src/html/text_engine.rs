@@ -0,0 +1,862 @@
+//! Line breaking and layout for `flash.text.engine`.
+//!
+//! Unlike `EditText` layout, the text engine breaks a text block one line at
+//! a time, and every line reports the position of each of its atoms to
+//! ActionScript. Each glyph is laid out as its own `LayoutBox`, so that tabs,
+//! justification and bidirectional reordering can move glyphs individually
+//! without the rendered text disagreeing with the reported atom bounds.
+
+use crate::context::UpdateContext;
+use crate::font::{EvalParameters, Font};
+use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::layout::{resolve_font, LayoutBox};
+use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::string::WString;
+use crate::tag_utils::SwfMovie;
+use std::sync::Arc;
+use swf::Twips;
+
+/// How a content element allows lines to break within its text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BreakOpportunity {
+    /// Break after whitespace and hyphens, and between ideographs.
+    #[default]
+    Auto,
+
+    /// Break between any two characters.
+    Any,
+
+    /// Break after every character.
+    All,
+
+    /// Never break within the element.
+    None,
+}
+
+/// A piece of a text block's content with a single format.
+#[derive(Clone, Debug)]
+pub struct ContentRun {
+    pub text: WString,
+
+    /// The font, size, color and kerning of the text.
+    pub format: TextFormat,
+
+    /// Whether the font should be looked up on the device instead of in the movie.
+    pub is_device_font: bool,
+
+    pub break_opportunity: BreakOpportunity,
+
+    /// How far the text is moved down from the baseline, in pixels.
+    pub baseline_shift: f64,
+
+    /// Extra space before each glyph, in pixels.
+    pub tracking_left: f64,
+
+    /// Extra space after each glyph, in pixels.
+    pub tracking_right: f64,
+
+    /// The size of a `GraphicElement` in pixels, which takes up a single atom
+    /// standing on the baseline.
+    pub graphic_size: Option<(f64, f64)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TabAlignment {
+    Start,
+    Center,
+    End,
+    Decimal,
+}
+
+#[derive(Clone, Debug)]
+pub struct TabStop {
+    pub alignment: TabAlignment,
+
+    /// The position of the tab stop, in pixels from the start of the text block.
+    pub position: f64,
+
+    /// The text that `Decimal` tab stops align to.
+    pub decimal_token: WString,
+}
+
+/// Which lines of a text block are justified, matching `LineJustification`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineJustification {
+    #[default]
+    Unjustified,
+    AllButLast,
+    AllButMandatoryBreak,
+    AllIncludingLast,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Justifier {
+    pub line_justification: LineJustification,
+
+    /// Whether extra space is added between every letter, rather than only at spaces.
+    pub letter_spacing: bool,
+}
+
+/// The properties of a `TextBlock` that affect how its lines are laid out.
+#[derive(Clone, Debug, Default)]
+pub struct TextBlockSettings {
+    pub tab_stops: Vec<TabStop>,
+    pub justifier: Justifier,
+
+    /// The embedding level of the paragraph; odd levels are right-to-left.
+    pub bidi_level: u8,
+}
+
+/// The parameters of a single request for a line.
+#[derive(Clone, Copy, Debug)]
+pub struct LineRequest {
+    /// The index into the text block's text that the line starts at.
+    pub begin: usize,
+
+    /// The width available to the line, in pixels.
+    pub width: f64,
+
+    /// The position of the line relative to the start of the block, used to place tabs.
+    pub line_offset: f64,
+
+    /// Whether to place at least one atom on the line, even if it doesn't fit.
+    pub fit_something: bool,
+}
+
+/// The outcome of requesting a line, matching `TextLineCreationResult`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineCreationResult {
+    Success,
+
+    /// The line was broken in the middle of a word, as no break opportunity fit.
+    Emergency,
+
+    /// Nothing fit in the requested width.
+    InsufficientWidth,
+
+    /// There is no text left in the block.
+    Complete,
+}
+
+impl LineCreationResult {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Emergency => "emergency",
+            Self::InsufficientWidth => "insufficientWidth",
+            Self::Complete => "complete",
+        }
+    }
+}
+
+/// A single character of a laid-out line.
+#[derive(Clone, Debug)]
+pub struct Atom {
+    /// The index of the atom's text, relative to the start of the line.
+    pub begin: usize,
+    pub end: usize,
+
+    /// The bounds of the atom in pixels, relative to the line's origin on its baseline.
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+
+    pub bidi_level: u8,
+
+    pub word_boundary_on_left: bool,
+}
+
+/// The measurements of a laid-out line, as reported by `TextLine`.
+#[derive(Clone, Debug, Default)]
+pub struct TextLineLayout {
+    /// The index into the text block's text that the line starts at.
+    pub begin: usize,
+
+    pub raw_text_length: usize,
+
+    /// The distance from the baseline to the top of the tallest glyph, in pixels.
+    pub ascent: f64,
+
+    /// The distance from the baseline to the bottom of the lowest glyph, in pixels.
+    pub descent: f64,
+
+    pub text_width: f64,
+    pub unjustified_text_width: f64,
+    pub has_tabs: bool,
+    pub has_graphic_element: bool,
+
+    /// The atoms of the line, in logical order.
+    pub atoms: Vec<Atom>,
+}
+
+impl TextLineLayout {
+    pub fn atom_at_char_index(&self, char_index: usize) -> Option<usize> {
+        let index = char_index.checked_sub(self.begin)?;
+        self.atoms
+            .iter()
+            .position(|atom| atom.begin <= index && index < atom.end)
+    }
+
+    /// Find the atom under a point relative to the line's origin, in pixels.
+    pub fn atom_at_point(&self, x: f64, y: f64) -> Option<usize> {
+        if y < -self.ascent || y > self.descent {
+            return None;
+        }
+        self.atoms
+            .iter()
+            .position(|atom| atom.x <= x && x < atom.x + atom.width)
+    }
+}
+
+/// A `GraphicElement` placed on a line.
+#[derive(Clone, Copy, Debug)]
+pub struct PlacedGraphic {
+    /// The index of the element's content run.
+    pub run: usize,
+
+    /// The top-left corner of the element in pixels, relative to the line's origin on its baseline.
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A laid-out line, ready to be displayed by a `TextLine`.
+pub struct LaidOutLine<'gc> {
+    /// The text and formatting of only this line.
+    pub spans: FormatSpans,
+
+    /// A box for every visible glyph, relative to the top-left of the line.
+    pub boxes: Vec<LayoutBox<'gc>>,
+
+    /// The graphic elements on the line, whose graphics are displayed as children of the line.
+    pub graphics: Vec<PlacedGraphic>,
+
+    pub layout: TextLineLayout,
+}
+
+/// A character of the text block, measured with the font of its run.
+#[derive(Clone, Debug)]
+pub(super) struct MeasuredChar {
+    pub run: usize,
+
+    /// The index into the text block's text.
+    pub begin: usize,
+    pub end: usize,
+
+    pub ch: char,
+
+    /// The distance to the next character, including tracking and tabs.
+    pub advance: Twips,
+
+    /// How far the glyph is drawn after the start of the atom.
+    pub glyph_offset: Twips,
+
+    pub ascent: Twips,
+    pub descent: Twips,
+    pub baseline_shift: Twips,
+}
+
+impl MeasuredChar {
+    fn is_tab(&self) -> bool {
+        self.ch == '\t'
+    }
+
+    fn break_opportunity(&self, runs: &[ContentRun]) -> BreakOpportunity {
+        runs[self.run].break_opportunity
+    }
+}
+
+fn is_mandatory_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+fn is_whitespace(c: char) -> bool {
+    c.is_whitespace() && c != '\u{a0}'
+}
+
+fn is_ideographic(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3040..=0x30ff | 0x3400..=0x4dbf | 0x4e00..=0x9fff | 0xac00..=0xd7af | 0xf900..=0xfaff
+    )
+}
+
+/// Whether a line may break between `chars[i]` and the character after it.
+fn can_break_after(chars: &[MeasuredChar], runs: &[ContentRun], i: usize) -> bool {
+    let Some(next) = chars.get(i + 1) else {
+        return true;
+    };
+    let current = &chars[i];
+    match current.break_opportunity(runs) {
+        BreakOpportunity::Any | BreakOpportunity::All => true,
+        BreakOpportunity::None => next.run != current.run,
+        BreakOpportunity::Auto => {
+            if next.break_opportunity(runs) == BreakOpportunity::None && next.run != current.run {
+                return is_whitespace(current.ch);
+            }
+            (is_whitespace(current.ch) && !is_whitespace(next.ch))
+                || (current.ch == '-' && !is_whitespace(next.ch))
+                || is_ideographic(current.ch)
+                || is_ideographic(next.ch)
+        }
+    }
+}
+
+/// Decides when enough characters have been measured to lay out a line.
+///
+/// Measuring stops at the first visible character that can't fit, as the line
+/// has to end before it. Tabs can only make the line wider, so they're left
+/// out of the width here, but a tab aligned to the text after it needs that
+/// text measured up to the next tab.
+pub(super) struct MeasureLimit {
+    width: Twips,
+    x: Twips,
+    has_tabs: bool,
+    overflowed: bool,
+}
+
+impl MeasureLimit {
+    pub(super) fn new(width: Twips) -> Self {
+        Self {
+            width,
+            x: Twips::ZERO,
+            has_tabs: false,
+            overflowed: false,
+        }
+    }
+
+    /// Adds a measured character, returning `true` once no more are needed.
+    pub(super) fn add(&mut self, c: &MeasuredChar) -> bool {
+        // Lines never continue past the end of a paragraph.
+        if is_mandatory_break(c.ch) {
+            return true;
+        }
+
+        if c.is_tab() {
+            if self.overflowed {
+                return true;
+            }
+            self.has_tabs = true;
+            return false;
+        }
+
+        self.x += c.advance;
+        if self.x > self.width && !is_whitespace(c.ch) {
+            self.overflowed = true;
+        }
+        self.overflowed && !self.has_tabs
+    }
+}
+
+/// Measure the characters of a text block from `begin`, until enough are known
+/// to fill a line of the given width.
+///
+/// Also returns the font of every run, which is `None` for runs whose font
+/// couldn't be found or which weren't reached.
+fn measure_chars<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    movie: &Arc<SwfMovie>,
+    runs: &[ContentRun],
+    spans: &[TextSpan],
+    begin: usize,
+    width: Twips,
+) -> (Vec<MeasuredChar>, Vec<Option<Font<'gc>>>) {
+    let mut chars = Vec::new();
+    let mut fonts = vec![None; runs.len()];
+    let mut limit = MeasureLimit::new(width);
+    let mut run_start = 0;
+
+    for (run_index, (run, span)) in runs.iter().zip(spans).enumerate() {
+        let run_end = run_start + run.text.len();
+        if run_end <= begin {
+            run_start = run_end;
+            continue;
+        }
+
+        let Some(font) = resolve_font(context, movie.clone(), span, run.is_device_font) else {
+            run_start = run_end;
+            continue;
+        };
+        fonts[run_index] = Some(font);
+        let params = EvalParameters::from_span(span);
+        let height = params.height();
+        let (ascent, descent) = match run.graphic_size {
+            Some((_, height)) => (Twips::from_pixels(height), Twips::ZERO),
+            None => (
+                font.get_baseline_for_height(height),
+                font.get_descent_for_height(height),
+            ),
+        };
+        let tracking_left = Twips::from_pixels(run.tracking_left);
+        let tracking = tracking_left + Twips::from_pixels(run.tracking_right);
+
+        let offset = begin.saturating_sub(run_start);
+        let text = &run.text[offset..];
+        let mut char_indices = text.char_indices().peekable();
+        while let Some((pos, ch)) = char_indices.next() {
+            let ch = ch.unwrap_or(char::REPLACEMENT_CHARACTER);
+            let end = char_indices.peek().map_or(text.len(), |(end, _)| *end);
+
+            // Kerning depends on the next character, so it's measured along with this one.
+            let advance = if is_mandatory_break(ch) {
+                Twips::ZERO
+            } else if let Some((graphic_width, _)) = run.graphic_size {
+                Twips::from_pixels(graphic_width) + tracking
+            } else {
+                let kerning_end = text[end..]
+                    .char_indices()
+                    .nth(1)
+                    .map_or(text.len(), |(next_end, _)| end + next_end);
+                let mut advance = Twips::ZERO;
+                font.evaluate(
+                    &text[pos..kerning_end],
+                    Default::default(),
+                    params,
+                    |glyph_pos, _transform, _glyph, glyph_advance, _x| {
+                        if glyph_pos == 0 {
+                            advance = glyph_advance;
+                        }
+                    },
+                );
+                advance + tracking
+            };
+
+            let c = MeasuredChar {
+                run: run_index,
+                begin: run_start + offset + pos,
+                end: run_start + offset + end,
+                ch,
+                advance,
+                glyph_offset: tracking_left,
+                ascent,
+                descent,
+                baseline_shift: Twips::from_pixels(run.baseline_shift),
+            };
+            let done = limit.add(&c);
+            chars.push(c);
+            if done {
+                return (chars, fonts);
+            }
+        }
+
+        run_start = run_end;
+    }
+
+    (chars, fonts)
+}
+
+/// Calculate how far a tab at `chars[i]` has to advance when it starts at `x`.
+fn tab_advance(
+    chars: &[MeasuredChar],
+    spans: &[TextSpan],
+    i: usize,
+    x: Twips,
+    settings: &TextBlockSettings,
+    line_offset: f64,
+) -> Twips {
+    let stop = settings
+        .tab_stops
+        .iter()
+        .map(|stop| (stop, Twips::from_pixels(stop.position - line_offset)))
+        .find(|(_, position)| *position > x);
+
+    let Some((stop, position)) = stop else {
+        // Past the last tab stop, use the same default stops as `EditText`.
+        let interval = Twips::from_pixels(spans[chars[i].run].size * 2.7)
+            .get()
+            .max(1);
+        return Twips::new((x.get() / interval + 1) * interval) - x;
+    };
+
+    let segment = chars[i + 1..]
+        .iter()
+        .take_while(|c| !c.is_tab() && !is_mandatory_break(c.ch));
+    let start = match stop.alignment {
+        TabAlignment::Start => position,
+        TabAlignment::Center => {
+            let width = segment.fold(Twips::ZERO, |width, c| width + c.advance);
+            position - width / 2
+        }
+        TabAlignment::End => {
+            let width = segment.fold(Twips::ZERO, |width, c| width + c.advance);
+            position - width
+        }
+        TabAlignment::Decimal => {
+            let segment: Vec<_> = segment.collect();
+            let token: Vec<char> = stop
+                .decimal_token
+                .chars()
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect();
+            let token_start = (0..segment.len())
+                .find(|&j| {
+                    !token.is_empty()
+                        && segment[j..]
+                            .iter()
+                            .map(|c| c.ch)
+                            .take(token.len())
+                            .eq(token.iter().copied())
+                })
+                .unwrap_or(segment.len());
+            let width = segment[..token_start]
+                .iter()
+                .fold(Twips::ZERO, |width, c| width + c.advance);
+            position - width
+        }
+    };
+
+    (start - x).max(Twips::ZERO)
+}
+
+/// A simplified classification of characters for the Unicode bidirectional algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum BidiClass {
+    Left,
+    Right,
+    Number,
+    Neutral,
+}
+
+impl BidiClass {
+    pub(super) fn of(c: char) -> Self {
+        match c as u32 {
+            0x0030..=0x0039 | 0x0660..=0x0669 | 0x06f0..=0x06f9 => Self::Number,
+            0x0590..=0x08ff | 0xfb1d..=0xfdff | 0xfe70..=0xfeff => Self::Right,
+            _ if c.is_alphabetic() => Self::Left,
+            _ => Self::Neutral,
+        }
+    }
+}
+
+/// Resolve the embedding level of each character of a line.
+///
+/// This follows the broad strokes of the Unicode bidirectional algorithm:
+/// numbers follow the direction of the preceding strong text, neutrals take the
+/// direction of the text around them if both sides agree, and trailing
+/// whitespace is reset to the paragraph level. Explicit embeddings and
+/// bracket pairs are not supported.
+pub(super) fn resolve_bidi_levels(classes: &[BidiClass], base: u8) -> Vec<u8> {
+    let base_rtl = base % 2 == 1;
+    let level_for = |rtl: bool| if rtl == base_rtl { base } else { base + 1 };
+
+    // The direction of the preceding strong character, for each character.
+    let mut previous = Vec::with_capacity(classes.len());
+    let mut last_strong = base_rtl;
+    for class in classes {
+        previous.push(last_strong);
+        match class {
+            BidiClass::Left => last_strong = false,
+            BidiClass::Right => last_strong = true,
+            _ => {}
+        }
+    }
+
+    let mut levels = vec![base; classes.len()];
+    let mut next_strong = base_rtl;
+    for (i, class) in classes.iter().enumerate().rev() {
+        levels[i] = match class {
+            BidiClass::Left => level_for(false),
+            BidiClass::Right => level_for(true),
+            // Numbers after right-to-left text are laid out left-to-right, embedded within it.
+            BidiClass::Number if previous[i] => {
+                if base_rtl {
+                    base + 1
+                } else {
+                    base + 2
+                }
+            }
+            BidiClass::Number => level_for(false),
+            BidiClass::Neutral if previous[i] == next_strong => level_for(next_strong),
+            BidiClass::Neutral => base,
+        };
+        match class {
+            BidiClass::Left => next_strong = false,
+            // Numbers count as right-to-left when resolving the neutrals before them.
+            BidiClass::Right | BidiClass::Number => next_strong = true,
+            BidiClass::Neutral => {}
+        }
+    }
+
+    levels
+}
+
+/// Find the visual order of characters from their embedding levels.
+pub(super) fn reorder_by_levels(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let Some(&highest) = levels.iter().max() else {
+        return order;
+    };
+    let Some(lowest_odd) = levels.iter().filter(|level| *level % 2 == 1).min() else {
+        return order;
+    };
+
+    for level in (*lowest_odd..=highest).rev() {
+        let mut i = 0;
+        while i < order.len() {
+            if levels[order[i]] < level {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < order.len() && levels[order[i]] >= level {
+                i += 1;
+            }
+            order[start..i].reverse();
+        }
+    }
+
+    order
+}
+
+/// Where a line ends, as found by `find_line_end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct LineEnd {
+    /// The number of characters on the line.
+    pub end: usize,
+
+    pub result: LineCreationResult,
+
+    /// Whether the line ended at a mandatory break, rather than running out of space.
+    pub is_mandatory: bool,
+}
+
+/// Find how many of the measured characters fit on a line, expanding tabs on the way.
+///
+/// Returns `None` if nothing fits, and `request.fit_something` is false.
+pub(super) fn find_line_end(
+    chars: &mut [MeasuredChar],
+    runs: &[ContentRun],
+    spans: &[TextSpan],
+    settings: &TextBlockSettings,
+    request: LineRequest,
+) -> Option<LineEnd> {
+    let width = Twips::from_pixels(request.width);
+    let mut line_end = LineEnd {
+        end: chars.len(),
+        result: LineCreationResult::Success,
+        is_mandatory: false,
+    };
+    let mut x = Twips::ZERO;
+    let mut last_break = None;
+    for i in 0..chars.len() {
+        if chars[i].is_tab() {
+            chars[i].advance = tab_advance(chars, spans, i, x, settings, request.line_offset);
+        }
+
+        let c = &chars[i];
+        if is_mandatory_break(c.ch) {
+            line_end.end = i + 1;
+            line_end.is_mandatory = true;
+            break;
+        }
+
+        if !is_whitespace(c.ch) && x + c.advance > width {
+            if let Some(last_break) = last_break {
+                line_end.end = last_break;
+            } else if i > 0 {
+                line_end.end = i;
+                line_end.result = LineCreationResult::Emergency;
+            } else if request.fit_something {
+                line_end.end = 1;
+            } else {
+                return None;
+            }
+            break;
+        }
+
+        x += c.advance;
+        if c.break_opportunity(runs) == BreakOpportunity::All {
+            line_end.end = i + 1;
+            line_end.is_mandatory = true;
+            break;
+        }
+        if can_break_after(chars, runs, i) {
+            last_break = Some(i + 1);
+        }
+    }
+
+    Some(line_end)
+}
+
+/// Lay out the line of a text block that starts at `request.begin`.
+pub fn layout_line<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    movie: Arc<SwfMovie>,
+    runs: &[ContentRun],
+    settings: &TextBlockSettings,
+    request: LineRequest,
+) -> (LineCreationResult, Option<LaidOutLine<'gc>>) {
+    let block_length: usize = runs.iter().map(|run| run.text.len()).sum();
+    let spans: Vec<_> = runs
+        .iter()
+        .map(|run| TextSpan::with_length_and_format(run.text.len(), run.format.clone()))
+        .collect();
+    let width = Twips::from_pixels(request.width);
+    let (mut chars, fonts) = measure_chars(context, &movie, runs, &spans, request.begin, width);
+    if chars.is_empty() {
+        return (LineCreationResult::Complete, None);
+    }
+
+    let Some(line_end) = find_line_end(&mut chars, runs, &spans, settings, request) else {
+        return (LineCreationResult::InsufficientWidth, None);
+    };
+    let LineEnd {
+        end,
+        result,
+        is_mandatory,
+    } = line_end;
+    chars.truncate(end);
+    let has_tabs = chars.iter().any(|c| c.is_tab());
+    let has_graphic_element = chars.iter().any(|c| runs[c.run].graphic_size.is_some());
+    let is_last_line = chars[chars.len() - 1].end >= block_length;
+
+    let unjustified_width = chars.iter().fold(Twips::ZERO, |width, c| width + c.advance);
+
+    // Spread out the remaining space if the line is justified.
+    let justify = match settings.justifier.line_justification {
+        LineJustification::Unjustified => false,
+        LineJustification::AllButLast => !is_last_line,
+        LineJustification::AllButMandatoryBreak => !is_last_line && !is_mandatory,
+        LineJustification::AllIncludingLast => true,
+    };
+    if justify {
+        justify_chars(&mut chars, width, settings.justifier.letter_spacing);
+    }
+
+    // Bidirectional text is measured in logical order but displayed in visual order.
+    let classes: Vec<_> = chars.iter().map(|c| BidiClass::of(c.ch)).collect();
+    let mut levels = resolve_bidi_levels(&classes, settings.bidi_level);
+    for (level, c) in levels.iter_mut().zip(&chars).rev() {
+        if !is_whitespace(c.ch) {
+            break;
+        }
+        *level = settings.bidi_level;
+    }
+    let order = reorder_by_levels(&levels);
+
+    let line_begin = chars[0].begin;
+    let line_end = chars[chars.len() - 1].end;
+    let (ascent, descent) = chars.iter().fold((Twips::ZERO, Twips::ZERO), |(a, d), c| {
+        (
+            a.max(c.ascent - c.baseline_shift),
+            d.max(c.descent + c.baseline_shift),
+        )
+    });
+
+    let mut atoms = vec![None; chars.len()];
+    let mut boxes = Vec::new();
+    let mut graphics = Vec::new();
+    let mut x = Twips::ZERO;
+    for &i in &order {
+        let c = &chars[i];
+        let begin = c.begin - line_begin;
+        let end = c.end - line_begin;
+        let previous = i.checked_sub(1).map(|p| &chars[p]);
+
+        atoms[i] = Some(Atom {
+            begin,
+            end,
+            x: x.to_pixels(),
+            y: (c.baseline_shift - c.ascent).to_pixels(),
+            width: c.advance.to_pixels(),
+            height: (c.ascent + c.descent).to_pixels(),
+            bidi_level: levels[i],
+            word_boundary_on_left: previous
+                .map_or(true, |p| is_whitespace(p.ch) != is_whitespace(c.ch)),
+        });
+
+        if runs[c.run].graphic_size.is_some() {
+            graphics.push(PlacedGraphic {
+                run: c.run,
+                x: (x + c.glyph_offset).to_pixels(),
+                y: (c.baseline_shift - c.ascent).to_pixels(),
+            });
+        }
+
+        let font = fonts[c.run].filter(|font| font.get_glyph_for_char(c.ch).is_some());
+        if let Some(font) = font.filter(|_| !is_whitespace(c.ch)) {
+            let position =
+                Position::from((x + c.glyph_offset, ascent + c.baseline_shift - c.ascent));
+            let size = Size::from((c.advance, c.ascent + c.descent));
+            boxes.push(
+                LayoutBox::from_text(begin, end, font, &spans[c.run])
+                    .with_bounds(BoxBounds::from_position_and_size(position, size)),
+            );
+        }
+
+        x += c.advance;
+    }
+
+    // Give the line its own copy of the formatted text it contains.
+    let mut text = WString::new();
+    let mut line_spans = Vec::new();
+    let mut run_start = 0;
+    for run in runs {
+        let run_end = run_start + run.text.len();
+        let start = line_begin.clamp(run_start, run_end);
+        let end = line_end.clamp(run_start, run_end);
+        if start < end {
+            text.push_str(&run.text[start - run_start..end - run_start]);
+            line_spans.push(TextSpan::with_length_and_format(
+                end - start,
+                run.format.clone(),
+            ));
+        }
+        run_start = run_end;
+    }
+
+    let layout = TextLineLayout {
+        begin: line_begin,
+        raw_text_length: line_end - line_begin,
+        ascent: ascent.to_pixels(),
+        descent: descent.to_pixels(),
+        text_width: x.to_pixels(),
+        unjustified_text_width: unjustified_width.to_pixels(),
+        has_tabs,
+        has_graphic_element,
+        atoms: atoms.into_iter().flatten().collect(),
+    };
+
+    let line = LaidOutLine {
+        spans: FormatSpans::from_str_and_spans(&text, &line_spans),
+        boxes,
+        graphics,
+        layout,
+    };
+
+    (result, Some(line))
+}
+
+/// Add the space left over on a line between its words, or its letters.
+pub(super) fn justify_chars(chars: &mut [MeasuredChar], width: Twips, letter_spacing: bool) {
+    // Trailing whitespace hangs past the end of the line and is never expanded.
+    let Some(last_visible) = chars.iter().rposition(|c| !is_whitespace(c.ch)) else {
+        return;
+    };
+    let used = chars[..=last_visible]
+        .iter()
+        .fold(Twips::ZERO, |width, c| width + c.advance);
+    if used >= width {
+        return;
+    }
+
+    // Space before a tab would only move the tab, so only justify after the last one.
+    let first = chars[..last_visible]
+        .iter()
+        .rposition(|c| c.is_tab())
+        .map_or(0, |tab| tab + 1);
+    let expandable: Vec<usize> = (first..last_visible)
+        .filter(|&i| letter_spacing || is_whitespace(chars[i].ch))
+        .collect();
+    if expandable.is_empty() {
+        return;
+    }
+
+    let extra = width - used;
+    let count = expandable.len() as i32;
+    for (n, &i) in expandable.iter().enumerate() {
+        // Hand out the remainder one twip at a time, so the line ends exactly at `width`.
+        let remainder = i32::from((n as i32) < extra.get() % count);
+        chars[i].advance += extra / count + Twips::new(remainder);
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::style_sheet::{Display, StyleSheet};
 use crate::string::{Integer, SwfStrExt as _, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -98,6 +99,30 @@ fn process_html_entity(src: &WStr) -> Option<WString> {
     Some(result_str)
 }
 
+fn is_html_white(ch: u16) -> bool {
+    matches!(u8::try_from(ch), Ok(b' ' | b'\t' | b'\n' | b'\r'))
+}
+
+/// Collapse each run of whitespace into a single space, as done by `TextField.condenseWhite`.
+///
+/// Whitespace at the start is removed entirely if the text so far already ends with
+/// whitespace (`after_white`). Line breaks only come from tags like `<br>` and `<p>`.
+fn condense_white(src: &WStr, mut after_white: bool) -> WString {
+    let mut result_str = WString::with_capacity(src.len(), src.is_wide());
+    for ch in src.iter() {
+        if is_html_white(ch) {
+            if !after_white {
+                result_str.push_byte(b' ');
+            }
+            after_white = true;
+        } else {
+            result_str.push(ch);
+            after_white = false;
+        }
+    }
+    result_str
+}
+
 /// A set of text formatting options to be applied to some part, or the whole
 /// of, a given text field.
 ///
@@ -540,7 +565,6 @@ impl FormatSpans {
     }
 
     /// Construct a format span from its raw parts.
-    #[allow(dead_code)]
     pub fn from_str_and_spans(text: &WStr, spans: &[TextSpan]) -> Self {
         Self {
             text: text.into(),
@@ -562,11 +586,18 @@ impl FormatSpans {
 
     /// Lower an HTML tree into text-span representation.
     ///
-    /// This is the "legacy" implementation of this process: it only looks for
-    /// a handful of presentational attributes in the HTML tree to generate
-    /// styling. There's also a `lower_from_css` that respects both
-    /// presentational markup and CSS stylesheets.
-    pub fn from_html(html: &WStr, default_format: TextFormat, is_multiline: bool) -> Self {
+    /// This looks for a handful of presentational attributes in the HTML tree
+    /// to generate styling. If a `style_sheet` is given, styles for tags,
+    /// classes and links are applied on top of them.
+    ///
+    /// With `condense_white`, extra whitespace in the source is removed.
+    pub fn from_html(
+        html: &WStr,
+        default_format: TextFormat,
+        is_multiline: bool,
+        condense_white: bool,
+        style_sheet: Option<&StyleSheet>,
+    ) -> Self {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
@@ -601,6 +632,9 @@ impl FormatSpans {
         let mut opened_buffer: Vec<u8> = Vec::new();
         let mut opened_starts = Vec::new();
 
+        // The `display` of each open element that has one in the style sheet.
+        let mut display_stack: Vec<Option<Display>> = Vec::new();
+
         let mut reader = Reader::from_reader(&raw_bytes[..]);
         reader.expand_empty_elements(true);
         reader.check_end_names(false);
@@ -766,10 +800,45 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+
+                    let mut display = None;
+                    if let Some(style_sheet) = style_sheet {
+                        let tag = decode_to_wstr(&e.name().into_inner().to_ascii_lowercase());
+                        let class = attribute(b"class").map(|class| {
+                            let mut selector = WString::from_utf8(".");
+                            selector.push_str(&class);
+                            selector
+                        });
+                        let link =
+                            (tag == WStr::from_units(b"a")).then_some(WStr::from_units(b"a:link"));
+                        // Later styles take priority over earlier ones.
+                        let styles = [Some(tag.as_wstr()), class.as_deref(), link]
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|name| style_sheet.style(name));
+                        for style in styles {
+                            format = style.text_format().mix_with(format);
+                            display = style.display().or(display);
+                        }
+                    }
                     format_stack.push(format);
+                    display_stack.push(display);
                 }
                 Ok(Event::Text(e)) if !e.is_empty() => {
-                    let e = decode_to_wstr(&e.into_inner());
+                    if display_stack.contains(&Some(Display::None)) {
+                        continue;
+                    }
+                    let mut e = decode_to_wstr(&e.into_inner());
+                    if condense_white {
+                        let after_white = match text.len() {
+                            0 => true,
+                            len => is_html_white(text.at(len - 1)),
+                        };
+                        e = self::condense_white(&e, after_white);
+                        if e.is_empty() {
+                            continue;
+                        }
+                    }
                     let e = process_html_entity(&e).unwrap_or(e);
                     let format = format_stack.last().unwrap().clone();
                     text.push_str(&e);
@@ -789,24 +858,27 @@ impl FormatSpans {
                         None => continue,
                     }
 
-                    match &e.name().into_inner().to_ascii_lowercase()[..] {
-                        b"br" | b"sbr" => {
-                            // Skip pop from `format_stack`.
-                            continue;
-                        }
-                        b"p" | b"li" if is_multiline => {
-                            text.push_byte(b'\n');
-                            if let Some(span) = spans.last_mut() {
-                                span.span_length += 1;
-                            } else {
-                                // This must be at the start; make an empty span so our total length is correct
-                                spans.push(TextSpan::with_length_and_format(
-                                    1,
-                                    format_stack.last().unwrap().clone(),
-                                ));
-                            }
+                    let tag = e.name().into_inner().to_ascii_lowercase();
+                    if matches!(&tag[..], b"br" | b"sbr") {
+                        // Skip pop from `format_stack`.
+                        continue;
+                    }
+
+                    let is_block = match display_stack.pop().flatten() {
+                        Some(display) => display == Display::Block,
+                        None => matches!(&tag[..], b"p" | b"li"),
+                    };
+                    if is_block && is_multiline {
+                        text.push_byte(b'\n');
+                        if let Some(span) = spans.last_mut() {
+                            span.span_length += 1;
+                        } else {
+                            // This must be at the start; make an empty span so our total length is correct
+                            spans.push(TextSpan::with_length_and_format(
+                                1,
+                                format_stack.last().unwrap().clone(),
+                            ));
                         }
-                        _ => {}
                     }
                     format_stack.pop();
                 }
@@ -29,6 +29,7 @@ pub mod buffer;
 mod character;
 pub mod context;
 pub mod context_menu;
+mod debugger;
 mod drawing;
 mod ecma_conversions;
 pub(crate) mod either;
@@ -36,15 +37,20 @@ pub mod events;
 pub mod focus_tracker;
 mod font;
 mod frame_lifecycle;
+mod globalization;
 mod html;
 mod library;
 pub mod limits;
 pub mod loader;
+mod local_connection;
 mod locale;
 mod net_connection;
 pub mod pixel_bender;
 mod player;
+mod policy_file;
 mod prelude;
+pub mod profiler;
+mod shared_object;
 pub mod socket;
 mod streams;
 pub mod string;
@@ -9,10 +9,12 @@ use crate::avm2::object::{
     BitmapDataObject, ByteArrayObject, EventObject as Avm2EventObject, LoaderStream, TObject as _,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Object as Avm2Object,
+    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Multiname, Object as Avm2Object,
     Value as Avm2Value,
 };
-use crate::backend::navigator::{OwnedFuture, Request};
+use crate::backend::navigator::{
+    ErrorResponse, OwnedFuture, Request, ResponseBody, SuccessResponse,
+};
 use crate::backend::ui::DialogResultFuture;
 use crate::bitmap::bitmap_data::Color;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
@@ -24,9 +26,10 @@ use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
 use crate::player::Player;
+use crate::policy_file::{check_url_access, url_policy_check};
 use crate::streams::NetStream;
 use crate::string::AvmString;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{ProgressiveSwf, SwfMovie};
 use crate::vminterface::Instantiator;
 use encoding_rs::UTF_8;
 use gc_arena::{Collect, GcCell};
@@ -240,6 +243,7 @@ impl<'gc> LoadManager<'gc> {
             | Loader::Form { self_handle, .. }
             | Loader::LoadVars { self_handle, .. }
             | Loader::LoadURLLoader { self_handle, .. }
+            | Loader::LoadURLStream { self_handle, .. }
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
             | Loader::NetStream { self_handle, .. }
@@ -405,6 +409,27 @@ impl<'gc> LoadManager<'gc> {
         loader.load_url_loader(player, request, data_format)
     }
 
+    /// Kick off a data load into a `URLStream`, appending to its buffer
+    /// as the data is received.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_data_into_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        buffer: Avm2Object<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::LoadURLStream {
+            self_handle: None,
+            target_object,
+            buffer,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.load_url_stream(player, request)
+    }
+
     /// Kick off an AVM1 audio load.
     ///
     /// Returns the loader's async process, which you will need to spawn.
@@ -465,14 +490,22 @@ impl<'gc> LoadManager<'gc> {
         let handles: Vec<_> = context.load_manager.0.iter().map(|(h, _)| h).collect();
 
         for handle in handles {
-            let status = match context.load_manager.get_loader(handle) {
-                Some(Loader::Movie { loader_status, .. }) => Some(loader_status),
-                _ => None,
+            let (status, downloading) = match context.load_manager.get_loader(handle) {
+                Some(Loader::Movie {
+                    loader_status,
+                    movie,
+                    ..
+                }) => (
+                    Some(loader_status),
+                    movie.as_ref().is_some_and(|movie| !movie.is_complete()),
+                ),
+                _ => (None, false),
             };
 
-            if matches!(status, Some(LoaderStatus::Parsing)) {
+            if matches!(status, Some(LoaderStatus::Parsing | LoaderStatus::Playing)) {
                 match Loader::preload_tick(handle, context, limit, 0, false) {
-                    Ok(f) => did_finish = did_finish && f,
+                    // A movie that waits for the rest of its download doesn't hold up the player.
+                    Ok(f) => did_finish = did_finish && (f || downloading),
                     Err(e) => tracing::error!("Error encountered while preloading movie: {}", e),
                 }
             }
@@ -555,6 +588,8 @@ pub enum LoaderStatus {
     Pending,
     /// The movie is currently being parsed (e.g. mc.preload)
     Parsing,
+    /// The movie is already shown, while the rest of it is still downloaded and parsed.
+    Playing,
     /// The movie loaded successfully.
     Succeeded,
     /// An error occurred while loading the movie.
@@ -651,6 +686,22 @@ pub enum Loader<'gc> {
         target_object: Avm2Object<'gc>,
     },
 
+    /// Loader that is streaming data into a `URLStream` as it's received.
+    LoadURLStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The target `URLStream` that receives events.
+        target_object: Avm2Object<'gc>,
+
+        /// The `ByteArray` the data is appended to.
+        ///
+        /// The stream replaces its buffer when it's closed or starts another
+        /// load, which cancels this one.
+        buffer: Avm2Object<'gc>,
+    },
+
     /// Loader that is loading an MP3 into an AVM1 Sound object.
     SoundAvm1 {
         /// The handle to refer to this loader instance.
@@ -740,9 +791,12 @@ impl<'gc> Loader<'gc> {
         status: u16,
         redirected: bool,
     ) -> Result<bool, Error> {
-        let mc = match context.load_manager.get_loader_mut(handle) {
+        let (mc, is_avm2) = match context.load_manager.get_loader_mut(handle) {
             Some(Self::Movie {
-                target_clip, movie, ..
+                target_clip,
+                movie,
+                vm_data,
+                ..
             }) => {
                 if movie.is_none() {
                     //Non-SWF load or file not loaded yet
@@ -755,7 +809,10 @@ impl<'gc> Loader<'gc> {
                     return Ok(false);
                 }
 
-                *target_clip
+                (
+                    *target_clip,
+                    matches!(vm_data, MovieLoaderVMData::Avm2 { .. }),
+                )
             }
             None => return Err(Error::Cancelled),
             Some(_) => panic!("Attempted to preload a non-SWF loader"),
@@ -774,6 +831,10 @@ impl<'gc> Loader<'gc> {
 
         if did_finish {
             Loader::movie_loader_complete(handle, context, Some(mc.into()), status, redirected)?;
+        } else if is_avm2 && !mc.movie().is_complete() && mc.frames_loaded() >= 1 {
+            // A movie that's still downloading is added to its `Loader` as soon as its
+            // first frame arrived. AVM1 movies already replaced their target clip.
+            Loader::movie_loader_init(handle, context, Some(mc.into()))?;
         }
 
         Ok(did_finish)
@@ -857,9 +918,10 @@ impl<'gc> Loader<'gc> {
             let request_url = request.url().to_string();
             let resolved_url = player.lock().unwrap().navigator().resolve_url(&request_url);
 
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().navigator().fetch_stream(request);
 
             let mut replacing_root_movie = false;
+            let mut progress_reported = false;
             player.lock().unwrap().update(|uc| -> Result<(), Error> {
                 let clip = match uc.load_manager.get_loader(handle) {
                     Some(Loader::Movie { target_clip, .. }) => *target_clip,
@@ -885,7 +947,88 @@ impl<'gc> Loader<'gc> {
                 Loader::movie_loader_start(handle, uc)
             })?;
 
-            match fetch.await {
+            let response = match fetch.await {
+                Ok(response) if replacing_root_movie => response.into_complete().await,
+                Ok(mut response) => {
+                    // SWFs start playing while they're downloading. Until then, the download
+                    // progress is reported, so that preloaders don't freeze until the whole
+                    // file is received. The final chunk is reported once it's parsed.
+                    let expected_length = response.expected_length.map(|len| len as usize);
+                    let bytes_total = expected_length.unwrap_or_default();
+                    let mut progressive = ProgressiveSwf::new(
+                        response.url.clone(),
+                        loader_url.clone(),
+                        expected_length,
+                    );
+                    // The movie is only started once more data arrives after it's ready,
+                    // so that files received at once are loaded the same as before.
+                    let mut ready_movie = None;
+                    let mut started = false;
+                    let mut body = Vec::new();
+                    let (url, status, redirected) =
+                        (response.url.clone(), response.status, response.redirected);
+                    let received = receive_body(
+                        &player,
+                        &response.url,
+                        &mut *response.body,
+                        |uc, chunk, bytes_loaded| {
+                            if started {
+                                progressive.push(&chunk);
+                                return Ok(());
+                            }
+
+                            body.extend_from_slice(&chunk);
+                            let new_movie = progressive.push(&chunk);
+                            if let Some(movie) = ready_movie.take() {
+                                started = true;
+                                body = Vec::new();
+                                // Only the pixels of images depend on whether the content is accessible.
+                                return Loader::movie_loader_data(
+                                    handle,
+                                    uc,
+                                    &[],
+                                    url.clone(),
+                                    status,
+                                    redirected,
+                                    loader_url.clone(),
+                                    true,
+                                    progress_reported,
+                                    Some(movie),
+                                );
+                            }
+                            ready_movie = new_movie;
+
+                            if bytes_loaded < bytes_total {
+                                progress_reported = true;
+                                Loader::movie_loader_progress(
+                                    handle,
+                                    uc,
+                                    bytes_loaded,
+                                    bytes_total,
+                                )?;
+                            }
+                            Ok(())
+                        },
+                    )
+                    .await?;
+                    progressive.finish();
+
+                    match received {
+                        // The movie already received its data as it arrived.
+                        Ok(()) if started => return Ok(()),
+                        Ok(()) => Ok(SuccessResponse {
+                            url: response.url,
+                            body,
+                            status: response.status,
+                            redirected: response.redirected,
+                        }),
+                        Err(response) => Err(response),
+                    }
+                }
+                Err(response) => Err(response),
+            };
+
+            match response {
                 Ok(response) if replacing_root_movie => {
                     ContentType::sniff(&response.body).expect(ContentType::Swf)?;
 
@@ -894,15 +1037,49 @@ impl<'gc> Loader<'gc> {
                     return Ok(());
                 }
                 Ok(response) => {
-                    Loader::movie_loader_data(
-                        handle,
-                        player,
-                        &response.body,
-                        response.url,
-                        response.status,
-                        response.redirected,
-                        loader_url,
-                    )?;
+                    // The pixels of images from other domains are only accessible if the movie
+                    // asked for a policy file with `LoaderContext.checkPolicyFile`, and it grants access.
+                    let (needs_policy, check_policy_file) =
+                        player.lock().unwrap().mutate_with_update_context(|uc| {
+                            let context = match uc.load_manager.get_loader(handle) {
+                                Some(Loader::Movie {
+                                    vm_data: MovieLoaderVMData::Avm2 { context, .. },
+                                    ..
+                                }) => *context,
+                                _ => None,
+                            };
+                            let check_policy_file = context
+                                .and_then(|context| {
+                                    let mut activation =
+                                        Avm2Activation::from_nothing(uc.reborrow());
+                                    context
+                                        .get_public_property("checkPolicyFile", &mut activation)
+                                        .ok()
+                                })
+                                .map(|value| value.coerce_to_boolean())
+                                .unwrap_or(false);
+                            (
+                                url_policy_check(uc, &response.url).is_some(),
+                                check_policy_file,
+                            )
+                        });
+                    let content_accessible = !needs_policy
+                        || (check_policy_file && check_url_access(&player, &response.url).await);
+
+                    player.lock().unwrap().update(|uc| {
+                        Loader::movie_loader_data(
+                            handle,
+                            uc,
+                            &response.body,
+                            response.url,
+                            response.status,
+                            response.redirected,
+                            loader_url,
+                            content_accessible,
+                            progress_reported,
+                            None,
+                        )
+                    })?;
                 }
                 Err(response) => {
                     tracing::error!(
@@ -985,7 +1162,20 @@ impl<'gc> Loader<'gc> {
                 return Ok(());
             }
 
-            Loader::movie_loader_data(handle, player, &bytes, "file:///".into(), 0, false, None)
+            player.lock().unwrap().update(|uc| {
+                Loader::movie_loader_data(
+                    handle,
+                    uc,
+                    &bytes,
+                    "file:///".into(),
+                    0,
+                    false,
+                    None,
+                    true,
+                    false,
+                    None,
+                )
+            })
         })
     }
 
@@ -1169,8 +1359,85 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = fetch.await;
+            let url = request.url().to_string();
+            if !check_url_access(&player, &url).await {
+                return player.lock().unwrap().update(|uc| {
+                    let target = match uc.load_manager.get_loader(handle) {
+                        Some(&Loader::LoadURLLoader { target_object, .. }) => target_object,
+                        _ => unreachable!(),
+                    };
+                    dispatch_load_security_error(uc, target, &url)
+                });
+            }
+
+            let fetch = player.lock().unwrap().navigator().fetch_stream(request);
+            let response = match fetch.await {
+                Ok(mut response) => {
+                    // A redirect may have led to a domain that didn't grant access.
+                    if response.url != url && !check_url_access(&player, &response.url).await {
+                        return player.lock().unwrap().update(|uc| {
+                            let target = match uc.load_manager.get_loader(handle) {
+                                Some(&Loader::LoadURLLoader { target_object, .. }) => target_object,
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
+                            dispatch_load_security_error(uc, target, &response.url)
+                        });
+                    }
+
+                    let bytes_total = response.expected_length.map_or(0, |len| len as usize);
+                    player.lock().unwrap().update(|uc| {
+                        let target = match uc.load_manager.get_loader(handle) {
+                            Some(&Loader::LoadURLLoader { target_object, .. }) => target_object,
+                            None => return Err(Error::Cancelled),
+                            _ => unreachable!(),
+                        };
+
+                        // The "open" event is only fired once the connection succeeded,
+                        // so it's not fired if e.g. a file doesn't exist on disk.
+                        let open_evt = Avm2EventObject::bare_default_event(uc, "open");
+                        Avm2::dispatch_event(uc, open_evt, target);
+                        Ok(())
+                    })?;
+
+                    let mut body = Vec::new();
+                    receive_body(
+                        &player,
+                        &response.url,
+                        &mut *response.body,
+                        |uc, chunk, bytes_loaded| {
+                            let target = match uc.load_manager.get_loader(handle) {
+                                Some(&Loader::LoadURLLoader { target_object, .. }) => target_object,
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
+                            body.extend(chunk);
+
+                            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                            set_url_loader_progress(
+                                &mut activation,
+                                target,
+                                bytes_loaded,
+                                bytes_total,
+                            )?;
+                            dispatch_progress_event(
+                                &mut activation,
+                                target,
+                                bytes_loaded,
+                                bytes_total,
+                            )
+                        },
+                    )
+                    .await?
+                    .map(|()| SuccessResponse {
+                        url: response.url,
+                        body,
+                        status: response.status,
+                        redirected: response.redirected,
+                    })
+                }
+                Err(response) => Err(response),
+            };
 
             player.lock().unwrap().update(|uc| {
                 let loader = uc.load_manager.get_loader(handle);
@@ -1223,42 +1490,11 @@ impl<'gc> Loader<'gc> {
 
                 match response {
                     Ok(response) => {
+                        // The total length is known for sure now.
                         let total_len = response.body.len();
-
-                        // FIXME - the "open" event should be fired earlier, just before
-                        // we start to fetch the data.
-                        // However, the "open" event should not be fired if an IO error
-                        // occurs opening the connection (e.g. if a file does not exist on disk).
-                        // We currently have no way of detecting this, so we settle for firing
-                        // the event after the entire fetch is complete. This causes there
-                        // to a longer delay between the initial load triggered by the script
-                        // and the "load" event firing, but it ensures that we match
-                        // the Flash behavior w.r.t when an event is fired vs not fired.
-                        let open_evt =
-                            Avm2EventObject::bare_default_event(&mut activation.context, "open");
-                        Avm2::dispatch_event(&mut activation.context, open_evt, target);
+                        set_url_loader_progress(&mut activation, target, total_len, total_len)?;
                         set_data(response.body, &mut activation, target, data_format);
 
-                        // FIXME - we should fire "progress" events as we receive data, not
-                        // just at the end
-                        let progress_evt = activation
-                            .avm2()
-                            .classes()
-                            .progressevent
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "progress".into(),
-                                    false.into(),
-                                    false.into(),
-                                    total_len.into(),
-                                    total_len.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
-
-                        Avm2::dispatch_event(&mut activation.context, progress_evt, target);
-
                         let http_status_evt = activation
                             .avm2()
                             .classes()
@@ -1339,6 +1575,181 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for a LoadURLStream load call.
+    fn load_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::LoadURLStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotLoadDataLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        /// Get the target and buffer of the stream, unless it moved on to another buffer.
+        fn stream_target<'gc>(
+            uc: &mut UpdateContext<'_, 'gc>,
+            handle: Handle,
+        ) -> Result<(Avm2Object<'gc>, Avm2Object<'gc>), Error> {
+            let (target, buffer) = match uc.load_manager.get_loader(handle) {
+                Some(&Loader::LoadURLStream {
+                    target_object,
+                    buffer,
+                    ..
+                }) => (target_object, buffer),
+                None => return Err(Error::Cancelled),
+                _ => unreachable!(),
+            };
+
+            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+            let current_buffer = target
+                .get_property(
+                    &Multiname::new(activation.avm2().flash_net_internal, "_buffer"),
+                    &mut activation,
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            if !current_buffer
+                .as_object()
+                .is_some_and(|current| Avm2Object::ptr_eq(current, buffer))
+            {
+                uc.load_manager.remove_loader(handle);
+                return Err(Error::Cancelled);
+            }
+            Ok((target, buffer))
+        }
+
+        Box::pin(async move {
+            let url = request.url().to_string();
+            if !check_url_access(&player, &url).await {
+                return player.lock().unwrap().update(|uc| {
+                    let (target, _) = stream_target(uc, handle)?;
+                    uc.load_manager.remove_loader(handle);
+                    dispatch_load_security_error(uc, target, &url)
+                });
+            }
+
+            let fetch = player.lock().unwrap().navigator().fetch_stream(request);
+            let result = match fetch.await {
+                Ok(mut response) => {
+                    // A redirect may have led to a domain that didn't grant access.
+                    if response.url != url && !check_url_access(&player, &response.url).await {
+                        return player.lock().unwrap().update(|uc| {
+                            let (target, _) = stream_target(uc, handle)?;
+                            uc.load_manager.remove_loader(handle);
+                            dispatch_load_security_error(uc, target, &response.url)
+                        });
+                    }
+
+                    let bytes_total = response.expected_length.map_or(0, |len| len as usize);
+                    player.lock().unwrap().update(|uc| {
+                        let (target, _) = stream_target(uc, handle)?;
+                        let open_evt = Avm2EventObject::bare_default_event(uc, "open");
+                        Avm2::dispatch_event(uc, open_evt, target);
+                        Ok(())
+                    })?;
+
+                    receive_body(
+                        &player,
+                        &response.url,
+                        &mut *response.body,
+                        |uc, chunk, bytes_loaded| {
+                            let (target, buffer) = stream_target(uc, handle)?;
+                            if let Some(mut buffer) = buffer.as_bytearray_mut(uc.gc_context) {
+                                // Reading moves the position, so new data always goes at the end.
+                                let end = buffer.len();
+                                buffer
+                                    .write_at(&chunk, end)
+                                    .map_err(|_| Error::FetchError("URLStream is full".into()))?;
+                            }
+
+                            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                            dispatch_progress_event(
+                                &mut activation,
+                                target,
+                                bytes_loaded,
+                                bytes_total,
+                            )
+                        },
+                    )
+                    .await?
+                    .map(|()| (response.status, response.redirected))
+                }
+                Err(response) => Err(response),
+            };
+
+            player.lock().unwrap().update(|uc| {
+                let (target, _) = stream_target(uc, handle)?;
+                uc.load_manager.remove_loader(handle);
+
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                let (status, redirected) = match &result {
+                    Ok((status, redirected)) => (*status, *redirected),
+                    Err(ErrorResponse {
+                        error: Error::HttpNotOk(_, status, redirected, _),
+                        ..
+                    }) => (*status, *redirected),
+                    Err(_) => (0, false),
+                };
+                let http_status_evt = activation
+                    .avm2()
+                    .classes()
+                    .httpstatusevent
+                    .construct(
+                        &mut activation,
+                        &[
+                            "httpStatus".into(),
+                            false.into(),
+                            false.into(),
+                            status.into(),
+                            redirected.into(),
+                        ],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+
+                if result.is_ok() {
+                    let complete_evt =
+                        Avm2EventObject::bare_default_event(&mut activation.context, "complete");
+                    Avm2::dispatch_event(uc, complete_evt, target);
+                } else {
+                    // The stream is no longer connected after an error.
+                    target
+                        .set_property(
+                            &Multiname::new(activation.avm2().flash_net_internal, "_connected"),
+                            false.into(),
+                            &mut activation,
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                    let io_error_evt = activation
+                        .avm2()
+                        .classes()
+                        .ioerrorevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "ioError".into(),
+                                false.into(),
+                                false.into(),
+                                "Error #2032: Stream Error".into(),
+                                2032.into(),
+                            ],
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    Avm2::dispatch_event(uc, io_error_evt, target);
+                }
+
+                Ok(())
+            })
+        })
+    }
+
     /// Creates a future for a Sound load call.
     fn sound_loader_avm1(
         &mut self,
@@ -1432,6 +1843,13 @@ impl<'gc> Loader<'gc> {
                     _ => return Err(Error::NotSoundLoader),
                 };
 
+                if sound_object
+                    .as_sound_object()
+                    .map_or(false, |sound| sound.is_closed())
+                {
+                    return Err(Error::Cancelled);
+                }
+
                 match response {
                     Ok(response) => {
                         let handle = uc.audio.register_mp3(&response.body)?;
@@ -1519,30 +1937,57 @@ impl<'gc> Loader<'gc> {
             .upgrade()
             .expect("Could not upgrade weak reference to player");
 
-        Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = fetch.await;
+        fn target_stream<'gc>(
+            uc: &mut UpdateContext<'_, 'gc>,
+            handle: Handle,
+        ) -> Result<NetStream<'gc>, Error> {
+            match uc.load_manager.get_loader(handle) {
+                Some(&Loader::NetStream { target_stream, .. }) => Ok(target_stream),
+                None => Err(Error::Cancelled),
+                _ => Err(Error::NotNetStreamLoader),
+            }
+        }
 
-            player.lock().unwrap().update(|uc| {
-                let loader = uc.load_manager.get_loader(handle);
-                let stream = match loader {
-                    Some(&Loader::NetStream { target_stream, .. }) => target_stream,
-                    None => return Err(Error::Cancelled),
-                    _ => return Err(Error::NotNetStreamLoader),
-                };
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch_stream(request);
+            let response = match fetch.await {
+                Ok(mut response) => {
+                    player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                        target_stream(uc, handle)?.reset_buffer(uc);
+                        Ok(())
+                    })?;
 
-                match response {
-                    Ok(mut response) => {
-                        stream.reset_buffer(uc);
-                        stream.load_buffer(uc, &mut response.body);
-                    }
-                    Err(response) => {
-                        stream.report_error(response.error);
-                    }
+                    // Media is buffered as it's received, so playback can start
+                    // before the download finishes.
+                    let mut buffer_started = false;
+                    receive_body(
+                        &player,
+                        &response.url,
+                        &mut *response.body,
+                        |uc, mut chunk, _| {
+                            let stream = target_stream(uc, handle)?;
+                            if buffer_started {
+                                stream.append_buffer(uc.gc_context, &mut chunk);
+                            } else {
+                                stream.load_buffer(uc, &mut chunk);
+                                buffer_started = true;
+                            }
+                            Ok(())
+                        },
+                    )
+                    .await?
                 }
+                Err(response) => Err(response),
+            };
 
-                Ok(())
-            })
+            if let Err(response) = response {
+                player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                    target_stream(uc, handle)?.report_error(response.error);
+                    Ok(())
+                })?;
+            }
+
+            Ok(())
         })
     }
 
@@ -1588,242 +2033,257 @@ impl<'gc> Loader<'gc> {
     }
 
     /// Load data into a movie loader.
+    ///
+    /// `progress_reported` is set if progress events were already sent while the
+    /// data was being downloaded.
+    ///
+    /// `progressive_movie` is a movie that was started while its SWF is still
+    /// downloading, which is loaded instead of `data`.
+    #[allow(clippy::too_many_arguments)]
     fn movie_loader_data(
         handle: Handle,
-        player: Arc<Mutex<Player>>,
+        uc: &mut UpdateContext<'_, 'gc>,
         data: &[u8],
         url: String,
         status: u16,
         redirected: bool,
         loader_url: Option<String>,
+        content_accessible: bool,
+        progress_reported: bool,
+        progressive_movie: Option<Arc<SwfMovie>>,
     ) -> Result<(), Error> {
-        let sniffed_type = ContentType::sniff(data);
-        let length = data.len();
+        let (sniffed_type, length) = match &progressive_movie {
+            Some(movie) => (ContentType::Swf, movie.compressed_len()),
+            None => (ContentType::sniff(data), data.len()),
+        };
 
         if sniffed_type == ContentType::Unknown {
             if let Ok(data) = extract_swz(data) {
                 return Self::movie_loader_data(
-                    handle, player, &data, url, status, redirected, loader_url,
+                    handle,
+                    uc,
+                    &data,
+                    url,
+                    status,
+                    redirected,
+                    loader_url,
+                    content_accessible,
+                    progress_reported,
+                    None,
                 );
             }
         }
-        player.lock().unwrap().update(|uc| {
-            let (clip, vm_data) = match uc.load_manager.get_loader(handle) {
-                Some(Loader::Movie {
-                    target_clip,
-                    vm_data,
-                    ..
-                }) => (*target_clip, *vm_data),
-                None => return Err(Error::Cancelled),
-                _ => unreachable!(),
-            };
+        let (clip, vm_data) = match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie {
+                target_clip,
+                vm_data,
+                ..
+            }) => (*target_clip, *vm_data),
+            None => return Err(Error::Cancelled),
+            _ => unreachable!(),
+        };
 
-            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+        let domain = if let MovieLoaderVMData::Avm2 {
+            context,
+            default_domain,
+            ..
+        } = vm_data
+        {
+            let domain = context
+                .and_then(|o| {
+                    o.get_public_property("applicationDomain", &mut activation)
+                        .ok()
+                })
+                .and_then(|v| v.coerce_to_object(&mut activation).ok())
+                .and_then(|o| o.as_application_domain())
+                .unwrap_or_else(|| {
+                    let parent_domain = default_domain;
+                    Avm2Domain::movie_domain(&mut activation, parent_domain)
+                });
+            Some(domain)
+        } else {
+            None
+        };
 
-            let domain = if let MovieLoaderVMData::Avm2 {
-                context,
-                default_domain,
+        let movie = match sniffed_type {
+            ContentType::Swf => match progressive_movie {
+                Some(movie) => movie,
+                None => Arc::new(SwfMovie::from_data(data, url.clone(), loader_url)?),
+            },
+            ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
+                Arc::new(SwfMovie::from_loaded_image(url.clone(), length))
+            }
+            ContentType::Unknown => Arc::new(SwfMovie::error_movie(url.clone())),
+        };
+
+        match activation.context.load_manager.get_loader_mut(handle) {
+            Some(Loader::Movie {
+                movie: old,
+                loader_status,
                 ..
-            } = vm_data
-            {
-                let domain = context
-                    .and_then(|o| {
-                        o.get_public_property("applicationDomain", &mut activation)
-                            .ok()
-                    })
-                    .and_then(|v| v.coerce_to_object(&mut activation).ok())
-                    .and_then(|o| o.as_application_domain())
-                    .unwrap_or_else(|| {
-                        let parent_domain = default_domain;
-                        Avm2Domain::movie_domain(&mut activation, parent_domain)
-                    });
-                Some(domain)
-            } else {
-                None
-            };
+            }) => {
+                *loader_status = LoaderStatus::Parsing;
+                *old = Some(movie.clone())
+            }
+            _ => unreachable!(),
+        };
 
-            let movie = match sniffed_type {
-                ContentType::Swf => Arc::new(SwfMovie::from_data(data, url.clone(), loader_url)?),
-                ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
-                    Arc::new(SwfMovie::from_loaded_image(url.clone(), length))
-                }
-                ContentType::Unknown => Arc::new(SwfMovie::error_movie(url.clone())),
-            };
+        if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
+            let fake_movie = Arc::new(SwfMovie::empty_fake_compressed_len(
+                activation.context.swf.version(),
+                length,
+            ));
+
+            // Expose 'bytesTotal' (via the fake movie) during the first 'progress' event,
+            // but nothing else (in particular, the `parameters` and `url` properties are not set
+            // to their real values)
+            loader_info
+                .as_loader_info_object()
+                .unwrap()
+                .set_loader_stream(
+                    LoaderStream::NotYetLoaded(fake_movie, Some(clip), false),
+                    activation.context.gc_context,
+                );
 
-            match activation.context.load_manager.get_loader_mut(handle) {
-                Some(Loader::Movie {
-                    movie: old,
-                    loader_status,
-                    ..
-                }) => {
-                    *loader_status = LoaderStatus::Parsing;
-                    *old = Some(movie.clone())
+            // Flash always fires an initial 'progress' event with
+            // bytesLoaded=0 and bytesTotal set to the proper value.
+            // This only seems to happen for an AVM2 event handler.
+            // It's skipped if the download already reported its progress,
+            // as preloaders would otherwise jump back to zero.
+            if !progress_reported {
+                Loader::movie_loader_progress(handle, &mut activation.context, 0, length)?;
+            }
+
+            // Update the LoaderStream - we now have a real SWF movie and a real target clip
+            // This is intentionally set *after* the first 'progress' event, to match Flash's behavior
+            // (`LoaderInfo.parameters` is always empty during the first 'progress' event)
+            loader_info
+                .as_loader_info_object()
+                .unwrap()
+                .set_loader_stream(
+                    LoaderStream::NotYetLoaded(movie.clone(), Some(clip), false),
+                    activation.context.gc_context,
+                );
+        }
+
+        match sniffed_type {
+            ContentType::Swf => {
+                let library = activation
+                    .context
+                    .library
+                    .library_for_movie_mut(movie.clone());
+
+                if let Some(domain) = domain {
+                    library.set_avm2_domain(domain);
                 }
-                _ => unreachable!(),
-            };
 
-            if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
-                let fake_movie = Arc::new(SwfMovie::empty_fake_compressed_len(
-                    activation.context.swf.version(),
-                    length,
-                ));
-
-                // Expose 'bytesTotal' (via the fake movie) during the first 'progress' event,
-                // but nothing else (in particular, the `parameters` and `url` properties are not set
-                // to their real values)
-                loader_info
-                    .as_loader_info_object()
-                    .unwrap()
-                    .set_loader_stream(
-                        LoaderStream::NotYetLoaded(fake_movie, Some(clip), false),
-                        activation.context.gc_context,
-                    );
+                if let Some(mc) = clip.as_movie_clip() {
+                    let loader_info = if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
+                        Some(*loader_info.as_loader_info_object().unwrap())
+                    } else {
+                        None
+                    };
 
-                // Flash always fires an initial 'progress' event with
-                // bytesLoaded=0 and bytesTotal set to the proper value.
-                // This only seems to happen for an AVM2 event handler
-                Loader::movie_loader_progress(handle, &mut activation.context, 0, length)?;
+                    // Store our downloaded `SwfMovie` into our target `MovieClip`,
+                    // and initialize it.
 
-                // Update the LoaderStream - we now have a real SWF movie and a real target clip
-                // This is intentionally set *after* the first 'progress' event, to match Flash's behavior
-                // (`LoaderInfo.parameters` is always empty during the first 'progress' event)
-                loader_info
-                    .as_loader_info_object()
-                    .unwrap()
-                    .set_loader_stream(
-                        LoaderStream::NotYetLoaded(movie.clone(), Some(clip), false),
-                        activation.context.gc_context,
-                    );
-            }
+                    mc.replace_with_movie(&mut activation.context, Some(movie), true, loader_info);
+                }
 
-            match sniffed_type {
-                ContentType::Swf => {
-                    let library = activation
-                        .context
-                        .library
-                        .library_for_movie_mut(movie.clone());
+                // NOTE: Certain tests specifically expect small files to preload immediately
+                Loader::preload_tick(
+                    handle,
+                    uc,
+                    &mut ExecutionLimit::with_max_ops_and_time(10000, Duration::from_millis(1)),
+                    status,
+                    redirected,
+                )?;
 
-                    if let Some(domain) = domain {
-                        library.set_avm2_domain(domain);
-                    }
+                return Ok(());
+            }
+            ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
+                let library = activation.context.library.library_for_movie_mut(movie);
 
-                    if let Some(mc) = clip.as_movie_clip() {
-                        let loader_info =
-                            if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
-                                Some(*loader_info.as_loader_info_object().unwrap())
-                            } else {
-                                None
-                            };
+                if let Some(domain) = domain {
+                    library.set_avm2_domain(domain);
+                }
 
-                        // Store our downloaded `SwfMovie` into our target `MovieClip`,
-                        // and initialize it.
+                // This will construct AVM2-side objects even under AVM1, but it doesn't matter,
+                // since Bitmap and BitmapData never have AVM1-side objects.
+                let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None)?;
 
-                        mc.replace_with_movie(
-                            &mut activation.context,
-                            Some(movie),
-                            true,
-                            loader_info,
-                        );
-                    }
+                let transparency = true;
+                let mut bitmap_data = BitmapData::new_with_pixels(
+                    bitmap.width(),
+                    bitmap.height(),
+                    transparency,
+                    bitmap.as_colors().map(Color::from).collect(),
+                );
+                bitmap_data.set_cross_domain(!content_accessible);
+                let bitmapdata_wrapper =
+                    BitmapDataWrapper::new(GcCell::new(activation.context.gc_context, bitmap_data));
+                let bitmapdata_class = activation.context.avm2.classes().bitmapdata;
+                let bitmapdata_avm2 = BitmapDataObject::from_bitmap_data_internal(
+                    &mut activation,
+                    bitmapdata_wrapper,
+                    bitmapdata_class,
+                )
+                .unwrap();
 
-                    // NOTE: Certain tests specifically expect small files to preload immediately
-                    Loader::preload_tick(
+                let bitmap_avm2 = activation
+                    .avm2()
+                    .classes()
+                    .bitmap
+                    .construct(&mut activation, &[bitmapdata_avm2.into()])
+                    .unwrap();
+                let bitmap_obj = bitmap_avm2.as_display_object().unwrap();
+
+                Loader::movie_loader_progress(handle, &mut activation.context, length, length)?;
+                Loader::movie_loader_complete(
+                    handle,
+                    &mut activation.context,
+                    Some(bitmap_obj),
+                    status,
+                    redirected,
+                )?;
+            }
+            ContentType::Unknown => {
+                if activation.context.is_action_script_3() {
+                    Loader::movie_loader_progress(handle, &mut activation.context, length, length)?;
+                    Loader::movie_loader_error(
                         handle,
                         uc,
-                        &mut ExecutionLimit::with_max_ops_and_time(10000, Duration::from_millis(1)),
+                        AvmString::new_utf8(
+                            uc.gc_context,
+                            &format!("Error #2124: Loaded file is an unknown type. URL: {url}"),
+                        ),
                         status,
                         redirected,
+                        url,
                     )?;
-
-                    return Ok(());
-                }
-                ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
-                    let library = activation.context.library.library_for_movie_mut(movie);
-
-                    if let Some(domain) = domain {
-                        library.set_avm2_domain(domain);
+                } else {
+                    // If the file is no valid supported file, the MovieClip enters the error state
+                    if let Some(mut mc) = clip.as_movie_clip() {
+                        Loader::load_error_swf(&mut mc, &mut activation.context, url.clone());
                     }
 
-                    // This will construct AVM2-side objects even under AVM1, but it doesn't matter,
-                    // since Bitmap and BitmapData never have AVM1-side objects.
-                    let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None)?;
-
-                    let transparency = true;
-                    let bitmap_data = BitmapData::new_with_pixels(
-                        bitmap.width(),
-                        bitmap.height(),
-                        transparency,
-                        bitmap.as_colors().map(Color::from).collect(),
-                    );
-                    let bitmapdata_wrapper = BitmapDataWrapper::new(GcCell::new(
-                        activation.context.gc_context,
-                        bitmap_data,
-                    ));
-                    let bitmapdata_class = activation.context.avm2.classes().bitmapdata;
-                    let bitmapdata_avm2 = BitmapDataObject::from_bitmap_data_internal(
-                        &mut activation,
-                        bitmapdata_wrapper,
-                        bitmapdata_class,
-                    )
-                    .unwrap();
-
-                    let bitmap_avm2 = activation
-                        .avm2()
-                        .classes()
-                        .bitmap
-                        .construct(&mut activation, &[bitmapdata_avm2.into()])
-                        .unwrap();
-                    let bitmap_obj = bitmap_avm2.as_display_object().unwrap();
-
-                    Loader::movie_loader_progress(handle, &mut activation.context, length, length)?;
+                    // AVM1 fires the event with the current and total length as 0
+                    Loader::movie_loader_progress(handle, &mut activation.context, 0, 0)?;
                     Loader::movie_loader_complete(
                         handle,
                         &mut activation.context,
-                        Some(bitmap_obj),
+                        None,
                         status,
                         redirected,
                     )?;
                 }
-                ContentType::Unknown => {
-                    if activation.context.is_action_script_3() {
-                        Loader::movie_loader_progress(
-                            handle,
-                            &mut activation.context,
-                            length,
-                            length,
-                        )?;
-                        Loader::movie_loader_error(
-                            handle,
-                            uc,
-                            AvmString::new_utf8(
-                                uc.gc_context,
-                                &format!("Error #2124: Loaded file is an unknown type. URL: {url}"),
-                            ),
-                            status,
-                            redirected,
-                            url,
-                        )?;
-                    } else {
-                        // If the file is no valid supported file, the MovieClip enters the error state
-                        if let Some(mut mc) = clip.as_movie_clip() {
-                            Loader::load_error_swf(&mut mc, &mut activation.context, url.clone());
-                        }
-
-                        // AVM1 fires the event with the current and total length as 0
-                        Loader::movie_loader_progress(handle, &mut activation.context, 0, 0)?;
-                        Loader::movie_loader_complete(
-                            handle,
-                            &mut activation.context,
-                            None,
-                            status,
-                            redirected,
-                        )?;
-                    }
-                }
             }
+        }
 
-            Ok(())
-        }) //TODO: content sniffing errors need to be reported somehow
+        Ok(())
+        //TODO: content sniffing errors need to be reported somehow
     }
 
     /// Report a movie loader progress event to script code.
@@ -1901,14 +2361,75 @@ impl<'gc> Loader<'gc> {
         dobj: Option<DisplayObject<'gc>>,
         status: u16,
         redirected: bool,
+    ) -> Result<(), Error> {
+        let (target_clip, vm_data) = match uc.load_manager.get_loader_mut(handle) {
+            Some(Loader::Movie {
+                target_clip,
+                vm_data,
+                ..
+            }) => (*target_clip, *vm_data),
+            None => return Err(Error::Cancelled),
+            _ => unreachable!(),
+        };
+
+        Loader::movie_loader_init(handle, uc, dobj)?;
+
+        match vm_data {
+            MovieLoaderVMData::Avm1 { broadcaster } => {
+                if let Some(broadcaster) = broadcaster {
+                    Avm1::run_stack_frame_for_method(
+                        target_clip,
+                        broadcaster,
+                        uc,
+                        "broadcastMessage".into(),
+                        // TODO: Pass an actual httpStatus argument instead of 0.
+                        &["onLoadComplete".into(), target_clip.object(), status.into()],
+                    );
+                }
+            }
+            // This is fired after we process the movie's first frame,
+            // in `MovieClip.on_exit_frame`
+            MovieLoaderVMData::Avm2 { loader_info, .. } => {
+                if let Some(dobj) = dobj {
+                    if dobj.as_movie_clip().is_none() {
+                        let loader_info_obj = loader_info.as_loader_info_object().unwrap();
+                        loader_info_obj.fire_init_and_complete_events(uc, status, redirected);
+                    }
+                }
+            }
+        }
+
+        if let Loader::Movie { loader_status, .. } = uc.load_manager.get_loader_mut(handle).unwrap()
+        {
+            *loader_status = LoaderStatus::Succeeded;
+        };
+
+        Ok(())
+    }
+
+    /// Show the loaded content of a movie loader.
+    ///
+    /// This happens when the loader completes, unless the movie was already shown
+    /// while it was still downloading.
+    fn movie_loader_init(
+        handle: Index,
+        uc: &mut UpdateContext<'_, 'gc>,
+        dobj: Option<DisplayObject<'gc>>,
     ) -> Result<(), Error> {
         let (target_clip, vm_data, movie) = match uc.load_manager.get_loader_mut(handle) {
             Some(Loader::Movie {
                 target_clip,
                 movie,
                 vm_data,
+                loader_status,
                 ..
-            }) => (*target_clip, *vm_data, movie.clone()),
+            }) => {
+                if *loader_status == LoaderStatus::Playing {
+                    return Ok(());
+                }
+                *loader_status = LoaderStatus::Playing;
+                (*target_clip, *vm_data, movie.clone())
+            }
             None => return Err(Error::Cancelled),
             _ => unreachable!(),
         };
@@ -2003,41 +2524,16 @@ impl<'gc> Loader<'gc> {
             }
         }
 
-        match vm_data {
-            MovieLoaderVMData::Avm1 { broadcaster } => {
-                if let Some(broadcaster) = broadcaster {
-                    Avm1::run_stack_frame_for_method(
-                        target_clip,
-                        broadcaster,
-                        uc,
-                        "broadcastMessage".into(),
-                        // TODO: Pass an actual httpStatus argument instead of 0.
-                        &["onLoadComplete".into(), target_clip.object(), status.into()],
-                    );
-                }
-            }
-            // This is fired after we process the movie's first frame,
-            // in `MovieClip.on_exit_frame`
-            MovieLoaderVMData::Avm2 { loader_info, .. } => {
-                let loader_info_obj = loader_info.as_loader_info_object().unwrap();
-                loader_info_obj.set_loader_stream(
+        if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
+            loader_info
+                .as_loader_info_object()
+                .unwrap()
+                .set_loader_stream(
                     LoaderStream::Swf(target_clip.as_movie_clip().unwrap().movie(), dobj.unwrap()),
                     uc.gc_context,
                 );
-
-                if let Some(dobj) = dobj {
-                    if dobj.as_movie_clip().is_none() {
-                        loader_info_obj.fire_init_and_complete_events(uc, status, redirected);
-                    }
-                }
-            }
         }
 
-        if let Loader::Movie { loader_status, .. } = uc.load_manager.get_loader_mut(handle).unwrap()
-        {
-            *loader_status = LoaderStatus::Succeeded;
-        };
-
         Ok(())
     }
 
@@ -2227,6 +2723,7 @@ impl<'gc> Loader<'gc> {
         match loader_status {
             LoaderStatus::Pending => false,
             LoaderStatus::Parsing => false,
+            LoaderStatus::Playing => false,
             LoaderStatus::Failed => true,
             LoaderStatus::Succeeded => {
                 // AVM2 is handled separately
@@ -2698,3 +3195,112 @@ impl<'gc> Loader<'gc> {
         })
     }
 }
+
+/// Receive the body of a response as it arrives.
+///
+/// `on_chunk` is called with every chunk and the amount of bytes received so far.
+/// If it returns an error, the load is stopped and the error is returned.
+async fn receive_body<F>(
+    player: &Arc<Mutex<Player>>,
+    url: &str,
+    body: &mut dyn ResponseBody,
+    mut on_chunk: F,
+) -> Result<Result<(), ErrorResponse>, Error>
+where
+    F: for<'a, 'gc> FnMut(&mut UpdateContext<'a, 'gc>, Vec<u8>, usize) -> Result<(), Error>,
+{
+    let mut bytes_loaded = 0;
+    loop {
+        match body.next_chunk().await {
+            Ok(Some(chunk)) => {
+                bytes_loaded += chunk.len();
+                player
+                    .lock()
+                    .unwrap()
+                    .update(|uc| on_chunk(uc, chunk, bytes_loaded))?;
+            }
+            Ok(None) => return Ok(Ok(())),
+            Err(error) => {
+                return Ok(Err(ErrorResponse {
+                    url: url.to_string(),
+                    error,
+                }))
+            }
+        }
+    }
+}
+
+/// Dispatch a `progress` event to an AVM2 object.
+fn dispatch_progress_event<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    target: Avm2Object<'gc>,
+    bytes_loaded: usize,
+    bytes_total: usize,
+) -> Result<(), Error> {
+    let progress_evt = activation
+        .avm2()
+        .classes()
+        .progressevent
+        .construct(
+            activation,
+            &[
+                "progress".into(),
+                false.into(),
+                false.into(),
+                bytes_loaded.into(),
+                bytes_total.into(),
+            ],
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+    Ok(())
+}
+
+/// Update the `bytesLoaded` and `bytesTotal` properties of a `URLLoader`.
+fn set_url_loader_progress<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    target: Avm2Object<'gc>,
+    bytes_loaded: usize,
+    bytes_total: usize,
+) -> Result<(), Error> {
+    target
+        .set_public_property("bytesLoaded", bytes_loaded.into(), activation)
+        .and_then(|_| target.set_public_property("bytesTotal", bytes_total.into(), activation))
+        .map_err(|e| Error::Avm2Error(e.to_string()))
+}
+
+/// Dispatch a `securityError` event for a load that the policy files don't allow.
+fn dispatch_load_security_error<'gc>(
+    uc: &mut UpdateContext<'_, 'gc>,
+    target: Avm2Object<'gc>,
+    url: &str,
+) -> Result<(), Error> {
+    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+    let message = AvmString::new_utf8(
+        activation.context.gc_context,
+        format!(
+            "Error #2048: Security sandbox violation: {} cannot load data from {}.",
+            activation.context.swf.url(),
+            url
+        ),
+    );
+    let security_error_evt = activation
+        .avm2()
+        .classes()
+        .securityerrorevent
+        .construct(
+            &mut activation,
+            &[
+                "securityError".into(),
+                false.into(),
+                false.into(),
+                message.into(),
+                2048.into(),
+            ],
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    Avm2::dispatch_event(uc, security_error_evt, target);
+    Ok(())
+}
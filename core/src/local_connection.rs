@@ -0,0 +1,463 @@
+//! Management of `LocalConnection`s, which let movies send messages to each other.
+
+use crate::avm1::globals::local_connection::LocalConnection as Avm1LocalConnection;
+use crate::avm1::globals::shared_object::deserialize_value as deserialize_avm1_value;
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    ExecutionReason as Avm1ExecutionReason, Object as Avm1Object, ScriptObject as Avm1ScriptObject,
+    TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::amf::deserialize_value as deserialize_avm2_value;
+use crate::avm2::object::{
+    LocalConnectionObject as Avm2LocalConnectionObject, TObject as Avm2TObject,
+};
+use crate::avm2::{Activation as Avm2Activation, Avm2, Value as Avm2Value};
+use crate::backend::local_connection::LocalConnectionMessage;
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
+use fnv::FnvHashMap;
+use gc_arena::Collect;
+use std::rc::Rc;
+
+/// The domain of a movie, as used by `LocalConnection.domain` and connection names.
+///
+/// Returns `None` if the URL of the movie can't be parsed.
+pub fn movie_domain(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    match url.domain() {
+        Some(domain) if url.scheme() != "file" => Some(domain.to_string()),
+        // Local files, and URLs without a domain.
+        _ => Some("localhost".to_string()),
+    }
+}
+
+/// Turn a name given to `connect` or `send` into the full name of a connection.
+///
+/// Names starting with an underscore are shared by all domains, and names
+/// containing a colon already specify their domain. Any other name belongs to
+/// the domain of the movie that uses it. Names are case insensitive.
+fn full_name(domain: &str, name: &str) -> String {
+    if name.starts_with('_') || name.contains(':') {
+        name.to_lowercase()
+    } else {
+        format!("{domain}:{name}").to_lowercase()
+    }
+}
+
+/// Encode the arguments of a message as an LSO with one element per argument.
+fn write_arguments(arguments: Vec<Rc<AmfValue>>) -> Option<Vec<u8>> {
+    let body = arguments
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| Element::new(i.to_string(), value))
+        .collect();
+    let mut lso = Lso::new(body, "", AMFVersion::AMF0);
+    flash_lso::write::write_to_bytes(&mut lso).ok()
+}
+
+/// Decode the arguments of a message, along with the decoder needed to resolve AMF0 references.
+fn read_arguments(bytes: &[u8]) -> Option<(Vec<Rc<AmfValue>>, AMF0Decoder)> {
+    let mut reader = flash_lso::read::Reader::default();
+    let lso = reader.parse(bytes).ok()?;
+    let arguments = lso.body.into_iter().map(|element| element.value).collect();
+    Some((arguments, reader.amf0_decoder))
+}
+
+#[derive(Copy, Clone, Collect)]
+#[collect(no_drop)]
+pub enum LocalConnectionObject<'gc> {
+    Avm1(Avm1Object<'gc>),
+    Avm2(Avm2LocalConnectionObject<'gc>),
+}
+
+impl<'gc> LocalConnectionObject<'gc> {
+    fn connection_name(&self) -> Option<String> {
+        match self {
+            LocalConnectionObject::Avm1(object) => {
+                Avm1LocalConnection::cast((*object).into()).and_then(|lc| lc.connection_name())
+            }
+            LocalConnectionObject::Avm2(object) => object.connection_name(),
+        }
+    }
+
+    fn set_connection_name(&self, name: Option<String>) -> Option<String> {
+        match self {
+            LocalConnectionObject::Avm1(object) => Avm1LocalConnection::cast((*object).into())
+                .and_then(|lc| lc.set_connection_name(name)),
+            LocalConnectionObject::Avm2(object) => object.set_connection_name(name),
+        }
+    }
+
+    /// Whether this connection accepts messages from a movie of another domain.
+    fn is_domain_allowed(&self, context: &mut UpdateContext<'_, 'gc>, domain: &str) -> bool {
+        match self {
+            LocalConnectionObject::Avm1(object) => {
+                // AVM1 movies decide by overriding `allowDomain`.
+                let Some(mut activation) = Avm1Activation::try_from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[LocalConnection allowDomain]"),
+                ) else {
+                    return false;
+                };
+                let domain = AvmString::new_utf8(activation.context.gc_context, domain);
+                match object.call_method(
+                    "allowDomain".into(),
+                    &[domain.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    Ok(allowed) => allowed.as_bool(activation.swf_version()),
+                    Err(e) => {
+                        tracing::error!("Unhandled error in LocalConnection.allowDomain: {e}");
+                        false
+                    }
+                }
+            }
+            LocalConnectionObject::Avm2(object) => object.is_domain_allowed(domain),
+        }
+    }
+
+    /// Tell the sender of a message whether it was delivered.
+    fn send_status(&self, context: &mut UpdateContext<'_, 'gc>, delivered: bool) {
+        let level = if delivered { "status" } else { "error" };
+        match self {
+            LocalConnectionObject::Avm1(object) => {
+                let Some(mut activation) = Avm1Activation::try_from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[LocalConnection onStatus]"),
+                ) else {
+                    return;
+                };
+                let object_proto = activation.context.avm1.prototypes().object;
+                let info_object =
+                    Avm1ScriptObject::new(activation.context.gc_context, Some(object_proto));
+                info_object
+                    .set("level", Avm1Value::String(level.into()), &mut activation)
+                    .expect("valid set");
+
+                if let Err(e) = object.call_method(
+                    "onStatus".into(),
+                    &[info_object.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!("Unhandled error in LocalConnection.onStatus: {e}");
+                }
+            }
+            LocalConnectionObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let event = activation.avm2().classes().statusevent.construct(
+                    &mut activation,
+                    &[
+                        "status".into(),
+                        false.into(),
+                        false.into(),
+                        Avm2Value::Null,
+                        level.into(),
+                    ],
+                );
+                match event {
+                    Ok(event) => {
+                        Avm2::dispatch_event(&mut activation.context, event, (*object).into())
+                    }
+                    Err(e) => tracing::error!("Couldn't create LocalConnection status event: {e}"),
+                }
+            }
+        }
+    }
+
+    /// Call a method on the receiving end of a connection.
+    fn call(&self, context: &mut UpdateContext<'_, 'gc>, method_name: &str, arguments: &[u8]) {
+        let Some((arguments, decoder)) = read_arguments(arguments) else {
+            tracing::error!("Couldn't read arguments of LocalConnection message");
+            return;
+        };
+
+        match self {
+            LocalConnectionObject::Avm1(object) => {
+                // AVM1 calls methods on the connection itself.
+                let Some(mut activation) = Avm1Activation::try_from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[LocalConnection Message]"),
+                ) else {
+                    return;
+                };
+                let mut reference_cache = Default::default();
+                let arguments: Vec<_> = arguments
+                    .iter()
+                    .map(|argument| {
+                        deserialize_avm1_value(
+                            &mut activation,
+                            argument,
+                            &decoder,
+                            &mut reference_cache,
+                        )
+                    })
+                    .collect();
+                let name = AvmString::new_utf8(activation.context.gc_context, method_name);
+
+                if let Err(e) = object.call_method(
+                    name,
+                    &arguments,
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!("Unhandled error calling {name} on LocalConnection: {e}");
+                }
+            }
+            LocalConnectionObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let name = AvmString::new_utf8(activation.context.gc_context, method_name);
+                let result = (|| {
+                    let mut values = Vec::with_capacity(arguments.len());
+                    for argument in &arguments {
+                        values.push(deserialize_avm2_value(&mut activation, argument)?);
+                    }
+                    let client = object
+                        .get_public_property("client", &mut activation)?
+                        .coerce_to_object(&mut activation)?;
+                    client.call_public_property(name, &values, &mut activation)
+                })();
+
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled error calling {name} on LocalConnection client: {e}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<'gc> From<Avm1Object<'gc>> for LocalConnectionObject<'gc> {
+    fn from(value: Avm1Object<'gc>) -> Self {
+        LocalConnectionObject::Avm1(value)
+    }
+}
+
+impl<'gc> From<Avm2LocalConnectionObject<'gc>> for LocalConnectionObject<'gc> {
+    fn from(value: Avm2LocalConnectionObject<'gc>) -> Self {
+        LocalConnectionObject::Avm2(value)
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct Listener<'gc> {
+    object: LocalConnectionObject<'gc>,
+
+    /// The domain of the movie that connected.
+    #[collect(require_static)]
+    domain: String,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct QueuedMessage<'gc> {
+    sender: LocalConnectionObject<'gc>,
+
+    #[collect(require_static)]
+    message: LocalConnectionMessage,
+}
+
+/// Manages the collection of LocalConnections, and the messages sent between them.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct LocalConnections<'gc> {
+    /// The connections that are listening, by their full name.
+    listeners: FnvHashMap<String, Listener<'gc>>,
+
+    /// Messages that were sent, but not delivered yet.
+    ///
+    /// Messages are never delivered during `send`, only on the next update.
+    queue: Vec<QueuedMessage<'gc>>,
+}
+
+impl<'gc> LocalConnections<'gc> {
+    /// Start listening on a connection.
+    ///
+    /// Returns `false` if the object is already connected, or if the name is
+    /// already in use, either in this player or another one.
+    pub fn connect<O: Into<LocalConnectionObject<'gc>>>(
+        context: &mut UpdateContext<'_, 'gc>,
+        object: O,
+        domain: &str,
+        name: &str,
+    ) -> bool {
+        let object = object.into();
+        if object.connection_name().is_some() {
+            return false;
+        }
+
+        let name = full_name(domain, name);
+        if context.local_connections.listeners.contains_key(&name)
+            || !context.local_connection_backend.register(&name)
+        {
+            return false;
+        }
+
+        object.set_connection_name(Some(name.clone()));
+        context.local_connections.listeners.insert(
+            name,
+            Listener {
+                object,
+                domain: domain.to_string(),
+            },
+        );
+        true
+    }
+
+    /// Stop listening on a connection.
+    ///
+    /// Returns `false` if the object wasn't connected.
+    pub fn close<O: Into<LocalConnectionObject<'gc>>>(
+        context: &mut UpdateContext<'_, 'gc>,
+        object: O,
+    ) -> bool {
+        let Some(name) = object.into().set_connection_name(None) else {
+            return false;
+        };
+
+        context.local_connections.listeners.remove(&name);
+        context.local_connection_backend.unregister(&name);
+        true
+    }
+
+    /// Queue a message to be delivered on the next update.
+    pub fn send<O: Into<LocalConnectionObject<'gc>>>(
+        &mut self,
+        sender: O,
+        sender_domain: &str,
+        connection_name: &str,
+        method_name: String,
+        arguments: Vec<Rc<AmfValue>>,
+    ) {
+        let Some(arguments) = write_arguments(arguments) else {
+            tracing::error!("Couldn't serialize arguments of LocalConnection message");
+            return;
+        };
+
+        self.queue.push(QueuedMessage {
+            sender: sender.into(),
+            message: LocalConnectionMessage {
+                connection_name: full_name(sender_domain, connection_name),
+                sender_domain: sender_domain.to_string(),
+                method_name,
+                arguments,
+            },
+        });
+    }
+
+    /// Deliver the messages sent since the last update, including those sent
+    /// by other players.
+    pub fn update_connections(context: &mut UpdateContext<'_, 'gc>) {
+        for QueuedMessage { sender, message } in
+            std::mem::take(&mut context.local_connections.queue)
+        {
+            let delivered = if context
+                .local_connections
+                .listeners
+                .contains_key(&message.connection_name)
+            {
+                Self::deliver(context, &message)
+            } else {
+                context.local_connection_backend.send(message)
+            };
+            sender.send_status(context, delivered);
+        }
+
+        for message in context.local_connection_backend.receive() {
+            Self::deliver(context, &message);
+        }
+    }
+
+    /// Deliver a message to a connection of this player.
+    ///
+    /// Returns `false` if nobody is listening, or the listener doesn't accept
+    /// messages from the domain of the sender.
+    fn deliver(context: &mut UpdateContext<'_, 'gc>, message: &LocalConnectionMessage) -> bool {
+        let Some(listener) = context
+            .local_connections
+            .listeners
+            .get(&message.connection_name)
+        else {
+            return false;
+        };
+        let object = listener.object;
+        let is_same_domain = listener.domain.eq_ignore_ascii_case(&message.sender_domain);
+
+        if !is_same_domain && !object.is_domain_allowed(context, &message.sender_domain) {
+            return false;
+        }
+
+        object.call(context, &message.method_name, &message.arguments);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_names() {
+        assert_eq!(full_name("example.com", "conn"), "example.com:conn");
+        assert_eq!(full_name("example.com", "Conn"), "example.com:conn");
+        assert_eq!(full_name("example.com", "_Global"), "_global");
+        assert_eq!(full_name("example.com", "other.org:conn"), "other.org:conn");
+    }
+
+    #[test]
+    fn movie_domains() {
+        assert_eq!(
+            movie_domain("https://www.example.com/movie.swf").as_deref(),
+            Some("www.example.com")
+        );
+        assert_eq!(
+            movie_domain("file:///home/user/movie.swf").as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(movie_domain("not a url"), None);
+    }
+
+    #[test]
+    fn arguments_round_trip() {
+        let arguments = vec![
+            Rc::new(AmfValue::String("hello".to_string())),
+            Rc::new(AmfValue::Number(5.0)),
+            Rc::new(AmfValue::Bool(true)),
+        ];
+        let bytes = write_arguments(arguments.clone()).unwrap();
+        let (read, _) = read_arguments(&bytes).unwrap();
+        assert_eq!(read, arguments);
+    }
+
+    #[test]
+    fn message_round_trip() {
+        let message = LocalConnectionMessage {
+            connection_name: "_conn".to_string(),
+            sender_domain: "localhost".to_string(),
+            method_name: "ping".to_string(),
+            arguments: vec![1, 2, 3],
+        };
+        assert_eq!(
+            LocalConnectionMessage::from_bytes(&message.to_bytes()).unwrap(),
+            message
+        );
+        assert!(LocalConnectionMessage::from_bytes(&message.to_bytes()[..10]).is_err());
+    }
+
+    #[test]
+    fn oversized_message_fields() {
+        let mut bytes = u32::MAX.to_le_bytes().to_vec();
+        bytes.extend_from_slice(b"_conn");
+        assert_eq!(
+            LocalConnectionMessage::from_bytes(&bytes)
+                .unwrap_err()
+                .kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+}
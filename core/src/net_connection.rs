@@ -1,19 +1,45 @@
+mod rtmp;
+
+use crate::avm1::globals::netconnection::NetConnection as Avm1NetConnection;
+use crate::avm1::globals::shared_object::{
+    deserialize_value as deserialize_avm1_value, serialize_values as serialize_avm1_values,
+};
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    ExecutionReason as Avm1ExecutionReason, Object as Avm1Object, ScriptObject as Avm1ScriptObject,
+    TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::amf::{deserialize_value, serialize_value};
 use crate::avm2::object::{
     NetConnectionObject as Avm2NetConnectionObject, ResponderObject as Avm2ResponderObject,
+    TObject as Avm2TObject,
 };
 use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject};
 use crate::backend::navigator::{NavigatorBackend, OwnedFuture, Request};
 use crate::context::UpdateContext;
 use crate::loader::Error;
+use crate::socket::{ConnectionState, SocketAction};
+use crate::streams::NetStream;
 use crate::string::AvmString;
+use crate::vminterface::AvmObject;
 use crate::Player;
+use async_channel::unbounded;
+use flash_lso::amf0::read::AMF0Decoder;
 use flash_lso::packet::{Header, Message, Packet};
 use flash_lso::types::{AMFVersion, Value as AmfValue};
 use gc_arena::{Collect, DynamicRoot, Rootable};
 use generational_arena::{Arena, Index};
+use rtmp::{
+    message_type, Command, NetStreamHandle, RtmpAddress, RtmpConnection, RtmpMessage, Transaction,
+};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Mutex, Weak};
+use std::time::Duration;
+
+/// How long to wait for the socket of an RTMP connection to connect.
+const RTMP_CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
 
 pub type NetConnectionHandle = Index;
 
@@ -25,12 +51,14 @@ pub enum ResponderCallback {
 
 #[derive(Clone)]
 pub enum ResponderHandle {
+    Avm1(DynamicRoot<Rootable![Avm1Object<'_>]>),
     Avm2(DynamicRoot<Rootable![Avm2ResponderObject<'_>]>),
 }
 
 impl Debug for ResponderHandle {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            ResponderHandle::Avm1(_) => write!(f, "ResponderHandle::Avm1"),
             ResponderHandle::Avm2(_) => write!(f, "ResponderHandle::Avm2"),
         }
     }
@@ -44,6 +72,33 @@ impl ResponderHandle {
         message: Rc<AmfValue>,
     ) {
         match self {
+            ResponderHandle::Avm1(handle) => {
+                let object = *context.dynamic_root.fetch(handle);
+                let Some(mut activation) = Avm1Activation::try_from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[NetConnection Responder]"),
+                ) else {
+                    return;
+                };
+                let method_name = match callback {
+                    ResponderCallback::Result => "onResult",
+                    ResponderCallback::Status => "onStatus",
+                };
+                let value = deserialize_avm1_value(
+                    &mut activation,
+                    &message,
+                    &AMF0Decoder::default(),
+                    &mut Default::default(),
+                );
+                if let Err(e) = object.call_method(
+                    method_name.into(),
+                    &[value],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!("Unhandled error sending {callback:?} callback: {e}");
+                }
+            }
             ResponderHandle::Avm2(handle) => {
                 let object = context.dynamic_root.fetch(handle);
                 if let Err(e) = object.send_callback(context, callback, &message) {
@@ -57,15 +112,76 @@ impl ResponderHandle {
 #[derive(Copy, Clone, Collect)]
 #[collect(no_drop)]
 pub enum NetConnectionObject<'gc> {
+    Avm1(Avm1Object<'gc>),
     Avm2(Avm2NetConnectionObject<'gc>),
 }
 
 impl<'gc> NetConnectionObject<'gc> {
     pub fn set_handle(&self, handle: Option<NetConnectionHandle>) -> Option<NetConnectionHandle> {
         match self {
+            NetConnectionObject::Avm1(object) => Avm1NetConnection::cast((*object).into())
+                .and_then(|net_connection| net_connection.set_handle(handle)),
             NetConnectionObject::Avm2(object) => object.set_handle(handle),
         }
     }
+
+    pub fn avm_object(&self) -> AvmObject<'gc> {
+        match self {
+            NetConnectionObject::Avm1(object) => AvmObject::Avm1(*object),
+            NetConnectionObject::Avm2(object) => AvmObject::Avm2((*object).into()),
+        }
+    }
+
+    /// Dispatch a `netStatus` event (or call `onStatus` in AVM1) with the given info.
+    fn net_status(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        info: Vec<(&'static str, impl Into<AvmString<'gc>>)>,
+    ) {
+        match self {
+            NetConnectionObject::Avm1(object) => {
+                let Some(mut activation) = Avm1Activation::try_from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[NetConnection Status Event]"),
+                ) else {
+                    return;
+                };
+                let object_proto = activation.context.avm1.prototypes().object;
+                let info_object =
+                    Avm1ScriptObject::new(activation.context.gc_context, Some(object_proto));
+
+                for (key, value) in info {
+                    if let Err(e) =
+                        info_object.set(key, Avm1Value::String(value.into()), &mut activation)
+                    {
+                        tracing::error!("Couldn't set {key} of NetConnection status info: {e}");
+                    }
+                }
+
+                if let Err(e) = object.call_method(
+                    "onStatus".into(),
+                    &[info_object.into()],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!(
+                        "Got error when dispatching AVM1 onStatus event from NetConnection: {e}"
+                    );
+                }
+            }
+            NetConnectionObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let event = Avm2EventObject::net_status_event(&mut activation, "netStatus", info);
+                Avm2::dispatch_event(&mut activation.context, event, (*object).into());
+            }
+        }
+    }
+}
+
+impl<'gc> From<Avm1Object<'gc>> for NetConnectionObject<'gc> {
+    fn from(value: Avm1Object<'gc>) -> Self {
+        NetConnectionObject::Avm1(value)
+    }
 }
 
 impl<'gc> From<Avm2NetConnectionObject<'gc>> for NetConnectionObject<'gc> {
@@ -77,6 +193,12 @@ impl<'gc> From<Avm2NetConnectionObject<'gc>> for NetConnectionObject<'gc> {
 /// Manages the collection of NetConnections.
 pub struct NetConnections<'gc> {
     connections: Arena<NetConnection<'gc>>,
+
+    /// The channel that the sockets of RTMP connections report to.
+    ///
+    /// Sockets are identified by the handle of their connection.
+    socket_receiver: Receiver<SocketAction>,
+    socket_sender: Sender<SocketAction>,
 }
 
 unsafe impl<'gc> Collect for NetConnections<'gc> {
@@ -89,8 +211,12 @@ unsafe impl<'gc> Collect for NetConnections<'gc> {
 
 impl<'gc> Default for NetConnections<'gc> {
     fn default() -> Self {
+        let (socket_sender, socket_receiver) = channel();
+
         Self {
             connections: Arena::new(),
+            socket_receiver,
+            socket_sender,
         }
     }
 }
@@ -111,20 +237,13 @@ impl<'gc> NetConnections<'gc> {
             NetConnections::close(context, existing_handle, false);
         }
 
-        match target {
-            NetConnectionObject::Avm2(object) => {
-                let mut activation = Avm2Activation::from_nothing(context.reborrow());
-                let event = Avm2EventObject::net_status_event(
-                    &mut activation,
-                    "netStatus",
-                    vec![
-                        ("code", "NetConnection.Connect.Success"),
-                        ("level", "status"),
-                    ],
-                );
-                Avm2::dispatch_event(&mut activation.context, event, object.into());
-            }
-        }
+        target.net_status(
+            context,
+            vec![
+                ("code", "NetConnection.Connect.Success"),
+                ("level", "status"),
+            ],
+        );
     }
 
     pub fn connect_to_flash_remoting<O: Into<NetConnectionObject<'gc>>>(
@@ -150,6 +269,60 @@ impl<'gc> NetConnections<'gc> {
         // No open event here
     }
 
+    pub fn connect_to_rtmp<O: Into<NetConnectionObject<'gc>>>(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: O,
+        url: String,
+        object_encoding: f64,
+        arguments: Vec<AmfValue>,
+    ) {
+        let target = target.into();
+        let Some(address) = RtmpAddress::parse(&url) else {
+            tracing::error!("Invalid RTMP URL {url}");
+            target.net_status(
+                context,
+                vec![("code", "NetConnection.Connect.Failed"), ("level", "error")],
+            );
+            return;
+        };
+
+        let (sender, receiver) = unbounded();
+        let connection = match RtmpConnection::new(
+            url,
+            &address,
+            format!("LNX {},0,0,0", context.player_version),
+            context.swf.url().to_string(),
+            context.page_url.clone(),
+            object_encoding,
+            arguments,
+            sender,
+        ) {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracing::error!("Couldn't create RTMP connect command: {e}");
+                return;
+            }
+        };
+        let handle = context.net_connections.connections.insert(NetConnection {
+            object: target,
+            protocol: NetConnectionProtocol::Rtmp(connection),
+        });
+
+        if let Some(existing_handle) = target.set_handle(Some(handle)) {
+            NetConnections::close(context, existing_handle, false);
+        }
+
+        // NOTE: This call will send SocketAction::Connect to our socket channel with connection status.
+        context.navigator.connect_socket(
+            address.host,
+            address.port,
+            RTMP_CONNECT_TIMEOUT,
+            handle,
+            receiver,
+            context.net_connections.socket_sender.clone(),
+        );
+    }
+
     pub fn close(
         context: &mut UpdateContext<'_, 'gc>,
         handle: NetConnectionHandle,
@@ -159,42 +332,403 @@ impl<'gc> NetConnections<'gc> {
             return;
         };
 
-        match connection.object {
-            NetConnectionObject::Avm2(object) => {
-                let mut activation = Avm2Activation::from_nothing(context.reborrow());
-                let event = Avm2EventObject::net_status_event(
+        connection.object.net_status(
+            context,
+            vec![
+                ("code", "NetConnection.Connect.Closed"),
+                ("level", "status"),
+            ],
+        );
+
+        if is_explicit && matches!(connection.protocol, NetConnectionProtocol::FlashRemoting(_)) {
+            // [NA] I have no idea why, but a NetConnection receives a second and nonsensical event on close
+            connection.object.net_status(
+                context,
+                vec![
+                    ("code", ""),
+                    ("description", ""),
+                    ("details", ""),
+                    ("level", "status"),
+                ],
+            );
+        }
+    }
+
+    pub fn update_connections(context: &mut UpdateContext<'_, 'gc>) {
+        Self::update_rtmp_sockets(context);
+
+        for (handle, connection) in context.net_connections.connections.iter_mut() {
+            connection.update(handle, context.navigator, context.player.clone());
+        }
+    }
+
+    fn rtmp_mut(&mut self, handle: NetConnectionHandle) -> Option<&mut RtmpConnection> {
+        match self.connections.get_mut(handle) {
+            Some(NetConnection {
+                protocol: NetConnectionProtocol::Rtmp(rtmp),
+                ..
+            }) => Some(rtmp),
+            _ => None,
+        }
+    }
+
+    /// Process everything the sockets of RTMP connections have received.
+    fn update_rtmp_sockets(context: &mut UpdateContext<'_, 'gc>) {
+        let actions: Vec<_> = context.net_connections.socket_receiver.try_iter().collect();
+
+        for action in actions {
+            match action {
+                SocketAction::Connect(handle, ConnectionState::Connected) => {
+                    if let Some(rtmp) = context.net_connections.rtmp_mut(handle) {
+                        rtmp.client.start_handshake();
+                    }
+                }
+                SocketAction::Connect(
+                    handle,
+                    ConnectionState::Failed | ConnectionState::TimedOut,
+                ) => {
+                    // A connection that never opened isn't reported as closed.
+                    if let Some(connection) = context.net_connections.connections.remove(handle) {
+                        connection.object.net_status(
+                            context,
+                            vec![("code", "NetConnection.Connect.Failed"), ("level", "error")],
+                        );
+                    }
+                }
+                SocketAction::Data(handle, data) => {
+                    let Some(rtmp) = context.net_connections.rtmp_mut(handle) else {
+                        continue;
+                    };
+                    match rtmp.client.receive(&data) {
+                        Ok(messages) => {
+                            for message in messages {
+                                Self::handle_rtmp_message(context, handle, message);
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("RTMP connection failed: {e}");
+                            Self::close(context, handle, false);
+                        }
+                    }
+                }
+                SocketAction::Close(handle) => Self::close(context, handle, false),
+            }
+        }
+    }
+
+    fn handle_rtmp_message(
+        context: &mut UpdateContext<'_, 'gc>,
+        handle: NetConnectionHandle,
+        message: RtmpMessage,
+    ) {
+        match message.type_id {
+            message_type::COMMAND_AMF0 | message_type::COMMAND_AMF3 => {
+                match Command::decode(&message) {
+                    Ok(command) => {
+                        Self::handle_rtmp_command(context, handle, message.stream_id, command)
+                    }
+                    Err(e) => tracing::error!("Invalid RTMP command: {e}"),
+                }
+            }
+            message_type::AUDIO
+            | message_type::VIDEO
+            | message_type::DATA_AMF0
+            | message_type::DATA_AMF3 => {
+                let Some(rtmp) = context.net_connections.rtmp_mut(handle) else {
+                    return;
+                };
+                let Some(stream) = rtmp
+                    .stream_index(message.stream_id)
+                    .and_then(|index| rtmp.streams.get_mut(&index))
+                else {
+                    return;
+                };
+
+                // Media is played by converting it back into the FLV tags it
+                // would have been stored as.
+                let is_first_tag = !stream.flv_header_written;
+                stream.flv_header_written = true;
+                let mut data = vec![];
+                rtmp::write_flv_tag(&mut data, &message, is_first_tag);
+
+                let net_stream = *context.dynamic_root.fetch(&stream.net_stream);
+                net_stream.append_buffer(context.gc_context, &mut data);
+                if is_first_tag {
+                    net_stream.trigger_status_event(
+                        context,
+                        vec![("code", "NetStream.Buffer.Full"), ("level", "status")],
+                    );
+                }
+            }
+            // Shared object messages are ignored, as remote shared objects aren't supported.
+            _ => {}
+        }
+    }
+
+    fn handle_rtmp_command(
+        context: &mut UpdateContext<'_, 'gc>,
+        handle: NetConnectionHandle,
+        stream_id: u32,
+        command: Command,
+    ) {
+        let Some(object) = context
+            .net_connections
+            .connections
+            .get(handle)
+            .map(|connection| connection.object)
+        else {
+            return;
+        };
+        let Some(rtmp) = context.net_connections.rtmp_mut(handle) else {
+            return;
+        };
+
+        match command.name.as_str() {
+            "_result" | "_error" => {
+                let is_error = command.name == "_error";
+                let Some(transaction) = rtmp.finish_transaction(command.transaction_id) else {
+                    return;
+                };
+                let info = command
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .unwrap_or(AmfValue::Undefined);
+
+                match transaction {
+                    Transaction::Connect => {
+                        rtmp.connected = !is_error;
+                        Self::dispatch_rtmp_status(context, object.avm_object(), &info);
+                        if is_error {
+                            // The server rejected us.
+                            Self::close(context, handle, false);
+                        }
+                    }
+                    Transaction::CreateStream(index) => {
+                        let id = match info {
+                            AmfValue::Number(id) => id as u32,
+                            AmfValue::Integer(id) => id as u32,
+                            _ => {
+                                tracing::error!("RTMP server couldn't create a stream: {info:?}");
+                                return;
+                            }
+                        };
+                        if let Err(e) = rtmp.stream_created(index, id) {
+                            tracing::error!("Couldn't send RTMP stream command: {e}");
+                        }
+                    }
+                    Transaction::Call(responder) => {
+                        let callback = if is_error {
+                            ResponderCallback::Status
+                        } else {
+                            ResponderCallback::Result
+                        };
+                        responder.call(context, callback, Rc::new(info));
+                    }
+                }
+            }
+            "onStatus" => {
+                let info = command
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .unwrap_or(AmfValue::Undefined);
+                if stream_id == 0 {
+                    Self::dispatch_rtmp_status(context, object.avm_object(), &info);
+                } else if let Some(index) = rtmp.stream_index(stream_id) {
+                    let net_stream = &rtmp.streams[&index].net_stream;
+                    let net_stream = *context.dynamic_root.fetch(net_stream);
+                    if let Some(object) = net_stream.avm_object() {
+                        Self::dispatch_rtmp_status(context, object, &info);
+                    }
+                }
+            }
+            "close" => Self::close(context, handle, false),
+            name => {
+                // Anything else is the server calling a method on the `client`.
+                let result = match object {
+                    NetConnectionObject::Avm1(object) => {
+                        Self::call_avm1_client(context, object, name, &command.arguments)
+                    }
+                    NetConnectionObject::Avm2(object) => {
+                        Self::call_avm2_client(context, object, name, &command.arguments)
+                    }
+                };
+
+                if let Some(result) = result.filter(|_| command.transaction_id != 0.0) {
+                    if let Some(rtmp) = context.net_connections.rtmp_mut(handle) {
+                        if let Err(e) = rtmp.respond(command.transaction_id, result) {
+                            tracing::error!("Couldn't send RTMP result: {e}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Call a method that the server invoked on an AVM1 `NetConnection`, returning its result.
+    ///
+    /// Unlike AVM2, there's no `client`; the methods are called on the connection itself.
+    fn call_avm1_client(
+        context: &mut UpdateContext<'_, 'gc>,
+        object: Avm1Object<'gc>,
+        name: &str,
+        arguments: &[AmfValue],
+    ) -> Option<AmfValue> {
+        let mut activation = Avm1Activation::try_from_stub(
+            context.reborrow(),
+            Avm1ActivationIdentifier::root("[NetConnection Client Call]"),
+        )?;
+        let decoder = AMF0Decoder::default();
+        let arguments: Vec<_> = arguments
+            .iter()
+            .map(|argument| {
+                deserialize_avm1_value(&mut activation, argument, &decoder, &mut Default::default())
+            })
+            .collect();
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+
+        match object.call_method(
+            name,
+            &arguments,
+            &mut activation,
+            Avm1ExecutionReason::Special,
+        ) {
+            Ok(result) => serialize_avm1_values(&mut activation, &[result])
+                .pop()
+                .map(|result| AmfValue::clone(&result)),
+            Err(e) => {
+                tracing::error!("Unhandled error calling {name} on NetConnection: {e}");
+                None
+            }
+        }
+    }
+
+    /// Call a method that the server invoked on the `client` of an AVM2 `NetConnection`,
+    /// returning its result.
+    fn call_avm2_client(
+        context: &mut UpdateContext<'_, 'gc>,
+        object: Avm2NetConnectionObject<'gc>,
+        name: &str,
+        arguments: &[AmfValue],
+    ) -> Option<AmfValue> {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        let result = (|| {
+            let mut values = Vec::with_capacity(arguments.len());
+            for argument in arguments {
+                values.push(deserialize_value(&mut activation, argument)?);
+            }
+            let client = object
+                .get_public_property("client", &mut activation)?
+                .coerce_to_object(&mut activation)?;
+            client.call_public_property(name, &values, &mut activation)
+        })();
+
+        match result {
+            Ok(result) => Some(
+                serialize_value(
                     &mut activation,
-                    "netStatus",
-                    vec![
-                        ("code", "NetConnection.Connect.Closed"),
-                        ("level", "status"),
-                    ],
-                );
-                Avm2::dispatch_event(&mut activation.context, event, object.into());
+                    result,
+                    AMFVersion::AMF0,
+                    &mut Default::default(),
+                )
+                .unwrap_or(AmfValue::Undefined),
+            ),
+            Err(e) => {
+                tracing::error!("Unhandled error calling {name} on NetConnection client: {e}");
+                None
+            }
+        }
+    }
 
-                if is_explicit
-                    && matches!(connection.protocol, NetConnectionProtocol::FlashRemoting(_))
-                {
-                    // [NA] I have no idea why, but a NetConnection receives a second and nonsensical event on close
-                    let event = Avm2EventObject::net_status_event(
+    /// Dispatch a `netStatus` event (or call `onStatus` in AVM1) with an info object sent by the server.
+    fn dispatch_rtmp_status(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: AvmObject<'gc>,
+        info: &AmfValue,
+    ) {
+        match target {
+            AvmObject::Avm1(target) => {
+                let Some(mut activation) = Avm1Activation::try_from_stub(
+                    context.reborrow(),
+                    Avm1ActivationIdentifier::root("[RTMP Status Event]"),
+                ) else {
+                    return;
+                };
+                let info = deserialize_avm1_value(
+                    &mut activation,
+                    info,
+                    &AMF0Decoder::default(),
+                    &mut Default::default(),
+                );
+                if let Err(e) = target.call_method(
+                    "onStatus".into(),
+                    &[info],
+                    &mut activation,
+                    Avm1ExecutionReason::Special,
+                ) {
+                    tracing::error!("Got error when dispatching AVM1 RTMP onStatus event: {e}");
+                }
+            }
+            AvmObject::Avm2(target) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let event = deserialize_value(&mut activation, info).and_then(|info| {
+                    activation.avm2().classes().netstatusevent.construct(
                         &mut activation,
-                        "netStatus",
-                        vec![
-                            ("code", ""),
-                            ("description", ""),
-                            ("details", ""),
-                            ("level", "status"),
-                        ],
-                    );
-                    Avm2::dispatch_event(&mut activation.context, event, object.into());
+                        &["netStatus".into(), false.into(), false.into(), info],
+                    )
+                });
+                match event {
+                    Ok(event) => Avm2::dispatch_event(&mut activation.context, event, target),
+                    Err(e) => tracing::error!("Couldn't create RTMP netStatus event: {e}"),
                 }
             }
         }
     }
 
-    pub fn update_connections(context: &mut UpdateContext<'_, 'gc>) {
-        for (handle, connection) in context.net_connections.connections.iter_mut() {
-            connection.update(handle, context.navigator, context.player.clone());
+    /// Ask the server of an RTMP connection to create a stream for a `NetStream`.
+    ///
+    /// This does nothing for other kinds of connection, which don't need a stream.
+    pub fn create_stream(
+        context: &mut UpdateContext<'_, 'gc>,
+        handle: NetConnectionHandle,
+        net_stream: NetStream<'gc>,
+    ) {
+        if context.net_connections.rtmp_mut(handle).is_none() {
+            return;
+        }
+        let root: NetStreamHandle = context.dynamic_root.stash(context.gc_context, net_stream);
+        let Some(rtmp) = context.net_connections.rtmp_mut(handle) else {
+            return;
+        };
+        match rtmp.create_stream(root) {
+            Ok(index) => net_stream.set_rtmp_stream(context.gc_context, Some((handle, index))),
+            Err(e) => tracing::error!("Couldn't send RTMP createStream: {e}"),
+        }
+    }
+
+    /// Send a command such as `play` or `publish` on the RTMP stream of a `NetStream`.
+    pub fn send_stream_command(
+        &mut self,
+        handle: NetConnectionHandle,
+        index: usize,
+        name: &str,
+        arguments: Vec<AmfValue>,
+    ) {
+        if let Some(rtmp) = self.rtmp_mut(handle) {
+            if let Err(e) = rtmp.send_stream_command(index, Command::new(name, 0.0, arguments)) {
+                tracing::error!("Couldn't send RTMP {name}: {e}");
+            }
+        }
+    }
+
+    /// Delete the RTMP stream of a `NetStream` that was closed.
+    pub fn delete_stream(&mut self, handle: NetConnectionHandle, index: usize) {
+        if let Some(rtmp) = self.rtmp_mut(handle) {
+            if let Err(e) = rtmp.delete_stream(index) {
+                tracing::error!("Couldn't send RTMP deleteStream: {e}");
+            }
         }
     }
 
@@ -209,6 +743,20 @@ impl<'gc> NetConnections<'gc> {
         }
     }
 
+    pub fn send_avm1(
+        context: &mut UpdateContext<'_, 'gc>,
+        handle: NetConnectionHandle,
+        command: String,
+        message: AmfValue,
+        responder: Avm1Object<'gc>,
+    ) {
+        if let Some(connection) = context.net_connections.connections.get_mut(handle) {
+            let responder_handle =
+                ResponderHandle::Avm1(context.dynamic_root.stash(context.gc_context, responder));
+            connection.send(command, Some(responder_handle), message);
+        }
+    }
+
     pub fn send_avm2(
         context: &mut UpdateContext<'_, 'gc>,
         handle: NetConnectionHandle,
@@ -282,55 +830,66 @@ pub struct NetConnection<'gc> {
 
 impl<'gc> NetConnection<'gc> {
     pub fn is_connected(&self) -> bool {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => true,
             NetConnectionProtocol::FlashRemoting(_) => false,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.connected,
         }
     }
 
     pub fn connected_proxy_type(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some("none"),
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) if rtmp.connected => Some("none"),
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
     pub fn far_id(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some(""),
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) if rtmp.connected => Some(""),
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
     pub fn far_nonce(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) if rtmp.connected => {
+                Some("0000000000000000000000000000000000000000000000000000000000000000")
+            }
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
     pub fn near_id(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some(""),
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) if rtmp.connected => Some(""),
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
     pub fn near_nonce(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) if rtmp.connected => {
+                Some("0000000000000000000000000000000000000000000000000000000000000000")
+            }
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
     pub fn protocol(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some("rtmp"),
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) if rtmp.connected => Some("rtmp"),
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
@@ -338,13 +897,15 @@ impl<'gc> NetConnection<'gc> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some("null".to_string()), // Yes, it's a string "null", not a real null.
             NetConnectionProtocol::FlashRemoting(remoting) => Some(remoting.url.to_string()),
+            NetConnectionProtocol::Rtmp(rtmp) => Some(rtmp.url.to_string()),
         }
     }
 
     pub fn using_tls(&self) -> Option<bool> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some(false),
-            NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) if rtmp.connected => Some(false),
+            NetConnectionProtocol::FlashRemoting(_) | NetConnectionProtocol::Rtmp(_) => None,
         }
     }
 
@@ -359,6 +920,18 @@ impl<'gc> NetConnection<'gc> {
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.send(command, responder_handle, message)
             }
+            NetConnectionProtocol::Rtmp(rtmp) => {
+                let arguments = match message {
+                    AmfValue::StrictArray(arguments) => arguments
+                        .iter()
+                        .map(|value| AmfValue::clone(value))
+                        .collect(),
+                    message => vec![message],
+                };
+                if let Err(e) = rtmp.call(command, responder_handle, arguments) {
+                    tracing::error!("Couldn't send RTMP call: {e}");
+                }
+            }
         }
     }
 
@@ -375,12 +948,13 @@ impl<'gc> NetConnection<'gc> {
                     navigator.spawn_future(remoting.flush_queue(self_handle, player));
                 }
             }
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.flush(),
         }
     }
 
     pub fn set_header(&mut self, header: Header) {
         match &mut self.protocol {
-            NetConnectionProtocol::Local => {}
+            NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_) => {}
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.set_header(header);
             }
@@ -395,6 +969,9 @@ pub enum NetConnectionProtocol {
 
     /// Flash Remoting protocol, caused by connecting to a `http://` address.
     FlashRemoting(FlashRemoting),
+
+    /// A connection to a media server, caused by connecting to a `rtmp://` address.
+    Rtmp(RtmpConnection),
 }
 
 #[derive(Debug)]
@@ -464,32 +1041,22 @@ impl FlashRemoting {
                             response.url,
                             response.error
                         );
-                        if let Some(connection) = uc.net_connections.connections.get(self_handle) {
-                            match connection.object {
-                                NetConnectionObject::Avm2(object) => {
-                                    let mut activation =
-                                        Avm2Activation::from_nothing(uc.reborrow());
-                                    let url = AvmString::new_utf8(
-                                        activation.context.gc_context,
-                                        response.url,
-                                    );
-                                    let event = Avm2EventObject::net_status_event(
-                                        &mut activation,
-                                        "netStatus",
-                                        vec![
-                                            ("code", "NetConnection.Call.Failed".into()),
-                                            ("level", "error".into()),
-                                            ("details", url),
-                                            ("description", "HTTP: Failed".into()),
-                                        ],
-                                    );
-                                    Avm2::dispatch_event(
-                                        &mut activation.context,
-                                        event,
-                                        object.into(),
-                                    );
-                                }
-                            }
+                        if let Some(object) = uc
+                            .net_connections
+                            .connections
+                            .get(self_handle)
+                            .map(|connection| connection.object)
+                        {
+                            let url = AvmString::new_utf8(uc.gc_context, response.url);
+                            object.net_status(
+                                uc,
+                                vec![
+                                    ("code", "NetConnection.Call.Failed".into()),
+                                    ("level", "error".into()),
+                                    ("details", url),
+                                    ("description", "HTTP: Failed".into()),
+                                ],
+                            );
                         }
                     });
                     return Ok(());
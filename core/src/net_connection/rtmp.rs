@@ -0,0 +1,1111 @@
+//! The client side of the Real Time Messaging Protocol (RTMP).
+//!
+//! This handles the handshake, splitting messages into chunks and reassembling
+//! them, the protocol control messages, and the encoding of AMF command
+//! messages. Everything that needs to talk to ActionScript is handled by
+//! `NetConnections` instead.
+
+use super::ResponderHandle;
+use crate::streams::NetStream;
+use async_channel::Sender as AsyncSender;
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
+use fnv::FnvHashMap;
+use gc_arena::{DynamicRoot, Rootable};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use thiserror::Error;
+use url::Url;
+
+/// The port that RTMP servers listen on if the URL doesn't specify one.
+pub const DEFAULT_PORT: u16 = 1935;
+
+/// The RTMP version sent in the handshake, which is the only version in use.
+const RTMP_VERSION: u8 = 3;
+
+/// The size of C1, C2, S1 and S2 in the handshake.
+const HANDSHAKE_SIZE: usize = 1536;
+
+/// The chunk size that both sides use until they send a Set Chunk Size message.
+const DEFAULT_CHUNK_SIZE: usize = 128;
+
+/// The chunk size we switch to for our own messages once the handshake is done.
+const OUTGOING_CHUNK_SIZE: usize = 4096;
+
+/// The chunk stream used for protocol control messages.
+const PROTOCOL_CHUNK_STREAM: u32 = 2;
+
+/// The chunk stream used for commands sent on the connection itself.
+const COMMAND_CHUNK_STREAM: u32 = 3;
+
+/// The chunk stream used for commands sent on a media stream.
+const STREAM_CHUNK_STREAM: u32 = 8;
+
+/// RTMP message type IDs.
+///
+/// The audio, video and AMF0 data message types share their IDs with the
+/// equivalent FLV tag types.
+pub mod message_type {
+    pub const SET_CHUNK_SIZE: u8 = 1;
+    pub const ABORT: u8 = 2;
+    pub const ACKNOWLEDGEMENT: u8 = 3;
+    pub const USER_CONTROL: u8 = 4;
+    pub const WINDOW_ACK_SIZE: u8 = 5;
+    pub const SET_PEER_BANDWIDTH: u8 = 6;
+    pub const AUDIO: u8 = 8;
+    pub const VIDEO: u8 = 9;
+    pub const DATA_AMF3: u8 = 15;
+    pub const COMMAND_AMF3: u8 = 17;
+    pub const DATA_AMF0: u8 = 18;
+    pub const COMMAND_AMF0: u8 = 20;
+}
+
+/// User control event types.
+const PING_REQUEST: u16 = 6;
+const PING_RESPONSE: u16 = 7;
+
+#[derive(Debug, Error)]
+pub enum RtmpError {
+    #[error("Server uses unsupported RTMP version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("Chunk continues unknown chunk stream {0}")]
+    UnknownChunkStream(u32),
+
+    #[error("Malformed {0} message")]
+    MalformedMessage(&'static str),
+
+    #[error("Invalid AMF data in command")]
+    InvalidAmf,
+
+    #[error("Couldn't serialize command")]
+    Serialization,
+}
+
+/// A complete RTMP message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RtmpMessage {
+    pub type_id: u8,
+
+    /// The message stream this message belongs to, where `0` is the
+    /// connection itself.
+    pub stream_id: u32,
+
+    /// The timestamp of the message in milliseconds.
+    pub timestamp: u32,
+
+    pub payload: Vec<u8>,
+}
+
+impl RtmpMessage {
+    fn control(type_id: u8, payload: Vec<u8>) -> Self {
+        Self {
+            type_id,
+            stream_id: 0,
+            timestamp: 0,
+            payload,
+        }
+    }
+
+    fn user_control(event: u16, data: u32) -> Self {
+        let mut payload = event.to_be_bytes().to_vec();
+        payload.extend_from_slice(&data.to_be_bytes());
+        Self::control(message_type::USER_CONTROL, payload)
+    }
+}
+
+fn read_u24(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn write_u24(output: &mut Vec<u8>, value: u32) {
+    output.extend_from_slice(&value.to_be_bytes()[1..]);
+}
+
+/// The header state of one chunk stream, which later chunks on the same
+/// stream may omit parts of.
+#[derive(Debug, Default)]
+struct ChunkStream {
+    timestamp: u32,
+    timestamp_delta: u32,
+    length: usize,
+    type_id: u8,
+    stream_id: u32,
+
+    /// Whether the last header on this stream used an extended timestamp,
+    /// in which case type 3 chunks repeat it.
+    extended_timestamp: bool,
+
+    /// The part of the current message received so far.
+    payload: Vec<u8>,
+}
+
+enum Chunk {
+    /// There isn't a whole chunk in the buffer yet.
+    Incomplete,
+
+    /// A chunk was read, but its message isn't complete yet.
+    Partial,
+
+    Message(RtmpMessage),
+}
+
+/// Reassembles the messages sent by the server from its chunk stream.
+#[derive(Debug)]
+pub struct ChunkReader {
+    chunk_size: usize,
+    buffer: Vec<u8>,
+    streams: FnvHashMap<u32, ChunkStream>,
+}
+
+impl Default for ChunkReader {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            buffer: Vec::new(),
+            streams: Default::default(),
+        }
+    }
+}
+
+impl ChunkReader {
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.max(1);
+    }
+
+    /// Discard the partially received message on a chunk stream.
+    pub fn abort(&mut self, chunk_stream_id: u32) {
+        if let Some(stream) = self.streams.get_mut(&chunk_stream_id) {
+            stream.payload.clear();
+        }
+    }
+
+    /// Read the next complete message, if one has been received.
+    pub fn next_message(&mut self) -> Result<Option<RtmpMessage>, RtmpError> {
+        loop {
+            match self.read_chunk()? {
+                Chunk::Incomplete => return Ok(None),
+                Chunk::Partial => continue,
+                Chunk::Message(message) => return Ok(Some(message)),
+            }
+        }
+    }
+
+    fn read_chunk(&mut self) -> Result<Chunk, RtmpError> {
+        let buffer = &self.buffer;
+        let Some(&first) = buffer.first() else {
+            return Ok(Chunk::Incomplete);
+        };
+
+        let format = first >> 6;
+        let (chunk_stream_id, mut pos) = match first & 0x3F {
+            0 => match buffer.get(1) {
+                Some(&id) => (64 + id as u32, 2),
+                None => return Ok(Chunk::Incomplete),
+            },
+            1 => match buffer.get(1..3) {
+                Some(id) => (64 + id[0] as u32 + id[1] as u32 * 256, 3),
+                None => return Ok(Chunk::Incomplete),
+            },
+            id => (id as u32, 1),
+        };
+
+        let stream = self.streams.get(&chunk_stream_id);
+        if stream.is_none() && format != 0 {
+            return Err(RtmpError::UnknownChunkStream(chunk_stream_id));
+        }
+        let (received, length, extended_timestamp) = stream.map_or((0, 0, false), |stream| {
+            (
+                stream.payload.len(),
+                stream.length,
+                stream.extended_timestamp,
+            )
+        });
+
+        let header_length = [11, 7, 3, 0][format as usize];
+        let Some(header) = buffer.get(pos..pos + header_length) else {
+            return Ok(Chunk::Incomplete);
+        };
+        pos += header_length;
+
+        // Nothing is changed until the whole chunk has arrived, so that an
+        // incomplete chunk is read again from the start.
+        let timestamp_field = if format < 3 { read_u24(header) } else { 0 };
+        let extended_timestamp = if format < 3 {
+            timestamp_field == 0xFFFFFF
+        } else {
+            extended_timestamp
+        };
+        let length = if format < 2 {
+            read_u24(&header[3..]) as usize
+        } else {
+            length
+        };
+
+        let timestamp_field = if extended_timestamp {
+            let Some(extended) = buffer.get(pos..pos + 4) else {
+                return Ok(Chunk::Incomplete);
+            };
+            pos += 4;
+            read_u32(extended)
+        } else {
+            timestamp_field
+        };
+
+        let data_length = length.saturating_sub(received).min(self.chunk_size);
+        let Some(data) = buffer.get(pos..pos + data_length) else {
+            return Ok(Chunk::Incomplete);
+        };
+        pos += data_length;
+
+        let stream = self.streams.entry(chunk_stream_id).or_default();
+        stream.extended_timestamp = extended_timestamp;
+        stream.length = length;
+        if format < 2 {
+            stream.type_id = header[6];
+        }
+        if format == 0 {
+            stream.stream_id = u32::from_le_bytes([header[7], header[8], header[9], header[10]]);
+        }
+
+        if received == 0 {
+            match format {
+                0 => {
+                    // A following type 3 chunk repeats this timestamp as its delta.
+                    stream.timestamp = timestamp_field;
+                    stream.timestamp_delta = timestamp_field;
+                }
+                1 | 2 => {
+                    stream.timestamp_delta = timestamp_field;
+                    stream.timestamp = stream.timestamp.wrapping_add(timestamp_field);
+                }
+                _ => stream.timestamp = stream.timestamp.wrapping_add(stream.timestamp_delta),
+            }
+        }
+
+        stream.payload.extend_from_slice(data);
+        let chunk = if stream.payload.len() >= stream.length {
+            Chunk::Message(RtmpMessage {
+                type_id: stream.type_id,
+                stream_id: stream.stream_id,
+                timestamp: stream.timestamp,
+                payload: std::mem::take(&mut stream.payload),
+            })
+        } else {
+            Chunk::Partial
+        };
+
+        self.buffer.drain(..pos);
+        Ok(chunk)
+    }
+}
+
+/// Split a message into chunks on the given chunk stream.
+///
+/// Every message is sent with a full header, so `chunk_stream_id` must be
+/// between 2 and 63.
+pub fn write_chunks(
+    output: &mut Vec<u8>,
+    chunk_size: usize,
+    chunk_stream_id: u32,
+    message: &RtmpMessage,
+) {
+    debug_assert!((2..64).contains(&chunk_stream_id));
+    let extended_timestamp = message.timestamp >= 0xFFFFFF;
+
+    output.push(chunk_stream_id as u8);
+    write_u24(output, message.timestamp.min(0xFFFFFF));
+    write_u24(output, message.payload.len() as u32);
+    output.push(message.type_id);
+    output.extend_from_slice(&message.stream_id.to_le_bytes());
+    if extended_timestamp {
+        output.extend_from_slice(&message.timestamp.to_be_bytes());
+    }
+
+    for (i, chunk) in message.payload.chunks(chunk_size).enumerate() {
+        if i > 0 {
+            output.push(0xC0 | chunk_stream_id as u8);
+            if extended_timestamp {
+                output.extend_from_slice(&message.timestamp.to_be_bytes());
+            }
+        }
+        output.extend_from_slice(chunk);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HandshakeState {
+    /// The socket isn't connected yet.
+    NotStarted,
+
+    /// C0 and C1 have been sent, and we're waiting for S0, S1 and S2.
+    AwaitingServer,
+
+    Done,
+}
+
+/// The client side of an RTMP session, independent of any transport.
+///
+/// Data received from the server is passed to `receive`, and the data that
+/// should be sent back is collected with `take_output`.
+#[derive(Debug)]
+pub struct RtmpClient {
+    handshake_state: HandshakeState,
+    handshake_buffer: Vec<u8>,
+    reader: ChunkReader,
+    chunk_size: usize,
+    output: Vec<u8>,
+
+    /// Messages sent before the handshake was done.
+    queued: Vec<(u32, RtmpMessage)>,
+
+    bytes_received: u64,
+    bytes_acknowledged: u64,
+
+    /// How many bytes the server wants us to receive between acknowledgements.
+    window_ack_size: Option<u32>,
+}
+
+impl Default for RtmpClient {
+    fn default() -> Self {
+        Self {
+            handshake_state: HandshakeState::NotStarted,
+            handshake_buffer: Vec::new(),
+            reader: Default::default(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            output: Vec::new(),
+            queued: Vec::new(),
+            bytes_received: 0,
+            bytes_acknowledged: 0,
+            window_ack_size: None,
+        }
+    }
+}
+
+impl RtmpClient {
+    /// Send C0 and C1, once the socket has connected.
+    pub fn start_handshake(&mut self) {
+        if self.handshake_state != HandshakeState::NotStarted {
+            return;
+        }
+
+        // C1 is the time, four zero bytes and "random" data. Only the simple
+        // handshake is supported, so we leave it all zeroed.
+        self.output.push(RTMP_VERSION);
+        self.output.extend_from_slice(&[0; HANDSHAKE_SIZE]);
+        self.handshake_state = HandshakeState::AwaitingServer;
+    }
+
+    pub fn is_handshake_done(&self) -> bool {
+        self.handshake_state == HandshakeState::Done
+    }
+
+    /// Send a message on the given chunk stream.
+    ///
+    /// Messages sent before the handshake is done are queued until it is.
+    fn send(&mut self, chunk_stream_id: u32, message: RtmpMessage) {
+        if self.is_handshake_done() {
+            write_chunks(&mut self.output, self.chunk_size, chunk_stream_id, &message);
+        } else {
+            self.queued.push((chunk_stream_id, message));
+        }
+    }
+
+    /// Send a command, either on the connection or on a media stream.
+    pub fn send_command(&mut self, stream_id: u32, command: &Command) -> Result<(), RtmpError> {
+        let chunk_stream_id = if stream_id == 0 {
+            COMMAND_CHUNK_STREAM
+        } else {
+            STREAM_CHUNK_STREAM
+        };
+        self.send(
+            chunk_stream_id,
+            RtmpMessage {
+                type_id: message_type::COMMAND_AMF0,
+                stream_id,
+                timestamp: 0,
+                payload: command.encode()?,
+            },
+        );
+        Ok(())
+    }
+
+    /// The bytes that should be sent to the server.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Process data received from the server, returning all the messages that
+    /// aren't protocol control messages.
+    pub fn receive(&mut self, data: &[u8]) -> Result<Vec<RtmpMessage>, RtmpError> {
+        self.bytes_received += data.len() as u64;
+
+        match self.handshake_state {
+            HandshakeState::NotStarted => return Ok(vec![]),
+            HandshakeState::AwaitingServer => {
+                self.handshake_buffer.extend_from_slice(data);
+                if self.handshake_buffer.len() < 1 + HANDSHAKE_SIZE * 2 {
+                    return Ok(vec![]);
+                }
+                self.finish_handshake()?;
+            }
+            HandshakeState::Done => self.reader.push(data),
+        }
+
+        let mut messages = vec![];
+        while let Some(message) = self.reader.next_message()? {
+            self.handle_message(message, &mut messages)?;
+        }
+
+        if let Some(window_ack_size) = self.window_ack_size {
+            if self.bytes_received - self.bytes_acknowledged >= window_ack_size as u64 {
+                self.bytes_acknowledged = self.bytes_received;
+                self.send(
+                    PROTOCOL_CHUNK_STREAM,
+                    RtmpMessage::control(
+                        message_type::ACKNOWLEDGEMENT,
+                        (self.bytes_received as u32).to_be_bytes().to_vec(),
+                    ),
+                );
+            }
+        }
+
+        Ok(messages)
+    }
+
+    fn finish_handshake(&mut self) -> Result<(), RtmpError> {
+        let buffer = std::mem::take(&mut self.handshake_buffer);
+        let version = buffer[0];
+        if version != RTMP_VERSION {
+            return Err(RtmpError::UnsupportedVersion(version));
+        }
+
+        // C2 echoes S1 back to the server. We don't check S2, as the server
+        // doesn't need to echo our all-zero C1.
+        let s1 = &buffer[1..1 + HANDSHAKE_SIZE];
+        self.output.extend_from_slice(s1);
+        self.reader.push(&buffer[1 + HANDSHAKE_SIZE * 2..]);
+        self.handshake_state = HandshakeState::Done;
+
+        self.send(
+            PROTOCOL_CHUNK_STREAM,
+            RtmpMessage::control(
+                message_type::SET_CHUNK_SIZE,
+                (OUTGOING_CHUNK_SIZE as u32).to_be_bytes().to_vec(),
+            ),
+        );
+        self.chunk_size = OUTGOING_CHUNK_SIZE;
+
+        for (chunk_stream_id, message) in std::mem::take(&mut self.queued) {
+            self.send(chunk_stream_id, message);
+        }
+        Ok(())
+    }
+
+    fn handle_message(
+        &mut self,
+        message: RtmpMessage,
+        messages: &mut Vec<RtmpMessage>,
+    ) -> Result<(), RtmpError> {
+        let payload = &message.payload;
+        match message.type_id {
+            message_type::SET_CHUNK_SIZE => {
+                let size = payload
+                    .get(0..4)
+                    .ok_or(RtmpError::MalformedMessage("Set Chunk Size"))?;
+                self.reader
+                    .set_chunk_size((read_u32(size) & 0x7FFFFFFF) as usize);
+            }
+            message_type::ABORT => {
+                let chunk_stream_id = payload
+                    .get(0..4)
+                    .ok_or(RtmpError::MalformedMessage("Abort"))?;
+                self.reader.abort(read_u32(chunk_stream_id));
+            }
+            message_type::ACKNOWLEDGEMENT => {}
+            message_type::USER_CONTROL => {
+                let event = payload
+                    .get(0..2)
+                    .ok_or(RtmpError::MalformedMessage("User Control"))?;
+                if u16::from_be_bytes([event[0], event[1]]) == PING_REQUEST {
+                    let timestamp = payload
+                        .get(2..6)
+                        .ok_or(RtmpError::MalformedMessage("Ping Request"))?;
+                    self.send(
+                        PROTOCOL_CHUNK_STREAM,
+                        RtmpMessage::user_control(PING_RESPONSE, read_u32(timestamp)),
+                    );
+                }
+            }
+            message_type::WINDOW_ACK_SIZE => {
+                let size = payload
+                    .get(0..4)
+                    .ok_or(RtmpError::MalformedMessage("Window Acknowledgement Size"))?;
+                self.window_ack_size = Some(read_u32(size));
+            }
+            message_type::SET_PEER_BANDWIDTH => {
+                // Flash Player answers by adopting the peer bandwidth as its
+                // own acknowledgement window.
+                let size = payload
+                    .get(0..4)
+                    .ok_or(RtmpError::MalformedMessage("Set Peer Bandwidth"))?;
+                self.send(
+                    PROTOCOL_CHUNK_STREAM,
+                    RtmpMessage::control(message_type::WINDOW_ACK_SIZE, size.to_vec()),
+                );
+            }
+            _ => messages.push(message),
+        }
+        Ok(())
+    }
+}
+
+/// Write a single AMF0 value.
+fn write_amf0(output: &mut Vec<u8>, value: &AmfValue) -> Result<(), RtmpError> {
+    let element = Element::new("", Rc::new(value.clone()));
+    let mut lso = Lso::new(vec![element], "", AMFVersion::AMF0);
+    let bytes = flash_lso::write::write_to_bytes(&mut lso).map_err(|_| RtmpError::Serialization)?;
+    // Like `Socket.writeObject`, strip out the LSO header, element name and
+    // padding so that only the value itself is left.
+    output.extend_from_slice(
+        &bytes[flash_lso::write::header_length(&lso.header) + 8..bytes.len() - 1],
+    );
+    Ok(())
+}
+
+/// Read every AMF0 value in a message payload.
+pub fn read_amf0_values(mut bytes: &[u8]) -> Result<Vec<AmfValue>, RtmpError> {
+    let mut decoder = AMF0Decoder::default();
+    let mut values = vec![];
+    while !bytes.is_empty() {
+        let (rest, value) = decoder
+            .parse_single_element(bytes)
+            .map_err(|_| RtmpError::InvalidAmf)?;
+        values.push(AmfValue::clone(&value));
+        bytes = rest;
+    }
+    Ok(values)
+}
+
+/// The payload of a data message as AMF0, which is how FLV script tags store it.
+///
+/// AMF3 data messages are AMF0 values that switch to AMF3 where needed,
+/// preceded by a format selector byte.
+pub fn amf0_data_payload(message: &RtmpMessage) -> &[u8] {
+    match message.type_id {
+        message_type::DATA_AMF3 | message_type::COMMAND_AMF3 => {
+            message.payload.get(1..).unwrap_or_default()
+        }
+        _ => &message.payload,
+    }
+}
+
+/// An AMF command message, such as `connect` or the `_result` of a call.
+#[derive(Clone, Debug)]
+pub struct Command {
+    pub name: String,
+
+    /// The ID used to match up a command with its `_result` or `_error`, or
+    /// `0` if no response is expected.
+    pub transaction_id: f64,
+
+    pub command_object: AmfValue,
+    pub arguments: Vec<AmfValue>,
+}
+
+impl Command {
+    pub fn new(name: impl Into<String>, transaction_id: f64, arguments: Vec<AmfValue>) -> Self {
+        Self {
+            name: name.into(),
+            transaction_id,
+            command_object: AmfValue::Null,
+            arguments,
+        }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, RtmpError> {
+        let mut output = vec![];
+        write_amf0(&mut output, &AmfValue::String(self.name.clone()))?;
+        write_amf0(&mut output, &AmfValue::Number(self.transaction_id))?;
+        write_amf0(&mut output, &self.command_object)?;
+        for argument in &self.arguments {
+            write_amf0(&mut output, argument)?;
+        }
+        Ok(output)
+    }
+
+    pub fn decode(message: &RtmpMessage) -> Result<Self, RtmpError> {
+        let mut values = read_amf0_values(amf0_data_payload(message))?.into_iter();
+        let Some(AmfValue::String(name)) = values.next() else {
+            return Err(RtmpError::MalformedMessage("command"));
+        };
+        let transaction_id = match values.next() {
+            Some(AmfValue::Number(id)) => id,
+            _ => 0.0,
+        };
+        let command_object = values.next().unwrap_or(AmfValue::Null);
+        Ok(Self {
+            name,
+            transaction_id,
+            command_object,
+            arguments: values.collect(),
+        })
+    }
+}
+
+fn amf_object(properties: Vec<(&str, AmfValue)>) -> AmfValue {
+    AmfValue::Object(
+        properties
+            .into_iter()
+            .map(|(name, value)| Element::new(name, Rc::new(value)))
+            .collect(),
+        None,
+    )
+}
+
+/// The place a server should send an RTMP connection.
+#[derive(Clone, Debug)]
+pub struct RtmpAddress {
+    pub host: String,
+    pub port: u16,
+
+    /// The application to connect to, which is the path of the URL.
+    pub app: String,
+}
+
+impl RtmpAddress {
+    /// Parse an `rtmp://host[:port]/app[/instance]` URL.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = Url::parse(url).ok()?;
+        if url.scheme() != "rtmp" {
+            return None;
+        }
+        Some(Self {
+            host: url.host_str()?.to_string(),
+            port: url.port().unwrap_or(DEFAULT_PORT),
+            app: url.path().trim_start_matches('/').to_string(),
+        })
+    }
+}
+
+/// A `DynamicRoot` holding the `NetStream` that plays an RTMP stream.
+pub type NetStreamHandle = DynamicRoot<Rootable![NetStream<'_>]>;
+
+/// What to do with the `_result` or `_error` of a command we sent.
+#[derive(Debug)]
+pub enum Transaction {
+    Connect,
+
+    /// A `createStream` for the stream with this index in `RtmpConnection::streams`.
+    CreateStream(usize),
+
+    /// A `NetConnection.call`.
+    Call(ResponderHandle),
+}
+
+/// A media stream created on an RTMP connection for a `NetStream`.
+pub struct RtmpStream {
+    /// The message stream ID the server assigned, once `createStream` succeeded.
+    pub id: Option<u32>,
+
+    pub net_stream: NetStreamHandle,
+
+    /// Commands sent by the `NetStream` before the stream was created.
+    queued: Vec<Command>,
+
+    /// Whether the FLV header has been written to the `NetStream` buffer
+    /// since the last `play`.
+    pub flv_header_written: bool,
+}
+
+impl Debug for RtmpStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RtmpStream")
+            .field("id", &self.id)
+            .field("queued", &self.queued)
+            .field("flv_header_written", &self.flv_header_written)
+            .finish()
+    }
+}
+
+/// An RTMP `NetConnection` to a media server.
+#[derive(Debug)]
+pub struct RtmpConnection {
+    pub url: String,
+    pub client: RtmpClient,
+
+    /// The sending half of the socket. Dropping it closes the socket.
+    pub socket: Option<AsyncSender<Vec<u8>>>,
+
+    /// Whether the server accepted our `connect`.
+    pub connected: bool,
+
+    next_transaction_id: u32,
+    transactions: FnvHashMap<u32, Transaction>,
+
+    /// The streams created for `NetStream`s, by an index that is never reused,
+    /// so that a late `createStream` result can't reach the wrong stream.
+    pub streams: FnvHashMap<usize, RtmpStream>,
+    next_stream_index: usize,
+}
+
+impl RtmpConnection {
+    /// Create a connection, queueing the `connect` command to send once the
+    /// handshake is done.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        url: String,
+        address: &RtmpAddress,
+        flash_version: String,
+        swf_url: String,
+        page_url: Option<String>,
+        object_encoding: f64,
+        arguments: Vec<AmfValue>,
+        socket: AsyncSender<Vec<u8>>,
+    ) -> Result<Self, RtmpError> {
+        let mut connection = Self {
+            url: url.clone(),
+            client: Default::default(),
+            socket: Some(socket),
+            connected: false,
+            next_transaction_id: 1,
+            transactions: Default::default(),
+            streams: Default::default(),
+            next_stream_index: 0,
+        };
+
+        let command_object = amf_object(vec![
+            ("app", AmfValue::String(address.app.clone())),
+            ("flashVer", AmfValue::String(flash_version)),
+            ("swfUrl", AmfValue::String(swf_url)),
+            ("tcUrl", AmfValue::String(url)),
+            ("fpad", AmfValue::Bool(false)),
+            ("capabilities", AmfValue::Number(239.0)),
+            ("audioCodecs", AmfValue::Number(3575.0)),
+            ("videoCodecs", AmfValue::Number(252.0)),
+            ("videoFunction", AmfValue::Number(1.0)),
+            (
+                "pageUrl",
+                page_url.map_or(AmfValue::Undefined, AmfValue::String),
+            ),
+            ("objectEncoding", AmfValue::Number(object_encoding)),
+        ]);
+        let transaction_id = connection.start_transaction(Transaction::Connect);
+        connection.client.send_command(
+            0,
+            &Command {
+                name: "connect".to_string(),
+                transaction_id,
+                command_object,
+                arguments,
+            },
+        )?;
+        Ok(connection)
+    }
+
+    fn start_transaction(&mut self, transaction: Transaction) -> f64 {
+        let id = self.next_transaction_id;
+        self.next_transaction_id += 1;
+        self.transactions.insert(id, transaction);
+        id as f64
+    }
+
+    /// Take the transaction that a `_result` or `_error` responds to.
+    pub fn finish_transaction(&mut self, id: f64) -> Option<Transaction> {
+        self.transactions.remove(&(id as u32))
+    }
+
+    /// Send the data the client wants to send down the socket.
+    pub fn flush(&mut self) {
+        let output = self.client.take_output();
+        if output.is_empty() {
+            return;
+        }
+        if let Some(socket) = &self.socket {
+            if let Err(e) = socket.try_send(output) {
+                tracing::error!("Failed to send RTMP data to socket: {:?}", e);
+            }
+        }
+    }
+
+    /// Call a method on the server, like `NetConnection.call`.
+    pub fn call(
+        &mut self,
+        name: String,
+        responder: Option<ResponderHandle>,
+        arguments: Vec<AmfValue>,
+    ) -> Result<(), RtmpError> {
+        let transaction_id = match responder {
+            Some(responder) => self.start_transaction(Transaction::Call(responder)),
+            None => 0.0,
+        };
+        self.client
+            .send_command(0, &Command::new(name, transaction_id, arguments))
+    }
+
+    /// Answer a call made by the server.
+    pub fn respond(&mut self, transaction_id: f64, result: AmfValue) -> Result<(), RtmpError> {
+        self.client
+            .send_command(0, &Command::new("_result", transaction_id, vec![result]))
+    }
+
+    /// Ask the server to create a stream for a `NetStream`, returning the
+    /// index of the stream in `streams`.
+    pub fn create_stream(&mut self, net_stream: NetStreamHandle) -> Result<usize, RtmpError> {
+        let index = self.next_stream_index;
+        self.next_stream_index += 1;
+        self.streams.insert(
+            index,
+            RtmpStream {
+                id: None,
+                net_stream,
+                queued: vec![],
+                flv_header_written: false,
+            },
+        );
+        let transaction_id = self.start_transaction(Transaction::CreateStream(index));
+        self.client
+            .send_command(0, &Command::new("createStream", transaction_id, vec![]))?;
+        Ok(index)
+    }
+
+    /// Record the ID the server assigned to a stream, and send everything the
+    /// `NetStream` wanted to send to it in the meantime.
+    ///
+    /// If the stream was deleted in the meantime, the server is told to delete it too.
+    pub fn stream_created(&mut self, index: usize, id: u32) -> Result<(), RtmpError> {
+        let Some(stream) = self.streams.get_mut(&index) else {
+            return self.send_delete_stream(id);
+        };
+        stream.id = Some(id);
+        for command in std::mem::take(&mut stream.queued) {
+            self.client.send_command(id, &command)?;
+        }
+        Ok(())
+    }
+
+    /// Send a command such as `play` or `publish` on a stream.
+    pub fn send_stream_command(&mut self, index: usize, command: Command) -> Result<(), RtmpError> {
+        let Some(stream) = self.streams.get_mut(&index) else {
+            return Ok(());
+        };
+        if matches!(command.name.as_str(), "play" | "publish") {
+            stream.flv_header_written = false;
+        }
+        match stream.id {
+            Some(id) => self.client.send_command(id, &command),
+            None => {
+                stream.queued.push(command);
+                Ok(())
+            }
+        }
+    }
+
+    /// Forget a stream when its `NetStream` is closed, and ask the server to delete it.
+    pub fn delete_stream(&mut self, index: usize) -> Result<(), RtmpError> {
+        match self.streams.remove(&index) {
+            Some(RtmpStream { id: Some(id), .. }) => self.send_delete_stream(id),
+            _ => Ok(()),
+        }
+    }
+
+    fn send_delete_stream(&mut self, id: u32) -> Result<(), RtmpError> {
+        self.client.send_command(
+            0,
+            &Command::new("deleteStream", 0.0, vec![AmfValue::Number(id as f64)]),
+        )
+    }
+
+    pub fn stream_index(&self, id: u32) -> Option<usize> {
+        self.streams
+            .iter()
+            .find(|(_, stream)| stream.id == Some(id))
+            .map(|(index, _)| *index)
+    }
+}
+
+/// Convert an audio, video or data message into an FLV tag.
+///
+/// If `with_header` is set, the FLV file header is written first.
+pub fn write_flv_tag(output: &mut Vec<u8>, message: &RtmpMessage, with_header: bool) {
+    if with_header {
+        output.extend_from_slice(b"FLV\x01\x05\x00\x00\x00\x09");
+        output.extend_from_slice(&0u32.to_be_bytes());
+    }
+
+    let (tag_type, data) = match message.type_id {
+        message_type::DATA_AMF3 => (message_type::DATA_AMF0, amf0_data_payload(message)),
+        type_id => (type_id, message.payload.as_slice()),
+    };
+
+    output.push(tag_type);
+    write_u24(output, data.len() as u32);
+    write_u24(output, message.timestamp & 0xFFFFFF);
+    output.push((message.timestamp >> 24) as u8);
+    write_u24(output, 0);
+    output.extend_from_slice(data);
+    output.extend_from_slice(&(data.len() as u32 + 11).to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(type_id: u8, timestamp: u32, payload: Vec<u8>) -> RtmpMessage {
+        RtmpMessage {
+            type_id,
+            stream_id: 1,
+            timestamp,
+            payload,
+        }
+    }
+
+    #[test]
+    fn chunks_round_trip() {
+        let first = message(
+            message_type::VIDEO,
+            40,
+            (0..=255).cycle().take(300).collect(),
+        );
+        let second = message(message_type::AUDIO, 0x1234567, vec![1, 2, 3]);
+
+        let mut bytes = vec![];
+        write_chunks(&mut bytes, DEFAULT_CHUNK_SIZE, 6, &first);
+        write_chunks(&mut bytes, DEFAULT_CHUNK_SIZE, 4, &second);
+
+        // Feed the data in byte by byte to check that partial chunks are handled.
+        let mut reader = ChunkReader::default();
+        let mut messages = vec![];
+        for byte in bytes {
+            reader.push(&[byte]);
+            while let Some(message) = reader.next_message().unwrap() {
+                messages.push(message);
+            }
+        }
+        assert_eq!(messages, [first, second]);
+    }
+
+    #[test]
+    fn compressed_headers_accumulate_timestamps() {
+        let mut bytes = vec![];
+        // Type 0: timestamp 1000, length 2, audio, stream 1.
+        bytes.extend_from_slice(&[0x04, 0x00, 0x03, 0xE8, 0x00, 0x00, 0x02, 0x08, 1, 0, 0, 0]);
+        bytes.extend_from_slice(&[0xAA, 0xBB]);
+        // Type 2: delta 20.
+        bytes.extend_from_slice(&[0x84, 0x00, 0x00, 0x14, 0xCC, 0xDD]);
+        // Type 3: same delta again.
+        bytes.extend_from_slice(&[0xC4, 0xEE, 0xFF]);
+
+        let mut reader = ChunkReader::default();
+        reader.push(&bytes);
+        let timestamps: Vec<_> = std::iter::from_fn(|| reader.next_message().unwrap())
+            .map(|message| (message.timestamp, message.payload))
+            .collect();
+        assert_eq!(
+            timestamps,
+            [
+                (1000, vec![0xAA, 0xBB]),
+                (1020, vec![0xCC, 0xDD]),
+                (1040, vec![0xEE, 0xFF])
+            ]
+        );
+    }
+
+    #[test]
+    fn type_3_after_type_0_repeats_timestamp() {
+        let mut bytes = vec![];
+        // Type 0: timestamp 40, length 1, video, stream 1.
+        bytes.extend_from_slice(&[0x06, 0x00, 0x00, 0x28, 0x00, 0x00, 0x01, 0x09, 1, 0, 0, 0]);
+        bytes.push(0xAA);
+        // Type 3: a new message, with the type 0 timestamp as its delta.
+        bytes.extend_from_slice(&[0xC6, 0xBB]);
+        bytes.extend_from_slice(&[0xC6, 0xCC]);
+
+        let mut reader = ChunkReader::default();
+        reader.push(&bytes);
+        let messages: Vec<_> = std::iter::from_fn(|| reader.next_message().unwrap()).collect();
+        assert_eq!(
+            messages,
+            [
+                message(message_type::VIDEO, 40, vec![0xAA]),
+                message(message_type::VIDEO, 80, vec![0xBB]),
+                message(message_type::VIDEO, 120, vec![0xCC]),
+            ]
+        );
+    }
+
+    #[test]
+    fn handshake_then_control_messages() {
+        let mut client = RtmpClient::default();
+        client.start_handshake();
+        let c0c1 = client.take_output();
+        assert_eq!(c0c1.len(), 1 + HANDSHAKE_SIZE);
+        assert_eq!(c0c1[0], RTMP_VERSION);
+
+        let mut server = vec![RTMP_VERSION];
+        server.extend((0..HANDSHAKE_SIZE).map(|i| i as u8));
+        server.extend_from_slice(&[0; HANDSHAKE_SIZE]);
+        let mut ping = vec![];
+        write_chunks(
+            &mut ping,
+            DEFAULT_CHUNK_SIZE,
+            PROTOCOL_CHUNK_STREAM,
+            &RtmpMessage::user_control(PING_REQUEST, 1234),
+        );
+        server.extend_from_slice(&ping);
+
+        assert!(client.receive(&server).unwrap().is_empty());
+        assert!(client.is_handshake_done());
+
+        let output = client.take_output();
+        assert_eq!(&output[..HANDSHAKE_SIZE], &server[1..1 + HANDSHAKE_SIZE]);
+
+        let mut reader = ChunkReader::default();
+        reader.push(&output[HANDSHAKE_SIZE..]);
+        let set_chunk_size = reader.next_message().unwrap().unwrap();
+        assert_eq!(set_chunk_size.type_id, message_type::SET_CHUNK_SIZE);
+        reader.set_chunk_size(OUTGOING_CHUNK_SIZE);
+        let pong = reader.next_message().unwrap().unwrap();
+        assert_eq!(pong, RtmpMessage::user_control(PING_RESPONSE, 1234));
+    }
+
+    #[test]
+    fn flv_tags_from_messages() {
+        let mut flv = vec![];
+        write_flv_tag(
+            &mut flv,
+            &message(message_type::AUDIO, 33, vec![0xAF]),
+            true,
+        );
+        assert_eq!(
+            flv,
+            [
+                b'F', b'L', b'V', 1, 5, 0, 0, 0, 9, 0, 0, 0, 0, // Header
+                8, 0, 0, 1, 0, 0, 33, 0, 0, 0, 0, 0xAF, // Tag
+                0, 0, 0, 12, // Previous tag size
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_addresses() {
+        let address = RtmpAddress::parse("rtmp://example.com/live/room").unwrap();
+        assert_eq!(address.host, "example.com");
+        assert_eq!(address.port, DEFAULT_PORT);
+        assert_eq!(address.app, "live/room");
+
+        let address = RtmpAddress::parse("rtmp://localhost:1936/vod").unwrap();
+        assert_eq!(address.port, 1936);
+        assert_eq!(address.app, "vod");
+
+        assert!(RtmpAddress::parse("http://example.com/gateway").is_none());
+    }
+}
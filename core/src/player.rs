@@ -9,11 +9,12 @@ use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm2::api_version::ApiVersion;
 use crate::avm2::{
     object::LoaderInfoObject, object::TObject as _, Activation as Avm2Activation, Avm2, CallStack,
-    Object as Avm2Object,
+    Object as Avm2Object, Worker as Avm2Worker,
 };
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
@@ -39,9 +40,12 @@ use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
+use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
 use crate::net_connection::NetConnections;
+use crate::policy_file::PolicyFiles;
 use crate::prelude::*;
+use crate::profiler::{ProfileCategory, ProfileFormat, Profiler};
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::{AvmString, AvmStringInterner};
@@ -172,6 +176,9 @@ struct GcRootData<'gc> {
     /// List of active NetConnection objects.
     net_connections: NetConnections<'gc>,
 
+    /// List of LocalConnection objects, and the messages sent between them.
+    local_connections: LocalConnections<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     dynamic_root: DynamicRootSet<'gc>,
 }
@@ -201,6 +208,7 @@ impl<'gc> GcRootData<'gc> {
         &mut StreamManager<'gc>,
         &mut Sockets<'gc>,
         &mut NetConnections<'gc>,
+        &mut LocalConnections<'gc>,
         DynamicRootSet<'gc>,
     ) {
         (
@@ -222,6 +230,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.stream_manager,
             &mut self.sockets,
             &mut self.net_connections,
+            &mut self.local_connections,
             self.dynamic_root,
         )
     }
@@ -234,6 +243,7 @@ type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
 type Log = Box<dyn LogBackend>;
+type LocalConnectionBox = Box<dyn LocalConnectionBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
 
@@ -261,6 +271,7 @@ pub struct Player {
     storage: Storage,
     log: Log,
     ui: Ui,
+    local_connection_backend: LocalConnectionBox,
     video: Video,
 
     transform_stack: TransformStack,
@@ -275,8 +286,17 @@ pub struct Player {
 
     frame_phase: FramePhase,
 
+    /// The ActionScript profiler, if profiling has been started.
+    profiler: Option<Profiler>,
+
     stub_tracker: StubCollection,
 
+    /// The policy files that grant movies access to other domains.
+    policy_files: PolicyFiles,
+
+    /// The data of shared objects that are waiting for the user to grant more storage space, by name.
+    pending_flushes: HashMap<String, Vec<u8>>,
+
     /// A time budget for executing frames.
     /// Gained by passage of time between host frames, spent by executing SWF frames.
     /// This is how we support custom SWF framerates
@@ -284,6 +304,9 @@ pub struct Player {
     frame_accumulator: f64,
     recent_run_frame_timings: VecDeque<f64>,
 
+    /// The number of times `run_frame` has been called since the player was created.
+    run_frame_count: u64,
+
     /// Faked time passage for fooling hand-written busy-loop FPS limiters.
     time_offset: u32,
 
@@ -608,6 +631,19 @@ impl Player {
         self.mouse_in_stage = is_in;
     }
 
+    /// The number of times `run_frame` has been called since the player was created.
+    pub fn run_frame_count(&self) -> u64 {
+        self.run_frame_count
+    }
+
+    /// Converts a position in viewport pixels, as used by mouse events, into stage pixels.
+    pub fn viewport_to_stage_position(&mut self, x: f64, y: f64) -> (f64, f64) {
+        let inverse_view_matrix =
+            self.mutate_with_update_context(|context| context.stage.inverse_view_matrix());
+        let position = inverse_view_matrix * Point::from_pixels(x, y);
+        (position.x.to_pixels(), position.y.to_pixels())
+    }
+
     /// Returns the master volume of the player. 1.0 is 100% volume.
     ///
     /// The volume is linear and not adapted for logarithmic hearing.
@@ -1638,6 +1674,7 @@ impl Player {
 
     #[instrument(level = "debug", skip_all)]
     pub fn run_frame(&mut self) {
+        self.run_frame_count += 1;
         let frame_time = Duration::from_nanos((750_000_000.0 / self.frame_rate) as u64);
         let (mut execution_limit, may_execute_while_streaming) = match self.load_behavior {
             LoadBehavior::Streaming => (
@@ -1659,9 +1696,12 @@ impl Player {
         self.update(|context| {
             if context.is_action_script_3() {
                 run_all_phases_avm2(context);
+                Avm2Worker::run_all(context);
             } else {
                 Avm1::run_frame(context);
             }
+            // LocalConnection messages are delivered once per frame.
+            LocalConnections::update_connections(context);
             AudioManager::update_sounds(context);
         });
 
@@ -1745,6 +1785,11 @@ impl Player {
         &self.navigator
     }
 
+    /// The unimplemented APIs that this player's movies have used so far.
+    pub fn stub_tracker(&self) -> &StubCollection {
+        &self.stub_tracker
+    }
+
     // The frame rate of the current movie in FPS.
     pub fn frame_rate(&self) -> f64 {
         self.frame_rate
@@ -1905,6 +1950,7 @@ impl Player {
                 stream_manager,
                 sockets,
                 net_connections,
+                local_connections,
                 dynamic_root,
             ) = root_data.update_context_params();
 
@@ -1932,6 +1978,7 @@ impl Player {
                 page_url: &mut self.page_url,
                 instance_counter: &mut self.instance_counter,
                 storage: self.storage.deref_mut(),
+                local_connection_backend: self.local_connection_backend.deref_mut(),
                 log: self.log.deref_mut(),
                 video: self.video.deref_mut(),
                 avm1_shared_objects,
@@ -1954,10 +2001,14 @@ impl Player {
                 forced_frame_rate: self.forced_frame_rate,
                 actions_since_timeout_check: &mut self.actions_since_timeout_check,
                 frame_phase: &mut self.frame_phase,
+                profiler: &mut self.profiler,
                 stub_tracker: &mut self.stub_tracker,
+                policy_files: &mut self.policy_files,
+                pending_flushes: &mut self.pending_flushes,
                 stream_manager,
                 sockets,
                 net_connections,
+                local_connections,
                 dynamic_root,
             };
 
@@ -2033,7 +2084,21 @@ impl Player {
         self.update_mouse_state(false, false);
 
         // GC
+        if let Some(profiler) = &mut self.profiler {
+            profiler.enter(ProfileCategory::Gc, "[GC]".to_string());
+        }
         self.gc_arena.borrow_mut().collect_debt();
+        if let Some(profiler) = &mut self.profiler {
+            profiler.exit();
+        }
+
+        // Report the objects that were just collected to `flash.sampler`.
+        self.gc_arena.borrow().mutate(|gc_context, gc_root| {
+            let sampler = gc_root.data.read().avm2.sampler();
+            if sampler.read().has_live_objects() {
+                sampler.write(gc_context).record_collected();
+            }
+        });
 
         rval
     }
@@ -2044,9 +2109,12 @@ impl Player {
                 Activation::try_from_stub(context.reborrow(), ActivationIdentifier::root("[Flush]"))
             {
                 for so in avm1_activation.context.avm1_shared_objects.clone().values() {
-                    if let Err(e) =
-                        crate::avm1::globals::shared_object::flush(&mut avm1_activation, *so, &[])
-                    {
+                    if let Err(e) = crate::avm1::globals::shared_object::write_to_storage(
+                        &mut avm1_activation,
+                        *so,
+                        0,
+                        false,
+                    ) {
                         tracing::error!("Error flushing AVM1 shared object `{:?}`: {:?}", so, e);
                     }
                 }
@@ -2054,10 +2122,11 @@ impl Player {
 
             let mut avm2_activation = Avm2Activation::from_nothing(context.reborrow());
             for so in avm2_activation.context.avm2_shared_objects.clone().values() {
-                if let Err(e) = crate::avm2::globals::flash::net::shared_object::flush(
+                if let Err(e) = crate::avm2::globals::flash::net::shared_object::write_to_storage(
                     &mut avm2_activation,
                     *so,
-                    &[],
+                    0,
+                    false,
                 ) {
                     tracing::error!("Error flushing AVM2 shared object `{:?}`: {:?}", so, e);
                 }
@@ -2132,6 +2201,30 @@ impl Player {
         self.max_execution_duration = max_execution_duration
     }
 
+    /// Start recording a profile of ActionScript execution.
+    ///
+    /// Any profile that was already being recorded is discarded.
+    pub fn start_profiling(&mut self) {
+        self.profiler = Some(Profiler::new());
+    }
+
+    /// Whether a profile of ActionScript execution is being recorded.
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.is_some()
+    }
+
+    /// Stop profiling, returning the recorded profile.
+    pub fn stop_profiling(&mut self) -> Option<Profiler> {
+        self.profiler.take()
+    }
+
+    /// Export the profile recorded so far, without stopping profiling.
+    pub fn export_profile(&self, format: ProfileFormat) -> Option<String> {
+        self.profiler
+            .as_ref()
+            .map(|profiler| profiler.export(format))
+    }
+
     pub fn callstack(&self) -> StaticCallstack {
         StaticCallstack {
             arena: Rc::downgrade(&self.gc_arena),
@@ -2162,6 +2255,7 @@ pub struct PlayerBuilder {
 
     // Backends
     audio: Option<Audio>,
+    local_connection_backend: Option<LocalConnectionBox>,
     log: Option<Log>,
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
@@ -2187,6 +2281,7 @@ pub struct PlayerBuilder {
     player_version: Option<u8>,
     quality: StageQuality,
     sandbox_type: SandboxType,
+    enforce_policy_files: bool,
     page_url: Option<String>,
     frame_rate: Option<f64>,
     external_interface_providers: Vec<Box<dyn ExternalInterfaceProvider>>,
@@ -2204,6 +2299,7 @@ impl PlayerBuilder {
             movie: None,
 
             audio: None,
+            local_connection_backend: None,
             log: None,
             navigator: None,
             renderer: None,
@@ -2233,6 +2329,7 @@ impl PlayerBuilder {
             player_version: None,
             quality: StageQuality::High,
             sandbox_type: SandboxType::LocalTrusted,
+            enforce_policy_files: false,
             page_url: None,
             frame_rate: None,
             external_interface_providers: vec![],
@@ -2282,6 +2379,16 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the local connection backend of the player.
+    #[inline]
+    pub fn with_local_connection_backend(
+        mut self,
+        backend: impl 'static + LocalConnectionBackend,
+    ) -> Self {
+        self.local_connection_backend = Some(Box::new(backend));
+        self
+    }
+
     /// Sets the storage backend of the player.
     #[inline]
     pub fn with_storage(mut self, storage: impl 'static + StorageBackend) -> Self {
@@ -2396,6 +2503,12 @@ impl PlayerBuilder {
         self
     }
 
+    /// Configures whether remote movies need policy files to access other domains (default is false)
+    pub fn with_policy_files(mut self, enforce: bool) -> Self {
+        self.enforce_policy_files = enforce;
+        self
+    }
+
     // Configure the embedding page's URL (if applicable)
     pub fn with_page_url(mut self, page_url: Option<String>) -> Self {
         self.page_url = page_url;
@@ -2464,6 +2577,7 @@ impl PlayerBuilder {
                     stream_manager: StreamManager::new(),
                     sockets: Sockets::empty(),
                     net_connections: NetConnections::default(),
+                    local_connections: LocalConnections::default(),
                     dynamic_root,
                 },
             ),
@@ -2477,6 +2591,9 @@ impl PlayerBuilder {
         let audio = self
             .audio
             .unwrap_or_else(|| Box::new(audio::NullAudioBackend::new()));
+        let local_connection_backend = self
+            .local_connection_backend
+            .unwrap_or_else(|| Box::new(local_connection::NullLocalConnectionBackend::new()));
         let log = self
             .log
             .unwrap_or_else(|| Box::new(log::NullLogBackend::new()));
@@ -2510,6 +2627,7 @@ impl PlayerBuilder {
             Mutex::new(Player {
                 // Backends
                 audio,
+                local_connection_backend,
                 log,
                 navigator,
                 renderer,
@@ -2525,8 +2643,10 @@ impl PlayerBuilder {
                 frame_rate,
                 forced_frame_rate,
                 frame_phase: Default::default(),
+                profiler: None,
                 frame_accumulator: 0.0,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
+                run_frame_count: 0,
                 start_time: Instant::now(),
                 time_offset: 0,
                 time_til_next_timer: None,
@@ -2554,6 +2674,8 @@ impl PlayerBuilder {
                 spoofed_url: self.spoofed_url.clone(),
                 compatibility_rules: self.compatibility_rules.clone(),
                 stub_tracker: StubCollection::new(),
+                policy_files: PolicyFiles::new(self.enforce_policy_files),
+                pending_flushes: HashMap::new(),
                 #[cfg(feature = "egui")]
                 debug_ui: Default::default(),
 
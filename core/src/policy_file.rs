@@ -0,0 +1,603 @@
+//! Cross-domain policy files, which grant movies access to data on other domains.
+//!
+//! URL policy files (`crossdomain.xml`) are fetched over HTTP, and decide
+//! whether a movie may read data loaded from another domain. Socket policy
+//! files are requested from a policy server with `<policy-file-request/>`, and
+//! decide whether a movie may open a socket to a host and port.
+
+use crate::avm1::globals::system::SandboxType;
+use crate::backend::navigator::Request;
+use crate::context::UpdateContext;
+use crate::player::Player;
+use async_channel::{bounded, Sender as AsyncSender};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// The port on which socket master policy files are served.
+pub const SOCKET_MASTER_POLICY_PORT: u16 = 843;
+
+/// The request a policy server answers with its policy file.
+pub const SOCKET_POLICY_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// Which policy files a master policy file permits on its host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetaPolicy {
+    /// No policy files are permitted, including the master policy file itself.
+    None,
+
+    /// Only the master policy file is permitted.
+    MasterOnly,
+
+    /// Only policy files served as `text/x-cross-domain-policy` are permitted.
+    ByContentType,
+
+    /// Only policy files named `crossdomain.xml` are permitted.
+    ByFtpFilename,
+
+    /// All policy files are permitted.
+    All,
+}
+
+impl MetaPolicy {
+    fn from_attribute(value: &[u8]) -> Option<Self> {
+        match value {
+            b"none" => Some(Self::None),
+            b"master-only" => Some(Self::MasterOnly),
+            b"by-content-type" => Some(Self::ByContentType),
+            b"by-ftp-filename" => Some(Self::ByFtpFilename),
+            b"all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    /// Whether policy files other than the master policy file are permitted.
+    ///
+    /// We don't know the content type of fetched files, so `by-content-type`
+    /// is treated like `all`.
+    fn permits_other_policies(self) -> bool {
+        !matches!(self, Self::None | Self::MasterOnly)
+    }
+}
+
+/// A single `<allow-access-from>` entry of a policy file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct AllowAccessFrom {
+    /// The domain that is granted access, possibly with a `*.` wildcard prefix.
+    domain: String,
+
+    /// The ports that sockets may connect to, if the entry specifies them.
+    to_ports: Option<Vec<RangeInclusive<u16>>>,
+
+    /// Whether movies loaded over HTTP are denied access to an HTTPS host.
+    secure: Option<bool>,
+}
+
+impl AllowAccessFrom {
+    fn allows_domain(&self, domain: &str) -> bool {
+        if self.domain == "*" {
+            return true;
+        }
+
+        if let Some(suffix) = self.domain.strip_prefix("*.") {
+            let domain = domain.to_ascii_lowercase();
+            let suffix = suffix.to_ascii_lowercase();
+            return domain == suffix || domain.ends_with(&format!(".{suffix}"));
+        }
+
+        self.domain.eq_ignore_ascii_case(domain)
+    }
+
+    fn allows_port(&self, port: u16) -> bool {
+        self.to_ports
+            .as_ref()
+            .map(|ranges| ranges.iter().any(|range| range.contains(&port)))
+            .unwrap_or(false)
+    }
+}
+
+/// A parsed cross-domain policy file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PolicyFile {
+    /// The meta-policy declared by a `<site-control>` entry.
+    meta_policy: Option<MetaPolicy>,
+
+    allow_access_from: Vec<AllowAccessFrom>,
+}
+
+impl PolicyFile {
+    /// Parse a policy file.
+    ///
+    /// Returns `None` if the data isn't a `<cross-domain-policy>` document.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        reader.trim_text(true);
+
+        let mut policy = PolicyFile::default();
+        let mut depth = 0;
+        let mut is_policy = false;
+        loop {
+            let (element, is_empty) = match reader.read_event() {
+                Ok(Event::Start(element)) => (element, false),
+                Ok(Event::Empty(element)) => (element, true),
+                Ok(Event::End(_)) => {
+                    depth -= 1;
+                    continue;
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => continue,
+                Err(e) => {
+                    tracing::warn!("Invalid policy file: {e}");
+                    return None;
+                }
+            };
+
+            let name = element.name().into_inner();
+            if depth == 0 {
+                if name != b"cross-domain-policy" {
+                    return None;
+                }
+                is_policy = true;
+            } else if depth == 1 {
+                let attribute = |key: &[u8]| {
+                    element
+                        .attributes()
+                        .with_checks(false)
+                        .filter_map(Result::ok)
+                        .find(|attribute| attribute.key.into_inner() == key)
+                        .map(|attribute| String::from_utf8_lossy(&attribute.value).into_owned())
+                };
+
+                match name {
+                    b"site-control" => {
+                        policy.meta_policy = attribute(b"permitted-cross-domain-policies")
+                            .and_then(|value| MetaPolicy::from_attribute(value.as_bytes()));
+                    }
+                    b"allow-access-from" => {
+                        if let Some(domain) = attribute(b"domain") {
+                            policy.allow_access_from.push(AllowAccessFrom {
+                                domain,
+                                to_ports: attribute(b"to-ports").map(|ports| parse_ports(&ports)),
+                                secure: attribute(b"secure").map(|secure| secure != "false"),
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if !is_empty {
+                depth += 1;
+            }
+        }
+
+        is_policy.then_some(policy)
+    }
+
+    pub fn meta_policy(&self) -> Option<MetaPolicy> {
+        self.meta_policy
+    }
+
+    /// Whether this policy file, served at `policy_url`, lets a movie from
+    /// `requester` read data from its host.
+    pub fn allows_url(&self, requester: &Url, policy_url: &Url) -> bool {
+        let domain = requester.host_str().unwrap_or_default();
+        let is_insecure_requester = requester.scheme() != "https";
+        let is_secure_policy = policy_url.scheme() == "https";
+
+        self.allow_access_from.iter().any(|entry| {
+            // HTTPS hosts only grant access to HTTP movies if they explicitly say so.
+            let secure = entry.secure.unwrap_or(is_secure_policy);
+            entry.allows_domain(domain) && !(is_secure_policy && is_insecure_requester && secure)
+        })
+    }
+
+    /// Whether this policy file, served on `policy_port`, lets a movie from
+    /// `domain` open a socket to `port`.
+    pub fn allows_socket(&self, domain: &str, port: u16, policy_port: u16) -> bool {
+        // Policy servers on unprivileged ports can't grant access to privileged ports.
+        if policy_port >= 1024 && port < 1024 {
+            return false;
+        }
+
+        self.allow_access_from
+            .iter()
+            .any(|entry| entry.allows_domain(domain) && entry.allows_port(port))
+    }
+}
+
+/// Parse a `to-ports` attribute, such as `*` or `507,516-523`.
+fn parse_ports(ports: &str) -> Vec<RangeInclusive<u16>> {
+    ports
+        .split(',')
+        .filter_map(|range| {
+            let range = range.trim();
+            if range == "*" {
+                return Some(0..=u16::MAX);
+            }
+            match range.split_once('-') {
+                Some((start, end)) => Some(start.trim().parse().ok()?..=end.trim().parse().ok()?),
+                None => {
+                    let port = range.parse().ok()?;
+                    Some(port..=port)
+                }
+            }
+        })
+        .collect()
+}
+
+/// The outcome of checking whether a movie may connect a socket.
+pub enum SocketAccess {
+    Allowed,
+    Denied,
+
+    /// The policy file of a policy server must be requested before deciding.
+    Pending {
+        host: String,
+        port: u16,
+    },
+}
+
+/// Manages the policy files known to a player.
+///
+/// Policy files are cached by their location, so every file is only fetched
+/// once per player.
+#[derive(Default)]
+pub struct PolicyFiles {
+    /// Whether policy files are required for remote movies at all.
+    ///
+    /// Embedders that can't serve policy files, like the web player, leave this off.
+    enforced: bool,
+
+    /// Fetched URL policy files, by their URL. `None` if no valid policy file was found.
+    url_policies: HashMap<String, Option<PolicyFile>>,
+
+    /// URL policy files that are being fetched, with the loads waiting for them.
+    pending_url_policies: HashMap<String, Vec<AsyncSender<Option<PolicyFile>>>>,
+
+    /// URL policy files registered with `Security.loadPolicyFile`.
+    extra_url_policies: Vec<Url>,
+
+    /// Requested socket policy files, by the host and port of their policy server.
+    /// `None` if no valid policy file was received.
+    socket_policies: HashMap<(String, u16), Option<PolicyFile>>,
+
+    /// Socket policy servers registered with `Security.loadPolicyFile`.
+    extra_socket_policies: Vec<(String, u16)>,
+}
+
+impl PolicyFiles {
+    pub fn new(enforced: bool) -> Self {
+        Self {
+            enforced,
+            ..Default::default()
+        }
+    }
+
+    /// Whether movies in `sandbox_type` need policy files to access other domains.
+    pub fn is_required(&self, sandbox_type: &SandboxType) -> bool {
+        self.enforced && !matches!(sandbox_type, SandboxType::LocalTrusted)
+    }
+
+    /// Register a policy file location given to `Security.loadPolicyFile`.
+    ///
+    /// `xmlsocket://host:port` URLs name socket policy servers, anything else
+    /// names a URL policy file.
+    pub fn load_policy_file(&mut self, url: Url) {
+        if url.scheme() == "xmlsocket" {
+            match (url.host_str(), url.port()) {
+                (Some(host), Some(port)) => {
+                    let server = (host.to_ascii_lowercase(), port);
+                    if !self.extra_socket_policies.contains(&server) {
+                        self.extra_socket_policies.push(server);
+                    }
+                }
+                _ => tracing::warn!("Invalid socket policy file location: {url}"),
+            }
+        } else if !self.extra_url_policies.contains(&url) {
+            self.extra_url_policies.push(url);
+        }
+    }
+
+    /// Record the policy file received from a socket policy server.
+    pub fn set_socket_policy(&mut self, host: &str, port: u16, policy: Option<PolicyFile>) {
+        self.socket_policies
+            .insert((host.to_ascii_lowercase(), port), policy);
+    }
+
+    /// The policy servers to ask, in order, before connecting a socket to `host:port`.
+    ///
+    /// The master policy server comes first, then the servers registered with
+    /// `Security.loadPolicyFile`, and finally the destination port itself.
+    pub fn socket_policy_ports(&self, host: &str, port: u16) -> Vec<u16> {
+        let host = host.to_ascii_lowercase();
+        let mut ports = vec![SOCKET_MASTER_POLICY_PORT];
+        for (extra_host, extra_port) in &self.extra_socket_policies {
+            if *extra_host == host && !ports.contains(extra_port) {
+                ports.push(*extra_port);
+            }
+        }
+        if !ports.contains(&port) {
+            ports.push(port);
+        }
+        ports
+    }
+
+    /// Decide whether a movie from `domain` may connect a socket to `host:port`,
+    /// given the policy servers that are left to ask.
+    ///
+    /// Policy servers that turned out not to grant access are removed from `policy_ports`.
+    pub fn socket_access(
+        &self,
+        domain: &str,
+        host: &str,
+        port: u16,
+        policy_ports: &mut Vec<u16>,
+    ) -> SocketAccess {
+        let host = host.to_ascii_lowercase();
+        while let Some(&policy_port) = policy_ports.first() {
+            let Some(policy) = self.socket_policies.get(&(host.clone(), policy_port)) else {
+                return SocketAccess::Pending {
+                    host,
+                    port: policy_port,
+                };
+            };
+            policy_ports.remove(0);
+
+            let Some(policy) = policy else {
+                continue;
+            };
+            let is_master = policy_port == SOCKET_MASTER_POLICY_PORT;
+            // Socket master policy files permit all other policy files by default.
+            let meta_policy = policy.meta_policy().unwrap_or(MetaPolicy::All);
+            if is_master && meta_policy == MetaPolicy::None {
+                return SocketAccess::Denied;
+            }
+            if policy.allows_socket(domain, port, policy_port) {
+                return SocketAccess::Allowed;
+            }
+            if is_master && !meta_policy.permits_other_policies() {
+                return SocketAccess::Denied;
+            }
+        }
+        SocketAccess::Denied
+    }
+}
+
+/// Fetch a URL policy file, or take it from the cache.
+///
+/// Concurrent fetches of the same policy file wait for the first one.
+async fn fetch_url_policy(player: &Arc<Mutex<Player>>, url: &Url) -> Option<PolicyFile> {
+    let waiting = player
+        .lock()
+        .unwrap()
+        .mutate_with_update_context(|context| {
+            let policy_files = &mut context.policy_files;
+            if let Some(policy) = policy_files.url_policies.get(url.as_str()) {
+                return Err(policy.clone());
+            }
+            let pending = &mut policy_files.pending_url_policies;
+            if let Some(waiters) = pending.get_mut(url.as_str()) {
+                let (sender, receiver) = bounded(1);
+                waiters.push(sender);
+                Ok(Some(receiver))
+            } else {
+                pending.insert(url.to_string(), vec![]);
+                Ok(None)
+            }
+        });
+    match waiting {
+        Err(policy) => return policy,
+        Ok(Some(receiver)) => return receiver.recv().await.ok().flatten(),
+        Ok(None) => {}
+    }
+
+    let fetch = player
+        .lock()
+        .unwrap()
+        .navigator()
+        .fetch(Request::get(url.to_string()));
+    let policy = match fetch.await {
+        Ok(response) => PolicyFile::parse(&response.body),
+        Err(_) => None,
+    };
+    if policy.is_none() {
+        tracing::warn!("No valid policy file at {url}");
+    }
+
+    let waiters = player
+        .lock()
+        .unwrap()
+        .mutate_with_update_context(|context| {
+            let policy_files = &mut context.policy_files;
+            policy_files
+                .url_policies
+                .insert(url.to_string(), policy.clone());
+            policy_files
+                .pending_url_policies
+                .remove(url.as_str())
+                .unwrap_or_default()
+        });
+    for waiter in waiters {
+        let _ = waiter.try_send(policy.clone());
+    }
+    policy
+}
+
+/// Find out whether reading data loaded from `url` needs a policy file.
+///
+/// Returns the URLs of the requesting movie and of the target if it does.
+pub fn url_policy_check(context: &mut UpdateContext<'_, '_>, url: &str) -> Option<(Url, Url)> {
+    if !context
+        .policy_files
+        .is_required(&context.system.sandbox_type)
+    {
+        return None;
+    }
+
+    // If either URL is invalid, let the load itself fail.
+    let requester = Url::parse(context.swf.url()).ok()?;
+    let target = context.navigator.resolve_url(url).ok()?;
+    if !matches!(target.scheme(), "http" | "https") || requester.origin() == target.origin() {
+        return None;
+    }
+
+    Some((requester, target))
+}
+
+/// Check whether the movies of a player may read data loaded from `url`,
+/// fetching the policy files that could grant access as needed.
+pub async fn check_url_access(player: &Arc<Mutex<Player>>, url: &str) -> bool {
+    let (check, extra_policies) = player
+        .lock()
+        .unwrap()
+        .mutate_with_update_context(|context| {
+            (
+                url_policy_check(context, url),
+                context.policy_files.extra_url_policies.clone(),
+            )
+        });
+    let Some((requester, target)) = check else {
+        return true;
+    };
+
+    let Ok(master_url) = target.join("/crossdomain.xml") else {
+        return false;
+    };
+    let master = fetch_url_policy(player, &master_url).await;
+    // Without a master policy file, other policy files aren't permitted.
+    let meta_policy = master
+        .as_ref()
+        .and_then(|policy| policy.meta_policy())
+        .unwrap_or(MetaPolicy::MasterOnly);
+    if meta_policy == MetaPolicy::None {
+        return false;
+    }
+    if master
+        .as_ref()
+        .map(|policy| policy.allows_url(&requester, &master_url))
+        .unwrap_or(false)
+    {
+        return true;
+    }
+    if !meta_policy.permits_other_policies() {
+        return false;
+    }
+
+    for policy_url in extra_policies {
+        // A policy file only covers its own directory and its subdirectories.
+        let directory = policy_url.path().rsplit_once('/').map(|(dir, _)| dir);
+        let is_covered = policy_url.origin() == target.origin()
+            && directory
+                .map(|dir| target.path().starts_with(&format!("{dir}/")))
+                .unwrap_or(false);
+        if !is_covered || policy_url == master_url {
+            continue;
+        }
+
+        if let Some(policy) = fetch_url_policy(player, &policy_url).await {
+            if policy.allows_url(&requester, &policy_url) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &[u8] = br#"<?xml version="1.0"?>
+<!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+<cross-domain-policy>
+    <site-control permitted-cross-domain-policies="master-only"/>
+    <allow-access-from domain="*.example.com" to-ports="843,2000-2010" />
+    <allow-access-from domain="games.org" to-ports="*" secure="false" />
+</cross-domain-policy>"#;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn parse_policy() {
+        let policy = PolicyFile::parse(POLICY).unwrap();
+        assert_eq!(policy.meta_policy(), Some(MetaPolicy::MasterOnly));
+        assert_eq!(policy.allow_access_from.len(), 2);
+        assert_eq!(
+            policy.allow_access_from[0].to_ports,
+            Some(vec![843..=843, 2000..=2010])
+        );
+
+        assert_eq!(PolicyFile::parse(b"<html></html>"), None);
+        assert_eq!(PolicyFile::parse(b"not xml"), None);
+    }
+
+    #[test]
+    fn url_access() {
+        let policy = PolicyFile::parse(POLICY).unwrap();
+        let http_policy = url("http://data.net/crossdomain.xml");
+        let https_policy = url("https://data.net/crossdomain.xml");
+
+        assert!(policy.allows_url(&url("http://example.com/a.swf"), &http_policy));
+        assert!(policy.allows_url(&url("http://www.example.com/a.swf"), &http_policy));
+        assert!(!policy.allows_url(&url("http://badexample.com/a.swf"), &http_policy));
+
+        // HTTPS policies only grant access to HTTP movies with `secure="false"`.
+        assert!(!policy.allows_url(&url("http://example.com/a.swf"), &https_policy));
+        assert!(policy.allows_url(&url("https://example.com/a.swf"), &https_policy));
+        assert!(policy.allows_url(&url("http://games.org/a.swf"), &https_policy));
+    }
+
+    #[test]
+    fn socket_access() {
+        let policy = PolicyFile::parse(POLICY).unwrap();
+        assert!(policy.allows_socket("example.com", 2005, 843));
+        assert!(!policy.allows_socket("example.com", 2011, 843));
+        assert!(policy.allows_socket("games.org", 80, 843));
+        assert!(!policy.allows_socket("games.org", 80, 2000));
+
+        let mut policy_files = PolicyFiles::default();
+        let mut ports = policy_files.socket_policy_ports("Data.net", 2005);
+        assert_eq!(ports, vec![843, 2005]);
+        assert!(matches!(
+            policy_files.socket_access("example.com", "data.net", 2005, &mut ports),
+            SocketAccess::Pending { port: 843, .. }
+        ));
+
+        // Without a master policy file, the destination port is asked next.
+        policy_files.set_socket_policy("data.net", 843, None);
+        assert!(matches!(
+            policy_files.socket_access("example.com", "data.net", 2005, &mut ports),
+            SocketAccess::Pending { port: 2005, .. }
+        ));
+        policy_files.set_socket_policy("data.net", 2005, Some(policy));
+        assert!(matches!(
+            policy_files.socket_access("example.com", "data.net", 2005, &mut ports),
+            SocketAccess::Allowed
+        ));
+
+        // A master-only master policy file is the only one that counts.
+        let master = PolicyFile::parse(
+            br#"<cross-domain-policy><site-control permitted-cross-domain-policies="master-only"/></cross-domain-policy>"#,
+        );
+        policy_files.set_socket_policy("data.net", 843, master);
+        let mut ports = policy_files.socket_policy_ports("data.net", 2005);
+        assert!(matches!(
+            policy_files.socket_access("example.com", "data.net", 2005, &mut ports),
+            SocketAccess::Denied
+        ));
+    }
+
+    #[test]
+    fn ports() {
+        assert_eq!(parse_ports("*"), vec![0..=u16::MAX]);
+        assert_eq!(parse_ports("80, 100-200"), vec![80..=80, 100..=200]);
+        assert_eq!(parse_ports("invalid"), vec![]);
+    }
+}
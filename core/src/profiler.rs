@@ -0,0 +1,384 @@
+//! Profiling of ActionScript execution.
+//!
+//! While profiling, the player records when every AVM1 and AVM2 function is
+//! entered and exited, along with the time spent in native builtins, in each
+//! AVM2 frame phase and in garbage collection. The result can be exported as a
+//! Chrome trace (for `chrome://tracing` or Perfetto) or as a speedscope profile.
+
+use crate::frame_lifecycle::FramePhase;
+use fnv::FnvHashMap;
+use serde_json::{json, Value as JsonValue};
+use std::time::Duration;
+use web_time::Instant;
+
+/// The most frames a profile records, to bound the memory used by long profiles.
+///
+/// Each recorded frame takes two events of 32 bytes, so this is about 128MB.
+const MAX_RECORDED_FRAMES: usize = 2_000_000;
+
+/// What kind of work a profiled frame represents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProfileCategory {
+    /// An AVM1 function defined in bytecode.
+    Avm1,
+
+    /// An AVM2 method defined in bytecode.
+    Avm2,
+
+    /// A builtin function implemented in Rust.
+    Native,
+
+    /// One of the phases of an AVM2 frame.
+    FramePhase,
+
+    /// A garbage collection pause.
+    Gc,
+}
+
+impl ProfileCategory {
+    fn as_str(self) -> &'static str {
+        match self {
+            ProfileCategory::Avm1 => "avm1",
+            ProfileCategory::Avm2 => "avm2",
+            ProfileCategory::Native => "native",
+            ProfileCategory::FramePhase => "frame",
+            ProfileCategory::Gc => "gc",
+        }
+    }
+}
+
+/// The file formats a profile can be exported as.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfileFormat {
+    /// The Chrome trace event format, as read by `chrome://tracing` and Perfetto.
+    ChromeTrace,
+
+    /// The speedscope "evented" profile format.
+    Speedscope,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EventKind {
+    Open,
+    Close,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Event {
+    kind: EventKind,
+
+    /// The index of the frame in `Profiler::frames`.
+    frame: usize,
+
+    /// The time of the event, relative to the start of profiling.
+    at: Duration,
+}
+
+#[derive(Clone, Debug)]
+struct Frame {
+    name: String,
+    category: ProfileCategory,
+}
+
+/// Records a profile of ActionScript execution.
+///
+/// Frames are identified by their category and name. Names are built every
+/// time a frame is entered, as functions don't have a stable identity that
+/// outlives them: their addresses are reused after they're collected, and
+/// many natives share the same Rust function.
+pub struct Profiler {
+    start_time: Instant,
+
+    /// Every distinct frame that was entered.
+    frames: Vec<Frame>,
+
+    /// Indices into `frames` by category and name.
+    frames_by_name: FnvHashMap<(ProfileCategory, String), usize>,
+
+    events: Vec<Event>,
+
+    /// How many more frames may be recorded before the profile is truncated.
+    remaining_frames: usize,
+
+    /// The frames that are currently open, innermost last, and whether
+    /// they were recorded.
+    stack: Vec<(usize, bool)>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            start_time: Instant::now(),
+            frames: Vec::new(),
+            frames_by_name: Default::default(),
+            events: Vec::new(),
+            remaining_frames: MAX_RECORDED_FRAMES,
+            stack: Vec::new(),
+        }
+    }
+
+    fn now(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+
+    fn intern(&mut self, category: ProfileCategory, name: String) -> usize {
+        if let Some(&index) = self.frames_by_name.get(&(category, name.clone())) {
+            return index;
+        }
+        let index = self.frames.len();
+        self.frames.push(Frame {
+            name: name.clone(),
+            category,
+        });
+        self.frames_by_name.insert((category, name), index);
+        index
+    }
+
+    fn open(&mut self, frame: usize) {
+        // Only opening frames is limited, so that every recorded frame is closed.
+        let recorded = self.remaining_frames > 0;
+        self.stack.push((frame, recorded));
+        if !recorded {
+            return;
+        }
+        self.remaining_frames -= 1;
+        if self.remaining_frames == 0 {
+            tracing::warn!("Profile is too long, ignoring further frames");
+        }
+        let at = self.now();
+        self.events.push(Event {
+            kind: EventKind::Open,
+            frame,
+            at,
+        });
+    }
+
+    fn close(&mut self) {
+        if let Some((frame, true)) = self.stack.pop() {
+            let at = self.now();
+            self.events.push(Event {
+                kind: EventKind::Close,
+                frame,
+                at,
+            });
+        }
+    }
+
+    /// Enter a frame.
+    pub fn enter(&mut self, category: ProfileCategory, name: String) {
+        let frame = self.intern(category, name);
+        self.open(frame);
+    }
+
+    /// Exit the innermost frame.
+    pub fn exit(&mut self) {
+        self.close();
+    }
+
+    /// Record a change of the AVM2 frame phase.
+    ///
+    /// Phases are only recorded when they aren't nested inside of a script,
+    /// such as the frame run by a `gotoAndStop` call from a frame script.
+    pub fn set_frame_phase(&mut self, phase: FramePhase) {
+        if self
+            .stack
+            .last()
+            .is_some_and(|&(frame, _)| self.frames[frame].category == ProfileCategory::FramePhase)
+        {
+            self.close();
+        }
+        if phase != FramePhase::Idle && self.stack.is_empty() {
+            self.enter(ProfileCategory::FramePhase, format!("[{phase:?}]"));
+        }
+    }
+
+    /// The recorded events, with every frame that's still open closed at the current time.
+    fn finished_events(&self) -> (Vec<Event>, Duration) {
+        let end = self.now();
+        let mut events = self.events.clone();
+        events.extend(
+            self.stack
+                .iter()
+                .rev()
+                .filter(|(_, recorded)| *recorded)
+                .map(|&(frame, _)| Event {
+                    kind: EventKind::Close,
+                    frame,
+                    at: end,
+                }),
+        );
+        (events, end)
+    }
+
+    /// Export the profile as a Chrome trace event JSON document.
+    pub fn to_chrome_trace(&self) -> String {
+        let (events, _) = self.finished_events();
+        let trace_events: Vec<JsonValue> = events
+            .iter()
+            .map(|event| {
+                let frame = &self.frames[event.frame];
+                json!({
+                    "name": frame.name,
+                    "cat": frame.category.as_str(),
+                    "ph": match event.kind {
+                        EventKind::Open => "B",
+                        EventKind::Close => "E",
+                    },
+                    "ts": event.at.as_secs_f64() * 1_000_000.0,
+                    "pid": 1,
+                    "tid": 1,
+                })
+            })
+            .collect();
+
+        json!({
+            "traceEvents": trace_events,
+            "displayTimeUnit": "ms",
+        })
+        .to_string()
+    }
+
+    /// Export the profile as a speedscope JSON document.
+    pub fn to_speedscope(&self) -> String {
+        let (events, end) = self.finished_events();
+        let frames: Vec<JsonValue> = self
+            .frames
+            .iter()
+            .map(|frame| json!({ "name": frame.name }))
+            .collect();
+        let events: Vec<JsonValue> = events
+            .iter()
+            .map(|event| {
+                json!({
+                    "type": match event.kind {
+                        EventKind::Open => "O",
+                        EventKind::Close => "C",
+                    },
+                    "frame": event.frame,
+                    "at": event.at.as_secs_f64() * 1_000_000.0,
+                })
+            })
+            .collect();
+
+        json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "shared": { "frames": frames },
+            "profiles": [{
+                "type": "evented",
+                "name": "ActionScript",
+                "unit": "microseconds",
+                "startValue": 0.0,
+                "endValue": end.as_secs_f64() * 1_000_000.0,
+                "events": events,
+            }],
+            "exporter": "Ruffle",
+        })
+        .to_string()
+    }
+
+    /// Export the profile in the given format.
+    pub fn export(&self, format: ProfileFormat) -> String {
+        match format {
+            ProfileFormat::ChromeTrace => self.to_chrome_trace(),
+            ProfileFormat::Speedscope => self.to_speedscope(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event_kinds(trace: &JsonValue, key: &str, field: &str) -> Vec<String> {
+        trace[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event[field].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn frames_are_shared_by_name() {
+        let mut profiler = Profiler::new();
+        profiler.enter(ProfileCategory::Avm2, "Main/update".to_string());
+        profiler.enter(ProfileCategory::Native, "Math/floor".to_string());
+        profiler.exit();
+        profiler.enter(ProfileCategory::Native, "Math/floor".to_string());
+        profiler.exit();
+        profiler.enter(ProfileCategory::Native, "Math/ceil".to_string());
+        profiler.exit();
+        profiler.exit();
+
+        let profile: JsonValue = serde_json::from_str(&profiler.to_speedscope()).unwrap();
+        let frames = profile["shared"]["frames"].as_array().unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(
+            event_kinds(&profile["profiles"][0], "events", "type"),
+            ["O", "O", "C", "O", "C", "O", "C", "C"]
+        );
+    }
+
+    #[test]
+    fn frames_past_the_limit_are_not_recorded() {
+        let mut profiler = Profiler::new();
+        profiler.remaining_frames = 2;
+        profiler.enter(ProfileCategory::Avm2, "outer".to_string());
+        profiler.enter(ProfileCategory::Avm2, "inner".to_string());
+        profiler.enter(ProfileCategory::Native, "ignored".to_string());
+        profiler.exit();
+        profiler.exit();
+
+        let trace: JsonValue = serde_json::from_str(&profiler.to_chrome_trace()).unwrap();
+        assert_eq!(
+            event_kinds(&trace, "traceEvents", "name"),
+            ["outer", "inner", "inner", "outer"]
+        );
+    }
+
+    #[test]
+    fn open_frames_are_closed_on_export() {
+        let mut profiler = Profiler::new();
+        profiler.set_frame_phase(FramePhase::Enter);
+        profiler.enter(ProfileCategory::Avm1, "onEnterFrame".to_string());
+
+        let trace: JsonValue = serde_json::from_str(&profiler.to_chrome_trace()).unwrap();
+        assert_eq!(
+            event_kinds(&trace, "traceEvents", "ph"),
+            ["B", "B", "E", "E"]
+        );
+        assert_eq!(trace["traceEvents"][0]["name"], "[Enter]");
+    }
+
+    #[test]
+    fn phases_inside_scripts_are_not_recorded() {
+        let mut profiler = Profiler::new();
+        profiler.set_frame_phase(FramePhase::FrameScripts);
+        profiler.enter(ProfileCategory::Avm2, "frame1".to_string());
+        profiler.set_frame_phase(FramePhase::Construct);
+        profiler.set_frame_phase(FramePhase::FrameScripts);
+        profiler.exit();
+        profiler.set_frame_phase(FramePhase::Exit);
+        profiler.set_frame_phase(FramePhase::Idle);
+
+        let trace: JsonValue = serde_json::from_str(&profiler.to_chrome_trace()).unwrap();
+        assert_eq!(
+            event_kinds(&trace, "traceEvents", "name"),
+            [
+                "[FrameScripts]",
+                "frame1",
+                "frame1",
+                "[FrameScripts]",
+                "[Exit]",
+                "[Exit]"
+            ]
+        );
+    }
+}
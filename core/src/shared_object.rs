@@ -0,0 +1,194 @@
+//! Writing local shared objects to storage, within the quota of their domain.
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    ExecutionReason as Avm1ExecutionReason, ScriptObject as Avm1ScriptObject,
+    TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject};
+use crate::backend::storage::{quota_for, storage_domain};
+use crate::context::UpdateContext;
+use crate::vminterface::AvmObject;
+use gc_arena::{DynamicRoot, Rootable};
+use url::Url;
+
+/// The outcome of writing a shared object to storage.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlushStatus {
+    /// The shared object was written.
+    Flushed,
+
+    /// The user is being asked for more space, and the shared object will be sent
+    /// a `netStatus` event (or `onStatus` in AVM1) once they have answered.
+    Pending,
+
+    /// The shared object could not be written.
+    Failed,
+}
+
+/// Returns the domain of a shared object name, such as `foo.com/folder/game.swf/SOName`.
+pub fn domain_of(name: &str) -> &str {
+    name.split('/').next().unwrap_or_default()
+}
+
+/// Returns the domain of `url`, as passed to `SharedObject.deleteAll` and `getDiskUsage`.
+///
+/// Both a full URL and a bare host name are accepted. Movies may only access the shared objects
+/// of their own domain, so this returns `None` for any other domain.
+pub fn domain_of_url(movie_url: &str, url: &str) -> Option<String> {
+    let domain = match Url::parse(url) {
+        Ok(url) => storage_domain(&url).to_string(),
+        Err(_) => url.trim_matches('/').to_string(),
+    };
+    let movie_domain = Url::parse(movie_url).ok()?;
+    if domain.is_empty() || domain != storage_domain(&movie_domain) {
+        None
+    } else {
+        Some(domain)
+    }
+}
+
+/// Writes the serialized shared object `name` to storage.
+///
+/// `min_disk_space` is the amount of bytes the movie wants reserved for this shared object.
+/// If its domain doesn't have enough quota left, the user is asked for more space if `target` is given,
+/// and the write is completed once they accept. If nobody can be asked, the quota is exceeded instead,
+/// unless the quota is 0, which means that the user doesn't want the domain to store anything.
+///
+/// While the user is being asked, flushing the same shared object again doesn't ask a second time.
+/// The latest data is written once they answer.
+pub fn flush<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    name: &str,
+    data: Vec<u8>,
+    min_disk_space: usize,
+    target: Option<AvmObject<'gc>>,
+) -> FlushStatus {
+    if let Some(pending) = context.pending_flushes.get_mut(name) {
+        if target.is_some() {
+            *pending = data;
+            return FlushStatus::Pending;
+        }
+        // Nobody will be around for the answer, so the data is written right away below.
+        // It must not be replaced by older data once the user does answer.
+        pending.clone_from(&data);
+    }
+
+    let domain = domain_of(name).to_string();
+    let other_usage = context
+        .storage
+        .usage(&format!("{domain}/"))
+        .saturating_sub(context.storage.get_size(name).unwrap_or_default());
+    let required = other_usage.saturating_add(data.len().max(min_disk_space));
+
+    let quota = context.storage.quota(&domain);
+    let over_quota = required > quota;
+    if over_quota && quota == 0 {
+        return FlushStatus::Failed;
+    }
+
+    let prompt = match target {
+        Some(target) if over_quota => context
+            .ui
+            .display_storage_quota_prompt(&domain, required)
+            .map(|prompt| (target, prompt)),
+        _ => None,
+    };
+
+    let Some((target, prompt)) = prompt else {
+        if over_quota {
+            // Nobody can be asked for more space, either because the movie is closing or because
+            // this frontend has no UI. Losing the data would be worse than exceeding the quota.
+            tracing::warn!(
+                "SharedObject {name} exceeds the storage quota of {domain}, writing it anyway"
+            );
+        }
+        return if context.storage.put(name, &data) {
+            FlushStatus::Flushed
+        } else {
+            FlushStatus::Failed
+        };
+    };
+
+    context.pending_flushes.insert(name.to_string(), data);
+    let target: DynamicRoot<Rootable![AvmObject<'_>]> =
+        context.dynamic_root.stash(context.gc_context, target);
+    let name = name.to_string();
+    let player = context.player.clone();
+    context.navigator.spawn_future(Box::pin(async move {
+        let granted = match prompt.await {
+            Ok(granted) => granted,
+            Err(e) => {
+                tracing::warn!("Error on storage quota prompt: {e}");
+                false
+            }
+        };
+
+        let Some(player) = player.upgrade() else {
+            return Ok(());
+        };
+        player.lock().unwrap().update(|uc| {
+            let target = *uc.dynamic_root.fetch(&target);
+            let Some(data) = uc.pending_flushes.remove(&name) else {
+                return;
+            };
+            if granted {
+                uc.storage.set_quota(&domain, quota_for(required));
+            }
+            let flushed = granted && uc.storage.put(&name, &data);
+            let info = if flushed {
+                [("code", "SharedObject.Flush.Success"), ("level", "status")]
+            } else {
+                [("code", "SharedObject.Flush.Failed"), ("level", "error")]
+            };
+            net_status(uc, target, info);
+        });
+        Ok(())
+    }));
+
+    FlushStatus::Pending
+}
+
+/// Dispatch a `netStatus` event (or call `onStatus` in AVM1) on a shared object.
+fn net_status<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: AvmObject<'gc>,
+    info: [(&'static str, &'static str); 2],
+) {
+    match target {
+        AvmObject::Avm1(object) => {
+            let Some(mut activation) = Avm1Activation::try_from_stub(
+                context.reborrow(),
+                Avm1ActivationIdentifier::root("[SharedObject Status Event]"),
+            ) else {
+                return;
+            };
+            let object_proto = activation.context.avm1.prototypes().object;
+            let info_object =
+                Avm1ScriptObject::new(activation.context.gc_context, Some(object_proto));
+
+            for (key, value) in info {
+                info_object
+                    .set(key, Avm1Value::String(value.into()), &mut activation)
+                    .expect("valid set");
+            }
+
+            if let Err(e) = object.call_method(
+                "onStatus".into(),
+                &[info_object.into()],
+                &mut activation,
+                Avm1ExecutionReason::Special,
+            ) {
+                tracing::error!(
+                    "Got error when dispatching AVM1 onStatus event from SharedObject: {e}"
+                );
+            }
+        }
+        AvmObject::Avm2(object) => {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let event =
+                Avm2EventObject::net_status_event(&mut activation, "netStatus", info.to_vec());
+            Avm2::dispatch_event(&mut activation.context, event, object);
+        }
+    }
+}
@@ -9,9 +9,10 @@ use crate::{
     },
     backend::navigator::NavigatorBackend,
     context::UpdateContext,
+    policy_file::{PolicyFile, SocketAccess, SOCKET_POLICY_REQUEST},
     string::AvmString,
 };
-use async_channel::{unbounded, Sender as AsyncSender};
+use async_channel::{unbounded, Receiver as AsyncReceiver, Sender as AsyncSender};
 use gc_arena::Collect;
 use generational_arena::{Arena, Index};
 use std::{
@@ -19,9 +20,15 @@ use std::{
     sync::mpsc::{channel, Receiver, Sender},
     time::Duration,
 };
+use web_time::Instant;
 
 pub type SocketHandle = Index;
 
+/// How long to wait for a policy server to answer.
+///
+/// This is enforced here, as not every backend supports socket timeouts.
+const POLICY_REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
 #[derive(Copy, Clone, Collect)]
 #[collect(no_drop)]
 enum SocketKind<'gc> {
@@ -34,17 +41,49 @@ enum SocketKind<'gc> {
 struct Socket<'gc> {
     target: SocketKind<'gc>,
     sender: RefCell<AsyncSender<Vec<u8>>>,
+
+    /// The connection to open once a policy file grants access to it.
+    #[collect(require_static)]
+    pending: Option<PendingConnection>,
 }
 
 impl<'gc> Socket<'gc> {
-    fn new(target: SocketKind<'gc>, sender: AsyncSender<Vec<u8>>) -> Self {
+    fn new(
+        target: SocketKind<'gc>,
+        sender: AsyncSender<Vec<u8>>,
+        pending: PendingConnection,
+    ) -> Self {
         Self {
             target,
             sender: RefCell::new(sender),
+            pending: Some(pending),
         }
     }
 }
 
+struct PendingConnection {
+    host: String,
+    port: u16,
+    timeout: Duration,
+    receiver: AsyncReceiver<Vec<u8>>,
+
+    /// Whether a policy file must grant access to the socket, as the movie
+    /// isn't trusted and the socket wasn't approved some other way.
+    policy_required: bool,
+
+    /// The policy servers that are left to ask.
+    policy_ports: Vec<u16>,
+}
+
+/// A request for the policy file of a socket policy server.
+struct PolicyRequest {
+    host: String,
+    port: u16,
+    sender: AsyncSender<Vec<u8>>,
+    response: Vec<u8>,
+    started: Instant,
+}
+
 #[derive(Debug)]
 pub enum ConnectionState {
     Connected,
@@ -65,6 +104,12 @@ pub struct Sockets<'gc> {
 
     receiver: Receiver<SocketAction>,
     sender: Sender<SocketAction>,
+
+    /// Requests to policy servers, which have their own channel so that
+    /// their handles don't clash with those of sockets.
+    policy_requests: Arena<PolicyRequest>,
+    policy_receiver: Receiver<SocketAction>,
+    policy_sender: Sender<SocketAction>,
 }
 
 unsafe impl<'gc> Collect for Sockets<'gc> {
@@ -78,74 +123,303 @@ unsafe impl<'gc> Collect for Sockets<'gc> {
 impl<'gc> Sockets<'gc> {
     pub fn empty() -> Self {
         let (sender, receiver) = channel();
+        let (policy_sender, policy_receiver) = channel();
 
         Self {
             sockets: Arena::new(),
             receiver,
             sender,
+            policy_requests: Arena::new(),
+            policy_receiver,
+            policy_sender,
         }
     }
 
     pub fn connect_avm2(
-        &mut self,
-        backend: &mut dyn NavigatorBackend,
+        context: &mut UpdateContext<'_, 'gc>,
         target: SocketObject<'gc>,
         host: String,
         port: u16,
     ) {
-        let (sender, receiver) = unbounded();
-
-        let socket = Socket::new(SocketKind::Avm2(target), sender);
-        let handle = self.sockets.insert(socket);
-
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
-            host,
-            port,
-            Duration::from_millis(target.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
-        );
+        let timeout = Duration::from_millis(target.timeout().into());
+        let handle = Self::connect(context, SocketKind::Avm2(target), host, port, timeout);
 
         if let Some(existing_handle) = target.set_handle(handle) {
             // As written in the AS3 docs, we are supposed to close the existing connection,
             // when a new one is created.
-            self.close(existing_handle)
+            context.sockets.close(existing_handle)
         }
+
+        Self::open_when_allowed(context, handle);
     }
 
     pub fn connect_avm1(
-        &mut self,
-        backend: &mut dyn NavigatorBackend,
+        context: &mut UpdateContext<'_, 'gc>,
         target: Avm1Object<'gc>,
         host: String,
         port: u16,
     ) {
-        let (sender, receiver) = unbounded();
-
         let xml_socket = match XmlSocket::cast(target.into()) {
             Some(xml_socket) => xml_socket,
             None => return,
         };
 
-        let socket = Socket::new(SocketKind::Avm1(target), sender);
-        let handle = self.sockets.insert(socket);
+        let timeout = Duration::from_millis(xml_socket.timeout().into());
+        let handle = Self::connect(context, SocketKind::Avm1(target), host, port, timeout);
+
+        if let Some(existing_handle) = xml_socket.set_handle(handle) {
+            // NOTE: AS2 docs don't specify what happens when connect is called with open connection,
+            //       but we will close the existing connection anyway.
+            context.sockets.close(existing_handle)
+        }
+
+        Self::open_when_allowed(context, handle);
+    }
+
+    /// Add a socket that waits for the policy files to be checked before it's opened.
+    fn connect(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: SocketKind<'gc>,
+        host: String,
+        port: u16,
+        timeout: Duration,
+    ) -> SocketHandle {
+        let (sender, receiver) = unbounded();
+
+        // Trusted movies don't need any policy file.
+        let policy_required = context
+            .policy_files
+            .is_required(&context.system.sandbox_type)
+            && !context.navigator.is_socket_approved(&host, port);
+        let policy_ports = if policy_required {
+            context.policy_files.socket_policy_ports(&host, port)
+        } else {
+            vec![]
+        };
+
+        let pending = PendingConnection {
+            host,
+            port,
+            timeout,
+            receiver,
+            policy_required,
+            policy_ports,
+        };
+        context
+            .sockets
+            .sockets
+            .insert(Socket::new(target, sender, pending))
+    }
+
+    /// Open a pending socket once the policy files grant access to it, or
+    /// refuse the connection if they don't.
+    fn open_when_allowed(context: &mut UpdateContext<'_, 'gc>, handle: SocketHandle) {
+        let domain = url::Url::parse(context.swf.url())
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let Some(socket) = context.sockets.sockets.get_mut(handle) else {
+            return;
+        };
+        let Some(pending) = socket.pending.as_mut() else {
+            return;
+        };
+
+        let access = if !pending.policy_required {
+            SocketAccess::Allowed
+        } else {
+            context.policy_files.socket_access(
+                &domain,
+                &pending.host,
+                pending.port,
+                &mut pending.policy_ports,
+            )
+        };
+
+        match access {
+            SocketAccess::Allowed => {
+                let pending = socket.pending.take().expect("Socket must be pending");
 
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
+                // NOTE: This call will send SocketAction::Connect to sender with connection status.
+                context.navigator.connect_socket(
+                    pending.host,
+                    pending.port,
+                    pending.timeout,
+                    handle,
+                    pending.receiver,
+                    context.sockets.sender.clone(),
+                );
+            }
+            SocketAccess::Denied => Self::refuse(context, handle),
+            SocketAccess::Pending { host, port } => {
+                context
+                    .sockets
+                    .request_policy(context.navigator, host, port);
+            }
+        }
+    }
+
+    /// Ask a policy server for its policy file, unless we're already waiting for it.
+    fn request_policy(&mut self, backend: &mut dyn NavigatorBackend, host: String, port: u16) {
+        if self
+            .policy_requests
+            .iter()
+            .any(|(_, request)| request.host == host && request.port == port)
+        {
+            return;
+        }
+
+        let (sender, receiver) = unbounded();
+        let handle = self.policy_requests.insert(PolicyRequest {
+            host: host.clone(),
+            port,
+            sender,
+            response: vec![],
+            started: Instant::now(),
+        });
+
+        // NOTE: This call will send SocketAction::Connect to policy_sender with connection status.
         backend.connect_socket(
             host,
             port,
-            Duration::from_millis(xml_socket.timeout().into()),
+            POLICY_REQUEST_TIMEOUT,
             handle,
             receiver,
-            self.sender.clone(),
+            self.policy_sender.clone(),
         );
+    }
 
-        if let Some(existing_handle) = xml_socket.set_handle(handle) {
-            // NOTE: AS2 docs don't specify what happens when connect is called with open connection,
-            //       but we will close the existing connection anyway.
-            self.close(existing_handle)
+    /// Record the policy file received from a policy server, and retry the
+    /// sockets that were waiting for it.
+    fn finish_policy_request(context: &mut UpdateContext<'_, 'gc>, handle: SocketHandle) {
+        // NOTE: Dropping the request's sender closes the connection.
+        let Some(request) = context.sockets.policy_requests.remove(handle) else {
+            return;
+        };
+
+        let response = request.response.split(|&b| b == 0).next().unwrap_or(&[]);
+        let policy = PolicyFile::parse(response);
+        if policy.is_none() {
+            tracing::warn!(
+                "No valid socket policy file from {}:{}",
+                request.host,
+                request.port
+            );
+        }
+        context
+            .policy_files
+            .set_socket_policy(&request.host, request.port, policy);
+
+        let waiting: Vec<_> = context
+            .sockets
+            .sockets
+            .iter()
+            .filter(|(_, socket)| socket.pending.is_some())
+            .map(|(handle, _)| handle)
+            .collect();
+        for handle in waiting {
+            Self::open_when_allowed(context, handle);
+        }
+    }
+
+    fn update_policy_requests(context: &mut UpdateContext<'_, 'gc>) {
+        let actions: Vec<_> = context.sockets.policy_receiver.try_iter().collect();
+
+        for action in actions {
+            match action {
+                SocketAction::Connect(handle, ConnectionState::Connected) => {
+                    if let Some(request) = context.sockets.policy_requests.get(handle) {
+                        if let Err(e) = request.sender.try_send(SOCKET_POLICY_REQUEST.to_vec()) {
+                            tracing::error!("Failed to request socket policy file: {:?}", e);
+                        }
+                    }
+                }
+                SocketAction::Connect(
+                    handle,
+                    ConnectionState::Failed | ConnectionState::TimedOut,
+                )
+                | SocketAction::Close(handle) => Self::finish_policy_request(context, handle),
+                SocketAction::Data(handle, data) => {
+                    let Some(request) = context.sockets.policy_requests.get_mut(handle) else {
+                        continue;
+                    };
+
+                    // The policy file ends with a null byte.
+                    request.response.extend(data);
+                    if request.response.contains(&0) {
+                        Self::finish_policy_request(context, handle);
+                    }
+                }
+            }
+        }
+
+        let timed_out: Vec<_> = context
+            .sockets
+            .policy_requests
+            .iter()
+            .filter(|(_, request)| request.started.elapsed() >= POLICY_REQUEST_TIMEOUT)
+            .map(|(handle, _)| handle)
+            .collect();
+        for handle in timed_out {
+            Self::finish_policy_request(context, handle);
+        }
+    }
+
+    /// Refuse a connection that no policy file grants access to.
+    fn refuse(context: &mut UpdateContext<'_, 'gc>, handle: SocketHandle) {
+        let Some(socket) = context.sockets.sockets.remove(handle) else {
+            return;
+        };
+        let (host, port) = socket
+            .pending
+            .map(|pending| (pending.host, pending.port))
+            .unwrap_or_default();
+
+        match socket.target {
+            SocketKind::Avm2(target) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let message = AvmString::new_utf8(
+                    activation.context.gc_context,
+                    format!(
+                        "Error #2048: Security sandbox violation: {} cannot load data from {}:{}.",
+                        activation.context.swf.url(),
+                        host,
+                        port
+                    ),
+                );
+
+                let security_error_evt = activation
+                    .avm2()
+                    .classes()
+                    .securityerrorevent
+                    .construct(
+                        &mut activation,
+                        &[
+                            "securityError".into(),
+                            false.into(),
+                            false.into(),
+                            message.into(),
+                            2048.into(),
+                        ],
+                    )
+                    .expect("SecurityErrorEvent should be constructed");
+
+                Avm2::dispatch_event(&mut activation.context, security_error_evt, target.into());
+            }
+            SocketKind::Avm1(target) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    ActivationIdentifier::root("[XMLSocket]"),
+                );
+
+                let _ = target.call_method(
+                    "onConnect".into(),
+                    &[false.into()],
+                    &mut activation,
+                    ExecutionReason::Special,
+                );
+            }
         }
     }
 
@@ -164,7 +438,7 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn close(&mut self, handle: SocketHandle) {
-        if let Some(Socket { sender, target }) = self.sockets.remove(handle) {
+        if let Some(Socket { sender, target, .. }) = self.sockets.remove(handle) {
             drop(sender); // NOTE: By dropping the sender, the reading task will close automatically.
 
             // Clear the buffers if the connection was closed.
@@ -184,6 +458,8 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn update_sockets(context: &mut UpdateContext<'_, 'gc>) {
+        Self::update_policy_requests(context);
+
         let mut actions = vec![];
 
         while let Ok(action) = context.sockets.receiver.try_recv() {
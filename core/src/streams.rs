@@ -18,8 +18,10 @@ use crate::buffer::{Buffer, Slice, Substream, SubstreamError};
 use crate::context::UpdateContext;
 use crate::display_object::MovieClip;
 use crate::loader::Error;
+use crate::net_connection::NetConnectionHandle;
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
+use flash_lso::types::Value as AmfValue;
 use flv_rs::{
     AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
     FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
@@ -241,6 +243,13 @@ pub struct NetStreamData<'gc> {
 
     /// True if the stream should play when ticked.
     playing: bool,
+
+    /// The RTMP connection this stream was created on, and the index of its
+    /// stream on that connection.
+    ///
+    /// Media for these streams comes from the server rather than a download.
+    #[collect(require_static)]
+    rtmp_stream: Option<(NetConnectionHandle, usize)>,
 }
 
 impl<'gc> NetStream<'gc> {
@@ -264,6 +273,7 @@ impl<'gc> NetStream<'gc> {
                 sound_instance: None,
                 attached_to: None,
                 playing: false,
+                rtmp_stream: None,
             },
         ))
     }
@@ -280,6 +290,18 @@ impl<'gc> NetStream<'gc> {
         self.0.write(gc_context).avm_object = Some(avm_object);
     }
 
+    pub fn avm_object(self) -> Option<AvmObject<'gc>> {
+        self.0.read().avm_object
+    }
+
+    pub fn set_rtmp_stream(
+        self,
+        gc_context: &Mutation<'gc>,
+        rtmp_stream: Option<(NetConnectionHandle, usize)>,
+    ) {
+        self.0.write(gc_context).rtmp_stream = rtmp_stream;
+    }
+
     /// Reset the `NetStream` buffer to accept new source data.
     ///
     /// This must be done once per source change and should ideally be done
@@ -329,6 +351,14 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Append media received from an RTMP server to the buffer.
+    ///
+    /// Unlike `load_buffer`, this doesn't report that the buffer is full, as
+    /// the stream receives many small pieces of data.
+    pub fn append_buffer(self, gc_context: &Mutation<'gc>, data: &mut Vec<u8>) {
+        self.0.write(gc_context).buffer.append(data);
+    }
+
     pub fn report_error(self, _error: Error) {
         //TODO: Report an `asyncError` to AVM1 or 2.
     }
@@ -495,6 +525,25 @@ impl<'gc> NetStream<'gc> {
     /// available in the buffer.
     pub fn play(self, context: &mut UpdateContext<'_, 'gc>, name: Option<AvmString<'gc>>) {
         if let Some(name) = name {
+            let rtmp_stream = self.0.read().rtmp_stream;
+            if let Some((connection, index)) = rtmp_stream {
+                // The server sends the media over the connection, and reports
+                // the status of the stream itself.
+                self.reset_buffer(context);
+                context.net_connections.send_stream_command(
+                    connection,
+                    index,
+                    "play",
+                    vec![
+                        AmfValue::String(name.to_string()),
+                        AmfValue::Number(-2000.0),
+                    ],
+                );
+                self.0.write(context.gc_context).playing = true;
+                StreamManager::activate(context, self);
+                return;
+            }
+
             let request = if let Ok(stream_url) =
                 Url::parse(context.swf.url()).and_then(|url| url.join(name.to_string().as_str()))
             {
@@ -521,6 +570,58 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Start publishing to a stream on the RTMP server this stream was created
+    /// for, or stop publishing if `name` is `None`.
+    ///
+    /// Returns false if this stream isn't on an RTMP connection.
+    pub fn publish(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        name: Option<AvmString<'gc>>,
+        publish_type: Option<AvmString<'gc>>,
+    ) -> bool {
+        let Some((connection, index)) = self.0.read().rtmp_stream else {
+            return false;
+        };
+
+        match name {
+            Some(name) => {
+                let publish_type =
+                    publish_type.map_or_else(|| "live".to_string(), |t| t.to_string());
+                context.net_connections.send_stream_command(
+                    connection,
+                    index,
+                    "publish",
+                    vec![
+                        AmfValue::String(name.to_string()),
+                        AmfValue::String(publish_type),
+                    ],
+                );
+            }
+            None => context.net_connections.send_stream_command(
+                connection,
+                index,
+                "closeStream",
+                vec![],
+            ),
+        }
+        true
+    }
+
+    /// Stop playing this stream, and delete the stream on the RTMP server it
+    /// was created for.
+    ///
+    /// Returns false if this stream isn't on an RTMP connection.
+    pub fn close(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let Some((connection, index)) = self.0.write(context.gc_context).rtmp_stream.take() else {
+            return false;
+        };
+
+        self.pause(context, false);
+        context.net_connections.delete_stream(connection, index);
+        true
+    }
+
     /// Pause stream playback.
     pub fn pause(self, context: &mut UpdateContext<'_, 'gc>, notify: bool) {
         // NOTE: We do not deactivate the stream here as there may be other
@@ -1155,9 +1256,12 @@ impl<'gc> NetStream<'gc> {
             //TODO: Fire an error event at AS.
             tracing::error!("Error committing sound stream: {}", e);
         }
+        // Running out of data on a stream from a server only means we're
+        // waiting for more, as the server tells us when the stream ends.
+        let is_live = write.rtmp_stream.is_some();
         drop(write);
 
-        if end_of_video {
+        if end_of_video && !is_live {
             self.trigger_status_event(
                 context,
                 vec![("code", "NetStream.Buffer.Flush"), ("level", "status")],
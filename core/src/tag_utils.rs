@@ -1,6 +1,9 @@
+use flate2::{Decompress, FlushDecompress, Status};
 use gc_arena::Collect;
-use std::sync::Arc;
-use swf::{CharacterId, Fixed8, HeaderExt, Rectangle, TagCode, Twips};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use swf::{CharacterId, Compression, Fixed8, HeaderExt, Rectangle, TagCode, Twips};
 use thiserror::Error;
 use url::Url;
 
@@ -45,8 +48,8 @@ pub struct SwfMovie {
     /// The SWF header parsed from the data stream.
     header: HeaderExt,
 
-    /// Uncompressed SWF data.
-    data: Vec<u8>,
+    /// Uncompressed SWF data, which may still be downloading.
+    data: MovieData,
 
     /// The URL the SWF was downloaded from.
     url: String,
@@ -60,9 +63,6 @@ pub struct SwfMovie {
 
     /// The suggest encoding for this SWF.
     encoding: &'static swf::Encoding,
-
-    /// The compressed length of the entire datastream
-    compressed_len: usize,
 }
 
 impl SwfMovie {
@@ -70,12 +70,11 @@ impl SwfMovie {
     pub fn empty(swf_version: u8) -> Self {
         Self {
             header: HeaderExt::default_with_swf_version(swf_version),
-            data: vec![],
+            data: MovieData::complete(vec![], 0),
             url: "file:///".into(),
             loader_url: None,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
-            compressed_len: 0,
         }
     }
 
@@ -86,12 +85,11 @@ impl SwfMovie {
     pub fn empty_fake_compressed_len(swf_version: u8, compressed_len: usize) -> Self {
         Self {
             header: HeaderExt::default_with_swf_version(swf_version),
-            data: vec![],
+            data: MovieData::complete(vec![], compressed_len),
             url: "file:///".into(),
             loader_url: None,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
-            compressed_len,
         }
     }
 
@@ -103,12 +101,11 @@ impl SwfMovie {
     pub fn error_movie(movie_url: String) -> Self {
         Self {
             header: HeaderExt::default_error_header(),
-            data: vec![],
+            data: MovieData::complete(vec![], 0),
             url: movie_url,
             loader_url: None,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
-            compressed_len: 0,
         }
     }
 
@@ -137,12 +134,11 @@ impl SwfMovie {
         let encoding = swf::SwfStr::encoding_for_version(swf_buf.header.version());
         let mut movie = Self {
             header: swf_buf.header,
-            data: swf_buf.data,
+            data: MovieData::complete(swf_buf.data, compressed_len),
             url,
             loader_url,
             parameters: Vec::new(),
             encoding,
-            compressed_len,
         };
         movie.append_parameters_from_url();
         Ok(movie)
@@ -152,12 +148,11 @@ impl SwfMovie {
     pub fn from_loaded_image(url: String, length: usize) -> Self {
         let mut movie = Self {
             header: HeaderExt::default_with_uncompressed_len(length as i32),
-            data: vec![],
+            data: MovieData::complete(vec![], length),
             url,
             loader_url: None,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
-            compressed_len: length,
         };
         movie.append_parameters_from_url();
         movie
@@ -188,8 +183,23 @@ impl SwfMovie {
         self.header.version()
     }
 
-    pub fn data(&self) -> &[u8] {
-        &self.data
+    /// The uncompressed data of the movie that was loaded so far.
+    ///
+    /// While the movie is still downloading, this always ends with a whole tag,
+    /// and only ever grows. Data that arrived very recently may not be part of
+    /// it yet.
+    pub fn data(&self) -> Arc<[u8]> {
+        self.data.get()
+    }
+
+    /// The length of the uncompressed data of the movie once it's fully loaded.
+    pub fn full_data_len(&self) -> usize {
+        self.data.full_len()
+    }
+
+    /// Whether all of the data of the movie was loaded.
+    pub fn is_complete(&self) -> bool {
+        self.data.is_complete()
     }
 
     /// Returns the suggested string encoding for the given SWF version.
@@ -232,8 +242,11 @@ impl SwfMovie {
         self.parameters.extend(params);
     }
 
+    /// The compressed length of the entire datastream.
+    ///
+    /// While the movie is still downloading, this is only an estimate.
     pub fn compressed_len(&self) -> usize {
-        self.compressed_len
+        self.data.compressed_len.load(Ordering::Relaxed)
     }
 
     pub fn uncompressed_len(&self) -> i32 {
@@ -257,21 +270,412 @@ impl SwfMovie {
     }
 }
 
+/// The uncompressed data of a movie, which can still grow while the movie is downloading.
+///
+/// The data is handed out as snapshots of the part that was received so far,
+/// which stay valid while more of it arrives. Taking a snapshot copies the
+/// data, so while the movie is downloading, a new snapshot is only taken once
+/// the data grew by an eighth. That keeps both the copying and the memory held
+/// by older snapshots proportional to the size of the movie.
+struct MovieData {
+    state: Mutex<MovieDataState>,
+
+    /// The length of the data according to the header of the movie.
+    expected_len: usize,
+
+    /// The compressed length of the entire datastream.
+    compressed_len: AtomicUsize,
+}
+
+#[derive(Clone)]
+struct MovieDataState {
+    /// The data that was handed out so far.
+    snapshot: Arc<[u8]>,
+
+    /// Data that was received after the snapshot was taken.
+    pending: Vec<u8>,
+
+    /// Whether all of the data was received.
+    complete: bool,
+}
+
+impl MovieData {
+    fn complete(data: Vec<u8>, compressed_len: usize) -> Self {
+        Self {
+            expected_len: data.len(),
+            state: Mutex::new(MovieDataState {
+                snapshot: data.into(),
+                pending: Vec::new(),
+                complete: true,
+            }),
+            compressed_len: AtomicUsize::new(compressed_len),
+        }
+    }
+
+    fn downloading(expected_len: usize, compressed_len: usize) -> Self {
+        Self {
+            expected_len,
+            state: Mutex::new(MovieDataState {
+                snapshot: Arc::new([]),
+                pending: Vec::new(),
+                complete: false,
+            }),
+            compressed_len: AtomicUsize::new(compressed_len),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, MovieDataState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn get(&self) -> Arc<[u8]> {
+        let mut state = self.state();
+        let grown_enough = state.pending.len() * 8 >= state.snapshot.len();
+        if !state.pending.is_empty() && (state.complete || grown_enough) {
+            let snapshot = [&state.snapshot[..], &state.pending[..]].concat();
+            state.snapshot = snapshot.into();
+            state.pending = Vec::new();
+        }
+        state.snapshot.clone()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.state().complete
+    }
+
+    /// Adds data after the data that was received so far.
+    fn append(&self, data: &[u8]) {
+        let mut state = self.state();
+        if !state.complete {
+            state.pending.extend_from_slice(data);
+        }
+    }
+
+    /// Marks all of the data as received.
+    fn finish(&self, compressed_len: usize) {
+        self.compressed_len.store(compressed_len, Ordering::Relaxed);
+        self.state().complete = true;
+    }
+
+    /// The length of the data that was received so far.
+    fn received_len(&self) -> usize {
+        let state = self.state();
+        state.snapshot.len() + state.pending.len()
+    }
+
+    /// The length of the data once all of it was received.
+    fn full_len(&self) -> usize {
+        if self.is_complete() {
+            self.received_len()
+        } else {
+            self.received_len().max(self.expected_len)
+        }
+    }
+}
+
+impl Clone for MovieData {
+    fn clone(&self) -> Self {
+        Self {
+            state: Mutex::new(self.state().clone()),
+            expected_len: self.expected_len,
+            compressed_len: AtomicUsize::new(self.compressed_len.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl fmt::Debug for MovieData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MovieData")
+            .field("received_len", &self.received_len())
+            .field("expected_len", &self.expected_len)
+            .field("complete", &self.is_complete())
+            .finish()
+    }
+}
+
+/// The largest SWF that's loaded progressively. Longer header lengths are
+/// left to `SwfMovie::from_data`.
+const MAX_PROGRESSIVE_LEN: u32 = 512 * 1024 * 1024;
+
+/// How many tags have to arrive before a progressively loaded movie is started,
+/// as the header of the movie includes information from the first tags.
+const HEADER_TAGS: usize = 3;
+
+enum ProgressiveState {
+    /// Waiting for the signature, version and length at the start of the file.
+    FileHeader(Vec<u8>),
+
+    /// Waiting for the rest of the header and the first tags.
+    Header {
+        compression: Compression,
+        version: u8,
+        uncompressed_len: u32,
+    },
+
+    /// The movie started, and receives the data as it arrives.
+    Movie(Arc<SwfMovie>),
+
+    /// The file can't be loaded progressively.
+    Unsupported,
+}
+
+/// Reads a SWF file while it's being downloaded, so that its movie can start
+/// playing before the download finishes.
+///
+/// Uncompressed and zlib compressed files are loaded progressively. LZMA
+/// compressed files have to be read at once with `SwfMovie::from_data` after
+/// they were downloaded.
+pub struct ProgressiveSwf {
+    url: String,
+    loader_url: Option<String>,
+    expected_len: Option<usize>,
+
+    /// How many bytes of the file were received so far.
+    received_len: usize,
+
+    state: ProgressiveState,
+
+    /// Decompresses the data of a zlib compressed file.
+    inflater: Option<Decompress>,
+
+    /// Whether the end of the compressed data was reached, or it turned out
+    /// to be corrupt, so the rest of the file is ignored.
+    inflater_done: bool,
+
+    /// Uncompressed data that wasn't added to the movie yet, as it doesn't
+    /// end with a whole tag.
+    pending: Vec<u8>,
+}
+
+impl ProgressiveSwf {
+    pub fn new(url: String, loader_url: Option<String>, expected_len: Option<usize>) -> Self {
+        Self {
+            url,
+            loader_url,
+            expected_len,
+            received_len: 0,
+            state: ProgressiveState::FileHeader(Vec::with_capacity(8)),
+            inflater: None,
+            inflater_done: false,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Reads the next chunk of the file.
+    ///
+    /// Returns the movie once enough of the file arrived to start playing it.
+    pub fn push(&mut self, mut chunk: &[u8]) -> Option<Arc<SwfMovie>> {
+        self.received_len += chunk.len();
+
+        if let ProgressiveState::FileHeader(start) = &mut self.state {
+            let needed = (8 - start.len()).min(chunk.len());
+            start.extend_from_slice(&chunk[..needed]);
+            chunk = &chunk[needed..];
+            if start.len() < 8 {
+                return None;
+            }
+            self.state = self.read_file_header();
+        }
+
+        match self.state {
+            ProgressiveState::FileHeader(_) | ProgressiveState::Unsupported => return None,
+            _ => self.decompress(chunk),
+        }
+
+        if let ProgressiveState::Header {
+            compression,
+            version,
+            uncompressed_len,
+        } = self.state
+        {
+            let movie = self.start_movie(compression, version, uncompressed_len)?;
+            self.state = ProgressiveState::Movie(movie.clone());
+            return Some(movie);
+        }
+        if let ProgressiveState::Movie(movie) = &self.state {
+            let movie = movie.clone();
+            self.append_whole_tags(&movie);
+        }
+        None
+    }
+
+    /// Finishes reading the file, once all of it was received or the download failed.
+    ///
+    /// This also happens when it's dropped, so that an abandoned download
+    /// doesn't leave the movie waiting for more data.
+    pub fn finish(mut self) {
+        self.finish_movie();
+    }
+
+    fn finish_movie(&mut self) {
+        let ProgressiveState::Movie(movie) =
+            std::mem::replace(&mut self.state, ProgressiveState::Unsupported)
+        else {
+            return;
+        };
+
+        // Anything left is an incomplete tag, which the movie will report once it gets there.
+        movie.data.append(&self.pending);
+        movie.data.finish(self.received_len);
+        if movie.data.received_len() != movie.data.expected_len {
+            tracing::warn!("SWF length doesn't match header, may be corrupt");
+        }
+    }
+
+    fn read_file_header(&mut self) -> ProgressiveState {
+        let ProgressiveState::FileHeader(start) = &self.state else {
+            unreachable!();
+        };
+        let Ok(compression) = swf::read::read_compression_type(&start[..3]) else {
+            return ProgressiveState::Unsupported;
+        };
+        let version = start[3];
+        let uncompressed_len = u32::from_le_bytes([start[4], start[5], start[6], start[7]]);
+
+        // Anything unusual is left to `SwfMovie::from_data`.
+        if version == 0 || !(8..=MAX_PROGRESSIVE_LEN).contains(&uncompressed_len) {
+            return ProgressiveState::Unsupported;
+        }
+        match compression {
+            Compression::None => {}
+            Compression::Zlib => self.inflater = Some(Decompress::new(true)),
+            Compression::Lzma => return ProgressiveState::Unsupported,
+        }
+
+        ProgressiveState::Header {
+            compression,
+            version,
+            uncompressed_len,
+        }
+    }
+
+    fn decompress(&mut self, mut input: &[u8]) {
+        if self.inflater_done {
+            return;
+        }
+        let Some(inflater) = &mut self.inflater else {
+            self.pending.extend_from_slice(input);
+            return;
+        };
+
+        while !input.is_empty() {
+            self.pending.reserve(64 * 1024);
+            let total_in = inflater.total_in();
+            let total_out = inflater.total_out();
+            let status = inflater.decompress_vec(input, &mut self.pending, FlushDecompress::None);
+            let consumed = (inflater.total_in() - total_in) as usize;
+            let progressed = consumed > 0 || inflater.total_out() > total_out;
+            input = &input[consumed..];
+            match status {
+                Ok(Status::Ok) if progressed => {}
+                // Anything after the compressed data is ignored.
+                Ok(Status::StreamEnd) => {
+                    self.inflater_done = true;
+                    break;
+                }
+                Ok(_) => break,
+                Err(e) => {
+                    // The data that came through may still be usable, like with `SwfMovie::from_data`.
+                    tracing::error!("Error decompressing SWF: {}", e);
+                    self.inflater_done = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    fn start_movie(
+        &mut self,
+        compression: Compression,
+        version: u8,
+        uncompressed_len: u32,
+    ) -> Option<Arc<SwfMovie>> {
+        let (header, header_len) =
+            swf::read::read_swf_header_ext(compression, version, uncompressed_len, &self.pending)
+                .ok()?;
+        let (_, tag_count, end_found) = whole_tags(&self.pending[header_len..], version);
+        if tag_count < HEADER_TAGS && !end_found {
+            return None;
+        }
+
+        let encoding = swf::SwfStr::encoding_for_version(version);
+        let expected_len = (uncompressed_len as usize).saturating_sub(8 + header_len);
+        let compressed_len = self.expected_len.unwrap_or(uncompressed_len as usize);
+        let mut movie = SwfMovie {
+            header,
+            data: MovieData::downloading(expected_len, compressed_len),
+            url: self.url.clone(),
+            loader_url: self.loader_url.clone(),
+            parameters: Vec::new(),
+            encoding,
+        };
+        movie.append_parameters_from_url();
+        let movie = Arc::new(movie);
+
+        self.pending.drain(..header_len);
+        self.append_whole_tags(&movie);
+        Some(movie)
+    }
+
+    fn append_whole_tags(&mut self, movie: &SwfMovie) {
+        let (len, _, _) = whole_tags(&self.pending, movie.version());
+        if len > 0 {
+            movie.data.append(&self.pending[..len]);
+            self.pending.drain(..len);
+        }
+    }
+}
+
+impl Drop for ProgressiveSwf {
+    fn drop(&mut self) {
+        self.finish_movie();
+    }
+}
+
+/// Finds the whole tags at the start of some SWF data.
+///
+/// Returns their length and count, and whether the End tag is one of them.
+fn whole_tags(data: &[u8], version: u8) -> (usize, usize, bool) {
+    let mut reader = swf::read::Reader::new(data, version);
+    let mut len = 0;
+    let mut count = 0;
+    while let Ok((tag_code, tag_len)) = reader.read_tag_code_and_length() {
+        let rest = reader.get_ref();
+        if tag_len > rest.len() {
+            break;
+        }
+        *reader.get_mut() = &rest[tag_len..];
+        len = data.len() - reader.get_ref().len();
+        count += 1;
+        if TagCode::from_u16(tag_code) == Some(TagCode::End) {
+            return (len, count, true);
+        }
+    }
+    (len, count, false)
+}
+
 /// A shared-ownership reference to some portion of an SWF datastream.
 #[derive(Debug, Clone, Collect)]
 #[collect(no_drop)]
 pub struct SwfSlice {
     pub movie: Arc<SwfMovie>,
+
+    /// The data of the movie that was loaded when the slice was made.
+    #[collect(require_static)]
+    movie_data: Arc<[u8]>,
+
     pub start: usize,
     pub end: usize,
 }
 
 impl From<Arc<SwfMovie>> for SwfSlice {
     fn from(movie: Arc<SwfMovie>) -> Self {
-        let end = movie.data().len();
+        let movie_data = movie.data();
+        let end = movie_data.len();
 
         Self {
             movie,
+            movie_data,
             start: 0,
             end,
         }
@@ -290,6 +694,7 @@ impl SwfSlice {
     #[inline]
     pub fn empty(movie: Arc<SwfMovie>) -> Self {
         Self {
+            movie_data: movie.data(),
             movie,
             start: 0,
             end: 0,
@@ -299,7 +704,20 @@ impl SwfSlice {
     /// Creates an empty SwfSlice of the same movie.
     #[inline]
     pub fn copy_empty(&self) -> Self {
-        Self::empty(self.movie.clone())
+        Self {
+            movie: self.movie.clone(),
+            movie_data: self.movie_data.clone(),
+            start: 0,
+            end: 0,
+        }
+    }
+
+    /// The data of the movie that this slice is a part of.
+    ///
+    /// This is only the part of the data that was loaded when the slice was made.
+    #[inline]
+    pub fn movie_data(&self) -> &[u8] {
+        &self.movie_data
     }
 
     /// Construct a new SwfSlice from a regular slice.
@@ -307,12 +725,13 @@ impl SwfSlice {
     /// This function returns None if the given slice is not a subslice of the
     /// current slice.
     pub fn to_subslice(&self, slice: &[u8]) -> Self {
-        let self_pval = self.movie.data().as_ptr() as usize;
+        let self_pval = self.movie_data.as_ptr() as usize;
         let slice_pval = slice.as_ptr() as usize;
 
         if (self_pval + self.start) <= slice_pval && slice_pval < (self_pval + self.end) {
             Self {
                 movie: self.movie.clone(),
+                movie_data: self.movie_data.clone(),
                 start: slice_pval - self_pval,
                 end: (slice_pval - self_pval) + slice.len(),
             }
@@ -326,13 +745,14 @@ impl SwfSlice {
     /// This function allows subslices outside the current slice to be formed,
     /// as long as they are valid subslices of the movie itself.
     pub fn to_unbounded_subslice(&self, slice: &[u8]) -> Self {
-        let self_pval = self.movie.data().as_ptr() as usize;
-        let self_len = self.movie.data().len();
+        let self_pval = self.movie_data.as_ptr() as usize;
+        let self_len = self.movie_data.len();
         let slice_pval = slice.as_ptr() as usize;
 
         if self_pval <= slice_pval && slice_pval < (self_pval + self_len) {
             Self {
                 movie: self.movie.clone(),
+                movie_data: self.movie_data.clone(),
                 start: slice_pval - self_pval,
                 end: (slice_pval - self_pval) + slice.len(),
             }
@@ -352,20 +772,21 @@ impl SwfSlice {
     /// movie, or the given reader refers to a different underlying movie, this
     /// function returns an empty slice.
     pub fn resize_to_reader(&self, reader: &mut SwfStream<'_>, size: usize) -> Self {
-        if self.movie.data().as_ptr() as usize <= reader.get_ref().as_ptr() as usize
+        if self.movie_data.as_ptr() as usize <= reader.get_ref().as_ptr() as usize
             && (reader.get_ref().as_ptr() as usize)
-                < self.movie.data().as_ptr() as usize + self.movie.data().len()
+                < self.movie_data.as_ptr() as usize + self.movie_data.len()
         {
             let outer_offset =
-                reader.get_ref().as_ptr() as usize - self.movie.data().as_ptr() as usize;
+                reader.get_ref().as_ptr() as usize - self.movie_data.as_ptr() as usize;
             let new_start = outer_offset;
             let new_end = outer_offset + size;
 
-            let len = self.movie.data().len();
+            let len = self.movie_data.len();
 
             if new_start < len && new_end < len {
                 Self {
                     movie: self.movie.clone(),
+                    movie_data: self.movie_data.clone(),
                     start: new_start,
                     end: new_end,
                 }
@@ -388,7 +809,7 @@ impl SwfSlice {
         let new_end = self.start + end;
 
         if new_start <= new_end {
-            if let Some(result) = self.movie.data().get(new_start..new_end) {
+            if let Some(result) = self.movie_data.get(new_start..new_end) {
                 self.to_subslice(result)
             } else {
                 self.copy_empty()
@@ -400,7 +821,7 @@ impl SwfSlice {
 
     /// Convert the SwfSlice into a standard data slice.
     pub fn data(&self) -> &[u8] {
-        &self.movie.data()[self.start..self.end]
+        &self.movie_data[self.start..self.end]
     }
 
     /// Get the version of the SWF this data comes from.
@@ -482,3 +903,110 @@ where
 
     Ok(true)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{Color, Header, Tag};
+
+    const URL: &str = "file:///movie.swf";
+
+    fn test_swf(compression: Compression) -> Vec<u8> {
+        let header = Header {
+            compression,
+            num_frames: 20,
+            ..Header::default_with_swf_version(10)
+        };
+        let actions = [0; 300];
+        let mut tags = vec![Tag::SetBackgroundColor(Color {
+            r: 0x33,
+            g: 0x66,
+            b: 0x99,
+            a: 0xff,
+        })];
+        for _ in 0..20 {
+            tags.push(Tag::DoAction(&actions));
+            tags.push(Tag::ShowFrame);
+        }
+
+        let mut file = vec![];
+        swf::write_swf(&header, &tags, &mut file).unwrap();
+        file
+    }
+
+    /// Reads a SWF in chunks, as if it was being downloaded.
+    fn read_progressively(file: &[u8], chunk_len: usize) -> Arc<SwfMovie> {
+        let mut progressive = ProgressiveSwf::new(URL.into(), None, Some(file.len()));
+        let mut movie: Option<Arc<SwfMovie>> = None;
+        let mut loaded_len = 0;
+        for chunk in file.chunks(chunk_len) {
+            if let Some(started) = progressive.push(chunk) {
+                movie = Some(started);
+            }
+            if let Some(movie) = &movie {
+                // Only whole tags are ever visible, and they never go away.
+                let data = movie.data();
+                assert!(data.len() >= loaded_len);
+                assert_eq!(whole_tags(&data, movie.version()).0, data.len());
+                assert!(!movie.is_complete());
+                loaded_len = data.len();
+            }
+        }
+
+        let movie = movie.expect("Movie should start");
+        progressive.finish();
+        assert!(movie.is_complete());
+        movie
+    }
+
+    #[test]
+    fn progressive_movies_match_movies_read_at_once() {
+        for compression in [Compression::None, Compression::Zlib] {
+            let file = test_swf(compression);
+            let expected = SwfMovie::from_data(&file, URL.into(), None).unwrap();
+            for chunk_len in [1, 7, 100, file.len()] {
+                let movie = read_progressively(&file, chunk_len);
+                assert_eq!(movie.data(), expected.data());
+                assert_eq!(movie.full_data_len(), expected.data().len());
+                assert_eq!(movie.compressed_len(), file.len());
+                assert_eq!(movie.header().swf_header(), expected.header().swf_header());
+                assert_eq!(
+                    movie.header().background_color(),
+                    expected.header().background_color()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn movies_longer_than_their_header_are_not_truncated() {
+        let mut file = test_swf(Compression::None);
+        let short_len = (file.len() as u32 - 500).to_le_bytes();
+        file[4..8].copy_from_slice(&short_len);
+        let expected = SwfMovie::from_data(&file, URL.into(), None).unwrap();
+        let movie = read_progressively(&file, 100);
+        assert_eq!(movie.data(), expected.data());
+    }
+
+    #[test]
+    fn slices_keep_their_data_while_the_movie_grows() {
+        let file = test_swf(Compression::None);
+        let (start, rest) = file.split_at(file.len() / 2);
+        let mut progressive = ProgressiveSwf::new(URL.into(), None, Some(file.len()));
+        let movie = progressive.push(start).expect("Movie should start");
+        let slice = SwfSlice::from(movie.clone());
+        let early = slice.data().to_vec();
+
+        progressive.push(rest);
+        progressive.finish();
+        assert_eq!(slice.data(), &early[..]);
+        assert!(SwfSlice::from(movie).len() > slice.len());
+    }
+
+    #[test]
+    fn lzma_movies_are_read_at_once() {
+        let file = test_swf(Compression::Lzma);
+        let mut progressive = ProgressiveSwf::new(URL.into(), None, None);
+        assert!(progressive.push(&file).is_none());
+    }
+}
@@ -18,6 +18,9 @@ controls-menu = Controls
 controls-menu-suspend = Suspend
 controls-menu-resume = Resume
 controls-menu-volume = Volume controls
+controls-menu-storage = Local storage settings
+controls-menu-record-input = Record Input
+controls-menu-stop-recording-input = Stop Recording Input...
 
 help-menu = Help
 help-menu-join-discord = Join Discord
@@ -31,4 +34,6 @@ debug-menu-open-stage = View Stage Info
 debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-search-display-objects = Search Display Objects...
+debug-menu-open-avm1-debugger = AVM1 Script Tracer...
+debug-menu-open-avm2-debugger = AVM2 Script Tracer...
 
@@ -0,0 +1,9 @@
+storage-settings = Local storage settings
+storage-settings-domain = Domain
+storage-settings-usage = Currently used
+storage-settings-quota = Maximum storage
+storage-settings-quota-none = None
+storage-settings-quota-unlimited = Unlimited
+storage-settings-delete-all = Delete all
+storage-quota-prompt-title = Allow local storage?
+storage-quota-prompt = { $domain } wants to store up to { $kilobytes } KB of information on your computer.
@@ -1,3 +1,4 @@
+use crate::backends::HttpArchive;
 use crate::cli::Opt;
 use crate::custom_event::RuffleEvent;
 use crate::gui::{GuiController, MENU_HEIGHT};
@@ -49,6 +50,12 @@ impl App {
             .build(&event_loop)?;
         let window = Rc::new(window);
 
+        // Replaying must never fall back to the live network, so don't start without the archive.
+        if let Some(path) = &opt.replay_network {
+            HttpArchive::load(path)
+                .with_context(|| format!("Couldn't load network archive {path:?}"))?;
+        }
+
         let mut gui = GuiController::new(window.clone(), &event_loop, &opt)?;
 
         let mut player = PlayerController::new(
@@ -103,6 +110,9 @@ impl App {
                     if let Some(mut player) = self.player.get() {
                         player.flush_shared_objects();
                     }
+                    self.player.stop_input_recording();
+                    self.player.save_profile();
+                    self.player.save_network_recording();
                     crate::shutdown();
                     return;
                 }
@@ -129,6 +139,9 @@ impl App {
                 winit::event::Event::RedrawRequested(_) => {
                     // Don't render when minimized to avoid potential swap chain errors in `wgpu`.
                     if !minimized {
+                        self.gui
+                            .borrow_mut()
+                            .set_recording_input(self.player.is_recording_input());
                         if let Some(mut player) = self.player.get() {
                             // Even if the movie is paused, user interaction with debug tools can change the render output
                             player.render();
@@ -436,6 +449,18 @@ impl App {
                     self.player.destroy();
                 }
 
+                winit::event::Event::UserEvent(RuffleEvent::StartInputRecording) => {
+                    self.player.start_input_recording();
+                }
+
+                winit::event::Event::UserEvent(RuffleEvent::StopInputRecording) => {
+                    self.player.stop_input_recording();
+                }
+
+                winit::event::Event::UserEvent(RuffleEvent::ShowStorageSettings(domain)) => {
+                    self.gui.borrow_mut().show_storage_settings(domain);
+                }
+
                 winit::event::Event::UserEvent(RuffleEvent::ExitRequested) => {
                     *control_flow = ControlFlow::Exit;
                     return;
@@ -1,11 +1,15 @@
+mod archive;
 mod audio;
 mod external_interface;
+mod local_connection;
 mod navigator;
 mod storage;
 mod ui;
 
+pub use archive::{ArchiveNavigatorBackend, HttpArchive, NetworkRecording};
 pub use audio::CpalAudioBackend;
 pub use external_interface::DesktopExternalInterfaceProvider;
+pub use local_connection::DesktopLocalConnectionBackend;
 pub use navigator::ExternalNavigatorBackend;
 pub use storage::DiskStorageBackend;
 pub use ui::DesktopUiBackend;
@@ -0,0 +1,1192 @@
+//! Recording and offline replay of network traffic
+
+use async_channel::Receiver;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_lite::FutureExt;
+use isahc::http::StatusCode;
+use ruffle_core::backend::navigator::{
+    async_return, ErrorResponse, NavigationMethod, NavigatorBackend, OwnedFuture, Request,
+    ResponseBody, StreamingResponse, SuccessResponse,
+};
+use ruffle_core::indexmap::IndexMap;
+use ruffle_core::loader::Error;
+use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use ruffle_socket_format::SocketEvent;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Sender};
+use std::time::{Duration, Instant, SystemTime};
+use url::{ParseError, Url};
+
+/// How often a recording is saved while it changes.
+const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// An archive of network traffic, stored in the HTTP Archive (HAR) format.
+///
+/// Bodies are stored as text if they're valid UTF-8, and base64 encoded otherwise.
+/// HAR can't mark request bodies as encoded, so they use an `_encoding` extension
+/// field for that. Socket connections have no HAR equivalent, so they're stored in
+/// a `_sockets` extension field, using the same events as the `socket.json` of a test.
+#[derive(Serialize, Deserialize)]
+pub struct HttpArchive {
+    log: ArchiveLog,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveLog {
+    version: String,
+    creator: ArchiveCreator,
+    entries: Vec<ArchiveEntry>,
+    #[serde(rename = "_sockets", default)]
+    sockets: Vec<SocketSession>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveCreator {
+    name: String,
+    version: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveEntry {
+    #[serde(default)]
+    started_date_time: String,
+    /// The total time of the request in milliseconds.
+    #[serde(default)]
+    time: f64,
+    request: ArchivedRequest,
+    response: ArchivedResponse,
+    #[serde(default)]
+    cache: ArchivedCache,
+    #[serde(default)]
+    timings: ArchivedTimings,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    http_version: String,
+    #[serde(default)]
+    cookies: Vec<ArchivedPair>,
+    headers: Vec<ArchivedPair>,
+    #[serde(default)]
+    query_string: Vec<ArchivedPair>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    post_data: Option<ArchivedPostData>,
+    #[serde(default = "unknown_size")]
+    headers_size: i64,
+    #[serde(default = "unknown_size")]
+    body_size: i64,
+}
+
+/// A cookie, header or query string parameter.
+#[derive(Serialize, Deserialize)]
+struct ArchivedPair {
+    name: String,
+    value: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedPostData {
+    mime_type: String,
+    text: String,
+    #[serde(rename = "_encoding", default, skip_serializing_if = "Option::is_none")]
+    encoding: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedResponse {
+    status: u16,
+    #[serde(default)]
+    status_text: String,
+    #[serde(default)]
+    http_version: String,
+    #[serde(default)]
+    cookies: Vec<ArchivedPair>,
+    #[serde(default)]
+    headers: Vec<ArchivedPair>,
+    content: ArchivedContent,
+    #[serde(rename = "redirectURL")]
+    redirect_url: String,
+    #[serde(default = "unknown_size")]
+    headers_size: i64,
+    #[serde(default = "unknown_size")]
+    body_size: i64,
+    /// The final URL of the response, which may differ from the request URL.
+    #[serde(rename = "_url")]
+    url: String,
+    #[serde(rename = "_error", default, skip_serializing_if = "Option::is_none")]
+    error: Option<ArchivedError>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedContent {
+    size: usize,
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encoding: Option<String>,
+    #[serde(default)]
+    text: String,
+}
+
+/// Responses are never cached, so there's nothing to say about the cache.
+#[derive(Default, Serialize, Deserialize)]
+struct ArchivedCache {}
+
+/// How long each phase of a request took, in milliseconds.
+///
+/// Connecting and sending happen inside the HTTP client, so they're counted
+/// as waiting for the response.
+#[derive(Default, Serialize, Deserialize)]
+struct ArchivedTimings {
+    send: f64,
+    wait: f64,
+    receive: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum ArchivedError {
+    Http { message: String, body_size: u64 },
+    Dns { message: String },
+    Fetch { message: String },
+}
+
+#[derive(Serialize, Deserialize)]
+struct SocketSession {
+    host: String,
+    port: u16,
+    state: ArchivedConnectionState,
+    /// What happened on the connection, from the point of view of the server.
+    events: Vec<SocketEvent>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ArchivedConnectionState {
+    Connected,
+    Failed,
+    TimedOut,
+}
+
+impl HttpArchive {
+    pub fn new() -> Self {
+        Self {
+            log: ArchiveLog {
+                version: "1.2".to_string(),
+                creator: ArchiveCreator {
+                    name: "Ruffle".to_string(),
+                    version: crate::RUFFLE_VERSION.to_string(),
+                },
+                entries: vec![],
+                sockets: vec![],
+            },
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, std::io::Error> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        let writer = BufWriter::new(File::create(path)?);
+        Ok(serde_json::to_writer(writer, self)?)
+    }
+}
+
+impl Default for HttpArchive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// HAR uses -1 for sizes that aren't known.
+fn unknown_size() -> i64 {
+    -1
+}
+
+/// Stores a body as text, along with the encoding needed if it isn't valid UTF-8.
+fn encode_body(body: &[u8]) -> (String, Option<String>) {
+    match std::str::from_utf8(body) {
+        Ok(text) => (text.to_string(), None),
+        Err(_) => (STANDARD.encode(body), Some("base64".to_string())),
+    }
+}
+
+fn decode_body(text: &str, encoding: Option<&str>) -> Result<Vec<u8>, String> {
+    match encoding {
+        None => Ok(text.as_bytes().to_vec()),
+        Some("base64") => STANDARD.decode(text).map_err(|e| e.to_string()),
+        Some(encoding) => Err(format!("Unknown encoding {encoding}")),
+    }
+}
+
+impl ArchiveEntry {
+    fn new(
+        request: ArchivedRequest,
+        response: ArchivedResponse,
+        started: SystemTime,
+        waited: Duration,
+        received: Duration,
+    ) -> Self {
+        let timings = ArchivedTimings {
+            send: 0.0,
+            wait: waited.as_secs_f64() * 1000.0,
+            receive: received.as_secs_f64() * 1000.0,
+        };
+        Self {
+            started_date_time: DateTime::<Utc>::from(started)
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            time: timings.send + timings.wait + timings.receive,
+            request,
+            response,
+            cache: ArchivedCache::default(),
+            timings,
+        }
+    }
+}
+
+impl ArchivedRequest {
+    fn new(request: &Request, url: String) -> Self {
+        let query_string = Url::parse(&url)
+            .map(|url| {
+                url.query_pairs()
+                    .map(|(name, value)| ArchivedPair {
+                        name: name.into_owned(),
+                        value: value.into_owned(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            method: request.method().to_string(),
+            url,
+            // The HTTP client picks the version and adds cookies from its own jar,
+            // without telling us.
+            http_version: String::new(),
+            cookies: vec![],
+            headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| ArchivedPair {
+                    name: name.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+            query_string,
+            post_data: request.body().as_ref().map(|(body, mime_type)| {
+                let (text, encoding) = encode_body(body);
+                ArchivedPostData {
+                    mime_type: mime_type.clone(),
+                    text,
+                    encoding,
+                }
+            }),
+            headers_size: unknown_size(),
+            body_size: request
+                .body()
+                .as_ref()
+                .map_or(0, |(body, _)| body.len() as i64),
+        }
+    }
+
+    fn matches(&self, method: &str, url: &str, body: &[u8]) -> bool {
+        let own_body = self.post_data.as_ref().map_or(Ok(vec![]), |data| {
+            decode_body(&data.text, data.encoding.as_deref())
+        });
+        self.method == method && self.url == url && own_body.as_deref() == Ok(body)
+    }
+}
+
+impl ArchivedResponse {
+    fn new(response: &Result<SuccessResponse, ErrorResponse>) -> Self {
+        match response {
+            Ok(response) => Self::success(response),
+            Err(response) => Self::error(&response.url, &response.error),
+        }
+    }
+
+    fn success(response: &SuccessResponse) -> Self {
+        Self::with_body(
+            response.status,
+            &response.url,
+            response.redirected,
+            &response.body,
+            None,
+        )
+    }
+
+    fn error(url: &str, error: &Error) -> Self {
+        let (status, redirected, error) = match error {
+            Error::HttpNotOk(message, status, redirected, body_size) => (
+                *status,
+                *redirected,
+                ArchivedError::Http {
+                    message: message.clone(),
+                    body_size: *body_size,
+                },
+            ),
+            Error::InvalidDomain(message) => (
+                0,
+                false,
+                ArchivedError::Dns {
+                    message: message.clone(),
+                },
+            ),
+            Error::FetchError(message) => (
+                0,
+                false,
+                ArchivedError::Fetch {
+                    message: message.clone(),
+                },
+            ),
+            error => (
+                0,
+                false,
+                ArchivedError::Fetch {
+                    message: error.to_string(),
+                },
+            ),
+        };
+        Self::with_body(status, url, redirected, &[], Some(error))
+    }
+
+    fn with_body(
+        status: u16,
+        url: &str,
+        redirected: bool,
+        body: &[u8],
+        error: Option<ArchivedError>,
+    ) -> Self {
+        Self {
+            status,
+            status_text: StatusCode::from_u16(status)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or_default()
+                .to_string(),
+            // As with requests, the HTTP client doesn't tell us about these.
+            http_version: String::new(),
+            cookies: vec![],
+            headers: vec![],
+            content: {
+                let (text, encoding) = encode_body(body);
+                ArchivedContent {
+                    size: body.len(),
+                    mime_type: String::new(),
+                    encoding,
+                    text,
+                }
+            },
+            redirect_url: if redirected {
+                url.to_string()
+            } else {
+                String::new()
+            },
+            headers_size: unknown_size(),
+            body_size: body.len() as i64,
+            url: url.to_string(),
+            error,
+        }
+    }
+
+    fn to_response(&self) -> Result<SuccessResponse, ErrorResponse> {
+        let redirected = !self.redirect_url.is_empty();
+        let error = match &self.error {
+            None => {
+                let body = decode_body(&self.content.text, self.content.encoding.as_deref())
+                    .map_err(|e| ErrorResponse {
+                        url: self.url.clone(),
+                        error: Error::FetchError(format!("Invalid archived body: {e}")),
+                    })?;
+                return Ok(SuccessResponse {
+                    url: self.url.clone(),
+                    body,
+                    status: self.status,
+                    redirected,
+                });
+            }
+            Some(ArchivedError::Http { message, body_size }) => {
+                Error::HttpNotOk(message.clone(), self.status, redirected, *body_size)
+            }
+            Some(ArchivedError::Dns { message }) => Error::InvalidDomain(message.clone()),
+            Some(ArchivedError::Fetch { message }) => Error::FetchError(message.clone()),
+        };
+        Err(ErrorResponse {
+            url: self.url.clone(),
+            error,
+        })
+    }
+}
+
+/// Something that passed through a recorded socket, or `None` once that side closed it.
+enum Traffic {
+    /// Data sent by the movie.
+    Outgoing(Option<Vec<u8>>),
+
+    /// Something that happened on the connection the inner navigator made.
+    Incoming(Option<SocketAction>),
+}
+
+impl From<&ConnectionState> for ArchivedConnectionState {
+    fn from(state: &ConnectionState) -> Self {
+        match state {
+            ConnectionState::Connected => Self::Connected,
+            ConnectionState::Failed => Self::Failed,
+            ConnectionState::TimedOut => Self::TimedOut,
+        }
+    }
+}
+
+impl From<ArchivedConnectionState> for ConnectionState {
+    fn from(state: ArchivedConnectionState) -> Self {
+        match state {
+            ArchivedConnectionState::Connected => Self::Connected,
+            ArchivedConnectionState::Failed => Self::Failed,
+            ArchivedConnectionState::TimedOut => Self::TimedOut,
+        }
+    }
+}
+
+struct Recording {
+    path: PathBuf,
+    archive: HttpArchive,
+    /// Whether anything was recorded since the archive was last saved.
+    dirty: bool,
+    last_saved: Instant,
+}
+
+impl Recording {
+    /// Notes that something was recorded, saving the archive if it hasn't been saved for a while.
+    ///
+    /// Saving rewrites the whole archive, so doing it on every change would get slow.
+    fn changed(&mut self) {
+        self.dirty = true;
+        if self.last_saved.elapsed() >= SAVE_INTERVAL {
+            self.save();
+        }
+    }
+
+    fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        if let Err(e) = self.archive.save(&self.path) {
+            tracing::error!("Couldn't save network archive to {:?}: {e}", self.path);
+        }
+        self.dirty = false;
+        self.last_saved = Instant::now();
+    }
+
+    fn record_entry(&mut self, entry: ArchiveEntry) {
+        self.archive.log.entries.push(entry);
+        self.changed();
+    }
+}
+
+/// Network traffic being recorded into an archive.
+///
+/// The archive is saved periodically while recording, and must be saved one
+/// last time once the player is done with it.
+#[derive(Clone)]
+pub struct NetworkRecording(Rc<RefCell<Recording>>);
+
+impl NetworkRecording {
+    pub fn new(path: PathBuf) -> Self {
+        let mut recording = Recording {
+            path,
+            archive: HttpArchive::new(),
+            dirty: true,
+            last_saved: Instant::now(),
+        };
+        // Report a bad path right away, rather than after the first request.
+        recording.save();
+        Self(Rc::new(RefCell::new(recording)))
+    }
+
+    /// Saves everything that was recorded so far.
+    pub fn save(&self) {
+        self.0.borrow_mut().save();
+    }
+}
+
+/// A request whose response is still being recorded.
+struct PendingEntry {
+    recording: Rc<RefCell<Recording>>,
+    request: ArchivedRequest,
+    started: SystemTime,
+    start: Instant,
+}
+
+impl PendingEntry {
+    fn new(recording: &Rc<RefCell<Recording>>, request: &Request, url: String) -> Self {
+        Self {
+            recording: recording.clone(),
+            request: ArchivedRequest::new(request, url),
+            started: SystemTime::now(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Records the response, which was fully received after the given time spent waiting for it.
+    fn finish(self, response: ArchivedResponse, waited: Duration) {
+        let entry = ArchiveEntry::new(
+            self.request,
+            response,
+            self.started,
+            waited,
+            self.start.elapsed().saturating_sub(waited),
+        );
+        self.recording.borrow_mut().record_entry(entry);
+    }
+}
+
+/// Passes on the body of a streamed response, recording it once it's complete.
+///
+/// A body that isn't read to the end isn't recorded.
+struct RecordingBody {
+    inner: Box<dyn ResponseBody>,
+    entry: Option<PendingEntry>,
+    status: u16,
+    url: String,
+    redirected: bool,
+    /// How long it took for the response to start.
+    waited: Duration,
+    received: Vec<u8>,
+}
+
+impl ResponseBody for RecordingBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        Box::pin(async move {
+            let chunk = self.inner.next_chunk().await;
+            let response = match &chunk {
+                Ok(Some(data)) => {
+                    self.received.extend_from_slice(data);
+                    None
+                }
+                Ok(None) => Some(ArchivedResponse::with_body(
+                    self.status,
+                    &self.url,
+                    self.redirected,
+                    &self.received,
+                    None,
+                )),
+                Err(error) => Some(ArchivedResponse::error(&self.url, error)),
+            };
+            if let Some(response) = response {
+                if let Some(entry) = self.entry.take() {
+                    entry.finish(response, self.waited);
+                }
+            }
+            chunk
+        })
+    }
+}
+
+struct Replay {
+    archive: HttpArchive,
+    /// Which entries have already been served.
+    served: Vec<bool>,
+    /// Which socket sessions have already been served.
+    served_sockets: Vec<bool>,
+}
+
+impl Replay {
+    /// Finds the response to a request.
+    ///
+    /// Identical requests are answered in the order they were recorded, repeating
+    /// the last answer once they run out.
+    fn response(&mut self, method: &str, url: &str, body: &[u8]) -> Option<&ArchivedResponse> {
+        let entries = &self.archive.log.entries;
+        let mut matching =
+            (0..entries.len()).filter(|&i| entries[i].request.matches(method, url, body));
+        let first_unserved = matching.clone().find(|&i| !self.served[i]);
+        let index = first_unserved.or_else(|| matching.next_back())?;
+        self.served[index] = true;
+        Some(&entries[index].response)
+    }
+
+    /// Takes the next recorded connection to the given host and port.
+    fn socket_session(&mut self, host: &str, port: u16) -> Option<&SocketSession> {
+        let sessions = &self.archive.log.sockets;
+        let index = (0..sessions.len()).find(|&i| {
+            !self.served_sockets[i] && sessions[i].host == host && sessions[i].port == port
+        })?;
+        self.served_sockets[index] = true;
+        Some(&sessions[index])
+    }
+}
+
+enum Mode {
+    Record(Rc<RefCell<Recording>>),
+    Replay(RefCell<Replay>),
+}
+
+/// A `NavigatorBackend` that records all network traffic of another navigator
+/// into an archive, or replays a previously recorded archive without touching
+/// the network.
+///
+/// Requests are matched on their method, URL and body. Local files are never
+/// archived, and are always read through the inner navigator.
+pub struct ArchiveNavigatorBackend<N: NavigatorBackend> {
+    inner: N,
+    mode: Mode,
+}
+
+impl<N: NavigatorBackend> ArchiveNavigatorBackend<N> {
+    /// Wraps a navigator, recording its traffic.
+    pub fn record(inner: N, recording: NetworkRecording) -> Self {
+        Self {
+            inner,
+            mode: Mode::Record(recording.0),
+        }
+    }
+
+    /// Wraps a navigator, answering all network requests from an archive.
+    pub fn replay(inner: N, archive: HttpArchive) -> Self {
+        let served = vec![false; archive.log.entries.len()];
+        let served_sockets = vec![false; archive.log.sockets.len()];
+        Self {
+            inner,
+            mode: Mode::Replay(RefCell::new(Replay {
+                archive,
+                served,
+                served_sockets,
+            })),
+        }
+    }
+
+    /// The URL under which a request is archived, unless it's for a local file.
+    fn archived_url(&self, request: &Request) -> Option<String> {
+        match self.inner.resolve_url(request.url()) {
+            Ok(url) if url.scheme() != "file" => Some(url.to_string()),
+            _ => None,
+        }
+    }
+
+    fn replay_response(
+        replay: &RefCell<Replay>,
+        request: &Request,
+        url: String,
+    ) -> Result<SuccessResponse, ErrorResponse> {
+        let method = request.method().to_string();
+        let body = request
+            .body()
+            .as_ref()
+            .map_or(&[][..], |(body, _)| body.as_slice());
+        match replay.borrow_mut().response(&method, &url, body) {
+            Some(response) => response.to_response(),
+            None => {
+                tracing::warn!("{method} request to {url} is not in the network archive");
+                Err(ErrorResponse {
+                    url: url.clone(),
+                    error: Error::FetchError(format!("{url} is not in the archive")),
+                })
+            }
+        }
+    }
+}
+
+impl<N: NavigatorBackend> NavigatorBackend for ArchiveNavigatorBackend<N> {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, target, vars_method);
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        let Some(url) = self.archived_url(&request) else {
+            return self.inner.fetch(request);
+        };
+
+        match &self.mode {
+            Mode::Record(recording) => {
+                let entry = PendingEntry::new(recording, &request, url);
+                let response = self.inner.fetch(request);
+                Box::pin(async move {
+                    let response = response.await;
+                    let waited = entry.start.elapsed();
+                    entry.finish(ArchivedResponse::new(&response), waited);
+                    response
+                })
+            }
+            Mode::Replay(replay) => async_return(Self::replay_response(replay, &request, url)),
+        }
+    }
+
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, ErrorResponse> {
+        let Some(url) = self.archived_url(&request) else {
+            return self.inner.fetch_stream(request);
+        };
+
+        match &self.mode {
+            Mode::Record(recording) => {
+                let entry = PendingEntry::new(recording, &request, url);
+                let response = self.inner.fetch_stream(request);
+                Box::pin(async move {
+                    let response = response.await;
+                    let waited = entry.start.elapsed();
+                    match response {
+                        Ok(response) => Ok(StreamingResponse {
+                            url: response.url.clone(),
+                            status: response.status,
+                            redirected: response.redirected,
+                            expected_length: response.expected_length,
+                            body: Box::new(RecordingBody {
+                                inner: response.body,
+                                entry: Some(entry),
+                                status: response.status,
+                                url: response.url,
+                                redirected: response.redirected,
+                                waited,
+                                received: vec![],
+                            }),
+                        }),
+                        Err(response) => {
+                            entry.finish(
+                                ArchivedResponse::error(&response.url, &response.error),
+                                waited,
+                            );
+                            Err(response)
+                        }
+                    }
+                })
+            }
+            Mode::Replay(replay) => async_return(
+                Self::replay_response(replay, &request, url).map(StreamingResponse::from_complete),
+            ),
+        }
+    }
+
+    fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        self.inner.resolve_url(url)
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future);
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+
+    fn is_socket_approved(&self, host: &str, port: u16) -> bool {
+        self.inner.is_socket_approved(host, port)
+    }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let future: OwnedFuture<(), Error> = match &self.mode {
+            Mode::Record(recording) => {
+                let (inner_sender, inner_receiver) = channel();
+                let (outgoing_sender, outgoing_receiver) = async_channel::unbounded();
+                self.inner.connect_socket(
+                    host.clone(),
+                    port,
+                    timeout,
+                    handle,
+                    outgoing_receiver,
+                    inner_sender,
+                );
+
+                let recording = recording.clone();
+                let session = {
+                    let mut recording = recording.borrow_mut();
+                    let sessions = &mut recording.archive.log.sockets;
+                    sessions.push(SocketSession {
+                        host,
+                        port,
+                        state: ArchivedConnectionState::Failed,
+                        events: vec![],
+                    });
+                    sessions.len() - 1
+                };
+                let record =
+                    move |event: Option<SocketEvent>, state: Option<ArchivedConnectionState>| {
+                        let mut recording = recording.borrow_mut();
+                        let session = &mut recording.archive.log.sockets[session];
+                        if let Some(state) = state {
+                            session.state = state;
+                        }
+                        session.events.extend(event);
+                        recording.changed();
+                    };
+
+                // The inner navigator reports back through a channel that can't be awaited,
+                // so a thread waits on it and passes everything on to one that can.
+                let (action_sender, action_receiver) = async_channel::unbounded();
+                std::thread::spawn(move || {
+                    while let Ok(action) = inner_receiver.recv() {
+                        if action_sender.send_blocking(action).is_err() {
+                            break;
+                        }
+                    }
+                });
+
+                // Sit between the movie and the inner navigator, recording everything that passes.
+                Box::pin(async move {
+                    loop {
+                        let traffic = async { Traffic::Outgoing(receiver.recv().await.ok()) }
+                            .or(async { Traffic::Incoming(action_receiver.recv().await.ok()) })
+                            .await;
+                        match traffic {
+                            Traffic::Outgoing(Some(data)) => {
+                                record(
+                                    Some(SocketEvent::Receive {
+                                        expected: data.clone(),
+                                    }),
+                                    None,
+                                );
+                                let _ = outgoing_sender.send(data).await;
+                            }
+                            Traffic::Outgoing(None) => {
+                                record(Some(SocketEvent::WaitForDisconnect), None);
+                                return Ok(());
+                            }
+                            Traffic::Incoming(Some(action)) => {
+                                let closed = match &action {
+                                    SocketAction::Connect(_, state) => {
+                                        record(None, Some(state.into()));
+                                        false
+                                    }
+                                    SocketAction::Data(_, payload) => {
+                                        record(
+                                            Some(SocketEvent::Send {
+                                                payload: payload.clone(),
+                                            }),
+                                            None,
+                                        );
+                                        false
+                                    }
+                                    SocketAction::Close(_) => {
+                                        record(Some(SocketEvent::Disconnect), None);
+                                        true
+                                    }
+                                };
+                                sender.send(action).expect("working channel send");
+                                if closed {
+                                    return Ok(());
+                                }
+                            }
+                            // The inner navigator gave up on the connection.
+                            Traffic::Incoming(None) => return Ok(()),
+                        }
+                    }
+                })
+            }
+            Mode::Replay(replay) => {
+                let session = replay
+                    .borrow_mut()
+                    .socket_session(&host, port)
+                    .map(|session| (session.state, session.events.clone()));
+
+                Box::pin(async move {
+                    let Some((state, events)) = session else {
+                        tracing::warn!("Connection to {host}:{port} is not in the network archive");
+                        sender
+                            .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                            .expect("working channel send");
+                        return Ok(());
+                    };
+
+                    sender
+                        .send(SocketAction::Connect(handle, state.into()))
+                        .expect("working channel send");
+                    if !matches!(state, ArchivedConnectionState::Connected) {
+                        return Ok(());
+                    }
+
+                    // The movie may split its writes differently than when it was recorded,
+                    // so only the amount of data is compared. Data received beyond what one
+                    // event expects counts towards the next.
+                    let mut received = 0;
+                    for event in events {
+                        match event {
+                            SocketEvent::Receive { expected } => {
+                                while received < expected.len() {
+                                    match receiver.recv().await {
+                                        Ok(data) => received += data.len(),
+                                        Err(_) => return Ok(()),
+                                    }
+                                }
+                                received -= expected.len();
+                            }
+                            SocketEvent::Send { payload } => {
+                                sender
+                                    .send(SocketAction::Data(handle, payload))
+                                    .expect("working channel send");
+                            }
+                            SocketEvent::Disconnect => {
+                                sender
+                                    .send(SocketAction::Close(handle))
+                                    .expect("working channel send");
+                                return Ok(());
+                            }
+                            SocketEvent::WaitForDisconnect => break,
+                        }
+                    }
+
+                    // Keep the connection open until the movie closes it.
+                    while receiver.recv().await.is_ok() {}
+                    Ok(())
+                })
+            }
+        };
+
+        self.inner.spawn_future(future);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn success(url: &str, body: &[u8]) -> SuccessResponse {
+        SuccessResponse {
+            url: url.to_string(),
+            body: body.to_vec(),
+            status: 200,
+            redirected: false,
+        }
+    }
+
+    fn entry(request: Request, response: &Result<SuccessResponse, ErrorResponse>) -> ArchiveEntry {
+        let url = request.url().to_string();
+        ArchiveEntry::new(
+            ArchivedRequest::new(&request, url),
+            ArchivedResponse::new(response),
+            SystemTime::now(),
+            Duration::ZERO,
+            Duration::ZERO,
+        )
+    }
+
+    fn replay(entries: Vec<ArchiveEntry>) -> Replay {
+        let mut archive = HttpArchive::new();
+        archive.log.entries = entries;
+        Replay {
+            served: vec![false; archive.log.entries.len()],
+            served_sockets: vec![],
+            archive,
+        }
+    }
+
+    fn replayed_body(replay: &mut Replay, method: &str, url: &str, body: &[u8]) -> Option<Vec<u8>> {
+        replay
+            .response(method, url, body)
+            .map(|response| response.to_response().ok().unwrap().body)
+    }
+
+    #[test]
+    fn requests_match_on_method_url_and_body() {
+        let url = "https://example.com/data?a=1";
+        let request = ArchivedRequest::new(
+            &Request::post(
+                url.to_string(),
+                Some((b"hello".to_vec(), "text/plain".to_string())),
+            ),
+            url.to_string(),
+        );
+        let body = b"hello";
+
+        assert!(request.matches("POST", url, body));
+        assert!(!request.matches("GET", url, body));
+        assert!(!request.matches("POST", "https://example.com/data?a=2", body));
+        assert!(!request.matches("POST", url, b""));
+        assert_eq!(request.query_string.len(), 1);
+        assert_eq!(request.query_string[0].name, "a");
+        assert_eq!(request.query_string[0].value, "1");
+    }
+
+    #[test]
+    fn identical_requests_are_replayed_in_order() {
+        let url = "https://example.com/counter";
+        let other_url = "https://example.com/other";
+        let mut replay = replay(vec![
+            entry(Request::get(url.to_string()), &Ok(success(url, b"1"))),
+            entry(
+                Request::get(other_url.to_string()),
+                &Ok(success(other_url, b"x")),
+            ),
+            entry(Request::get(url.to_string()), &Ok(success(url, b"2"))),
+        ]);
+
+        assert_eq!(
+            replayed_body(&mut replay, "GET", url, b""),
+            Some(b"1".to_vec())
+        );
+        assert_eq!(
+            replayed_body(&mut replay, "GET", url, b""),
+            Some(b"2".to_vec())
+        );
+        // Once they run out, the last answer is repeated.
+        assert_eq!(
+            replayed_body(&mut replay, "GET", url, b""),
+            Some(b"2".to_vec())
+        );
+        assert_eq!(
+            replayed_body(&mut replay, "GET", other_url, b""),
+            Some(b"x".to_vec())
+        );
+        assert!(replay.response("POST", url, b"").is_none());
+        assert!(replay
+            .response("GET", "https://example.com/", b"")
+            .is_none());
+    }
+
+    #[test]
+    fn responses_round_trip() {
+        let redirected = SuccessResponse {
+            url: "https://example.com/final".to_string(),
+            body: vec![0, 1, 2, 255],
+            status: 200,
+            redirected: true,
+        };
+        let responses = [
+            Ok(redirected),
+            Err(ErrorResponse {
+                url: "https://example.com/missing".to_string(),
+                error: Error::HttpNotOk("Not Found".to_string(), 404, false, 12),
+            }),
+            Err(ErrorResponse {
+                url: "https://nowhere.invalid/".to_string(),
+                error: Error::InvalidDomain("nowhere.invalid".to_string()),
+            }),
+            Err(ErrorResponse {
+                url: "https://example.com/reset".to_string(),
+                error: Error::FetchError("Connection reset".to_string()),
+            }),
+        ];
+
+        // Go through JSON, just like when saving and loading an archive.
+        let mut archive = HttpArchive::new();
+        archive.log.entries = responses
+            .iter()
+            .map(|response| entry(Request::get("https://example.com/".to_string()), response))
+            .collect();
+        let archive: HttpArchive =
+            serde_json::from_str(&serde_json::to_string(&archive).unwrap()).unwrap();
+        let replayed: Vec<_> = archive
+            .log
+            .entries
+            .iter()
+            .map(|entry| entry.response.to_response())
+            .collect();
+
+        let Ok(response) = &replayed[0] else {
+            panic!("Expected a successful response");
+        };
+        assert_eq!(response.url, "https://example.com/final");
+        assert_eq!(response.body, vec![0, 1, 2, 255]);
+        assert_eq!(response.status, 200);
+        assert!(response.redirected);
+
+        let Err(response) = &replayed[1] else {
+            panic!("Expected an HTTP error");
+        };
+        assert_eq!(response.url, "https://example.com/missing");
+        assert!(matches!(
+            &response.error,
+            Error::HttpNotOk(message, 404, false, 12) if message == "Not Found"
+        ));
+
+        assert!(matches!(
+            replayed[2].as_ref().map_err(|response| &response.error),
+            Err(Error::InvalidDomain(domain)) if domain == "nowhere.invalid"
+        ));
+        assert!(matches!(
+            replayed[3].as_ref().map_err(|response| &response.error),
+            Err(Error::FetchError(message)) if message == "Connection reset"
+        ));
+    }
+
+    #[test]
+    fn bodies_are_text_unless_binary() {
+        let url = "https://example.com/form";
+        let request = |body: &[u8]| {
+            Request::post(
+                url.to_string(),
+                Some((body.to_vec(), "application/octet-stream".to_string())),
+            )
+        };
+        let text = entry(request(b"a=1&b=2"), &Ok(success(url, b"Hello!")));
+        let binary = entry(request(&[0xFF, 0x00]), &Ok(success(url, &[0x80, 0x81])));
+        let json = serde_json::to_value([&text, &binary]).unwrap();
+
+        assert_eq!(json[0]["request"]["postData"]["text"], "a=1&b=2");
+        assert!(json[0]["request"]["postData"].get("_encoding").is_none());
+        assert_eq!(json[0]["response"]["content"]["text"], "Hello!");
+        assert!(json[0]["response"]["content"].get("encoding").is_none());
+
+        assert_eq!(json[1]["request"]["postData"]["text"], "/wA=");
+        assert_eq!(json[1]["request"]["postData"]["_encoding"], "base64");
+        assert_eq!(json[1]["response"]["content"]["text"], "gIE=");
+        assert_eq!(json[1]["response"]["content"]["encoding"], "base64");
+
+        let mut replay = replay(serde_json::from_value(json).unwrap());
+        assert_eq!(
+            replayed_body(&mut replay, "POST", url, b"a=1&b=2"),
+            Some(b"Hello!".to_vec())
+        );
+        assert_eq!(
+            replayed_body(&mut replay, "POST", url, &[0xFF, 0x00]),
+            Some(vec![0x80, 0x81])
+        );
+    }
+
+    #[test]
+    fn archives_without_optional_fields_load() {
+        let archive: HttpArchive = serde_json::from_value(serde_json::json!({
+            "log": {
+                "version": "1.2",
+                "creator": { "name": "Ruffle", "version": "0" },
+                "entries": [{
+                    "request": {
+                        "method": "GET",
+                        "url": "https://example.com/",
+                        "headers": []
+                    },
+                    "response": {
+                        "status": 200,
+                        "redirectURL": "",
+                        "content": { "size": 2, "encoding": "base64", "text": "aGk=" },
+                        "_url": "https://example.com/"
+                    }
+                }]
+            }
+        }))
+        .unwrap();
+
+        let mut replay = replay(archive.log.entries);
+        assert_eq!(
+            replayed_body(&mut replay, "GET", "https://example.com/", b""),
+            Some(b"hi".to_vec())
+        );
+    }
+}
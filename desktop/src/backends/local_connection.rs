@@ -0,0 +1,351 @@
+use anyhow::{Context, Error};
+use ruffle_core::backend::local_connection::{LocalConnectionBackend, LocalConnectionMessage};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// How long another player may take to send us a message.
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The largest message accepted from another player.
+///
+/// Flash Player limits the arguments of a message to 40KB.
+const MAX_MESSAGE_SIZE: u64 = 64 * 1024;
+
+/// How often a player renews the connection names it registered.
+const RENEW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long after its last renewal a connection name is considered abandoned,
+/// as the player that registered it must have exited without unregistering it.
+const ABANDONED_AFTER: Duration = Duration::from_secs(5);
+
+/// Where to reach the player that registered a connection name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Registration {
+    port: u16,
+
+    /// A random secret that must start every message sent to the player, so
+    /// that only other players can send messages, and not any program that
+    /// can connect to a local port (such as a web page).
+    token: u128,
+}
+
+impl Registration {
+    /// Read the registration of a connection name, even if it was abandoned.
+    fn read(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        let (port, token) = contents.trim().split_once(' ')?;
+        Some(Self {
+            port: port.parse().ok()?,
+            token: u128::from_str_radix(token, 16).ok()?,
+        })
+    }
+
+    /// Read the registration of a connection name, if its player is still running.
+    fn read_live(path: &Path) -> Option<Self> {
+        if is_abandoned(path) {
+            return None;
+        }
+        Self::read(path)
+    }
+
+    /// Write the registration of a connection name.
+    ///
+    /// When claiming the name, this fails if the file already exists, so
+    /// that two players can't both claim the same name. Otherwise, the
+    /// existing file is renewed.
+    fn write(&self, path: &Path, claim: bool) -> std::io::Result<()> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true);
+        if claim {
+            options.create_new(true);
+        } else {
+            options.truncate(true);
+        }
+        #[cfg(unix)]
+        {
+            // Other users mustn't learn the token.
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        write!(options.open(path)?, "{} {:032x}", self.port, self.token)
+    }
+}
+
+/// Whether the player that registered a connection name stopped renewing it.
+///
+/// Files that are still being written count as live, so that their name isn't
+/// claimed twice.
+fn is_abandoned(path: &Path) -> bool {
+    match fs::metadata(path).and_then(|m| m.modified()) {
+        Ok(modified) => modified.elapsed().unwrap_or_default() > ABANDONED_AFTER,
+        Err(_) => true,
+    }
+}
+
+/// Where connection names are registered.
+///
+/// This is a per-user directory that only its user can access, as the
+/// registrations contain the tokens needed to message a player.
+fn registry_path() -> Result<PathBuf, Error> {
+    let path = dirs::runtime_dir()
+        .or_else(dirs::data_local_dir)
+        .context("Couldn't find a directory for the LocalConnection registry")?
+        .join("ruffle")
+        .join("LocalConnections");
+
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+        builder.mode(0o700);
+        builder.create(&path)?;
+        // The directory may predate us, so make sure it's private.
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o700))?;
+    }
+    #[cfg(not(unix))]
+    builder.create(&path)?;
+    Ok(path)
+}
+
+fn name_path(registry_path: &Path, name: &str) -> PathBuf {
+    // Connection names may contain characters that aren't allowed in file names.
+    let file_name: String = name.bytes().map(|b| format!("{b:02x}")).collect();
+    registry_path.join(file_name)
+}
+
+fn local_address(port: u16) -> SocketAddr {
+    SocketAddr::from((Ipv4Addr::LOCALHOST, port))
+}
+
+/// Bridges LocalConnections between Ruffle processes on the same machine.
+///
+/// Each player listens on a local TCP port. Claimed connection names are
+/// recorded in a per-user directory, as files containing the port and token of
+/// the player that owns them. Players renew these files while they run, so
+/// that the names of players that crashed can be claimed again.
+///
+/// Nothing here blocks the player: messages are sent and received on other threads.
+pub struct DesktopLocalConnectionBackend {
+    registry_path: PathBuf,
+    registration: Registration,
+    registered: Arc<Mutex<HashSet<String>>>,
+    receiver: Receiver<LocalConnectionMessage>,
+
+    /// Messages for the worker thread to send. Dropping it stops the worker,
+    /// which then stops the listener.
+    outgoing: Sender<(Registration, LocalConnectionMessage)>,
+}
+
+impl DesktopLocalConnectionBackend {
+    pub fn new() -> Result<Self, Error> {
+        let registry_path = registry_path()?;
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let registration = Registration {
+            port: listener.local_addr()?.port(),
+            token: rand::random(),
+        };
+        let registered = Arc::new(Mutex::new(HashSet::new()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = channel();
+        let (outgoing, outgoing_receiver) = channel();
+
+        let listener_shutdown = shutdown.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if listener_shutdown.load(Ordering::Relaxed) {
+                    break;
+                }
+                match stream {
+                    Ok(stream) => {
+                        // A slow sender mustn't hold up the others.
+                        let sender = sender.clone();
+                        std::thread::spawn(move || {
+                            receive_message(stream, registration.token, sender)
+                        });
+                    }
+                    Err(e) => tracing::warn!("Couldn't accept LocalConnection message: {e}"),
+                }
+            }
+        });
+
+        let worker_registry_path = registry_path.clone();
+        let worker_registered = registered.clone();
+        std::thread::spawn(move || {
+            run_worker(
+                &worker_registry_path,
+                registration,
+                &worker_registered,
+                outgoing_receiver,
+            );
+            // Wake up the listener, so that it stops and closes its port.
+            shutdown.store(true, Ordering::Relaxed);
+            let _ = TcpStream::connect_timeout(&local_address(registration.port), CONNECT_TIMEOUT);
+        });
+
+        Ok(Self {
+            registry_path,
+            registration,
+            registered,
+            receiver,
+            outgoing,
+        })
+    }
+}
+
+/// Receive a message from another player, checking that it knows our token.
+fn receive_message(stream: TcpStream, token: u128, sender: Sender<LocalConnectionMessage>) {
+    let mut bytes = vec![];
+    if let Err(e) = stream
+        .set_read_timeout(Some(READ_TIMEOUT))
+        .and_then(|_| (&stream).take(MAX_MESSAGE_SIZE).read_to_end(&mut bytes))
+    {
+        tracing::warn!("Couldn't receive LocalConnection message: {e}");
+        return;
+    }
+
+    let token = token.to_le_bytes();
+    if bytes.len() < token.len() || bytes[..token.len()] != token {
+        tracing::warn!("Ignoring LocalConnection message from an unknown sender");
+        return;
+    }
+
+    match LocalConnectionMessage::from_bytes(&bytes[token.len()..]) {
+        Ok(message) => {
+            // If the player is gone, nobody needs the message anymore.
+            let _ = sender.send(message);
+        }
+        Err(e) => tracing::warn!("Invalid LocalConnection message: {e}"),
+    }
+}
+
+/// Send messages to other players, and renew our connection names, until the
+/// backend is dropped.
+fn run_worker(
+    registry_path: &Path,
+    registration: Registration,
+    registered: &Mutex<HashSet<String>>,
+    outgoing: Receiver<(Registration, LocalConnectionMessage)>,
+) {
+    let mut last_renewed = Instant::now();
+    loop {
+        match outgoing.recv_timeout(RENEW_INTERVAL.saturating_sub(last_renewed.elapsed())) {
+            Ok((owner, message)) => send_message(owner, &message),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        if last_renewed.elapsed() >= RENEW_INTERVAL {
+            let registered = registered.lock().expect("Registry lock must be available");
+            for name in registered.iter() {
+                let path = name_path(registry_path, name);
+                // Don't take back a name that another player claimed in the meantime.
+                if Registration::read(&path) == Some(registration) {
+                    if let Err(e) = registration.write(&path, false) {
+                        tracing::warn!("Couldn't renew LocalConnection {name}: {e}");
+                    }
+                }
+            }
+            last_renewed = Instant::now();
+        }
+    }
+}
+
+fn send_message(owner: Registration, message: &LocalConnectionMessage) {
+    let mut bytes = owner.token.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&message.to_bytes());
+    if let Err(e) = TcpStream::connect_timeout(&local_address(owner.port), CONNECT_TIMEOUT)
+        .and_then(|mut stream| stream.write_all(&bytes))
+    {
+        tracing::warn!("Couldn't send LocalConnection message: {e}");
+    }
+}
+
+impl LocalConnectionBackend for DesktopLocalConnectionBackend {
+    fn register(&mut self, name: &str) -> bool {
+        let path = name_path(&self.registry_path, name);
+        let mut registered = self
+            .registered
+            .lock()
+            .expect("Registry lock must be available");
+
+        // Creating the file claims the name, so only one player can succeed.
+        // If the name belongs to a player that is gone, we try once more
+        // after removing its registration.
+        for _ in 0..2 {
+            let result = match self.registration.write(&path, true) {
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if Registration::read(&path) == Some(self.registration) {
+                        self.registration.write(&path, false)
+                    } else if is_abandoned(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    } else {
+                        return false;
+                    }
+                }
+                result => result,
+            };
+
+            return match result {
+                Ok(()) => {
+                    registered.insert(name.to_string());
+                    true
+                }
+                Err(e) => {
+                    // A partial registration isn't renewed, so it's soon abandoned.
+                    tracing::warn!("Couldn't register LocalConnection {name}: {e}");
+                    false
+                }
+            };
+        }
+        false
+    }
+
+    fn unregister(&mut self, name: &str) {
+        let mut registered = self
+            .registered
+            .lock()
+            .expect("Registry lock must be available");
+        if registered.remove(name) {
+            let _ = fs::remove_file(name_path(&self.registry_path, name));
+        }
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) -> bool {
+        let path = name_path(&self.registry_path, &message.connection_name);
+        let Some(owner) = Registration::read_live(&path) else {
+            return false;
+        };
+
+        // Connecting may take a while, so the worker sends the message.
+        self.outgoing.send((owner, message)).is_ok()
+    }
+
+    fn receive(&mut self) -> Vec<LocalConnectionMessage> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Drop for DesktopLocalConnectionBackend {
+    fn drop(&mut self) {
+        // Holding the lock keeps the worker from renewing the names while they're removed.
+        let mut registered = self
+            .registered
+            .lock()
+            .expect("Registry lock must be available");
+        for name in registered.drain() {
+            let _ = fs::remove_file(name_path(&self.registry_path, &name));
+        }
+    }
+}
@@ -9,19 +9,23 @@ use futures::{AsyncReadExt, AsyncWriteExt};
 use futures_lite::FutureExt;
 use isahc::http::{HeaderName, HeaderValue};
 use isahc::{
-    config::RedirectPolicy, prelude::*, AsyncReadResponseExt, HttpClient, Request as IsahcRequest,
+    config::RedirectPolicy, prelude::*, AsyncBody, HttpClient, Request as IsahcRequest,
+    Response as IsahcResponse,
 };
 use rfd::{AsyncMessageDialog, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
 use ruffle_core::backend::navigator::{
     async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SocketMode, SuccessResponse,
+    NavigatorBackend, OpenURLMode, OwnedFuture, Request, ResponseBody, SocketMode,
+    StreamingResponse, SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
 use std::collections::HashSet;
+use std::future::Future;
 use std::io;
 use std::io::ErrorKind;
+use std::pin::Pin;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::mpsc::Sender;
@@ -30,6 +34,28 @@ use tracing::warn;
 use url::{ParseError, Url};
 use winit::event_loop::EventLoopProxy;
 
+/// The body of an HTTP response, read as it arrives from the network.
+struct HttpResponseBody(IsahcResponse<AsyncBody>);
+
+impl ResponseBody for HttpResponseBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        Box::pin(async move {
+            let mut buffer = vec![0; 64 * 1024];
+            let read = self
+                .0
+                .body_mut()
+                .read(&mut buffer)
+                .await
+                .map_err(|e| Error::FetchError(e.to_string()))?;
+            if read == 0 {
+                return Ok(None);
+            }
+            buffer.truncate(read);
+            Ok(Some(buffer))
+        })
+    }
+}
+
 /// Implementation of `NavigatorBackend` for non-web environments that can call
 /// out to a web browser.
 pub struct ExternalNavigatorBackend {
@@ -180,147 +206,158 @@ impl NavigatorBackend for ExternalNavigatorBackend {
             }
         };
 
-        let client = self.client.clone();
+        if processed_url.scheme() != "file" {
+            let response = self.fetch_stream(request);
+            return Box::pin(async move { response.await?.into_complete().await });
+        }
 
-        match processed_url.scheme() {
-            "file" => Box::pin(async move {
-                // We send the original url (including query parameters)
-                // back to ruffle_core in the `Response`
-                let response_url = processed_url.clone();
-                // Flash supports query parameters with local urls.
-                // SwfMovie takes care of exposing those to ActionScript -
-                // when we actually load a filesystem url, strip them out.
-                processed_url.set_query(None);
-
-                let path = match processed_url.to_file_path() {
-                    Ok(path) => path,
-                    Err(_) => {
-                        return create_specific_fetch_error(
-                            "Unable to create path out of URL",
-                            response_url.as_str(),
-                            "",
-                        )
-                    }
-                };
+        Box::pin(async move {
+            // We send the original url (including query parameters)
+            // back to ruffle_core in the `Response`
+            let response_url = processed_url.clone();
+            // Flash supports query parameters with local urls.
+            // SwfMovie takes care of exposing those to ActionScript -
+            // when we actually load a filesystem url, strip them out.
+            processed_url.set_query(None);
+
+            let path = match processed_url.to_file_path() {
+                Ok(path) => path,
+                Err(_) => {
+                    return create_specific_fetch_error(
+                        "Unable to create path out of URL",
+                        response_url.as_str(),
+                        "",
+                    )
+                }
+            };
 
-                let body = match std::fs::read(&path).or_else(|e| {
-                    if cfg!(feature = "sandbox") {
-                        use rfd::FileDialog;
+            let body = match std::fs::read(&path).or_else(|e| {
+                if cfg!(feature = "sandbox") {
+                    use rfd::FileDialog;
 
-                        if e.kind() == ErrorKind::PermissionDenied {
-                            let attempt_sandbox_open = MessageDialog::new()
-                                .set_level(MessageLevel::Warning)
-                                .set_description(format!("The current movie is attempting to read files stored in {}.\n\nTo allow it to do so, click Yes, and then Open to grant read access to that directory.\n\nOtherwise, click No to deny access.", path.parent().unwrap_or(&path).to_string_lossy()))
-                                .set_buttons(MessageButtons::YesNo)
-                                .show() == MessageDialogResult::Yes;
+                    if e.kind() == ErrorKind::PermissionDenied {
+                        let attempt_sandbox_open = MessageDialog::new()
+                            .set_level(MessageLevel::Warning)
+                            .set_description(format!("The current movie is attempting to read files stored in {}.\n\nTo allow it to do so, click Yes, and then Open to grant read access to that directory.\n\nOtherwise, click No to deny access.", path.parent().unwrap_or(&path).to_string_lossy()))
+                            .set_buttons(MessageButtons::YesNo)
+                            .show() == MessageDialogResult::Yes;
 
-                            if attempt_sandbox_open {
-                                FileDialog::new().set_directory(&path).pick_folder();
+                        if attempt_sandbox_open {
+                            FileDialog::new().set_directory(&path).pick_folder();
 
-                                return std::fs::read(&path);
-                            }
+                            return std::fs::read(&path);
                         }
                     }
+                }
 
-                    Err(e)
-                }) {
-                    Ok(body) => body,
-                    Err(e) => return create_specific_fetch_error("Can't open file", response_url.as_str(), e)
-                };
+                Err(e)
+            }) {
+                Ok(body) => body,
+                Err(e) => return create_specific_fetch_error("Can't open file", response_url.as_str(), e)
+            };
 
-                Ok(SuccessResponse {
-                    url: response_url.to_string(),
-                    body,
-                    status: 0,
-                    redirected: false,
-                })
-            }),
-            _ => Box::pin(async move {
-                let client = client.ok_or_else(|| ErrorResponse {
-                    url: processed_url.to_string(),
-                    error: Error::FetchError("Network unavailable".to_string()),
-                })?;
+            Ok(SuccessResponse {
+                url: response_url.to_string(),
+                body,
+                status: 0,
+                redirected: false,
+            })
+        })
+    }
 
-                let mut isahc_request = match request.method() {
-                    NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
-                    NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
-                };
-                let (body_data, mime) = request.body().clone().unwrap_or_default();
-                if let Some(headers) = isahc_request.headers_mut() {
-                    for (name, val) in request.headers().iter() {
-                        headers.insert(
-                            HeaderName::from_str(name).map_err(|e| ErrorResponse {
-                                url: processed_url.to_string(),
-                                error: Error::FetchError(e.to_string()),
-                            })?,
-                            HeaderValue::from_str(val).map_err(|e| ErrorResponse {
-                                url: processed_url.to_string(),
-                                error: Error::FetchError(e.to_string()),
-                            })?,
-                        );
-                    }
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, ErrorResponse> {
+        let processed_url = match self.resolve_url(request.url()) {
+            Ok(url) => url,
+            Err(e) => {
+                return async_return(create_fetch_error(request.url(), e));
+            }
+        };
+
+        if processed_url.scheme() == "file" {
+            // Local files are read all at once.
+            let response = self.fetch(request);
+            return Box::pin(async move { response.await.map(StreamingResponse::from_complete) });
+        }
+
+        let client = self.client.clone();
+
+        Box::pin(async move {
+            let client = client.ok_or_else(|| ErrorResponse {
+                url: processed_url.to_string(),
+                error: Error::FetchError("Network unavailable".to_string()),
+            })?;
+
+            let mut isahc_request = match request.method() {
+                NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
+                NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
+            };
+            let (body_data, mime) = request.body().clone().unwrap_or_default();
+            if let Some(headers) = isahc_request.headers_mut() {
+                for (name, val) in request.headers().iter() {
                     headers.insert(
-                        "Content-Type",
-                        HeaderValue::from_str(&mime).map_err(|e| ErrorResponse {
+                        HeaderName::from_str(name).map_err(|e| ErrorResponse {
+                            url: processed_url.to_string(),
+                            error: Error::FetchError(e.to_string()),
+                        })?,
+                        HeaderValue::from_str(val).map_err(|e| ErrorResponse {
                             url: processed_url.to_string(),
                             error: Error::FetchError(e.to_string()),
                         })?,
                     );
                 }
+                headers.insert(
+                    "Content-Type",
+                    HeaderValue::from_str(&mime).map_err(|e| ErrorResponse {
+                        url: processed_url.to_string(),
+                        error: Error::FetchError(e.to_string()),
+                    })?,
+                );
+            }
 
-                let body = isahc_request.body(body_data).map_err(|e| ErrorResponse {
-                    url: processed_url.to_string(),
-                    error: Error::FetchError(e.to_string()),
-                })?;
+            let body = isahc_request.body(body_data).map_err(|e| ErrorResponse {
+                url: processed_url.to_string(),
+                error: Error::FetchError(e.to_string()),
+            })?;
 
-                let mut response = client.send_async(body).await.map_err(|e| {
-                    let inner = match e.kind() {
-                        isahc::error::ErrorKind::NameResolution => {
-                            Error::InvalidDomain(processed_url.to_string())
-                        }
-                        _ => Error::FetchError(e.to_string()),
-                    };
-                    ErrorResponse {
-                        url: processed_url.to_string(),
-                        error: inner,
+            let response = client.send_async(body).await.map_err(|e| {
+                let inner = match e.kind() {
+                    isahc::error::ErrorKind::NameResolution => {
+                        Error::InvalidDomain(processed_url.to_string())
                     }
-                })?;
-
-                let url = if let Some(uri) = response.effective_uri() {
-                    uri.to_string()
-                } else {
-                    processed_url.into()
+                    _ => Error::FetchError(e.to_string()),
                 };
-
-                let status = response.status().as_u16();
-                let redirected = response.effective_uri().is_some();
-                if !response.status().is_success() {
-                    let error = Error::HttpNotOk(
-                        format!("HTTP status is not ok, got {}", response.status()),
-                        status,
-                        redirected,
-                        response.body().len().unwrap_or(0),
-                    );
-                    return Err(ErrorResponse { url, error });
+                ErrorResponse {
+                    url: processed_url.to_string(),
+                    error: inner,
                 }
+            })?;
 
-                let mut body = vec![];
-                response
-                    .copy_to(&mut body)
-                    .await
-                    .map_err(|e| ErrorResponse {
-                        url: url.clone(),
-                        error: Error::FetchError(e.to_string()),
-                    })?;
+            let url = if let Some(uri) = response.effective_uri() {
+                uri.to_string()
+            } else {
+                processed_url.into()
+            };
 
-                Ok(SuccessResponse {
-                    url,
-                    body,
+            let status = response.status().as_u16();
+            let redirected = response.effective_uri().is_some();
+            if !response.status().is_success() {
+                let error = Error::HttpNotOk(
+                    format!("HTTP status is not ok, got {}", response.status()),
                     status,
                     redirected,
-                })
-            }),
-        }
+                    response.body().len().unwrap_or(0),
+                );
+                return Err(ErrorResponse { url, error });
+            }
+
+            Ok(StreamingResponse {
+                url,
+                status,
+                redirected,
+                expected_length: response.body().len(),
+                body: Box::new(HttpResponseBody(response)),
+            })
+        })
     }
 
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
@@ -347,6 +384,13 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         url
     }
 
+    fn is_socket_approved(&self, host: &str, port: u16) -> bool {
+        // When asking, the user's answer replaces the policy file. Otherwise,
+        // fetching the policy files would ask the user about them too.
+        self.socket_allowed.contains(&format!("{host}:{port}"))
+            || self.socket_mode != SocketMode::Deny
+    }
+
     fn connect_socket(
         &mut self,
         host: String,
@@ -8,6 +8,7 @@ use std::path::{Component, Path, PathBuf};
 pub struct DiskStorageBackend {
     base_path: PathBuf,
     shared_objects_path: PathBuf,
+    quotas_path: PathBuf,
 }
 
 impl DiskStorageBackend {
@@ -16,6 +17,7 @@ impl DiskStorageBackend {
             .context("Couldn't find a valid data_local dir")?
             .join("ruffle");
         let shared_objects_path = base_path.join("SharedObjects");
+        let quotas_path = base_path.join("Quotas");
 
         // Create a base dir if one doesn't exist yet
         if !shared_objects_path.exists() {
@@ -28,6 +30,7 @@ impl DiskStorageBackend {
         Ok(DiskStorageBackend {
             base_path,
             shared_objects_path,
+            quotas_path,
         })
     }
 
@@ -40,12 +43,39 @@ impl DiskStorageBackend {
         self.shared_objects_path.join(format!("{name}.sol"))
     }
 
+    fn get_quota_path(&self, domain: &str) -> PathBuf {
+        self.quotas_path.join(domain)
+    }
+
     fn get_back_compat_shared_object_path(&self, name: &str) -> PathBuf {
         // Backwards compatibility with pre-05/09/2021:
         // Search for data in old location, without .sol extension and # prefix.
         // Remove this code eventually.
         self.base_path.join(name.replacen("/#", "/", 1))
     }
+
+    /// Collects the names of all shared objects stored below `dir`.
+    fn collect_keys(&self, dir: &Path, keys: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                self.collect_keys(&path, keys);
+            } else if path.extension().is_some_and(|ext| ext == "sol") {
+                let Ok(relative) = path.strip_prefix(&self.shared_objects_path) else {
+                    continue;
+                };
+                let name: Vec<_> = relative
+                    .with_extension("")
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect();
+                keys.push(name.join("/"));
+            }
+        }
+    }
 }
 
 impl StorageBackend for DiskStorageBackend {
@@ -110,4 +140,54 @@ impl StorageBackend for DiskStorageBackend {
         }
         let _ = fs::remove_file(path);
     }
+
+    fn get_size(&self, name: &str) -> Option<usize> {
+        let path = self.get_shared_object_path(name);
+        if !Self::is_path_allowed(&path) {
+            return None;
+        }
+        fs::metadata(path).ok().map(|md| md.len() as usize)
+    }
+
+    fn keys(&self, prefix: &str) -> Vec<String> {
+        // Only walk the directory the prefix points into, such as the one of a domain.
+        let dir = match prefix.rsplit_once('/') {
+            Some((dir, _)) => self.shared_objects_path.join(dir),
+            None => self.shared_objects_path.clone(),
+        };
+        if !Self::is_path_allowed(&dir) {
+            return Vec::new();
+        }
+        let mut keys = Vec::new();
+        self.collect_keys(&dir, &mut keys);
+        keys.retain(|name| name.starts_with(prefix));
+        keys
+    }
+
+    fn quota(&self, domain: &str) -> usize {
+        let path = self.get_quota_path(domain);
+        if !Self::is_path_allowed(&path) {
+            return 0;
+        }
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|quota| quota.trim().parse().ok())
+            .unwrap_or_else(|| self.default_quota(domain))
+    }
+
+    fn set_quota(&mut self, domain: &str, quota: usize) -> bool {
+        let path = self.get_quota_path(domain);
+        if !Self::is_path_allowed(&path) {
+            return false;
+        }
+        if let Err(r) = fs::create_dir_all(&self.quotas_path) {
+            tracing::warn!("Unable to create quota dir {}", r);
+            return false;
+        }
+        if let Err(r) = fs::write(path, quota.to_string()) {
+            tracing::warn!("Unable to save quota {:?}", r);
+            return false;
+        }
+        true
+    }
 }
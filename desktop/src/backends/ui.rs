@@ -1,18 +1,24 @@
+use crate::custom_event::RuffleEvent;
+use crate::gui::{text, text_with_args};
 use anyhow::{Context, Error};
 use arboard::Clipboard;
 use chrono::{DateTime, Utc};
+use fluent_templates::fluent_bundle::FluentValue;
 use rfd::{
-    AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel,
+    AsyncFileDialog, AsyncMessageDialog, FileHandle, MessageButtons, MessageDialog,
+    MessageDialogResult, MessageLevel,
 };
 use ruffle_core::backend::navigator::OpenURLMode;
 use ruffle_core::backend::ui::{
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    FullscreenError, LanguageIdentifier, MouseCursor, StorageQuotaFuture, UiBackend, US_ENGLISH,
 };
+use std::collections::HashMap;
 use std::rc::Rc;
 use sys_locale::get_locale;
 use tracing::error;
 use url::Url;
+use winit::event_loop::EventLoopProxy;
 use winit::window::{Fullscreen, Window};
 
 pub struct DesktopFileDialogResult {
@@ -115,6 +121,7 @@ impl FileDialogResult for DesktopFileDialogResult {
 
 pub struct DesktopUiBackend {
     window: Rc<Window>,
+    event_loop: EventLoopProxy<RuffleEvent>,
     cursor_visible: bool,
     clipboard: Clipboard,
     language: LanguageIdentifier,
@@ -125,13 +132,18 @@ pub struct DesktopUiBackend {
 }
 
 impl DesktopUiBackend {
-    pub fn new(window: Rc<Window>, open_url_mode: OpenURLMode) -> Result<Self, Error> {
+    pub fn new(
+        window: Rc<Window>,
+        event_loop: EventLoopProxy<RuffleEvent>,
+        open_url_mode: OpenURLMode,
+    ) -> Result<Self, Error> {
         let preferred_language = get_locale();
         let language = preferred_language
             .and_then(|l| l.parse().ok())
             .unwrap_or_else(|| US_ENGLISH.clone());
         Ok(Self {
             window,
+            event_loop,
             cursor_visible: true,
             clipboard: Clipboard::new().context("Couldn't get platform clipboard")?,
             language,
@@ -324,4 +336,31 @@ impl UiBackend for DesktopUiBackend {
     fn close_file_dialog(&mut self) {
         self.dialog_open = false;
     }
+
+    fn display_storage_quota_prompt(
+        &mut self,
+        domain: &str,
+        requested: usize,
+    ) -> Option<StorageQuotaFuture> {
+        let args = HashMap::from([
+            ("domain", FluentValue::from(domain)),
+            ("kilobytes", FluentValue::from(requested.div_ceil(1024))),
+        ]);
+        let message = text_with_args(&self.language, "storage-quota-prompt", &args);
+        let dialog = AsyncMessageDialog::new()
+            .set_title(text(&self.language, "storage-quota-prompt-title"))
+            .set_level(MessageLevel::Info)
+            .set_description(message)
+            .set_buttons(MessageButtons::YesNo)
+            .show();
+        Some(Box::pin(async move {
+            Ok(dialog.await == MessageDialogResult::Yes)
+        }))
+    }
+
+    fn display_storage_settings(&mut self, domain: &str) {
+        let _ = self
+            .event_loop
+            .send_event(RuffleEvent::ShowStorageSettings(domain.to_string()));
+    }
 }
@@ -3,10 +3,11 @@ use anyhow::Error;
 use clap::Parser;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
 use ruffle_core::config::Letterbox;
+use ruffle_core::profiler::ProfileFormat;
 use ruffle_core::{LoadBehavior, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use url::Url;
 
 #[derive(Parser, Debug)]
@@ -136,6 +137,31 @@ pub struct Opt {
     /// Hides the menu bar (the bar at the top of the window).
     #[clap(long)]
     pub no_gui: bool,
+
+    /// Records all input sent to the movie into a FlashTAS input file at the given path,
+    /// which is saved when the movie is closed.
+    /// The recording can be replayed as the `input.json` of a test.
+    #[clap(long)]
+    pub record_input: Option<PathBuf>,
+
+    /// Profiles all ActionScript executed by the movie, and writes the profile to the given path
+    /// when the movie is closed.
+    #[clap(long)]
+    pub profile: Option<PathBuf>,
+
+    /// The format to write the profile in.
+    /// Chrome traces can be opened in Perfetto or `chrome://tracing`, and speedscope
+    /// profiles at https://www.speedscope.app.
+    #[clap(long, default_value = "chrome-trace")]
+    pub profile_format: ProfileFormat,
+
+    /// Records all network traffic of the movie into an HTTP archive (HAR) at the given path.
+    #[clap(long, conflicts_with = "replay_network")]
+    pub record_network: Option<PathBuf>,
+
+    /// Replays the network traffic recorded in the given HTTP archive, instead of using the network.
+    #[clap(long)]
+    pub replay_network: Option<PathBuf>,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
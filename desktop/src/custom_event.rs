@@ -24,4 +24,13 @@ pub enum RuffleEvent {
 
     /// The user selected an item in the right-click context menu.
     ContextMenuItemClicked(usize),
+
+    /// The user requested to start recording input to the current SWF.
+    StartInputRecording,
+
+    /// The user requested to stop recording input and save the recording.
+    StopInputRecording,
+
+    /// The movie requested to show the local storage settings of the given domain.
+    ShowStorageSettings(String),
 }
@@ -15,6 +15,7 @@ use egui::*;
 use fluent_templates::fluent_bundle::FluentValue;
 use fluent_templates::{static_loader, Loader};
 use rfd::FileDialog;
+use ruffle_core::backend::storage::{storage_domain, QUOTA_STEPS};
 use ruffle_core::backend::ui::US_ENGLISH;
 use ruffle_core::debug_ui::Message as DebugMessage;
 use ruffle_core::Player;
@@ -41,7 +42,6 @@ pub fn text<'a>(locale: &LanguageIdentifier, id: &'a str) -> Cow<'a, str> {
     })
 }
 
-#[allow(dead_code)]
 pub fn text_with_args<'a, T: AsRef<str>>(
     locale: &LanguageIdentifier,
     id: &'a str,
@@ -67,6 +67,8 @@ pub struct RuffleGui {
     is_about_visible: bool,
     is_volume_visible: bool,
     volume_controls: VolumeControls,
+    /// The local storage settings that are shown, if any.
+    storage_settings: Option<StorageSettings>,
     is_open_dialog_visible: bool,
     context_menu: Vec<ruffle_core::ContextMenuItem>,
     open_dialog: OpenDialog,
@@ -74,6 +76,7 @@ pub struct RuffleGui {
     default_player_options: PlayerOptions,
     currently_opened: Option<(Url, PlayerOptions)>,
     was_suspended_before_debug: bool,
+    is_recording_input: bool,
 }
 
 impl RuffleGui {
@@ -94,8 +97,10 @@ impl RuffleGui {
             is_about_visible: false,
             is_volume_visible: false,
             volume_controls: VolumeControls::new(false, default_player_options.volume * 100.0),
+            storage_settings: None,
             is_open_dialog_visible: false,
             was_suspended_before_debug: false,
+            is_recording_input: false,
 
             context_menu: vec![],
             open_dialog: OpenDialog::new(
@@ -154,9 +159,11 @@ impl RuffleGui {
                 });
             }
 
+            self.storage_settings_window(egui_ctx, player);
             self.volume_window(egui_ctx, Some(player));
         } else {
             self.volume_window(egui_ctx, None);
+            self.storage_settings = None;
         }
 
         if !self.context_menu.is_empty() {
@@ -270,10 +277,21 @@ impl RuffleGui {
                                 player.set_is_playing(!player.is_playing());
                             }
                         }
+                        if Button::new(text(&self.locale, if self.is_recording_input { "controls-menu-stop-recording-input" } else { "controls-menu-record-input" })).ui(ui).clicked() {
+                            ui.close_menu();
+                            let _ = self.event_loop.send_event(if self.is_recording_input {
+                                RuffleEvent::StopInputRecording
+                            } else {
+                                RuffleEvent::StartInputRecording
+                            });
+                        }
                     });
                     if Button::new(text(&self.locale, "controls-menu-volume")).ui(ui).clicked() {
                         self.show_volume_screen(ui);
                     }
+                    if ui.add_enabled(player.is_some(), Button::new(text(&self.locale, "controls-menu-storage"))).clicked() {
+                        self.show_storage_settings_screen(ui);
+                    }
                 });
                 menu::menu_button(ui, text(&self.locale, "debug-menu"), |ui| {
                     ui.add_enabled_ui(player.is_some(), |ui| {
@@ -301,6 +319,18 @@ impl RuffleGui {
                                 player.debug_ui().queue_message(DebugMessage::SearchForDisplayObject);
                             }
                         }
+                        if Button::new(text(&self.locale, "debug-menu-open-avm1-debugger")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowAvm1Debugger);
+                            }
+                        }
+                        if Button::new(text(&self.locale, "debug-menu-open-avm2-debugger")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowAvm2Debugger);
+                            }
+                        }
                     });
                 });
                 menu::menu_button(ui, text(&self.locale, "help-menu"), |ui| {
@@ -445,6 +475,72 @@ impl RuffleGui {
             });
     }
 
+    /// Renders the local storage settings window.
+    fn storage_settings_window(&mut self, egui_ctx: &egui::Context, player: &mut Player) {
+        let Some(settings) = &mut self.storage_settings else {
+            return;
+        };
+        let domain = &settings.domain;
+        let prefix = format!("{domain}/");
+        let storage = player.storage_mut();
+        let (usage, mut quota) = *settings
+            .usage_and_quota
+            .get_or_insert_with(|| (storage.usage(&prefix), storage.quota(domain)));
+
+        let mut is_open = true;
+        egui::Window::new(text(&self.locale, "storage-settings"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .open(&mut is_open)
+            .show(egui_ctx, |ui| {
+                Grid::new("storage_settings_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(text(&self.locale, "storage-settings-domain"));
+                        ui.label(domain.as_str());
+                        ui.end_row();
+
+                        ui.label(text(&self.locale, "storage-settings-usage"));
+                        ui.label(format_size(usage));
+                        ui.end_row();
+
+                        ui.label(text(&self.locale, "storage-settings-quota"));
+                        let old_quota = quota;
+                        ComboBox::from_id_source("storage_settings_quota")
+                            .selected_text(format_quota(&self.locale, quota))
+                            .show_ui(ui, |ui| {
+                                for step in QUOTA_STEPS {
+                                    ui.selectable_value(
+                                        &mut quota,
+                                        step,
+                                        format_quota(&self.locale, step),
+                                    );
+                                }
+                            });
+                        if quota != old_quota {
+                            storage.set_quota(domain, quota);
+                            settings.usage_and_quota = None;
+                        }
+                        ui.end_row();
+                    });
+
+                if ui
+                    .button(text(&self.locale, "storage-settings-delete-all"))
+                    .clicked()
+                {
+                    for name in storage.keys(&prefix) {
+                        storage.remove_key(&name);
+                    }
+                    settings.usage_and_quota = None;
+                }
+            });
+
+        if !is_open {
+            self.storage_settings = None;
+        }
+    }
+
     /// Renders the right-click context menu.
     fn context_menu(&mut self, egui_ctx: &egui::Context) {
         let mut item_clicked = false;
@@ -541,6 +637,47 @@ impl RuffleGui {
         self.is_volume_visible = true;
         ui.close_menu();
     }
+
+    fn show_storage_settings_screen(&mut self, ui: &mut egui::Ui) {
+        if let Some((movie_url, opts)) = &self.currently_opened {
+            let url = opts.spoof_url.as_ref().unwrap_or(movie_url);
+            self.show_storage_settings(storage_domain(url).to_string());
+        }
+        ui.close_menu();
+    }
+
+    pub fn show_storage_settings(&mut self, domain: String) {
+        self.storage_settings = Some(StorageSettings {
+            domain,
+            usage_and_quota: None,
+        });
+    }
+}
+
+/// The local storage settings of a domain, as shown in the settings window.
+struct StorageSettings {
+    domain: String,
+
+    /// The usage and quota of the domain, read once instead of on every frame,
+    /// as reading them may walk the disk.
+    usage_and_quota: Option<(usize, usize)>,
+}
+
+fn format_quota(locale: &LanguageIdentifier, quota: usize) -> Cow<'static, str> {
+    match quota {
+        0 => text(locale, "storage-settings-quota-none"),
+        usize::MAX => text(locale, "storage-settings-quota-unlimited"),
+        quota => format_size(quota).into(),
+    }
+}
+
+/// Formats an amount of bytes for display, such as `100 KB`.
+fn format_size(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
 }
 
 /// The volume controls of the Ruffle GUI.
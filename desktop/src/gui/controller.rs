@@ -322,6 +322,14 @@ impl GuiController {
     pub fn show_open_dialog(&mut self) {
         self.gui.open_file_advanced()
     }
+
+    pub fn show_storage_settings(&mut self, domain: String) {
+        self.gui.show_storage_settings(domain)
+    }
+
+    pub fn set_recording_input(&mut self, is_recording: bool) {
+        self.gui.is_recording_input = is_recording;
+    }
 }
 
 // try to load known unicode supporting fonts to draw cjk characters in egui
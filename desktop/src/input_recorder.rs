@@ -0,0 +1,193 @@
+//! Recording of player input into the FlashTAS input format
+
+use ruffle_core::events::{MouseButton, MouseWheelDelta, TextControlCode};
+use ruffle_core::{Player, PlayerEvent};
+use ruffle_input_format::{
+    AutomatedEvent, MouseButton as InputMouseButton, MousePosition,
+    MouseWheelDelta as InputMouseWheelDelta, TextControlCode as InputTextControlCode,
+};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// Records the events sent to a player, so that they can be replayed by the test framework.
+pub struct InputRecorder {
+    /// Where the recording will be saved to, if known in advance.
+    path: Option<PathBuf>,
+
+    events: Vec<AutomatedEvent>,
+
+    /// The number of frames waited for by the `Wait` events recorded so far.
+    waits: u64,
+}
+
+impl InputRecorder {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            events: vec![],
+            waits: 0,
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Records an event that is about to be handled by the player.
+    ///
+    /// Mouse events for unknown buttons can't be represented, and are skipped.
+    pub fn record(&mut self, player: &mut Player, event: &PlayerEvent) {
+        let Some(event) = Self::convert_event(player, event) else {
+            return;
+        };
+
+        // The test framework injects each frame's input after running that frame,
+        // so events received before the second frame runs are part of the first group.
+        let frame = player.run_frame_count().saturating_sub(1);
+        self.push(frame, event);
+    }
+
+    /// Appends an event received during `frame`, preceded by a wait for the frames since the last one.
+    fn push(&mut self, frame: u64, event: AutomatedEvent) {
+        if self.waits < frame {
+            let frames = (frame - self.waits).min(u32::MAX as u64);
+            self.events.push(AutomatedEvent::Wait {
+                frames: (frames > 1).then_some(frames as u32),
+                ms: None,
+            });
+            self.waits += frames;
+        }
+
+        self.events.push(event);
+    }
+
+    fn convert_event(player: &mut Player, event: &PlayerEvent) -> Option<AutomatedEvent> {
+        let mut position = |x, y| {
+            let (x, y) = player.viewport_to_stage_position(x, y);
+            MousePosition(x, y)
+        };
+
+        Some(match *event {
+            PlayerEvent::MouseMove { x, y } => AutomatedEvent::MouseMove {
+                pos: position(x, y),
+            },
+            PlayerEvent::MouseDown { x, y, button } => AutomatedEvent::MouseDown {
+                pos: position(x, y),
+                btn: convert_mouse_button(button)?,
+            },
+            PlayerEvent::MouseUp { x, y, button } => AutomatedEvent::MouseUp {
+                pos: position(x, y),
+                btn: convert_mouse_button(button)?,
+            },
+            PlayerEvent::MouseLeave => AutomatedEvent::MouseLeave,
+            PlayerEvent::MouseWheel { delta } => AutomatedEvent::MouseWheel {
+                delta: match delta {
+                    MouseWheelDelta::Lines(lines) => InputMouseWheelDelta::Lines(lines),
+                    MouseWheelDelta::Pixels(pixels) => InputMouseWheelDelta::Pixels(pixels),
+                },
+            },
+            PlayerEvent::KeyDown { key_code, key_char } => AutomatedEvent::KeyDown {
+                key_code: key_code as u8,
+                key_char,
+            },
+            PlayerEvent::KeyUp { key_code, key_char } => AutomatedEvent::KeyUp {
+                key_code: key_code as u8,
+                key_char,
+            },
+            PlayerEvent::TextInput { codepoint } => AutomatedEvent::TextInput { codepoint },
+            PlayerEvent::TextControl { code } => AutomatedEvent::TextControl {
+                code: convert_text_control_code(code),
+            },
+        })
+    }
+
+    /// Saves the recording as JSON.
+    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &self.events)?;
+        Ok(())
+    }
+}
+
+fn convert_mouse_button(button: MouseButton) -> Option<InputMouseButton> {
+    match button {
+        MouseButton::Left => Some(InputMouseButton::Left),
+        MouseButton::Middle => Some(InputMouseButton::Middle),
+        MouseButton::Right => Some(InputMouseButton::Right),
+        MouseButton::Unknown => None,
+    }
+}
+
+fn convert_text_control_code(code: TextControlCode) -> InputTextControlCode {
+    match code {
+        TextControlCode::MoveLeft => InputTextControlCode::MoveLeft,
+        TextControlCode::MoveRight => InputTextControlCode::MoveRight,
+        TextControlCode::SelectLeft => InputTextControlCode::SelectLeft,
+        TextControlCode::SelectRight => InputTextControlCode::SelectRight,
+        TextControlCode::SelectAll => InputTextControlCode::SelectAll,
+        TextControlCode::Copy => InputTextControlCode::Copy,
+        TextControlCode::Paste => InputTextControlCode::Paste,
+        TextControlCode::Cut => InputTextControlCode::Cut,
+        TextControlCode::Backspace => InputTextControlCode::Backspace,
+        TextControlCode::Enter => InputTextControlCode::Enter,
+        TextControlCode::Delete => InputTextControlCode::Delete,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_input(codepoint: char) -> AutomatedEvent {
+        AutomatedEvent::TextInput { codepoint }
+    }
+
+    #[test]
+    fn waits_between_frames() {
+        let mut recorder = InputRecorder::new(None);
+        recorder.push(0, text_input('a'));
+        recorder.push(0, text_input('b'));
+        recorder.push(1, text_input('c'));
+        recorder.push(4, text_input('d'));
+
+        assert_eq!(
+            serde_json::to_value(&recorder.events).unwrap(),
+            serde_json::json!([
+                { "type": "TextInput", "codepoint": "a" },
+                { "type": "TextInput", "codepoint": "b" },
+                { "type": "Wait" },
+                { "type": "TextInput", "codepoint": "c" },
+                { "type": "Wait", "frames": 3 },
+                { "type": "TextInput", "codepoint": "d" },
+            ])
+        );
+    }
+
+    #[test]
+    fn text_control_codes_round_trip() {
+        // Every code must be recorded as the one that the test framework replays it as.
+        for (code, name) in [
+            (TextControlCode::MoveLeft, "MoveLeft"),
+            (TextControlCode::MoveRight, "MoveRight"),
+            (TextControlCode::SelectAll, "SelectAll"),
+            (TextControlCode::Backspace, "Backspace"),
+            (TextControlCode::Delete, "Delete"),
+        ] {
+            let event = AutomatedEvent::TextControl {
+                code: convert_text_control_code(code),
+            };
+            let json = serde_json::to_value(&event).unwrap();
+            assert_eq!(json["code"], name);
+        }
+    }
+
+    #[test]
+    fn unknown_mouse_buttons_are_skipped() {
+        assert!(convert_mouse_button(MouseButton::Unknown).is_none());
+        assert!(matches!(
+            convert_mouse_button(MouseButton::Right),
+            Some(InputMouseButton::Right)
+        ));
+    }
+}
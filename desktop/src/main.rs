@@ -12,6 +12,7 @@ mod cli;
 mod custom_event;
 mod executor;
 mod gui;
+mod input_recorder;
 mod player;
 mod task;
 mod time_demo;
@@ -1,21 +1,28 @@
 use crate::backends::{
-    CpalAudioBackend, DesktopExternalInterfaceProvider, DesktopUiBackend, DiskStorageBackend,
-    ExternalNavigatorBackend,
+    ArchiveNavigatorBackend, CpalAudioBackend, DesktopExternalInterfaceProvider,
+    DesktopLocalConnectionBackend, DesktopUiBackend, DiskStorageBackend, ExternalNavigatorBackend,
+    HttpArchive, NetworkRecording,
 };
 use crate::cli::Opt;
 use crate::custom_event::RuffleEvent;
 use crate::executor::WinitAsyncExecutor;
 use crate::gui::MovieView;
+use crate::input_recorder::InputRecorder;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
+use rfd::FileDialog;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
 use ruffle_core::config::Letterbox;
-use ruffle_core::{LoadBehavior, Player, PlayerBuilder, PlayerEvent, StageAlign, StageScaleMode};
+use ruffle_core::profiler::ProfileFormat;
+use ruffle_core::{
+    LoadBehavior, Player, PlayerBuilder, PlayerEvent, SandboxType, StageAlign, StageScaleMode,
+};
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
 use ruffle_render_wgpu::descriptors::Descriptors;
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
@@ -48,6 +55,11 @@ pub struct PlayerOptions {
     pub frame_rate: Option<f64>,
     pub open_url_mode: OpenURLMode,
     pub dummy_external_interface: bool,
+    pub record_input: Option<PathBuf>,
+    pub profile: Option<PathBuf>,
+    pub profile_format: ProfileFormat,
+    pub record_network: Option<PathBuf>,
+    pub replay_network: Option<PathBuf>,
 }
 
 impl From<&Opt> for PlayerOptions {
@@ -72,6 +84,11 @@ impl From<&Opt> for PlayerOptions {
             frame_rate: value.frame_rate,
             open_url_mode: value.open_url_mode,
             dummy_external_interface: value.dummy_external_interface,
+            record_input: value.record_input.clone(),
+            profile: value.profile.clone(),
+            profile_format: value.profile_format,
+            record_network: value.record_network.clone(),
+            replay_network: value.replay_network.clone(),
             socket_allowed: HashSet::from_iter(value.socket_allow.iter().cloned()),
             tcp_connections: value.tcp_connections,
         }
@@ -83,6 +100,7 @@ impl From<&Opt> for PlayerOptions {
 struct ActivePlayer {
     player: Arc<Mutex<Player>>,
     executor: Arc<Mutex<WinitAsyncExecutor>>,
+    network_recording: Option<NetworkRecording>,
 }
 
 impl ActivePlayer {
@@ -117,6 +135,23 @@ impl ActivePlayer {
             opt.tcp_connections,
         );
 
+        let mut network_recording = None;
+        if let Some(path) = &opt.replay_network {
+            // The archive was checked when starting, and we mustn't fall back to the live network.
+            let archive = HttpArchive::load(path)
+                .unwrap_or_else(|e| panic!("Couldn't load network archive {path:?}: {e}"));
+            builder = builder.with_navigator(ArchiveNavigatorBackend::replay(navigator, archive));
+        } else if let Some(path) = &opt.record_network {
+            let recording = NetworkRecording::new(path.clone());
+            builder = builder.with_navigator(ArchiveNavigatorBackend::record(
+                navigator,
+                recording.clone(),
+            ));
+            network_recording = Some(recording);
+        } else {
+            builder = builder.with_navigator(navigator);
+        }
+
         if cfg!(feature = "software_video") {
             builder =
                 builder.with_video(ruffle_video_software::backend::SoftwareVideoBackend::new());
@@ -139,12 +174,23 @@ impl ActivePlayer {
             Duration::from_secs_f64(opt.max_execution_duration)
         };
 
+        match DesktopLocalConnectionBackend::new() {
+            Ok(backend) => builder = builder.with_local_connection_backend(backend),
+            Err(e) => tracing::error!("Unable to create LocalConnection backend: {e}"),
+        }
+
+        // Local movies are trusted, but remote ones need policy files to access other domains.
+        let sandbox_type = if opt.spoof_url.as_ref().unwrap_or(movie_url).scheme() == "file" {
+            SandboxType::LocalTrusted
+        } else {
+            SandboxType::Remote
+        };
+
         builder = builder
-            .with_navigator(navigator)
             .with_renderer(renderer)
             .with_storage(DiskStorageBackend::new().expect("Couldn't create storage backend"))
             .with_ui(
-                DesktopUiBackend::new(window.clone(), opt.open_url_mode)
+                DesktopUiBackend::new(window.clone(), event_loop.clone(), opt.open_url_mode)
                     .expect("Couldn't create ui backend"),
             )
             .with_autoplay(true)
@@ -158,6 +204,8 @@ impl ActivePlayer {
             .with_spoofed_url(opt.spoof_url.clone().map(|url| url.to_string()))
             .with_page_url(opt.spoof_url.clone().map(|url| url.to_string()))
             .with_player_version(Some(opt.player_version))
+            .with_sandbox_type(sandbox_type)
+            .with_policy_files(true)
             .with_frame_rate(opt.frame_rate);
         let player = builder.build();
 
@@ -177,6 +225,9 @@ impl ActivePlayer {
 
         {
             let mut player_lock = player.lock().expect("Player lock must be available");
+            if opt.profile.is_some() {
+                player_lock.start_profiling();
+            }
             CALLSTACK.with(|callstack| {
                 *callstack.borrow_mut() = Some(player_lock.callstack());
             });
@@ -187,7 +238,11 @@ impl ActivePlayer {
             );
         }
 
-        Self { player, executor }
+        Self {
+            player,
+            executor,
+            network_recording,
+        }
     }
 }
 
@@ -195,6 +250,9 @@ impl ActivePlayer {
 /// responsible for either creating, destroying or communicating with that player.
 pub struct PlayerController {
     player: Option<ActivePlayer>,
+    input_recorder: Option<InputRecorder>,
+    /// Where to save the profile of the current player, if it's being profiled.
+    profile_output: Option<(PathBuf, ProfileFormat)>,
     event_loop: EventLoopProxy<RuffleEvent>,
     window: Rc<Window>,
     descriptors: Arc<Descriptors>,
@@ -208,6 +266,8 @@ impl PlayerController {
     ) -> Self {
         Self {
             player: None,
+            input_recorder: None,
+            profile_output: None,
             event_loop,
             window,
             descriptors,
@@ -215,6 +275,9 @@ impl PlayerController {
     }
 
     pub fn create(&mut self, opt: &PlayerOptions, movie_url: &Url, movie_view: MovieView) {
+        self.stop_input_recording();
+        self.save_profile();
+        self.save_network_recording();
         self.player = Some(ActivePlayer::new(
             opt,
             self.event_loop.clone(),
@@ -223,12 +286,74 @@ impl PlayerController {
             self.descriptors.clone(),
             movie_view,
         ));
+        if let Some(path) = &opt.record_input {
+            self.input_recorder = Some(InputRecorder::new(Some(path.clone())));
+        }
+        self.profile_output = opt.profile.clone().map(|path| (path, opt.profile_format));
     }
 
     pub fn destroy(&mut self) {
+        self.stop_input_recording();
+        self.save_profile();
+        self.save_network_recording();
         self.player = None;
     }
 
+    pub fn is_recording_input(&self) -> bool {
+        self.input_recorder.is_some()
+    }
+
+    /// Starts recording all input sent to the current player.
+    /// The user will be asked where to save the recording once it's stopped.
+    pub fn start_input_recording(&mut self) {
+        if self.player.is_some() && self.input_recorder.is_none() {
+            self.input_recorder = Some(InputRecorder::new(None));
+        }
+    }
+
+    /// Stops recording input, and saves the recording.
+    pub fn stop_input_recording(&mut self) {
+        let Some(recorder) = self.input_recorder.take() else {
+            return;
+        };
+
+        let path = recorder.path().map(PathBuf::from).or_else(|| {
+            FileDialog::new()
+                .add_filter("FlashTAS input", &["json"])
+                .set_file_name("input.json")
+                .save_file()
+        });
+        if let Some(path) = path {
+            if let Err(e) = recorder.save(&path) {
+                tracing::error!("Couldn't save input recording to {path:?}: {e}");
+            }
+        }
+    }
+
+    /// Stops profiling the current player, and saves the profile to the path given on the command line.
+    pub fn save_profile(&mut self) {
+        let Some((path, format)) = self.profile_output.take() else {
+            return;
+        };
+        let Some(profile) = self.get().and_then(|mut player| player.stop_profiling()) else {
+            return;
+        };
+        if let Err(e) = std::fs::write(&path, profile.export(format)) {
+            tracing::error!("Couldn't save profile to {path:?}: {e}");
+        }
+    }
+
+    /// Saves the network traffic recorded by the current player, if it's being recorded.
+    pub fn save_network_recording(&self) {
+        if let Some(recording) = self
+            .player
+            .as_ref()
+            .and_then(|player| player.network_recording.as_ref())
+        {
+            recording.save();
+        }
+    }
+
     pub fn get(&self) -> Option<MutexGuard<Player>> {
         match &self.player {
             None => None,
@@ -242,11 +367,19 @@ impl PlayerController {
         }
     }
 
-    pub fn handle_event(&self, event: PlayerEvent) {
-        if let Some(mut player) = self.get() {
-            if player.is_playing() {
-                player.handle_event(event);
+    pub fn handle_event(&mut self, event: PlayerEvent) {
+        let Some(active_player) = &self.player else {
+            return;
+        };
+        let mut player = active_player
+            .player
+            .try_lock()
+            .expect("Player lock must be available");
+        if player.is_playing() {
+            if let Some(recorder) = &mut self.input_recorder {
+                recorder.record(&mut player, &event);
             }
+            player.handle_event(event);
         }
     }
 
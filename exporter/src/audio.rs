@@ -0,0 +1,172 @@
+use anyhow::Result;
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
+    SoundStreamInfo, SoundTransform,
+};
+use ruffle_core::impl_audio_mixer_backend;
+use std::io::{Seek, SeekFrom, Write};
+
+/// An audio backend that mixes audio in lockstep with the frames being exported,
+/// instead of playing it back in real time.
+///
+/// The exported video has one image per frame at the frame rate of the SWF
+/// header, so one frame's worth of audio is mixed at that rate too. Changes of
+/// the frame rate at runtime are ignored, as they don't change the rate of the
+/// video either.
+pub struct ExportAudioBackend {
+    mixer: AudioMixer,
+    frame_rate: f64,
+
+    /// The number of frames ticked so far.
+    frames_ticked: u64,
+
+    /// Interleaved stereo samples that were mixed, but not yet taken.
+    buffer: Vec<i16>,
+}
+
+impl ExportAudioBackend {
+    pub const NUM_CHANNELS: u8 = 2;
+    pub const SAMPLE_RATE: u32 = 44100;
+
+    pub fn new(frame_rate: f64) -> Self {
+        Self {
+            mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
+            frame_rate,
+            frames_ticked: 0,
+            buffer: vec![],
+        }
+    }
+
+    /// The number of sample frames that should have been mixed after `frames` frames.
+    ///
+    /// This is computed from the total rather than per frame, so that rounding
+    /// never accumulates and the audio stays in sync with the video.
+    fn sample_frames_at(&self, frames: u64) -> u64 {
+        (frames as f64 * Self::SAMPLE_RATE as f64 / self.frame_rate).round() as u64
+    }
+
+    /// Takes all interleaved stereo samples mixed so far.
+    pub fn take_samples(&mut self) -> Vec<i16> {
+        std::mem::take(&mut self.buffer)
+    }
+}
+
+impl AudioBackend for ExportAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+
+    fn tick(&mut self) {
+        let start = self.sample_frames_at(self.frames_ticked);
+        self.frames_ticked += 1;
+        let end = self.sample_frames_at(self.frames_ticked);
+
+        let len = self.buffer.len();
+        let num_samples = (end - start) as usize * Self::NUM_CHANNELS as usize;
+        self.buffer.resize(len + num_samples, 0);
+        self.mixer.mix::<i16>(&mut self.buffer[len..]);
+    }
+}
+
+/// Writes 16-bit stereo PCM audio to a WAV file as it is mixed.
+pub struct WavWriter<W: Write + Seek> {
+    writer: W,
+    data_len: u32,
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    const HEADER_LEN: u32 = 44;
+
+    pub fn new(mut writer: W) -> Result<Self> {
+        // The chunk sizes are filled in by `finish`, once the length of the data is known.
+        Self::write_header(&mut writer, 0)?;
+        Ok(Self {
+            writer,
+            data_len: 0,
+        })
+    }
+
+    fn write_header(writer: &mut W, data_len: u32) -> Result<()> {
+        let num_channels = ExportAudioBackend::NUM_CHANNELS as u16;
+        let sample_rate = ExportAudioBackend::SAMPLE_RATE;
+        let block_align = num_channels * 2;
+
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(Self::HEADER_LEN - 8 + data_len).to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+        writer.write_all(b"fmt ")?;
+        writer.write_all(&16u32.to_le_bytes())?;
+        // PCM
+        writer.write_all(&1u16.to_le_bytes())?;
+        writer.write_all(&num_channels.to_le_bytes())?;
+        writer.write_all(&sample_rate.to_le_bytes())?;
+        writer.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+        writer.write_all(&block_align.to_le_bytes())?;
+        writer.write_all(&16u16.to_le_bytes())?;
+        writer.write_all(b"data")?;
+        writer.write_all(&data_len.to_le_bytes())?;
+        Ok(())
+    }
+
+    pub fn write_samples(&mut self, samples: &[i16]) -> Result<()> {
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        self.writer.write_all(&bytes)?;
+        self.data_len += bytes.len() as u32;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<()> {
+        self.writer.seek(SeekFrom::Start(0))?;
+        Self::write_header(&mut self.writer, self.data_len)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn wav_header() {
+        let mut file = Cursor::new(Vec::new());
+        let mut writer = WavWriter::new(&mut file).unwrap();
+        writer.write_samples(&[1, -1, 256, -256]).unwrap();
+        writer.finish().unwrap();
+
+        let bytes = file.into_inner();
+        assert_eq!(bytes.len(), 44 + 8);
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32_at(4), 36 + 8);
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(u32_at(16), 16);
+        assert_eq!(u16_at(20), 1);
+        assert_eq!(u16_at(22), 2);
+        assert_eq!(u32_at(24), 44100);
+        assert_eq!(u32_at(28), 44100 * 4);
+        assert_eq!(u16_at(32), 4);
+        assert_eq!(u16_at(34), 16);
+        assert_eq!(&bytes[36..40], b"data");
+        assert_eq!(u32_at(40), 8);
+        assert_eq!(&bytes[44..], &[1, 0, 0xff, 0xff, 0, 1, 0, 0xff]);
+    }
+
+    #[test]
+    fn samples_follow_header_frame_rate() {
+        let mut backend = ExportAudioBackend::new(11.0);
+        for _ in 0..11 {
+            backend.tick();
+        }
+        // Changing the frame rate at runtime doesn't change the rate of the video.
+        backend.set_frame_rate(30.0);
+        for _ in 0..11 {
+            backend.tick();
+        }
+        // Two seconds of stereo audio, without drift from rounding 44100 / 11 every frame.
+        assert_eq!(backend.take_samples().len(), 2 * 44100 * 2);
+    }
+}
@@ -1,3 +1,8 @@
+mod audio;
+mod video;
+
+use crate::audio::{ExportAudioBackend, WavWriter};
+use crate::video::{VideoFormat, VideoWriter};
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use image::RgbaImage;
@@ -5,17 +10,18 @@ use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::PlayerBuilder;
+use ruffle_core::{Player, PlayerBuilder, ViewportDimensions};
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
-use std::fs::create_dir_all;
-use std::io::{self, Write};
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufWriter, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use walkdir::{DirEntry, WalkDir};
 
 #[derive(Parser, Debug, Copy, Clone)]
@@ -81,24 +87,86 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Render on the CPU instead of using a graphics device.
+    /// This is much slower, but works on machines without a GPU.
+    #[clap(long, action)]
+    software: bool,
+
+    /// Export a video of a single swf instead of separate screenshots.
+    /// Audio is written next to it as a WAV file, unless `--no-audio` is given.
+    #[clap(long, value_enum)]
+    video: Option<VideoFormat>,
+
+    /// Number of seconds to export, instead of a number of frames.
+    /// Only used when exporting a video, which always has the frame rate of the SWF header.
+    #[clap(long)]
+    duration: Option<f64>,
+
+    /// Don't write audio when exporting a video.
+    #[clap(long, action)]
+    no_audio: bool,
 }
 
-/// Captures a screenshot. The resulting image uses straight alpha
-fn take_screenshot(
-    descriptors: Arc<Descriptors>,
-    swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
-    progress: &Option<ProgressBar>,
-    size: SizeOpt,
-    skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
-    let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+/// The device used to render frames.
+#[derive(Clone)]
+enum RenderDevice {
+    Wgpu(Arc<Descriptors>),
+    Software,
+}
 
-    if movie.is_action_script_3() && skip_unsupported {
-        return Err(anyhow!("Skipping unsupported movie"));
+impl RenderDevice {
+    fn create_player(
+        &self,
+        movie: SwfMovie,
+        width: u32,
+        height: u32,
+        scale: f64,
+        capture_audio: bool,
+    ) -> Result<Arc<Mutex<Player>>> {
+        let builder = match self {
+            RenderDevice::Wgpu(descriptors) => {
+                let target = TextureTarget::new(&descriptors.device, (width, height))
+                    .map_err(|e| anyhow!(e.to_string()))?;
+                PlayerBuilder::new().with_renderer(
+                    WgpuRenderBackend::new(descriptors.clone(), target)
+                        .map_err(|e| anyhow!(e.to_string()))?,
+                )
+            }
+            RenderDevice::Software => {
+                PlayerBuilder::new().with_renderer(SoftwareRenderBackend::new(ViewportDimensions {
+                    width,
+                    height,
+                    scale_factor: scale,
+                }))
+            }
+        };
+        let builder = if capture_audio {
+            builder.with_audio(ExportAudioBackend::new(movie.frame_rate().to_f64()))
+        } else {
+            builder
+        };
+        Ok(builder
+            .with_movie(movie)
+            .with_viewport_dimensions(width, height, scale)
+            .build())
+    }
+}
+
+/// Captures the last rendered frame of the player, using straight alpha.
+fn capture_frame(player: &mut Player) -> Option<RgbaImage> {
+    let renderer = player.renderer_mut();
+    if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>() {
+        renderer.capture_frame()
+    } else {
+        renderer
+            .downcast_mut::<SoftwareRenderBackend>()
+            .map(|renderer| renderer.capture_frame())
     }
+}
 
+/// Calculates the size to render a movie at.
+fn output_size(movie: &SwfMovie, size: SizeOpt) -> (u32, u32) {
     let width = size
         .width
         .map(f64::from)
@@ -111,15 +179,27 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
-            WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
-        )
-        .with_movie(movie)
-        .with_viewport_dimensions(width, height, size.scale)
-        .build();
+    (width, height)
+}
+
+/// Captures a screenshot. The resulting image uses straight alpha
+fn take_screenshot(
+    device: RenderDevice,
+    swf_path: &Path,
+    frames: u32,
+    skipframes: u32,
+    progress: &Option<ProgressBar>,
+    size: SizeOpt,
+    skip_unsupported: bool,
+) -> Result<Vec<RgbaImage>> {
+    let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+
+    if movie.is_action_script_3() && skip_unsupported {
+        return Err(anyhow!("Skipping unsupported movie"));
+    }
+
+    let (width, height) = output_size(&movie, size);
+    let player = device.create_player(movie, width, height, size.scale, false)?;
 
     let mut result = Vec::new();
     let totalframes = frames + skipframes;
@@ -139,12 +219,7 @@ fn take_screenshot(
         if i >= skipframes {
             match catch_unwind(|| {
                 player.lock().unwrap().render();
-                let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
+                capture_frame(&mut player.lock().unwrap())
             }) {
                 Ok(Some(image)) => result.push(image),
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
@@ -166,6 +241,111 @@ fn take_screenshot(
     Ok(result)
 }
 
+/// Exports a video of a single movie, and optionally its audio as a WAV file.
+fn capture_video(device: RenderDevice, opt: &Opt, format: VideoFormat) -> Result<()> {
+    let movie = SwfMovie::from_path(&opt.swf, None).map_err(|e| anyhow!(e.to_string()))?;
+
+    if movie.is_action_script_3() && opt.skip_unsupported {
+        return Err(anyhow!("Skipping unsupported movie"));
+    }
+
+    let output = opt.output_path.clone().unwrap_or_else(|| {
+        let mut result = PathBuf::new();
+        result.set_file_name(opt.swf.file_stem().unwrap());
+        if format == VideoFormat::Y4m {
+            result.set_extension("y4m");
+        }
+        result
+    });
+
+    let (width, height) = output_size(&movie, opt.size);
+    let frame_rate = movie.frame_rate().to_f64();
+    let frames = match opt.duration {
+        Some(duration) => (duration * frame_rate).ceil() as u32,
+        None => opt.frames,
+    };
+    let player = device.create_player(movie, width, height, opt.size.scale, !opt.no_audio)?;
+
+    let mut video = VideoWriter::new(format, &output, width, height, frame_rate)?;
+    let mut wav = if !opt.no_audio {
+        let path = match format {
+            VideoFormat::Y4m => output.with_extension("wav"),
+            VideoFormat::Png => output.join("audio.wav"),
+        };
+        Some(WavWriter::new(BufWriter::new(File::create(path)?))?)
+    } else {
+        None
+    };
+
+    let progress = if !opt.silent {
+        let progress = ProgressBar::new(frames as u64);
+        progress.set_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] {bar:40.cyan/blue} [{eta_precise}] {pos:>7}/{len:7} {msg}",
+            )
+            .unwrap()
+            .progress_chars("##-"),
+        );
+        Some(progress)
+    } else {
+        None
+    };
+
+    for i in 0..frames + opt.skipframes {
+        if let Some(progress) = &progress {
+            progress.set_message(format!(
+                "{} frame {}",
+                opt.swf.file_stem().unwrap().to_string_lossy(),
+                i
+            ));
+        }
+
+        let mut player = player.lock().unwrap();
+        player.preload(&mut ExecutionLimit::none());
+        player.run_frame();
+        // Audio is mixed for one frame at the video's frame rate, keeping it in sync.
+        player.audio_mut().tick();
+        let samples = player
+            .audio_mut()
+            .downcast_mut::<ExportAudioBackend>()
+            .map(|audio| audio.take_samples())
+            .unwrap_or_default();
+
+        if i >= opt.skipframes {
+            player.render();
+            let image = capture_frame(&mut player)
+                .ok_or_else(|| anyhow!("Unable to capture frame {} of {:?}", i, opt.swf))?;
+            video.write_frame(&image)?;
+            if let Some(wav) = &mut wav {
+                wav.write_samples(&samples)?;
+            }
+
+            if let Some(progress) = &progress {
+                progress.inc(1);
+            }
+        }
+    }
+
+    video.finish()?;
+    if let Some(wav) = wav {
+        wav.finish()?;
+    }
+
+    let message = format!(
+        "Saved {} frames of {} to {}",
+        frames,
+        opt.swf.to_string_lossy(),
+        output.to_string_lossy()
+    );
+    if let Some(progress) = progress {
+        progress.finish_with_message(message);
+    } else {
+        println!("{message}");
+    }
+
+    Ok(())
+}
+
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     let progress = if with_progress {
         Some(ProgressBar::new_spinner())
@@ -196,7 +376,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(device: RenderDevice, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
@@ -225,7 +405,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
     };
 
     let frames = take_screenshot(
-        descriptors,
+        device,
         &opt.swf,
         opt.frames,
         opt.skipframes,
@@ -293,7 +473,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(device: RenderDevice, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -322,7 +502,7 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
             );
         }
         if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
+            device.clone(),
             file.path(),
             opt.frames,
             opt.skipframes,
@@ -399,8 +579,7 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
     None
 }
 
-fn main() -> Result<()> {
-    let opt: Opt = Opt::parse();
+fn create_wgpu_device(opt: &Opt) -> Result<RenderDevice> {
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
         backends: opt.graphics.into(),
         dx12_shader_compiler: wgpu::Dx12Compiler::default(),
@@ -410,18 +589,34 @@ fn main() -> Result<()> {
         &instance,
         None,
         opt.power.into(),
-        trace_path(&opt),
+        trace_path(opt),
     ))
     .map_err(|e| anyhow!(e.to_string()))?;
 
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+    Ok(RenderDevice::Wgpu(Arc::new(Descriptors::new(
+        instance, adapter, device, queue,
+    ))))
+}
 
-    if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+fn main() -> Result<()> {
+    let opt: Opt = Opt::parse();
+    let device = if opt.software {
+        RenderDevice::Software
+    } else {
+        create_wgpu_device(&opt)?
+    };
+
+    if let Some(format) = opt.video {
+        if !opt.swf.is_file() {
+            return Err(anyhow!("Videos can only be exported from a single file."));
+        }
+        capture_video(device, &opt, format)?;
+    } else if opt.swf.is_file() {
+        capture_single_swf(device, &opt)?;
     } else if !opt.swf.is_dir() {
         return Err(anyhow!("Given path is not a file or directory."));
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(device, &opt)?;
     } else {
         return Err(anyhow!(
             "Output directory is required when exporting multiple files."
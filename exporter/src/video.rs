@@ -0,0 +1,188 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use image::RgbaImage;
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// The lossless formats that a video can be exported as.
+#[derive(ValueEnum, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum VideoFormat {
+    /// A single uncompressed YUV4MPEG2 stream, using 4:4:4 chroma.
+    Y4m,
+
+    /// A directory of PNG images, plus an ffconcat manifest with the timing of each frame.
+    Png,
+}
+
+/// Writes captured frames to a video, one at a time.
+pub enum VideoWriter {
+    Y4m {
+        writer: BufWriter<File>,
+        width: u32,
+        height: u32,
+    },
+    Png {
+        directory: PathBuf,
+        manifest: BufWriter<File>,
+        frame_duration: f64,
+        frames_written: u32,
+    },
+}
+
+impl VideoWriter {
+    /// The name of the ffconcat manifest written alongside a PNG sequence.
+    pub const MANIFEST_NAME: &'static str = "frames.ffconcat";
+
+    pub fn new(
+        format: VideoFormat,
+        path: &Path,
+        width: u32,
+        height: u32,
+        frame_rate: f64,
+    ) -> Result<Self> {
+        match format {
+            VideoFormat::Y4m => {
+                let mut writer = BufWriter::new(File::create(path)?);
+                let (numerator, denominator) = frame_rate_ratio(frame_rate);
+                writeln!(
+                    writer,
+                    "YUV4MPEG2 W{width} H{height} F{numerator}:{denominator} Ip A1:1 C444 XCOLORRANGE=FULL"
+                )?;
+                Ok(Self::Y4m {
+                    writer,
+                    width,
+                    height,
+                })
+            }
+            VideoFormat::Png => {
+                create_dir_all(path)?;
+                let mut manifest = BufWriter::new(File::create(path.join(Self::MANIFEST_NAME))?);
+                writeln!(manifest, "ffconcat version 1.0")?;
+                Ok(Self::Png {
+                    directory: path.to_path_buf(),
+                    manifest,
+                    frame_duration: 1.0 / frame_rate,
+                    frames_written: 0,
+                })
+            }
+        }
+    }
+
+    pub fn write_frame(&mut self, image: &RgbaImage) -> Result<()> {
+        match self {
+            Self::Y4m {
+                writer,
+                width,
+                height,
+            } => {
+                // Frames may come back from the renderer at a different size than requested,
+                // but every frame of a Y4M stream must match the header.
+                let image = if image.dimensions() == (*width, *height) {
+                    std::borrow::Cow::Borrowed(image)
+                } else {
+                    std::borrow::Cow::Owned(image::imageops::resize(
+                        image,
+                        *width,
+                        *height,
+                        image::imageops::FilterType::Nearest,
+                    ))
+                };
+
+                let num_pixels = (*width * *height) as usize;
+                let mut planes = vec![0u8; num_pixels * 3];
+                let (y_plane, rest) = planes.split_at_mut(num_pixels);
+                let (u_plane, v_plane) = rest.split_at_mut(num_pixels);
+                for (i, pixel) in image.pixels().enumerate() {
+                    let [y, u, v] = rgb_to_yuv(pixel.0);
+                    y_plane[i] = y;
+                    u_plane[i] = u;
+                    v_plane[i] = v;
+                }
+
+                writer.write_all(b"FRAME\n")?;
+                writer.write_all(&planes)?;
+            }
+            Self::Png {
+                directory,
+                manifest,
+                frame_duration,
+                frames_written,
+            } => {
+                let name = format!("{frames_written:06}.png");
+                image.save(directory.join(&name))?;
+                writeln!(manifest, "file '{name}'")?;
+                writeln!(manifest, "duration {frame_duration}")?;
+                *frames_written += 1;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<()> {
+        match self {
+            Self::Y4m { mut writer, .. } => writer.flush()?,
+            Self::Png {
+                mut manifest,
+                frames_written,
+                ..
+            } => {
+                // The concat demuxer ignores the duration of the last entry,
+                // so the last frame has to be listed again for it to be shown.
+                if let Some(last_frame) = frames_written.checked_sub(1) {
+                    writeln!(manifest, "file '{last_frame:06}.png'")?;
+                }
+                manifest.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Converts a frame rate into the `numerator:denominator` ratio used by Y4M.
+fn frame_rate_ratio(frame_rate: f64) -> (u64, u64) {
+    let numerator = (frame_rate * 1000.0).round().max(1.0) as u64;
+    let mut denominator = 1000;
+
+    let (mut a, mut b) = (numerator, denominator);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    denominator /= a;
+    (numerator / a, denominator)
+}
+
+/// Converts a pixel to full-range BT.601 YCbCr, ignoring alpha.
+fn rgb_to_yuv([r, g, b, _]: [u8; 4]) -> [u8; 3] {
+    let (r, g, b) = (r as f32, g as f32, b as f32);
+    let y = 0.299 * r + 0.587 * g + 0.114 * b;
+    let u = 128.0 - 0.168736 * r - 0.331264 * g + 0.5 * b;
+    let v = 128.0 + 0.5 * r - 0.418688 * g - 0.081312 * b;
+    [
+        y.round().clamp(0.0, 255.0) as u8,
+        u.round().clamp(0.0, 255.0) as u8,
+        v.round().clamp(0.0, 255.0) as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_rate_ratios() {
+        assert_eq!(frame_rate_ratio(24.0), (24, 1));
+        assert_eq!(frame_rate_ratio(29.97), (2997, 100));
+        assert_eq!(frame_rate_ratio(12.5), (25, 2));
+        // A frame rate of zero would be an invalid header.
+        assert_eq!(frame_rate_ratio(0.0), (1, 1000));
+    }
+
+    #[test]
+    fn rgb_to_yuv_full_range() {
+        assert_eq!(rgb_to_yuv([0, 0, 0, 255]), [0, 128, 128]);
+        assert_eq!(rgb_to_yuv([255, 255, 255, 0]), [255, 128, 128]);
+        assert_eq!(rgb_to_yuv([255, 0, 0, 255]), [76, 85, 255]);
+        assert_eq!(rgb_to_yuv([0, 0, 255, 255]), [29, 255, 107]);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::mesh::{BitmapDraw, Draw, DrawType, Mesh};
+use crate::paint::Affine;
+use crate::pixmap::Pixmap;
+use crate::target::{samples_per_axis, Target};
+use crate::texture::{as_texture, SoftwareSyncHandle, Texture};
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{Bitmap, BitmapHandle, BitmapSource, PixelRegion, SyncHandle};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::{DrawType as TessDrawType, ShapeTessellator};
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+/// A render backend that rasterizes everything on the CPU.
+///
+/// This doesn't need a GPU or a windowing system, which makes it suitable for
+/// headless environments such as CI. Each submitted frame is kept around and
+/// can be retrieved with `capture_frame`.
+pub struct SoftwareRenderBackend {
+    viewport_dimensions: ViewportDimensions,
+    shape_tessellator: ShapeTessellator,
+    quality: StageQuality,
+    frame: Pixmap,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(viewport_dimensions: ViewportDimensions) -> Self {
+        Self {
+            frame: Pixmap::new(viewport_dimensions.width, viewport_dimensions.height),
+            viewport_dimensions,
+            shape_tessellator: ShapeTessellator::new(),
+            quality: StageQuality::default(),
+        }
+    }
+
+    /// Returns the most recently submitted frame. The resulting image uses straight alpha.
+    pub fn capture_frame(&self) -> image::RgbaImage {
+        let mut bytes = self.frame.data().to_vec();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut bytes);
+        image::RgbaImage::from_raw(self.frame.width(), self.frame.height(), bytes)
+            .expect("Frame buffer must be a valid RgbaImage")
+    }
+
+    fn render_cache_entry(&mut self, entry: BitmapCacheEntry) {
+        let texture = as_texture(&entry.handle);
+        let (width, height) = {
+            let pixmap = texture.read();
+            (pixmap.width(), pixmap.height())
+        };
+
+        let mut target = Target::new(width, height, samples_per_axis(self.quality));
+        target.clear(entry.clear);
+        entry.commands.execute(&mut target);
+        if !entry.filters.is_empty() {
+            tracing::warn!(
+                "Filters are not supported by the software renderer, ignoring {} filter(s)",
+                entry.filters.len()
+            );
+        }
+        *texture.write() = target.resolve();
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+        self.frame = Pixmap::new(dimensions.width, dimensions.height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+
+        let mut draws = Vec::with_capacity(lyon_mesh.len());
+        for mut draw in lyon_mesh {
+            let draw_type = match std::mem::replace(&mut draw.draw_type, TessDrawType::Color) {
+                TessDrawType::Color => DrawType::Color,
+                TessDrawType::Gradient(gradient) => DrawType::Gradient(Box::new(gradient)),
+                TessDrawType::Bitmap(bitmap) => DrawType::Bitmap(BitmapDraw {
+                    matrix: Affine::from_columns(&bitmap.matrix),
+                    handle: bitmap_source.bitmap_handle(bitmap.bitmap_id, self),
+                    is_smoothed: bitmap.is_smoothed,
+                    is_repeating: bitmap.is_repeating,
+                }),
+            };
+            draws.push(Draw::new(draw, draw_type));
+        }
+
+        ShapeHandle(Arc::new(Mesh { draws }))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let texture = as_texture(&handle);
+
+        // Drawing happens on top of the existing contents of the bitmap. The lock must not
+        // be held while executing the commands, as they may draw the bitmap onto itself.
+        let mut target = Target::with_contents(&texture.read(), samples_per_axis(quality));
+        commands.execute(&mut target);
+        let result = target.resolve();
+
+        let mut pixmap = texture.write();
+        pixmap.copy_region_from(&result, bounds);
+        let mut region = bounds;
+        region.clamp(pixmap.width(), pixmap.height());
+        Some(Box::new(SoftwareSyncHandle {
+            data: pixmap.region_data(region),
+            width: region.width(),
+        }))
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            self.render_cache_entry(entry);
+        }
+
+        let mut target = Target::new(
+            self.viewport_dimensions.width,
+            self.viewport_dimensions.height,
+            samples_per_axis(self.quality),
+        );
+        target.clear(clear);
+        commands.execute(&mut target);
+        self.frame = target.resolve();
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        Ok(BitmapHandle(Arc::new(Texture::new(Pixmap::new(
+            width, height,
+        )))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        if bitmap.width() == 0 || bitmap.height() == 0 {
+            return Err(Error::InvalidSize);
+        }
+        Ok(BitmapHandle(Arc::new(Texture::new(Pixmap::from_bitmap(
+            bitmap,
+        )))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let texture = as_texture(handle);
+        texture
+            .write()
+            .update_region(&Pixmap::from_bitmap(bitmap), region);
+        Ok(())
+    }
+
+    fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "Renderer: Software\nSamples per pixel: {}\n",
+            samples_per_axis(self.quality).pow(2)
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _handle: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: BitmapHandle,
+    ) -> Result<Box<dyn SyncHandle>, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+}
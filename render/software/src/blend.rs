@@ -0,0 +1,103 @@
+use crate::pixmap::Rgba;
+use swf::BlendMode;
+
+/// Composites a premultiplied `src` color onto a premultiplied `dst` color.
+///
+/// These mirror the blend states and blend shaders used by the wgpu backend.
+pub fn blend(mode: BlendMode, src: Rgba, dst: Rgba) -> Rgba {
+    let [sr, sg, sb, sa] = src;
+    let [dr, dg, db, da] = dst;
+    let over_alpha = sa + da * (1.0 - sa);
+
+    match mode {
+        BlendMode::Normal | BlendMode::Layer => over(src, dst),
+        BlendMode::Add => [
+            (sr + dr).min(1.0),
+            (sg + dg).min(1.0),
+            (sb + db).min(1.0),
+            over_alpha,
+        ],
+        BlendMode::Subtract => [
+            (dr - sr).max(0.0),
+            (dg - sg).max(0.0),
+            (db - sb).max(0.0),
+            over_alpha,
+        ],
+        BlendMode::Screen => [
+            sr + dr * (1.0 - sr),
+            sg + dg * (1.0 - sg),
+            sb + db * (1.0 - sb),
+            over_alpha,
+        ],
+        _ if sa <= 0.0 => dst,
+        BlendMode::Alpha => [dr * sa, dg * sa, db * sa, da * sa],
+        BlendMode::Erase => [
+            dr * (1.0 - sa),
+            dg * (1.0 - sa),
+            db * (1.0 - sa),
+            da * (1.0 - sa),
+        ],
+        // Flash doesn't multiply onto fully transparent pixels, it just draws the source.
+        BlendMode::Multiply if da <= 0.0 => src,
+        BlendMode::Multiply => separable(src, dst, |s, d| s * d),
+        BlendMode::Lighten => separable(src, dst, f32::max),
+        BlendMode::Darken => separable(src, dst, f32::min),
+        BlendMode::Difference => separable(src, dst, |s, d| (d - s).abs()),
+        BlendMode::Invert => separable(src, dst, |_, d| 1.0 - d),
+        BlendMode::Overlay => separable(src, dst, |s, d| hard_light(d, s)),
+        BlendMode::HardLight => separable(src, dst, hard_light),
+    }
+}
+
+#[inline]
+pub fn over(src: Rgba, dst: Rgba) -> Rgba {
+    let inv_alpha = 1.0 - src[3];
+    [
+        src[0] + dst[0] * inv_alpha,
+        src[1] + dst[1] * inv_alpha,
+        src[2] + dst[2] * inv_alpha,
+        src[3] + dst[3] * inv_alpha,
+    ]
+}
+
+/// Applies a separable blend function to the unmultiplied colors, and composites the result.
+#[inline]
+fn separable(src: Rgba, dst: Rgba, func: impl Fn(f32, f32) -> f32) -> Rgba {
+    let sa = src[3];
+    let da = dst[3];
+    let channel = |i: usize| {
+        let s = src[i] / sa;
+        let d = if da > 0.0 { dst[i] / da } else { 0.0 };
+        (src[i] * (1.0 - da) + dst[i] * (1.0 - sa) + sa * da * func(s, d)).clamp(0.0, 1.0)
+    };
+    [channel(0), channel(1), channel(2), sa + da * (1.0 - sa)]
+}
+
+#[inline]
+fn hard_light(s: f32, d: f32) -> f32 {
+    if s <= 0.5 {
+        2.0 * s * d
+    } else {
+        1.0 - 2.0 * (1.0 - d) * (1.0 - s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiply_onto_transparent_keeps_source() {
+        let src = [0.25, 0.5, 0.0, 0.5];
+        assert_eq!(blend(BlendMode::Multiply, src, [0.0; 4]), src);
+    }
+
+    #[test]
+    fn erase_removes_destination_alpha() {
+        let dst = [1.0, 1.0, 1.0, 1.0];
+        assert_eq!(
+            blend(BlendMode::Erase, [0.0, 0.0, 0.0, 1.0], dst),
+            [0.0, 0.0, 0.0, 0.0]
+        );
+    }
+}
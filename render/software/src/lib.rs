@@ -0,0 +1,11 @@
+#![deny(clippy::unwrap_used)]
+
+mod backend;
+mod blend;
+mod mesh;
+mod paint;
+mod pixmap;
+mod target;
+mod texture;
+
+pub use backend::SoftwareRenderBackend;
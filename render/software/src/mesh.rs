@@ -0,0 +1,63 @@
+use crate::paint::{color_to_rgba, Affine};
+use crate::pixmap::Rgba;
+use ruffle_render::backend::{ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::BitmapHandle;
+use ruffle_render::tessellator::Gradient;
+
+/// A tessellated shape, ready to be rasterized.
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+pub fn as_mesh(handle: &ShapeHandle) -> &Mesh {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).expect("Shape handle must be a software mesh")
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub draw_type: DrawType,
+    /// Vertex positions in shape space, in pixels.
+    pub positions: Vec<(f32, f32)>,
+    /// Straight (non-premultiplied) vertex colors.
+    pub colors: Vec<Rgba>,
+    pub indices: Vec<u32>,
+    /// The number of indices to draw when this shape is used as a mask (strokes are excluded).
+    pub mask_index_count: usize,
+}
+
+#[derive(Debug)]
+pub enum DrawType {
+    Color,
+    Gradient(Box<Gradient>),
+    Bitmap(BitmapDraw),
+}
+
+#[derive(Debug)]
+pub struct BitmapDraw {
+    /// Maps shape space to normalized texture coordinates.
+    pub matrix: Affine,
+    pub handle: Option<BitmapHandle>,
+    pub is_smoothed: bool,
+    pub is_repeating: bool,
+}
+
+impl Draw {
+    pub fn new(draw: ruffle_render::tessellator::Draw, draw_type: DrawType) -> Self {
+        let positions = draw.vertices.iter().map(|v| (v.x, v.y)).collect();
+        let colors = draw
+            .vertices
+            .iter()
+            .map(|v| color_to_rgba(&v.color))
+            .collect();
+        Self {
+            draw_type,
+            positions,
+            colors,
+            mask_index_count: (draw.mask_index_count as usize).min(draw.indices.len()),
+            indices: draw.indices,
+        }
+    }
+}
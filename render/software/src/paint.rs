@@ -0,0 +1,378 @@
+use crate::pixmap::Rgba;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::Gradient;
+use swf::{Color, ColorTransform, GradientInterpolation, GradientSpread};
+
+/// A 2D affine transformation in (fractional) pixels.
+///
+/// Unlike `Matrix`, the translation isn't rounded to twips, which matters once the
+/// transform has been scaled up to the sample grid or inverted.
+#[derive(Copy, Clone, Debug)]
+pub struct Affine {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    tx: f32,
+    ty: f32,
+}
+
+impl Affine {
+    pub fn from_matrix(matrix: &Matrix, scale: f32) -> Self {
+        Self {
+            a: matrix.a * scale,
+            b: matrix.b * scale,
+            c: matrix.c * scale,
+            d: matrix.d * scale,
+            tx: matrix.tx.to_pixels() as f32 * scale,
+            ty: matrix.ty.to_pixels() as f32 * scale,
+        }
+    }
+
+    /// Creates the transform described by a 3x3 column-major texture matrix,
+    /// as produced by the tessellator.
+    pub fn from_columns(matrix: &[[f32; 3]; 3]) -> Self {
+        Self {
+            a: matrix[0][0],
+            b: matrix[0][1],
+            c: matrix[1][0],
+            d: matrix[1][1],
+            tx: matrix[2][0],
+            ty: matrix[2][1],
+        }
+    }
+
+    /// Returns a transform that applies `first`, and then `self`.
+    pub fn after(&self, first: &Affine) -> Self {
+        Self {
+            a: self.a * first.a + self.c * first.b,
+            b: self.b * first.a + self.d * first.b,
+            c: self.a * first.c + self.c * first.d,
+            d: self.b * first.c + self.d * first.d,
+            tx: self.a * first.tx + self.c * first.ty + self.tx,
+            ty: self.b * first.tx + self.d * first.ty + self.ty,
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Self {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            tx: (self.c * self.ty - self.d * self.tx) / det,
+            ty: (self.b * self.tx - self.a * self.ty) / det,
+        })
+    }
+
+    #[inline]
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+}
+
+/// A 2D projective transformation in (fractional) pixels, as produced by projecting
+/// the z = 0 plane of a 3D transform.
+///
+/// Each row maps `(x, y, 1)` to one of `x`, `y` and `w`, and the result has to be
+/// divided by `w`.
+#[derive(Copy, Clone, Debug)]
+pub struct Projective {
+    rows: [[f64; 3]; 3],
+}
+
+impl Projective {
+    pub fn from_matrix3d(matrix: &Matrix3D, scale: f64) -> Self {
+        let m = &matrix.raw_data;
+        Self {
+            rows: [
+                [m[0] * scale, m[4] * scale, m[12] * scale],
+                [m[1] * scale, m[5] * scale, m[13] * scale],
+                [m[3], m[7], m[15]],
+            ],
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.rows;
+        let det = a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g);
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        // Dividing the adjugate by the (possibly negative) determinant keeps `w`
+        // positive for points in front of the viewer.
+        let adjugate = [
+            [e * i - f * h, c * h - b * i, b * f - c * e],
+            [f * g - d * i, a * i - c * g, c * d - a * f],
+            [d * h - e * g, b * g - a * h, a * e - b * d],
+        ];
+        Some(Self {
+            rows: adjugate.map(|row| row.map(|value| value / det)),
+        })
+    }
+
+    /// Transforms a point, or returns `None` if it lies behind the viewer.
+    #[inline]
+    pub fn apply(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        let (x, y) = (f64::from(x), f64::from(y));
+        let [x, y, w] = self.rows.map(|row| row[0] * x + row[1] * y + row[2]);
+        (w > 0.0).then(|| ((x / w) as f32, (y / w) as f32))
+    }
+}
+
+/// A color transform, converted to normalized floats once per draw.
+#[derive(Copy, Clone, Debug)]
+pub struct ColorTransformer {
+    mult: [f32; 4],
+    add: [f32; 4],
+}
+
+impl ColorTransformer {
+    pub fn new(color_transform: &ColorTransform) -> Self {
+        Self {
+            mult: color_transform.mult_rgba_normalized(),
+            add: color_transform.add_rgba_normalized(),
+        }
+    }
+
+    /// Transforms a straight (non-premultiplied) color, returning a premultiplied color.
+    #[inline]
+    pub fn transform_straight(&self, color: Rgba) -> Rgba {
+        let [r, g, b, a] = self.transform_unclamped(color).map(|c| c.clamp(0.0, 1.0));
+        [r * a, g * a, b * a, a]
+    }
+
+    /// Transforms a premultiplied color, as sampled from a texture.
+    #[inline]
+    pub fn transform_premultiplied(&self, color: Rgba) -> Rgba {
+        let alpha = color[3];
+        if alpha <= 0.0 {
+            return color;
+        }
+        self.transform_straight([color[0] / alpha, color[1] / alpha, color[2] / alpha, alpha])
+    }
+
+    #[inline]
+    fn transform_unclamped(&self, color: Rgba) -> Rgba {
+        [
+            color[0] * self.mult[0] + self.add[0],
+            color[1] * self.mult[1] + self.add[1],
+            color[2] * self.mult[2] + self.add[2],
+            color[3] * self.mult[3] + self.add[3],
+        ]
+    }
+}
+
+#[inline]
+pub fn color_to_rgba(color: &Color) -> Rgba {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
+
+const GRADIENT_SIZE: usize = 256;
+
+/// A gradient fill, resolved into a lookup table for a specific color transform.
+pub struct GradientPaint {
+    matrix: Affine,
+    gradient_type: GradientType,
+    repeat_mode: GradientSpread,
+    focal_point: f32,
+    colors: Vec<Rgba>,
+}
+
+impl GradientPaint {
+    pub fn new(gradient: &Gradient, color_transform: &ColorTransformer) -> Self {
+        let is_linear = gradient.interpolation == GradientInterpolation::LinearRgb;
+
+        // Color transforms are applied to the gradient stops, before interpolation.
+        let records: Vec<(f32, Rgba)> = gradient
+            .records
+            .iter()
+            .map(|record| {
+                let mut color = color_to_rgba(&record.color);
+                if is_linear {
+                    color = srgb_to_linear(color);
+                }
+                let color = color_transform
+                    .transform_unclamped(color)
+                    .map(|c| c.clamp(0.0, 1.0));
+                (f32::from(record.ratio) / 255.0, color)
+            })
+            .collect();
+
+        let colors = (0..GRADIENT_SIZE)
+            .map(|i| {
+                let t = i as f32 / (GRADIENT_SIZE - 1) as f32;
+                let mut color = interpolate_records(&records, t);
+                if is_linear {
+                    color = linear_to_srgb(color);
+                }
+                let alpha = color[3];
+                [color[0] * alpha, color[1] * alpha, color[2] * alpha, alpha]
+            })
+            .collect();
+
+        Self {
+            matrix: Affine::from_columns(&gradient.matrix),
+            gradient_type: gradient.gradient_type,
+            repeat_mode: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+            colors,
+        }
+    }
+
+    /// Returns the color of this gradient at the given point in shape space.
+    pub fn sample(&self, x: f32, y: f32) -> Rgba {
+        let (u, v) = self.matrix.apply(x, y);
+        let t = match self.gradient_type {
+            GradientType::Linear => u,
+            GradientType::Radial => {
+                let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                (u * u + v * v).sqrt()
+            }
+            GradientType::Focal => {
+                let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                let (dx, dy) = (self.focal_point - u, -v);
+                let l = (dx * dx + dy * dy).sqrt();
+                if l == 0.0 {
+                    0.0
+                } else {
+                    let (dx, dy) = (dx / l, dy / l);
+                    l / ((1.0 - self.focal_point * self.focal_point * dy * dy).sqrt()
+                        + self.focal_point * dx)
+                }
+            }
+        };
+
+        let t = match self.repeat_mode {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Repeat => t.rem_euclid(1.0),
+            GradientSpread::Reflect => {
+                let t = t.abs().rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        };
+        if !t.is_finite() {
+            return self.colors[0];
+        }
+
+        let index = (t * (GRADIENT_SIZE - 1) as f32).round() as usize;
+        self.colors[index.min(GRADIENT_SIZE - 1)]
+    }
+}
+
+fn interpolate_records(records: &[(f32, Rgba)], t: f32) -> Rgba {
+    let Some(first) = records.first() else {
+        return [0.0; 4];
+    };
+    if t <= first.0 {
+        return first.1;
+    }
+    for window in records.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        if t <= end.0 {
+            let range = end.0 - start.0;
+            if range <= 0.0 {
+                return end.1;
+            }
+            return crate::pixmap::lerp(start.1, end.1, (t - start.0) / range);
+        }
+    }
+    records[records.len() - 1].1
+}
+
+fn srgb_to_linear(color: Rgba) -> Rgba {
+    let convert = |c: f32| {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    [
+        convert(color[0]),
+        convert(color[1]),
+        convert(color[2]),
+        color[3],
+    ]
+}
+
+fn linear_to_srgb(color: Rgba) -> Rgba {
+    let convert = |c: f32| {
+        if c < 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    [
+        convert(color[0]),
+        convert(color[1]),
+        convert(color[2]),
+        color[3],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::Twips;
+
+    #[test]
+    fn affine_inverse_round_trips() {
+        let matrix = Matrix {
+            a: 2.0,
+            b: 0.5,
+            c: -1.0,
+            d: 3.0,
+            tx: Twips::from_pixels(10.0),
+            ty: Twips::from_pixels(-4.0),
+        };
+        let affine = Affine::from_matrix(&matrix, 2.0);
+        let inverse = affine.inverse().expect("matrix is invertible");
+        let (x, y) = affine.apply(3.0, 7.0);
+        let (x, y) = inverse.apply(x, y);
+        assert!((x - 3.0).abs() < 1e-4);
+        assert!((y - 7.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn projective_inverse_round_trips() {
+        let mut matrix = Matrix3D::rotate_y(0.4);
+        matrix.raw_data[3] = 0.002;
+        matrix.raw_data[12] = 20.0;
+        let projective = Projective::from_matrix3d(&matrix, 2.0);
+        let inverse = projective.inverse().expect("matrix is invertible");
+        let (x, y) = projective.apply(30.0, 12.0).expect("point is in front");
+        let (x, y) = inverse.apply(x, y).expect("point is in front");
+        assert!((x - 30.0).abs() < 1e-3);
+        assert!((y - 12.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn color_transform_is_premultiplied() {
+        let transformer = ColorTransformer::new(&ColorTransform {
+            a_multiply: swf::Fixed8::from_f32(0.5),
+            ..Default::default()
+        });
+        let color = transformer.transform_straight([1.0, 0.5, 0.0, 1.0]);
+        assert_eq!(color, [0.5, 0.25, 0.0, 0.5]);
+    }
+}
@@ -0,0 +1,284 @@
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, PixelRegion};
+use std::fmt;
+
+/// A single premultiplied RGBA color, with each channel normalized to `0.0..=1.0`.
+pub type Rgba = [f32; 4];
+
+pub const TRANSPARENT: Rgba = [0.0; 4];
+
+/// An image stored as 8-bit premultiplied RGBA, the same layout used by `Bitmap` and
+/// by the other render backends' textures.
+#[derive(Clone)]
+pub struct Pixmap {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl fmt::Debug for Pixmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pixmap")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+impl Pixmap {
+    /// Creates a new, fully transparent pixmap.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    pub fn from_bitmap(bitmap: Bitmap) -> Self {
+        let bitmap = bitmap.to_rgba();
+        debug_assert_eq!(bitmap.format(), BitmapFormat::Rgba);
+        let width = bitmap.width();
+        let height = bitmap.height();
+        let mut data = bitmap.data().to_vec();
+        // `Bitmap::new` will have already warned about this; don't let it turn into a panic.
+        data.resize(width as usize * height as usize * 4, 0);
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The raw premultiplied RGBA bytes of this pixmap.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn get(&self, index: usize) -> Rgba {
+        let pixel = &self.data[index * 4..index * 4 + 4];
+        [
+            f32::from(pixel[0]) / 255.0,
+            f32::from(pixel[1]) / 255.0,
+            f32::from(pixel[2]) / 255.0,
+            f32::from(pixel[3]) / 255.0,
+        ]
+    }
+
+    #[inline]
+    pub fn set(&mut self, index: usize, color: Rgba) {
+        let pixel = &mut self.data[index * 4..index * 4 + 4];
+        for (dst, src) in pixel.iter_mut().zip(color) {
+            *dst = to_u8(src);
+        }
+    }
+
+    pub fn fill(&mut self, color: Rgba) {
+        let color = color.map(to_u8);
+        for pixel in self.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    #[inline]
+    fn texel(&self, x: u32, y: u32) -> Rgba {
+        self.get((y * self.width + x) as usize)
+    }
+
+    /// Samples this pixmap at the normalized coordinates `u`, `v`.
+    ///
+    /// Coordinates outside of `0.0..1.0` are either wrapped around or clamped to the edge.
+    pub fn sample(&self, u: f32, v: f32, smoothed: bool, repeating: bool) -> Rgba {
+        if self.is_empty() || !u.is_finite() || !v.is_finite() {
+            return TRANSPARENT;
+        }
+
+        let width = self.width as f32;
+        let height = self.height as f32;
+        if !smoothed {
+            let x = wrap_coordinate((u * width).floor() as i64, self.width, repeating);
+            let y = wrap_coordinate((v * height).floor() as i64, self.height, repeating);
+            return self.texel(x, y);
+        }
+
+        let x = u * width - 0.5;
+        let y = v * height - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let x1 = wrap_coordinate(x0 as i64 + 1, self.width, repeating);
+        let y1 = wrap_coordinate(y0 as i64 + 1, self.height, repeating);
+        let x0 = wrap_coordinate(x0 as i64, self.width, repeating);
+        let y0 = wrap_coordinate(y0 as i64, self.height, repeating);
+
+        let top = lerp(self.texel(x0, y0), self.texel(x1, y0), fx);
+        let bottom = lerp(self.texel(x0, y1), self.texel(x1, y1), fx);
+        lerp(top, bottom, fy)
+    }
+
+    /// Creates a copy of this pixmap where every pixel has been repeated `factor` times on each
+    /// axis.
+    pub fn upsample(&self, factor: u32) -> Self {
+        if factor == 1 {
+            return self.clone();
+        }
+
+        let mut result = Self::new(self.width * factor, self.height * factor);
+        let row_len = result.width as usize * 4;
+        for y in 0..self.height as usize {
+            let start = y * factor as usize * row_len;
+            let row = &mut result.data[start..start + row_len];
+            for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+                let source = (y * self.width as usize + x / factor as usize) * 4;
+                pixel.copy_from_slice(&self.data[source..source + 4]);
+            }
+            for i in 1..factor as usize {
+                result
+                    .data
+                    .copy_within(start..start + row_len, start + i * row_len);
+            }
+        }
+        result
+    }
+
+    /// Creates a copy of this pixmap where every `factor` by `factor` block of pixels has been
+    /// averaged into a single pixel.
+    pub fn downsample(&self, factor: u32) -> Self {
+        if factor == 1 {
+            return self.clone();
+        }
+
+        let width = self.width / factor;
+        let height = self.height / factor;
+        let samples = factor * factor;
+        let mut result = Self::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0u32; 4];
+                for sy in 0..factor {
+                    let row = ((y * factor + sy) * self.width + x * factor) as usize * 4;
+                    for pixel in self.data[row..row + factor as usize * 4].chunks_exact(4) {
+                        for (sum, value) in sum.iter_mut().zip(pixel) {
+                            *sum += u32::from(*value);
+                        }
+                    }
+                }
+                let target = (y * width + x) as usize * 4;
+                for (dst, sum) in result.data[target..target + 4].iter_mut().zip(sum) {
+                    *dst = ((sum + samples / 2) / samples) as u8;
+                }
+            }
+        }
+        result
+    }
+
+    /// Copies the pixels inside `region` from `source`, which must be the same size as `self`.
+    pub fn copy_region_from(&mut self, source: &Pixmap, mut region: PixelRegion) {
+        debug_assert_eq!((self.width, self.height), (source.width, source.height));
+        region.clamp(self.width, self.height);
+        for y in region.y_min..region.y_max {
+            let start = (y * self.width + region.x_min) as usize * 4;
+            let end = (y * self.width + region.x_max) as usize * 4;
+            self.data[start..end].copy_from_slice(&source.data[start..end]);
+        }
+    }
+
+    /// Returns the pixels inside `region` as tightly packed rows.
+    pub fn region_data(&self, mut region: PixelRegion) -> Vec<u8> {
+        region.clamp(self.width, self.height);
+        let mut result = Vec::with_capacity(region.width() as usize * region.height() as usize * 4);
+        for y in region.y_min..region.y_max {
+            let start = (y * self.width + region.x_min) as usize * 4;
+            let end = (y * self.width + region.x_max) as usize * 4;
+            result.extend_from_slice(&self.data[start..end]);
+        }
+        result
+    }
+
+    /// Overwrites the pixels inside `region` with the pixels of `bitmap`, which must be the same
+    /// size as `self`.
+    pub fn update_region(&mut self, bitmap: &Pixmap, region: PixelRegion) {
+        if (bitmap.width, bitmap.height) != (self.width, self.height) {
+            tracing::warn!(
+                "Tried to update a {}x{} texture with a {}x{} bitmap",
+                self.width,
+                self.height,
+                bitmap.width,
+                bitmap.height
+            );
+            return;
+        }
+        self.copy_region_from(bitmap, region);
+    }
+}
+
+#[inline]
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[inline]
+fn wrap_coordinate(value: i64, size: u32, repeating: bool) -> u32 {
+    if repeating {
+        value.rem_euclid(i64::from(size)) as u32
+    } else {
+        value.clamp(0, i64::from(size) - 1) as u32
+    }
+}
+
+#[inline]
+pub fn lerp(a: Rgba, b: Rgba, t: f32) -> Rgba {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upsample_then_downsample_is_lossless() {
+        let mut pixmap = Pixmap::new(3, 2);
+        for i in 0..pixmap.len() {
+            pixmap.set(i, [0.1 * i as f32, 0.2, 0.3, 0.9]);
+        }
+        for factor in 1..=4 {
+            let round_trip = pixmap.upsample(factor).downsample(factor);
+            assert_eq!(round_trip.data(), pixmap.data());
+        }
+    }
+
+    #[test]
+    fn downsample_averages_samples() {
+        let mut pixmap = Pixmap::new(2, 2);
+        pixmap.set(0, [1.0, 1.0, 1.0, 1.0]);
+        let result = pixmap.downsample(2);
+        assert_eq!(result.data(), &[64, 64, 64, 64]);
+    }
+}
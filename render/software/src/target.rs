@@ -0,0 +1,481 @@
+use crate::blend::{blend, over};
+use crate::mesh::{as_mesh, DrawType};
+use crate::paint::{color_to_rgba, Affine, ColorTransformer, GradientPaint, Projective};
+use crate::pixmap::{Pixmap, Rgba};
+use crate::texture::as_texture;
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use swf::{BlendMode, Color};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// Returns how many samples are taken along each axis of a pixel for the given quality.
+pub fn samples_per_axis(quality: StageQuality) -> u32 {
+    match quality.sample_count() {
+        0 | 1 => 1,
+        2 | 4 => 2,
+        8 => 3,
+        _ => 4,
+    }
+}
+
+/// A supersampled surface that commands are rendered into.
+///
+/// Every pixel of the final image is made up of `scale * scale` samples, which are
+/// averaged together by `resolve`. Masks are kept as one flag per sample, which plays
+/// the role of the stencil buffer in the GPU backends.
+pub struct Target {
+    pixmap: Pixmap,
+    scale: u32,
+    masks: Vec<Vec<bool>>,
+    mask_state: MaskState,
+}
+
+impl Target {
+    pub fn new(width: u32, height: u32, scale: u32) -> Self {
+        Self {
+            pixmap: Pixmap::new(width * scale, height * scale),
+            scale,
+            masks: Vec::new(),
+            mask_state: MaskState::NoMask,
+        }
+    }
+
+    /// Creates a target that starts out with the contents of an existing image.
+    pub fn with_contents(contents: &Pixmap, scale: u32) -> Self {
+        Self {
+            pixmap: contents.upsample(scale),
+            scale,
+            masks: Vec::new(),
+            mask_state: MaskState::NoMask,
+        }
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        let [r, g, b, a] = color_to_rgba(&color);
+        self.pixmap.fill([r * a, g * a, b * a, a]);
+    }
+
+    /// Averages all samples down into the final image.
+    pub fn resolve(&self) -> Pixmap {
+        self.pixmap.downsample(self.scale)
+    }
+
+    /// Writes a single sample, respecting the current mask state.
+    #[inline]
+    fn put(&mut self, index: usize, color: impl FnOnce() -> Rgba) {
+        match self.mask_state {
+            MaskState::NoMask => {
+                let color = over(color(), self.pixmap.get(index));
+                self.pixmap.set(index, color);
+            }
+            MaskState::DrawMaskStencil => self.cover_mask(index),
+            MaskState::DrawMaskedContent => {
+                if self.is_masked_in(index) {
+                    let color = over(color(), self.pixmap.get(index));
+                    self.pixmap.set(index, color);
+                }
+            }
+            MaskState::ClearMaskStencil => {}
+        }
+    }
+
+    /// Marks a sample as being part of the mask that's currently being drawn.
+    /// Nested masks only cover samples that were also covered by their parent mask.
+    #[inline]
+    fn cover_mask(&mut self, index: usize) {
+        let count = self.masks.len();
+        if count >= 2 && !self.masks[count - 2][index] {
+            return;
+        }
+        if let Some(mask) = self.masks.last_mut() {
+            mask[index] = true;
+        }
+    }
+
+    #[inline]
+    fn is_masked_in(&self, index: usize) -> bool {
+        self.masks.last().map_or(true, |mask| mask[index])
+    }
+
+    fn is_drawing_mask(&self) -> bool {
+        matches!(
+            self.mask_state,
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil
+        )
+    }
+
+    /// Fills a list of triangles, given in sample coordinates.
+    ///
+    /// `shade` is called with the index of the first vertex of the triangle being drawn,
+    /// and the sample position; it's only called for samples that will actually be written.
+    fn fill_triangles(
+        &mut self,
+        vertices: &[(f32, f32)],
+        indices: &[u32],
+        mut shade: impl FnMut(usize, f32, f32) -> Rgba,
+    ) {
+        let width = self.pixmap.width();
+        let height = self.pixmap.height();
+        for triangle in indices.chunks_exact(3) {
+            let (Some(&a), Some(&b), Some(&c)) = (
+                vertices.get(triangle[0] as usize),
+                vertices.get(triangle[1] as usize),
+                vertices.get(triangle[2] as usize),
+            ) else {
+                continue;
+            };
+            let first = triangle[0] as usize;
+            rasterize_triangle(a, b, c, width, height, |x, y| {
+                let index = (y * width + x) as usize;
+                self.put(index, || shade(first, x as f32 + 0.5, y as f32 + 0.5));
+            });
+        }
+    }
+
+    /// Fills the unit square transformed by `transform`, given in sample coordinates.
+    fn fill_quad(&mut self, transform: &Affine, shade: impl FnMut(usize, f32, f32) -> Rgba) {
+        let vertices = [
+            transform.apply(0.0, 0.0),
+            transform.apply(1.0, 0.0),
+            transform.apply(1.0, 1.0),
+            transform.apply(0.0, 1.0),
+        ];
+        self.fill_triangles(&vertices, &[0, 1, 2, 0, 2, 3], shade);
+    }
+
+    /// Composites another target of the same size onto this one.
+    fn composite(&mut self, layer: &Target, blend_mode: BlendMode) {
+        for index in 0..self.pixmap.len() {
+            let src = layer.pixmap.get(index);
+            match self.mask_state {
+                MaskState::NoMask => {}
+                MaskState::DrawMaskStencil => {
+                    if src[3] > 0.0 {
+                        self.cover_mask(index);
+                    }
+                    continue;
+                }
+                MaskState::DrawMaskedContent => {
+                    if !self.is_masked_in(index) {
+                        continue;
+                    }
+                }
+                MaskState::ClearMaskStencil => return,
+            }
+            let dst = self.pixmap.get(index);
+            self.pixmap.set(index, blend(blend_mode, src, dst));
+        }
+    }
+
+    fn sample_transform(&self, matrix: &Matrix) -> Affine {
+        Affine::from_matrix(matrix, self.scale as f32)
+    }
+}
+
+/// Calls `plot` for every sample whose center lies inside the given triangle.
+///
+/// Samples that lie exactly on an edge are assigned to only one of the triangles
+/// sharing that edge, so that adjacent triangles of a tessellated shape never
+/// draw the same sample twice.
+fn rasterize_triangle(
+    a: (f32, f32),
+    b: (f32, f32),
+    c: (f32, f32),
+    width: u32,
+    height: u32,
+    mut plot: impl FnMut(u32, u32),
+) {
+    let (ax, ay) = (f64::from(a.0), f64::from(a.1));
+    let (mut bx, mut by) = (f64::from(b.0), f64::from(b.1));
+    let (mut cx, mut cy) = (f64::from(c.0), f64::from(c.1));
+
+    let area = (bx - ax) * (cy - ay) - (by - ay) * (cx - ax);
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    if area < 0.0 {
+        std::mem::swap(&mut bx, &mut cx);
+        std::mem::swap(&mut by, &mut cy);
+    }
+
+    let min_x = ax.min(bx).min(cx).floor().max(0.0);
+    let min_y = ay.min(by).min(cy).floor().max(0.0);
+    let max_x = ax.max(bx).max(cx).ceil().min(f64::from(width));
+    let max_y = ay.max(by).max(cy).ceil().min(f64::from(height));
+    if min_x >= max_x || min_y >= max_y {
+        return;
+    }
+
+    let edges = [
+        Edge::new(ax, ay, bx, by),
+        Edge::new(bx, by, cx, cy),
+        Edge::new(cx, cy, ax, ay),
+    ];
+
+    for y in min_y as u32..max_y as u32 {
+        let py = f64::from(y) + 0.5;
+        for x in min_x as u32..max_x as u32 {
+            let px = f64::from(x) + 0.5;
+            if edges.iter().all(|edge| edge.contains(px, py)) {
+                plot(x, y);
+            }
+        }
+    }
+}
+
+struct Edge {
+    x: f64,
+    y: f64,
+    dx: f64,
+    dy: f64,
+    inclusive: bool,
+}
+
+impl Edge {
+    fn new(x0: f64, y0: f64, x1: f64, y1: f64) -> Self {
+        let dx = x1 - x0;
+        let dy = y1 - y0;
+        Self {
+            x: x0,
+            y: y0,
+            dx,
+            dy,
+            // The same edge is traversed in the opposite direction by the neighbouring
+            // triangle, so exactly one of them will include samples lying on it.
+            inclusive: dy > 0.0 || (dy == 0.0 && dx < 0.0),
+        }
+    }
+
+    #[inline]
+    fn contains(&self, px: f64, py: f64) -> bool {
+        let w = self.dx * (py - self.y) - self.dy * (px - self.x);
+        w > 0.0 || (w == 0.0 && self.inclusive)
+    }
+}
+
+impl CommandHandler for Target {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let texture = as_texture(&bitmap);
+        let pixmap = texture.read();
+
+        if let Some(matrix3d) = &transform.matrix3d {
+            let size = Matrix3D::scale(pixmap.width().into(), pixmap.height().into(), 1.0);
+            let world = Projective::from_matrix3d(&(*matrix3d * size), self.scale.into());
+            let Some(inverse) = world.inverse() else {
+                return;
+            };
+            let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+            let [Some(a), Some(b), Some(c), Some(d)] = corners.map(|(x, y)| world.apply(x, y))
+            else {
+                return;
+            };
+            let color_transform = ColorTransformer::new(&transform.color_transform);
+            self.fill_triangles(&[a, b, c, d], &[0, 1, 2, 0, 2, 3], |_, x, y| {
+                let (u, v) = inverse.apply(x, y).unwrap_or_default();
+                color_transform.transform_premultiplied(pixmap.sample(u, v, smoothing, false))
+            });
+            return;
+        }
+
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        matrix *= Matrix::scale(pixmap.width() as f32, pixmap.height() as f32);
+
+        let world = self.sample_transform(&matrix);
+        let Some(inverse) = world.inverse() else {
+            return;
+        };
+        let color_transform = ColorTransformer::new(&transform.color_transform);
+
+        self.fill_quad(&world, |_, x, y| {
+            let (u, v) = inverse.apply(x, y);
+            color_transform.transform_premultiplied(pixmap.sample(u, v, smoothing, false))
+        });
+    }
+
+    fn render_stage3d(&mut self, _bitmap: BitmapHandle, _transform: Transform) {
+        // `create_context3d` always fails here, so there is never any Stage3D content to draw.
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let mesh = as_mesh(&shape);
+        let world = self.sample_transform(&transform.matrix);
+        let Some(inverse) = world.inverse() else {
+            return;
+        };
+        let color_transform = ColorTransformer::new(&transform.color_transform);
+        let drawing_mask = self.is_drawing_mask();
+
+        for draw in &mesh.draws {
+            // Ignore strokes when drawing a mask.
+            let indices = if drawing_mask {
+                &draw.indices[..draw.mask_index_count]
+            } else {
+                &draw.indices[..]
+            };
+            if indices.is_empty() {
+                continue;
+            }
+
+            let vertices: Vec<_> = draw
+                .positions
+                .iter()
+                .map(|&(x, y)| world.apply(x, y))
+                .collect();
+
+            match &draw.draw_type {
+                DrawType::Color => {
+                    self.fill_triangles(&vertices, indices, |first, _, _| {
+                        color_transform.transform_straight(draw.colors[first])
+                    });
+                }
+                DrawType::Gradient(gradient) => {
+                    let paint = GradientPaint::new(gradient, &color_transform);
+                    self.fill_triangles(&vertices, indices, |_, x, y| {
+                        let (x, y) = inverse.apply(x, y);
+                        paint.sample(x, y)
+                    });
+                }
+                DrawType::Bitmap(bitmap) => {
+                    let Some(handle) = &bitmap.handle else {
+                        tracing::warn!("Tried to render a handleless bitmap");
+                        continue;
+                    };
+                    let texture = as_texture(handle);
+                    let pixmap = texture.read();
+                    let uv_transform = bitmap.matrix.after(&inverse);
+                    self.fill_triangles(&vertices, indices, |_, x, y| {
+                        let (u, v) = uv_transform.apply(x, y);
+                        color_transform.transform_premultiplied(pixmap.sample(
+                            u,
+                            v,
+                            bitmap.is_smoothed,
+                            bitmap.is_repeating,
+                        ))
+                    });
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let world = self.sample_transform(&matrix);
+        let [r, g, b, a] = color_to_rgba(&color);
+        let color = [r * a, g * a, b * a, a];
+        self.fill_quad(&world, |_, _, _| color);
+    }
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
+        );
+        self.masks.push(vec![false; self.pixmap.len()]);
+        self.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::DrawMaskStencil);
+        self.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::DrawMaskedContent);
+        self.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::ClearMaskStencil);
+        self.masks.pop();
+        self.mask_state = if self.masks.is_empty() {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let blend_mode = match blend_mode {
+            RenderBlendMode::Builtin(blend_mode) => blend_mode,
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend modes are not supported by the software renderer");
+                BlendMode::Normal
+            }
+        };
+
+        // Blended content is drawn into its own layer, with its own masks, and then
+        // composited onto this target through our current mask.
+        let mut layer = Target {
+            pixmap: Pixmap::new(self.pixmap.width(), self.pixmap.height()),
+            scale: self.scale,
+            masks: Vec::new(),
+            mask_state: MaskState::NoMask,
+        };
+        commands.execute(&mut layer);
+        self.composite(&layer, blend_mode);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coverage(vertices: &[(f32, f32)], indices: &[u32]) -> Vec<u32> {
+        let mut counts = vec![0; 16];
+        for triangle in indices.chunks_exact(3) {
+            rasterize_triangle(
+                vertices[triangle[0] as usize],
+                vertices[triangle[1] as usize],
+                vertices[triangle[2] as usize],
+                4,
+                4,
+                |x, y| counts[(y * 4 + x) as usize] += 1,
+            );
+        }
+        counts
+    }
+
+    #[test]
+    fn adjacent_triangles_cover_samples_once() {
+        // Two triangles sharing a diagonal that passes exactly through sample centers.
+        let vertices = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)];
+        let counts = coverage(&vertices, &[0, 1, 2, 0, 2, 3]);
+        assert!(counts.iter().all(|&count| count == 1), "{counts:?}");
+
+        // Winding order shouldn't matter.
+        let counts = coverage(&vertices, &[2, 1, 0, 3, 2, 0]);
+        assert!(counts.iter().all(|&count| count == 1), "{counts:?}");
+    }
+
+    #[test]
+    fn masked_content_is_clipped() {
+        let mut target = Target::new(2, 1, 1);
+        target.push_mask();
+        target.draw_rect(Color::WHITE, Matrix::scale(1.0, 1.0));
+        target.activate_mask();
+        target.draw_rect(Color::RED, Matrix::scale(2.0, 1.0));
+        target.deactivate_mask();
+        target.draw_rect(Color::WHITE, Matrix::scale(1.0, 1.0));
+        target.pop_mask();
+
+        let result = target.resolve();
+        assert_eq!(result.data(), &[255, 0, 0, 255, 0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::pixmap::Pixmap;
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl, RgbaBufRead, SyncHandle};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A bitmap owned by the software renderer.
+#[derive(Debug)]
+pub struct Texture {
+    pixmap: RwLock<Pixmap>,
+}
+
+impl BitmapHandleImpl for Texture {}
+
+impl Texture {
+    pub fn new(pixmap: Pixmap) -> Self {
+        Self {
+            pixmap: RwLock::new(pixmap),
+        }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, Pixmap> {
+        self.pixmap
+            .read()
+            .expect("Texture lock should not be poisoned")
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, Pixmap> {
+        self.pixmap
+            .write()
+            .expect("Texture lock should not be poisoned")
+    }
+}
+
+pub fn as_texture(handle: &BitmapHandle) -> &Texture {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a software texture")
+}
+
+/// The result of an offscreen render. Rendering happens synchronously, so this simply
+/// holds on to a copy of the rendered region.
+#[derive(Debug)]
+pub struct SoftwareSyncHandle {
+    pub data: Vec<u8>,
+    pub width: u32,
+}
+
+impl SyncHandle for SoftwareSyncHandle {
+    fn retrieve_offscreen_texture(
+        self: Box<Self>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), ruffle_render::error::Error> {
+        with_rgba(&self.data, self.width * 4);
+        Ok(())
+    }
+}
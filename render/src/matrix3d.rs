@@ -0,0 +1,531 @@
+use crate::matrix::Matrix;
+use swf::{Point, Twips};
+
+/// A 3D transformation matrix, as used by `flash.geom.Matrix3D`.
+///
+/// The values are stored in column-major order (like `Matrix3D.rawData`), and points are
+/// treated as column vectors. Translations are in pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3D {
+    pub raw_data: [f64; 16],
+}
+
+/// The result of decomposing a `Matrix3D`, matching `Matrix3D.decompose` with
+/// `Orientation3D.EULER_ANGLES`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Components3D {
+    pub translation: [f64; 3],
+    /// The rotation around the X, Y and Z axes in radians, applied in that order.
+    pub rotation: [f64; 3],
+    pub scale: [f64; 3],
+}
+
+impl Default for Components3D {
+    fn default() -> Self {
+        Self {
+            translation: [0.0; 3],
+            rotation: [0.0; 3],
+            scale: [1.0; 3],
+        }
+    }
+}
+
+impl Matrix3D {
+    #[rustfmt::skip]
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ],
+    };
+
+    /// Creates a 3D matrix that performs the same transformation as a 2D matrix, leaving z untouched.
+    pub fn from_2d(matrix: &Matrix) -> Self {
+        let mut result = Self::IDENTITY;
+        result.raw_data[0] = matrix.a.into();
+        result.raw_data[1] = matrix.b.into();
+        result.raw_data[4] = matrix.c.into();
+        result.raw_data[5] = matrix.d.into();
+        result.raw_data[12] = matrix.tx.to_pixels();
+        result.raw_data[13] = matrix.ty.to_pixels();
+        result
+    }
+
+    /// Flattens this matrix into a 2D matrix by discarding everything involving the z axis.
+    pub fn to_2d(&self) -> Matrix {
+        Matrix {
+            a: self.raw_data[0] as f32,
+            b: self.raw_data[1] as f32,
+            c: self.raw_data[4] as f32,
+            d: self.raw_data[5] as f32,
+            tx: Twips::from_pixels(self.raw_data[12]),
+            ty: Twips::from_pixels(self.raw_data[13]),
+        }
+    }
+
+    /// Replaces the 2D part of this matrix (the x/y scale, rotation, skew and translation).
+    pub fn set_2d(&mut self, matrix: &Matrix) {
+        self.raw_data[0] = matrix.a.into();
+        self.raw_data[1] = matrix.b.into();
+        self.raw_data[4] = matrix.c.into();
+        self.raw_data[5] = matrix.d.into();
+        self.raw_data[12] = matrix.tx.to_pixels();
+        self.raw_data[13] = matrix.ty.to_pixels();
+    }
+
+    #[rustfmt::skip]
+    pub fn translate(x: f64, y: f64, z: f64) -> Self {
+        Self {
+            raw_data: [
+                1.0, 0.0, 0.0, 0.0,
+                0.0, 1.0, 0.0, 0.0,
+                0.0, 0.0, 1.0, 0.0,
+                x,   y,   z,   1.0,
+            ],
+        }
+    }
+
+    #[rustfmt::skip]
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        Self {
+            raw_data: [
+                x,   0.0, 0.0, 0.0,
+                0.0, y,   0.0, 0.0,
+                0.0, 0.0, z,   0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ],
+        }
+    }
+
+    #[rustfmt::skip]
+    pub fn rotate_x(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            raw_data: [
+                1.0, 0.0,  0.0, 0.0,
+                0.0, cos,  sin, 0.0,
+                0.0, -sin, cos, 0.0,
+                0.0, 0.0,  0.0, 1.0,
+            ],
+        }
+    }
+
+    #[rustfmt::skip]
+    pub fn rotate_y(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            raw_data: [
+                cos, 0.0, -sin, 0.0,
+                0.0, 1.0, 0.0,  0.0,
+                sin, 0.0, cos,  0.0,
+                0.0, 0.0, 0.0,  1.0,
+            ],
+        }
+    }
+
+    #[rustfmt::skip]
+    pub fn rotate_z(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            raw_data: [
+                cos,  sin, 0.0, 0.0,
+                -sin, cos, 0.0, 0.0,
+                0.0,  0.0, 1.0, 0.0,
+                0.0,  0.0, 0.0, 1.0,
+            ],
+        }
+    }
+
+    /// Builds a matrix that scales, then rotates around X, Y and Z, and then translates.
+    /// This is the inverse of `decompose`.
+    pub fn compose(components: &Components3D) -> Self {
+        let [tx, ty, tz] = components.translation;
+        let [rx, ry, rz] = components.rotation;
+        let [sx, sy, sz] = components.scale;
+        Self::translate(tx, ty, tz)
+            * Self::rotate_z(rz)
+            * Self::rotate_y(ry)
+            * Self::rotate_x(rx)
+            * Self::scale(sx, sy, sz)
+    }
+
+    /// Splits this matrix into a translation, rotation and scale.
+    /// Any skew or projection in the matrix is lost.
+    pub fn decompose(&self) -> Components3D {
+        let m = &self.raw_data;
+        let column_length =
+            |i: usize| (m[i] * m[i] + m[i + 1] * m[i + 1] + m[i + 2] * m[i + 2]).sqrt();
+        let mut scale = [column_length(0), column_length(4), column_length(8)];
+        if self.determinant_3x3() < 0.0 {
+            scale[0] = -scale[0];
+        }
+
+        // Element at `row` of the rotation part of column `column`.
+        let rotation = |row: usize, column: usize| {
+            let scale = scale[column];
+            if scale == 0.0 {
+                0.0
+            } else {
+                m[column * 4 + row] / scale
+            }
+        };
+
+        let ry = (-rotation(2, 0)).clamp(-1.0, 1.0).asin();
+        let (rx, rz) = if ry.cos().abs() > 1e-6 {
+            (
+                f64::atan2(rotation(2, 1), rotation(2, 2)),
+                f64::atan2(rotation(1, 0), rotation(0, 0)),
+            )
+        } else {
+            // Gimbal lock: the X and Z rotations are around the same axis, so put it all in X.
+            (f64::atan2(-rotation(1, 2), rotation(1, 1)), 0.0)
+        };
+
+        Components3D {
+            translation: [m[12], m[13], m[14]],
+            rotation: [rx, ry, rz],
+            scale,
+        }
+    }
+
+    fn determinant_3x3(&self) -> f64 {
+        let m = &self.raw_data;
+        m[0] * (m[5] * m[10] - m[9] * m[6]) - m[4] * (m[1] * m[10] - m[9] * m[2])
+            + m[8] * (m[1] * m[6] - m[5] * m[2])
+    }
+
+    /// Transforms a point, returning its homogeneous coordinates without dividing by `w`.
+    pub fn transform_point(&self, point: [f64; 3]) -> [f64; 4] {
+        let m = &self.raw_data;
+        let [x, y, z] = point;
+        std::array::from_fn(|row| m[row] * x + m[4 + row] * y + m[8 + row] * z + m[12 + row])
+    }
+
+    /// Whether this matrix maps the z = 0 plane without foreshortening it,
+    /// meaning that `w` is the same everywhere on that plane.
+    pub fn is_affine_on_plane(&self) -> bool {
+        let m = &self.raw_data;
+        let epsilon = m[15].abs() * 1e-9;
+        m[3].abs() <= epsilon && m[7].abs() <= epsilon
+    }
+
+    /// The columns of this matrix, as used for the world matrix of GPU draws.
+    pub fn to_columns(&self) -> [[f32; 4]; 4] {
+        std::array::from_fn(|column| {
+            std::array::from_fn(|row| self.raw_data[column * 4 + row] as f32)
+        })
+    }
+
+    /// Projects the z = 0 plane of an object with this matrix into final coordinates,
+    /// for an object whose parent has the (already concatenated) transform `parent`.
+    ///
+    /// Unlike `project`, this keeps the foreshortening: the `x` and `y` of the result must
+    /// be divided by its `w` for every point of the plane separately. The `z` of the result
+    /// is always zero.
+    pub fn project_plane(
+        &self,
+        parent: &Matrix,
+        perspective: Option<&PerspectiveProjection>,
+    ) -> Matrix3D {
+        let (a, b, c, d) = (
+            f64::from(parent.a),
+            f64::from(parent.b),
+            f64::from(parent.c),
+            f64::from(parent.d),
+        );
+        let (tx, ty) = (parent.tx.to_pixels(), parent.ty.to_pixels());
+        // See `project` for why z is scaled along with the parent.
+        let z_scale = (a * d - b * c).abs().sqrt();
+
+        // A point `p` at depth `z` lands on `center + (p - center) * f / (f + z)`, which is
+        // `(f * p + center * z) / (f + z)` in homogeneous coordinates.
+        #[rustfmt::skip]
+        let projection = match perspective {
+            Some(perspective) => {
+                let f = perspective.focal_length;
+                let (cx, cy) = perspective.center;
+                Self {
+                    raw_data: [
+                        f * a,        f * b,        0.0, 0.0,
+                        f * c,        f * d,        0.0, 0.0,
+                        cx * z_scale, cy * z_scale, 0.0, z_scale,
+                        f * tx,       f * ty,       0.0, f,
+                    ],
+                }
+            }
+            None => Self {
+                raw_data: [
+                    a,   b,   0.0, 0.0,
+                    c,   d,   0.0, 0.0,
+                    0.0, 0.0, 0.0, 0.0,
+                    tx,  ty,  0.0, 1.0,
+                ],
+            },
+        };
+        projection * *self
+    }
+
+    /// Projects this matrix into a 2D matrix, for an object whose parent has the
+    /// (already concatenated) transform `parent`.
+    ///
+    /// Both the homogeneous `w` of this matrix and the `perspective` are divided through,
+    /// but only at the local origin of the object: the result is the linear approximation
+    /// of the projection around that point. This is what the descendants of the object and
+    /// everything else that needs a 2D matrix use. The object itself is drawn with the
+    /// exact projection from `project_plane` instead, if it's foreshortened.
+    pub fn project(&self, parent: &Matrix, perspective: Option<&PerspectiveProjection>) -> Matrix {
+        let m = &self.raw_data;
+        let w = m[15];
+        if w <= 0.0 {
+            return Matrix::ZERO;
+        }
+        // The derivative of `column * u / (w + w_u * u)` at `u = 0`, for each local axis.
+        let origin = [m[12] / w, m[13] / w, m[14] / w];
+        let axis = |i: usize| {
+            let w_axis = m[i + 3] / w;
+            [
+                m[i] / w - origin[0] * w_axis,
+                m[i + 1] / w - origin[1] * w_axis,
+                m[i + 2] / w - origin[2] * w_axis,
+            ]
+        };
+        let (axis_x, axis_y) = (axis(0), axis(4));
+
+        let (a, b, c, d) = (
+            f64::from(parent.a),
+            f64::from(parent.b),
+            f64::from(parent.c),
+            f64::from(parent.d),
+        );
+        // Z values are scaled along with the parent, so that they stay comparable to the focal length.
+        let z_scale = (a * d - b * c).abs().sqrt();
+        let linear = |x: f64, y: f64, z: f64| (a * x + c * y, b * x + d * y, z * z_scale);
+
+        let (ox, oy, oz) = linear(origin[0], origin[1], origin[2]);
+        let (ox, oy) = (ox + parent.tx.to_pixels(), oy + parent.ty.to_pixels());
+        let axis_x = linear(axis_x[0], axis_x[1], axis_x[2]);
+        let axis_y = linear(axis_y[0], axis_y[1], axis_y[2]);
+
+        let ((ox, oy), (xx, xy), (yx, yy)) = match perspective {
+            Some(perspective) => {
+                let (cx, cy) = perspective.center;
+                let focal_length = perspective.focal_length;
+                let depth = focal_length + oz;
+                if depth <= 0.0 {
+                    // The object is behind the viewer, so nothing of it is visible.
+                    return Matrix::ZERO;
+                }
+                let k = focal_length / depth;
+                let dk = -focal_length / (depth * depth);
+                let (rx, ry) = (ox - cx, oy - cy);
+                (
+                    (cx + rx * k, cy + ry * k),
+                    (
+                        axis_x.0 * k + rx * dk * axis_x.2,
+                        axis_x.1 * k + ry * dk * axis_x.2,
+                    ),
+                    (
+                        axis_y.0 * k + rx * dk * axis_y.2,
+                        axis_y.1 * k + ry * dk * axis_y.2,
+                    ),
+                )
+            }
+            None => ((ox, oy), (axis_x.0, axis_x.1), (axis_y.0, axis_y.1)),
+        };
+
+        Matrix {
+            a: xx as f32,
+            b: xy as f32,
+            c: yx as f32,
+            d: yy as f32,
+            tx: Twips::from_pixels(ox),
+            ty: Twips::from_pixels(oy),
+        }
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl std::ops::Mul for Matrix3D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut raw_data = [0.0; 16];
+        for column in 0..4 {
+            for row in 0..4 {
+                raw_data[column * 4 + row] = (0..4)
+                    .map(|i| self.raw_data[i * 4 + row] * rhs.raw_data[column * 4 + i])
+                    .sum();
+            }
+        }
+        Self { raw_data }
+    }
+}
+
+/// A perspective projection, as used by `flash.geom.PerspectiveProjection`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The field of view in degrees, kept only to report it back to ActionScript.
+    pub field_of_view: f64,
+
+    /// The distance between the viewer and the z = 0 plane, in pixels.
+    pub focal_length: f64,
+
+    /// The vanishing point, in pixels.
+    pub center: (f64, f64),
+}
+
+impl PerspectiveProjection {
+    /// The field of view Flash uses when nothing else was specified.
+    pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+    /// Creates a projection for a view of the given width, as Flash derives the focal length
+    /// from the field of view and the width of the stage.
+    pub fn from_field_of_view(field_of_view: f64, width: f64, center: (f64, f64)) -> Self {
+        Self {
+            field_of_view,
+            focal_length: (width / 2.0) / (field_of_view.to_radians() / 2.0).tan(),
+            center,
+        }
+    }
+
+    /// Converts this projection into the coordinate space described by `matrix`.
+    pub fn transformed(&self, matrix: &Matrix) -> Self {
+        let center = *matrix
+            * Point::new(
+                Twips::from_pixels(self.center.0),
+                Twips::from_pixels(self.center.1),
+            );
+        Self {
+            field_of_view: self.field_of_view,
+            focal_length: self.focal_length * f64::from(matrix.determinant()).abs().sqrt(),
+            center: (center.x.to_pixels(), center.y.to_pixels()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(left: [f64; 3], right: [f64; 3]) {
+        for (l, r) in left.iter().zip(right.iter()) {
+            assert!((l - r).abs() < 1e-9, "{left:?} != {right:?}");
+        }
+    }
+
+    #[test]
+    fn decompose_round_trips() {
+        let components = Components3D {
+            translation: [10.0, -20.0, 30.0],
+            rotation: [0.3, -0.7, 1.2],
+            scale: [2.0, 0.5, 1.5],
+        };
+        let decomposed = Matrix3D::compose(&components).decompose();
+        assert_close(decomposed.translation, components.translation);
+        assert_close(decomposed.rotation, components.rotation);
+        assert_close(decomposed.scale, components.scale);
+    }
+
+    #[test]
+    fn rotate_z_matches_2d_rotation() {
+        let angle = 0.5f32;
+        let matrix = Matrix3D::rotate_z(angle.into()).to_2d();
+        let expected = Matrix::rotate(angle);
+        assert!((matrix.a - expected.a).abs() < 1e-6);
+        assert!((matrix.b - expected.b).abs() < 1e-6);
+        assert!((matrix.c - expected.c).abs() < 1e-6);
+        assert!((matrix.d - expected.d).abs() < 1e-6);
+    }
+
+    #[test]
+    fn project_without_depth_is_2d() {
+        let parent = Matrix::scale(2.0, 2.0);
+        let local = Matrix {
+            tx: Twips::from_pixels(10.0),
+            ..Matrix::rotate(0.25)
+        };
+        let perspective = PerspectiveProjection::from_field_of_view(55.0, 500.0, (250.0, 250.0));
+        let projected = Matrix3D::from_2d(&local).project(&parent, Some(&perspective));
+        let expected = parent * local;
+        assert!((projected.a - expected.a).abs() < 1e-6);
+        assert!((projected.d - expected.d).abs() < 1e-6);
+        assert!((projected.tx - expected.tx).to_pixels().abs() < 0.1);
+    }
+
+    #[test]
+    fn project_divides_by_w() {
+        let mut matrix = Matrix3D::IDENTITY;
+        matrix.raw_data[12] = 40.0;
+        matrix.raw_data[15] = 2.0;
+        let projected = matrix.project(&Matrix::IDENTITY, None);
+        assert!((projected.a - 0.5).abs() < 1e-6);
+        assert!((projected.d - 0.5).abs() < 1e-6);
+        assert!((projected.tx.to_pixels() - 20.0).abs() < 0.1);
+
+        // With `w` growing along the x axis, the x axis shrinks towards the origin.
+        matrix.raw_data[3] = 0.01;
+        let projected = matrix.project(&Matrix::IDENTITY, None);
+        assert!((projected.a - 0.4).abs() < 1e-6);
+        assert!((projected.b - 0.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn project_plane_matches_project_at_origin() {
+        let parent = Matrix {
+            tx: Twips::from_pixels(30.0),
+            ty: Twips::from_pixels(-10.0),
+            ..Matrix::scale(2.0, 2.0)
+        };
+        let perspective = PerspectiveProjection::from_field_of_view(55.0, 500.0, (250.0, 250.0));
+        let matrix = Matrix3D::translate(20.0, 40.0, 100.0) * Matrix3D::rotate_y(0.6);
+        let plane = matrix.project_plane(&parent, Some(&perspective));
+        let linear = matrix.project(&parent, Some(&perspective));
+
+        let [x, y, _, w] = plane.transform_point([0.0, 0.0, 0.0]);
+        assert!((x / w - linear.tx.to_pixels()).abs() < 0.1);
+        assert!((y / w - linear.ty.to_pixels()).abs() < 0.1);
+        // Away from the origin, the exact projection differs from the linear approximation.
+        assert!(!plane.is_affine_on_plane());
+        let [x, _, _, w] = plane.transform_point([100.0, 0.0, 0.0]);
+        let approximated = linear * Point::new(Twips::from_pixels(100.0), Twips::ZERO);
+        assert!((x / w - approximated.x.to_pixels()).abs() > 1.0);
+    }
+
+    #[test]
+    fn project_plane_foreshortens_far_edges() {
+        // A square rotated around its left edge, so that its right edge moves away from the viewer.
+        let perspective = PerspectiveProjection::from_field_of_view(55.0, 500.0, (0.0, 50.0));
+        let matrix = Matrix3D::rotate_y(-0.5);
+        let plane = matrix.project_plane(&Matrix::IDENTITY, Some(&perspective));
+        let project = |x: f64, y: f64| {
+            let [px, py, _, w] = plane.transform_point([x, y, 0.0]);
+            (px / w, py / w)
+        };
+        let left = project(0.0, 100.0).1 - project(0.0, 0.0).1;
+        let right = project(100.0, 100.0).1 - project(100.0, 0.0).1;
+        assert!((left - 100.0).abs() < 1e-9);
+        assert!(right < left);
+    }
+
+    #[test]
+    fn project_plane_without_perspective_is_affine() {
+        let matrix = Matrix3D::rotate_y(0.5);
+        let plane = matrix.project_plane(&Matrix::IDENTITY, None);
+        assert!(plane.is_affine_on_plane());
+        let [x, _, _, w] = plane.transform_point([100.0, 0.0, 0.0]);
+        assert!((x / w - 100.0 * 0.5f64.cos()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn default_focal_length() {
+        let perspective = PerspectiveProjection::from_field_of_view(55.0, 500.0, (250.0, 250.0));
+        assert!((perspective.focal_length - 480.25).abs() < 0.01);
+    }
+}
@@ -0,0 +1,523 @@
+//! Nine-slice scaling (`scale9Grid`).
+//!
+//! A scaling grid splits the content of a display object into nine regions. When the object is
+//! scaled, the corners keep their size, the edges only stretch along one axis, and the center
+//! region absorbs the rest of the scale.
+//!
+//! The object's own matrix still applies the full scale when rendering, so we instead distort the
+//! content in its local space with a piecewise linear mapping that undoes the scale in the border
+//! regions. Every edge is split where it crosses a grid line first, so that each piece is affected
+//! by a single affine transform and stays exact.
+
+use crate::matrix::Matrix;
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use swf::{Point, Rectangle, Twips};
+
+type Vec2 = (f64, f64);
+
+/// The mapping along one axis: `bounds` are the edges of the content, and `grid` are the grid
+/// lines, which end up at `mapped_grid`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct AxisMapping {
+    bounds: (f64, f64),
+    grid: (f64, f64),
+    mapped_grid: (f64, f64),
+}
+
+impl AxisMapping {
+    fn new(bounds: (f64, f64), grid: (f64, f64), scale: f64) -> Option<Self> {
+        // The grid is clamped to the content, as nothing outside of it can be stretched.
+        let grid = (grid.0.max(bounds.0), grid.1.min(bounds.1));
+        if grid.1 <= grid.0 || scale == 0.0 || !scale.is_finite() {
+            return None;
+        }
+        let scale = scale.abs();
+        // Rounded to whole twips, as finer positions aren't visible anyway, and this lets tiny
+        // changes in scale reuse the same distorted shape.
+        let mapped_grid = (
+            (bounds.0 + (grid.0 - bounds.0) / scale).round(),
+            (bounds.1 - (bounds.1 - grid.1) / scale).round(),
+        );
+        if mapped_grid.1 < mapped_grid.0 {
+            // The object is too small to fit its borders, so they get scaled down evenly.
+            let total = (grid.0 - bounds.0) + (bounds.1 - grid.1);
+            let split = (bounds.0 + (bounds.1 - bounds.0) * (grid.0 - bounds.0) / total).round();
+            return Some(Self {
+                bounds,
+                grid,
+                mapped_grid: (split, split),
+            });
+        }
+        Some(Self {
+            bounds,
+            grid,
+            mapped_grid,
+        })
+    }
+
+    /// The region (0 to 2) that the given coordinate falls into.
+    fn region(&self, value: f64) -> usize {
+        if value < self.grid.0 {
+            0
+        } else if value <= self.grid.1 {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Maps a coordinate with the affine transform of the given region.
+    fn map(&self, value: f64, region: usize) -> f64 {
+        let (from_start, from_end, to_start, to_end) = match region {
+            0 => (
+                self.bounds.0,
+                self.grid.0,
+                self.bounds.0,
+                self.mapped_grid.0,
+            ),
+            1 => (
+                self.grid.0,
+                self.grid.1,
+                self.mapped_grid.0,
+                self.mapped_grid.1,
+            ),
+            _ => (
+                self.grid.1,
+                self.bounds.1,
+                self.mapped_grid.1,
+                self.bounds.1,
+            ),
+        };
+        if from_end == from_start {
+            return to_start;
+        }
+        to_start + (value - from_start) * (to_end - to_start) / (from_end - from_start)
+    }
+}
+
+/// Distorts content so that it is nine-slice scaled when rendered with a scaled matrix.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scale9Mapping {
+    x: AxisMapping,
+    y: AxisMapping,
+}
+
+impl Scale9Mapping {
+    /// Creates the mapping for an object with the content `bounds` and the scaling `grid`,
+    /// both in local space, whose matrix scales it by `scale_x` and `scale_y`.
+    ///
+    /// Returns `None` if the grid is not usable, in which case the content should be
+    /// scaled normally.
+    pub fn new(
+        bounds: &Rectangle<Twips>,
+        grid: &Rectangle<Twips>,
+        scale_x: f64,
+        scale_y: f64,
+    ) -> Option<Self> {
+        if !bounds.is_valid() || !grid.is_valid() {
+            return None;
+        }
+        let x = AxisMapping::new(
+            (bounds.x_min.get() as f64, bounds.x_max.get() as f64),
+            (grid.x_min.get() as f64, grid.x_max.get() as f64),
+            scale_x,
+        )?;
+        let y = AxisMapping::new(
+            (bounds.y_min.get() as f64, bounds.y_max.get() as f64),
+            (grid.y_min.get() as f64, grid.y_max.get() as f64),
+            scale_y,
+        )?;
+        Some(Self { x, y })
+    }
+
+    /// Whether this mapping leaves everything in place, which happens when the object isn't scaled.
+    pub fn is_identity(&self) -> bool {
+        self.x.mapped_grid == self.x.grid && self.y.mapped_grid == self.y.grid
+    }
+
+    /// Maps a shape whose coordinates are transformed into the grid's space by `to_grid`.
+    pub fn map_shape<'a>(
+        &self,
+        shape: &DistilledShape<'a>,
+        to_grid: &Matrix,
+    ) -> DistilledShape<'a> {
+        let paths = shape
+            .paths
+            .iter()
+            .map(|path| match path {
+                DrawPath::Stroke {
+                    style,
+                    is_closed,
+                    commands,
+                } => DrawPath::Stroke {
+                    style: *style,
+                    is_closed: *is_closed,
+                    commands: self.map_commands(commands, to_grid),
+                },
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => DrawPath::Fill {
+                    style: *style,
+                    commands: self.map_commands(commands, to_grid),
+                    winding_rule: *winding_rule,
+                },
+            })
+            .collect();
+        DistilledShape {
+            paths,
+            shape_bounds: shape.shape_bounds.clone(),
+            edge_bounds: shape.edge_bounds.clone(),
+            id: shape.id,
+        }
+    }
+
+    /// Maps draw commands whose coordinates are transformed into the grid's space by `to_grid`.
+    /// The result is in the original space of the commands.
+    pub fn map_commands(&self, commands: &[DrawCommand], to_grid: &Matrix) -> Vec<DrawCommand> {
+        let Some(from_grid) = to_grid.inverse() else {
+            return commands.to_vec();
+        };
+        let to_local = |point: Vec2| {
+            let (x, y) = apply_matrix(&from_grid, point);
+            Point::new(Twips::new(x.round() as i32), Twips::new(y.round() as i32))
+        };
+
+        let mut result = Vec::with_capacity(commands.len());
+        let mut cursor = (0.0, 0.0);
+        for command in commands {
+            let mut points = vec![cursor];
+            match command {
+                DrawCommand::MoveTo(point) => {
+                    cursor = apply_matrix(to_grid, twips_point(point));
+                    let region = self.region(cursor);
+                    result.push(DrawCommand::MoveTo(to_local(self.map(cursor, region))));
+                    continue;
+                }
+                DrawCommand::LineTo(point) => points.push(twips_point(point)),
+                DrawCommand::QuadraticCurveTo { control, anchor } => {
+                    points.extend([twips_point(control), twips_point(anchor)])
+                }
+                DrawCommand::CubicCurveTo {
+                    control_a,
+                    control_b,
+                    anchor,
+                } => points.extend([
+                    twips_point(control_a),
+                    twips_point(control_b),
+                    twips_point(anchor),
+                ]),
+            }
+            for point in &mut points[1..] {
+                *point = apply_matrix(to_grid, *point);
+            }
+            cursor = points[points.len() - 1];
+
+            for piece in self.split(&points) {
+                // Control points may lie outside of the region of their piece,
+                // so the region is taken from a point on the curve itself.
+                let region = self.region(evaluate(&piece, 0.5));
+                let mapped: Vec<_> = piece
+                    .iter()
+                    .map(|point| to_local(self.map(*point, region)))
+                    .collect();
+                result.push(match mapped.len() {
+                    2 => DrawCommand::LineTo(mapped[1]),
+                    3 => DrawCommand::QuadraticCurveTo {
+                        control: mapped[1],
+                        anchor: mapped[2],
+                    },
+                    _ => DrawCommand::CubicCurveTo {
+                        control_a: mapped[1],
+                        control_b: mapped[2],
+                        anchor: mapped[3],
+                    },
+                });
+            }
+        }
+        result
+    }
+
+    fn region(&self, point: Vec2) -> (usize, usize) {
+        (self.x.region(point.0), self.y.region(point.1))
+    }
+
+    fn map(&self, point: Vec2, region: (usize, usize)) -> Vec2 {
+        (self.x.map(point.0, region.0), self.y.map(point.1, region.1))
+    }
+
+    /// Splits a line or curve (given as its control polygon) where it crosses a grid line.
+    fn split(&self, points: &[Vec2]) -> Vec<Vec<Vec2>> {
+        let mut splits = Vec::new();
+        for line in [self.x.grid.0, self.x.grid.1] {
+            let values: Vec<_> = points.iter().map(|p| p.0).collect();
+            splits.extend(crossings(&values, line));
+        }
+        for line in [self.y.grid.0, self.y.grid.1] {
+            let values: Vec<_> = points.iter().map(|p| p.1).collect();
+            splits.extend(crossings(&values, line));
+        }
+        splits.sort_by(|a, b| a.total_cmp(b));
+        splits.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+
+        let mut pieces = Vec::with_capacity(splits.len() + 1);
+        let mut remaining = points.to_vec();
+        let mut start = 0.0;
+        for t in splits {
+            let (head, tail) = subdivide(&remaining, (t - start) / (1.0 - start));
+            pieces.push(head);
+            remaining = tail;
+            start = t;
+        }
+        pieces.push(remaining);
+        pieces
+    }
+}
+
+/// The distorted shapes of the most recently used mappings.
+///
+/// Every distinct mapping needs its shape to be tessellated again, so this keeps a few of them
+/// around for objects that are scaled back and forth, or shared between instances of the same size.
+#[derive(Clone, Debug)]
+pub struct Scale9Cache<T> {
+    entries: Vec<(Scale9Mapping, Matrix, T)>,
+}
+
+impl<T: Clone> Scale9Cache<T> {
+    /// How many mappings are remembered before the least recently used one is dropped.
+    pub const MAX_ENTRIES: usize = 8;
+
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the value cached for `mapping` and `to_grid`, or creates and caches it with `create`.
+    pub fn get_or_insert_with(
+        &mut self,
+        mapping: &Scale9Mapping,
+        to_grid: &Matrix,
+        create: impl FnOnce() -> Option<T>,
+    ) -> Option<T> {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|(m, matrix, _)| m == mapping && matrix == to_grid)
+        {
+            let entry = self.entries.remove(index);
+            let value = entry.2.clone();
+            self.entries.insert(0, entry);
+            return Some(value);
+        }
+
+        let value = create()?;
+        self.entries.truncate(Self::MAX_ENTRIES - 1);
+        self.entries.insert(0, (*mapping, *to_grid, value.clone()));
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl<T: Clone> Default for Scale9Cache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn twips_point(point: &Point<Twips>) -> Vec2 {
+    (point.x.get() as f64, point.y.get() as f64)
+}
+
+fn apply_matrix(matrix: &Matrix, (x, y): Vec2) -> Vec2 {
+    (
+        matrix.a as f64 * x + matrix.c as f64 * y + matrix.tx.get() as f64,
+        matrix.b as f64 * x + matrix.d as f64 * y + matrix.ty.get() as f64,
+    )
+}
+
+/// Evaluates a Bézier curve of any degree with de Casteljau's algorithm.
+fn evaluate(points: &[Vec2], t: f64) -> Vec2 {
+    let mut points = points.to_vec();
+    while points.len() > 1 {
+        points = points.windows(2).map(|w| lerp(w[0], w[1], t)).collect();
+    }
+    points[0]
+}
+
+/// Splits a Bézier curve of any degree into two at `t`.
+fn subdivide(points: &[Vec2], t: f64) -> (Vec<Vec2>, Vec<Vec2>) {
+    let mut head = Vec::with_capacity(points.len());
+    let mut tail = Vec::with_capacity(points.len());
+    let mut points = points.to_vec();
+    loop {
+        head.push(points[0]);
+        tail.push(points[points.len() - 1]);
+        if points.len() == 1 {
+            break;
+        }
+        points = points.windows(2).map(|w| lerp(w[0], w[1], t)).collect();
+    }
+    tail.reverse();
+    (head, tail)
+}
+
+fn lerp(a: Vec2, b: Vec2, t: f64) -> Vec2 {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+/// Finds the parameters in `(0, 1)` where a one-dimensional Bézier curve equals `value`.
+fn crossings(values: &[f64], value: f64) -> Vec<f64> {
+    let inside = |t: f64| t > 1e-6 && t < 1.0 - 1e-6;
+    match *values {
+        [a, b] => {
+            if a == b {
+                return vec![];
+            }
+            let t = (value - a) / (b - a);
+            if inside(t) {
+                vec![t]
+            } else {
+                vec![]
+            }
+        }
+        [a, b, c] => {
+            // (a - 2b + c)t² + 2(b - a)t + (a - value) = 0
+            let qa = a - 2.0 * b + c;
+            let qb = 2.0 * (b - a);
+            let qc = a - value;
+            if qa.abs() < 1e-9 {
+                if qb == 0.0 {
+                    return vec![];
+                }
+                let t = -qc / qb;
+                return if inside(t) { vec![t] } else { vec![] };
+            }
+            let discriminant = qb * qb - 4.0 * qa * qc;
+            if discriminant < 0.0 {
+                return vec![];
+            }
+            let root = discriminant.sqrt();
+            [(-qb - root) / (2.0 * qa), (-qb + root) / (2.0 * qa)]
+                .into_iter()
+                .filter(|t| inside(*t))
+                .collect()
+        }
+        _ => {
+            // Cubics are rare enough that sampling for sign changes and bisecting is good enough.
+            const STEPS: usize = 32;
+            let at = |t: f64| {
+                evaluate(&values.iter().map(|v| (*v, 0.0)).collect::<Vec<_>>(), t).0 - value
+            };
+            let mut result = Vec::new();
+            for i in 0..STEPS {
+                let (mut lo, mut hi) = (i as f64 / STEPS as f64, (i + 1) as f64 / STEPS as f64);
+                if at(lo).signum() == at(hi).signum() {
+                    continue;
+                }
+                for _ in 0..32 {
+                    let mid = (lo + hi) / 2.0;
+                    if at(lo).signum() == at(mid).signum() {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                let t = (lo + hi) / 2.0;
+                if inside(t) {
+                    result.push(t);
+                }
+            }
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x_min: i32, y_min: i32, x_max: i32, y_max: i32) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::new(x_min),
+            y_min: Twips::new(y_min),
+            x_max: Twips::new(x_max),
+            y_max: Twips::new(y_max),
+        }
+    }
+
+    fn point(x: i32, y: i32) -> Point<Twips> {
+        Point::new(Twips::new(x), Twips::new(y))
+    }
+
+    #[test]
+    fn unscaled_is_identity() {
+        let mapping =
+            Scale9Mapping::new(&rect(0, 0, 100, 100), &rect(10, 10, 90, 90), 1.0, 1.0).unwrap();
+        assert!(mapping.is_identity());
+    }
+
+    #[test]
+    fn borders_keep_their_size() {
+        let mapping =
+            Scale9Mapping::new(&rect(0, 0, 100, 100), &rect(10, 10, 90, 90), 2.0, 1.0).unwrap();
+        let commands = [
+            DrawCommand::MoveTo(point(0, 0)),
+            DrawCommand::LineTo(point(100, 0)),
+        ];
+        let mapped = mapping.map_commands(&commands, &Matrix::IDENTITY);
+        // The line is split at both grid lines, and the left border is halved,
+        // so that it is back to 10 twips once the object is scaled by 2.
+        assert_eq!(
+            mapped,
+            vec![
+                DrawCommand::MoveTo(point(0, 0)),
+                DrawCommand::LineTo(point(5, 0)),
+                DrawCommand::LineTo(point(95, 0)),
+                DrawCommand::LineTo(point(100, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn curves_are_split_at_grid_lines() {
+        let mapping =
+            Scale9Mapping::new(&rect(0, 0, 100, 100), &rect(10, 10, 90, 90), 2.0, 2.0).unwrap();
+        let commands = [
+            DrawCommand::MoveTo(point(0, 50)),
+            DrawCommand::QuadraticCurveTo {
+                control: point(50, 0),
+                anchor: point(100, 50),
+            },
+        ];
+        let mapped = mapping.map_commands(&commands, &Matrix::IDENTITY);
+        assert_eq!(mapped.len(), 4);
+        assert_eq!(mapped[3].end_point(), point(100, 50));
+    }
+
+    #[test]
+    fn cache_reuses_and_evicts() {
+        let bounds = rect(0, 0, 10000, 10000);
+        let grid = rect(1000, 1000, 9000, 9000);
+        let mapping = |scale: f64| Scale9Mapping::new(&bounds, &grid, scale, 1.0).unwrap();
+        let mut cache = Scale9Cache::new();
+
+        let created = cache.get_or_insert_with(&mapping(2.0), &Matrix::IDENTITY, || Some(1));
+        assert_eq!(created, Some(1));
+        let reused = cache.get_or_insert_with(&mapping(2.0), &Matrix::IDENTITY, || Some(2));
+        assert_eq!(reused, Some(1));
+        // Scales that end up at the same twips share the same shape.
+        let reused = cache.get_or_insert_with(&mapping(2.0001), &Matrix::IDENTITY, || Some(3));
+        assert_eq!(reused, Some(1));
+
+        for i in 0..Scale9Cache::<i32>::MAX_ENTRIES {
+            cache.get_or_insert_with(&mapping(3.0 + i as f64), &Matrix::IDENTITY, || Some(0));
+        }
+        let recreated = cache.get_or_insert_with(&mapping(2.0), &Matrix::IDENTITY, || Some(4));
+        assert_eq!(recreated, Some(4));
+    }
+}
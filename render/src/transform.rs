@@ -1,4 +1,5 @@
 use crate::matrix::Matrix;
+use crate::matrix3d::{Matrix3D, PerspectiveProjection};
 use swf::ColorTransform;
 
 /// Represents the transform for a DisplayObject.
@@ -7,6 +8,18 @@ use swf::ColorTransform;
 pub struct Transform {
     pub matrix: Matrix,
     pub color_transform: ColorTransform,
+
+    /// The 3D transform of this object. If set, this is used instead of `matrix`.
+    ///
+    /// On a bitmap draw command, this is instead the complete projection of the bitmap
+    /// into final coordinates, as produced by `Matrix3D::project_plane`: `x` and `y`
+    /// have to be divided by `w` for each point separately. Backends that can't do that
+    /// draw the bitmap with `matrix`, which holds the linear approximation of it.
+    pub matrix3d: Option<Matrix3D>,
+
+    /// The perspective projection applied to the 3D children of this object.
+    /// If not set, the projection of the parent is used.
+    pub perspective_projection: Option<PerspectiveProjection>,
 }
 
 pub struct TransformStack(Vec<Transform>);
@@ -18,11 +31,26 @@ impl TransformStack {
 
     pub fn push(&mut self, transform: &Transform) {
         let cur_transform = self.transform();
-        let matrix = cur_transform.matrix * transform.matrix;
+        let matrix = if let Some(matrix3d) = &transform.matrix3d {
+            matrix3d.project(
+                &cur_transform.matrix,
+                cur_transform.perspective_projection.as_ref(),
+            )
+        } else {
+            cur_transform.matrix * transform.matrix
+        };
         let color_transform = cur_transform.color_transform * transform.color_transform;
+        // Projections are stored in the final coordinate space, so that 3D descendants
+        // don't need to know about anything in-between.
+        let perspective_projection = transform
+            .perspective_projection
+            .map(|projection| projection.transformed(&matrix))
+            .or(cur_transform.perspective_projection);
         self.0.push(Transform {
             matrix,
             color_transform,
+            matrix3d: None,
+            perspective_projection,
         });
     }
 
@@ -12,6 +12,7 @@ use ruffle_render::bitmap::{
 };
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error as BitmapError;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, GradientType};
 use ruffle_render::tessellator::{
@@ -1190,21 +1191,28 @@ impl CommandHandler for WebGlRenderBackend {
         };
 
         // Scale the quad to the bitmap's dimensions.
-        let mut matrix = transform.matrix;
-        pixel_snapping.apply(&mut matrix);
-        matrix *= ruffle_render::matrix::Matrix::scale(entry.width as f32, entry.height as f32);
+        let world_matrix = if let Some(matrix3d) = &transform.matrix3d {
+            // A projective world matrix makes WebGL interpolate the texture coordinates
+            // with perspective correction.
+            let scale = Matrix3D::scale(entry.width.into(), entry.height.into(), 1.0);
+            (*matrix3d * scale).to_columns()
+        } else {
+            let mut matrix = transform.matrix;
+            pixel_snapping.apply(&mut matrix);
+            matrix *= ruffle_render::matrix::Matrix::scale(entry.width as f32, entry.height as f32);
 
-        let world_matrix = [
-            [matrix.a, matrix.b, 0.0, 0.0],
-            [matrix.c, matrix.d, 0.0, 0.0],
-            [0.0, 0.0, 1.0, 0.0],
             [
-                matrix.tx.to_pixels() as f32,
-                matrix.ty.to_pixels() as f32,
-                0.0,
-                1.0,
-            ],
-        ];
+                [matrix.a, matrix.b, 0.0, 0.0],
+                [matrix.c, matrix.d, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [
+                    matrix.tx.to_pixels() as f32,
+                    matrix.ty.to_pixels() as f32,
+                    0.0,
+                    1.0,
+                ],
+            ]
+        };
 
         let mult_color = transform.color_transform.mult_rgba_normalized();
         let add_color = transform.color_transform.add_rgba_normalized();
@@ -0,0 +1,66 @@
+#import filter
+
+struct Filter {
+    /// The convolution matrix, row by row. Flash allows at most 15x15 entries.
+    matrix: array<vec4<f32>, 64>,
+    /// The (unmultiplied) color used for pixels outside of the source, when not clamping.
+    default_color: vec4<f32>,
+    /// The area of the source texture that may be sampled, in texels. `source_max` is exclusive.
+    source_min: vec2<i32>,
+    source_max: vec2<i32>,
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    clamp_edges: u32,
+    preserve_alpha: u32,
+    _padding: vec2<u32>,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> filter_args: Filter;
+
+@vertex
+fn main_vertex(in: filter::VertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+/// Loads an unmultiplied source color, applying the edge mode for coordinates outside of the source.
+fn load_source(coord: vec2<i32>) -> vec4<f32> {
+    var texel = coord;
+    if (any(texel < filter_args.source_min) || any(texel >= filter_args.source_max)) {
+        if (filter_args.clamp_edges == 0u) {
+            return filter_args.default_color;
+        }
+        texel = clamp(texel, filter_args.source_min, filter_args.source_max - vec2<i32>(1, 1));
+    }
+    let color = textureLoad(texture, texel, 0);
+    if (color.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let center = vec2<i32>(floor(in.uv * vec2<f32>(textureDimensions(texture))));
+    let half_size = vec2<i32>(i32(filter_args.matrix_x / 2u), i32(filter_args.matrix_y / 2u));
+
+    var sum = vec4<f32>(0.0);
+    for (var y = 0u; y < filter_args.matrix_y; y++) {
+        for (var x = 0u; x < filter_args.matrix_x; x++) {
+            let index = y * filter_args.matrix_x + x;
+            let weight = filter_args.matrix[index / 4u][index % 4u];
+            if (weight != 0.0) {
+                let offset = vec2<i32>(i32(x), i32(y)) - half_size;
+                sum += weight * load_source(center + offset);
+            }
+        }
+    }
+
+    var color = saturate(sum / filter_args.divisor + vec4<f32>(filter_args.bias / 255.0));
+    if (filter_args.preserve_alpha != 0u) {
+        color.a = load_source(center).a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
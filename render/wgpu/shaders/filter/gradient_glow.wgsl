@@ -0,0 +1,84 @@
+struct Filter {
+    strength: f32,
+    glow_type: u32,
+    knockout: u32,
+    composite_source: u32,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+@group(0) @binding(2) var<uniform> filter_args: Filter;
+@group(0) @binding(3) var blurred: texture_2d<f32>;
+@group(0) @binding(4) var gradient: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) source_uv: vec2<f32>,
+    @location(1) blur_uv: vec2<f32>,
+};
+
+struct VertexInput {
+    /// The position of the vertex in texture space (topleft 0,0, bottomright 1,1)
+    @location(0) position: vec2<f32>,
+
+    /// The coordinate of the source texture to sample in texture space (topleft 0,0, bottomright 1,1)
+    @location(1) source_uv: vec2<f32>,
+
+    /// The coordinate of the blur texture to sample in texture space (topleft 0,0, bottomright 1,1)
+    @location(2) blur_uv: vec2<f32>,
+};
+
+@vertex
+fn main_vertex(in: VertexInput) -> VertexOutput {
+    // Convert texture space (topleft 0,0 to bottomright 1,1) to render space (topleft -1,1 to bottomright 1,-1)
+    let pos = vec4<f32>((in.position.x * 2.0 - 1.0), (1.0 - in.position.y * 2.0), 0.0, 1.0);
+    return VertexOutput(pos, in.source_uv, in.blur_uv);
+}
+
+/// Looks up the (premultiplied) gradient color for a ratio between 0 and 1.
+fn gradient_color(ratio: f32) -> vec4<f32> {
+    return textureLoad(gradient, vec2<i32>(i32(round(saturate(ratio) * 255.0)), 0), 0);
+}
+
+@fragment
+fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let knockout = filter_args.knockout > 0u;
+    let composite_source = filter_args.composite_source > 0u;
+    var blur = textureSample(blurred, texture_sampler, in.blur_uv).a;
+    let dest = textureSample(texture, texture_sampler, in.source_uv);
+
+    if (in.blur_uv.x < 0.0 || in.blur_uv.x > 1.0 || in.blur_uv.y < 0.0 || in.blur_uv.y > 1.0) {
+        blur = 0.0;
+    }
+
+    let outer = filter_args.glow_type == 0u || filter_args.glow_type == 2u;
+    let inner = filter_args.glow_type == 1u || filter_args.glow_type == 2u;
+
+    if (inner && outer) {
+        let glow = gradient_color(blur * filter_args.strength);
+        if (knockout) {
+            return glow;
+        } else {
+            return dest - dest * glow.a + glow;
+        }
+    } else if (inner) {
+        // The inside of the shape is mapped from the far end of the gradient inwards.
+        let glow = gradient_color((1.0 - blur) * filter_args.strength);
+        if (knockout) {
+            return glow * dest.a;
+        } else if (composite_source) {
+            return glow * dest.a + dest * (1.0 - glow.a);
+        } else {
+            return glow * dest.a;
+        }
+    } else {
+        let glow = gradient_color(blur * filter_args.strength);
+        if (knockout) {
+            return glow * (1.0 - dest.a);
+        } else if (composite_source) {
+            return glow * (1.0 - dest.a) + dest;
+        } else {
+            return glow;
+        }
+    }
+}
@@ -834,6 +834,9 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 | Filter::ShaderFilter(_)
                 | Filter::BevelFilter(_)
                 | Filter::DisplacementMapFilter(_)
+                | Filter::GradientGlowFilter(_)
+                | Filter::GradientBevelFilter(_)
+                | Filter::ConvolutionFilter(_)
         )
     }
 
@@ -1,29 +1,36 @@
 mod bevel;
 mod blur;
 mod color_matrix;
+mod convolution;
 mod displacement_map;
 mod drop_shadow;
 mod glow;
+mod gradient_bevel;
+mod gradient_glow;
 mod shader;
 
-use std::collections::HashSet;
-use std::sync::{Mutex, OnceLock};
-
 use crate::buffer_pool::TexturePool;
 use crate::descriptors::Descriptors;
 use crate::filters::bevel::BevelFilter;
 use crate::filters::blur::BlurFilter;
 use crate::filters::color_matrix::ColorMatrixFilter;
+use crate::filters::convolution::ConvolutionFilter;
 use crate::filters::displacement_map::DisplacementMapFilter;
 use crate::filters::drop_shadow::DropShadowFilter;
 use crate::filters::glow::GlowFilter;
+use crate::filters::gradient_bevel::GradientBevelFilter;
+use crate::filters::gradient_glow::GradientGlowFilter;
 use crate::filters::shader::ShaderFilter;
 use crate::surface::target::CommandTarget;
 use bytemuck::{Pod, Zeroable};
 use ruffle_render::filters::Filter;
+use swf::GradientRecord;
 use wgpu::util::DeviceExt;
 use wgpu::vertex_attr_array;
 
+/// How many entries the lookup texture of a gradient filter has, one for each possible ratio.
+const GRADIENT_FILTER_SIZE: usize = 256;
+
 #[derive(Debug)]
 pub struct FilterSource<'a> {
     pub texture: &'a wgpu::Texture,
@@ -209,6 +216,9 @@ pub struct Filters {
     pub glow: GlowFilter,
     pub bevel: BevelFilter,
     pub displacement_map: DisplacementMapFilter,
+    pub gradient_glow: GradientGlowFilter,
+    pub gradient_bevel: GradientBevelFilter,
+    pub convolution: ConvolutionFilter,
 }
 
 impl Filters {
@@ -220,6 +230,9 @@ impl Filters {
             glow: GlowFilter::new(device),
             bevel: BevelFilter::new(device),
             displacement_map: DisplacementMapFilter::new(device),
+            gradient_glow: GradientGlowFilter::new(device),
+            gradient_bevel: GradientBevelFilter::new(device),
+            convolution: ConvolutionFilter::new(device),
         }
     }
 
@@ -231,85 +244,85 @@ impl Filters {
         source: FilterSource,
         filter: Filter,
     ) -> CommandTarget {
-        let target =
-            match filter {
-                Filter::ColorMatrixFilter(filter) => Some(descriptors.filters.color_matrix.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                )),
-                Filter::BlurFilter(filter) => descriptors.filters.blur.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                ),
-                Filter::ShaderFilter(shader) => Some(descriptors.filters.shader.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    shader,
-                )),
-                Filter::GlowFilter(filter) => Some(descriptors.filters.glow.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                    &self.blur,
-                    (0.0, 0.0),
-                )),
-                Filter::DropShadowFilter(filter) => Some(DropShadowFilter::apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                    &self.blur,
-                    &self.glow,
-                )),
-                Filter::BevelFilter(filter) => Some(descriptors.filters.bevel.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                    &self.blur,
-                )),
-                Filter::DisplacementMapFilter(filter) => descriptors
-                    .filters
-                    .displacement_map
-                    .apply(descriptors, texture_pool, draw_encoder, &source, &filter),
-                filter => {
-                    static WARNED_FILTERS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
-                    let name = match filter {
-                        Filter::GradientGlowFilter(_) => "GradientGlowFilter",
-                        Filter::GradientBevelFilter(_) => "GradientBevelFilter",
-                        Filter::ConvolutionFilter(_) => "ConvolutionFilter",
-                        Filter::ColorMatrixFilter(_)
-                        | Filter::BlurFilter(_)
-                        | Filter::GlowFilter(_)
-                        | Filter::DropShadowFilter(_)
-                        | Filter::BevelFilter(_)
-                        | Filter::DisplacementMapFilter(_)
-                        | Filter::ShaderFilter(_) => unreachable!(),
-                    };
-                    // Only warn once per filter type
-                    if WARNED_FILTERS
-                        .get_or_init(Default::default)
-                        .lock()
-                        .unwrap()
-                        .insert(name)
-                    {
-                        tracing::warn!("Unsupported filter {filter:?}");
-                    }
-                    None
-                }
-            };
+        let target = match filter {
+            Filter::ColorMatrixFilter(filter) => Some(descriptors.filters.color_matrix.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+            )),
+            Filter::BlurFilter(filter) => descriptors.filters.blur.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+            ),
+            Filter::ShaderFilter(shader) => Some(descriptors.filters.shader.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                shader,
+            )),
+            Filter::GlowFilter(filter) => Some(descriptors.filters.glow.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+                (0.0, 0.0),
+            )),
+            Filter::DropShadowFilter(filter) => Some(DropShadowFilter::apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+                &self.glow,
+            )),
+            Filter::BevelFilter(filter) => Some(descriptors.filters.bevel.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::DisplacementMapFilter(filter) => descriptors.filters.displacement_map.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+            ),
+            Filter::GradientGlowFilter(filter) => Some(descriptors.filters.gradient_glow.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::GradientBevelFilter(filter) => Some(descriptors.filters.gradient_bevel.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::ConvolutionFilter(filter) => Some(descriptors.filters.convolution.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+            )),
+        };
 
         let target = target.unwrap_or_else(|| {
             // Apply a default color matrix - it's essentially a blit
@@ -331,6 +344,61 @@ impl Filters {
     }
 }
 
+/// Creates a lookup texture mapping every possible ratio of a gradient filter to its
+/// (premultiplied) color.
+pub fn create_gradient_filter_texture(
+    descriptors: &Descriptors,
+    records: &[GradientRecord],
+) -> wgpu::Texture {
+    let mut colors = [0u8; GRADIENT_FILTER_SIZE * 4];
+    if let (Some(first), Some(last)) = (records.first(), records.last()) {
+        for (t, color) in colors.chunks_exact_mut(4).enumerate() {
+            let (start, end) = if t <= first.ratio as usize {
+                (first, first)
+            } else if t >= last.ratio as usize {
+                (last, last)
+            } else {
+                let next = records
+                    .iter()
+                    .position(|record| record.ratio as usize >= t)
+                    .unwrap_or(records.len() - 1)
+                    .max(1);
+                (&records[next - 1], &records[next])
+            };
+            let a = if end.ratio > start.ratio {
+                (t as f32 - start.ratio as f32) / (end.ratio as f32 - start.ratio as f32)
+            } else {
+                0.0
+            };
+            let lerp = |from: u8, to: u8| from as f32 + (to as f32 - from as f32) * a;
+            let alpha = lerp(start.color.a, end.color.a);
+            color[0] = (lerp(start.color.r, end.color.r) * alpha / 255.0).round() as u8;
+            color[1] = (lerp(start.color.g, end.color.g) * alpha / 255.0).round() as u8;
+            color[2] = (lerp(start.color.b, end.color.b) * alpha / 255.0).round() as u8;
+            color[3] = alpha.round() as u8;
+        }
+    }
+
+    descriptors.device.create_texture_with_data(
+        &descriptors.queue,
+        &wgpu::TextureDescriptor {
+            label: create_debug_label!("Gradient filter colors").as_deref(),
+            size: wgpu::Extent3d {
+                width: GRADIENT_FILTER_SIZE as u32,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        &colors[..],
+    )
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct FilterVertex {
@@ -13,6 +13,7 @@ use ruffle_render::backend::ShapeHandle;
 use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
 use ruffle_render::commands::{Command, RenderBlendMode};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -195,7 +196,14 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 1.0,
             ],
         ];
+        self.apply_world_matrix(world_matrix, color_adjustments);
+    }
 
+    pub fn apply_world_matrix(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        color_adjustments: &ColorTransform,
+    ) {
         if self.descriptors.limits.max_push_constant_size > 0 {
             self.render_pass.set_push_constants(
                 wgpu::ShaderStages::VERTEX_FRAGMENT,
@@ -259,13 +267,18 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
             &descriptors.bitmap_samplers,
         );
         self.prep_bitmap(&bind.bind_group, blend_mode, render_stage3d);
-        let mut matrix = transform.matrix;
-        pixel_snapping.apply(&mut matrix);
-        matrix *= Matrix::scale(
-            texture.texture.width() as f32,
-            texture.texture.height() as f32,
-        );
-        self.apply_transform(&matrix, &transform.color_transform);
+        let (width, height) = (texture.texture.width(), texture.texture.height());
+        if let Some(matrix3d) = &transform.matrix3d {
+            // A projective world matrix makes the GPU interpolate the texture coordinates
+            // with perspective correction.
+            let world_matrix = *matrix3d * Matrix3D::scale(width.into(), height.into(), 1.0);
+            self.apply_world_matrix(world_matrix.to_columns(), &transform.color_transform);
+        } else {
+            let mut matrix = transform.matrix;
+            pixel_snapping.apply(&mut matrix);
+            matrix *= Matrix::scale(width as f32, height as f32);
+            self.apply_transform(&matrix, &transform.color_transform);
+        }
 
         self.draw(
             self.descriptors.quad.vertices_pos.slice(..),
@@ -520,6 +533,7 @@ pub fn chunk_blends<'a>(
                         let transform = Transform {
                             matrix: Matrix::scale(target.width() as f32, target.height() as f32),
                             color_transform: Default::default(),
+                            ..Default::default()
                         };
                         let texture = target.take_color_texture();
                         let bind_group =
@@ -2,10 +2,13 @@
 
 use crate::cli_options::AnalyzeOpt;
 use crate::file_results::{FileResults, Step};
+use std::collections::HashMap;
 use std::fs::File;
 
 /// Generate and print statistics related to a scan's results
-pub fn analyze(results: impl Iterator<Item = FileResults>) {
+///
+/// Up to `top_stubs` of the stubs hit by the most files are listed.
+pub fn analyze(results: impl Iterator<Item = FileResults>, top_stubs: usize) {
     let mut total = 0;
     let mut start = 0;
     let mut read = 0;
@@ -13,10 +16,15 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut parse = 0;
     let mut execute = 0;
     let mut complete = 0;
+    let mut stub_counts: HashMap<String, usize> = HashMap::new();
 
     for result in results {
         total += 1;
 
+        for stub in result.stubs {
+            *stub_counts.entry(stub).or_default() += 1;
+        }
+
         match result.progress {
             Step::Start => start += 1,
             Step::Read => read += 1,
@@ -48,24 +56,77 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     println!("{execute:>digits$} movies failed to execute");
     println!("{complete:>digits$} movies completed without errors");
     println!();
+
+    if !stub_counts.is_empty() && top_stubs > 0 {
+        let stub_counts = rank_stubs(stub_counts);
+
+        println!(
+            "{} different stubs were hit, the most common being:",
+            stub_counts.len()
+        );
+        for (stub, count) in stub_counts.iter().take(top_stubs) {
+            println!("{count:>digits$} movies hit {stub}");
+        }
+        println!();
+    }
+}
+
+/// Orders stubs by the number of files that hit them, most common first.
+///
+/// Stubs hit by the same number of files are ordered by name.
+fn rank_stubs(stub_counts: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut stub_counts: Vec<_> = stub_counts.into_iter().collect();
+    stub_counts.sort_by(|(a_stub, a_count), (b_stub, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_stub.cmp(b_stub))
+    });
+    stub_counts
 }
 
 pub fn analyze_main(opt: AnalyzeOpt) -> Result<(), std::io::Error> {
     let file = File::open(opt.input_path)?;
     let reader = csv::Reader::from_reader(file);
 
-    analyze(reader.into_deserialize::<FileResults>().map(|r| {
-        match r {
-            Ok(fr) => fr,
-            Err(e) => {
-                // Treat unparseable CSV rows as a scanner panic
-                FileResults {
-                    error: Some(format!("{e}")),
-                    ..FileResults::default()
+    analyze(
+        reader.into_deserialize::<FileResults>().map(|r| {
+            match r {
+                Ok(fr) => fr,
+                Err(e) => {
+                    // Treat unparseable CSV rows as a scanner panic
+                    FileResults {
+                        error: Some(format!("{e}")),
+                        ..FileResults::default()
+                    }
                 }
             }
-        }
-    }));
+        }),
+        opt.top_stubs,
+    );
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::rank_stubs;
+    use std::collections::HashMap;
+
+    #[test]
+    fn stubs_are_ranked_by_count_then_name() {
+        let stub_counts = HashMap::from([
+            ("flash.net.Socket::connect".to_string(), 2),
+            ("flash.display.Stage3D::requestContext3D".to_string(), 5),
+            ("flash.media.Camera::getCamera".to_string(), 2),
+            ("flash.system.IME::enabled".to_string(), 1),
+        ]);
+
+        assert_eq!(
+            rank_stubs(stub_counts),
+            vec![
+                ("flash.display.Stage3D::requestContext3D".to_string(), 5),
+                ("flash.media.Camera::getCamera".to_string(), 2),
+                ("flash.net.Socket::connect".to_string(), 2),
+                ("flash.system.IME::enabled".to_string(), 1),
+            ]
+        );
+    }
+}
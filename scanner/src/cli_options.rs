@@ -35,6 +35,10 @@ pub struct ScanOpt {
     /// Filenames to ignore
     #[clap(short = 'i', long = "ignore", action = clap::ArgAction::Append)]
     pub ignore: Vec<String>,
+
+    /// How many of the most commonly hit stubs to list
+    #[clap(long = "top-stubs", default_value = "25")]
+    pub top_stubs: usize,
 }
 
 #[derive(Parser, Debug)]
@@ -42,6 +46,10 @@ pub struct AnalyzeOpt {
     /// The CSV file to reanalyze
     #[clap(name = "input")]
     pub input_path: PathBuf,
+
+    /// How many of the most commonly hit stubs to list
+    #[clap(long = "top-stubs", default_value = "25")]
+    pub top_stubs: usize,
 }
 
 #[derive(Parser, Debug)]
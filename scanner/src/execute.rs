@@ -14,7 +14,8 @@ use std::panic::catch_unwind;
 use std::path::Path;
 use std::time::{Duration, Instant};
 
-fn execute_swf(file: &Path) {
+/// Runs one frame of the movie, returning the stubs it hit.
+fn execute_swf(file: &Path) -> Vec<String> {
     let base_path = file.parent().unwrap();
     let executor = NullExecutor::new();
     let movie = SwfMovie::from_path(file, None).unwrap();
@@ -31,6 +32,16 @@ fn execute_swf(file: &Path) {
     player.lock().unwrap().run_frame();
     player.lock().unwrap().update_timers(frame_time);
     //executor.poll_all().unwrap();
+
+    let mut stubs: Vec<String> = player
+        .lock()
+        .unwrap()
+        .stub_tracker()
+        .iter()
+        .map(|stub| stub.to_string())
+        .collect();
+    stubs.sort();
+    stubs
 }
 
 fn checkpoint<W: Write>(
@@ -150,8 +161,9 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     file_result.progress = Step::Execute;
 
     //Run one frame of the movie in Ruffle.
-    if let Err(e) = catch_unwind(|| execute_swf(&file_path)) {
-        match e.downcast::<String>() {
+    match catch_unwind(|| execute_swf(&file_path)) {
+        Ok(stubs) => file_result.stubs = stubs,
+        Err(e) => match e.downcast::<String>() {
             Ok(e) => {
                 file_result.error = Some(format!("PANIC: {e}"));
                 checkpoint(&mut file_result, &start, &mut writer)?;
@@ -160,7 +172,7 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
                 file_result.error = Some("PANIC".to_string());
                 checkpoint(&mut file_result, &start, &mut writer)?;
             }
-        }
+        },
     }
 
     let errors = LOCAL_LOGGER.with(|log_buffer| {
@@ -126,6 +126,15 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// The unimplemented ActionScript APIs this SWF used while executing.
+    #[serde(
+        rename = "Stubs",
+        default,
+        serialize_with = "into_lines",
+        deserialize_with = "from_lines"
+    )]
+    pub stubs: Vec<String>,
 }
 
 impl Default for FileResults {
@@ -153,6 +162,7 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            stubs: vec![],
         }
     }
 }
@@ -170,6 +180,27 @@ where
     s.serialize_str(&out)
 }
 
+/// Formats a list of strings as one string per line
+fn into_lines<S>(lines: &[String], s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_str(&lines.join("\n"))
+}
+
+/// Parses a string with one entry per line into a list
+fn from_lines<'de, D>(d: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let lines = String::deserialize(d)?;
+    Ok(lines
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
 /// Parses hex strings into data
 fn from_hex<'de, D>(d: D) -> Result<Vec<u8>, D::Error>
 where
@@ -207,3 +238,42 @@ where
 
     d.deserialize_str(HexVisitor())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{from_lines, FileResults};
+    use serde::de::value::{Error, StrDeserializer};
+
+    fn round_trip(stubs: &[&str]) -> Vec<String> {
+        let mut results = FileResults::new("test.swf");
+        results.stubs = stubs.iter().map(|stub| stub.to_string()).collect();
+
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.serialize(&results).unwrap();
+        let csv = writer.into_inner().unwrap();
+
+        let mut reader = csv::Reader::from_reader(csv.as_slice());
+        let results: FileResults = reader.deserialize().next().unwrap().unwrap();
+        results.stubs
+    }
+
+    #[test]
+    fn stubs_round_trip() {
+        let stubs = [
+            "flash.net.Socket::connect",
+            "flash.text.TextField::setTextFormat with \"quotes\", commas",
+        ];
+        assert_eq!(round_trip(&stubs), stubs);
+    }
+
+    #[test]
+    fn no_stubs_round_trip() {
+        assert_eq!(round_trip(&[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn empty_lines_are_skipped() {
+        let lines = StrDeserializer::<Error>::new("first\n\nsecond\n");
+        assert_eq!(from_lines(lines).unwrap(), ["first", "second"]);
+    }
+}
@@ -68,6 +68,7 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                             use_gpu,
                             use_network_sandbox,
                             vm_type,
+                            stubs,
                         } = child_results;
 
                         file_results.hash = hash;
@@ -85,6 +86,7 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
                         file_results.use_gpu = use_gpu;
                         file_results.use_network_sandbox = use_network_sandbox;
                         file_results.vm_type = vm_type;
+                        file_results.stubs = stubs;
                     }
                     Err(e) => {
                         file_results.error = Some(e.to_string());
@@ -115,6 +117,7 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
 /// Should be called with parsed options corresponding to the `scan` command.
 pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
     let binary_path = env::current_exe()?;
+    let opt_top_stubs = opt.top_stubs;
     let to_scan = find_files(&opt.input_path, &opt.ignore);
     let mut writer = csv::Writer::from_path(opt.output_path.clone())?;
 
@@ -151,7 +154,7 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
             result
         });
 
-    analyze(result_iter);
+    analyze(result_iter, opt_top_stubs);
 
     Ok(())
 }
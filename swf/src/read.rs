@@ -124,7 +124,27 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         log::warn!("SWF length doesn't match header, may be corrupt");
     }
 
-    let mut reader = Reader::new(&data, version);
+    let (header, header_len) = read_swf_header_ext(compression, version, uncompressed_len, &data)?;
+    Ok(SwfBuf {
+        header,
+        data: data[header_len..].to_vec(),
+    })
+}
+
+/// Parses the rest of a SWF header from the start of the uncompressed data of a SWF,
+/// i.e. everything after its signature, version and uncompressed length.
+///
+/// This also parses the first tags of the SWF, searching for the FileAttributes and
+/// SetBackgroundColor tags, so `data` should include those.
+///
+/// Returns the extended header and its length in `data`, where the tags of the SWF start.
+pub fn read_swf_header_ext(
+    compression: Compression,
+    version: u8,
+    uncompressed_len: u32,
+    data: &[u8],
+) -> Result<(HeaderExt, usize)> {
+    let mut reader = Reader::new(data, version);
     let stage_size = reader.read_rectangle()?;
     let frame_rate = reader.read_fixed8()?;
     let num_frames = reader.read_u16()?;
@@ -135,7 +155,7 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         frame_rate,
         num_frames,
     };
-    let data = reader.get_ref().to_vec();
+    let header_len = data.len() - reader.get_ref().len();
 
     // Parse the first two tags, searching for the FileAttributes and SetBackgroundColor tags.
     // This metadata is useful, so we want to return it along with the header.
@@ -161,15 +181,15 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         tag = reader.read_tag();
     }
 
-    Ok(SwfBuf {
-        header: HeaderExt {
+    Ok((
+        HeaderExt {
             header,
             file_attributes,
             background_color,
             uncompressed_len: uncompressed_len as i32,
         },
-        data,
-    })
+        header_len,
+    ))
 }
 
 #[cfg(feature = "flate2")]
@@ -1,4 +1,4 @@
-use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord};
+use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord, Rectangle, Twips};
 use bitflags::bitflags;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -28,6 +28,11 @@ impl GradientFilter {
         self.flags.contains(GradientFilterFlags::ON_TOP)
     }
 
+    #[inline]
+    pub fn composite_source(&self) -> bool {
+        self.flags.contains(GradientFilterFlags::COMPOSITE_SOURCE)
+    }
+
     #[inline]
     pub fn num_passes(&self) -> u8 {
         (self.flags & GradientFilterFlags::PASSES).bits()
@@ -39,6 +44,47 @@ impl GradientFilter {
         self.distance *= Fixed16::from_f32(y);
     }
 
+    /// The offset of the blurred copy of the source, in pixels.
+    pub fn offset(&self) -> (f64, f64) {
+        let distance = self.distance.to_f64();
+        let angle = self.angle.to_f64();
+        (angle.cos() * distance, angle.sin() * distance)
+    }
+
+    /// Calculates the destination rectangle when this is used as a gradient glow.
+    /// The glow is only offset in one direction, like a drop shadow.
+    pub fn calculate_glow_dest_rect(&self, source_rect: Rectangle<Twips>) -> Rectangle<Twips> {
+        let mut result = self.inner_blur_filter().calculate_dest_rect(source_rect);
+        let (x, y) = self.offset();
+        let x = Twips::from_pixels(x);
+        let y = Twips::from_pixels(y);
+        if x < Twips::ZERO {
+            result.x_min += x;
+        } else {
+            result.x_max += x;
+        }
+        if y < Twips::ZERO {
+            result.y_min += y;
+        } else {
+            result.y_max += y;
+        }
+        result
+    }
+
+    /// Calculates the destination rectangle when this is used as a gradient bevel.
+    /// The highlight and shadow are offset in opposite directions, so both sides grow.
+    pub fn calculate_bevel_dest_rect(&self, source_rect: Rectangle<Twips>) -> Rectangle<Twips> {
+        let mut result = self.inner_blur_filter().calculate_dest_rect(source_rect);
+        let (x, y) = self.offset();
+        let x = Twips::from_pixels(x.abs());
+        let y = Twips::from_pixels(y.abs());
+        result.x_min -= x;
+        result.x_max += x;
+        result.y_min -= y;
+        result.y_max += y;
+        result
+    }
+
     pub fn inner_blur_filter(&self) -> BlurFilter {
         BlurFilter {
             blur_x: self.blur_x,
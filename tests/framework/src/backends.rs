@@ -1,9 +1,10 @@
 mod audio;
 mod log;
 mod navigator;
+mod rtmp_server;
 mod ui;
 
 pub use audio::TestAudioBackend;
 pub use log::TestLogBackend;
-pub use navigator::TestNavigatorBackend;
+pub use navigator::{ChunkTicker, TestNavigatorBackend};
 pub use ui::TestUiBackend;
@@ -1,3 +1,4 @@
+use crate::backends::rtmp_server;
 use crate::backends::TestLogBackend;
 use crate::util::read_bytes;
 use async_channel::Receiver;
@@ -5,13 +6,19 @@ use percent_encoding::percent_decode_str;
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::{
     async_return, create_fetch_error, ErrorResponse, NavigationMethod, NavigatorBackend,
-    NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
+    NullExecutor, NullSpawner, OwnedFuture, Request, ResponseBody, StreamingResponse,
+    SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
 use ruffle_socket_format::SocketEvent;
+use std::collections::VecDeque;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
 use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
 use std::time::Duration;
 use url::{ParseError, Url};
 use vfs::VfsPath;
@@ -27,11 +34,20 @@ use vfs::VfsPath;
 ///
 /// These are formatted as query params, rather than domains/whole URLs, so that real/real-invalid
 /// URLs can be used in Flash Player when writing tests
+///
+/// If `fetch_chunk_size` is set, the bodies of responses are delivered in chunks of that size,
+/// one chunk every time the `ChunkTicker` ticks.
+///
+/// If `rtmp_server` is set, sockets are answered by an RTMP server that streams FLV files from
+/// the test directory, instead of by the events in `socket_events`.
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: VfsPath,
     socket_events: Option<Vec<SocketEvent>>,
+    rtmp_server: bool,
     log: Option<TestLogBackend>,
+    fetch_chunk_size: Option<usize>,
+    chunk_ticker: ChunkTicker,
 }
 
 impl TestNavigatorBackend {
@@ -39,15 +55,80 @@ impl TestNavigatorBackend {
         path: VfsPath,
         executor: &NullExecutor,
         socket_events: Option<Vec<SocketEvent>>,
+        rtmp_server: bool,
         log: Option<TestLogBackend>,
+        fetch_chunk_size: Option<usize>,
     ) -> Result<Self, std::io::Error> {
         Ok(Self {
             spawner: executor.spawner(),
             relative_base_path: path,
             socket_events,
+            rtmp_server,
             log,
+            fetch_chunk_size,
+            chunk_ticker: ChunkTicker::default(),
         })
     }
+
+    pub fn chunk_ticker(&self) -> ChunkTicker {
+        self.chunk_ticker.clone()
+    }
+}
+
+/// Lets the bodies of chunked responses deliver their next chunk.
+#[derive(Clone, Default)]
+pub struct ChunkTicker(Arc<Mutex<ChunkTickerState>>);
+
+#[derive(Default)]
+struct ChunkTickerState {
+    ticks: u64,
+    waiting: Vec<Waker>,
+}
+
+impl ChunkTicker {
+    pub fn tick(&self) {
+        let mut state = self.0.lock().expect("Chunk ticker lock must be available");
+        state.ticks += 1;
+        for waker in state.waiting.drain(..) {
+            waker.wake();
+        }
+    }
+
+    fn ticks(&self) -> u64 {
+        self.0
+            .lock()
+            .expect("Chunk ticker lock must be available")
+            .ticks
+    }
+}
+
+/// A response body that's delivered one chunk per tick.
+struct ChunkedBody {
+    chunks: VecDeque<Vec<u8>>,
+    ticker: ChunkTicker,
+    last_tick: u64,
+}
+
+impl ResponseBody for ChunkedBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        Box::pin(poll_fn(move |cx| {
+            if self.chunks.is_empty() {
+                return Poll::Ready(Ok(None));
+            }
+
+            let mut state = self
+                .ticker
+                .0
+                .lock()
+                .expect("Chunk ticker lock must be available");
+            if state.ticks == self.last_tick {
+                state.waiting.push(cx.waker().clone());
+                return Poll::Pending;
+            }
+            self.last_tick = state.ticks;
+            Poll::Ready(Ok(self.chunks.pop_front()))
+        }))
+    }
 }
 
 impl NavigatorBackend for TestNavigatorBackend {
@@ -193,6 +274,35 @@ impl NavigatorBackend for TestNavigatorBackend {
         })
     }
 
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, ErrorResponse> {
+        let fetch = self.fetch(request);
+        let chunk_size = self.fetch_chunk_size;
+        let ticker = self.chunk_ticker.clone();
+        Box::pin(async move {
+            let response = fetch.await?;
+            let Some(chunk_size) = chunk_size else {
+                return Ok(StreamingResponse::from_complete(response));
+            };
+
+            let last_tick = ticker.ticks();
+            Ok(StreamingResponse {
+                url: response.url,
+                status: response.status,
+                redirected: response.redirected,
+                expected_length: Some(response.body.len() as u64),
+                body: Box::new(ChunkedBody {
+                    chunks: response
+                        .body
+                        .chunks(chunk_size)
+                        .map(<[u8]>::to_vec)
+                        .collect(),
+                    ticker,
+                    last_tick,
+                }),
+            })
+        })
+    }
+
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
         let mut base_url = Url::parse("file:///")?;
 
@@ -231,6 +341,14 @@ impl NavigatorBackend for TestNavigatorBackend {
             log.avm_trace(&format!("    Host: {}; Port: {}", host, port));
         }
 
+        if self.rtmp_server {
+            let directory = self.relative_base_path.clone();
+            self.spawn_future(Box::pin(rtmp_server::serve(
+                directory, handle, receiver, sender,
+            )));
+            return;
+        }
+
         if let Some(events) = self.socket_events.clone() {
             self.spawn_future(Box::pin(async move {
                 sender
@@ -0,0 +1,442 @@
+//! A minimal RTMP server for tests of `NetConnection` and `NetStream`.
+//!
+//! It accepts any connection and stream, and answers `play` by streaming the
+//! tags of the FLV file with that name in the test directory. Other commands
+//! are ignored, apart from `deleteStream`, which the server acknowledges by
+//! calling `onStreamDeleted` on the client.
+
+use async_channel::Receiver;
+use ruffle_core::loader::Error;
+use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use vfs::VfsPath;
+
+const HANDSHAKE_SIZE: usize = 1536;
+
+/// The chunk size the client uses until it sends a Set Chunk Size message,
+/// which we keep using for everything we send.
+const DEFAULT_CHUNK_SIZE: usize = 128;
+
+const SET_CHUNK_SIZE: u8 = 1;
+const COMMAND_AMF0: u8 = 20;
+
+const COMMAND_CHUNK_STREAM: u8 = 3;
+const STATUS_CHUNK_STREAM: u8 = 4;
+const MEDIA_CHUNK_STREAM: u8 = 6;
+
+/// The ID of the one media stream, which is all that the tests need.
+const STREAM_ID: u32 = 1;
+
+/// The subset of AMF0 values that the client sends and the server needs.
+#[derive(Clone, Debug, PartialEq)]
+enum Amf {
+    Number(f64),
+    Bool(bool),
+    String(String),
+    Object(Vec<(String, Amf)>),
+    Null,
+    Undefined,
+}
+
+impl Amf {
+    fn object(properties: &[(&str, &str)]) -> Self {
+        Amf::Object(
+            properties
+                .iter()
+                .map(|(name, value)| (name.to_string(), Amf::String(value.to_string())))
+                .collect(),
+        )
+    }
+
+    fn get(&self, name: &str) -> Option<&Amf> {
+        match self {
+            Amf::Object(properties) => properties
+                .iter()
+                .find(|(property, _)| property == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn write(&self, output: &mut Vec<u8>) {
+        match self {
+            Amf::Number(value) => {
+                output.push(0x00);
+                output.extend_from_slice(&value.to_be_bytes());
+            }
+            Amf::Bool(value) => output.extend_from_slice(&[0x01, *value as u8]),
+            Amf::String(value) => {
+                output.push(0x02);
+                write_string(output, value);
+            }
+            Amf::Object(properties) => {
+                output.push(0x03);
+                for (name, value) in properties {
+                    write_string(output, name);
+                    value.write(output);
+                }
+                output.extend_from_slice(&[0x00, 0x00, 0x09]);
+            }
+            Amf::Null => output.push(0x05),
+            Amf::Undefined => output.push(0x06),
+        }
+    }
+
+    fn read(bytes: &mut &[u8]) -> Self {
+        let (&marker, rest) = bytes.split_first().expect("AMF0 value");
+        *bytes = rest;
+        match marker {
+            0x00 => {
+                let (value, rest) = bytes.split_at(8);
+                *bytes = rest;
+                Amf::Number(f64::from_be_bytes(value.try_into().unwrap()))
+            }
+            0x01 => {
+                let (&value, rest) = bytes.split_first().expect("AMF0 boolean");
+                *bytes = rest;
+                Amf::Bool(value != 0)
+            }
+            0x02 => Amf::String(read_string(bytes)),
+            0x03 => {
+                let mut properties = vec![];
+                loop {
+                    let name = read_string(bytes);
+                    if name.is_empty() && bytes.first() == Some(&0x09) {
+                        *bytes = &bytes[1..];
+                        break Amf::Object(properties);
+                    }
+                    properties.push((name, Amf::read(bytes)));
+                }
+            }
+            0x05 => Amf::Null,
+            0x06 => Amf::Undefined,
+            marker => panic!("RTMP test server can't read AMF0 values of type {marker}"),
+        }
+    }
+}
+
+fn write_string(output: &mut Vec<u8>, value: &str) {
+    output.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    output.extend_from_slice(value.as_bytes());
+}
+
+fn read_string(bytes: &mut &[u8]) -> String {
+    let (length, rest) = bytes.split_at(2);
+    let (value, rest) = rest.split_at(u16::from_be_bytes([length[0], length[1]]) as usize);
+    *bytes = rest;
+    String::from_utf8(value.to_vec()).expect("UTF-8 string")
+}
+
+fn read_u24(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])
+}
+
+/// A message that is still being received on one chunk stream.
+struct IncomingMessage {
+    type_id: u8,
+    stream_id: u32,
+    length: usize,
+    payload: Vec<u8>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum HandshakeState {
+    /// Waiting for C0 and C1.
+    AwaitingC1,
+
+    /// S0, S1 and S2 have been sent, and we're waiting for C2.
+    AwaitingC2,
+
+    Done,
+}
+
+struct RtmpServer {
+    directory: VfsPath,
+    handshake_state: HandshakeState,
+
+    /// Received data that hasn't been processed yet.
+    buffer: Vec<u8>,
+    chunk_size: usize,
+    incoming: HashMap<u8, IncomingMessage>,
+
+    /// The stream ID and timestamp of the last message sent on each chunk
+    /// stream, so that later messages can use a shorter header.
+    sent: HashMap<u8, (u32, u32)>,
+    output: Vec<u8>,
+}
+
+impl RtmpServer {
+    fn new(directory: VfsPath) -> Self {
+        Self {
+            directory,
+            handshake_state: HandshakeState::AwaitingC1,
+            buffer: vec![],
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            incoming: HashMap::new(),
+            sent: HashMap::new(),
+            output: vec![],
+        }
+    }
+
+    /// Process data from the client, returning the data to send back.
+    fn receive(&mut self, data: &[u8]) -> Vec<u8> {
+        self.buffer.extend_from_slice(data);
+
+        if self.handshake_state == HandshakeState::AwaitingC1 {
+            if self.buffer.len() < 1 + HANDSHAKE_SIZE {
+                return vec![];
+            }
+            // S0, then S1, then S2 echoing C1.
+            self.output.push(3);
+            self.output.extend_from_slice(&[0; HANDSHAKE_SIZE]);
+            self.output
+                .extend_from_slice(&self.buffer[1..1 + HANDSHAKE_SIZE]);
+            self.buffer.drain(..1 + HANDSHAKE_SIZE);
+            self.handshake_state = HandshakeState::AwaitingC2;
+        }
+
+        if self.handshake_state == HandshakeState::AwaitingC2 {
+            if self.buffer.len() < HANDSHAKE_SIZE {
+                return std::mem::take(&mut self.output);
+            }
+            self.buffer.drain(..HANDSHAKE_SIZE);
+            self.handshake_state = HandshakeState::Done;
+        }
+
+        while let Some(message) = self.read_chunk() {
+            self.handle_message(message);
+        }
+        std::mem::take(&mut self.output)
+    }
+
+    /// Read chunks until a message is complete, returning that message.
+    ///
+    /// The client sends every message with a full header, followed by type 3
+    /// chunks if it doesn't fit in one chunk.
+    fn read_chunk(&mut self) -> Option<IncomingMessage> {
+        loop {
+            let &first = self.buffer.first()?;
+            let chunk_stream_id = first & 0x3F;
+            let header_length = match first >> 6 {
+                0 => 11,
+                3 => 0,
+                format => panic!("RTMP test server can't read type {format} chunks"),
+            };
+            let header = self.buffer.get(1..1 + header_length)?;
+            let mut message = if header_length > 0 {
+                IncomingMessage {
+                    type_id: header[6],
+                    stream_id: u32::from_le_bytes(header[7..11].try_into().unwrap()),
+                    length: read_u24(&header[3..]) as usize,
+                    payload: vec![],
+                }
+            } else {
+                self.incoming
+                    .remove(&chunk_stream_id)
+                    .expect("type 3 chunk continues a message")
+            };
+
+            let start = 1 + header_length;
+            let data_length = (message.length - message.payload.len()).min(self.chunk_size);
+            let Some(data) = self.buffer.get(start..start + data_length) else {
+                if header_length == 0 {
+                    self.incoming.insert(chunk_stream_id, message);
+                }
+                return None;
+            };
+            message.payload.extend_from_slice(data);
+            self.buffer.drain(..start + data_length);
+
+            if message.payload.len() == message.length {
+                return Some(message);
+            }
+            self.incoming.insert(chunk_stream_id, message);
+        }
+    }
+
+    fn handle_message(&mut self, message: IncomingMessage) {
+        match message.type_id {
+            SET_CHUNK_SIZE => {
+                self.chunk_size =
+                    u32::from_be_bytes(message.payload[..4].try_into().unwrap()) as usize;
+            }
+            COMMAND_AMF0 => {
+                let mut payload = message.payload.as_slice();
+                let mut values = vec![];
+                while !payload.is_empty() {
+                    values.push(Amf::read(&mut payload));
+                }
+                self.handle_command(message.stream_id, &values);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_command(&mut self, stream_id: u32, values: &[Amf]) {
+        let (Some(Amf::String(name)), Some(&Amf::Number(transaction_id))) =
+            (values.first(), values.get(1))
+        else {
+            panic!("RTMP test server received an invalid command: {values:?}");
+        };
+        let arguments = values.get(3..).unwrap_or_default();
+
+        match name.as_str() {
+            "connect" => {
+                let app = match values.get(2).and_then(|object| object.get("app")) {
+                    Some(Amf::String(app)) => app.clone(),
+                    app => panic!("connect has no app: {app:?}"),
+                };
+                let info = Amf::object(&[
+                    ("level", "status"),
+                    ("code", "NetConnection.Connect.Success"),
+                    ("description", &format!("Connected to {app}")),
+                ]);
+                self.send_command(0, "_result", transaction_id, vec![Amf::Null, info]);
+            }
+            "createStream" => self.send_command(
+                0,
+                "_result",
+                transaction_id,
+                vec![Amf::Null, Amf::Number(STREAM_ID as f64)],
+            ),
+            "play" => {
+                let Some(Amf::String(stream_name)) = arguments.first() else {
+                    panic!("play has no stream name: {arguments:?}");
+                };
+                self.play(stream_id, stream_name);
+            }
+            "deleteStream" => self.send_command(
+                0,
+                "onStreamDeleted",
+                0.0,
+                vec![
+                    Amf::Null,
+                    arguments.first().cloned().unwrap_or(Amf::Undefined),
+                ],
+            ),
+            _ => {}
+        }
+    }
+
+    /// Stream the tags of `<stream_name>.flv` as messages.
+    fn play(&mut self, stream_id: u32, stream_name: &str) {
+        let flv = self
+            .directory
+            .join(format!("{stream_name}.flv"))
+            .ok()
+            .and_then(|path| crate::util::read_bytes(&path).ok());
+        let Some(flv) = flv else {
+            let info = Amf::object(&[
+                ("level", "error"),
+                ("code", "NetStream.Play.StreamNotFound"),
+                ("details", stream_name),
+            ]);
+            self.send_command(stream_id, "onStatus", 0.0, vec![Amf::Null, info]);
+            return;
+        };
+
+        let info = Amf::object(&[
+            ("level", "status"),
+            ("code", "NetStream.Play.Start"),
+            ("details", stream_name),
+        ]);
+        self.send_command(stream_id, "onStatus", 0.0, vec![Amf::Null, info]);
+
+        // Skip the FLV header and the first previous tag size.
+        let mut tags = &flv[13..];
+        while tags.len() >= 11 {
+            let type_id = tags[0];
+            let length = read_u24(&tags[1..]) as usize;
+            let timestamp = read_u24(&tags[4..]) | (tags[7] as u32) << 24;
+            self.send(
+                MEDIA_CHUNK_STREAM,
+                type_id,
+                stream_id,
+                timestamp,
+                &tags[11..11 + length],
+            );
+            tags = &tags[11 + length + 4..];
+        }
+    }
+
+    fn send_command(&mut self, stream_id: u32, name: &str, transaction_id: f64, values: Vec<Amf>) {
+        let mut payload = vec![];
+        Amf::String(name.to_string()).write(&mut payload);
+        Amf::Number(transaction_id).write(&mut payload);
+        for value in values {
+            value.write(&mut payload);
+        }
+        let chunk_stream_id = if stream_id == 0 {
+            COMMAND_CHUNK_STREAM
+        } else {
+            STATUS_CHUNK_STREAM
+        };
+        self.send(chunk_stream_id, COMMAND_AMF0, stream_id, 0, &payload);
+    }
+
+    /// Split a message into chunks.
+    ///
+    /// A message that follows another on the same chunk stream and message
+    /// stream only has a type 1 header, holding the timestamp delta, so that
+    /// the client has to fill in the rest from the previous message.
+    fn send(
+        &mut self,
+        chunk_stream_id: u8,
+        type_id: u8,
+        stream_id: u32,
+        timestamp: u32,
+        payload: &[u8],
+    ) {
+        let previous = self.sent.insert(chunk_stream_id, (stream_id, timestamp));
+        let length = (payload.len() as u32).to_be_bytes();
+        match previous {
+            Some((previous_stream_id, previous_timestamp))
+                if previous_stream_id == stream_id && previous_timestamp <= timestamp =>
+            {
+                self.output.push(0x40 | chunk_stream_id);
+                self.output
+                    .extend_from_slice(&(timestamp - previous_timestamp).to_be_bytes()[1..]);
+                self.output.extend_from_slice(&length[1..]);
+                self.output.push(type_id);
+            }
+            _ => {
+                self.output.push(chunk_stream_id);
+                self.output.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+                self.output.extend_from_slice(&length[1..]);
+                self.output.push(type_id);
+                self.output.extend_from_slice(&stream_id.to_le_bytes());
+            }
+        }
+
+        for (i, chunk) in payload.chunks(DEFAULT_CHUNK_SIZE).enumerate() {
+            if i > 0 {
+                self.output.push(0xC0 | chunk_stream_id);
+            }
+            self.output.extend_from_slice(chunk);
+        }
+    }
+}
+
+/// Serve an RTMP connection until the client closes it.
+pub async fn serve(
+    directory: VfsPath,
+    handle: SocketHandle,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+) -> Result<(), Error> {
+    sender
+        .send(SocketAction::Connect(handle, ConnectionState::Connected))
+        .expect("working channel send");
+
+    let mut server = RtmpServer::new(directory);
+    while let Ok(data) = receiver.recv().await {
+        let output = server.receive(&data);
+        if !output.is_empty() {
+            sender
+                .send(SocketAction::Data(handle, output))
+                .expect("working channel send");
+        }
+    }
+    Ok(())
+}
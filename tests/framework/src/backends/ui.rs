@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use image::EncodableLayout;
 use ruffle_core::backend::ui::{
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    FullscreenError, LanguageIdentifier, MouseCursor, StorageQuotaFuture, UiBackend, US_ENGLISH,
 };
 use url::Url;
 
@@ -76,6 +76,7 @@ impl FileDialogResult for TestFileDialogResult {
 ///   otherwise a user cancellation will be simulated
 /// * Attempting to display a file save dialog with a file name hint of "debug-success.txt" will simulate successfully selecting a destination
 ///   otherwise a user cancellation will be simulated
+/// * Storage quota prompts are always accepted
 #[derive(Default)]
 pub struct TestUiBackend;
 
@@ -158,4 +159,14 @@ impl UiBackend for TestUiBackend {
     }
 
     fn close_file_dialog(&mut self) {}
+
+    fn display_storage_quota_prompt(
+        &mut self,
+        _domain: &str,
+        _requested: usize,
+    ) -> Option<StorageQuotaFuture> {
+        Some(Box::pin(async move { Ok(true) }))
+    }
+
+    fn display_storage_settings(&mut self, _domain: &str) {}
 }
@@ -29,6 +29,8 @@ pub struct TestOptions {
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    pub fetch_chunk_size: Option<usize>,
+    pub rtmp_server: bool,
     pub required_features: RequiredFeatures,
 }
 
@@ -46,6 +48,8 @@ impl Default for TestOptions {
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            fetch_chunk_size: None,
+            rtmp_server: false,
             required_features: RequiredFeatures::default(),
         }
     }
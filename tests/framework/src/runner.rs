@@ -9,13 +9,15 @@ use anyhow::{anyhow, Result};
 use image::ImageOutputFormat;
 use ruffle_core::backend::navigator::NullExecutor;
 use ruffle_core::events::MouseButton as RuffleMouseButton;
-use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
+use ruffle_core::events::{
+    KeyCode, MouseWheelDelta as RuffleMouseWheelDelta, TextControlCode as RuffleTextControlCode,
+};
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
 use ruffle_input_format::{
     AutomatedEvent, InputInjector, MouseButton as InputMouseButton,
-    TextControlCode as InputTextControlCode,
+    MouseWheelDelta as InputMouseWheelDelta, TextControlCode as InputTextControlCode,
 };
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use ruffle_socket_format::SocketEvent;
@@ -50,8 +52,11 @@ pub fn run_swf(
         test.root_path.clone(),
         &executor,
         socket_events,
+        test.options.rtmp_server,
         test.options.log_fetch.then(|| log.clone()),
+        test.options.fetch_chunk_size,
     )?;
+    let chunk_ticker = navigator.chunk_ticker();
 
     let mut builder = PlayerBuilder::new()
         .with_log(log.clone())
@@ -132,6 +137,8 @@ pub fn run_swf(
         }
         remaining_iterations -= 1;
         current_iteration += 1;
+        // Chunked responses receive one more chunk every frame.
+        chunk_ticker.tick();
         executor.run();
 
         for command in fs_commands.try_iter() {
@@ -159,7 +166,7 @@ pub fn run_swf(
             }
         }
 
-        injector.next(|evt, _btns_down| {
+        injector.next(frame_time, |evt, _btns_down| {
             player.lock().unwrap().handle_event(match evt {
                 AutomatedEvent::MouseDown { pos, btn } => PlayerEvent::MouseDown {
                     x: pos.0,
@@ -180,17 +187,30 @@ pub fn run_swf(
                         InputMouseButton::Right => RuffleMouseButton::Right,
                     },
                 },
-                AutomatedEvent::KeyDown { key_code } => PlayerEvent::KeyDown {
+                AutomatedEvent::MouseLeave => PlayerEvent::MouseLeave,
+                AutomatedEvent::MouseWheel { delta } => PlayerEvent::MouseWheel {
+                    delta: match delta {
+                        InputMouseWheelDelta::Lines(lines) => RuffleMouseWheelDelta::Lines(*lines),
+                        InputMouseWheelDelta::Pixels(pixels) => {
+                            RuffleMouseWheelDelta::Pixels(*pixels)
+                        }
+                    },
+                },
+                AutomatedEvent::KeyDown { key_code, key_char } => PlayerEvent::KeyDown {
+                    key_code: KeyCode::from_u8(*key_code).expect("Invalid keycode in test"),
+                    key_char: *key_char,
+                },
+                AutomatedEvent::KeyUp { key_code, key_char } => PlayerEvent::KeyUp {
                     key_code: KeyCode::from_u8(*key_code).expect("Invalid keycode in test"),
-                    key_char: None,
+                    key_char: *key_char,
                 },
                 AutomatedEvent::TextInput { codepoint } => PlayerEvent::TextInput {
                     codepoint: *codepoint,
                 },
                 AutomatedEvent::TextControl { code } => PlayerEvent::TextControl {
                     code: match code {
-                        InputTextControlCode::MoveLeft => RuffleTextControlCode::Backspace,
-                        InputTextControlCode::MoveRight => RuffleTextControlCode::Delete,
+                        InputTextControlCode::MoveLeft => RuffleTextControlCode::MoveLeft,
+                        InputTextControlCode::MoveRight => RuffleTextControlCode::MoveRight,
                         InputTextControlCode::SelectLeft => RuffleTextControlCode::SelectLeft,
                         InputTextControlCode::SelectRight => RuffleTextControlCode::SelectRight,
                         InputTextControlCode::SelectAll => RuffleTextControlCode::SelectAll,
@@ -202,7 +222,7 @@ pub fn run_swf(
                         InputTextControlCode::Delete => RuffleTextControlCode::Delete,
                     },
                 },
-                AutomatedEvent::Wait => unreachable!(),
+                AutomatedEvent::Wait { .. } => unreachable!(),
             });
         });
         // Rendering has side-effects (such as processing 'DisplayObject.scrollRect' updates)
@@ -19,6 +19,16 @@ pub enum MouseButton {
     Right,
 }
 
+/// The distance scrolled by the mouse wheel.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub enum MouseWheelDelta {
+    /// Scroll by a number of lines.
+    Lines(f64),
+
+    /// Scroll by a number of pixels.
+    Pixels(f64),
+}
+
 /// Control inputs to a text field
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TextControlCode {
@@ -45,7 +55,17 @@ pub enum TextControlCode {
 pub enum AutomatedEvent {
     /// End the current frame's input and wait for the next frame before
     /// continuing to inject input.
-    Wait,
+    ///
+    /// If `frames` is given, input resumes after that many frames instead.
+    /// If `ms` is given, input resumes on the first frame after at least that
+    /// many milliseconds of movie time have passed.
+    Wait {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        frames: Option<u32>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ms: Option<f64>,
+    },
 
     /// Move the mouse to a new cursor position.
     MouseMove { pos: MousePosition },
@@ -62,8 +82,29 @@ pub enum AutomatedEvent {
         btn: MouseButton,
     },
 
+    /// Move the mouse outside of the player.
+    MouseLeave,
+
+    /// Scroll the mouse wheel.
+    MouseWheel { delta: MouseWheelDelta },
+
     /// Press a key
-    KeyDown { key_code: u8 },
+    KeyDown {
+        key_code: u8,
+
+        /// The character the key would type, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key_char: Option<char>,
+    },
+
+    /// Release a key
+    KeyUp {
+        key_code: u8,
+
+        /// The character the key would type, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key_char: Option<char>,
+    },
 
     /// Input a character code
     TextInput { codepoint: char },
@@ -71,3 +112,75 @@ pub enum AutomatedEvent {
     /// Input a control character code
     TextControl { code: TextControlCode },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    /// Deserializes `value` as an event, and checks that it serializes back to the same JSON.
+    fn round_trip(value: Value) -> AutomatedEvent {
+        let event: AutomatedEvent = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), value);
+        event
+    }
+
+    #[test]
+    fn wait() {
+        assert!(matches!(
+            round_trip(json!({ "type": "Wait" })),
+            AutomatedEvent::Wait {
+                frames: None,
+                ms: None
+            }
+        ));
+        assert!(matches!(
+            round_trip(json!({ "type": "Wait", "frames": 5 })),
+            AutomatedEvent::Wait {
+                frames: Some(5),
+                ms: None
+            }
+        ));
+        assert!(matches!(
+            round_trip(json!({ "type": "Wait", "ms": 250.5 })),
+            AutomatedEvent::Wait {
+                frames: None,
+                ms: Some(ms)
+            } if ms == 250.5
+        ));
+    }
+
+    #[test]
+    fn mouse_wheel() {
+        assert!(matches!(
+            round_trip(json!({ "type": "MouseWheel", "delta": { "Lines": -3.0 } })),
+            AutomatedEvent::MouseWheel {
+                delta: MouseWheelDelta::Lines(lines)
+            } if lines == -3.0
+        ));
+        assert!(matches!(
+            round_trip(json!({ "type": "MouseWheel", "delta": { "Pixels": 12.5 } })),
+            AutomatedEvent::MouseWheel {
+                delta: MouseWheelDelta::Pixels(pixels)
+            } if pixels == 12.5
+        ));
+    }
+
+    #[test]
+    fn key_up() {
+        assert!(matches!(
+            round_trip(json!({ "type": "KeyUp", "key_code": 65, "key_char": "a" })),
+            AutomatedEvent::KeyUp {
+                key_code: 65,
+                key_char: Some('a')
+            }
+        ));
+        assert!(matches!(
+            round_trip(json!({ "type": "KeyUp", "key_code": 16 })),
+            AutomatedEvent::KeyUp {
+                key_code: 16,
+                key_char: None
+            }
+        ));
+    }
+}
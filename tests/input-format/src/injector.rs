@@ -30,6 +30,16 @@ impl From<MouseButton> for MouseButtons {
     }
 }
 
+/// How long the injector still has to wait before injecting more input.
+#[derive(Clone, Copy)]
+enum PendingWait {
+    /// Skip this many more frames.
+    Frames(u32),
+
+    /// Skip frames until this many more milliseconds have passed.
+    Millis(f64),
+}
+
 pub struct InputInjector {
     /// The list of events to inject.
     items: Vec<AutomatedEvent>,
@@ -39,6 +49,9 @@ pub struct InputInjector {
 
     /// The current set of held-down buttons.
     buttons: MouseButtons,
+
+    /// The wait that ended the last frame's input, if it hasn't elapsed yet.
+    wait: Option<PendingWait>,
 }
 
 impl InputInjector {
@@ -54,6 +67,7 @@ impl InputInjector {
             items: from_reader(file)?,
             pos: 0,
             buttons: MouseButtons::empty(),
+            wait: None,
         })
     }
 
@@ -67,6 +81,7 @@ impl InputInjector {
             items: from_reader(reader)?,
             pos: 0,
             buttons: MouseButtons::empty(),
+            wait: None,
         })
     }
 
@@ -79,23 +94,54 @@ impl InputInjector {
             items: vec![],
             pos: 0,
             buttons: MouseButtons::empty(),
+            wait: None,
         }
     }
 
     /// Run the next frame's worth of events.
-    pub fn next<Sink>(&mut self, mut event_sink: Sink)
+    ///
+    /// `frame_time` is the amount of movie time, in milliseconds, that passed
+    /// since the previous frame.
+    pub fn next<Sink>(&mut self, frame_time: f64, mut event_sink: Sink)
     where
         Sink: FnMut(&AutomatedEvent, MouseButtons),
     {
+        match &mut self.wait {
+            Some(PendingWait::Frames(frames)) if *frames > 0 => {
+                *frames -= 1;
+                return;
+            }
+            Some(PendingWait::Millis(ms)) => {
+                *ms -= frame_time;
+                if *ms > 0.0 {
+                    return;
+                }
+            }
+            _ => {}
+        }
+        self.wait = None;
+
         let mut pos = self.pos;
         if let Some(events) = self.items.get(pos..) {
             for event in events {
                 pos += 1;
 
                 match event {
-                    AutomatedEvent::Wait => break,
+                    AutomatedEvent::Wait { frames, ms } => {
+                        self.wait = match (frames, ms) {
+                            (_, Some(ms)) => Some(PendingWait::Millis(*ms)),
+                            (Some(frames), None) => {
+                                Some(PendingWait::Frames(frames.saturating_sub(1)))
+                            }
+                            (None, None) => None,
+                        };
+                        break;
+                    }
                     AutomatedEvent::MouseMove { .. }
+                    | AutomatedEvent::MouseLeave
+                    | AutomatedEvent::MouseWheel { .. }
                     | AutomatedEvent::KeyDown { .. }
+                    | AutomatedEvent::KeyUp { .. }
                     | AutomatedEvent::TextInput { .. }
                     | AutomatedEvent::TextControl { .. } => {}
                     AutomatedEvent::MouseDown { btn, .. } => {
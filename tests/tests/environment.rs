@@ -3,15 +3,23 @@ use ruffle_test_framework::environment::Environment;
 pub struct NativeEnvironment;
 
 impl Environment for NativeEnvironment {
-    #[cfg(feature = "imgtests")]
+    #[cfg(any(feature = "imgtests", feature = "imgtests_software"))]
     fn is_render_supported(
         &self,
         requirements: &ruffle_test_framework::options::RenderOptions,
     ) -> bool {
-        renderer::is_supported(requirements)
+        #[cfg(feature = "imgtests")]
+        if renderer::is_supported(requirements) {
+            return true;
+        }
+        #[cfg(feature = "imgtests_software")]
+        if software_renderer::is_supported(requirements) {
+            return true;
+        }
+        false
     }
 
-    #[cfg(feature = "imgtests")]
+    #[cfg(any(feature = "imgtests", feature = "imgtests_software"))]
     fn create_renderers(
         &self,
         width: u32,
@@ -20,9 +28,58 @@ impl Environment for NativeEnvironment {
         Box<dyn ruffle_test_framework::environment::RenderInterface>,
         Box<dyn ruffle_test_framework::environment::RenderBackend>,
     )> {
-        renderer::NativeRenderInterface::create_pair(width, height)
-            .into_iter()
-            .collect()
+        let mut renderers = vec![];
+        #[cfg(feature = "imgtests")]
+        renderers.extend(renderer::NativeRenderInterface::create_pair(width, height));
+        #[cfg(feature = "imgtests_software")]
+        renderers.push(software_renderer::SoftwareRenderInterface::create_pair(
+            width, height,
+        ));
+        renderers
+    }
+}
+
+#[cfg(feature = "imgtests_software")]
+mod software_renderer {
+    use image::RgbaImage;
+    use ruffle_core::ViewportDimensions;
+    use ruffle_render_software::SoftwareRenderBackend;
+    use ruffle_test_framework::environment::{RenderBackend, RenderInterface};
+    use ruffle_test_framework::options::RenderOptions;
+
+    pub struct SoftwareRenderInterface;
+
+    impl SoftwareRenderInterface {
+        pub fn create_pair(
+            width: u32,
+            height: u32,
+        ) -> (Box<dyn RenderInterface>, Box<dyn RenderBackend>) {
+            (
+                Box::new(Self),
+                Box::new(SoftwareRenderBackend::new(ViewportDimensions {
+                    width,
+                    height,
+                    scale_factor: 1.0,
+                })),
+            )
+        }
+    }
+
+    impl RenderInterface for SoftwareRenderInterface {
+        fn name(&self) -> String {
+            format!("{}-software", std::env::consts::OS)
+        }
+
+        fn capture(&self, backend: &mut Box<dyn RenderBackend>) -> RgbaImage {
+            let renderer = backend.downcast_mut::<SoftwareRenderBackend>().unwrap();
+
+            renderer.capture_frame()
+        }
+    }
+
+    pub fn is_supported(_requirements: &RenderOptions) -> bool {
+        // The software renderer needs no device, so it can always run.
+        true
     }
 }
 
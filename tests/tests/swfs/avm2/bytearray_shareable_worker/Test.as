@@ -0,0 +1,65 @@
+package {
+    import flash.display.Sprite;
+    import flash.system.MessageChannel;
+    import flash.system.Worker;
+    import flash.system.WorkerDomain;
+    import flash.utils.ByteArray;
+    import flash.utils.Endian;
+
+    public class Test extends Sprite {
+        public function Test() {
+            if (Worker.current.isPrimordial) {
+                primordial();
+            } else {
+                background();
+            }
+        }
+
+        private function primordial():void {
+            var worker:Worker = WorkerDomain.current.createWorker(loaderInfo.bytes);
+            var fromWorker:MessageChannel = worker.createMessageChannel(Worker.current);
+            worker.setSharedProperty("fromWorker", fromWorker);
+
+            var shared:ByteArray = new ByteArray();
+            shared.shareable = true;
+            shared.writeUTFBytes("abc");
+            worker.setSharedProperty("shared", shared);
+
+            var copied:ByteArray = new ByteArray();
+            copied.writeUTFBytes("xyz");
+            worker.setSharedProperty("copied", copied);
+
+            worker.start();
+            fromWorker.receive(true);
+
+            trace("shared: " + shared.toString());
+            trace("shared length: " + shared.length);
+            trace("shared position: " + shared.position);
+            trace("shared endian: " + shared.endian);
+            trace("copied: " + copied.toString());
+
+            shared.position = 0;
+            shared.endian = Endian.LITTLE_ENDIAN;
+            trace("same bytes from another worker: " + (worker.getSharedProperty("shared") as ByteArray).toString());
+
+            worker.terminate();
+        }
+
+        private function background():void {
+            var shared:ByteArray = Worker.current.getSharedProperty("shared");
+            trace("background shareable: " + shared.shareable);
+            trace("background position: " + shared.position);
+            trace("background endian: " + shared.endian);
+            shared.position = shared.length;
+            shared.endian = Endian.LITTLE_ENDIAN;
+            shared.writeUTFBytes("def");
+
+            var copied:ByteArray = Worker.current.getSharedProperty("copied");
+            trace("background copied: " + copied.toString());
+            copied.writeUTFBytes("!");
+
+            var toPrimordial:MessageChannel = Worker.current.getSharedProperty("fromWorker");
+            toPrimordial.send("done");
+        }
+    }
+}
@@ -0,0 +1,48 @@
+package {
+	import flash.display.Shape;
+	import flash.display.Sprite;
+
+	public class Test extends Sprite {
+		public function Test() {
+			// Rotated around the y axis: the far edge should be shorter than the near one,
+			// and the quadrants should be narrower towards it.
+			var y = square();
+			y.x = 60;
+			y.y = 120;
+			y.rotationY = 50;
+			addChild(y);
+
+			// Rotated around the x axis.
+			var x = square();
+			x.x = 330;
+			x.y = 120;
+			x.rotationX = 50;
+			addChild(x);
+
+			// Inside a scaled 2D parent, depth is scaled along with the parent.
+			var parent = new Sprite();
+			parent.x = 150;
+			parent.y = 300;
+			parent.scaleX = 0.5;
+			parent.scaleY = 0.5;
+			var nested = square();
+			nested.rotationY = -40;
+			parent.addChild(nested);
+			addChild(parent);
+		}
+
+		private function square():Shape {
+			var shape = new Shape();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawRect(0, 0, 75, 75);
+			shape.graphics.beginFill(0x00FF00);
+			shape.graphics.drawRect(75, 0, 75, 75);
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.drawRect(0, 75, 75, 75);
+			shape.graphics.beginFill(0x000000);
+			shape.graphics.drawRect(75, 75, 75, 75);
+			shape.graphics.endFill();
+			return shape;
+		}
+	}
+}
@@ -0,0 +1,59 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.NetStatusEvent;
+	import flash.net.NetConnection;
+	import flash.net.NetStream;
+
+	public class Test extends Sprite {
+		private var connection:NetConnection;
+		private var stream:NetStream;
+
+		public function Test() {
+			connection = new NetConnection();
+			connection.client = {
+				onStreamDeleted: function(id:Number):void {
+					trace("Server deleted stream " + id);
+					connection.close();
+				}
+			};
+			connection.addEventListener(NetStatusEvent.NET_STATUS, onConnectionStatus);
+			connection.connect("rtmp://localhost/vod");
+		}
+
+		private function onConnectionStatus(event:NetStatusEvent):void {
+			trace("NetConnection: " + event.info.code + describe(event.info.description));
+			if (event.info.code != "NetConnection.Connect.Success") {
+				return;
+			}
+
+			stream = new NetStream(connection);
+			stream.client = {
+				onMetaData: function(data:Object):void {
+					// The description is long enough to be split into several chunks.
+					trace("onMetaData: " + data.title + ", " + data.description.length + " characters of description");
+				},
+				onCuePoint: function(data:Object):void {
+					trace("onCuePoint: " + data.name);
+					addEventListener(Event.ENTER_FRAME, closeStream);
+				}
+			};
+			stream.addEventListener(NetStatusEvent.NET_STATUS, onStreamStatus);
+			stream.play("stream");
+		}
+
+		private function onStreamStatus(event:NetStatusEvent):void {
+			trace("NetStream: " + event.info.code + describe(event.info.details));
+		}
+
+		private function closeStream(event:Event):void {
+			removeEventListener(Event.ENTER_FRAME, closeStream);
+			trace("Closing stream");
+			stream.close();
+		}
+
+		private function describe(text:*):String {
+			return text ? " (" + text + ")" : "";
+		}
+	}
+}
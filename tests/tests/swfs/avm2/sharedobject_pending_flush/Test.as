@@ -0,0 +1,37 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.NetStatusEvent;
+	import flash.net.SharedObject;
+
+	public class Test extends Sprite {
+		private var so:SharedObject;
+
+		public function Test() {
+			trace("Usage before flushing: " + SharedObject.getDiskUsage(loaderInfo.url));
+
+			so = SharedObject.getLocal("pending");
+			so.addEventListener(NetStatusEvent.NET_STATUS, onStatus);
+
+			// Asking for more than the default quota of 100 KB prompts the user.
+			so.data.value = "first";
+			trace("First flush: " + so.flush(200 * 1024));
+
+			// The user is still being asked, so this doesn't prompt again.
+			so.data.value = "second, which is longer than the first";
+			trace("Second flush: " + so.flush(200 * 1024));
+		}
+
+		private function onStatus(event:NetStatusEvent):void {
+			trace("netStatus: " + event.info.code);
+
+			var usage:Number = SharedObject.getDiskUsage(loaderInfo.url);
+			trace("Stored the second flush: " + (usage == so.size));
+
+			trace("Deleted from another domain: " + SharedObject.deleteAll("example.com"));
+			trace("Usage of another domain: " + SharedObject.getDiskUsage("example.com"));
+
+			trace("Deleted: " + SharedObject.deleteAll(loaderInfo.url));
+			trace("Usage after deleting: " + SharedObject.getDiskUsage(loaderInfo.url));
+		}
+	}
+}
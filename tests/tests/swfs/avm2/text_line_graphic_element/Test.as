@@ -0,0 +1,57 @@
+package {
+    import flash.display.DisplayObjectContainer;
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.text.engine.ContentElement;
+    import flash.text.engine.ElementFormat;
+    import flash.text.engine.GraphicElement;
+    import flash.text.engine.GroupElement;
+    import flash.text.engine.TextBlock;
+    import flash.text.engine.TextElement;
+    import flash.text.engine.TextLine;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var shape:Shape = new Shape();
+            shape.graphics.beginFill(0xFF0000);
+            shape.graphics.drawRect(0, 0, 20, 10);
+
+            var format:ElementFormat = new ElementFormat();
+            var elements:Vector.<ContentElement> = new <ContentElement>[
+                new GraphicElement(shape, 20, 10, format),
+                new TextElement("Hello", format)
+            ];
+            var block:TextBlock = new TextBlock(new GroupElement(elements));
+
+            var line:TextLine = block.createTextLine(null, 200);
+            trace("is container: " + (line is DisplayObjectContainer));
+            trace("hasGraphicElement: " + line.hasGraphicElement);
+            trace("atomCount: " + line.atomCount);
+            trace("numChildren: " + line.numChildren);
+            trace("child is graphic: " + (line.getChildAt(0) == shape));
+            trace("graphic parent is line: " + (shape.parent == line));
+            trace("graphic position: " + shape.x + ", " + shape.y);
+            trace("graphic atom bounds: " + line.getAtomBounds(0));
+
+            var extra:Sprite = new Sprite();
+            line.addChild(extra);
+            trace("numChildren after addChild: " + line.numChildren);
+
+            // Laying the line out again keeps its graphic and anything added to it.
+            var recreated:TextLine = block.recreateTextLine(line, null, 200);
+            trace("recreated same line: " + (recreated == line));
+            trace("numChildren after recreate: " + line.numChildren);
+            trace("graphic parent is line: " + (shape.parent == line));
+            trace("extra parent is line: " + (extra.parent == line));
+
+            // A new line for the same content takes the graphic away from the old one.
+            var other:TextLine = block.createTextLine(null, 200);
+            trace("graphic parent is new line: " + (shape.parent == other));
+            trace("old line numChildren: " + line.numChildren);
+
+            addChild(other);
+            other.x = 10;
+            other.y = 30;
+        }
+    }
+}
@@ -0,0 +1,24 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.text.TextField;
+    import flash.text.TextFieldType;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var field:TextField = new TextField();
+            field.type = TextFieldType.INPUT;
+            field.text = "Ruffle";
+            addChild(field);
+
+            field.addEventListener(Event.CHANGE, function(e:Event):void {
+                trace("New text: " + field.text);
+                trace("Caret: " + field.caretIndex + ", selection: " + field.selectionBeginIndex + "-" + field.selectionEndIndex);
+            });
+
+            stage.focus = field;
+            field.setSelection(6, 6);
+            trace("Caret: " + field.caretIndex + ", selection: " + field.selectionBeginIndex + "-" + field.selectionEndIndex);
+        }
+    }
+}
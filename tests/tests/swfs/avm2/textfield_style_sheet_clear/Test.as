@@ -0,0 +1,38 @@
+package {
+    import flash.display.MovieClip;
+    import flash.text.StyleSheet;
+    import flash.text.TextField;
+    import flash.text.TextFormat;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var field:TextField = new TextField();
+            addChild(field);
+
+            var styles:StyleSheet = new StyleSheet();
+            styles.parseCSS(".title { color: #FF0000; font-weight: bold; }");
+
+            trace("// styleSheet = styles");
+            field.styleSheet = styles;
+            field.htmlText = "<span class=\"title\">Hello</span> World";
+            traceField(field);
+
+            trace("// styleSheet = null");
+            field.styleSheet = null;
+            traceField(field);
+        }
+
+        private static function traceField(field:TextField):void {
+            trace("styleSheet: " + (field.styleSheet != null));
+            trace("text: " + field.text);
+            traceFormat(field, 0);
+            traceFormat(field, 7);
+            trace("");
+        }
+
+        private static function traceFormat(field:TextField, index:int):void {
+            var format:TextFormat = field.getTextFormat(index, index + 1);
+            trace("format at " + index + ": color " + format.color.toString(16) + ", bold " + format.bold);
+        }
+    }
+}
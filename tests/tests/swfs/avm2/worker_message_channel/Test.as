@@ -0,0 +1,60 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.system.MessageChannel;
+    import flash.system.Worker;
+    import flash.system.WorkerDomain;
+
+    public class Test extends Sprite {
+        private var toPrimordial:MessageChannel;
+
+        public function Test() {
+            if (Worker.current.isPrimordial) {
+                primordial();
+            } else {
+                background();
+            }
+        }
+
+        private function primordial():void {
+            var worker:Worker = WorkerDomain.current.createWorker(loaderInfo.bytes);
+            var toWorker:MessageChannel = Worker.current.createMessageChannel(worker);
+            var fromWorker:MessageChannel = worker.createMessageChannel(Worker.current);
+            worker.setSharedProperty("toWorker", toWorker);
+            worker.setSharedProperty("fromWorker", fromWorker);
+            worker.setSharedProperty("greeting", "hello");
+            trace("state: " + worker.state);
+
+            toWorker.send(1);
+            toWorker.send({a: 2});
+            trace("messageAvailable: " + toWorker.messageAvailable);
+            worker.start();
+
+            // Each of these waits for the background worker to send a message.
+            trace("primordial received: " + fromWorker.receive(true));
+            trace("primordial received: " + fromWorker.receive(true));
+            trace("nothing left: " + fromWorker.receive());
+
+            trace("terminate: " + worker.terminate());
+            trace("state: " + worker.state);
+        }
+
+        private function background():void {
+            var toWorker:MessageChannel = Worker.current.getSharedProperty("toWorker");
+            toPrimordial = Worker.current.getSharedProperty("fromWorker");
+            trace("background greeting: " + Worker.current.getSharedProperty("greeting"));
+            trace("background received: " + toWorker.receive());
+            trace("background received a: " + toWorker.receive().a);
+            trace("background messageAvailable: " + toWorker.messageAvailable);
+
+            toPrimordial.send("first reply");
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(event:Event):void {
+            removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            trace("background sending on the next frame");
+            toPrimordial.send("second reply");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+package {
+    import flash.concurrent.Condition;
+    import flash.concurrent.Mutex;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.system.MessageChannel;
+    import flash.system.Worker;
+    import flash.system.WorkerDomain;
+
+    public class Test extends Sprite {
+        private var mutex:Mutex;
+        private var toPrimordial:MessageChannel;
+        private var frames:int = 0;
+
+        public function Test() {
+            if (Worker.current.isPrimordial) {
+                primordial();
+            } else {
+                background();
+            }
+        }
+
+        private function primordial():void {
+            var worker:Worker = WorkerDomain.current.createWorker(loaderInfo.bytes);
+            var fromWorker:MessageChannel = worker.createMessageChannel(Worker.current);
+            mutex = new Mutex();
+            var condition:Condition = new Condition(mutex);
+            worker.setSharedProperty("mutex", mutex);
+            worker.setSharedProperty("condition", condition);
+            worker.setSharedProperty("fromWorker", fromWorker);
+
+            try {
+                condition.wait();
+            } catch (e:Error) {
+                trace("wait without the mutex: " + e.errorID);
+            }
+
+            mutex.lock();
+            worker.start();
+
+            // The background worker can only lock the mutex while this waits.
+            trace("primordial waiting");
+            trace("primordial notified: " + condition.wait());
+            trace("value: " + worker.getSharedProperty("value"));
+            mutex.unlock();
+
+            trace("tryLock while free: " + mutex.tryLock());
+            mutex.unlock();
+
+            trace("primordial received: " + fromWorker.receive(true));
+            trace("tryLock while locked: " + mutex.tryLock());
+            mutex.lock();
+            trace("primordial locked");
+
+            trace("wait with a timeout: " + condition.wait(50));
+            mutex.unlock();
+
+            try {
+                mutex.unlock();
+            } catch (e:Error) {
+                trace("unlock without the mutex: " + e.errorID);
+            }
+
+            worker.terminate();
+        }
+
+        private function background():void {
+            mutex = Worker.current.getSharedProperty("mutex");
+            var condition:Condition = Worker.current.getSharedProperty("condition");
+            toPrimordial = Worker.current.getSharedProperty("fromWorker");
+
+            mutex.lock();
+            Worker.current.setSharedProperty("value", 42);
+            trace("background notifying");
+            condition.notify();
+            mutex.unlock();
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(event:Event):void {
+            frames++;
+            if (frames == 1) {
+                mutex.lock();
+                trace("background locked");
+                toPrimordial.send("locked");
+            } else if (frames == 2) {
+                trace("background unlocking");
+                mutex.unlock();
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+#!/usr/bin/env python3
+"""Derives the gradient filter tests in this directory from Flash-authored tests.
+
+`GradientGlowFilter` and `GradientBevelFilter` can describe exactly what `GlowFilter`,
+`DropShadowFilter` and `BevelFilter` do, so rewriting the filters of those tests into their
+gradient equivalents lets the new tests keep the reference images captured from Flash Player.
+Run this from any directory; it writes next to itself.
+
+`gradient_glow_pass_scaling` creates its filters from ActionScript instead, so its `Test.as`
+is the one of `glow_pass_scaling` using `GradientGlowFilter`, compiled into a copy of the SWF
+of `glow_pass_scaling` when the compiled SWF is passed as the first argument.
+"""
+
+import os
+import shutil
+import struct
+import sys
+import zlib
+
+DROP_SHADOW, GLOW, BEVEL, GRADIENT_GLOW, GRADIENT_BEVEL = 0, 2, 3, 4, 7
+
+
+class BitReader:
+    def __init__(self, data, pos):
+        self.data, self.bit = data, pos * 8
+
+    def ub(self, n):
+        value = 0
+        for _ in range(n):
+            byte = self.data[self.bit // 8]
+            value = value << 1 | (byte >> (7 - self.bit % 8)) & 1
+            self.bit += 1
+        return value
+
+    def end(self):
+        return (self.bit + 7) // 8
+
+
+def skip_matrix(data, pos):
+    bits = BitReader(data, pos)
+    if bits.ub(1):
+        bits.ub(2 * bits.ub(5))
+    if bits.ub(1):
+        bits.ub(2 * bits.ub(5))
+    bits.ub(2 * bits.ub(5))
+    return bits.end()
+
+
+def skip_color_transform(data, pos):
+    bits = BitReader(data, pos)
+    has_add, has_mult = bits.ub(1), bits.ub(1)
+    n = bits.ub(4)
+    bits.ub(n * 4 * (has_add + has_mult))
+    return bits.end()
+
+
+def gradient_filter(kind, stops, blur_x, blur_y, angle, distance, strength, flags):
+    body = bytes([kind, len(stops)])
+    body += b"".join(bytes(color) for color, _ in stops)
+    body += bytes(ratio for _, ratio in stops)
+    return body + blur_x + blur_y + angle + distance + strength + bytes([flags])
+
+
+def convert_filters(data, pos):
+    """Rewrites the filter list at `pos`, returning the new list and the end of the old one."""
+    count = data[pos]
+    pos += 1
+    result = bytes([count])
+    for _ in range(count):
+        kind = data[pos]
+        pos += 1
+        if kind == DROP_SHADOW:
+            color = data[pos:pos + 4]
+            rest = data[pos + 4:pos + 23]
+            pos += 23
+            # See below for the flags.
+            flags = rest[18]
+            gradient_flags = (flags & 0b1110_0000) | min(flags & 0b1_1111, 15)
+            result += gradient_filter(
+                GRADIENT_GLOW,
+                [(bytes([*color[:3], 0]), 0), (color, 255)],
+                rest[0:4], rest[4:8], rest[8:12], rest[12:16], rest[16:18], gradient_flags,
+            )
+        elif kind == GLOW:
+            color = data[pos:pos + 4]
+            blur_x, blur_y = data[pos + 4:pos + 8], data[pos + 8:pos + 12]
+            strength, flags = data[pos + 12:pos + 14], data[pos + 14]
+            pos += 15
+            # Glow flags are inner, knockout, composite source and 5 bits of passes,
+            # while gradient flags are inner, knockout, composite source, on top and 4 bits of passes.
+            gradient_flags = (flags & 0b1110_0000) | min(flags & 0b1_1111, 15)
+            transparent = bytes([*color[:3], 0])
+            result += gradient_filter(
+                GRADIENT_GLOW,
+                [(transparent, 0), (color, 255)],
+                blur_x, blur_y, bytes(4), bytes(4), strength, gradient_flags,
+            )
+        elif kind == BEVEL:
+            highlight, shadow = data[pos:pos + 4], data[pos + 4:pos + 8]
+            rest = data[pos + 8:pos + 27]
+            pos += 27
+            # The highlight is at the start of the gradient and the shadow at its end.
+            result += gradient_filter(
+                GRADIENT_BEVEL,
+                [
+                    (highlight, 0),
+                    (bytes([*highlight[:3], 0]), 127),
+                    (bytes([*shadow[:3], 0]), 128),
+                    (shadow, 255),
+                ],
+                rest[0:4], rest[4:8], rest[8:12], rest[12:16], rest[16:18], rest[18],
+            )
+        else:
+            raise ValueError(f"unexpected filter {kind}")
+    return result, pos
+
+
+def convert_place_object(body):
+    flags, flags2 = body[0], body[1]
+    pos = 4  # Flags and depth
+    if flags2 & 0x08 or (flags2 & 0x10 and flags & 0x02):
+        pos = body.index(b"\0", pos) + 1
+    if flags & 0x02:
+        pos += 2
+    if flags & 0x04:
+        pos = skip_matrix(body, pos)
+    if flags & 0x08:
+        pos = skip_color_transform(body, pos)
+    if flags & 0x10:
+        pos += 2
+    if flags & 0x20:
+        pos = body.index(b"\0", pos) + 1
+    if flags & 0x40:
+        pos += 2
+    if not flags2 & 0x01:
+        return body
+    filters, end = convert_filters(body, pos)
+    return body[:pos] + filters + body[end:]
+
+
+def tag(code, body):
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def split_tags(data):
+    pos = 0
+    while pos < len(data):
+        header = struct.unpack("<H", data[pos:pos + 2])[0]
+        pos += 2
+        code, length = header >> 6, header & 0x3F
+        if length == 0x3F:
+            length = struct.unpack("<I", data[pos:pos + 4])[0]
+            pos += 4
+        yield code, data[pos:pos + length]
+        pos += length
+
+
+def convert_tags(data):
+    result = b""
+    for code, body in split_tags(data):
+        if code == 70:
+            body = convert_place_object(body)
+        elif code == 39:
+            body = body[:4] + convert_tags(body[4:])
+        result += tag(code, body)
+    return result
+
+
+def read_swf(path):
+    data = open(path, "rb").read()
+    body = zlib.decompress(data[8:]) if data[:3] == b"CWS" else data[8:]
+    header_length = (5 + 4 * (body[0] >> 3) + 7) // 8 + 4
+    return data[3], body[:header_length], body[header_length:]
+
+
+def write_swf(path, version, header, tags):
+    body = header + tags
+    with open(path, "wb") as f:
+        f.write(b"CWS" + bytes([version]) + struct.pack("<I", 8 + len(body)) + zlib.compress(body, 9))
+
+
+def convert_swf(source, destination):
+    version, header, tags = read_swf(source)
+    write_swf(destination, version, header, convert_tags(tags))
+
+
+def replace_abc(source, compiled, destination):
+    """Replaces the `DoABC` tag of `source` with the bytecode of the `compiled` SWF."""
+    version, header, tags = read_swf(source)
+    abc = next(body for code, body in split_tags(read_swf(compiled)[2]) if code in (72, 82))
+    if not abc.startswith(b"\x10\x00\x2e\x00"):
+        # `DoABC2` has flags and a name before the bytecode.
+        abc = abc[abc.index(b"\0", 4) + 1:]
+    result = b""
+    for code, body in split_tags(tags):
+        if code == 82:
+            body = struct.pack("<I", 1) + b"\0" + abc
+        result += tag(code, body)
+    write_swf(destination, version, header, result)
+
+
+def copy_reference(source, directory):
+    for file in ("output.expected.png", "output.txt"):
+        shutil.copy(os.path.join(source, file), os.path.join(directory, file))
+    with open(os.path.join(source, "test.toml")) as f:
+        toml = f.read()
+    with open(os.path.join(directory, "test.toml"), "w") as f:
+        name = os.path.basename(source)
+        f.write(f"# The reference is the one of `{name}`, which uses the equivalent non-gradient filter.\n")
+        f.write(toml)
+
+
+def main():
+    here = os.path.dirname(os.path.abspath(__file__))
+    tests = (
+        ("glow", "gradient_glow"),
+        ("drop_shadow_angles", "gradient_glow_drop_shadow_angles"),
+        ("bevel", "gradient_bevel"),
+        ("bevel_full", "gradient_bevel_full"),
+    )
+    for source, name in tests:
+        directory = os.path.join(here, name)
+        os.makedirs(directory, exist_ok=True)
+        convert_swf(os.path.join(here, source, "test.swf"), os.path.join(directory, "test.swf"))
+        copy_reference(os.path.join(here, source), directory)
+
+    if len(sys.argv) > 1:
+        # The path to `gradient_glow_pass_scaling/Test.as` compiled into a SWF.
+        directory = os.path.join(here, "gradient_glow_pass_scaling")
+        source = os.path.join(here, "glow_pass_scaling")
+        replace_abc(os.path.join(source, "test.swf"), sys.argv[1], os.path.join(directory, "test.swf"))
+        copy_reference(source, directory)
+
+
+if __name__ == "__main__":
+    main()
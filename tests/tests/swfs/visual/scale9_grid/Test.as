@@ -0,0 +1,79 @@
+package {
+    import flash.display.Graphics;
+    import flash.display.MovieClip;
+    import flash.display.Sprite;
+    import flash.geom.Rectangle;
+
+    public class Test extends MovieClip {
+        // One color per region of the grid, row by row.
+        private static const COLORS:Array = [
+            0xFF0000, 0x00FF00, 0x0000FF,
+            0xFFFF00, 0x00FFFF, 0xFF00FF,
+            0x800000, 0x008000, 0x000080
+        ];
+        private static const EDGES:Array = [0, 20, 80, 100];
+
+        public function Test() {
+            // The grid of a sprite applies to its own graphics.
+            var direct:Sprite = new Sprite();
+            drawRegions(direct.graphics, 0, 3);
+            direct.scale9Grid = new Rectangle(20, 20, 60, 60);
+            direct.x = 10;
+            direct.y = 10;
+            direct.scaleX = 1.5;
+            direct.scaleY = 1.2;
+            addChild(direct);
+
+            // The grid of an ancestor applies to the graphics of nested clips,
+            // in the coordinates of that ancestor.
+            var outer:MovieClip = new MovieClip();
+            var inner:MovieClip = new MovieClip();
+            var left:MovieClip = new MovieClip();
+            drawRegions(left.graphics, 0, 1);
+            inner.addChild(left);
+            var right:MovieClip = new MovieClip();
+            drawRegions(right.graphics, 1, 3);
+            right.x = 20;
+            inner.addChild(right);
+            outer.addChild(inner);
+            outer.scale9Grid = new Rectangle(20, 20, 60, 60);
+            outer.x = 200;
+            outer.y = 10;
+            outer.scaleX = 1.5;
+            outer.scaleY = 1.2;
+            addChild(outer);
+
+            // The borders keep their size on the stage when a parent is scaled as well.
+            var container:Sprite = new Sprite();
+            var child:Sprite = new Sprite();
+            drawRegions(child.graphics, 0, 3);
+            child.scale9Grid = new Rectangle(20, 20, 60, 60);
+            child.scaleX = 3;
+            child.scaleY = 2.4;
+            container.addChild(child);
+            container.x = 10;
+            container.y = 160;
+            container.scaleX = 0.5;
+            container.scaleY = 0.5;
+            addChild(container);
+        }
+
+        // Draws the columns from `firstColumn` up to `endColumn` of the regions,
+        // with the left edge of the first one at x = 0.
+        private static function drawRegions(graphics:Graphics, firstColumn:int, endColumn:int):void {
+            var offset:Number = EDGES[firstColumn];
+            for (var row:int = 0; row < 3; row++) {
+                for (var column:int = firstColumn; column < endColumn; column++) {
+                    graphics.beginFill(COLORS[row * 3 + column]);
+                    graphics.drawRect(
+                        EDGES[column] - offset,
+                        EDGES[row],
+                        EDGES[column + 1] - EDGES[column],
+                        EDGES[row + 1] - EDGES[row]
+                    );
+                    graphics.endFill();
+                }
+            }
+        }
+    }
+}
@@ -45,6 +45,7 @@ export const DEFAULT_CONFIG: Required<BaseLoadOptions> = {
     allowNetworking: NetworkingAccessMode.All,
     openInNewTab: null,
     socketProxy: [],
+    enforcePolicyFiles: false,
     fontSources: [],
     defaultFonts: {},
     credentialAllowList: [],
@@ -586,6 +586,17 @@ export interface BaseLoadOptions {
      */
     socketProxy?: Array<SocketProxy>;
 
+    /**
+     * If set to true, movies need cross-domain policy files to read data
+     * from other domains or to connect sockets, like in Flash Player.
+     *
+     * This is off by default, as the browser already applies its own
+     * cross-origin rules, and most servers don't serve policy files.
+     *
+     * @default false
+     */
+    enforcePolicyFiles?: boolean;
+
     /**
      * An array of font URLs to eagerly load and provide to Ruffle.
      *
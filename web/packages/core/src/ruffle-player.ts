@@ -2316,6 +2316,47 @@ export class RufflePlayer extends HTMLElement {
         };
     }
 
+    /**
+     * Ask the user whether a domain may store more shared objects, in front of the player.
+     *
+     * @param domain The domain of the shared objects.
+     * @param kilobytes The amount of storage requested.
+     * @returns A promise that resolves to whether the user allowed it.
+     */
+    protected displayStorageQuotaPrompt(
+        domain: string,
+        kilobytes: number,
+    ): Promise<boolean> {
+        return new Promise((resolve) => {
+            const div = document.createElement("div");
+            div.id = "message-overlay";
+            const messageDiv = document.createElement("div");
+            messageDiv.className = "message";
+            const messageP = document.createElement("p");
+            messageP.textContent = text("storage-quota-prompt", {
+                domain,
+                kilobytes,
+            });
+            messageDiv.appendChild(messageP);
+            const buttonDiv = document.createElement("div");
+            for (const [id, granted] of [
+                ["storage-quota-prompt-allow", true],
+                ["storage-quota-prompt-deny", false],
+            ] as const) {
+                const button = document.createElement("button");
+                button.textContent = text(id);
+                button.onclick = () => {
+                    div.parentNode!.removeChild(div);
+                    resolve(granted);
+                };
+                buttonDiv.appendChild(button);
+            }
+            messageDiv.appendChild(buttonDiv);
+            div.appendChild(messageDiv);
+            this.container.prepend(div);
+        });
+    }
+
     /**
      * Show a video that uses an unsupported codec in a pop up.
      *
@@ -0,0 +1,3 @@
+storage-quota-prompt = { $domain } wants to store up to { $kilobytes } KB of information on your computer.
+storage-quota-prompt-allow = Allow
+storage-quota-prompt-deny = Deny
@@ -127,6 +127,13 @@ extern "C" {
 
     #[wasm_bindgen(method, js_name = "displayUnsupportedVideo")]
     fn display_unsupported_video(this: &JavascriptPlayer, url: &str);
+
+    #[wasm_bindgen(method, js_name = "displayStorageQuotaPrompt")]
+    fn display_storage_quota_prompt(
+        this: &JavascriptPlayer,
+        domain: &str,
+        kilobytes: usize,
+    ) -> js_sys::Promise;
 }
 
 #[derive(Clone)]
@@ -303,6 +310,8 @@ struct Config {
 
     socket_proxy: Vec<SocketProxy>,
 
+    enforce_policy_files: bool,
+
     credential_allow_list: Vec<String>,
 }
 
@@ -674,6 +683,7 @@ impl Ruffle {
             .with_frame_rate(config.frame_rate)
             // FIXME - should this be configurable?
             .with_sandbox_type(SandboxType::Remote)
+            .with_policy_files(config.enforce_policy_files)
             .with_page_url(window.location().href().ok())
             .build();
 
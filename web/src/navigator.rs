@@ -3,15 +3,18 @@ use crate::SocketProxy;
 use async_channel::Receiver;
 use futures_util::{SinkExt, StreamExt};
 use gloo_net::websocket::{futures::WebSocket, Message};
-use js_sys::{Array, ArrayBuffer, Uint8Array};
+use js_sys::{Array, ArrayBuffer, Reflect, Uint8Array};
 use ruffle_core::backend::navigator::{
     async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SuccessResponse,
+    NavigatorBackend, OpenURLMode, OwnedFuture, Request, ResponseBody, StreamingResponse,
+    SuccessResponse,
 };
 use ruffle_core::config::NetworkingAccessMode;
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::time::Duration;
@@ -22,10 +25,33 @@ use url::{ParseError, Url};
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::{
-    window, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, Request as WebRequest,
-    RequestCredentials, RequestInit, Response as WebResponse,
+    window, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, ReadableStreamDefaultReader,
+    Request as WebRequest, RequestCredentials, RequestInit, Response as WebResponse,
 };
 
+/// The body of a fetch response, read as it arrives from the network.
+struct WebResponseBody(ReadableStreamDefaultReader);
+
+impl ResponseBody for WebResponseBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        Box::pin(async move {
+            let result = JsFuture::from(self.0.read())
+                .await
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+            let done = Reflect::get(&result, &"done".into())
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+            if done.as_bool().unwrap_or(true) {
+                return Ok(None);
+            }
+            let chunk: Uint8Array = Reflect::get(&result, &"value".into())
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?
+                .dyn_into()
+                .map_err(|_| Error::FetchError("Response chunk wasn't a Uint8Array".to_string()))?;
+            Ok(Some(chunk.to_vec()))
+        })
+    }
+}
+
 pub struct WebNavigatorBackend {
     log_subscriber: Arc<Layered<WASMLayer, Registry>>,
     allow_script_access: bool,
@@ -93,6 +119,114 @@ impl WebNavigatorBackend {
             credential_allow_list,
         }
     }
+
+    /// Send a request, returning the response once its headers are received.
+    fn send_request(&self, request: Request) -> OwnedFuture<WebResponse, ErrorResponse> {
+        let url = match self.resolve_url(request.url()) {
+            Ok(url) => {
+                if url.scheme() == "file" {
+                    return async_return(create_specific_fetch_error(
+                        "WASM target can't fetch local URL",
+                        url.as_str(),
+                        "",
+                    ));
+                } else {
+                    url
+                }
+            }
+            Err(e) => {
+                return async_return(create_fetch_error(request.url(), e));
+            }
+        };
+
+        let credentials = if let Some(host) = url.host_str() {
+            if self
+                .credential_allow_list
+                .iter()
+                .any(|allowed| allowed == &format!("{}://{}", url.scheme(), host))
+            {
+                RequestCredentials::Include
+            } else {
+                RequestCredentials::SameOrigin
+            }
+        } else {
+            RequestCredentials::SameOrigin
+        };
+
+        Box::pin(async move {
+            let mut init = RequestInit::new();
+
+            init.method(&request.method().to_string());
+            init.credentials(credentials);
+
+            if let Some((data, mime)) = request.body() {
+                let blob = Blob::new_with_buffer_source_sequence_and_options(
+                    &Array::from_iter([Uint8Array::from(data.as_slice()).buffer()]),
+                    BlobPropertyBag::new().type_(mime),
+                )
+                .map_err(|_| ErrorResponse {
+                    url: url.to_string(),
+                    error: Error::FetchError("Got JS error".to_string()),
+                })?
+                .dyn_into()
+                .map_err(|_| ErrorResponse {
+                    url: url.to_string(),
+                    error: Error::FetchError("Got JS error".to_string()),
+                })?;
+
+                init.body(Some(&blob));
+            }
+
+            let web_request = match WebRequest::new_with_str_and_init(url.as_str(), &init) {
+                Ok(web_request) => web_request,
+                Err(_) => {
+                    return create_specific_fetch_error(
+                        "Unable to create request for",
+                        url.as_str(),
+                        "",
+                    )
+                }
+            };
+
+            let headers = web_request.headers();
+
+            for (header_name, header_val) in request.headers() {
+                headers
+                    .set(header_name, header_val)
+                    .map_err(|_| ErrorResponse {
+                        url: url.to_string(),
+                        error: Error::FetchError("Got JS error".to_string()),
+                    })?;
+            }
+
+            let window = web_sys::window().expect("window()");
+            let fetchval = JsFuture::from(window.fetch_with_request(&web_request))
+                .await
+                .map_err(|_| ErrorResponse {
+                    url: url.to_string(),
+                    error: Error::FetchError("Got JS error".to_string()),
+                })?;
+
+            let response: WebResponse = fetchval.dyn_into().map_err(|_| ErrorResponse {
+                url: url.to_string(),
+                error: Error::FetchError("Fetch result wasn't a WebResponse".to_string()),
+            })?;
+            if !response.ok() {
+                let error = Error::HttpNotOk(
+                    format!("HTTP status is not ok, got {}", response.status_text()),
+                    response.status(),
+                    response.redirected(),
+                    0,
+                );
+                return Err(ErrorResponse {
+                    url: response.url(),
+                    error,
+                });
+            }
+
+            Ok(response)
+        })
+    }
 }
 
 impl NavigatorBackend for WebNavigatorBackend {
@@ -224,107 +358,12 @@ impl NavigatorBackend for WebNavigatorBackend {
     }
 
     fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
-        let url = match self.resolve_url(request.url()) {
-            Ok(url) => {
-                if url.scheme() == "file" {
-                    return async_return(create_specific_fetch_error(
-                        "WASM target can't fetch local URL",
-                        url.as_str(),
-                        "",
-                    ));
-                } else {
-                    url
-                }
-            }
-            Err(e) => {
-                return async_return(create_fetch_error(request.url(), e));
-            }
-        };
-
-        let credentials = if let Some(host) = url.host_str() {
-            if self
-                .credential_allow_list
-                .iter()
-                .any(|allowed| allowed == &format!("{}://{}", url.scheme(), host))
-            {
-                RequestCredentials::Include
-            } else {
-                RequestCredentials::SameOrigin
-            }
-        } else {
-            RequestCredentials::SameOrigin
-        };
-
+        let response = self.send_request(request);
         Box::pin(async move {
-            let mut init = RequestInit::new();
-
-            init.method(&request.method().to_string());
-            init.credentials(credentials);
-
-            if let Some((data, mime)) = request.body() {
-                let blob = Blob::new_with_buffer_source_sequence_and_options(
-                    &Array::from_iter([Uint8Array::from(data.as_slice()).buffer()]),
-                    BlobPropertyBag::new().type_(mime),
-                )
-                .map_err(|_| ErrorResponse {
-                    url: url.to_string(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                })?
-                .dyn_into()
-                .map_err(|_| ErrorResponse {
-                    url: url.to_string(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                })?;
-
-                init.body(Some(&blob));
-            }
-
-            let web_request = match WebRequest::new_with_str_and_init(url.as_str(), &init) {
-                Ok(web_request) => web_request,
-                Err(_) => {
-                    return create_specific_fetch_error(
-                        "Unable to create request for",
-                        url.as_str(),
-                        "",
-                    )
-                }
-            };
-
-            let headers = web_request.headers();
-
-            for (header_name, header_val) in request.headers() {
-                headers
-                    .set(header_name, header_val)
-                    .map_err(|_| ErrorResponse {
-                        url: url.to_string(),
-                        error: Error::FetchError("Got JS error".to_string()),
-                    })?;
-            }
-
-            let window = web_sys::window().expect("window()");
-            let fetchval = JsFuture::from(window.fetch_with_request(&web_request))
-                .await
-                .map_err(|_| ErrorResponse {
-                    url: url.to_string(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                })?;
-
-            let response: WebResponse = fetchval.dyn_into().map_err(|_| ErrorResponse {
-                url: url.to_string(),
-                error: Error::FetchError("Fetch result wasn't a WebResponse".to_string()),
-            })?;
+            let response = response.await?;
             let url = response.url();
             let status = response.status();
             let redirected = response.redirected();
-            if !response.ok() {
-                let error = Error::HttpNotOk(
-                    format!("HTTP status is not ok, got {}", response.status_text()),
-                    status,
-                    redirected,
-                    0,
-                );
-                return Err(ErrorResponse { url, error });
-            }
 
             let body: ArrayBuffer = JsFuture::from(response.array_buffer().map_err(|_| {
                 ErrorResponse {
@@ -355,6 +394,42 @@ impl NavigatorBackend for WebNavigatorBackend {
         })
     }
 
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, ErrorResponse> {
+        let response = self.send_request(request);
+        Box::pin(async move {
+            let response = response.await?;
+            let url = response.url();
+            let status = response.status();
+            let redirected = response.redirected();
+
+            // With a compressed transfer, this is the compressed length, which is
+            // still close enough to report the progress.
+            let expected_length = response
+                .headers()
+                .get("Content-Length")
+                .ok()
+                .flatten()
+                .and_then(|length| length.parse().ok());
+
+            let Some(body) = response.body() else {
+                return Ok(StreamingResponse::from_complete(SuccessResponse {
+                    url,
+                    body: vec![],
+                    status,
+                    redirected,
+                }));
+            };
+
+            Ok(StreamingResponse {
+                url,
+                status,
+                redirected,
+                expected_length,
+                body: Box::new(WebResponseBody(body.get_reader().unchecked_into())),
+            })
+        })
+    }
+
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
         if let Some(base_url) = &self.base_url {
             match base_url.join(url) {
@@ -386,6 +461,13 @@ impl NavigatorBackend for WebNavigatorBackend {
         url
     }
 
+    fn is_socket_approved(&self, host: &str, port: u16) -> bool {
+        // Whoever configured a proxy for the socket allowed the movie to use it.
+        self.socket_proxies
+            .iter()
+            .any(|proxy| proxy.host == host && proxy.port == port)
+    }
+
     fn connect_socket(
         &mut self,
         host: String,
@@ -33,4 +33,12 @@ impl StorageBackend for LocalStorageBackend {
     fn remove_key(&mut self, name: &str) {
         let _ = self.storage.delete(name);
     }
+
+    fn keys(&self, prefix: &str) -> Vec<String> {
+        let length = self.storage.length().unwrap_or_default();
+        (0..length)
+            .filter_map(|i| self.storage.key(i).ok().flatten())
+            .filter(|name| name.starts_with(prefix) && !name.starts_with('#'))
+            .collect()
+    }
 }
@@ -4,12 +4,14 @@ use ruffle_core::backend::ui::{
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
 };
 use ruffle_core::backend::ui::{
-    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, StorageQuotaFuture,
+    UiBackend, US_ENGLISH,
 };
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
 use url::Url;
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
 use web_sys::{HtmlCanvasElement, HtmlDocument, HtmlTextAreaElement};
 
 use chrono::{DateTime, Utc};
@@ -336,4 +338,24 @@ impl UiBackend for WebUiBackend {
             result
         }))
     }
+
+    fn display_storage_quota_prompt(
+        &mut self,
+        domain: &str,
+        requested: usize,
+    ) -> Option<StorageQuotaFuture> {
+        // Unlike `window.confirm`, this doesn't block the player while the user answers.
+        let prompt = self
+            .js_player
+            .display_storage_quota_prompt(domain, requested.div_ceil(1024));
+        Some(Box::pin(async move {
+            let granted = JsFuture::from(prompt).await.ok().and_then(|v| v.as_bool());
+            Ok(granted.unwrap_or_default())
+        }))
+    }
+
+    fn display_storage_settings(&mut self, domain: &str) {
+        // TODO: Add a settings panel to the web player.
+        tracing::warn!("Local storage settings of {domain} can't be displayed on web");
+    }
 }